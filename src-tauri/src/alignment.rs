@@ -0,0 +1,398 @@
+use serde::Deserialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+use crate::error::TranscriptionError;
+use crate::jobs::{tracked_output, JobKind};
+use crate::meeting::{get_audio_path, lock_meeting, update_meeting_metadata, write_atomic};
+use crate::model_cache::cache_env;
+use crate::transcript::Transcript;
+use crate::whisperx::get_python_executable_path;
+use crate::whisperx_stderr::StderrLog;
+use crate::AppState;
+
+/// Aligns every segment on its own, so the output has exactly one entry per
+/// input segment even though `whisperx.align` splits segments into sentences.
+/// Segments that can't be aligned come back with `null` timings.
+const ALIGN_SCRIPT: &str = r#"
+import json, sys, whisperx
+segments_path, audio_path, output_path, language = sys.argv[1:5]
+with open(segments_path, encoding="utf-8") as f:
+    segments = json.load(f)["segments"]
+audio = whisperx.load_audio(audio_path)
+model, metadata = whisperx.load_align_model(language_code=language, device="cpu")
+aligned = []
+for segment in segments:
+    result = whisperx.align([segment], model, metadata, audio, "cpu", return_char_alignments=False)
+    parts = [part for part in result["segments"] if "start" in part and "end" in part]
+    aligned.append({
+        "start": parts[0]["start"] if parts else None,
+        "end": parts[-1]["end"] if parts else None,
+        "words": [word for part in parts for word in part.get("words", [])],
+    })
+with open(output_path, "w", encoding="utf-8") as f:
+    json.dump({"segments": aligned}, f)
+"#;
+
+/// Language of the alignment model when the transcript doesn't say
+const DEFAULT_ALIGN_LANGUAGE: &str = "de";
+const ALIGN_INPUT_FILE: &str = "align-input.json";
+const ALIGN_OUTPUT_FILE: &str = "align-output.json";
+
+/// New timings for one segment of the transcript
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct AlignedSegment {
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    #[serde(default)]
+    pub words: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlignOutput {
+    segments: Vec<AlignedSegment>,
+}
+
+pub fn align_command(
+    python_exe: &str,
+    python_dir: &Path,
+    segments_path: &Path,
+    audio_path: &Path,
+    output_path: &Path,
+    language: &str,
+) -> Command {
+    let lib_path = python_dir
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages");
+
+    let mut command = Command::new(python_exe);
+    command
+        .env("PYTHONPATH", lib_path)
+        .arg("-c")
+        .arg(ALIGN_SCRIPT)
+        .arg(segments_path)
+        .arg(audio_path)
+        .arg(output_path)
+        .arg(language);
+    command
+}
+
+/// Replace segment timings with the aligned ones. Text and speakers are kept,
+/// segments without an alignment keep their old timings.
+/// Returns the number of segments that got new timings.
+pub fn merge_timings(
+    transcript: &mut Transcript,
+    aligned: Vec<AlignedSegment>,
+) -> Result<usize, String> {
+    if aligned.len() != transcript.segments.len() {
+        return Err(format!(
+            "Alignment returned {} segments for {} transcript segments",
+            aligned.len(),
+            transcript.segments.len()
+        ));
+    }
+
+    let mut updated = 0;
+    for (segment, aligned) in transcript.segments.iter_mut().zip(aligned) {
+        let (Some(start), Some(end)) = (aligned.start, aligned.end) else {
+            continue;
+        };
+
+        segment.start = start;
+        segment.end = end;
+        let words: Vec<serde_json::Value> = aligned
+            .words
+            .into_iter()
+            .map(|mut word| {
+                // Words inherit the (possibly renamed) speaker of their segment
+                if let (Some(speaker), Some(word)) = (&segment.speaker, word.as_object_mut()) {
+                    word.insert("speaker".to_string(), speaker.clone().into());
+                }
+                word
+            })
+            .collect();
+        segment
+            .extra
+            .insert("words".to_string(), serde_json::Value::Array(words));
+        updated += 1;
+    }
+
+    Ok(updated)
+}
+
+/// whisperx exports next to the transcript that still have the old timings
+async fn stale_exports(meeting_dir: &Path, meeting_id: &str) -> Vec<String> {
+    let mut stale = Vec::new();
+    for extension in ["srt", "vtt", "tsv"] {
+        let file_name = format!("{}.{}", meeting_id, extension);
+        if tokio::fs::try_exists(meeting_dir.join(&file_name))
+            .await
+            .unwrap_or(false)
+        {
+            stale.push(file_name);
+        }
+    }
+    stale
+}
+
+async fn run_alignment(
+    app: &AppHandle,
+    meeting_dir: &Path,
+    meeting_id: &str,
+) -> Result<usize, String> {
+    let audio_path = get_audio_path(meeting_dir, meeting_id).await?;
+    let transcript_path = meeting_dir.join(format!("{}.json", meeting_id));
+    let content = tokio::fs::read_to_string(&transcript_path)
+        .await
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let transcript: Transcript =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse transcript: {}", e))?;
+    let language = transcript
+        .extra
+        .get("language")
+        .and_then(|language| language.as_str())
+        .unwrap_or(DEFAULT_ALIGN_LANGUAGE)
+        .to_string();
+
+    let input_path = meeting_dir.join(ALIGN_INPUT_FILE);
+    let output_path = meeting_dir.join(ALIGN_OUTPUT_FILE);
+    tokio::fs::write(&input_path, &content)
+        .await
+        .map_err(|e| format!("Failed to write alignment input: {}", e))?;
+
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let python_dir = app_dir.join("python");
+    let mut command = align_command(
+        &get_python_executable_path(&python_dir),
+        &python_dir,
+        &input_path,
+        &audio_path,
+        &output_path,
+        &language,
+    );
+    command.envs(cache_env(app).await);
+
+    println!("Realigning transcript of meeting {}", meeting_id);
+    let output = tracked_output(app, &mut command).await;
+    let _ = tokio::fs::remove_file(&input_path).await;
+    let output = output.map_err(|e| format!("Failed to run whisperx alignment: {}", e))?;
+    if !output.status.success() {
+        let error: TranscriptionError =
+            StderrLog::from_output(&output.stderr).into_error(output.status);
+        return Err(error.into());
+    }
+
+    let aligned = tokio::fs::read_to_string(&output_path).await;
+    let _ = tokio::fs::remove_file(&output_path).await;
+    let aligned: AlignOutput =
+        serde_json::from_str(&aligned.map_err(|e| format!("Failed to read alignment: {}", e))?)
+            .map_err(|e| format!("Failed to parse alignment: {}", e))?;
+
+    // Re-read under the lock, the transcript may have been edited meanwhile
+    let updated = {
+        let _guard = lock_meeting(meeting_dir).await;
+        let content = tokio::fs::read_to_string(&transcript_path)
+            .await
+            .map_err(|e| format!("Failed to read transcript: {}", e))?;
+        let mut transcript: Transcript = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse transcript: {}", e))?;
+        let updated = merge_timings(&mut transcript, aligned.segments)?;
+
+        let json = serde_json::to_string_pretty(&transcript).map_err(|e| e.to_string())?;
+        write_atomic(&transcript_path, json)
+            .await
+            .map_err(|e| format!("Failed to write transcript: {}", e))?;
+        updated
+    };
+
+    let stale = stale_exports(meeting_dir, meeting_id).await;
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.stale_exports = stale;
+    })
+    .await?;
+
+    println!("Realigned {} segments of meeting {}", updated, meeting_id);
+    Ok(updated)
+}
+
+/// Improve the timestamps of an existing transcript without transcribing it
+/// again. Returns the number of segments with new timings.
+#[tauri::command]
+pub async fn realign_transcript(
+    app: AppHandle,
+    meeting_id: &str,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<usize, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+    // Refuse early, before claiming the transcription slot
+    get_audio_path(&meeting_dir, meeting_id).await?;
+
+    let mut state_lock = state.lock().await;
+    state_lock.claim_transcription(meeting_id)?;
+    let job_id = state_lock.jobs.start_job(JobKind::Alignment, meeting_id);
+    drop(state_lock);
+
+    let result = run_alignment(&app, &meeting_dir, meeting_id).await;
+
+    let mut state_lock = state.lock().await;
+    state_lock.release_transcription();
+    state_lock.jobs.finish_job(&job_id);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meeting::read_meeting_metadata;
+    use serde_json::json;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    fn fixture_transcript() -> Transcript {
+        serde_json::from_value(json!({
+            "segments": [
+                {
+                    "start": 0.0,
+                    "end": 4.0,
+                    "text": " Guten Morgen, wir fangen an.",
+                    "speaker": "Anna",
+                    "words": [{"word": "Guten", "start": 0.1, "end": 0.4, "speaker": "SPEAKER_00"}]
+                },
+                {
+                    "start": 4.0,
+                    "end": 9.0,
+                    "text": " Das Budget ist freigegeben (korrigiert).",
+                    "speaker": "Jürgen"
+                },
+                {"start": 9.0, "end": 12.0, "text": " [unverständlich]"}
+            ],
+            "language": "de"
+        }))
+        .unwrap()
+    }
+
+    fn fixture_alignment() -> Vec<AlignedSegment> {
+        let output: AlignOutput = serde_json::from_value(json!({
+            "segments": [
+                {
+                    "start": 0.35,
+                    "end": 3.2,
+                    "words": [
+                        {"word": "Guten", "start": 0.35, "end": 0.6, "score": 0.92},
+                        {"word": "Morgen,", "start": 0.6, "end": 1.1, "score": 0.88}
+                    ]
+                },
+                {"start": 4.4, "end": 8.1, "words": [{"word": "Das", "start": 4.4, "end": 4.6}]},
+                {"start": null, "end": null, "words": []}
+            ]
+        }))
+        .unwrap();
+        output.segments
+    }
+
+    #[test]
+    fn test_merge_keeps_text_and_speakers() {
+        let mut transcript = fixture_transcript();
+
+        let updated = merge_timings(&mut transcript, fixture_alignment()).unwrap();
+
+        assert_eq!(updated, 2);
+        let first = &transcript.segments[0];
+        assert_eq!((first.start, first.end), (0.35, 3.2));
+        assert_eq!(first.text, " Guten Morgen, wir fangen an.");
+        assert_eq!(first.speaker.as_deref(), Some("Anna"));
+        assert_eq!(first.extra["words"][1]["word"], "Morgen,");
+        assert_eq!(first.extra["words"][0]["speaker"], "Anna");
+
+        let second = &transcript.segments[1];
+        assert_eq!((second.start, second.end), (4.4, 8.1));
+        assert_eq!(second.text, " Das Budget ist freigegeben (korrigiert).");
+
+        // Not alignable, keeps the old timing
+        let third = &transcript.segments[2];
+        assert_eq!((third.start, third.end), (9.0, 12.0));
+        assert!(!third.extra.contains_key("words"));
+        assert_eq!(transcript.extra["language"], "de");
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_segments() {
+        let mut transcript = fixture_transcript();
+        let mut aligned = fixture_alignment();
+        aligned.pop();
+
+        assert!(merge_timings(&mut transcript, aligned).is_err());
+        assert_eq!(transcript, fixture_transcript());
+    }
+
+    #[test]
+    fn test_align_command() {
+        let command = align_command(
+            "/app/python/bin/python3",
+            Path::new("/app/python"),
+            Path::new("/meeting/align-input.json"),
+            Path::new("/meeting/recording.ogg"),
+            Path::new("/meeting/align-output.json"),
+            "de",
+        );
+        let command = command.as_std();
+
+        assert_eq!(command.get_program(), "/app/python/bin/python3");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args.len(), 6);
+        assert_eq!(args[0], "-c");
+        assert_eq!(args[1], ALIGN_SCRIPT);
+        assert_eq!(
+            &args[2..],
+            [
+                "/meeting/align-input.json",
+                "/meeting/recording.ogg",
+                "/meeting/align-output.json",
+                "de"
+            ]
+        );
+        let python_path = command
+            .get_envs()
+            .find(|(key, _)| *key == "PYTHONPATH")
+            .and_then(|(_, value)| value);
+        assert_eq!(
+            python_path,
+            Some(Path::new("/app/python/lib/python3.12/site-packages").as_os_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        for file_name in ["srt", "vtt", "txt"].map(|ext| format!("{}.{}", MEETING_ID, ext)) {
+            tokio::fs::write(dir.path().join(file_name), "")
+                .await
+                .unwrap();
+        }
+
+        let stale = stale_exports(dir.path(), MEETING_ID).await;
+        assert_eq!(
+            stale,
+            [format!("{}.srt", MEETING_ID), format!("{}.vtt", MEETING_ID)]
+        );
+
+        update_meeting_metadata(dir.path(), MEETING_ID, |metadata| {
+            metadata.stale_exports = stale;
+        })
+        .await
+        .unwrap();
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.stale_exports.len(), 2);
+    }
+}
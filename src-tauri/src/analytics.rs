@@ -0,0 +1,654 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::llm::file_manager::read_summary_from;
+use crate::llm::models::FinalSummaryFormat;
+use crate::llm::prompts::Language;
+use crate::llm::todo_groups::{group_todos_by_assignee, unassigned_heading};
+use crate::meeting::write_atomic;
+use crate::transcript::Transcript;
+
+/// Transcript statistics, recomputed when the transcript changes
+const CACHE_FILE_NAME: &str = "analytics.json";
+/// Words and bigrams listed per meeting
+const TOP_TERMS: usize = 20;
+
+/// Function words and filler that would top every frequency list
+const GERMAN_STOPWORDS: &[&str] = &[
+    "aber",
+    "alle",
+    "allem",
+    "allen",
+    "aller",
+    "alles",
+    "also",
+    "am",
+    "an",
+    "auch",
+    "auf",
+    "aus",
+    "bei",
+    "bin",
+    "bis",
+    "bist",
+    "da",
+    "dafür",
+    "damit",
+    "dann",
+    "das",
+    "dass",
+    "dem",
+    "den",
+    "denn",
+    "der",
+    "des",
+    "dich",
+    "die",
+    "dir",
+    "doch",
+    "dort",
+    "du",
+    "durch",
+    "eben",
+    "ein",
+    "eine",
+    "einem",
+    "einen",
+    "einer",
+    "eines",
+    "er",
+    "es",
+    "etwas",
+    "für",
+    "gibt",
+    "ganz",
+    "genau",
+    "gerade",
+    "gut",
+    "habe",
+    "haben",
+    "hat",
+    "hier",
+    "ich",
+    "ihr",
+    "ihm",
+    "ihn",
+    "im",
+    "in",
+    "ist",
+    "ja",
+    "jetzt",
+    "kann",
+    "kein",
+    "keine",
+    "können",
+    "man",
+    "mal",
+    "mehr",
+    "mich",
+    "mir",
+    "mit",
+    "muss",
+    "nach",
+    "nicht",
+    "noch",
+    "nur",
+    "ob",
+    "oder",
+    "ok",
+    "okay",
+    "schon",
+    "sehr",
+    "sein",
+    "sich",
+    "sie",
+    "sind",
+    "so",
+    "soll",
+    "um",
+    "und",
+    "uns",
+    "unser",
+    "vom",
+    "von",
+    "vor",
+    "war",
+    "was",
+    "weil",
+    "wenn",
+    "wer",
+    "wie",
+    "wir",
+    "wird",
+    "wo",
+    "würde",
+    "zu",
+    "zum",
+    "zur",
+    "äh",
+    "ähm",
+    "hm",
+    "halt",
+    "einfach",
+    "eigentlich",
+    "irgendwie",
+    "quasi",
+];
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "about", "all", "also", "am", "an", "and", "are", "as", "at", "be", "because", "been",
+    "but", "by", "can", "could", "did", "do", "does", "don't", "for", "from", "get", "go", "going",
+    "got", "had", "has", "have", "he", "her", "here", "him", "his", "how", "i", "i'm", "if", "in",
+    "is", "it", "it's", "just", "know", "like", "me", "more", "my", "no", "not", "now", "of", "ok",
+    "okay", "on", "one", "or", "our", "out", "really", "right", "she", "so", "some", "that",
+    "that's", "the", "their", "them", "then", "there", "they", "think", "this", "to", "up", "us",
+    "very", "was", "we", "well", "were", "what", "when", "which", "who", "will", "with", "would",
+    "yeah", "yes", "you", "your", "uh", "um", "mhm",
+];
+
+fn stopwords(language: &Language) -> &'static [&'static str] {
+    match language {
+        Language::English => ENGLISH_STOPWORDS,
+        Language::German => GERMAN_STOPWORDS,
+    }
+}
+
+/// Lowercase words of `text`. Apostrophes and hyphens inside a word are kept,
+/// numbers and single letters are dropped.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’' || c == '-'))
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .replace('’', "'")
+                .to_lowercase()
+        })
+        .filter(|word| word.chars().count() > 1 && !word.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+/// The language whisperx detected, or the one whose stopwords are more common
+fn transcript_language(transcript: &Transcript) -> Language {
+    let detected = transcript
+        .extra
+        .get("language")
+        .and_then(|language| language.as_str())
+        .and_then(|language| Language::from_str(language).ok());
+    if let Some(language) = detected {
+        return language;
+    }
+
+    let hits = |language: &Language| {
+        let stopwords = stopwords(language);
+        transcript
+            .segments
+            .iter()
+            .flat_map(|segment| tokenize(&segment.text))
+            .filter(|word| stopwords.contains(&word.as_str()))
+            .count()
+    };
+    if hits(&Language::English) > hits(&Language::German) {
+        Language::English
+    } else {
+        Language::German
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermCount {
+    pub term: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerStats {
+    /// `None` for segments nobody was assigned to
+    pub speaker: Option<String>,
+    pub words: usize,
+    /// Segments ending in `?`
+    pub questions: usize,
+}
+
+/// Consecutive segments of one speaker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Monologue {
+    pub speaker: Option<String>,
+    pub start: f64,
+    pub end: f64,
+    pub words: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptAnalytics {
+    /// Language of the stopword list used
+    pub language: Language,
+    pub top_words: Vec<TermCount>,
+    pub top_bigrams: Vec<TermCount>,
+    /// Most words first
+    pub speakers: Vec<SpeakerStats>,
+    pub longest_monologue: Option<Monologue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AssigneeTodos {
+    pub assignee: String,
+    pub todos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryAnalytics {
+    /// Top-level topics
+    pub topics: usize,
+    pub todos: usize,
+    /// Shared to-dos count for each of their assignees
+    pub todos_per_assignee: Vec<AssigneeTodos>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeetingAnalytics {
+    #[serde(flatten)]
+    pub transcript: TranscriptAnalytics,
+    /// `None` until the meeting is summarized
+    pub summary: Option<SummaryAnalytics>,
+}
+
+/// The `limit` most frequent terms, ties in alphabetical order
+fn top_terms(counts: HashMap<String, usize>, limit: usize) -> Vec<TermCount> {
+    let mut terms: Vec<TermCount> = counts
+        .into_iter()
+        .map(|(term, count)| TermCount { term, count })
+        .collect();
+    terms.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+    terms.truncate(limit);
+    terms
+}
+
+pub fn analyze_transcript(transcript: &Transcript) -> TranscriptAnalytics {
+    let language = transcript_language(transcript);
+    let stopwords = stopwords(&language);
+
+    let mut words: HashMap<String, usize> = HashMap::new();
+    let mut bigrams: HashMap<String, usize> = HashMap::new();
+    let mut speakers: Vec<SpeakerStats> = Vec::new();
+    let mut monologues: Vec<Monologue> = Vec::new();
+
+    for segment in &transcript.segments {
+        let tokens = tokenize(&segment.text);
+        for token in tokens.iter().filter(|t| !stopwords.contains(&t.as_str())) {
+            *words.entry(token.clone()).or_default() += 1;
+        }
+        // Bigrams don't span a stopword, "Budget für Technik" has none
+        for pair in tokens.windows(2) {
+            if pair.iter().all(|t| !stopwords.contains(&t.as_str())) {
+                *bigrams.entry(pair.join(" ")).or_default() += 1;
+            }
+        }
+
+        let index = match speakers.iter().position(|s| s.speaker == segment.speaker) {
+            Some(index) => index,
+            None => {
+                speakers.push(SpeakerStats {
+                    speaker: segment.speaker.clone(),
+                    words: 0,
+                    questions: 0,
+                });
+                speakers.len() - 1
+            }
+        };
+        speakers[index].words += tokens.len();
+        if segment.text.trim_end().ends_with('?') {
+            speakers[index].questions += 1;
+        }
+
+        match monologues.last_mut() {
+            Some(monologue) if monologue.speaker == segment.speaker => {
+                monologue.end = segment.end;
+                monologue.words += tokens.len();
+            }
+            _ => monologues.push(Monologue {
+                speaker: segment.speaker.clone(),
+                start: segment.start,
+                end: segment.end,
+                words: tokens.len(),
+            }),
+        }
+    }
+
+    speakers.sort_by_key(|s| std::cmp::Reverse(s.words));
+    // The first of equally long monologues
+    let longest_monologue = monologues
+        .into_iter()
+        .fold(None, |longest: Option<Monologue>, m| match longest {
+            Some(longest) if longest.end - longest.start >= m.end - m.start => Some(longest),
+            _ => Some(m),
+        });
+
+    TranscriptAnalytics {
+        language,
+        top_words: top_terms(words, TOP_TERMS),
+        top_bigrams: top_terms(bigrams, TOP_TERMS),
+        speakers,
+        longest_monologue,
+    }
+}
+
+pub fn analyze_summary(summary: &FinalSummaryFormat, language: &Language) -> SummaryAnalytics {
+    SummaryAnalytics {
+        topics: summary.topics.len(),
+        todos: summary.todos.len(),
+        todos_per_assignee: group_todos_by_assignee(&summary.todos)
+            .into_iter()
+            .map(|group| AssigneeTodos {
+                assignee: group
+                    .assignee
+                    .unwrap_or_else(|| unassigned_heading(language))
+                    .to_string(),
+                todos: group.todos.len(),
+            })
+            .collect(),
+    }
+}
+
+/// `analytics.json`, valid while the transcript has the modification time
+/// it was computed for
+#[derive(Serialize, Deserialize)]
+struct CachedAnalytics {
+    transcript_modified: String,
+    analytics: TranscriptAnalytics,
+}
+
+/// Analytics of the meeting's transcript and summary. The transcript part is
+/// cached in the meeting folder until the transcript file changes.
+pub async fn meeting_analytics_in(
+    meeting_dir: &Path,
+    meeting_id: &str,
+) -> Result<MeetingAnalytics, String> {
+    let transcript_path = meeting_dir.join(format!("{}.json", meeting_id));
+    let modified = fs::metadata(&transcript_path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .map_err(|_| "The meeting has no transcript to analyze".to_string())?;
+    let transcript_modified =
+        DateTime::<Utc>::from(modified).to_rfc3339_opts(SecondsFormat::Nanos, true);
+
+    let cache_path = meeting_dir.join(CACHE_FILE_NAME);
+    let cached = fs::read_to_string(&cache_path)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_str::<CachedAnalytics>(&content).ok())
+        .filter(|cached| cached.transcript_modified == transcript_modified);
+
+    let transcript = match cached {
+        Some(cached) => cached.analytics,
+        None => {
+            let json = fs::read_to_string(&transcript_path)
+                .await
+                .map_err(|e| format!("Failed to read transcript: {}", e))?;
+            let transcript: Transcript = serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to parse transcript: {}", e))?;
+            let analytics = analyze_transcript(&transcript);
+
+            let cached = CachedAnalytics {
+                transcript_modified,
+                analytics,
+            };
+            // Only a cache, the analytics are still returned if it can't be written
+            if let Err(e) = write_atomic(
+                &cache_path,
+                serde_json::to_string_pretty(&cached).map_err(|e| e.to_string())?,
+            )
+            .await
+            {
+                println!("⚠️  Failed to cache analytics of {}: {}", meeting_id, e);
+            }
+            cached.analytics
+        }
+    };
+
+    let summary = read_summary_from(meeting_dir)
+        .await
+        .ok()
+        .map(|summary| analyze_summary(&summary, &transcript.language));
+
+    Ok(MeetingAnalytics {
+        transcript,
+        summary,
+    })
+}
+
+#[tauri::command]
+pub async fn get_meeting_analytics(
+    app: AppHandle,
+    meeting_id: &str,
+) -> Result<MeetingAnalytics, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+
+    meeting_analytics_in(&app_dir.join("uploads").join(meeting_id), meeting_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::TranscriptSegment;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    fn transcript(segments: &[(f64, f64, Option<&str>, &str)]) -> Transcript {
+        Transcript {
+            segments: segments
+                .iter()
+                .map(|(start, end, speaker, text)| TranscriptSegment {
+                    start: *start,
+                    end: *end,
+                    text: text.to_string(),
+                    speaker: speaker.map(str::to_string),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn terms(counts: &[TermCount]) -> Vec<(&str, usize)> {
+        counts.iter().map(|t| (t.term.as_str(), t.count)).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Das Budget für's Sommerfest: 2025 – äh, 500 € für E-Mails? It’s fine."),
+            vec![
+                "das",
+                "budget",
+                "für's",
+                "sommerfest",
+                "äh",
+                "für",
+                "e-mails",
+                "it's",
+                "fine"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_german_meeting_with_english_terms() {
+        let transcript = transcript(&[
+            (
+                0.0,
+                4.0,
+                Some("Anna"),
+                "Das Budget für das Sommerfest ist knapp.",
+            ),
+            (
+                4.0,
+                9.0,
+                Some("Anna"),
+                "Wir brauchen ein neues Budget. Das Sommerfest Budget reicht nicht.",
+            ),
+            (
+                9.0,
+                10.0,
+                Some("Ben"),
+                "Ist das Deployment Window schon fix?",
+            ),
+            (
+                10.0,
+                11.0,
+                None,
+                "Okay, das Deployment Window ist am Freitag.",
+            ),
+            (11.0, 12.0, Some("Ben"), "Und das Sommerfest Budget?"),
+        ]);
+        let analytics = analyze_transcript(&transcript);
+
+        assert_eq!(analytics.language, Language::German);
+        assert_eq!(
+            terms(&analytics.top_words[..4]),
+            vec![
+                ("budget", 4),
+                ("sommerfest", 3),
+                ("deployment", 2),
+                ("window", 2)
+            ]
+        );
+        assert_eq!(
+            terms(&analytics.top_bigrams[..3]),
+            vec![
+                ("deployment window", 2),
+                ("sommerfest budget", 2),
+                ("budget reicht", 1)
+            ]
+        );
+
+        let speakers: Vec<(Option<&str>, usize, usize)> = analytics
+            .speakers
+            .iter()
+            .map(|s| (s.speaker.as_deref(), s.words, s.questions))
+            .collect();
+        assert_eq!(
+            speakers,
+            vec![(Some("Anna"), 17, 0), (Some("Ben"), 10, 2), (None, 7, 0)]
+        );
+        let monologue = analytics.longest_monologue.unwrap();
+        assert_eq!(monologue.speaker.as_deref(), Some("Anna"));
+        assert_eq!(
+            (monologue.start, monologue.end, monologue.words),
+            (0.0, 9.0, 17)
+        );
+    }
+
+    #[test]
+    fn test_language_from_whisperx_wins() {
+        let mut english = transcript(&[(
+            0.0,
+            1.0,
+            None,
+            "So we think the roadmap is fine and the roadmap is done.",
+        )]);
+        assert_eq!(analyze_transcript(&english).language, Language::English);
+        assert_eq!(
+            terms(&analyze_transcript(&english).top_words),
+            vec![("roadmap", 2), ("done", 1), ("fine", 1)]
+        );
+
+        english
+            .extra
+            .insert("language".to_string(), serde_json::json!("de"));
+        let analytics = analyze_transcript(&english);
+        assert_eq!(analytics.language, Language::German);
+        assert!(analytics.top_words.iter().any(|t| t.term == "the"));
+    }
+
+    #[tokio::test]
+    async fn test_analytics_are_cached_until_the_transcript_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(meeting_analytics_in(dir.path(), MEETING_ID).await.is_err());
+
+        let transcript_path = dir.path().join(format!("{}.json", MEETING_ID));
+        let write_transcript = |text: &str| {
+            std::fs::write(
+                &transcript_path,
+                serde_json::to_string(&transcript(&[(0.0, 1.0, Some("Anna"), text)])).unwrap(),
+            )
+            .unwrap();
+        };
+        write_transcript("Haushalt Haushalt Protokoll");
+        let first = meeting_analytics_in(dir.path(), MEETING_ID).await.unwrap();
+        assert_eq!(first.transcript.top_words[0].term, "haushalt");
+        assert_eq!(first.summary, None);
+        assert!(dir.path().join(CACHE_FILE_NAME).exists());
+
+        // A cached result is served as long as the transcript is unchanged
+        let cache = std::fs::read_to_string(dir.path().join(CACHE_FILE_NAME)).unwrap();
+        std::fs::write(
+            dir.path().join(CACHE_FILE_NAME),
+            cache.replace("\"haushalt\"", "\"aus dem cache\""),
+        )
+        .unwrap();
+        let cached = meeting_analytics_in(dir.path(), MEETING_ID).await.unwrap();
+        assert_eq!(cached.transcript.top_words[0].term, "aus dem cache");
+
+        // Editing the transcript changes its modification time
+        write_transcript("Satzung Satzung Satzung");
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&transcript_path)
+            .unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        let fresh = meeting_analytics_in(dir.path(), MEETING_ID).await.unwrap();
+        assert_eq!(terms(&fresh.transcript.top_words), vec![("satzung", 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_summary_counts_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(format!("{}.json", MEETING_ID)),
+            serde_json::to_string(&transcript(&[(0.0, 1.0, None, "Das Budget ist da.")])).unwrap(),
+        )
+        .unwrap();
+        meeting_analytics_in(dir.path(), MEETING_ID).await.unwrap();
+
+        std::fs::write(
+            dir.path().join("summary.json"),
+            r#"{
+                "title": {"emoji": "📅", "text": "Plenum"},
+                "key_facts": {
+                    "responisible_for_moderation": null,
+                    "responisible_for_protocol": null,
+                    "responisible_for_timekeeping": null,
+                    "attendees": null
+                },
+                "summary": "",
+                "topics": [{"title": "Budget", "bullet_points": [], "sub_topics": null}],
+                "todos": [
+                    {"assignees": ["Anna", "Ben"], "task": "Angebote einholen"},
+                    {"assignees": ["Anna"], "task": "Protokoll schreiben"},
+                    {"assignees": null, "task": "Raum buchen"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let summary = meeting_analytics_in(dir.path(), MEETING_ID)
+            .await
+            .unwrap()
+            .summary
+            .unwrap();
+
+        assert_eq!((summary.topics, summary.todos), (1, 3));
+        let per_assignee: Vec<(&str, usize)> = summary
+            .todos_per_assignee
+            .iter()
+            .map(|a| (a.assignee.as_str(), a.todos))
+            .collect();
+        assert_eq!(per_assignee, vec![("Anna", 2), ("Ben", 1), ("Offen", 1)]);
+    }
+}
@@ -0,0 +1,442 @@
+use axum::{
+    extract::{Path as UrlPath, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use rand::{distr::Alphanumeric, Rng};
+use serde::Deserialize;
+use serde_json::json;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::llm::{
+    file_manager::read_summary_from,
+    models::{FinalSummaryFormat, MeetingToMarkdown},
+};
+use crate::meeting::is_valid_meeting_id;
+use crate::search::{search_meetings_in, SearchResult};
+use crate::settings::{load_settings, save_settings, ApiServerSettings};
+use crate::{list_meetings, read_transcript, AppState, MeetingOverview, MeetingSort};
+
+const TOKEN_LENGTH: usize = 32;
+/// How long running requests may take to finish when the server stops
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn generate_token() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+#[derive(Clone)]
+struct ApiState {
+    uploads: Arc<PathBuf>,
+    token: Arc<str>,
+}
+
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// Compare without returning at the first differing byte
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn require_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| tokens_match(token, &state.token));
+
+    if !authorized {
+        return ApiError(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid bearer token".to_string(),
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
+
+fn meeting_dir(state: &ApiState, meeting_id: &str) -> Result<PathBuf, ApiError> {
+    if !is_valid_meeting_id(meeting_id) {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid meeting id: {}", meeting_id),
+        ));
+    }
+
+    let meeting_dir = state.uploads.join(meeting_id);
+    if !meeting_dir.is_dir() {
+        return Err(ApiError(
+            StatusCode::NOT_FOUND,
+            format!("Meeting not found: {}", meeting_id),
+        ));
+    }
+    Ok(meeting_dir)
+}
+
+async fn read_summary(state: &ApiState, meeting_id: &str) -> Result<FinalSummaryFormat, ApiError> {
+    let meeting_dir = meeting_dir(state, meeting_id)?;
+    if !meeting_dir.join("summary.json").exists() {
+        return Err(ApiError(
+            StatusCode::NOT_FOUND,
+            format!("Meeting {} has no summary", meeting_id),
+        ));
+    }
+    Ok(read_summary_from(&meeting_dir).await?)
+}
+
+async fn meetings(State(state): State<ApiState>) -> Result<Json<Vec<MeetingOverview>>, ApiError> {
+    Ok(Json(
+        list_meetings(&state.uploads, MeetingSort::default()).await?,
+    ))
+}
+
+async fn summary_markdown(
+    State(state): State<ApiState>,
+    UrlPath(meeting_id): UrlPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let summary = read_summary(&state, &meeting_id).await?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        summary.to_markdown(),
+    ))
+}
+
+async fn summary_json(
+    State(state): State<ApiState>,
+    UrlPath(meeting_id): UrlPath<String>,
+) -> Result<Json<FinalSummaryFormat>, ApiError> {
+    Ok(Json(read_summary(&state, &meeting_id).await?))
+}
+
+async fn transcript(
+    State(state): State<ApiState>,
+    UrlPath(meeting_id): UrlPath<String>,
+) -> Result<String, ApiError> {
+    let meeting_dir = meeting_dir(&state, &meeting_id)?;
+    if !meeting_dir.join(format!("{}.txt", meeting_id)).exists() {
+        return Err(ApiError(
+            StatusCode::NOT_FOUND,
+            format!("Meeting {} has no transcript", meeting_id),
+        ));
+    }
+    Ok(read_transcript(&meeting_dir, &meeting_id).await?)
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+}
+
+async fn search(
+    State(state): State<ApiState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<SearchResult>>, ApiError> {
+    Ok(Json(search_meetings_in(&state.uploads, &query.q).await?))
+}
+
+fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/meetings", get(meetings))
+        .route("/meetings/{id}/summary", get(summary_markdown))
+        .route("/meetings/{id}/summary.json", get(summary_json))
+        .route("/meetings/{id}/transcript", get(transcript))
+        .route("/search", get(search))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// A running HTTP API, bound to 127.0.0.1 only
+pub struct ApiServer {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl ApiServer {
+    /// Bind and serve in the background. Port 0 picks a free port.
+    pub async fn start(uploads: PathBuf, port: u16, token: String) -> std::io::Result<Self> {
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+        let addr = listener.local_addr()?;
+        let app = router(ApiState {
+            uploads: Arc::new(uploads),
+            token: token.into(),
+        });
+
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            let result = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    signal.await.ok();
+                })
+                .await;
+            if let Err(e) = result {
+                println!("HTTP API stopped with an error: {}", e);
+            }
+        });
+
+        Ok(Self {
+            addr,
+            shutdown,
+            task,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop accepting connections and give running requests a moment to finish
+    pub async fn shutdown(self) {
+        self.shutdown.send(()).ok();
+        let mut task = self.task;
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut task)
+            .await
+            .is_err()
+        {
+            task.abort();
+        }
+        println!("HTTP API on {} stopped", self.addr);
+    }
+}
+
+/// Stop the running server, if any, and start it again when the settings enable it
+pub async fn apply_api_settings(
+    app: &AppHandle,
+    settings: &ApiServerSettings,
+) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let previous = state.lock().await.api_server.take();
+    if let Some(server) = previous {
+        server.shutdown().await;
+    }
+
+    if !settings.enabled {
+        return Ok(());
+    }
+    if settings.token.is_empty() {
+        return Err("HTTP API has no token".to_string());
+    }
+
+    let uploads = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join("uploads");
+    let server = ApiServer::start(uploads, settings.port, settings.token.clone())
+        .await
+        .map_err(|e| format!("Failed to start HTTP API on port {}: {}", settings.port, e))?;
+    println!("HTTP API listening on http://{}", server.addr());
+    state.lock().await.api_server = Some(server);
+    Ok(())
+}
+
+/// Start the server on launch when it was enabled in an earlier session
+pub async fn start_api_on_startup(app: AppHandle) -> Result<(), String> {
+    let settings = load_settings(&app).await?;
+    if !settings.api_server.enabled {
+        return Ok(());
+    }
+    apply_api_settings(&app, &settings.api_server).await
+}
+
+/// Stop the server before the app exits
+pub async fn stop_api_server(app: &AppHandle) {
+    let state = app.state::<Mutex<AppState>>();
+    let server = state.lock().await.api_server.take();
+    if let Some(server) = server {
+        server.shutdown().await;
+    }
+}
+
+/// Enable or disable the HTTP API. A token is generated the first time it is enabled.
+#[tauri::command]
+pub async fn set_api_server(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<ApiServerSettings, String> {
+    let mut settings = load_settings(&app).await?;
+    settings.api_server.enabled = enabled;
+    if let Some(port) = port {
+        settings.api_server.port = port;
+    }
+    if settings.api_server.token.is_empty() {
+        settings.api_server.token = generate_token();
+    }
+    save_settings(&app, &settings).await?;
+
+    apply_api_settings(&app, &settings.api_server).await?;
+    Ok(settings.api_server)
+}
+
+/// Replace the token, clients using the old one are rejected from now on
+#[tauri::command]
+pub async fn regenerate_api_token(app: AppHandle) -> Result<ApiServerSettings, String> {
+    let mut settings = load_settings(&app).await?;
+    settings.api_server.token = generate_token();
+    save_settings(&app, &settings).await?;
+
+    apply_api_settings(&app, &settings.api_server).await?;
+    Ok(settings.api_server)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri_plugin_http::reqwest::{Client, StatusCode as HttpStatus};
+
+    const TOKEN: &str = "test-token";
+
+    async fn fixture() -> (tempfile::TempDir, ApiServer) {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path().join("recording-1");
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            r#"{"id":"recording-1","name":"Jour fixe"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            meeting_dir.join("recording-1.txt"),
+            "[Anna]: Der Haushalt ist beschlossen.",
+        )
+        .unwrap();
+
+        let server = ApiServer::start(dir.path().to_path_buf(), 0, TOKEN.to_string())
+            .await
+            .unwrap();
+        (dir, server)
+    }
+
+    fn summary() -> FinalSummaryFormat {
+        serde_json::from_value(json!({
+            "title": {"emoji": "📋", "text": "Jour fixe"},
+            "key_facts": {},
+            "summary": "Der Haushalt wurde beschlossen.",
+            "topics": [],
+            "todos": [],
+        }))
+        .unwrap()
+    }
+
+    async fn get(server: &ApiServer, path: &str, token: Option<&str>) -> (HttpStatus, String) {
+        let mut request = Client::new().get(format!("http://{}{}", server.addr(), path));
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.unwrap();
+        (response.status(), response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_requests_need_the_token() {
+        let (_dir, server) = fixture().await;
+
+        let (status, _) = get(&server, "/meetings", None).await;
+        assert_eq!(status, HttpStatus::UNAUTHORIZED);
+        let (status, _) = get(&server, "/meetings", Some("wrong")).await;
+        assert_eq!(status, HttpStatus::UNAUTHORIZED);
+        // The token followed by more characters is refused, not just its prefix checked
+        let (status, _) = get(
+            &server,
+            "/meetings/recording-1/transcript",
+            Some("test-token-"),
+        )
+        .await;
+        assert_eq!(status, HttpStatus::UNAUTHORIZED);
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_endpoints() {
+        let (dir, server) = fixture().await;
+
+        let (status, body) = get(&server, "/meetings", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::OK);
+        let meetings: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(meetings[0]["id"], "recording-1");
+        assert_eq!(meetings[0]["name"], "Jour fixe");
+
+        let (status, body) = get(&server, "/meetings/recording-1/transcript", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::OK);
+        assert_eq!(body, "[Anna]: Der Haushalt ist beschlossen.");
+
+        let (status, body) = get(&server, "/search?q=haushalt", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::OK);
+        let results: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(results[0]["meeting_id"], "recording-1");
+        assert_eq!(results[0]["matches"][0]["field"], "transcript");
+
+        let (status, _) = get(&server, "/meetings/recording-1/summary", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::NOT_FOUND);
+        let (status, _) = get(&server, "/meetings/recording-2/transcript", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::NOT_FOUND);
+        let (status, _) = get(&server, "/meetings/..%2Fsecret/transcript", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::BAD_REQUEST);
+
+        std::fs::write(
+            dir.path().join("recording-1").join("summary.json"),
+            serde_json::to_string(&summary()).unwrap(),
+        )
+        .unwrap();
+        let (status, body) = get(&server, "/meetings/recording-1/summary.json", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::OK);
+        let json: FinalSummaryFormat = serde_json::from_str(&body).unwrap();
+        assert_eq!(json.summary, "Der Haushalt wurde beschlossen.");
+        let (status, body) = get(&server, "/meetings/recording-1/summary", Some(TOKEN)).await;
+        assert_eq!(status, HttpStatus::OK);
+        assert_eq!(body, summary().to_markdown());
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_closes_the_port() {
+        let (_dir, server) = fixture().await;
+        let addr = server.addr();
+        server.shutdown().await;
+
+        assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+    }
+
+    #[test]
+    fn test_generated_tokens() {
+        let token = generate_token();
+        assert_eq!(token.len(), TOKEN_LENGTH);
+        assert_ne!(token, generate_token());
+        assert!(tokens_match(&token, &token.clone()));
+        assert!(!tokens_match(&token, &token[1..]));
+    }
+}
@@ -1,16 +1,31 @@
 // Note: audio transcription imports are removed as they're handled by whisperx module
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::path::Path;
+use std::process::{ExitStatus, Stdio};
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use chrono::Utc;
-use crate::MeetingMetadata;
+use crate::error::AudioError;
+use crate::jobs::{tracked_output, wait_tracked};
+use crate::llm::progress::ProgressEvents;
+use crate::meeting::{create_meeting_dir, get_audio_path};
+use crate::settings::load_settings;
+use crate::watchdog::LineSource;
+use crate::{AppState, MeetingMetadata};
+
+/// Lines of ffmpeg's stderr sent along when a conversion fails
+const FFMPEG_STDERR_TAIL_LINES: usize = 20;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AudioInfo {
     pub duration_seconds: f64,
     pub needs_splitting: bool,
     pub chunk_count: usize,
+    /// Shorter than the configured minimum, transcribing it is refused
+    #[serde(default)]
+    pub too_short: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -58,47 +73,90 @@ pub async fn get_audio_duration<P: AsRef<Path>>(audio_path: P) -> Result<f64, St
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("ffprobe failed: {}", stderr.trim()));
+        return Err(AudioError::Unreadable {
+            details: format!("ffprobe failed: {}", stderr.trim()),
+        }
+        .into());
     }
 
-    let duration_str = String::from_utf8_lossy(&output.stdout);
-    let duration: f64 = duration_str
-        .trim()
-        .parse()
-        .map_err(|e| format!("Failed to parse duration '{}': {}", duration_str.trim(), e))?;
+    Ok(parse_ffprobe_duration(&String::from_utf8_lossy(&output.stdout))?)
+}
+
+/// Duration printed by ffprobe. Corrupt files report "N/A", 0 or NaN.
+fn parse_ffprobe_duration(output: &str) -> Result<f64, AudioError> {
+    let output = output.trim();
+    let unreadable = || AudioError::Unreadable {
+        details: format!("ffprobe reported a duration of '{}'", output),
+    };
 
+    let duration: f64 = output.parse().map_err(|_| unreadable())?;
+    if !duration.is_finite() || duration <= 0.0 {
+        return Err(unreadable());
+    }
     Ok(duration)
 }
 
-/// Check audio length and determine if splitting is needed
-pub async fn analyze_audio<P: AsRef<Path>>(audio_path: P) -> Result<AudioInfo, String> {
-    let duration_seconds = get_audio_duration(&audio_path).await?;
+/// Reject audio shorter than `min_seconds`
+pub fn check_min_duration(duration_seconds: f64, min_seconds: f64) -> Result<(), AudioError> {
+    if duration_seconds < min_seconds {
+        return Err(AudioError::TooShort {
+            duration_seconds,
+            min_seconds,
+        });
+    }
+    Ok(())
+}
 
-    // 30 minutes = 1800 seconds
-    const MAX_CHUNK_DURATION: f64 = 600.0;
+/// Minimum audio length from the settings
+pub async fn min_audio_duration(app: &AppHandle) -> f64 {
+    load_settings(app)
+        .await
+        .unwrap_or_default()
+        .min_audio_duration_seconds as f64
+}
+
+/// Chunk length from the transcription config
+pub async fn chunk_duration(app: &AppHandle) -> f64 {
+    let state = app.state::<tokio::sync::Mutex<AppState>>();
+    let state = state.lock().await;
+    state.transcription_config.chunk_duration_seconds as f64
+}
 
-    let needs_splitting = duration_seconds > MAX_CHUNK_DURATION;
+fn audio_info(duration_seconds: f64, min_duration_seconds: f64, chunk_duration_seconds: f64) -> AudioInfo {
+    let needs_splitting = duration_seconds > chunk_duration_seconds;
     let chunk_count = if needs_splitting {
-        (duration_seconds / MAX_CHUNK_DURATION).ceil() as usize
+        (duration_seconds / chunk_duration_seconds).ceil() as usize
     } else {
         1
     };
 
-    Ok(AudioInfo {
+    AudioInfo {
         duration_seconds,
         needs_splitting,
         chunk_count,
-    })
+        too_short: check_min_duration(duration_seconds, min_duration_seconds).is_err(),
+    }
 }
 
-/// Split audio into chunks of maximum 30 minutes each
+/// Check audio length and determine if splitting is needed
+pub async fn analyze_audio<P: AsRef<Path>>(
+    audio_path: P,
+    min_duration_seconds: f64,
+    chunk_duration_seconds: f64,
+) -> Result<AudioInfo, String> {
+    let duration_seconds = get_audio_duration(&audio_path).await?;
+    Ok(audio_info(duration_seconds, min_duration_seconds, chunk_duration_seconds))
+}
+
+/// Split audio into chunks of at most `chunk_duration_seconds` each
 pub async fn split_audio_into_chunks<P: AsRef<Path>>(
     audio_path: P,
     output_dir: P,
     meeting_id: &str,
+    chunk_duration_seconds: f64,
     app: AppHandle,
 ) -> Result<Vec<AudioChunk>, String> {
-    let audio_info = analyze_audio(&audio_path).await?;
+    let audio_info = analyze_audio(&audio_path, 0.0, chunk_duration_seconds).await?;
 
     if !audio_info.needs_splitting {
         // Return single chunk info for the original file
@@ -111,6 +169,9 @@ pub async fn split_audio_into_chunks<P: AsRef<Path>>(
     }
 
     check_ffmpeg_installation().await?;
+    tokio::fs::create_dir_all(output_dir.as_ref())
+        .await
+        .map_err(|e| format!("Failed to create chunks directory: {}", e))?;
 
     // Emit start event if app handle is provided
     println!(
@@ -120,16 +181,14 @@ pub async fn split_audio_into_chunks<P: AsRef<Path>>(
     app.emit("ffmpeg-start", audio_info.chunk_count).unwrap();
 
     let mut chunks = Vec::new();
-    // const CHUNK_DURATION: f64 = 1800.0; // 30 minutes in seconds
-    const CHUNK_DURATION: f64 = 600.0; // 10 minutes in seconds
 
     for i in 0..audio_info.chunk_count {
         // Emit progress event if app handle is provided
 
         app.emit("ffmpeg-progress", i).unwrap();
 
-        let start_time = i as f64 * CHUNK_DURATION;
-        let end_time = ((i + 1) as f64 * CHUNK_DURATION).min(audio_info.duration_seconds);
+        let start_time = i as f64 * chunk_duration_seconds;
+        let end_time = ((i + 1) as f64 * chunk_duration_seconds).min(audio_info.duration_seconds);
         let chunk_duration = end_time - start_time;
 
         let chunk_filename = format!("{}_chunk_{:02}.ogg", meeting_id, i);
@@ -140,19 +199,21 @@ pub async fn split_audio_into_chunks<P: AsRef<Path>>(
             i, start_time, end_time, chunk_duration
         );
 
-        let output = Command::new("ffmpeg")
-            .arg("-i")
-            .arg(audio_path.as_ref())
-            .arg("-ss")
-            .arg(format!("{:.2}", start_time))
-            .arg("-t")
-            .arg(format!("{:.2}", chunk_duration))
-            .arg("-c")
-            .arg("copy")
-            .arg("-y") // Overwrite output files
-            .arg(&chunk_path)
-            .output()
-            .await
+        let output = tracked_output(
+            &app,
+            Command::new("ffmpeg")
+                .arg("-i")
+                .arg(audio_path.as_ref())
+                .arg("-ss")
+                .arg(format!("{:.2}", start_time))
+                .arg("-t")
+                .arg(format!("{:.2}", chunk_duration))
+                .arg("-c")
+                .arg("copy")
+                .arg("-y") // Overwrite output files
+                .arg(&chunk_path),
+        )
+        .await
             .map_err(|e| format!("Failed to execute ffmpeg for chunk {}: {}", i, e))?;
 
         if !output.status.success() {
@@ -186,8 +247,7 @@ pub async fn get_audio_duration_command(app: AppHandle, meeting_id: &str) -> Res
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.ogg", meeting_id);
-    let audio_path = base_dir.join(file_name);
+    let audio_path = get_audio_path(&base_dir, meeting_id).await?;
 
     get_audio_duration(audio_path).await
 }
@@ -200,10 +260,14 @@ pub async fn analyze_audio_command(app: AppHandle, meeting_id: &str) -> Result<A
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.ogg", meeting_id);
-    let audio_path = base_dir.join(file_name);
-
-    analyze_audio(audio_path).await
+    let audio_path = get_audio_path(&base_dir, meeting_id).await?;
+
+    analyze_audio(
+        audio_path,
+        min_audio_duration(&app).await,
+        chunk_duration(&app).await,
+    )
+    .await
 }
 
 /// Tauri command wrapper for split_audio_into_chunks
@@ -217,13 +281,160 @@ pub async fn split_audio_into_chunks_command(
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.ogg", meeting_id);
-    let audio_path = base_dir.join(file_name);
+    let audio_path = get_audio_path(&base_dir, meeting_id).await?;
+
+    let chunk_duration_seconds = chunk_duration(&app).await;
+    split_audio_into_chunks(audio_path, base_dir, meeting_id, chunk_duration_seconds, app.clone()).await
+}
+
+#[derive(Clone, Serialize)]
+struct AudioConversionProgress<'a> {
+    meeting_id: &'a str,
+    percent: f64,
+    /// Seconds of audio converted so far
+    out_time: f64,
+}
+
+#[derive(Clone, Serialize)]
+struct AudioConversionFailed<'a> {
+    meeting_id: &'a str,
+    error: &'a str,
+    stderr_tail: Vec<String>,
+}
+
+/// Seconds converted according to a line of `ffmpeg -progress` output
+fn parse_progress_out_time(line: &str) -> Option<f64> {
+    let (key, value) = line.trim().split_once('=')?;
+    // out_time_ms is in microseconds as well, older ffmpeg only prints that one
+    if key != "out_time_us" && key != "out_time_ms" {
+        return None;
+    }
+    let microseconds: i64 = value.trim().parse().ok()?;
+    (microseconds >= 0).then(|| microseconds as f64 / 1_000_000.0)
+}
+
+/// Emit `audio-conversion-progress` for the `-progress pipe:1` output of
+/// ffmpeg, at most once per whole percent
+async fn pump_conversion_progress<S: LineSource>(
+    mut lines: S,
+    events: &ProgressEvents,
+    meeting_id: &str,
+    duration_seconds: f64,
+) -> std::io::Result<()> {
+    let mut last_percent = None;
+    while let Some(line) = lines.next_line().await? {
+        let Some(out_time) = parse_progress_out_time(&line) else {
+            continue;
+        };
+        let percent = if duration_seconds > 0.0 {
+            (out_time / duration_seconds * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        };
+        let whole_percent = percent.floor() as u32;
+        if last_percent.is_some_and(|last| whole_percent <= last) {
+            continue;
+        }
+        last_percent = Some(whole_percent);
+
+        events.emit(
+            "audio-conversion-progress",
+            AudioConversionProgress {
+                meeting_id,
+                percent: (percent * 10.0).round() / 10.0,
+                out_time,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Convert `source` to OGG Vorbis, reporting progress as it goes. Returns the
+/// exit status and the end of ffmpeg's stderr.
+async fn convert_to_ogg(
+    app: &AppHandle,
+    events: &ProgressEvents,
+    meeting_id: &str,
+    source: &Path,
+    output_path: &Path,
+    duration_seconds: f64,
+) -> std::io::Result<(ExitStatus, Vec<String>)> {
+    let mut child = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(source)
+        .arg("-c:a")
+        .arg("libvorbis") // Use OGG Vorbis codec
+        .arg("-progress")
+        .arg("pipe:1")
+        .arg("-nostats")
+        .arg("-y") // Overwrite output file if it exists
+        .arg(output_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let stdout = child.stdout.take().expect("Failed to take stdout");
+    let progress_events = events.clone();
+    let progress_meeting_id = meeting_id.to_string();
+    let stdout_task = tokio::spawn(async move {
+        pump_conversion_progress(
+            BufReader::new(stdout).lines(),
+            &progress_events,
+            &progress_meeting_id,
+            duration_seconds,
+        )
+        .await
+    });
+
+    let stderr = child.stderr.take().expect("Failed to take stderr");
+    let stderr_task = tokio::spawn(async move {
+        let mut tail = VecDeque::with_capacity(FFMPEG_STDERR_TAIL_LINES);
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tail.len() == FFMPEG_STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+        Vec::from(tail)
+    });
+
+    let status = wait_tracked(app, child).await?;
+    if let Ok(Err(e)) = stdout_task.await {
+        println!("Warning: Failed to read ffmpeg progress: {}", e);
+    }
+    let stderr_tail = stderr_task.await.unwrap_or_default();
+    Ok((status, stderr_tail))
+}
 
-    split_audio_into_chunks(audio_path, base_dir, meeting_id, app.clone()).await
+/// Remove the half created meeting and tell the frontend why, returns the error.
+/// `meeting_dir` must be one `convert_user_audio` created itself.
+async fn conversion_failed(
+    events: &ProgressEvents,
+    meeting_dir: &Path,
+    meeting_id: &str,
+    error: String,
+    stderr_tail: Vec<String>,
+) -> String {
+    if let Err(e) = tokio::fs::remove_dir_all(meeting_dir).await {
+        println!("Warning: Failed to remove meeting directory: {}", e);
+    }
+    events
+        .emit_terminal(
+            "audio-conversion-failed",
+            AudioConversionFailed {
+                meeting_id,
+                error: &error,
+                stderr_tail,
+            },
+        )
+        .await;
+    error
 }
 
-/// Tauri command to convert user audio file to OGG format
+/// Tauri command to convert user audio file to OGG format in a new meeting,
+/// named after the file. Returns the id of the meeting.
 #[tauri::command]
 pub async fn convert_user_audio(app: AppHandle, audio_path: &str) -> Result<String, String> {
     println!("Converting user audio file: {}", audio_path);
@@ -245,52 +456,167 @@ pub async fn convert_user_audio(app: AppHandle, audio_path: &str) -> Result<Stri
 
     println!("Audio file exists: {}", audio_path.to_string_lossy());
 
+    // Voice memos and broken files would only fail later in transcription
+    let duration_seconds = get_audio_duration(audio_path).await?;
+    check_min_duration(duration_seconds, min_audio_duration(&app).await)?;
+
     // Create New Meeting Directory
-    // This will be the directory where the audio file will be stored
-    //
-    // The meeting_id follows this format:
-    // const timestamp = new Date().toISOString().replace(/[:.]/g, "-");
-    // const recordingName = `recording-${timestamp}`;
-    let meeting_id = format!(
-        "recording-{}",
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| format!("Failed to get current time: {}", e))?
-            .as_secs()
-    );
-    let meeting_dir = base_dir.join(&meeting_id);
+    // This will be the directory where the audio file will be stored.
+    // It is new and ours, so a failed conversion may remove it again.
+    let (meeting_id, meeting_dir) = create_meeting_dir(&base_dir).await?;
 
     println!(
-        "Creating meeting directory: {}",
+        "Created meeting directory: {}",
         meeting_dir.to_string_lossy()
     );
 
-    std::fs::create_dir_all(&meeting_dir)
-        .map_err(|e| format!("Failed to create meeting directory: {}", e))?;
-
     // Convert the audio file to OGG format
     let output_file_name = format!("{}.ogg", meeting_id);
     let output_path = meeting_dir.join(&output_file_name);
-    let _output = Command::new("ffmpeg")
-        .arg("-i")
-        .arg(audio_path)
-        .arg("-c:a")
-        .arg("libvorbis") // Use OGG Vorbis codec
-        .arg("-y") // Overwrite output file if it exists
-        .arg(&output_path)
-        .output()
+    let events = ProgressEvents::new(app.clone());
+    let conversion = convert_to_ogg(
+        &app,
+        &events,
+        &meeting_id,
+        audio_path,
+        &output_path,
+        duration_seconds,
+    )
+    .await;
+    let (status, stderr_tail) = match conversion {
+        Ok(result) => result,
+        Err(e) => {
+            let error = format!("Failed to execute ffmpeg: {}", e);
+            return Err(conversion_failed(&events, &meeting_dir, &meeting_id, error, Vec::new()).await);
+        }
+    };
+
+    // A failed conversion must not leave a meeting without audio behind
+    let converted = tokio::fs::metadata(&output_path)
         .await
-        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        .is_ok_and(|metadata| metadata.len() > 0);
+    if !status.success() || !converted {
+        let error = format!(
+            "ffmpeg failed to convert the audio ({}): {}",
+            status,
+            stderr_tail.last().map(String::as_str).unwrap_or_default()
+        );
+        return Err(conversion_failed(&events, &meeting_dir, &meeting_id, error, stderr_tail).await);
+    }
 
     // Create meeting metadata file
     let metadata = MeetingMetadata {
         id: meeting_id.clone(),
-        name: None,
+        name: audio_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned()),
         created_at: Some(Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
+        audio_duration_seconds: Some(duration_seconds),
+        ..Default::default()
     };
     let metadata_path = meeting_dir.join("meeting.json");
     let json = serde_json::to_string(&metadata).map_err(|e| format!("Failed to serialize metadata: {}", e))?;
-    tokio::fs::write(metadata_path, json).await.map_err(|e| format!("Failed to write metadata: {}", e))?;
+    if let Err(e) = tokio::fs::write(metadata_path, json).await {
+        let error = format!("Failed to write metadata: {}", e);
+        return Err(conversion_failed(&events, &meeting_dir, &meeting_id, error, Vec::new()).await);
+    }
 
+    events
+        .emit_terminal("audio-conversion-finished", &meeting_id)
+        .await;
     Ok(meeting_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::progress::ProgressSink;
+    use crate::llm::test_harness::read_fixture;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordingSink(Arc<Mutex<Vec<Value>>>);
+
+    impl ProgressSink for RecordingSink {
+        fn send(&self, _event: &str, payload: Value) -> Result<(), String> {
+            self.0.lock().unwrap().push(payload);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conversion_progress_from_ffmpeg_output() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let events = ProgressEvents::new(RecordingSink(sent.clone()));
+        let output = read_fixture("ffmpeg/progress.txt");
+
+        pump_conversion_progress(BufReader::new(output.as_bytes()).lines(), &events, "recording-1", 100.0)
+            .await
+            .unwrap();
+
+        // out_time_us and out_time_ms repeat each other, 41.38s is still 41%
+        let sent = sent.lock().unwrap();
+        let percents: Vec<f64> = sent.iter().map(|event| event["percent"].as_f64().unwrap()).collect();
+        assert_eq!(percents, vec![20.1, 41.2, 100.0]);
+        assert_eq!(sent[1]["out_time"], 41.16);
+        assert_eq!(sent[2]["meeting_id"], "recording-1");
+    }
+
+    #[test]
+    fn test_chunk_count_follows_the_chunk_duration() {
+        let info = audio_info(5412.3, 5.0, 600.0);
+        assert!(info.needs_splitting);
+        assert_eq!(info.chunk_count, 10);
+
+        let info = audio_info(5412.3, 5.0, 2700.0);
+        assert_eq!(info.chunk_count, 3);
+
+        // Exactly one chunk long stays in one piece
+        let info = audio_info(600.0, 5.0, 600.0);
+        assert!(!info.needs_splitting);
+        assert_eq!(info.chunk_count, 1);
+    }
+
+    #[test]
+    fn test_parse_progress_out_time() {
+        assert_eq!(parse_progress_out_time("out_time_us=20125000"), Some(20.125));
+        assert_eq!(parse_progress_out_time("out_time_ms=1500000\n"), Some(1.5));
+        assert_eq!(parse_progress_out_time("out_time_us=N/A"), None);
+        assert_eq!(parse_progress_out_time("out_time=00:00:20.125000"), None);
+        assert_eq!(parse_progress_out_time("out_time_us=-9223372036854775807"), None);
+    }
+
+    #[test]
+    fn test_parse_ffprobe_duration() {
+        assert_eq!(parse_ffprobe_duration("1834.512000\n").unwrap(), 1834.512);
+        assert_eq!(parse_ffprobe_duration("  3.2 ").unwrap(), 3.2);
+
+        // Corrupt or empty files
+        for output in ["0", "0.000000", "-1.5", "NaN", "nan", "inf", "N/A", "", "\n"] {
+            assert!(
+                matches!(parse_ffprobe_duration(output), Err(AudioError::Unreadable { .. })),
+                "{:?} was accepted",
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_duration() {
+        assert!(check_min_duration(15.0, 15.0).is_ok());
+        assert!(check_min_duration(600.0, 15.0).is_ok());
+        assert_eq!(
+            check_min_duration(3.0, 15.0),
+            Err(AudioError::TooShort {
+                duration_seconds: 3.0,
+                min_seconds: 15.0
+            })
+        );
+        assert!(check_min_duration(14.99, 15.0).is_err());
+        // A minimum of 0 accepts any readable file
+        assert!(check_min_duration(0.1, 0.0).is_ok());
+
+        let message = String::from(check_min_duration(3.0, 15.0).unwrap_err());
+        assert!(message.contains("only 3.0 seconds"), "{}", message);
+    }
+}
@@ -0,0 +1,228 @@
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::fs;
+
+use crate::audio::get_audio_duration;
+use crate::llm::file_manager::transcription_chunks_dir;
+use crate::meeting::{get_audio_path, update_meeting_metadata};
+use crate::settings::{load_settings, AudioDeletionMode};
+
+/// Transcripts below this size are never trusted, regardless of duration
+const MIN_TRANSCRIPT_BYTES: u64 = 50;
+/// Normal speech produces roughly 800 bytes of text per minute, anything far
+/// below that means most of the recording was lost during transcription
+const MIN_TRANSCRIPT_BYTES_PER_MINUTE: f64 = 100.0;
+
+/// Make sure the transcript exists and is plausibly complete for the given duration
+pub async fn check_transcript_for_deletion(
+    transcript_path: &Path,
+    duration_seconds: f64,
+) -> Result<(), String> {
+    let size = fs::metadata(transcript_path)
+        .await
+        .map_err(|e| format!("Transcript not found, keeping the audio: {}", e))?
+        .len();
+
+    let expected = (duration_seconds / 60.0 * MIN_TRANSCRIPT_BYTES_PER_MINUTE) as u64;
+    if size < MIN_TRANSCRIPT_BYTES.max(expected) {
+        return Err(format!(
+            "Transcript is suspiciously small ({} bytes for {:.0}s of audio), keeping the audio",
+            size, duration_seconds
+        ));
+    }
+
+    Ok(())
+}
+
+/// Delete `<id>.ogg` and its chunk files, recording the original size and duration
+pub async fn delete_meeting_audio(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    duration_seconds: f64,
+) -> Result<(), String> {
+    let transcript_path = meeting_dir.join(format!("{}.txt", meeting_id));
+    check_transcript_for_deletion(&transcript_path, duration_seconds).await?;
+
+    let audio_path = get_audio_path(meeting_dir, meeting_id).await?;
+    let size = fs::metadata(&audio_path)
+        .await
+        .map_err(|e| e.to_string())?
+        .len();
+
+    // Chunks are written to `chunks/transcription/` as `<id>_chunk_NN.ogg`
+    let chunk_prefix = format!("{}_chunk_", meeting_id);
+    // Short recordings are transcribed without chunks and have no such directory
+    if let Ok(mut entries) = fs::read_dir(transcription_chunks_dir(meeting_dir)).await {
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if file_name.starts_with(&chunk_prefix) && file_name.ends_with(".ogg") {
+                fs::remove_file(entry.path())
+                    .await
+                    .map_err(|e| format!("Failed to delete audio chunk: {}", e))?;
+            }
+        }
+    }
+
+    fs::remove_file(&audio_path)
+        .await
+        .map_err(|e| format!("Failed to delete audio: {}", e))?;
+
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.audio_deleted = true;
+        metadata.original_audio_duration_seconds = Some(duration_seconds);
+        metadata.original_audio_size_bytes = Some(size);
+    })
+    .await?;
+
+    println!("Deleted audio for meeting {} ({} bytes)", meeting_id, size);
+    Ok(())
+}
+
+/// Apply the `delete_audio_after_transcription` setting once a transcript was written
+pub async fn handle_audio_after_transcription(
+    app: &AppHandle,
+    meeting_dir: &Path,
+    meeting_id: &str,
+    duration_seconds: f64,
+) -> Result<(), String> {
+    let settings = load_settings(app).await?;
+
+    match settings.delete_audio_after_transcription {
+        AudioDeletionMode::Off => Ok(()),
+        AudioDeletionMode::Auto => {
+            delete_meeting_audio(meeting_dir, meeting_id, duration_seconds).await
+        }
+        AudioDeletionMode::Ask => {
+            // Don't offer a deletion we would refuse anyway
+            let transcript_path = meeting_dir.join(format!("{}.txt", meeting_id));
+            check_transcript_for_deletion(&transcript_path, duration_seconds).await?;
+
+            app.emit("audio-deletion-requested", meeting_id)
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Answer to the `audio-deletion-requested` prompt
+#[tauri::command]
+pub async fn confirm_audio_deletion(app: AppHandle, meeting_id: &str) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let base_dir = app_dir.join("uploads").join(meeting_id);
+    let audio_path = get_audio_path(&base_dir, meeting_id).await?;
+    let duration_seconds = get_audio_duration(&audio_path).await?;
+
+    delete_meeting_audio(&base_dir, meeting_id, duration_seconds).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meeting::read_meeting_metadata;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    async fn fixture_meeting(transcript: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path();
+        fs::write(
+            meeting_dir.join(format!("{}.ogg", MEETING_ID)),
+            vec![0u8; 1024],
+        )
+        .await
+        .unwrap();
+        let chunks_dir = transcription_chunks_dir(meeting_dir);
+        fs::create_dir_all(&chunks_dir).await.unwrap();
+        fs::write(
+            chunks_dir.join(format!("{}_chunk_00.ogg", MEETING_ID)),
+            b"OggS",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            chunks_dir.join(format!("{}_chunk_00.txt", MEETING_ID)),
+            "chunk",
+        )
+        .await
+        .unwrap();
+        fs::write(meeting_dir.join(format!("{}.txt", MEETING_ID)), transcript)
+            .await
+            .unwrap();
+        dir
+    }
+
+    fn audio_exists(meeting_dir: &Path) -> bool {
+        meeting_dir.join(format!("{}.ogg", MEETING_ID)).exists()
+    }
+
+    #[tokio::test]
+    async fn test_missing_transcript_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = check_transcript_for_deletion(&dir.path().join("missing.txt"), 60.0).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_small_transcript_is_rejected() {
+        let dir = fixture_meeting("[SPEAKER_00]: Hallo").await;
+        let transcript_path = dir.path().join(format!("{}.txt", MEETING_ID));
+
+        // Below the absolute minimum even for a short recording
+        assert!(check_transcript_for_deletion(&transcript_path, 5.0)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transcript_small_relative_to_duration_is_rejected() {
+        let dir = fixture_meeting(&"a".repeat(500)).await;
+        let transcript_path = dir.path().join(format!("{}.txt", MEETING_ID));
+
+        // 500 bytes is fine for two minutes but not for an hour
+        assert!(check_transcript_for_deletion(&transcript_path, 120.0)
+            .await
+            .is_ok());
+        assert!(check_transcript_for_deletion(&transcript_path, 3600.0)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delete_keeps_audio_when_unsafe() {
+        let dir = fixture_meeting("").await;
+
+        assert!(delete_meeting_audio(dir.path(), MEETING_ID, 600.0)
+            .await
+            .is_err());
+        assert!(audio_exists(dir.path()));
+        assert!(transcription_chunks_dir(dir.path())
+            .join(format!("{}_chunk_00.ogg", MEETING_ID))
+            .exists());
+        assert!(read_meeting_metadata(dir.path()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_audio_and_records_metadata() {
+        let dir = fixture_meeting(&"[SPEAKER_00]: Wir fangen an.\n".repeat(100)).await;
+
+        delete_meeting_audio(dir.path(), MEETING_ID, 600.0)
+            .await
+            .unwrap();
+
+        assert!(!audio_exists(dir.path()));
+        assert!(!transcription_chunks_dir(dir.path())
+            .join(format!("{}_chunk_00.ogg", MEETING_ID))
+            .exists());
+        // Chunk transcripts are kept
+        assert!(transcription_chunks_dir(dir.path())
+            .join(format!("{}_chunk_00.txt", MEETING_ID))
+            .exists());
+
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert!(metadata.audio_deleted);
+        assert_eq!(metadata.original_audio_duration_seconds, Some(600.0));
+        assert_eq!(metadata.original_audio_size_bytes, Some(1024));
+    }
+}
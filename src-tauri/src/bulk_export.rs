@@ -0,0 +1,489 @@
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::export::{render_summary_html, render_summary_pdf, summary_markdown};
+use crate::filename::sanitize_filename;
+use crate::llm::models::MarkdownOptions;
+use crate::{list_meetings, MeetingMetadata, MeetingSort};
+
+const INDEX_STEM: &str = "index";
+
+/// Format of the files written by `export_summaries_bulk`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Pdf,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BulkExportReport {
+    pub exported: usize,
+    /// Meetings in the range without a summary
+    pub skipped: usize,
+    /// Exported file names, in the order of the index
+    pub files: Vec<String>,
+}
+
+/// Sent as `bulk-export-progress` after each meeting of the range
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BulkExportProgress {
+    pub meeting_id: String,
+    pub current: usize,
+    pub total: usize,
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date {:?}, expected YYYY-MM-DD: {}", date, e))
+}
+
+/// Day of `created_at` in `tz`, the same date the file name shows.
+/// `created_at` is stored in UTC, so a meeting just after local midnight
+/// would otherwise land on the previous day.
+fn meeting_date<Tz: TimeZone>(created_at: &str, tz: &Tz) -> Option<NaiveDate> {
+    match DateTime::parse_from_rfc3339(created_at) {
+        Ok(created_at) => Some(created_at.with_timezone(tz).date_naive()),
+        Err(_) => created_at
+            .get(..10)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()),
+    }
+}
+
+/// Lowercased names of the entries already in `destination`
+async fn existing_file_names(destination: &Path) -> Result<HashSet<String>, String> {
+    let mut entries = tokio::fs::read_dir(destination)
+        .await
+        .map_err(|e| format!("Failed to read export folder: {}", e))?;
+    let mut names = HashSet::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| format!("Failed to read export folder: {}", e))?
+    {
+        names.insert(entry.file_name().to_string_lossy().to_lowercase());
+    }
+    Ok(names)
+}
+
+/// `<stem>.<extension>`, with ` (2)`, ` (3)`, ... if that name is already
+/// taken, by this export or by a file in the folder. Compared
+/// case-insensitively, like most desktop file systems do.
+fn unique_file_name(taken: &mut HashSet<String>, stem: &str, extension: &str) -> String {
+    let mut file_name = format!("{}.{}", stem, extension);
+    let mut n = 2;
+    while !taken.insert(file_name.to_lowercase()) {
+        file_name = format!("{} ({}).{}", stem, n, extension);
+        n += 1;
+    }
+    file_name
+}
+
+fn display_title(metadata: &MeetingMetadata) -> String {
+    let name = metadata.name.as_deref().unwrap_or(&metadata.id);
+    match &metadata.emoji {
+        Some(emoji) => format!("{} {}", emoji, name),
+        None => name.to_string(),
+    }
+}
+
+/// The index linking every exported file with its title and date
+fn index_markdown(
+    from: NaiveDate,
+    to: NaiveDate,
+    entries: &[(NaiveDate, String, String)],
+) -> String {
+    let mut index = format!("# Meeting summaries {} – {}\n\n", from, to);
+    if entries.is_empty() {
+        index.push_str("No summaries in this range.\n");
+    }
+    for (date, title, file_name) in entries {
+        let title = title.replace('[', "\\[").replace(']', "\\]");
+        index.push_str(&format!("- {} [{}](<{}>)\n", date, title, file_name));
+    }
+    index
+}
+
+async fn write_summary(
+    path: PathBuf,
+    title: String,
+    markdown: String,
+    format: ExportFormat,
+) -> Result<(), String> {
+    let written = match format {
+        ExportFormat::Markdown => tokio::fs::write(&path, markdown).await,
+        ExportFormat::Html => tokio::fs::write(&path, render_summary_html(&title, &markdown)).await,
+        ExportFormat::Pdf => tokio::task::spawn_blocking(move || {
+            let file = std::fs::File::create(&path)?;
+            render_summary_pdf(BufWriter::new(file), &markdown).map(|_| ())
+        })
+        .await
+        .map_err(|e| format!("PDF export task failed: {}", e))?,
+    };
+    written.map_err(|e| format!("Failed to write {}: {}", title, e))
+}
+
+/// Export the summaries of the meetings created between `from_date` and
+/// `to_date` (inclusive, `YYYY-MM-DD`) into `destination`, oldest first,
+/// with an `index.md` linking them. Meetings without a summary are skipped,
+/// files already in `destination` are never overwritten.
+pub async fn export_summaries_in(
+    uploads: &Path,
+    from_date: &str,
+    to_date: &str,
+    destination: &Path,
+    format: ExportFormat,
+    mut on_progress: impl FnMut(BulkExportProgress),
+) -> Result<BulkExportReport, String> {
+    let (from, to) = (parse_date(from_date)?, parse_date(to_date)?);
+    if from > to {
+        return Err(format!(
+            "The range starts ({}) after it ends ({})",
+            from, to
+        ));
+    }
+    tokio::fs::create_dir_all(destination)
+        .await
+        .map_err(|e| format!("Failed to create export folder: {}", e))?;
+
+    let mut meetings: Vec<(NaiveDate, MeetingMetadata)> =
+        list_meetings(uploads, MeetingSort::default())
+            .await?
+            .into_iter()
+            .filter_map(|meeting| {
+                let date = meeting
+                    .metadata
+                    .created_at
+                    .as_deref()
+                    .and_then(|created_at| meeting_date(created_at, &Local))?;
+                (from..=to)
+                    .contains(&date)
+                    .then_some((date, meeting.metadata))
+            })
+            .collect();
+    meetings.sort_by(|(_, a), (_, b)| a.created_at.cmp(&b.created_at));
+
+    let total = meetings.len();
+    let mut report = BulkExportReport::default();
+    let mut index_entries = Vec::new();
+    let mut taken = existing_file_names(destination).await?;
+    let index_file_name = unique_file_name(&mut taken, INDEX_STEM, "md");
+    for (i, (date, metadata)) in meetings.into_iter().enumerate() {
+        let meeting_dir = uploads.join(&metadata.id);
+        match summary_markdown(&meeting_dir, &MarkdownOptions::default()).await {
+            Ok(markdown) => {
+                let name = metadata.name.as_deref().unwrap_or(&metadata.id);
+                let stem = format!("{} {}", date, sanitize_filename(name));
+                let file_name = unique_file_name(&mut taken, &stem, format.extension());
+                let title = display_title(&metadata);
+                write_summary(
+                    destination.join(&file_name),
+                    title.clone(),
+                    markdown,
+                    format,
+                )
+                .await?;
+
+                index_entries.push((date, title, file_name.clone()));
+                report.files.push(file_name);
+                report.exported += 1;
+            }
+            Err(e) => {
+                println!("⏭️  Skipping {} in bulk export: {}", metadata.id, e);
+                report.skipped += 1;
+            }
+        }
+        on_progress(BulkExportProgress {
+            meeting_id: metadata.id,
+            current: i + 1,
+            total,
+        });
+    }
+
+    tokio::fs::write(
+        destination.join(&index_file_name),
+        index_markdown(from, to, &index_entries),
+    )
+    .await
+    .map_err(|e| format!("Failed to write index: {}", e))?;
+
+    println!(
+        "📦 Exported {} summaries to {} ({} without summary)",
+        report.exported,
+        destination.display(),
+        report.skipped
+    );
+    Ok(report)
+}
+
+/// Export the summaries of a date range into one folder, see `export_summaries_in`
+#[tauri::command]
+pub async fn export_summaries_bulk(
+    app: AppHandle,
+    from_date: &str,
+    to_date: &str,
+    destination_dir: &str,
+    format: ExportFormat,
+) -> Result<BulkExportReport, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+
+    export_summaries_in(
+        &app_dir.join("uploads"),
+        from_date,
+        to_date,
+        Path::new(destination_dir),
+        format,
+        |progress| {
+            let _ = app.emit("bulk-export-progress", progress);
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::{FinalSummaryFormat, KeyFact, Title, Topic};
+
+    fn write_meeting(uploads: &Path, id: &str, name: &str, created_at: &str, summary: bool) {
+        let meeting_dir = uploads.join(id);
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            format!(
+                r#"{{"id":"{}","name":"{}","emoji":"📅","created_at":"{}"}}"#,
+                id, name, created_at
+            ),
+        )
+        .unwrap();
+        if summary {
+            let summary = FinalSummaryFormat {
+                title: Title {
+                    emoji: "📅".to_string(),
+                    text: name.to_string(),
+                },
+                key_facts: KeyFact::default(),
+                summary: format!("Zusammenfassung von {} & Co.", name),
+                topics: vec![Topic {
+                    title: "Budget".to_string(),
+                    bullet_points: vec!["**Beschluss:** 500 € für <Getränke>".to_string()],
+                    sub_topics: None,
+                }],
+                todos: Vec::new(),
+            };
+            std::fs::write(
+                meeting_dir.join("summary.json"),
+                serde_json::to_string(&summary).unwrap(),
+            )
+            .unwrap();
+        }
+    }
+
+    fn fixture_meetings() -> tempfile::TempDir {
+        let uploads = tempfile::tempdir().unwrap();
+        for (id, name, created_at, summary) in [
+            ("recording-1", "Plenum", "2025-02-28T12:00:00.000Z", true),
+            ("recording-2", "Plenum", "2025-03-05T10:00:00.000Z", true),
+            ("recording-3", "plenum", "2025-03-05T12:00:00.000Z", true),
+            (
+                "recording-4",
+                "Jour fixe",
+                "2025-03-12T09:00:00.000Z",
+                false,
+            ),
+            (
+                "recording-5",
+                "AG: Finanzen",
+                "2025-03-31T12:00:00.000Z",
+                true,
+            ),
+            ("recording-6", "Plenum", "2025-04-01T12:00:00.000Z", true),
+        ] {
+            write_meeting(uploads.path(), id, name, created_at, summary);
+        }
+        uploads
+    }
+
+    async fn export(
+        uploads: &Path,
+        destination: &Path,
+        format: ExportFormat,
+    ) -> (BulkExportReport, Vec<BulkExportProgress>) {
+        let mut progress = Vec::new();
+        let report = export_summaries_in(
+            uploads,
+            "2025-03-01",
+            "2025-03-31",
+            destination,
+            format,
+            |p| progress.push(p),
+        )
+        .await
+        .unwrap();
+        (report, progress)
+    }
+
+    #[tokio::test]
+    async fn test_only_meetings_in_range_are_exported() {
+        let uploads = fixture_meetings();
+        let destination = tempfile::tempdir().unwrap();
+
+        let (report, progress) =
+            export(uploads.path(), destination.path(), ExportFormat::Markdown).await;
+
+        assert_eq!(report.exported, 3);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(
+            report.files,
+            vec![
+                "2025-03-05 Plenum.md",
+                "2025-03-05 plenum (2).md",
+                "2025-03-31 AG - Finanzen.md",
+            ]
+        );
+        let ids: Vec<&str> = progress.iter().map(|p| p.meeting_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["recording-2", "recording-3", "recording-4", "recording-5"]
+        );
+        assert_eq!(progress.last().unwrap().current, 4);
+        assert_eq!(progress.last().unwrap().total, 4);
+
+        let exported =
+            std::fs::read_to_string(destination.path().join("2025-03-05 Plenum.md")).unwrap();
+        assert!(exported.starts_with("# Plenum\n"));
+
+        let mut written: Vec<String> = std::fs::read_dir(destination.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        written.sort();
+        assert_eq!(written.len(), 4);
+        assert!(written.contains(&"index.md".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_index_links_the_exported_files() {
+        let uploads = fixture_meetings();
+        let destination = tempfile::tempdir().unwrap();
+
+        export(uploads.path(), destination.path(), ExportFormat::Html).await;
+
+        let index = std::fs::read_to_string(destination.path().join("index.md")).unwrap();
+        assert_eq!(
+            index,
+            "# Meeting summaries 2025-03-01 – 2025-03-31\n\n\
+             - 2025-03-05 [📅 Plenum](<2025-03-05 Plenum.html>)\n\
+             - 2025-03-05 [📅 plenum](<2025-03-05 plenum (2).html>)\n\
+             - 2025-03-31 [📅 AG: Finanzen](<2025-03-31 AG - Finanzen.html>)\n"
+        );
+
+        let html =
+            std::fs::read_to_string(destination.path().join("2025-03-05 Plenum.html")).unwrap();
+        assert!(html.contains("<title>📅 Plenum</title>"), "{}", html);
+        assert!(
+            html.contains("<p>Zusammenfassung von Plenum &amp; Co.</p>"),
+            "{}",
+            html
+        );
+        assert!(html.contains("<h3 id=\"budget\">Budget</h3>"), "{}", html);
+        assert!(
+            html.contains("<li><strong>Beschluss:</strong> 500 € für &lt;Getränke&gt;"),
+            "{}",
+            html
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pdf_export_and_invalid_ranges() {
+        let uploads = fixture_meetings();
+        let destination = tempfile::tempdir().unwrap();
+
+        let (report, _) = export(uploads.path(), destination.path(), ExportFormat::Pdf).await;
+        let pdf = std::fs::read(destination.path().join(&report.files[0])).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+
+        for (from, to) in [("2025-03-31", "2025-03-01"), ("März", "2025-03-31")] {
+            assert!(export_summaries_in(
+                uploads.path(),
+                from,
+                to,
+                destination.path(),
+                ExportFormat::Pdf,
+                |_| {}
+            )
+            .await
+            .is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_existing_files_are_not_overwritten() {
+        let uploads = fixture_meetings();
+        let destination = tempfile::tempdir().unwrap();
+        std::fs::write(destination.path().join("INDEX.md"), "notes").unwrap();
+        std::fs::write(destination.path().join("2025-03-05 plenum.md"), "old").unwrap();
+
+        let (report, _) = export(uploads.path(), destination.path(), ExportFormat::Markdown).await;
+
+        assert_eq!(
+            report.files,
+            vec![
+                "2025-03-05 Plenum (2).md",
+                "2025-03-05 plenum (3).md",
+                "2025-03-31 AG - Finanzen.md",
+            ]
+        );
+        let read = |name: &str| std::fs::read_to_string(destination.path().join(name)).unwrap();
+        assert_eq!(read("INDEX.md"), "notes");
+        assert_eq!(read("2025-03-05 plenum.md"), "old");
+        assert!(read("index (2).md").contains("(<2025-03-05 Plenum (2).md>)"));
+    }
+
+    #[test]
+    fn test_meeting_date_uses_the_local_day() {
+        let berlin = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let utc = chrono::Utc;
+        let created_at = "2025-03-31T22:30:00.000Z";
+        assert_eq!(
+            meeting_date(created_at, &berlin),
+            NaiveDate::from_ymd_opt(2025, 4, 1)
+        );
+        assert_eq!(
+            meeting_date(created_at, &utc),
+            NaiveDate::from_ymd_opt(2025, 3, 31)
+        );
+        assert_eq!(
+            meeting_date("2025-03-31 22:30", &berlin),
+            NaiveDate::from_ymd_opt(2025, 3, 31)
+        );
+        assert_eq!(meeting_date("gestern", &berlin), None);
+    }
+
+    #[test]
+    fn test_unique_file_names_ignore_case() {
+        let mut taken = HashSet::from(["index.md".to_string()]);
+        assert_eq!(unique_file_name(&mut taken, "index", "md"), "index (2).md");
+        assert_eq!(unique_file_name(&mut taken, "A", "md"), "A.md");
+        assert_eq!(unique_file_name(&mut taken, "a", "md"), "a (2).md");
+        assert_eq!(unique_file_name(&mut taken, "A", "md"), "A (3).md");
+    }
+}
@@ -0,0 +1,328 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::audio::AudioChunk;
+use crate::llm::file_manager::{chunk_file_name, chunk_summary_file_name, summarization_run_path};
+
+pub const MANIFEST_FILE_NAME: &str = "chunks_manifest.json";
+/// Chunk transcripts are joined with a blank line into `<id>.txt`
+const TRANSCRIPT_SEPARATOR: &str = "\n\n";
+
+/// Maps audio chunks (transcription) and text chunks (summarization) onto the
+/// recording, so a summary bullet can be traced back to a part of the audio.
+///
+/// Each pipeline owns its half: transcription writes `audio_chunks`,
+/// summarization writes `text_chunks`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    #[serde(default)]
+    pub audio_chunks: Vec<AudioChunkEntry>,
+    #[serde(default)]
+    pub text_chunks: Vec<TextChunkEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioChunkEntry {
+    pub index: usize,
+    pub start_time: f64,
+    pub end_time: f64,
+    /// File name relative to the meeting directory
+    pub file: String,
+    /// Character range of this chunk's text in `<id>.txt`, `None` if it failed to transcribe
+    pub transcript_range: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextChunkEntry {
+    pub index: usize,
+    /// Paths relative to the meeting directory
+    pub file: String,
+    pub summary_file: String,
+    /// Character range in `<id>.txt`
+    pub char_range: (usize, usize),
+    /// Time range of the audio chunks covering this text, when known
+    pub time_range: Option<(f64, f64)>,
+}
+
+/// Build the audio half from the transcribed chunks and their transcripts, in order
+pub fn audio_chunk_entries(
+    meeting_dir: &Path,
+    chunks: &[AudioChunk],
+    transcripts: &[Option<String>],
+) -> Vec<AudioChunkEntry> {
+    let separator_len = TRANSCRIPT_SEPARATOR.chars().count();
+    let mut cursor = 0;
+    let mut first = true;
+
+    chunks
+        .iter()
+        .zip(transcripts)
+        .map(|(chunk, transcript)| {
+            let transcript_range = transcript.as_ref().map(|text| {
+                if !first {
+                    cursor += separator_len;
+                }
+                first = false;
+                let start = cursor;
+                cursor += text.chars().count();
+                (start, cursor)
+            });
+
+            AudioChunkEntry {
+                index: chunk.chunk_index,
+                start_time: chunk.start_time,
+                end_time: chunk.end_time,
+                file: Path::new(&chunk.file_path)
+                    .strip_prefix(meeting_dir)
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .unwrap_or_else(|_| chunk.file_path.clone()),
+                transcript_range,
+            }
+        })
+        .collect()
+}
+
+/// Build the text half by locating each summarization chunk in the transcript
+pub fn text_chunk_entries(
+    transcript: &str,
+    chunks: &[String],
+    audio_chunks: &[AudioChunkEntry],
+    run_id: &str,
+) -> Vec<TextChunkEntry> {
+    let run_path = summarization_run_path(run_id);
+    let mut byte_cursor = 0;
+    let mut char_cursor = 0;
+
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            // Chunks are trimmed, so they appear in order but not necessarily back to back
+            let (start, end) = match transcript[byte_cursor..].find(chunk.as_str()) {
+                Some(offset) => {
+                    let start = char_cursor
+                        + transcript[byte_cursor..byte_cursor + offset]
+                            .chars()
+                            .count();
+                    byte_cursor += offset + chunk.len();
+                    char_cursor = start + chunk.chars().count();
+                    (start, char_cursor)
+                }
+                None => (char_cursor, char_cursor + chunk.chars().count()),
+            };
+
+            TextChunkEntry {
+                index: i,
+                file: format!("{}/{}", run_path, chunk_file_name(i)),
+                summary_file: format!("{}/{}", run_path, chunk_summary_file_name(i)),
+                char_range: (start, end),
+                time_range: covering_time_range(audio_chunks, start, end),
+            }
+        })
+        .collect()
+}
+
+/// Earliest start and latest end of the audio chunks overlapping a character range
+pub fn covering_time_range(
+    audio_chunks: &[AudioChunkEntry],
+    char_start: usize,
+    char_end: usize,
+) -> Option<(f64, f64)> {
+    audio_chunks
+        .iter()
+        .filter(|chunk| match chunk.transcript_range {
+            Some((start, end)) => start < char_end && char_start < end,
+            None => false,
+        })
+        .fold(None, |range, chunk| match range {
+            None => Some((chunk.start_time, chunk.end_time)),
+            Some((start, end)) => Some((start.min(chunk.start_time), end.max(chunk.end_time))),
+        })
+}
+
+pub async fn read_manifest(meeting_dir: &Path) -> Result<ChunkManifest, String> {
+    let path = meeting_dir.join(MANIFEST_FILE_NAME);
+    if !path.exists() {
+        return Ok(ChunkManifest::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read chunk manifest: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse chunk manifest: {}", e))
+}
+
+pub async fn write_manifest(meeting_dir: &Path, manifest: &ChunkManifest) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(meeting_dir.join(MANIFEST_FILE_NAME), json)
+        .await
+        .map_err(|e| format!("Failed to write chunk manifest: {}", e))
+}
+
+/// Replace the audio half. The text half refers to the old transcript and is dropped.
+pub async fn save_audio_chunks(
+    meeting_dir: &Path,
+    audio_chunks: Vec<AudioChunkEntry>,
+) -> Result<(), String> {
+    let manifest = ChunkManifest {
+        audio_chunks,
+        text_chunks: Vec::new(),
+    };
+    write_manifest(meeting_dir, &manifest).await
+}
+
+/// Replace the text half with the chunks of a summarization run, keeping what
+/// transcription recorded
+pub async fn save_text_chunks(
+    meeting_dir: &Path,
+    transcript: &str,
+    chunks: &[String],
+    run_id: &str,
+) -> Result<(), String> {
+    let mut manifest = read_manifest(meeting_dir).await?;
+    manifest.text_chunks = text_chunk_entries(transcript, chunks, &manifest.audio_chunks, run_id);
+    write_manifest(meeting_dir, &manifest).await
+}
+
+#[tauri::command]
+pub async fn get_chunk_manifest(app: AppHandle, meeting_id: &str) -> Result<ChunkManifest, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    read_manifest(&meeting_dir).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audio_chunk(index: usize, start_time: f64, end_time: f64) -> AudioChunk {
+        AudioChunk {
+            chunk_index: index,
+            start_time,
+            end_time,
+            file_path: format!("/tmp/meeting/recording-1_chunk_{:02}.ogg", index),
+        }
+    }
+
+    #[test]
+    fn test_audio_ranges_follow_joined_transcript() {
+        let chunks = vec![
+            audio_chunk(0, 0.0, 600.0),
+            audio_chunk(1, 600.0, 1200.0),
+            audio_chunk(2, 1200.0, 1500.0),
+        ];
+        let transcripts = vec![Some("a".repeat(10)), None, Some("b".repeat(5))];
+
+        let entries = audio_chunk_entries(Path::new("/tmp/meeting"), &chunks, &transcripts);
+
+        assert_eq!(entries[0].transcript_range, Some((0, 10)));
+        assert_eq!(entries[1].transcript_range, None);
+        // Separator only between chunks that made it into the transcript
+        assert_eq!(entries[2].transcript_range, Some((12, 17)));
+        assert_eq!(entries[2].file, "recording-1_chunk_02.ogg");
+        assert_eq!(entries[2].start_time, 1200.0);
+    }
+
+    #[test]
+    fn test_covering_time_range() {
+        let entries = audio_chunk_entries(
+            Path::new("/tmp/meeting"),
+            &[audio_chunk(0, 0.0, 600.0), audio_chunk(1, 600.0, 1200.0)],
+            &[Some("a".repeat(100)), Some("b".repeat(100))],
+        );
+
+        assert_eq!(covering_time_range(&entries, 0, 50), Some((0.0, 600.0)));
+        assert_eq!(covering_time_range(&entries, 90, 110), Some((0.0, 1200.0)));
+        assert_eq!(
+            covering_time_range(&entries, 150, 202),
+            Some((600.0, 1200.0))
+        );
+        assert_eq!(covering_time_range(&entries, 300, 400), None);
+        assert_eq!(covering_time_range(&[], 0, 10), None);
+    }
+
+    #[test]
+    fn test_text_chunks_are_located_in_transcript() {
+        let transcript = "Erster Satz hier. Zweiter Satz dort. Dritter Satz.";
+        let chunks = vec![
+            "Erster Satz hier.".to_string(),
+            "Zweiter Satz dort.".to_string(),
+            "Dritter Satz.".to_string(),
+        ];
+
+        let entries = text_chunk_entries(transcript, &chunks, &[], "run1");
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].char_range, (0, 17));
+        assert_eq!(entries[1].char_range, (18, 36));
+        assert_eq!(entries[2].char_range, (37, 50));
+        assert_eq!(entries[1].file, "chunks/summarization/run1/chunk_002.txt");
+        assert_eq!(
+            entries[1].summary_file,
+            "chunks/summarization/run1/chunk_002_summary.json"
+        );
+        assert_eq!(entries[0].time_range, None);
+    }
+
+    #[test]
+    fn test_text_chunk_ranges_count_chars_not_bytes() {
+        let transcript = "Über Änderungen. Danach Ärger.";
+        let chunks = vec!["Über Änderungen.".to_string(), "Danach Ärger.".to_string()];
+
+        let entries = text_chunk_entries(transcript, &chunks, &[], "run1");
+
+        assert_eq!(entries[0].char_range, (0, 16));
+        assert_eq!(entries[1].char_range, (17, 30));
+    }
+
+    #[test]
+    fn test_text_chunks_get_time_ranges_from_audio_chunks() {
+        let first = "a".repeat(20);
+        let second = "b".repeat(20);
+        let transcript = format!("{}{}{}", first, TRANSCRIPT_SEPARATOR, second);
+        let audio = audio_chunk_entries(
+            Path::new("/tmp/meeting"),
+            &[audio_chunk(0, 0.0, 600.0), audio_chunk(1, 600.0, 900.0)],
+            &[Some(first.clone()), Some(second.clone())],
+        );
+
+        let text_chunks = vec![first, second];
+        let entries = text_chunk_entries(&transcript, &text_chunks, &audio, "run1");
+
+        assert_eq!(entries[0].time_range, Some((0.0, 600.0)));
+        assert_eq!(entries[1].time_range, Some((600.0, 900.0)));
+    }
+
+    #[tokio::test]
+    async fn test_pipelines_write_their_halves() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = audio_chunk_entries(
+            dir.path(),
+            &[audio_chunk(0, 0.0, 60.0)],
+            &[Some("Hallo.".to_string())],
+        );
+
+        save_audio_chunks(dir.path(), audio.clone()).await.unwrap();
+        save_text_chunks(dir.path(), "Hallo.", &["Hallo.".to_string()], "run1")
+            .await
+            .unwrap();
+
+        let manifest = read_manifest(dir.path()).await.unwrap();
+        assert_eq!(manifest.audio_chunks, audio);
+        assert_eq!(manifest.text_chunks[0].time_range, Some((0.0, 60.0)));
+
+        // A new transcription invalidates the text half
+        save_audio_chunks(dir.path(), audio).await.unwrap();
+        assert!(read_manifest(dir.path())
+            .await
+            .unwrap()
+            .text_chunks
+            .is_empty());
+    }
+}
@@ -0,0 +1,447 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_opener::OpenerExt;
+use tokio::fs;
+
+use crate::llm::file_manager::{
+    current_summarization_run_dir, read_summary_from, summarization_dir, CHUNKS_DIR_NAME,
+};
+use crate::meeting::read_meeting_metadata;
+use crate::settings::{get_settings_path, SETTINGS_VERSION};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppInfo {
+    pub app_version: String,
+    /// Settings format written by this build
+    pub settings_version: u32,
+    /// Version stored in `settings.json`, `None` if there is no file or it predates versioning
+    pub settings_file_version: Option<u64>,
+    pub meeting_count: usize,
+    pub data_dir: PathBuf,
+    pub uploads_dir: PathBuf,
+    pub settings_path: PathBuf,
+}
+
+/// Where the app keeps its files, for the support screen
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DataPaths {
+    pub data_dir: String,
+    pub uploads_dir: String,
+    pub settings_path: String,
+    /// Python runtime used for whisperx
+    pub python_dir: String,
+    pub log_dir: String,
+}
+
+/// The folders `open_data_folder` may open. Anything else is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFolder {
+    /// Holds `settings.json`
+    Data,
+    Uploads,
+    Python,
+    Logs,
+}
+
+impl DataFolder {
+    pub fn parse(kind: &str) -> Result<Self, String> {
+        match kind {
+            "data" => Ok(Self::Data),
+            "uploads" => Ok(Self::Uploads),
+            "python" => Ok(Self::Python),
+            "logs" => Ok(Self::Logs),
+            _ => Err(format!("Unknown data folder: {}", kind)),
+        }
+    }
+
+    fn path(self, data_dir: &Path, log_dir: &Path) -> PathBuf {
+        match self {
+            Self::Data => data_dir.to_path_buf(),
+            Self::Uploads => data_dir.join("uploads"),
+            Self::Python => data_dir.join("python"),
+            Self::Logs => log_dir.to_path_buf(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Harmless, e.g. leftovers of an interrupted run
+    Info,
+    /// Something is missing or out of date, the meeting still opens
+    Warning,
+    /// A file the app needs can't be read
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeetingReport {
+    pub meeting_id: String,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntegrityReport {
+    pub meetings_checked: usize,
+    /// Only meetings with findings
+    pub meetings: Vec<MeetingReport>,
+}
+
+/// Names of the meeting folders in `uploads/`
+async fn meeting_ids(uploads: &Path) -> Result<Vec<String>, String> {
+    let mut ids = Vec::new();
+    let mut entries = match fs::read_dir(uploads).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(ids),
+    };
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        if entry.path().is_dir() {
+            ids.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
+
+/// Inspect the files of one meeting without changing anything
+pub async fn check_meeting(meeting_dir: &Path, meeting_id: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    match read_meeting_metadata(meeting_dir).await {
+        Ok(Some(metadata)) if metadata.id != meeting_id => findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "meeting.json says id {}, the folder is {} (fix with repair_meeting)",
+                metadata.id, meeting_id
+            ),
+        )),
+        Ok(Some(_)) => {}
+        Ok(None) => findings.push(Finding::new(Severity::Info, "meeting.json is missing")),
+        Err(e) => findings.push(Finding::new(Severity::Error, e)),
+    }
+
+    let has_txt = meeting_dir.join(format!("{}.txt", meeting_id)).exists();
+    let has_json = meeting_dir.join(format!("{}.json", meeting_id)).exists();
+    if has_txt != has_json {
+        let (present, missing) = if has_txt {
+            ("txt", "json")
+        } else {
+            ("json", "txt")
+        };
+        findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "Transcript {} exists without its {} counterpart",
+                present, missing
+            ),
+        ));
+    }
+
+    if meeting_dir.join("summary.json").exists() {
+        if let Err(e) = read_summary_from(meeting_dir).await {
+            findings.push(Finding::new(Severity::Error, e));
+        }
+    }
+
+    findings.extend(check_chunks(meeting_dir).await);
+    findings
+}
+
+/// Flat chunk files that were never migrated and summarization runs that never finished
+async fn check_chunks(meeting_dir: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if let Ok(mut entries) = fs::read_dir(meeting_dir.join(CHUNKS_DIR_NAME)).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if entry.path().is_file() {
+                findings.push(Finding::new(
+                    Severity::Warning,
+                    format!(
+                        "Chunk file {} uses the old layout",
+                        entry.file_name().to_string_lossy()
+                    ),
+                ));
+            }
+        }
+    }
+
+    let current_run = current_summarization_run_dir(meeting_dir).await;
+    if let Ok(mut entries) = fs::read_dir(summarization_dir(meeting_dir)).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.is_dir() && current_run.as_ref() != Some(&path) {
+                findings.push(Finding::new(
+                    Severity::Info,
+                    format!(
+                        "Summarization run {} was never completed",
+                        entry.file_name().to_string_lossy()
+                    ),
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+pub async fn verify_uploads(uploads: &Path) -> Result<IntegrityReport, String> {
+    let ids = meeting_ids(uploads).await?;
+
+    let mut meetings = Vec::new();
+    for id in &ids {
+        let mut findings = check_meeting(&uploads.join(id), id).await;
+        if !findings.is_empty() {
+            findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+            meetings.push(MeetingReport {
+                meeting_id: id.clone(),
+                findings,
+            });
+        }
+    }
+
+    Ok(IntegrityReport {
+        meetings_checked: ids.len(),
+        meetings,
+    })
+}
+
+/// `version` field of a settings file, read without applying defaults
+async fn settings_file_version(path: &Path) -> Option<u64> {
+    let content = fs::read_to_string(path).await.ok()?;
+    let settings: serde_json::Value = serde_json::from_str(&content).ok()?;
+    settings["version"].as_u64()
+}
+
+/// Versions and paths for bug reports
+#[tauri::command]
+pub async fn get_app_info(app: AppHandle) -> Result<AppInfo, String> {
+    let data_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let uploads_dir = data_dir.join("uploads");
+    let settings_path = get_settings_path(&app)?;
+
+    Ok(AppInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        settings_version: SETTINGS_VERSION,
+        settings_file_version: settings_file_version(&settings_path).await,
+        meeting_count: meeting_ids(&uploads_dir).await?.len(),
+        data_dir,
+        uploads_dir,
+        settings_path,
+    })
+}
+
+fn log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to get app log directory: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_data_paths(app: AppHandle) -> Result<DataPaths, String> {
+    let data_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let log_dir = log_dir(&app)?;
+    let folder = |folder: DataFolder| {
+        folder
+            .path(&data_dir, &log_dir)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    Ok(DataPaths {
+        data_dir: folder(DataFolder::Data),
+        uploads_dir: folder(DataFolder::Uploads),
+        settings_path: get_settings_path(&app)?.to_string_lossy().into_owned(),
+        python_dir: folder(DataFolder::Python),
+        log_dir: folder(DataFolder::Logs),
+    })
+}
+
+/// Open one of the app's own folders in the file manager. `kind` is one of
+/// `data`, `uploads`, `python` or `logs`, never a path.
+#[tauri::command]
+pub async fn open_data_folder(app: AppHandle, kind: String) -> Result<(), String> {
+    let folder = DataFolder::parse(&kind)?;
+    let data_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let path = folder.path(&data_dir, &log_dir(&app)?);
+
+    fs::create_dir_all(&path)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// Report structural problems of the stored meetings. Nothing is repaired here.
+#[tauri::command]
+pub async fn verify_data_integrity(app: AppHandle) -> Result<IntegrityReport, String> {
+    let uploads = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join("uploads");
+
+    verify_uploads(&uploads).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::file_manager::{finish_summarization_run, start_summarization_run};
+
+    async fn healthy_meeting(uploads: &Path, id: &str) -> PathBuf {
+        let meeting_dir = uploads.join(id);
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            format!("{{\"id\": \"{}\", \"name\": \"Planung\"}}", id),
+        )
+        .unwrap();
+        std::fs::write(meeting_dir.join(format!("{}.txt", id)), "Hallo").unwrap();
+        std::fs::write(meeting_dir.join(format!("{}.json", id)), "{}").unwrap();
+        meeting_dir
+    }
+
+    #[tokio::test]
+    async fn test_healthy_meeting_has_no_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = healthy_meeting(dir.path(), "recording-1").await;
+        let run_id = start_summarization_run(&meeting_dir).await.unwrap();
+        finish_summarization_run(&meeting_dir, &run_id)
+            .await
+            .unwrap();
+
+        let report = verify_uploads(dir.path()).await.unwrap();
+        assert_eq!(report.meetings_checked, 1);
+        assert!(report.meetings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_broken_meetings_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        healthy_meeting(dir.path(), "recording-1").await;
+
+        let broken = healthy_meeting(dir.path(), "recording-2").await;
+        std::fs::write(broken.join("meeting.json"), "{\"id\": ").unwrap();
+        std::fs::remove_file(broken.join("recording-2.json")).unwrap();
+        std::fs::write(broken.join("summary.json"), "{\"title\": \"Planung\"}").unwrap();
+        std::fs::create_dir_all(broken.join("chunks")).unwrap();
+        std::fs::write(broken.join("chunks").join("chunk_001.txt"), "chunk").unwrap();
+        start_summarization_run(&broken).await.unwrap();
+
+        let report = verify_uploads(dir.path()).await.unwrap();
+        assert_eq!(report.meetings_checked, 2);
+        assert_eq!(report.meetings.len(), 1);
+        assert_eq!(report.meetings[0].meeting_id, "recording-2");
+
+        let severities: Vec<Severity> = report.meetings[0]
+            .findings
+            .iter()
+            .map(|finding| finding.severity)
+            .collect();
+        // Worst first
+        assert_eq!(
+            severities,
+            vec![
+                Severity::Error,
+                Severity::Error,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Info
+            ]
+        );
+        assert!(report.meetings[0].findings[2]
+            .message
+            .contains("txt exists without its json"));
+    }
+
+    #[tokio::test]
+    async fn test_id_mismatch_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let copy = healthy_meeting(dir.path(), "recording-4 (conflicted copy)").await;
+        std::fs::write(copy.join("meeting.json"), "{\"id\": \"recording-4\"}").unwrap();
+
+        let findings = check_meeting(&copy, "recording-4 (conflicted copy)").await;
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0].message.contains("repair_meeting"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_metadata_is_informational() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path().join("recording-3");
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+
+        assert_eq!(
+            check_meeting(&meeting_dir, "recording-3").await,
+            vec![Finding::new(Severity::Info, "meeting.json is missing")]
+        );
+    }
+
+    #[test]
+    fn test_data_folder_kinds() {
+        let data_dir = Path::new("/data");
+        let log_dir = Path::new("/logs");
+        assert_eq!(
+            DataFolder::parse("uploads")
+                .unwrap()
+                .path(data_dir, log_dir),
+            data_dir.join("uploads")
+        );
+        assert_eq!(
+            DataFolder::parse("logs").unwrap().path(data_dir, log_dir),
+            log_dir
+        );
+
+        for kind in [
+            "",
+            "Uploads",
+            "settings",
+            "../uploads",
+            "/etc",
+            "uploads/recording-1",
+        ] {
+            assert!(DataFolder::parse(kind).is_err(), "{}", kind);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_settings_file_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        assert_eq!(settings_file_version(&path).await, None);
+
+        std::fs::write(&path, "{\"redirect_model_cache\": true}").unwrap();
+        assert_eq!(settings_file_version(&path).await, None);
+
+        std::fs::write(&path, "{\"version\": 1}").unwrap();
+        assert_eq!(settings_file_version(&path).await, Some(1));
+    }
+}
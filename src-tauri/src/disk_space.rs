@@ -0,0 +1,145 @@
+use std::io;
+use std::path::Path;
+
+use crate::error::InsufficientDiskSpace;
+
+/// Kept free on top of what an operation needs, so the rest of the system
+/// doesn't run out of space either
+pub const SAFETY_MARGIN_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Summary JSON and markdown, chunk files and the archived version
+pub const SUMMARY_SPACE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Free space on the volume holding a path.
+///
+/// Abstracted so the checks can be tested without filling up a disk.
+pub trait SpaceProbe {
+    fn available_space(&self, path: &Path) -> io::Result<u64>;
+}
+
+/// Asks the operating system
+pub struct SystemSpaceProbe;
+
+impl SpaceProbe for SystemSpaceProbe {
+    fn available_space(&self, path: &Path) -> io::Result<u64> {
+        fs4::available_space(path)
+    }
+}
+
+/// Splitting audio into chunks writes a converted copy next to the chunks
+pub fn transcription_space_needed(audio_bytes: u64) -> u64 {
+    audio_bytes.saturating_mul(2)
+}
+
+/// Fail if `path` has less than `required_bytes` plus the safety margin free.
+/// If the free space can't be determined the operation is allowed, a write
+/// that fails later is still cleaned up.
+pub fn ensure_free_space(
+    probe: &impl SpaceProbe,
+    path: &Path,
+    required_bytes: u64,
+) -> Result<(), InsufficientDiskSpace> {
+    let available_bytes = match probe.available_space(path) {
+        Ok(available_bytes) => available_bytes,
+        Err(e) => {
+            println!(
+                "Warning: Failed to check free space at {}: {}",
+                path.display(),
+                e
+            );
+            return Ok(());
+        }
+    };
+
+    let required_bytes = required_bytes.saturating_add(SAFETY_MARGIN_BYTES);
+    if available_bytes < required_bytes {
+        return Err(InsufficientDiskSpace {
+            required_bytes,
+            available_bytes,
+        });
+    }
+    Ok(())
+}
+
+/// ENOSPC on Unix, ERROR_DISK_FULL and ERROR_HANDLE_DISK_FULL on Windows
+pub fn is_out_of_space(error: &io::Error) -> bool {
+    if error.kind() == io::ErrorKind::StorageFull {
+        return true;
+    }
+    match error.raw_os_error() {
+        Some(code) if cfg!(windows) => code == 112 || code == 39,
+        Some(code) => code == 28,
+        None => false,
+    }
+}
+
+/// Error message for a failed write, saying so plainly when the disk is full
+pub fn write_error(what: &str, error: io::Error) -> String {
+    if is_out_of_space(&error) {
+        format!(
+            "{}: the disk is full. Free up some space and try again.",
+            what
+        )
+    } else {
+        format!("{}: {}", what, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedProbe(io::Result<u64>);
+
+    impl SpaceProbe for FixedProbe {
+        fn available_space(&self, _path: &Path) -> io::Result<u64> {
+            match &self.0 {
+                Ok(bytes) => Ok(*bytes),
+                Err(e) => Err(io::Error::new(e.kind(), e.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ensure_free_space() {
+        let path = Path::new("/meetings");
+        let needed = transcription_space_needed(100 * 1024 * 1024);
+        assert_eq!(needed, 200 * 1024 * 1024);
+
+        let error =
+            ensure_free_space(&FixedProbe(Ok(120 * 1024 * 1024)), path, needed).unwrap_err();
+        assert_eq!(
+            error,
+            InsufficientDiskSpace {
+                required_bytes: 250 * 1024 * 1024,
+                available_bytes: 120 * 1024 * 1024,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Not enough disk space: 250.0 MB needed, 120.0 MB available"
+        );
+
+        assert!(ensure_free_space(&FixedProbe(Ok(250 * 1024 * 1024)), path, needed).is_ok());
+        // The margin alone is enough to fail
+        assert!(ensure_free_space(&FixedProbe(Ok(1024)), path, 0).is_err());
+        // A failing probe doesn't block
+        let unknown = FixedProbe(Err(io::Error::other("statvfs failed")));
+        assert!(ensure_free_space(&unknown, path, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_write_error_names_a_full_disk() {
+        let full = io::Error::from(io::ErrorKind::StorageFull);
+        assert!(is_out_of_space(&full));
+        assert_eq!(
+            write_error("Failed to save summary JSON", full),
+            "Failed to save summary JSON: the disk is full. Free up some space and try again."
+        );
+
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert!(!is_out_of_space(&denied));
+        assert!(write_error("Failed to save summary JSON", denied)
+            .starts_with("Failed to save summary JSON: permission denied"));
+    }
+}
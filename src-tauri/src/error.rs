@@ -0,0 +1,368 @@
+use serde::Serialize;
+use std::fmt;
+
+/// Errors for meeting level operations outside of the LLM pipeline
+#[derive(Debug)]
+pub enum MeetingError {
+    /// The meeting has no audio file (imported transcript or deleted audio)
+    NoAudio(String),
+    FileError(String),
+}
+
+impl fmt::Display for MeetingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeetingError::NoAudio(meeting_id) => {
+                write!(f, "No audio for this meeting ({})", meeting_id)
+            }
+            MeetingError::FileError(msg) => write!(f, "File error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MeetingError {}
+
+impl From<MeetingError> for String {
+    fn from(error: MeetingError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Why an audio file can't be used for a meeting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AudioError {
+    /// ffprobe found no usable duration, the file is probably corrupt
+    Unreadable { details: String },
+    /// Too short for a transcript worth summarizing
+    TooShort {
+        duration_seconds: f64,
+        min_seconds: f64,
+    },
+}
+
+impl fmt::Display for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioError::Unreadable { details } => write!(
+                f,
+                "The audio file could not be read ({}). Make sure it is not corrupt, or convert it to another format.",
+                details
+            ),
+            AudioError::TooShort {
+                duration_seconds,
+                min_seconds,
+            } => write!(
+                f,
+                "The audio is only {:.1} seconds long, meetings need at least {} seconds of audio",
+                duration_seconds, min_seconds
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
+
+impl From<AudioError> for String {
+    fn from(error: AudioError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Not enough free space for an operation, checked before it starts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct InsufficientDiskSpace {
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl fmt::Display for InsufficientDiskSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let megabytes = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        write!(
+            f,
+            "Not enough disk space: {:.1} MB needed, {:.1} MB available",
+            megabytes(self.required_bytes),
+            megabytes(self.available_bytes)
+        )
+    }
+}
+
+impl std::error::Error for InsufficientDiskSpace {}
+
+impl From<InsufficientDiskSpace> for String {
+    fn from(error: InsufficientDiskSpace) -> Self {
+        error.to_string()
+    }
+}
+
+/// A transcription or summarization couldn't start because of one already running.
+///
+/// A meeting is never transcribed and summarized at the same time, the summary
+/// would be made from a transcript that is about to be replaced.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BusyError {
+    /// Only one transcription or alignment runs at a time
+    TranscriptionRunning { meeting_id: String },
+    /// Only one summarization runs at a time
+    SummarizationRunning { meeting_id: String },
+    /// The meeting to summarize is being transcribed
+    MeetingTranscribing { meeting_id: String },
+    /// The meeting to transcribe is being summarized
+    MeetingSummarizing { meeting_id: String },
+}
+
+impl fmt::Display for BusyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusyError::TranscriptionRunning { .. } => write!(f, "Another Transcription is running"),
+            BusyError::SummarizationRunning { .. } => {
+                write!(f, "Another summarization is running")
+            }
+            BusyError::MeetingTranscribing { meeting_id } => write!(
+                f,
+                "Meeting {} is still being transcribed, summarize it when the transcript is done",
+                meeting_id
+            ),
+            BusyError::MeetingSummarizing { meeting_id } => write!(
+                f,
+                "Meeting {} is being summarized, transcribe it when the summary is done",
+                meeting_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BusyError {}
+
+impl From<BusyError> for String {
+    fn from(error: BusyError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Chunk summaries of the last run were made from another transcript or with
+/// another chunk size than a new run would use
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StaleChunks {
+    /// The transcript was edited or transcribed again since the run
+    pub transcript_changed: bool,
+    pub run_chunk_size: usize,
+    /// Chunk size a new run would split the transcript into
+    pub current_chunk_size: usize,
+}
+
+impl fmt::Display for StaleChunks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut reasons = Vec::new();
+        if self.transcript_changed {
+            reasons.push("the transcript changed since they were generated".to_string());
+        }
+        if self.run_chunk_size != self.current_chunk_size {
+            reasons.push(format!(
+                "they were split with chunk size {} instead of {}",
+                self.run_chunk_size, self.current_chunk_size
+            ));
+        }
+        write!(
+            f,
+            "Stale chunk summaries: {}. Summarize the meeting again to use the current transcript",
+            reasons.join(" and ")
+        )
+    }
+}
+
+impl std::error::Error for StaleChunks {}
+
+/// The summary is approved as the official minutes, replacing it needs `supersede_approved`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryApproved {
+    pub by: String,
+    pub at: String,
+}
+
+impl fmt::Display for SummaryApproved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Summary approved by {} ({}). Supersede the approved summary to replace it, the approved copy is kept",
+            self.by, self.at
+        )
+    }
+}
+
+impl std::error::Error for SummaryApproved {}
+
+impl From<SummaryApproved> for String {
+    fn from(error: SummaryApproved) -> Self {
+        error.to_string()
+    }
+}
+
+/// Why a whisperx / mlx_whisper run failed, recognized from its stderr
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranscriptionError {
+    OutOfMemory,
+    /// Hugging Face rejected the token, usually for the gated pyannote models
+    HuggingFaceUnauthorized,
+    /// Diarization is enabled but no Hugging Face token is stored
+    MissingHuggingFaceToken,
+    UnreadableAudio,
+    TorchVersionMismatch,
+    /// No output for longer than the inactivity timeout, the process was killed
+    Stalled {
+        idle_seconds: u64,
+    },
+    /// The process could not be started or waited on
+    Process {
+        message: String,
+    },
+    /// Nonzero exit without a known cause, with the end of stderr
+    Failed {
+        status: String,
+        stderr_tail: Vec<String>,
+    },
+    /// The user stopped the transcription, the process was killed
+    Cancelled,
+}
+
+impl TranscriptionError {
+    /// What the user can do about it
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            TranscriptionError::OutOfMemory => Some(
+                "Close other applications or transcribe on the CPU with a smaller model.",
+            ),
+            TranscriptionError::HuggingFaceUnauthorized => Some(
+                "Check your Hugging Face token and accept the pyannote model terms on huggingface.co.",
+            ),
+            TranscriptionError::MissingHuggingFaceToken => Some(
+                "Add a Hugging Face token in the settings or turn off speaker diarization.",
+            ),
+            TranscriptionError::UnreadableAudio => Some(
+                "Make sure ffmpeg is installed and the recording is not corrupt, or convert it to another format.",
+            ),
+            TranscriptionError::TorchVersionMismatch => {
+                Some("Reinstall WhisperX from the settings to get matching torch versions.")
+            }
+            TranscriptionError::Stalled { .. } => Some(
+                "Try again. If it keeps happening, increase the inactivity timeout in the settings.",
+            ),
+            TranscriptionError::Process { .. }
+            | TranscriptionError::Failed { .. }
+            | TranscriptionError::Cancelled => None,
+        }
+    }
+}
+
+impl fmt::Display for TranscriptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptionError::OutOfMemory => write!(f, "Transcription ran out of memory")?,
+            TranscriptionError::HuggingFaceUnauthorized => {
+                write!(f, "Hugging Face refused to download a model (401)")?
+            }
+            TranscriptionError::MissingHuggingFaceToken => {
+                write!(f, "Speaker diarization needs a Hugging Face token")?
+            }
+            TranscriptionError::UnreadableAudio => write!(f, "The audio file could not be read")?,
+            TranscriptionError::TorchVersionMismatch => {
+                write!(f, "Installed torch versions do not match the models")?
+            }
+            TranscriptionError::Stalled { idle_seconds } => write!(
+                f,
+                "Transcription printed nothing for {} minutes and was stopped",
+                idle_seconds / 60
+            )?,
+            TranscriptionError::Process { message } => write!(f, "{}", message)?,
+            TranscriptionError::Cancelled => write!(f, "Transcription cancelled")?,
+            TranscriptionError::Failed {
+                status,
+                stderr_tail,
+            } => {
+                write!(f, "whisperx exited with status: {}", status)?;
+                if !stderr_tail.is_empty() {
+                    write!(f, "\n{}", stderr_tail.join("\n"))?;
+                }
+            }
+        }
+
+        match self.hint() {
+            Some(hint) => write!(f, ". {}", hint),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptionError {}
+
+impl From<TranscriptionError> for String {
+    fn from(error: TranscriptionError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Step of `process_meeting` that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStage {
+    Converting,
+    Transcribing,
+    Summarizing,
+}
+
+impl fmt::Display for PipelineStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineStage::Converting => write!(f, "Converting the audio"),
+            PipelineStage::Transcribing => write!(f, "Transcribing"),
+            PipelineStage::Summarizing => write!(f, "Summarizing"),
+        }
+    }
+}
+
+/// `process_meeting` stopped at `stage`. Stages before it are kept, the
+/// meeting can be continued from there.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineError {
+    pub stage: PipelineStage,
+    /// `None` if converting failed before the meeting was created
+    pub meeting_id: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.stage, self.message)
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+/// Why a transcription or summarization did not get its slot
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClaimError {
+    /// Refused outright, e.g. because the same job is already running or queued
+    Busy(BusyError),
+    /// The job waited in the queue and was removed with `cancel_queued_job`
+    Cancelled,
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimError::Busy(error) => write!(f, "{}", error),
+            ClaimError::Cancelled => write!(f, "The queued job was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ClaimError {}
+
+impl From<ClaimError> for String {
+    fn from(error: ClaimError) -> Self {
+        error.to_string()
+    }
+}
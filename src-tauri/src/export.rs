@@ -0,0 +1,551 @@
+use std::collections::BTreeSet;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::llm::attendee_refs::resolve_attendee_references_in;
+use crate::llm::file_manager::{read_summary_from, FileManager};
+use crate::llm::models::{MarkdownOptions, MeetingToMarkdown};
+use crate::llm::outline::slugify;
+use crate::meeting::read_meeting_metadata;
+use crate::notes::read_notes;
+use crate::pdf::{PdfDocument, Span};
+use crate::transcript::Transcript;
+
+const TITLE_SIZE: f32 = 22.0;
+const HEADING_SIZE: f32 = 14.0;
+const BODY_SIZE: f32 = 10.5;
+const SEGMENT_SPACING: f32 = 4.0;
+
+/// Format seconds as `HH:MM:SS`
+pub(crate) fn format_timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        (total % 3600) / 60,
+        total % 60
+    )
+}
+
+/// Render the user's markdown notes on a new page. Headings are set in bold,
+/// everything else is kept as written.
+fn render_notes<W: Write>(document: &mut PdfDocument<W>, notes: &str) -> io::Result<()> {
+    document.new_page()?;
+    document.paragraph(&[Span::bold("Notes")], HEADING_SIZE)?;
+    document.spacer(BODY_SIZE);
+
+    for line in notes.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            document.spacer(BODY_SIZE);
+        } else if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim();
+            document.paragraph(&[Span::bold(heading)], BODY_SIZE)?;
+        } else {
+            document.paragraph(&[Span::regular(line)], BODY_SIZE)?;
+        }
+    }
+    Ok(())
+}
+
+/// Render a transcript with a title page, and the notes at the end if given.
+/// Pages are streamed to `writer`.
+pub fn render_transcript_pdf<W: Write>(
+    writer: W,
+    title: &str,
+    created_at: Option<&str>,
+    transcript: &Transcript,
+    include_timestamps: bool,
+    notes: Option<&str>,
+) -> io::Result<usize> {
+    let mut document = PdfDocument::new(writer)?;
+
+    // Title page
+    document.new_page()?;
+    document.paragraph(&[Span::bold(title)], TITLE_SIZE)?;
+    document.spacer(TITLE_SIZE);
+
+    let speakers: BTreeSet<&str> = transcript
+        .segments
+        .iter()
+        .filter_map(|segment| segment.speaker.as_deref())
+        .collect();
+    let duration = transcript.segments.last().map_or(0.0, |s| s.end);
+    let speaker_list = speakers.into_iter().collect::<Vec<_>>().join(", ");
+    let segment_count = transcript.segments.len().to_string();
+    let duration = format_timestamp(duration);
+
+    let mut details = vec![("Duration:", duration.as_str())];
+    if let Some(created_at) = created_at {
+        details.insert(0, ("Date:", created_at));
+    }
+    if !speaker_list.is_empty() {
+        details.push(("Speakers:", speaker_list.as_str()));
+    }
+    details.push(("Segments:", segment_count.as_str()));
+    for (label, value) in details {
+        document.paragraph(&[Span::bold(label), Span::regular(value)], BODY_SIZE)?;
+    }
+
+    // Transcript
+    document.new_page()?;
+    for segment in &transcript.segments {
+        let timestamp = format!("[{}]", format_timestamp(segment.start));
+        let speaker = segment.speaker.as_ref().map(|s| format!("{}:", s));
+
+        let mut spans = Vec::with_capacity(3);
+        if include_timestamps {
+            spans.push(Span::regular(&timestamp));
+        }
+        if let Some(speaker) = &speaker {
+            spans.push(Span::bold(speaker));
+        }
+        spans.push(Span::regular(segment.text.trim()));
+
+        document.paragraph(&spans, BODY_SIZE)?;
+        document.spacer(SEGMENT_SPACING);
+    }
+
+    if let Some(notes) = notes.filter(|notes| !notes.trim().is_empty()) {
+        render_notes(&mut document, notes)?;
+    }
+
+    document.finish()
+}
+
+/// A line of the summary markdown, as far as the HTML and PDF exports care
+#[derive(Debug, PartialEq)]
+enum SummaryLine<'a> {
+    Heading {
+        level: usize,
+        text: &'a str,
+        anchor: Option<&'a str>,
+    },
+    Item {
+        depth: usize,
+        text: &'a str,
+    },
+    Text(&'a str),
+    Blank,
+}
+
+/// Classify a line of markdown written by `to_markdown_with`
+fn summary_line(line: &str) -> SummaryLine<'_> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return SummaryLine::Blank;
+    }
+    if trimmed.starts_with('#') {
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let heading = trimmed[level..].trim();
+        // Topic headings carry their anchor as `<a id="..."></a>`
+        let anchored = heading.strip_suffix("\"></a>").and_then(|rest| {
+            let (text, anchor) = rest.rsplit_once(" <a id=\"")?;
+            Some((text.trim(), anchor))
+        });
+        let (text, anchor) = match anchored {
+            Some((text, anchor)) => (text, Some(anchor)),
+            None => (heading, None),
+        };
+        return SummaryLine::Heading {
+            level,
+            text,
+            anchor,
+        };
+    }
+    let indent = line.len() - line.trim_start().len();
+    match line.trim_start().strip_prefix("- ") {
+        Some(text) => SummaryLine::Item {
+            depth: indent / 2,
+            text: text.trim(),
+        },
+        None => SummaryLine::Text(trimmed),
+    }
+}
+
+/// Split `text` at `**`, with whether each part is bold. An unclosed `**`
+/// stays as written.
+fn bold_runs(text: &str) -> Vec<(String, bool)> {
+    let parts: Vec<&str> = text.split("**").collect();
+    let balanced = parts.len() % 2 == 1;
+    parts
+        .iter()
+        .enumerate()
+        .filter(|(_, part)| !part.is_empty())
+        .map(|(i, part)| {
+            if i % 2 == 1 && !balanced && i + 1 == parts.len() {
+                (format!("**{}", part), false)
+            } else {
+                (part.to_string(), i % 2 == 1)
+            }
+        })
+        .collect()
+}
+
+/// Markdown links `[label](href)` in `text` as label and href, the text
+/// between them with `None`
+fn link_runs(text: &str) -> Vec<(&str, Option<&str>)> {
+    let mut runs = Vec::new();
+    let mut rest = text;
+    let mut searched = 0;
+    while let Some(start) = rest[searched..].find('[').map(|i| searched + i) {
+        let link = rest[start + 1..]
+            .split_once("](")
+            .and_then(|(label, after)| {
+                let (href, tail) = after.split_once(')')?;
+                (!label.contains(']')).then_some((label, href, tail))
+            });
+        match link {
+            Some((label, href, tail)) => {
+                if start > 0 {
+                    runs.push((&rest[..start], None));
+                }
+                runs.push((label, Some(href)));
+                rest = tail;
+                searched = 0;
+            }
+            // `[1] sagt` and the like aren't links
+            None => searched = start + 1,
+        }
+    }
+    if !rest.is_empty() {
+        runs.push((rest, None));
+    }
+    runs
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn inline_html(text: &str) -> String {
+    let mut html = String::new();
+    for (part, bold) in bold_runs(text) {
+        if bold {
+            html.push_str("<strong>");
+        }
+        for (text, href) in link_runs(&part) {
+            match href {
+                Some(href) => html.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(href),
+                    escape_html(text)
+                )),
+                None => html.push_str(&escape_html(text)),
+            }
+        }
+        if bold {
+            html.push_str("</strong>");
+        }
+    }
+    html
+}
+
+/// Close nested lists until only `keep` are open
+fn close_lists(html: &mut String, open_lists: &mut usize, keep: usize) {
+    while *open_lists > keep {
+        html.push_str("</li>\n</ul>\n");
+        *open_lists -= 1;
+    }
+}
+
+/// A standalone HTML page of the summary markdown, so it looks the same as
+/// the markdown export
+pub fn render_summary_html(title: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let mut open_lists = 0;
+    for line in markdown.lines() {
+        if let SummaryLine::Item { depth, text } = summary_line(line) {
+            // A list can't skip a level
+            let depth = depth.min(open_lists);
+            if depth == open_lists {
+                body.push_str("<ul>\n");
+                open_lists += 1;
+            } else {
+                close_lists(&mut body, &mut open_lists, depth + 1);
+                body.push_str("</li>\n");
+            }
+            body.push_str(&format!("<li>{}", inline_html(text)));
+            continue;
+        }
+
+        close_lists(&mut body, &mut open_lists, 0);
+        match summary_line(line) {
+            SummaryLine::Heading {
+                level,
+                text,
+                anchor,
+            } => {
+                let level = level.min(6);
+                let id = anchor.map_or_else(|| slugify(text), str::to_string);
+                body.push_str(&format!(
+                    "<h{level} id=\"{}\">{}</h{level}>\n",
+                    escape_html(&id),
+                    inline_html(text)
+                ));
+            }
+            SummaryLine::Text(text) => body.push_str(&format!("<p>{}</p>\n", inline_html(text))),
+            SummaryLine::Item { .. } | SummaryLine::Blank => {}
+        }
+    }
+    close_lists(&mut body, &mut open_lists, 0);
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body {{ font-family: sans-serif; max-width: 50em; margin: 2em auto; line-height: 1.5; }}</style>\n\
+         </head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+/// One line of summary markdown after `prefix`, links reduced to their label
+fn render_summary_line<W: Write>(
+    document: &mut PdfDocument<W>,
+    prefix: &str,
+    text: &str,
+    size: f32,
+    all_bold: bool,
+) -> io::Result<()> {
+    let runs: Vec<(String, bool)> = bold_runs(text)
+        .into_iter()
+        .map(|(part, bold)| {
+            let plain: String = link_runs(&part).into_iter().map(|(text, _)| text).collect();
+            (plain, bold || all_bold)
+        })
+        .collect();
+
+    let mut spans = Vec::with_capacity(runs.len() + 1);
+    if !prefix.is_empty() {
+        spans.push(Span::regular(prefix));
+    }
+    for (text, bold) in &runs {
+        spans.push(if *bold {
+            Span::bold(text)
+        } else {
+            Span::regular(text)
+        });
+    }
+    document.paragraph(&spans, size)
+}
+
+/// Render the summary markdown as a PDF, with the same headings and lists
+pub fn render_summary_pdf<W: Write>(writer: W, markdown: &str) -> io::Result<usize> {
+    let mut document = PdfDocument::new(writer)?;
+    document.new_page()?;
+
+    for line in markdown.lines() {
+        match summary_line(line) {
+            SummaryLine::Heading { level, text, .. } => {
+                let size = match level {
+                    1 => TITLE_SIZE,
+                    2 => HEADING_SIZE,
+                    _ => BODY_SIZE,
+                };
+                if level > 1 {
+                    document.spacer(SEGMENT_SPACING);
+                }
+                render_summary_line(&mut document, "", text, size, true)?;
+                document.spacer(SEGMENT_SPACING);
+            }
+            SummaryLine::Item { depth, text } => {
+                // Paragraphs collapse leading spaces, nesting shows in the bullet
+                let bullet = if depth == 0 { "•" } else { "–" };
+                render_summary_line(&mut document, bullet, text, BODY_SIZE, false)?;
+            }
+            SummaryLine::Text(text) => {
+                render_summary_line(&mut document, "", text, BODY_SIZE, false)?
+            }
+            SummaryLine::Blank => document.spacer(BODY_SIZE),
+        }
+    }
+
+    document.finish()
+}
+
+/// The summary of the meeting in `meeting_dir` as markdown, with attendee
+/// references resolved. Every summary export starts from this.
+pub async fn summary_markdown(
+    meeting_dir: &Path,
+    options: &MarkdownOptions,
+) -> Result<String, String> {
+    let summary = read_summary_from(meeting_dir).await?;
+    Ok(resolve_attendee_references_in(meeting_dir, &summary.to_markdown_with(options)).await)
+}
+
+/// Export the transcript as a PDF document
+#[tauri::command]
+pub async fn export_transcript_pdf(
+    app: AppHandle,
+    meeting_id: &str,
+    destination_path: &str,
+    include_timestamps: bool,
+    include_notes: Option<bool>,
+) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    let transcript_json =
+        tokio::fs::read_to_string(meeting_dir.join(format!("{}.json", meeting_id)))
+            .await
+            .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let transcript: Transcript = serde_json::from_str(&transcript_json)
+        .map_err(|e| format!("Failed to parse transcript: {}", e))?;
+
+    let metadata = read_meeting_metadata(&meeting_dir).await?;
+    let title = metadata
+        .as_ref()
+        .and_then(|m| m.name.clone())
+        .unwrap_or_else(|| meeting_id.to_string());
+    let created_at = metadata.and_then(|m| m.created_at);
+    let notes = if include_notes.unwrap_or(false) {
+        Some(read_notes(&meeting_dir).await?)
+    } else {
+        None
+    };
+
+    let destination = PathBuf::from(destination_path);
+    let pages = tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::create(&destination)?;
+        render_transcript_pdf(
+            BufWriter::new(file),
+            &title,
+            created_at.as_deref(),
+            &transcript,
+            include_timestamps,
+            notes.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("PDF export task failed: {}", e))?
+    .map_err(|e| format!("Failed to write PDF: {}", e))?;
+
+    println!("Exported transcript of {} ({} pages)", meeting_id, pages);
+    Ok(())
+}
+
+/// Export the summary as markdown, long summaries laid out by `options`
+#[tauri::command]
+pub async fn export_summary_markdown(
+    app: AppHandle,
+    meeting_id: &str,
+    destination_path: &str,
+    options: Option<MarkdownOptions>,
+) -> Result<(), String> {
+    let meeting_dir = FileManager::new(app).get_meeting_dir(meeting_id)?;
+    let markdown = summary_markdown(&meeting_dir, &options.unwrap_or_default()).await?;
+
+    tokio::fs::write(destination_path, markdown)
+        .await
+        .map_err(|e| format!("Failed to write markdown: {}", e))?;
+
+    println!("Exported summary of {}", meeting_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::TranscriptSegment;
+
+    fn transcript(segments: usize) -> Transcript {
+        Transcript {
+            segments: (0..segments)
+                .map(|i| TranscriptSegment {
+                    start: i as f64 * 5.0,
+                    end: i as f64 * 5.0 + 4.5,
+                    text: "Wir besprechen die Größe der Übergabe und die nächsten Schritte für das Team."
+                        .to_string(),
+                    speaker: Some(if i % 2 == 0 { "Jürgen" } else { "Anna" }.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn render_with_notes(segments: usize, notes: Option<&str>) -> (usize, Vec<u8>) {
+        let mut output = Vec::new();
+        let pages = render_transcript_pdf(
+            &mut output,
+            "📅 Übergabe",
+            Some("2024-05-01T10:00:00.000Z"),
+            &transcript(segments),
+            true,
+            notes,
+        )
+        .unwrap();
+        (pages, output)
+    }
+
+    fn render(segments: usize) -> (usize, Vec<u8>) {
+        render_with_notes(segments, None)
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0), "00:00:00");
+        assert_eq!(format_timestamp(3723.9), "01:02:03");
+    }
+
+    #[test]
+    fn test_page_count_scales_with_segments() {
+        let (small, _) = render(10);
+        let (medium, _) = render(500);
+        let (large, _) = render(2000);
+
+        // Title page plus one page of transcript
+        assert_eq!(small, 2);
+        assert!(medium > small);
+        // Transcript pages grow linearly with the number of segments
+        let ratio = (large - 1) as f64 / (medium - 1) as f64;
+        assert!((3.5..=4.5).contains(&ratio), "ratio was {}", ratio);
+    }
+
+    #[test]
+    fn test_umlauts_are_encoded() {
+        let (_, output) = render(1);
+        // "Jürgen" in WinAnsi
+        assert!(output.windows(6).any(|w| w == b"J\xFCrgen"));
+        assert!(output.windows(2).any(|w| w == b"\xDFe"));
+    }
+
+    #[test]
+    fn test_summary_html_nests_lists() {
+        let html = render_summary_html(
+            "Plenum",
+            "# Plenum\n\n## Key Facts\n- **Attendees:**\n  - Anna\n  - Ben\n- Ort: [Wiki](https://example.org?a=1&b=2)\n\
+             ### Budget <a id=\"budget-1\"></a>\n- [1] fragt\n",
+        );
+
+        assert!(
+            html.contains(
+                "<h2 id=\"key-facts\">Key Facts</h2>\n<ul>\n<li><strong>Attendees:</strong><ul>\n\
+             <li>Anna</li>\n<li>Ben</li>\n</ul>\n</li>\n\
+             <li>Ort: <a href=\"https://example.org?a=1&amp;b=2\">Wiki</a></li>\n</ul>\n\
+             <h3 id=\"budget-1\">Budget</h3>\n<ul>\n<li>[1] fragt</li>\n</ul>\n"
+            ),
+            "{}",
+            html
+        );
+    }
+
+    #[test]
+    fn test_notes_are_optional() {
+        let (without_notes, _) = render(10);
+        let (with_notes, output) =
+            render_with_notes(10, Some("# Offene Punkte\n\n- Budget klären"));
+        let (empty_notes, _) = render_with_notes(10, Some("  "));
+
+        assert_eq!(with_notes, without_notes + 1);
+        assert_eq!(empty_notes, without_notes);
+        assert!(output.windows(13).any(|w| w == b"Budget kl\xE4ren"));
+    }
+}
@@ -0,0 +1,170 @@
+use tauri::{AppHandle, Manager};
+
+use crate::meeting::read_meeting_metadata;
+
+/// Characters Windows doesn't allow in file names
+const FORBIDDEN_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+const MAX_FILENAME_CHARS: usize = 100;
+const FALLBACK_FILENAME: &str = "meeting";
+
+/// Turn a meeting name into something every file system and sync tool accepts.
+///
+/// Emoji (including ZWJ sequences, variation selectors, skin tones and flags) are
+/// dropped, path separators and `:`/`|` become dashes, the remaining forbidden
+/// characters are removed. Letters like umlauts are kept. Only use this for
+/// file names, the display name in `meeting.json` stays as it is.
+pub fn sanitize_filename(name: &str) -> String {
+    let mut replaced = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            c if is_emoji_component(c) || c.is_control() => {}
+            ':' | '|' => replaced.push_str(" - "),
+            '/' | '\\' => replaced.push('-'),
+            c if FORBIDDEN_CHARS.contains(&c) => {}
+            c => replaced.push(c),
+        }
+    }
+
+    // Removed emoji leave double spaces behind
+    let collapsed = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    let truncated: String = collapsed.chars().take(MAX_FILENAME_CHARS).collect();
+    let trimmed = truncated
+        .trim_start_matches([' ', '-'])
+        .trim_end_matches(['.', ' ']);
+
+    if trimmed.is_empty() {
+        return FALLBACK_FILENAME.to_string();
+    }
+
+    let stem = trimmed.split('.').next().unwrap_or_default();
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        return format!("_{}", trimmed);
+    }
+
+    trimmed.to_string()
+}
+
+pub fn is_emoji_component(c: char) -> bool {
+    matches!(
+        c as u32,
+        // Pictographs, emoticons, transport, flags (regional indicators), skin tones
+        0x1F000..=0x1FAFF
+            // Misc technical (⌚, ⏰), arrows, misc symbols and dingbats (☀, ✅, ❤)
+            | 0x2190..=0x21FF
+            | 0x2300..=0x23FF
+            | 0x2600..=0x27BF
+            | 0x2B00..=0x2BFF
+            | 0x3030
+            | 0x303D
+            | 0x3297
+            | 0x3299
+            // Zero width joiner, keycap, variation selectors, tags
+            | 0x200D
+            | 0x20E3
+            | 0xFE00..=0xFE0F
+            | 0xE0020..=0xE007F
+    )
+}
+
+/// File name for exporting a meeting, based on its display name
+#[tauri::command]
+pub async fn suggest_export_file_name(
+    app: AppHandle,
+    meeting_id: &str,
+    extension: &str,
+) -> Result<String, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    let name = read_meeting_metadata(&meeting_dir)
+        .await?
+        .and_then(|metadata| metadata.name)
+        .unwrap_or_else(|| meeting_id.to_string());
+
+    Ok(format!(
+        "{}.{}",
+        sanitize_filename(&name),
+        extension.trim_start_matches('.')
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_name_is_unchanged() {
+        assert_eq!(sanitize_filename("Weekly Sync 2024"), "Weekly Sync 2024");
+        assert_eq!(
+            sanitize_filename("Übergabe Änderungen"),
+            "Übergabe Änderungen"
+        );
+    }
+
+    #[test]
+    fn test_strips_title_emoji() {
+        assert_eq!(sanitize_filename("📅 Weekly Planning"), "Weekly Planning");
+        assert_eq!(sanitize_filename("🚀 Launch ✅ Review"), "Launch Review");
+    }
+
+    #[test]
+    fn test_strips_zwj_sequences() {
+        // Family: man, ZWJ, woman, ZWJ, girl
+        assert_eq!(
+            sanitize_filename("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} Familienrat"),
+            "Familienrat"
+        );
+        // Technologist with skin tone: person, skin tone, ZWJ, laptop
+        assert_eq!(
+            sanitize_filename("Dev \u{1F9D1}\u{1F3FD}\u{200D}\u{1F4BB} Sync"),
+            "Dev Sync"
+        );
+    }
+
+    #[test]
+    fn test_strips_variation_selectors_and_flags() {
+        assert_eq!(sanitize_filename("\u{2764}\u{FE0F} Team"), "Team");
+        assert_eq!(sanitize_filename("\u{1F1E9}\u{1F1EA} Kickoff"), "Kickoff");
+        assert_eq!(sanitize_filename("1\u{FE0F}\u{20E3} Eins"), "1 Eins");
+    }
+
+    #[test]
+    fn test_forbidden_characters() {
+        assert_eq!(sanitize_filename("Q3: Budget"), "Q3 - Budget");
+        assert_eq!(sanitize_filename("Budget 2024/25"), "Budget 2024-25");
+        assert_eq!(
+            sanitize_filename("Was \"kostet\" <das>?*"),
+            "Was kostet das"
+        );
+    }
+
+    #[test]
+    fn test_trailing_dots_and_spaces() {
+        assert_eq!(sanitize_filename("Meeting... "), "Meeting");
+        assert_eq!(sanitize_filename("Notes 🎉."), "Notes");
+    }
+
+    #[test]
+    fn test_reserved_and_empty_names() {
+        assert_eq!(sanitize_filename("CON"), "_CON");
+        assert_eq!(sanitize_filename("nul.txt"), "_nul.txt");
+        assert_eq!(sanitize_filename("🎉🎉"), "meeting");
+        assert_eq!(sanitize_filename("..."), "meeting");
+    }
+
+    #[test]
+    fn test_long_names_are_truncated() {
+        let name = "a".repeat(300);
+        assert_eq!(sanitize_filename(&name).chars().count(), MAX_FILENAME_CHARS);
+    }
+}
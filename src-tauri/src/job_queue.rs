@@ -0,0 +1,311 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{oneshot, Mutex, MutexGuard, Notify};
+
+use crate::error::ClaimError;
+use crate::jobs::JobKind;
+use crate::AppState;
+
+/// Queued jobs are also checked this often, in case a slot was freed without a notification
+const DISPATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A transcription or summarization waiting for its slot
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueuedJob {
+    pub id: String,
+    pub kind: JobKind,
+    pub meeting_id: String,
+    pub queued_at: String,
+}
+
+struct PendingJob {
+    job: QueuedJob,
+    /// Tells the waiting command to claim its slot. Dropping it cancels the job.
+    start: oneshot::Sender<()>,
+}
+
+/// Jobs that were requested while their slot was taken, oldest first
+#[derive(Default)]
+pub struct JobQueue {
+    pending: VecDeque<PendingJob>,
+    next_id: u64,
+    notify: Arc<Notify>,
+}
+
+impl JobQueue {
+    /// Add a job, at the front for one that lost its slot again right after it was started
+    fn enqueue(
+        &mut self,
+        kind: JobKind,
+        meeting_id: &str,
+        front: bool,
+    ) -> (String, oneshot::Receiver<()>) {
+        self.next_id += 1;
+        let id = format!("queued-{}", self.next_id);
+        let (start, started) = oneshot::channel();
+        let pending = PendingJob {
+            job: QueuedJob {
+                id: id.clone(),
+                kind,
+                meeting_id: meeting_id.to_string(),
+                queued_at: Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            },
+            start,
+        };
+        if front {
+            self.pending.push_front(pending);
+        } else {
+            self.pending.push_back(pending);
+        }
+        (id, started)
+    }
+
+    /// Forget jobs whose command is gone, e.g. because its window was closed
+    fn prune(&mut self) {
+        self.pending.retain(|pending| !pending.start.is_closed());
+    }
+
+    pub fn jobs(&self) -> Vec<QueuedJob> {
+        self.pending
+            .iter()
+            .filter(|pending| !pending.start.is_closed())
+            .map(|pending| pending.job.clone())
+            .collect()
+    }
+
+    pub fn is_queued(&self, kind: JobKind, meeting_id: &str) -> bool {
+        self.jobs()
+            .iter()
+            .any(|job| job.kind == kind && job.meeting_id == meeting_id)
+    }
+
+    /// Remove a job, its command returns `ClaimError::Cancelled`
+    pub fn cancel(&mut self, job_id: &str) -> bool {
+        let count = self.pending.len();
+        self.pending.retain(|pending| pending.job.id != job_id);
+        self.pending.len() != count
+    }
+
+    /// Let the first job of each kind that `can_start` allows claim its slot
+    pub fn start_ready(&mut self, can_start: impl Fn(JobKind, &str) -> bool) -> Vec<QueuedJob> {
+        self.prune();
+        let mut started: Vec<QueuedJob> = Vec::new();
+        let mut index = 0;
+        while index < self.pending.len() {
+            let job = &self.pending[index].job;
+            let kind_started = started.iter().any(|started| started.kind == job.kind);
+            if kind_started || !can_start(job.kind, &job.meeting_id) {
+                index += 1;
+                continue;
+            }
+            let pending = self.pending.remove(index).expect("index is in bounds");
+            if pending.start.send(()).is_ok() {
+                started.push(pending.job);
+            }
+        }
+        started
+    }
+
+    /// Check the queue now instead of at the next interval
+    pub fn notify(&self) {
+        self.notify.notify_one();
+    }
+}
+
+/// Claim the transcription or summarization slot for `meeting_id`. While it
+/// is taken the job waits in the queue, the state lock is held again once it
+/// returns. A job that is already running or queued for the meeting is refused.
+pub async fn claim_or_queue<'a>(
+    state: &'a Mutex<AppState>,
+    kind: JobKind,
+    meeting_id: &str,
+) -> Result<MutexGuard<'a, AppState>, ClaimError> {
+    let mut woken = false;
+    loop {
+        let mut state_lock = state.lock().await;
+        let claimed = match kind {
+            JobKind::Summarization => state_lock.claim_summarization(meeting_id),
+            _ => state_lock.claim_transcription(meeting_id),
+        };
+        let busy = match claimed {
+            Ok(()) => return Ok(state_lock),
+            Err(busy) => busy,
+        };
+
+        if !woken
+            && (state_lock.is_running(kind, meeting_id)
+                || state_lock.job_queue.is_queued(kind, meeting_id))
+        {
+            return Err(ClaimError::Busy(busy));
+        }
+        let (job_id, started) = state_lock.job_queue.enqueue(kind, meeting_id, woken);
+        println!(
+            "Queued {:?} of {} as {}: {}",
+            kind, meeting_id, job_id, busy
+        );
+        drop(state_lock);
+
+        if started.await.is_err() {
+            println!("Queued job {} was cancelled", job_id);
+            return Err(ClaimError::Cancelled);
+        }
+        woken = true;
+    }
+}
+
+/// Start queued jobs whenever a slot is freed
+pub async fn run_dispatcher(app: AppHandle) {
+    let notify = {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        state.job_queue.notify.clone()
+    };
+
+    loop {
+        tokio::select! {
+            _ = notify.notified() => {}
+            _ = tokio::time::sleep(DISPATCH_INTERVAL) => {}
+        }
+        let state = app.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+        if state.jobs.is_shutting_down() {
+            return;
+        }
+        state.start_queued_jobs();
+    }
+}
+
+#[tauri::command]
+pub async fn get_job_queue(app: AppHandle) -> Result<Vec<QueuedJob>, String> {
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().await;
+    Ok(state.job_queue.jobs())
+}
+
+#[tauri::command]
+pub async fn cancel_queued_job(app: AppHandle, job_id: &str) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    if state.job_queue.cancel(job_id) {
+        Ok(())
+    } else {
+        Err(format!("No queued job {}", job_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BusyError;
+
+    #[tokio::test]
+    async fn test_second_transcription_waits_for_the_first() {
+        let state = Arc::new(Mutex::new(AppState::default()));
+        state
+            .lock()
+            .await
+            .claim_transcription("recording-1")
+            .unwrap();
+
+        let queued = tokio::spawn({
+            let state = state.clone();
+            async move {
+                let mut state_lock = claim_or_queue(&state, JobKind::Transcription, "recording-2")
+                    .await
+                    .unwrap();
+                let transcribing = state_lock.currently_transcribing.clone();
+                state_lock.release_transcription();
+                transcribing
+            }
+        });
+        while state.lock().await.job_queue.jobs().is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        {
+            let mut state_lock = state.lock().await;
+            let jobs = state_lock.job_queue.jobs();
+            assert_eq!(jobs.len(), 1);
+            assert_eq!(jobs[0].meeting_id, "recording-2");
+            // Still running, nothing to start
+            state_lock.start_queued_jobs();
+            assert_eq!(state_lock.job_queue.jobs().len(), 1);
+
+            state_lock.release_transcription();
+            state_lock.start_queued_jobs();
+            assert!(state_lock.job_queue.jobs().is_empty());
+        }
+
+        assert_eq!(queued.await.unwrap().as_deref(), Some("recording-2"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_are_refused_and_queued_jobs_cancelled() {
+        let state = Arc::new(Mutex::new(AppState::default()));
+        state
+            .lock()
+            .await
+            .claim_summarization("recording-1")
+            .unwrap();
+
+        let error = claim_or_queue(&state, JobKind::Summarization, "recording-1")
+            .await
+            .err();
+        assert_eq!(
+            error,
+            Some(ClaimError::Busy(BusyError::SummarizationRunning {
+                meeting_id: "recording-1".to_string()
+            }))
+        );
+
+        let queued = tokio::spawn({
+            let state = state.clone();
+            async move {
+                claim_or_queue(&state, JobKind::Summarization, "recording-2")
+                    .await
+                    .err()
+            }
+        });
+        while state.lock().await.job_queue.jobs().is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let job_id = {
+            let state_lock = state.lock().await;
+            assert!(state_lock
+                .job_queue
+                .is_queued(JobKind::Summarization, "recording-2"));
+            state_lock.job_queue.jobs()[0].id.clone()
+        };
+        assert!(state.lock().await.job_queue.cancel(&job_id));
+        assert_eq!(queued.await.unwrap(), Some(ClaimError::Cancelled));
+        assert!(!state.lock().await.job_queue.cancel(&job_id));
+    }
+
+    #[test]
+    fn test_one_job_per_kind_is_started() {
+        let mut queue = JobQueue::default();
+        let (_, _first) = queue.enqueue(JobKind::Transcription, "recording-1", false);
+        let (_, _second) = queue.enqueue(JobKind::Transcription, "recording-2", false);
+        let (_, _summary) = queue.enqueue(JobKind::Summarization, "recording-1", false);
+        let (_, dropped) = queue.enqueue(JobKind::Summarization, "recording-3", true);
+        drop(dropped);
+
+        // recording-1 is blocked for summaries while it is transcribed
+        let started = queue.start_ready(|kind, meeting_id| {
+            !(kind == JobKind::Summarization && meeting_id == "recording-1")
+        });
+        let started: Vec<_> = started
+            .iter()
+            .map(|job| (job.kind, job.meeting_id.as_str()))
+            .collect();
+        assert_eq!(started, vec![(JobKind::Transcription, "recording-1")]);
+
+        let remaining: Vec<_> = queue.jobs().into_iter().map(|job| job.meeting_id).collect();
+        assert_eq!(remaining, vec!["recording-2", "recording-1"]);
+    }
+}
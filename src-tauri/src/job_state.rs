@@ -0,0 +1,358 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::jobs::JobKind;
+use crate::llm::file_manager::mark_run_cancelled;
+use crate::meeting::write_atomic;
+
+/// Progress of the job running on a meeting, only left behind by a crash
+const JOB_STATE_FILE_NAME: &str = "job_state.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStage {
+    /// Transcribing audio chunks or summarizing text chunks
+    Chunks,
+    /// Merging the chunk transcripts or writing the final summary
+    Combining,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStateStatus {
+    Running,
+    /// The process running the job is gone, the job can be resumed
+    Interrupted,
+}
+
+/// Mirrored to `job_state.json` while a chunked transcription or
+/// summarization runs, so a half-processed meeting is known after a crash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetingJobState {
+    pub kind: JobKind,
+    pub stage: JobStage,
+    pub status: JobStateStatus,
+    pub completed_chunks: usize,
+    pub total_chunks: usize,
+    pub started_at: String,
+    pub updated_at: String,
+    /// The summarization run whose chunk summaries a resume picks up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    /// Process running the job, a running state without it is stale
+    pub pid: u32,
+    /// When that process started, tells it apart from a later one reusing the pid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_started_at: Option<String>,
+}
+
+fn now() -> String {
+    Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+pub async fn read_job_state(meeting_dir: &Path) -> Option<MeetingJobState> {
+    let json = fs::read_to_string(meeting_dir.join(JOB_STATE_FILE_NAME))
+        .await
+        .ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+async fn write_job_state(meeting_dir: &Path, state: &MeetingJobState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    write_atomic(&meeting_dir.join(JOB_STATE_FILE_NAME), json)
+        .await
+        .map_err(|e| format!("Failed to write job state: {}", e))
+}
+
+/// Keeps `job_state.json` up to date for one run. Failing to write it never
+/// fails the run.
+pub struct JobStateFile {
+    meeting_dir: PathBuf,
+    state: MeetingJobState,
+}
+
+impl JobStateFile {
+    /// Replaces the state of an earlier, interrupted run
+    pub async fn start(meeting_dir: &Path, kind: JobKind, total_chunks: usize) -> Self {
+        let started_at = now();
+        let file = Self {
+            meeting_dir: meeting_dir.to_path_buf(),
+            state: MeetingJobState {
+                kind,
+                stage: JobStage::Chunks,
+                status: JobStateStatus::Running,
+                completed_chunks: 0,
+                total_chunks,
+                updated_at: started_at.clone(),
+                started_at,
+                run_id: None,
+                pid: std::process::id(),
+                process_started_at: own_start_time(),
+            },
+        };
+        file.save().await;
+        file
+    }
+
+    pub async fn set_run_id(&mut self, run_id: &str) {
+        self.state.run_id = Some(run_id.to_string());
+        self.save().await;
+    }
+
+    /// One more chunk is done, whether it was reused, processed or failed
+    pub async fn complete_chunk(&mut self) {
+        self.state.completed_chunks =
+            (self.state.completed_chunks + 1).min(self.state.total_chunks);
+        self.save().await;
+    }
+
+    pub async fn set_stage(&mut self, stage: JobStage) {
+        self.state.stage = stage;
+        self.save().await;
+    }
+
+    /// Remove the state once the run returns, however it ended
+    pub async fn finish(self) {
+        match fs::remove_file(self.meeting_dir.join(JOB_STATE_FILE_NAME)).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => println!("Warning: Failed to remove job state: {}", e),
+        }
+    }
+
+    async fn save(&self) {
+        let mut state = self.state.clone();
+        state.updated_at = now();
+        if let Err(e) = write_job_state(&self.meeting_dir, &state).await {
+            println!("Warning: {}", e);
+        }
+    }
+}
+
+/// When the process with `pid` started, `None` if there is no such process
+#[cfg(unix)]
+fn process_start_time(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "lstart=", "-p", &pid.to_string()])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let started_at = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !started_at.is_empty()).then_some(started_at)
+}
+
+#[cfg(windows)]
+fn process_start_time(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-Process -Id {}).StartTime.ToString('o')", pid),
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let started_at = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !started_at.is_empty()).then_some(started_at)
+}
+
+fn own_start_time() -> Option<String> {
+    static STARTED_AT: OnceLock<Option<String>> = OnceLock::new();
+    STARTED_AT
+        .get_or_init(|| process_start_time(std::process::id()))
+        .clone()
+}
+
+/// Whether a running state belongs to a process that is gone. This process
+/// only just started, a state with its pid is left by an earlier one. A live
+/// process with the pid but another start time reused it after a crash.
+fn is_stale(state: &MeetingJobState, start_time_of: impl Fn(u32) -> Option<String>) -> bool {
+    if state.status != JobStateStatus::Running {
+        return false;
+    }
+    if state.pid == std::process::id() {
+        return true;
+    }
+    match (start_time_of(state.pid), &state.process_started_at) {
+        (None, _) => true,
+        (Some(started_at), Some(recorded)) => started_at != *recorded,
+        // Written before the start time was recorded, the pid is all there is
+        (Some(_), None) => false,
+    }
+}
+
+/// Mark the running states of dead processes as interrupted. Their
+/// summarization run is marked cancelled, so summarizing again resumes it.
+/// Returns the meetings that were interrupted.
+async fn recover_job_states(
+    uploads: &Path,
+    start_time_of: impl Fn(u32) -> Option<String>,
+) -> Result<Vec<String>, String> {
+    let mut entries = match fs::read_dir(uploads).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut interrupted = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let meeting_dir = entry.path();
+        let Some(mut state) = read_job_state(&meeting_dir).await else {
+            continue;
+        };
+        if !is_stale(&state, &start_time_of) {
+            continue;
+        }
+
+        if let (JobKind::Summarization, Some(run_id)) = (state.kind, &state.run_id) {
+            if let Err(e) = mark_run_cancelled(&meeting_dir, run_id).await {
+                println!("Warning: {}", e);
+            }
+        }
+        state.status = JobStateStatus::Interrupted;
+        state.updated_at = now();
+        write_job_state(&meeting_dir, &state).await?;
+        interrupted.push(entry.file_name().to_string_lossy().into_owned());
+    }
+    Ok(interrupted)
+}
+
+/// Called once at startup, before any job can start
+pub async fn recover_job_states_on_startup(app: AppHandle) -> Result<(), String> {
+    let uploads = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join("uploads");
+
+    let interrupted = recover_job_states(&uploads, process_start_time).await?;
+    if !interrupted.is_empty() {
+        println!("Jobs of {:?} were interrupted", interrupted);
+    }
+    Ok(())
+}
+
+/// Progress of the meeting's running or interrupted job, `None` if there is none
+#[tauri::command]
+pub async fn get_meeting_job_state(
+    app: AppHandle,
+    meeting_id: &str,
+) -> Result<Option<MeetingJobState>, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    Ok(read_job_state(&meeting_dir).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::file_manager::{
+        resume_cancelled_run, save_run_manifest, start_summarization_run, RunManifest,
+    };
+
+    #[tokio::test]
+    async fn test_job_state_follows_the_run() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut file = JobStateFile::start(dir.path(), JobKind::Transcription, 2).await;
+        file.complete_chunk().await;
+        let state = read_job_state(dir.path()).await.unwrap();
+        assert_eq!(state.kind, JobKind::Transcription);
+        assert_eq!(state.stage, JobStage::Chunks);
+        assert_eq!(state.status, JobStateStatus::Running);
+        assert_eq!((state.completed_chunks, state.total_chunks), (1, 2));
+        assert_eq!(state.pid, std::process::id());
+        assert_eq!(state.process_started_at, own_start_time());
+
+        file.complete_chunk().await;
+        file.set_stage(JobStage::Combining).await;
+        let state = read_job_state(dir.path()).await.unwrap();
+        assert_eq!(state.stage, JobStage::Combining);
+        assert_eq!(state.completed_chunks, 2);
+
+        file.finish().await;
+        assert!(read_job_state(dir.path()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_states_of_dead_processes_are_interrupted() {
+        let uploads = tempfile::tempdir().unwrap();
+        let crashed = uploads.path().join("recording-1");
+        let running = uploads.path().join("recording-2");
+        std::fs::create_dir_all(&crashed).unwrap();
+        std::fs::create_dir_all(&running).unwrap();
+        std::fs::create_dir_all(uploads.path().join("recording-3")).unwrap();
+
+        let reused = uploads.path().join("recording-4");
+        std::fs::create_dir_all(&reused).unwrap();
+
+        for (meeting_dir, pid) in [(&crashed, 1001), (&running, 1002), (&reused, 1003)] {
+            let file = JobStateFile::start(meeting_dir, JobKind::Transcription, 3).await;
+            let mut state = file.state.clone();
+            state.pid = pid;
+            state.process_started_at = Some("Mon Oct 12 09:00:00 2026".to_string());
+            write_job_state(meeting_dir, &state).await.unwrap();
+        }
+
+        // 1003 belongs to a process started after the one that wrote the state
+        let mut interrupted = recover_job_states(uploads.path(), |pid| match pid {
+            1002 => Some("Mon Oct 12 09:00:00 2026".to_string()),
+            1003 => Some("Tue Oct 13 10:00:00 2026".to_string()),
+            _ => None,
+        })
+        .await
+        .unwrap();
+
+        interrupted.sort();
+        assert_eq!(interrupted, ["recording-1", "recording-4"]);
+        let state = read_job_state(&crashed).await.unwrap();
+        assert_eq!(state.status, JobStateStatus::Interrupted);
+        assert_eq!(state.total_chunks, 3);
+        let state = read_job_state(&running).await.unwrap();
+        assert_eq!(state.status, JobStateStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_summarization_can_be_resumed() {
+        let uploads = tempfile::tempdir().unwrap();
+        let meeting_dir = uploads.path().join("recording-1");
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        let run_id = start_summarization_run(&meeting_dir).await.unwrap();
+        let manifest = RunManifest {
+            transcript_hash: "hash".to_string(),
+            chunk_size: 4000,
+            chunk_count: 3,
+        };
+        save_run_manifest(&meeting_dir, &run_id, &manifest)
+            .await
+            .unwrap();
+
+        let mut file = JobStateFile::start(&meeting_dir, JobKind::Summarization, 3).await;
+        file.set_run_id(&run_id).await;
+        drop(file);
+        assert_eq!(resume_cancelled_run(&meeting_dir, &manifest).await, None);
+
+        // The pid of this process, as if it was reused after a crash
+        recover_job_states(uploads.path(), |_| own_start_time())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_job_state(&meeting_dir).await.unwrap().status,
+            JobStateStatus::Interrupted
+        );
+        assert_eq!(
+            resume_cancelled_run(&meeting_dir, &manifest).await,
+            Some(run_id)
+        );
+    }
+}
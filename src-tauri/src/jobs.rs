@@ -0,0 +1,588 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Output, Stdio};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncReadExt;
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+
+use crate::llm::progress::ProgressEvents;
+use crate::settings::load_settings;
+use crate::AppState;
+
+const JOBS_FILE_NAME: &str = "jobs.json";
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// How long killed processes get to actually exit before the app quits anyway
+const KILL_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Transcription,
+    Summarization,
+    /// Realigning the timestamps of an existing transcript
+    Alignment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    /// The app quit (or crashed) before the job finished
+    Interrupted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub kind: JobKind,
+    pub meeting_id: String,
+    pub started_at: String,
+    pub status: JobStatus,
+}
+
+/// A child process that can be killed when the app exits
+pub trait ChildHandle: Send {
+    fn kill(&mut self) -> std::io::Result<()>;
+    fn has_exited(&mut self) -> bool;
+}
+
+impl ChildHandle for tokio::process::Child {
+    fn kill(&mut self) -> std::io::Result<()> {
+        kill_child(self)
+    }
+
+    fn has_exited(&mut self) -> bool {
+        !matches!(self.try_wait(), Ok(None))
+    }
+}
+
+/// Long running jobs and the child processes they spawned.
+///
+/// Jobs are mirrored to `jobs.json` while they run, so a job that never
+/// finished shows up as interrupted on the next start, even after a crash.
+#[derive(Default)]
+pub struct JobRegistry {
+    records: Vec<JobRecord>,
+    children: HashMap<u64, Arc<StdMutex<dyn ChildHandle>>>,
+    next_child_id: u64,
+    shutting_down: bool,
+    /// `None` keeps the jobs in memory only
+    state_path: Option<PathBuf>,
+    /// Where `job-started` and `job-finished` are sent
+    events: Option<ProgressEvents>,
+}
+
+/// Sent as `job-started` and `job-finished`
+#[derive(Debug, Clone, Serialize)]
+struct JobEvent<'a> {
+    job_id: &'a str,
+    kind: JobKind,
+    meeting_id: &'a str,
+}
+
+impl JobRegistry {
+    /// Load the jobs left over from the last run. None of them is running anymore.
+    pub fn load(state_path: PathBuf) -> Self {
+        let mut records: Vec<JobRecord> = std::fs::read_to_string(&state_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        for record in &mut records {
+            record.status = JobStatus::Interrupted;
+        }
+
+        let registry = Self {
+            records,
+            state_path: Some(state_path),
+            ..Default::default()
+        };
+        registry.persist();
+        registry
+    }
+
+    pub fn set_events(&mut self, events: ProgressEvents) {
+        self.events = Some(events);
+    }
+
+    fn emit(&self, event: &str, record: &JobRecord) {
+        if let Some(events) = &self.events {
+            events.emit(
+                event,
+                JobEvent {
+                    job_id: &record.id,
+                    kind: record.kind,
+                    meeting_id: &record.meeting_id,
+                },
+            );
+        }
+    }
+
+    pub fn start_job(&mut self, kind: JobKind, meeting_id: &str) -> String {
+        // A new run replaces the interrupted one
+        self.records
+            .retain(|record| !(record.kind == kind && record.meeting_id == meeting_id));
+
+        let now = Utc::now();
+        let id = format!(
+            "{}-{}-{}",
+            serde_json::to_value(kind)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            meeting_id,
+            now.timestamp_millis()
+        );
+        self.records.push(JobRecord {
+            id: id.clone(),
+            kind,
+            meeting_id: meeting_id.to_string(),
+            started_at: now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            status: JobStatus::Running,
+        });
+        self.persist();
+        if let Some(record) = self.records.last() {
+            self.emit("job-started", record);
+        }
+        id
+    }
+
+    /// Remove a job once it is done. Jobs interrupted by the exit handler are
+    /// kept, even if they return afterwards because their process was killed.
+    pub fn finish_job(&mut self, job_id: &str) {
+        let Some(index) = self
+            .records
+            .iter()
+            .position(|record| record.id == job_id && record.status == JobStatus::Running)
+        else {
+            return;
+        };
+        let record = self.records.remove(index);
+        self.persist();
+        self.emit("job-finished", &record);
+    }
+
+    pub fn has_job(&self, job_id: &str) -> bool {
+        self.records.iter().any(|record| record.id == job_id)
+    }
+
+    pub fn incomplete_jobs(&self) -> Vec<JobRecord> {
+        self.records
+            .iter()
+            .filter(|record| record.status == JobStatus::Interrupted)
+            .cloned()
+            .collect()
+    }
+
+    pub fn track_child(&mut self, child: Arc<StdMutex<dyn ChildHandle>>) -> u64 {
+        self.next_child_id += 1;
+        self.children.insert(self.next_child_id, child);
+        self.next_child_id
+    }
+
+    pub fn untrack_child(&mut self, child_id: u64) {
+        self.children.remove(&child_id);
+    }
+
+    /// True while any tracked child process is still alive
+    pub fn has_running_children(&self) -> bool {
+        self.children
+            .values()
+            .any(|child| !child.lock().unwrap_or_else(|e| e.into_inner()).has_exited())
+    }
+
+    pub fn has_running_jobs(&self) -> bool {
+        self.records
+            .iter()
+            .any(|record| record.status == JobStatus::Running)
+            || !self.children.is_empty()
+    }
+
+    pub fn has_running_job_for(&self, meeting_id: &str) -> bool {
+        self.records
+            .iter()
+            .any(|record| record.status == JobStatus::Running && record.meeting_id == meeting_id)
+    }
+
+    pub fn has_running_job_of(&self, kind: JobKind, meeting_id: &str) -> bool {
+        self.records.iter().any(|record| {
+            record.status == JobStatus::Running
+                && record.kind == kind
+                && record.meeting_id == meeting_id
+        })
+    }
+
+    /// Jobs check this to stop early instead of starting more work
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down
+    }
+
+    pub fn begin_shutdown(&mut self) {
+        self.shutting_down = true;
+    }
+
+    /// Kill every tracked child and mark running jobs as interrupted.
+    /// Returns the number of processes that were killed.
+    pub fn interrupt_all(&mut self) -> usize {
+        let mut killed = 0;
+        for child in self.children.values() {
+            let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+            if child.has_exited() {
+                continue;
+            }
+            match child.kill() {
+                Ok(()) => killed += 1,
+                Err(e) => println!("Warning: Failed to kill child process: {}", e),
+            }
+        }
+
+        for record in &mut self.records {
+            record.status = JobStatus::Interrupted;
+        }
+        self.persist();
+
+        killed
+    }
+
+    fn persist(&self) {
+        let Some(path) = &self.state_path else {
+            return;
+        };
+
+        let result = serde_json::to_string_pretty(&self.records)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            println!("Warning: Failed to write job state: {}", e);
+        }
+    }
+}
+
+pub fn get_jobs_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(JOBS_FILE_NAME)
+}
+
+pub async fn start_job(app: &AppHandle, kind: JobKind, meeting_id: &str) -> String {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    state.jobs.start_job(kind, meeting_id)
+}
+
+pub async fn finish_job(app: &AppHandle, job_id: &str) {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    state.jobs.finish_job(job_id);
+}
+
+pub async fn is_shutting_down(app: &AppHandle) -> bool {
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().await;
+    state.jobs.is_shutting_down()
+}
+
+/// Put the child into a process group of its own, so `kill_child` reaches the
+/// processes it starts as well, e.g. the Python process behind `uv run`
+pub fn own_process_group(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    command.process_group(0);
+    command
+}
+
+/// Kill the child, and everything else in its process group if it leads one
+fn kill_child(child: &mut Child) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: killpg only sends a signal. Without a group of its own the
+        // call fails, nothing else can have the group id of a live child.
+        if unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) } == 0 {
+            return Ok(());
+        }
+    }
+    child.start_kill()
+}
+
+/// Wait for a spawned child while it is tracked, so it is killed when the
+/// app exits instead of being orphaned
+pub async fn wait_tracked(app: &AppHandle, child: Child) -> std::io::Result<ExitStatus> {
+    wait_tracked_unless(app, child, std::future::pending())
+        .await
+        .map(|status| status.expect("a pending future never stops the child"))
+}
+
+/// Like `wait_tracked`, but the child is killed when `stop` completes first.
+/// Returns `None` in that case.
+pub async fn wait_tracked_unless(
+    app: &AppHandle,
+    child: Child,
+    stop: impl Future<Output = ()>,
+) -> std::io::Result<Option<ExitStatus>> {
+    let child = Arc::new(StdMutex::new(child));
+    let child_id = {
+        let state = app.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+        state.jobs.track_child(child.clone())
+    };
+
+    tokio::pin!(stop);
+    let status = loop {
+        let status = child.lock().unwrap_or_else(|e| e.into_inner()).try_wait();
+        match status {
+            Ok(Some(status)) => break Ok(Some(status)),
+            Ok(None) => {}
+            Err(e) => break Err(e),
+        }
+
+        tokio::select! {
+            _ = &mut stop => {
+                let killed = kill_child(&mut child.lock().unwrap_or_else(|e| e.into_inner()));
+                break killed.map(|()| None);
+            }
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    };
+
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    state.jobs.untrack_child(child_id);
+
+    status
+}
+
+/// Like `Command::output`, but the child is killed when the app exits
+pub async fn tracked_output(app: &AppHandle, command: &mut Command) -> std::io::Result<Output> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let mut stdout = child.stdout.take();
+    let mut stderr = child.stderr.take();
+    let stdout_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(stdout) = stdout.as_mut() {
+            stdout.read_to_end(&mut buffer).await?;
+        }
+        Ok::<_, std::io::Error>(buffer)
+    });
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(stderr) = stderr.as_mut() {
+            stderr.read_to_end(&mut buffer).await?;
+        }
+        Ok::<_, std::io::Error>(buffer)
+    });
+
+    let status = wait_tracked(app, child).await;
+
+    let stdout = stdout_task.await.map_err(std::io::Error::other)??;
+    let stderr = stderr_task.await.map_err(std::io::Error::other)??;
+    Ok(Output {
+        status: status?,
+        stdout,
+        stderr,
+    })
+}
+
+/// Stop running jobs before the app exits: let child processes finish within
+/// the grace period, then kill them and record the jobs as interrupted.
+pub async fn shutdown(app: &AppHandle) {
+    let grace_period = load_settings(app)
+        .await
+        .map(|settings| settings.shutdown_grace_period_seconds)
+        .unwrap_or_default();
+    let state = app.state::<Mutex<AppState>>();
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(grace_period);
+    while tokio::time::Instant::now() < deadline {
+        if !state.lock().await.jobs.has_running_children() {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    let killed = state.lock().await.jobs.interrupt_all();
+    println!("Stopped {} child processes before exit", killed);
+
+    let deadline = tokio::time::Instant::now() + KILL_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        if !state.lock().await.jobs.has_running_children() {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Jobs that were interrupted by an app exit or crash
+#[tauri::command]
+pub async fn get_incomplete_jobs(app: AppHandle) -> Result<Vec<JobRecord>, String> {
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().await;
+    Ok(state.jobs.incomplete_jobs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeChild {
+        exited: bool,
+        killed: Arc<StdMutex<bool>>,
+    }
+
+    impl ChildHandle for FakeChild {
+        fn kill(&mut self) -> std::io::Result<()> {
+            *self.killed.lock().unwrap() = true;
+            self.exited = true;
+            Ok(())
+        }
+
+        fn has_exited(&mut self) -> bool {
+            self.exited
+        }
+    }
+
+    fn fake_child(exited: bool) -> (Arc<StdMutex<FakeChild>>, Arc<StdMutex<bool>>) {
+        let killed = Arc::new(StdMutex::new(false));
+        let child = Arc::new(StdMutex::new(FakeChild {
+            exited,
+            killed: killed.clone(),
+        }));
+        (child, killed)
+    }
+
+    #[test]
+    fn test_finished_jobs_are_removed() {
+        let mut registry = JobRegistry::default();
+
+        let job_id = registry.start_job(JobKind::Transcription, "recording-1");
+        assert!(registry.has_running_jobs());
+        assert!(registry.has_running_job_for("recording-1"));
+        assert!(!registry.has_running_job_for("recording-2"));
+
+        registry.finish_job(&job_id);
+        assert!(!registry.has_running_jobs());
+        assert!(registry.incomplete_jobs().is_empty());
+    }
+
+    #[test]
+    fn test_interrupt_kills_running_children() {
+        let mut registry = JobRegistry::default();
+        registry.start_job(JobKind::Transcription, "recording-1");
+        let (running, running_killed) = fake_child(false);
+        let (exited, exited_killed) = fake_child(true);
+        registry.track_child(running);
+        registry.track_child(exited);
+        assert!(registry.has_running_children());
+
+        assert_eq!(registry.interrupt_all(), 1);
+
+        assert!(*running_killed.lock().unwrap());
+        assert!(!*exited_killed.lock().unwrap());
+        assert!(!registry.has_running_children());
+        assert_eq!(registry.incomplete_jobs().len(), 1);
+        assert_eq!(registry.incomplete_jobs()[0].status, JobStatus::Interrupted);
+    }
+
+    #[test]
+    fn test_interrupted_jobs_are_kept_when_finished() {
+        let mut registry = JobRegistry::default();
+        let job_id = registry.start_job(JobKind::Transcription, "recording-1");
+
+        registry.interrupt_all();
+        registry.finish_job(&job_id);
+
+        assert_eq!(registry.incomplete_jobs().len(), 1);
+    }
+
+    #[test]
+    fn test_untracked_children_are_not_killed() {
+        let mut registry = JobRegistry::default();
+        let (child, killed) = fake_child(false);
+        let child_id = registry.track_child(child);
+
+        registry.untrack_child(child_id);
+        registry.interrupt_all();
+
+        assert!(!*killed.lock().unwrap());
+    }
+
+    #[test]
+    fn test_interrupted_jobs_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = get_jobs_path(dir.path());
+
+        let mut registry = JobRegistry::load(path.clone());
+        registry.start_job(JobKind::Transcription, "recording-1");
+        let finished = registry.start_job(JobKind::Summarization, "recording-2");
+        registry.finish_job(&finished);
+        registry.interrupt_all();
+
+        let restarted = JobRegistry::load(path.clone());
+        let jobs = restarted.incomplete_jobs();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].meeting_id, "recording-1");
+        assert_eq!(jobs[0].kind, JobKind::Transcription);
+    }
+
+    #[test]
+    fn test_crashed_jobs_are_interrupted_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = get_jobs_path(dir.path());
+
+        // The app died without running the exit handler
+        let mut registry = JobRegistry::load(path.clone());
+        registry.start_job(JobKind::Summarization, "recording-1");
+        drop(registry);
+
+        let mut restarted = JobRegistry::load(path.clone());
+        assert_eq!(restarted.incomplete_jobs().len(), 1);
+        assert!(!restarted.has_running_jobs());
+
+        // Running it again replaces the interrupted job
+        let job_id = restarted.start_job(JobKind::Summarization, "recording-1");
+        restarted.finish_job(&job_id);
+        assert!(JobRegistry::load(path).incomplete_jobs().is_empty());
+    }
+
+    #[cfg(unix)]
+    fn is_running(pid: &str) -> bool {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "stat=", "-p", pid])
+            .output()
+            .unwrap();
+        // A killed orphan may stay a zombie until it is reaped
+        let stat = String::from_utf8_lossy(&output.stdout);
+        output.status.success() && !stat.trim().starts_with('Z')
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_kill_reaches_the_children_of_the_child() {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let mut command = Command::new("sh");
+        command
+            .args(["-c", "sleep 30 & echo $!; wait"])
+            .stdout(Stdio::piped());
+        let mut child = own_process_group(&mut command).spawn().unwrap();
+        let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+        let grandchild = lines.next_line().await.unwrap().unwrap();
+        assert!(is_running(&grandchild));
+
+        kill_child(&mut child).unwrap();
+        child.wait().await.unwrap();
+        for _ in 0..20 {
+            if !is_running(&grandchild) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("sleep {} outlived the killed shell", grandchild);
+    }
+}
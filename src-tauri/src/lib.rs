@@ -3,43 +3,348 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::UNIX_EPOCH;
 use tauri::ipc::Response;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, Runtime};
 use tokio::fs;
 use tokio::sync::Mutex;
 
+mod alignment;
+mod analytics;
+mod api_server;
 mod audio;
+mod audio_cleanup;
+mod bulk_export;
+mod cancellation;
+mod chunk_manifest;
+mod diagnostics;
+mod disk_space;
+mod error;
+mod export;
+mod filename;
+mod job_queue;
+mod job_state;
+mod jobs;
 mod llm;
 mod meeting;
+mod meeting_archive;
+mod model_cache;
+mod notes;
+mod onboarding;
+mod pdf;
+mod pipeline;
+mod python_env;
+mod search;
+mod settings;
+mod settings_bundle;
+mod speakers;
+mod tags;
+mod transcript;
+mod transcript_check;
+mod transcription_config;
+mod transcription_progress;
+mod trash;
+mod vtt;
+mod watchdog;
 mod whisperx;
+mod whisperx_stderr;
 
+use error::BusyError;
+use llm::partial::{list_partial_summaries_in, PartialSummaryInfo};
 use llm::LlmConfig;
 
 #[derive(Default)]
 struct AppState {
     currently_transcribing: Option<String>,
+    /// Stops the transcription in `currently_transcribing`, alignment has none
+    transcription_cancellation: Option<cancellation::CancellationToken>,
     currently_summarizing: Option<String>,
+    /// Stops the summarization in `currently_summarizing`
+    summarization_cancellation: Option<cancellation::CancellationToken>,
+    /// Times and tokens of the running summarization, kept after it ends
+    summarization_performance: Option<llm::performance::SharedPerformanceTracker>,
     llm_config: LlmConfig,
+    transcription_config: transcription_config::TranscriptionConfig,
+    jobs: jobs::JobRegistry,
+    /// Transcriptions and summarizations waiting for their slot
+    job_queue: job_queue::JobQueue,
+    api_server: Option<api_server::ApiServer>,
+    python_env_cache: python_env::EnvironmentInfoCache,
+    /// Models of the configured endpoint, see `llm::model_list`
+    llm_models: llm::model_list::ModelListCache,
+}
+
+impl AppState {
+    /// Take the transcription slot, also used by alignment. Refused while the
+    /// meeting is summarized, its transcript would change underneath the summary.
+    fn claim_transcription(&mut self, meeting_id: &str) -> Result<(), BusyError> {
+        self.check_transcription(meeting_id)?;
+        self.currently_transcribing = Some(meeting_id.to_string());
+        Ok(())
+    }
+
+    fn check_transcription(&self, meeting_id: &str) -> Result<(), BusyError> {
+        if let Some(running) = &self.currently_transcribing {
+            return Err(BusyError::TranscriptionRunning {
+                meeting_id: running.clone(),
+            });
+        }
+        if self.currently_summarizing.as_deref() == Some(meeting_id)
+            || self
+                .jobs
+                .has_running_job_of(jobs::JobKind::Summarization, meeting_id)
+        {
+            return Err(BusyError::MeetingSummarizing {
+                meeting_id: meeting_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Take the summarization slot. Refused while the meeting's transcript is
+    /// still being written.
+    fn claim_summarization(&mut self, meeting_id: &str) -> Result<(), BusyError> {
+        self.check_summarization(meeting_id)?;
+        self.currently_summarizing = Some(meeting_id.to_string());
+        Ok(())
+    }
+
+    fn check_summarization(&self, meeting_id: &str) -> Result<(), BusyError> {
+        if let Some(running) = &self.currently_summarizing {
+            return Err(BusyError::SummarizationRunning {
+                meeting_id: running.clone(),
+            });
+        }
+        if self.is_transcribing(meeting_id) {
+            return Err(BusyError::MeetingTranscribing {
+                meeting_id: meeting_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether the slot of `kind` is taken by the meeting
+    fn is_running(&self, kind: jobs::JobKind, meeting_id: &str) -> bool {
+        let slot = match kind {
+            jobs::JobKind::Summarization => &self.currently_summarizing,
+            _ => &self.currently_transcribing,
+        };
+        slot.as_deref() == Some(meeting_id)
+    }
+
+    /// Let queued jobs whose slot is free claim it
+    fn start_queued_jobs(&mut self) {
+        // The queue is taken out while the closure looks at the rest of the state
+        let mut queue = std::mem::take(&mut self.job_queue);
+        let started = queue.start_ready(|kind, meeting_id| match kind {
+            jobs::JobKind::Summarization => self.check_summarization(meeting_id).is_ok(),
+            _ => self.check_transcription(meeting_id).is_ok(),
+        });
+        self.job_queue = queue;
+        for job in started {
+            println!("Starting queued {:?} of {}", job.kind, job.meeting_id);
+        }
+    }
+
+    /// Free the transcription slot, whether the run finished, failed or was cancelled
+    fn release_transcription(&mut self) {
+        self.currently_transcribing = None;
+        self.transcription_cancellation = None;
+        self.job_queue.notify();
+    }
+
+    /// Stop the transcription of `meeting_id`. Returns whether one was running.
+    fn cancel_transcription(&mut self, meeting_id: &str) -> bool {
+        match &self.transcription_cancellation {
+            Some(cancellation) if self.currently_transcribing.as_deref() == Some(meeting_id) => {
+                cancellation.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Free the summarization slot, whether the run finished, failed or was cancelled
+    fn release_summarization(&mut self) {
+        self.currently_summarizing = None;
+        self.summarization_cancellation = None;
+        self.job_queue.notify();
+    }
+
+    fn cancel_summarization(&mut self, meeting_id: &str) -> Result<(), String> {
+        match &self.summarization_cancellation {
+            Some(cancellation) if self.currently_summarizing.as_deref() == Some(meeting_id) => {
+                cancellation.cancel();
+                Ok(())
+            }
+            _ => Err(format!("No summarization of {} is running", meeting_id)),
+        }
+    }
+
+    /// Whether the meeting's transcript is still being written
+    fn is_transcribing(&self, meeting_id: &str) -> bool {
+        self.currently_transcribing.as_deref() == Some(meeting_id)
+            || [jobs::JobKind::Transcription, jobs::JobKind::Alignment]
+                .into_iter()
+                .any(|kind| self.jobs.has_running_job_of(kind, meeting_id))
+    }
+
+    /// Whether the meeting is transcribed, summarized or has any other job running
+    fn is_busy_with(&self, meeting_id: &str) -> bool {
+        self.currently_transcribing.as_deref() == Some(meeting_id)
+            || self.currently_summarizing.as_deref() == Some(meeting_id)
+            || self.jobs.has_running_job_for(meeting_id)
+    }
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
-#[derive(Serialize, Deserialize)]
+#[derive(Default, Serialize, Deserialize)]
 struct MeetingMetadata {
     id: String,
+    /// Plain text, the emoji of a generated title is kept in `emoji`
     name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    emoji: Option<String>,
     created_at: Option<String>, // ISO 8601 date string
+    /// Last time the user edited the meeting, e.g. their notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    /// Pinned meetings are listed first
+    #[serde(default)]
+    pinned: bool,
+    /// Set by the user, e.g. "1:1" or a client name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// Keywords extracted from the summary, kept apart from tags set by the user
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    auto_tags: Vec<String>,
+    /// Meetings imported from a transcript never had an audio file
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    audio_less: bool,
+    /// The recording was deleted after transcription to save disk space
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    audio_deleted: bool,
+    /// Length of the recording, kept so listing meetings needs no ffprobe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    audio_duration_seconds: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_audio_duration_seconds: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_audio_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    transcription_warnings: Vec<transcript_check::TranscriptionWarning>,
+    /// Exported subtitle files whose timings predate the last realignment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    stale_exports: Vec<String>,
+    /// Overrides the summary style from the settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary_style: Option<llm::prompts::SummaryStyle>,
+    /// Overrides whether the summary names speakers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attribute_speakers: Option<bool>,
+    /// Overrides the summary language from the LLM config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<llm::prompts::Language>,
+    /// Ids the meeting had before `repair_meeting` matched it to its folder
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    /// The summary approved as the official minutes, see `llm::approval`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    approved: Option<llm::approval::Approval>,
+    /// Fields written by newer versions are kept when the metadata is rewritten
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Metadata as shown in the meeting list and meeting view
+#[derive(Serialize)]
+struct MeetingOverview {
+    #[serde(flatten)]
+    metadata: MeetingMetadata,
+    /// False for imported transcripts or when the recording is gone
+    has_audio: bool,
+    has_transcript: bool,
+    has_summary: bool,
+    has_notes: bool,
+    /// Also known after the recording was deleted, `None` before it was transcribed
+    duration_seconds: Option<f64>,
+    /// Title of the summary, without its emoji
+    title: Option<String>,
+    /// Summaries of selected parts of the meeting, see `llm::generate_partial_summary`
+    partial_summaries: Vec<PartialSummaryInfo>,
 }
+
+/// Only the title of `summary.json`, the rest isn't needed for the list
+#[derive(Deserialize)]
+struct SummaryTitle {
+    title: llm::models::Title,
+}
+
+async fn read_summary_title(summary_path: &Path) -> Option<String> {
+    let json = fs::read_to_string(summary_path).await.ok()?;
+    serde_json::from_str::<SummaryTitle>(&json)
+        .ok()
+        .map(|summary| summary.title.text)
+}
+
+/// Order of the meeting list, pinned meetings always come first
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MeetingSort {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+    Name,
+}
+
+fn sort_meetings(meetings: &mut [MeetingOverview], sort: MeetingSort) {
+    match sort {
+        MeetingSort::NewestFirst => {
+            meetings.sort_by(|a, b| b.metadata.created_at.cmp(&a.metadata.created_at))
+        }
+        MeetingSort::OldestFirst => {
+            meetings.sort_by(|a, b| a.metadata.created_at.cmp(&b.metadata.created_at))
+        }
+        MeetingSort::Name => meetings.sort_by_cached_key(|meeting| {
+            meeting
+                .metadata
+                .name
+                .as_deref()
+                .unwrap_or(&meeting.metadata.id)
+                .to_lowercase()
+        }),
+    }
+
+    // Stable, so the requested order is kept among pinned and unpinned meetings
+    meetings.sort_by_key(|meeting| !meeting.metadata.pinned);
+}
+
 #[tauri::command]
-async fn get_meetings(app: AppHandle) -> Result<Vec<MeetingMetadata>, String> {
+async fn get_meetings(
+    app: AppHandle,
+    sort: Option<MeetingSort>,
+    tag: Option<String>,
+) -> Result<Vec<MeetingOverview>, String> {
     // resolve <app>/uploads
     let app_dir = app
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
-    let uploads = app_dir.join("uploads");
 
-    // read directory
-    let mut rd = fs::read_dir(uploads).await.map_err(|e| e.to_string())?;
+    let mut meetings = list_meetings(&app_dir.join("uploads"), sort.unwrap_or_default()).await?;
+    if let Some(tag) = tag {
+        meetings.retain(|meeting| tags::has_tag(&meeting.metadata, &tag));
+    }
+    Ok(meetings)
+}
+
+/// All meetings in the uploads directory, shared with the HTTP API
+async fn list_meetings(uploads: &Path, sort: MeetingSort) -> Result<Vec<MeetingOverview>, String> {
+    let mut rd = match fs::read_dir(uploads).await {
+        Ok(rd) => rd,
+        // Fresh install, nothing was added yet
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
 
     let mut folders = Vec::new();
     while let Some(entry) = rd.next_entry().await.map_err(|e| e.to_string())? {
@@ -52,11 +357,15 @@ async fn get_meetings(app: AppHandle) -> Result<Vec<MeetingMetadata>, String> {
     // fetch metadata for each folder
     let mut meetings = Vec::new();
     for id in folders {
-        let metadata = get_meeting_metadata(app.clone(), &id).await?;
-
-        meetings.push(metadata);
+        match read_meeting_overview(&uploads.join(&id), &id).await {
+            Ok(metadata) => meetings.push(metadata),
+            // One broken meeting.json must not hide all the other meetings
+            Err(e) => println!("Warning: Skipping meeting {}: {}", id, e),
+        }
     }
 
+    sort_meetings(&mut meetings, sort);
+
     Ok(meetings)
 }
 
@@ -83,7 +392,10 @@ async fn add_meeting(app: AppHandle, name: &str) -> Result<(), String> {
 }
 
 #[tauri::command]
-async fn get_meeting_transcript(app: AppHandle, meeting_id: &str) -> Result<String, String> {
+async fn get_meeting_transcript<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: &str,
+) -> Result<String, String> {
     println!("Getting meeting transcript for {}", meeting_id);
 
     let app_dir = app
@@ -91,8 +403,12 @@ async fn get_meeting_transcript(app: AppHandle, meeting_id: &str) -> Result<Stri
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.txt", meeting_id);
-    let transcript_path = base_dir.join(file_name);
+
+    read_transcript(&base_dir, meeting_id).await
+}
+
+async fn read_transcript(meeting_dir: &Path, meeting_id: &str) -> Result<String, String> {
+    let transcript_path = meeting_dir.join(format!("{}.txt", meeting_id));
 
     println!("Path: {}", transcript_path.display());
 
@@ -127,62 +443,215 @@ async fn get_llm_config(app: AppHandle) -> Result<LlmConfig, String> {
 }
 
 #[tauri::command]
-async fn set_llm_config(
-    app: AppHandle,
+#[allow(clippy::too_many_arguments)]
+async fn set_llm_config<R: Runtime>(
+    app: AppHandle<R>,
     use_external_api: bool,
+    provider: Option<llm::config::LlmProvider>,
     external_endpoint: String,
     external_model: String,
+    extract_keywords: Option<bool>,
+    min_transcript_chars: Option<usize>,
+    topic_merge: Option<llm::topic_merge::TopicMergeConfig>,
+    auto_chunk_sizing: Option<bool>,
+    context_window_tokens: Option<usize>,
+    tolerate_chunk_failures: Option<bool>,
+    bullet_dedup: Option<llm::bullet_dedup::BulletDedupConfig>,
+    ollama_legacy_generate: Option<bool>,
+    stream_tokens: Option<bool>,
+    language: Option<llm::prompts::Language>,
 ) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    // Holding the lock keeps concurrent saves in the order they reach the state
     let state = app.state::<Mutex<AppState>>();
     let mut state = state.lock().await;
-    state.llm_config = LlmConfig {
-        use_external_api,
-        external_endpoint,
-        external_model,
-        chunk_size: 10_000,
-        max_retries: 3,
-        timeout_seconds: 120,
-    };
+
+    // Settings the page doesn't send keep their current value
+    let mut config = state.llm_config.clone();
+    config.use_external_api = use_external_api;
+    config.external_endpoint = external_endpoint;
+    config.external_model = external_model;
+    if let Some(provider) = provider {
+        config.provider = provider;
+    }
+    if let Some(extract_keywords) = extract_keywords {
+        config.extract_keywords = extract_keywords;
+    }
+    if let Some(min_transcript_chars) = min_transcript_chars {
+        config.min_transcript_chars = min_transcript_chars;
+    }
+    if let Some(topic_merge) = topic_merge {
+        config.topic_merge = topic_merge;
+    }
+    if let Some(auto_chunk_sizing) = auto_chunk_sizing {
+        config.auto_chunk_sizing = auto_chunk_sizing;
+    }
+    if let Some(context_window_tokens) = context_window_tokens {
+        config.context_window_tokens = context_window_tokens;
+    }
+    if let Some(tolerate_chunk_failures) = tolerate_chunk_failures {
+        config.tolerate_chunk_failures = tolerate_chunk_failures;
+    }
+    if let Some(bullet_dedup) = bullet_dedup {
+        config.bullet_dedup = bullet_dedup;
+    }
+    if let Some(ollama_legacy_generate) = ollama_legacy_generate {
+        config.ollama_legacy_generate = ollama_legacy_generate;
+    }
+    if let Some(stream_tokens) = stream_tokens {
+        config.stream_tokens = stream_tokens;
+    }
+    if let Some(language) = language {
+        config.language = language;
+    }
+
+    config
+        .save(&llm::config::get_llm_config_path(&app_dir))
+        .await?;
+    state.llm_config = config;
     Ok(())
 }
 
 #[tauri::command]
-async fn get_meeting_metadata(app: AppHandle, meeting_id: &str) -> Result<MeetingMetadata, String> {
-    // resolve <app>/uploads/<meeting_id>/meeting.json
+async fn get_transcription_config(
+    app: AppHandle,
+) -> Result<transcription_config::TranscriptionSettings, String> {
+    let hf_token = transcription_config::get_hf_token()?;
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().await;
+    Ok(transcription_config::TranscriptionSettings {
+        config: state.transcription_config.clone(),
+        hf_token: hf_token.as_deref().map(transcription_config::mask_token),
+    })
+}
+
+/// `model: None` goes back to the default model of each backend
+#[tauri::command]
+async fn set_transcription_config<R: Runtime>(
+    app: AppHandle<R>,
+    model: Option<transcription_config::WhisperModelSize>,
+    diarize: Option<bool>,
+    min_speakers: Option<u32>,
+    max_speakers: Option<u32>,
+    chunk_duration_seconds: Option<u64>,
+) -> Result<(), String> {
     let app_dir = app
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
-    let metadata_path = app_dir
-        .join("uploads")
-        .join(meeting_id)
-        .join("meeting.json");
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+
+    // Settings that aren't sent keep their current value
+    let mut config = state.transcription_config.clone();
+    if model.is_some() {
+        config.model = model;
+    }
+    if let Some(diarize) = diarize {
+        config.diarize = diarize;
+    }
+    if min_speakers.is_some() {
+        config.min_speakers = min_speakers;
+    }
+    if max_speakers.is_some() {
+        config.max_speakers = max_speakers;
+    }
+    if let Some(chunk_duration_seconds) = chunk_duration_seconds {
+        config.chunk_duration_seconds = chunk_duration_seconds;
+    }
+    config.validate()?;
+
+    config
+        .save(&transcription_config::get_transcription_config_path(
+            &app_dir,
+        ))
+        .await?;
+    state.transcription_config = config;
+    Ok(())
+}
+
+/// Store the Hugging Face token for diarization, an empty token removes it
+#[tauri::command]
+async fn set_hf_token(token: String) -> Result<(), String> {
+    transcription_config::store_hf_token(&token)
+}
+
+#[tauri::command]
+async fn get_meeting_metadata(app: AppHandle, meeting_id: &str) -> Result<MeetingOverview, String> {
+    // resolve <app>/uploads/<meeting_id>
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    read_meeting_overview(&meeting_dir, meeting_id).await
+}
+
+async fn read_meeting_overview(
+    meeting_dir: &Path,
+    meeting_id: &str,
+) -> Result<MeetingOverview, String> {
+    let metadata_path = meeting_dir.join("meeting.json");
 
     // read and parse JSON
     let content = fs::read_to_string(&metadata_path)
         .await
         .map_err(|e| e.to_string());
 
-    if let Ok(content) = content {
+    let metadata = if let Ok(content) = content {
         let mut metadata: MeetingMetadata =
             serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        meeting::migrate_name_emoji(&mut metadata);
+
+        // A copy of the folder, e.g. a sync conflict, still carries the original id
+        if metadata.id != meeting_id {
+            println!(
+                "Warning: meeting.json in {} says id {}, using the folder name",
+                meeting_id, metadata.id
+            );
+            metadata.id = meeting_id.to_string();
+        }
 
         // If created_at is missing, try to get it from file creation time or meeting_id
         if metadata.created_at.is_none() {
             metadata.created_at = get_fallback_date(&metadata_path, meeting_id).await;
         }
 
-        Ok(metadata)
+        metadata
     } else {
         // Create new metadata with current date
         let created_at = Some(Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string());
 
-        Ok(MeetingMetadata {
+        MeetingMetadata {
             id: meeting_id.to_string(),
-            name: None,
             created_at,
-        })
-    }
+            ..Default::default()
+        }
+    };
+
+    let summary_path = meeting_dir.join("summary.json");
+    let has_summary = summary_path.exists();
+    let title = if has_summary {
+        read_summary_title(&summary_path).await
+    } else {
+        None
+    };
+    Ok(MeetingOverview {
+        duration_seconds: metadata
+            .audio_duration_seconds
+            .or(metadata.original_audio_duration_seconds),
+        metadata,
+        has_audio: meeting::has_audio(meeting_dir, meeting_id).await,
+        has_transcript: meeting_dir.join(format!("{}.txt", meeting_id)).exists(),
+        has_summary,
+        has_notes: notes::has_notes(meeting_dir).await,
+        title,
+        partial_summaries: list_partial_summaries_in(meeting_dir).await,
+    })
 }
 
 #[tauri::command]
@@ -192,8 +661,7 @@ async fn get_meeting_audio(app: AppHandle, meeting_id: &str) -> Result<Response,
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.ogg", meeting_id);
-    let audio_path = base_dir.join(file_name);
+    let audio_path = meeting::get_audio_path(&base_dir, meeting_id).await?;
 
     let data = fs::read(audio_path);
     return match data.await {
@@ -206,39 +674,21 @@ async fn get_meeting_audio(app: AppHandle, meeting_id: &str) -> Result<Response,
     };
 }
 
+/// Keeps the emoji unless a new one is given, an empty one removes it
 #[tauri::command]
-async fn rename_meeting(app: AppHandle, meeting_id: &str, new_name: &str) -> Result<(), String> {
+async fn rename_meeting(
+    app: AppHandle,
+    meeting_id: &str,
+    new_name: &str,
+    emoji: Option<String>,
+) -> Result<(), String> {
     let app_dir = app
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let meeting_dir = app_dir.join("uploads").join(meeting_id);
-    let metadata_path = meeting_dir.join("meeting.json");
-
-    // Get existing metadata or create new one
-    let mut metadata = if metadata_path.exists() {
-        let content = fs::read_to_string(&metadata_path)
-            .await
-            .map_err(|e| e.to_string())?;
-        serde_json::from_str::<MeetingMetadata>(&content).map_err(|e| e.to_string())?
-    } else {
-        MeetingMetadata {
-            id: meeting_id.to_string(),
-            name: None,
-            created_at: Some(Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
-        }
-    };
 
-    // Update the name
-    metadata.name = Some(new_name.to_string());
-
-    // Write back to file
-    let json_content = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
-    fs::write(&metadata_path, json_content)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+    meeting::set_meeting_name(&meeting_dir, meeting_id, new_name.to_string(), emoji).await
 }
 
 #[derive(Serialize, Deserialize)]
@@ -246,6 +696,8 @@ struct ChunkSummary {
     chunk_number: usize,
     content: String,
     markdown_content: String,
+    /// Made from another transcript or chunk size than a new run would use
+    stale: bool,
 }
 
 #[tauri::command]
@@ -257,18 +709,32 @@ async fn get_chunk_summaries(
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
-    let chunks_dir = app_dir.join("uploads").join(meeting_id).join("chunks");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
 
-    // Check if chunks directory exists
-    if !chunks_dir.exists() {
+    // Only the last complete run, an unfinished one may have fewer chunks
+    let Some(run_dir) = llm::file_manager::current_summarization_run_dir(&meeting_dir).await else {
         return Ok(Vec::new());
-    }
+    };
+    let stale = match read_transcript(&meeting_dir, meeting_id).await {
+        Ok(transcript) => {
+            let transcript = transcript.trim();
+            let chunk_size = get_llm_config(app.clone())
+                .await?
+                .chunk_size_for(transcript);
+            llm::file_manager::stale_chunks(&meeting_dir, transcript, chunk_size)
+                .await
+                .is_some()
+        }
+        Err(_) => false,
+    };
+    let names = llm::attendee_refs::attendee_names(&meeting_dir).await;
 
     let mut chunk_summaries = Vec::new();
     let mut chunk_number = 1;
 
     loop {
-        let summary_file = chunks_dir.join(format!("chunk_{:03}_summary.json", chunk_number));
+        let summary_file =
+            run_dir.join(llm::file_manager::chunk_summary_file_name(chunk_number - 1));
         if !summary_file.exists() {
             break;
         }
@@ -282,11 +748,14 @@ async fn get_chunk_summaries(
                     } else {
                         content.clone()
                     };
+                let markdown_content =
+                    llm::attendee_refs::replace_attendee_references(&markdown_content, &names);
 
                 chunk_summaries.push(ChunkSummary {
                     chunk_number,
                     content: content.clone(),
                     markdown_content,
+                    stale,
                 });
             }
             Err(_) => break,
@@ -298,6 +767,20 @@ async fn get_chunk_summaries(
     Ok(chunk_summaries)
 }
 
+/// The transcript text a chunk summary was generated from, for "view source"
+#[tauri::command]
+async fn get_chunk_text(
+    app: AppHandle,
+    meeting_id: &str,
+    chunk_number: usize,
+    max_chars: Option<usize>,
+) -> Result<llm::file_manager::ChunkText, String> {
+    let transcript = get_meeting_transcript(app.clone(), meeting_id).await.ok();
+    llm::file_manager::FileManager::new(app)
+        .read_chunk_text(meeting_id, chunk_number, max_chars, transcript.as_deref())
+        .await
+}
+
 fn format_chunk_summary_as_markdown(summary: &serde_json::Value) -> String {
     let mut markdown = String::new();
 
@@ -354,6 +837,30 @@ fn format_chunk_summary_as_markdown(summary: &serde_json::Value) -> String {
     }
 }
 
+/// Recording time encoded in the id, either `recording-{unix seconds}` or the
+/// frontend's `recording-2024-06-10T14-30-00-000Z` (ISO with `:` and `.` as `-`)
+fn date_from_meeting_id(meeting_id: &str) -> Option<String> {
+    let stamp = meeting_id.strip_prefix("recording-")?;
+
+    let dt = if let Ok(timestamp) = stamp.parse::<i64>() {
+        DateTime::from_timestamp(timestamp, 0)?
+    } else {
+        let (date, time) = stamp.split_once('T')?;
+        let mut parts = time.strip_suffix('Z')?.split('-');
+        let (hours, minutes, seconds) = (parts.next()?, parts.next()?, parts.next()?);
+        let iso = match (parts.next(), parts.next()) {
+            (Some(millis), None) => {
+                format!("{}T{}:{}:{}.{}Z", date, hours, minutes, seconds, millis)
+            }
+            (None, None) => format!("{}T{}:{}:{}Z", date, hours, minutes, seconds),
+            _ => return None,
+        };
+        DateTime::parse_from_rfc3339(&iso).ok()?.with_timezone(&Utc)
+    };
+
+    Some(dt.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+}
+
 // Helper function to get fallback date from file creation time or meeting_id
 async fn get_fallback_date(metadata_path: &Path, meeting_id: &str) -> Option<String> {
     // Try to get file creation time from the parent directory (meeting directory)
@@ -367,13 +874,9 @@ async fn get_fallback_date(metadata_path: &Path, meeting_id: &str) -> Option<Str
         }
     }
 
-    // Fallback: try to parse timestamp from meeting_id (format: recording-{timestamp})
-    if meeting_id.starts_with("recording-") {
-        if let Ok(timestamp) = meeting_id.trim_start_matches("recording-").parse::<i64>() {
-            if let Some(dt) = DateTime::from_timestamp(timestamp, 0) {
-                return Some(dt.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string());
-            }
-        }
+    // Fallback: try to parse the recording time from meeting_id
+    if let Some(date) = date_from_meeting_id(meeting_id) {
+        return Some(date);
     }
 
     // Final fallback: current time
@@ -394,32 +897,721 @@ pub fn run() {
             get_meeting_transcript_json,
             get_meeting_metadata,
             get_chunk_summaries,
+            get_chunk_text,
+            chunk_manifest::get_chunk_manifest,
+            filename::suggest_export_file_name,
+            export::export_transcript_pdf,
+            export::export_summary_markdown,
+            bulk_export::export_summaries_bulk,
+            analytics::get_meeting_analytics,
             llm::get_meeting_summary,
             llm::generate_summary,
             llm::regenerate_final_summary,
+            llm::regenerate_chunk_summary,
+            llm::summary::generate_partial_summary,
+            llm::partial::get_partial_summary,
+            llm::key_facts::update_key_facts,
+            llm::attendee_refs::resolve_attendee_references,
+            llm::approval::approve_summary,
+            llm::outline::get_summary_outline,
+            llm::list_summary_versions,
+            llm::diff_summaries,
+            llm::generate_digest,
+            llm::set_provider_api_key,
+            llm::has_provider_api_key,
+            llm::get_usage_report,
+            llm::preview_summary_prompts,
+            llm::get_supported_languages,
+            llm::model_list::list_llm_models,
+            llm::performance::get_summary_metrics,
+            llm::performance::get_performance_metrics,
             llm::is_summarizing,
+            llm::cancel_summarization,
             llm::test_llm_connection,
             whisperx::check_python_installation,
             whisperx::check_whisperx_installation,
+            python_env::get_python_environment_info,
             whisperx::transcribe,
             whisperx::transcribe_with_chunking,
+            alignment::realign_transcript,
             whisperx::is_transcribing,
+            whisperx::cancel_transcription,
             whisperx::download_python,
             whisperx::download_whisperx,
-            meeting::save_speaker_names,
+            speakers::save_speaker_names,
+            speakers::preview_speaker_rename,
+            meeting::create_meeting_from_transcript,
+            meeting::set_meeting_pinned,
+            meeting::set_meeting_summary_style,
+            meeting::set_meeting_attribute_speakers,
+            meeting::set_meeting_language,
+            meeting::repair_meeting,
+            notes::get_meeting_notes,
+            notes::set_meeting_notes,
             audio::check_ffmpeg_installation_command,
             audio::get_audio_duration_command,
             audio::analyze_audio_command,
             audio::split_audio_into_chunks_command,
             audio::convert_user_audio,
+            audio_cleanup::confirm_audio_deletion,
             get_llm_config,
             set_llm_config,
-            rename_meeting
+            get_transcription_config,
+            set_transcription_config,
+            set_hf_token,
+            rename_meeting,
+            onboarding::get_onboarding_state,
+            onboarding::advance_onboarding,
+            settings::get_settings,
+            job_queue::get_job_queue,
+            job_queue::cancel_queued_job,
+            meeting_archive::export_meeting,
+            meeting_archive::import_meeting,
+            pipeline::process_meeting,
+            tags::list_tags,
+            tags::set_meeting_tags,
+            settings::set_delete_audio_after_transcription,
+            settings::set_shutdown_grace_period,
+            settings::set_transcription_idle_timeout,
+            settings::set_redirect_model_cache,
+            settings::set_model_price,
+            settings::set_summary_style,
+            llm::endpoint_privacy::set_llm_endpoint_consent,
+            llm::endpoint_privacy::set_endpoint_privacy,
+            settings::set_min_audio_duration_seconds,
+            settings::set_speaker_attribution,
+            settings::set_trash_retention_days,
+            settings_bundle::export_settings,
+            settings_bundle::import_settings,
+            api_server::set_api_server,
+            api_server::regenerate_api_token,
+            search::search_meetings,
+            jobs::get_incomplete_jobs,
+            job_state::get_meeting_job_state,
+            diagnostics::get_app_info,
+            diagnostics::verify_data_integrity,
+            diagnostics::get_data_paths,
+            diagnostics::open_data_folder,
+            trash::delete_meeting,
+            trash::list_trashed_meetings,
+            trash::restore_meeting,
+            trash::empty_trash,
+            model_cache::get_model_cache_info,
+            model_cache::delete_cached_model,
+            model_cache::download_whisper_model
         ])
         .setup(|app| {
-            app.manage(Mutex::new(AppState::default()));
+            let app_dir = app
+                .path()
+                .app_local_data_dir()
+                .expect("Failed to get app local data directory");
+            std::fs::create_dir_all(&app_dir)?;
+            // Before any job runs, a state of this process is never stale
+            if let Err(e) = tauri::async_runtime::block_on(
+                job_state::recover_job_states_on_startup(app.handle().clone()),
+            ) {
+                println!("Warning: Failed to recover job states: {}", e);
+            }
+            let mut jobs = jobs::JobRegistry::load(jobs::get_jobs_path(&app_dir));
+            jobs.set_events(llm::progress::ProgressEvents::new(app.handle().clone()));
+            app.manage(Mutex::new(AppState {
+                llm_config: LlmConfig::load(&llm::config::get_llm_config_path(&app_dir)),
+                transcription_config: transcription_config::TranscriptionConfig::load(
+                    &transcription_config::get_transcription_config_path(&app_dir),
+                ),
+                jobs,
+                ..Default::default()
+            }));
+
+            tauri::async_runtime::spawn(job_queue::run_dispatcher(app.handle().clone()));
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = onboarding::check_onboarding_on_startup(handle).await {
+                    println!("Warning: Failed to check onboarding state: {}", e);
+                }
+            });
+
+            let uploads = app_dir.join("uploads");
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = llm::file_manager::migrate_chunk_layouts(&uploads).await {
+                    println!("Warning: Failed to migrate chunk files: {}", e);
+                }
+            });
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = trash::purge_trash_on_startup(handle).await {
+                    println!("Warning: Failed to purge the trash: {}", e);
+                }
+            });
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = api_server::start_api_on_startup(handle).await {
+                    println!("Warning: Failed to start HTTP API: {}", e);
+                }
+            });
+
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                tauri::async_runtime::block_on(api_server::stop_api_server(app));
+                return;
+            }
+            if let tauri::RunEvent::ExitRequested { api, .. } = event {
+                let state = app.state::<Mutex<AppState>>();
+                // Jobs hold the lock only briefly, skip the cleanup rather than block the exit
+                let Ok(mut state) = state.try_lock() else {
+                    return;
+                };
+                if state.jobs.is_shutting_down() || !state.jobs.has_running_jobs() {
+                    return;
+                }
+
+                // Exit again once the running jobs are stopped and recorded
+                api.prevent_exit();
+                state.jobs.begin_shutdown();
+                let handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    jobs::shutdown(&handle).await;
+                    handle.exit(0);
+                });
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Race a transcription and a summarization of the same meeting, the way
+    /// the command entry points take the state lock
+    async fn race_claims(state: std::sync::Arc<Mutex<AppState>>) -> (bool, bool) {
+        let transcription = tokio::spawn({
+            let state = state.clone();
+            async move {
+                state
+                    .lock()
+                    .await
+                    .claim_transcription("recording-1")
+                    .is_ok()
+            }
+        });
+        let summarization = tokio::spawn({
+            let state = state.clone();
+            async move {
+                state
+                    .lock()
+                    .await
+                    .claim_summarization("recording-1")
+                    .is_ok()
+            }
+        });
+        (transcription.await.unwrap(), summarization.await.unwrap())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_transcription_and_summarization_exclude_each_other() {
+        for _ in 0..50 {
+            let state = std::sync::Arc::new(Mutex::new(AppState::default()));
+            let (transcribed, summarized) = race_claims(state.clone()).await;
+            assert!(transcribed != summarized, "exactly one claim has to win");
+
+            let state = state.lock().await;
+            if transcribed {
+                assert_eq!(state.currently_transcribing.as_deref(), Some("recording-1"));
+                assert!(state.currently_summarizing.is_none());
+            } else {
+                assert_eq!(state.currently_summarizing.as_deref(), Some("recording-1"));
+                assert!(state.currently_transcribing.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_claims_report_what_is_busy() {
+        let mut state = AppState::default();
+        state.claim_transcription("recording-1").unwrap();
+        assert_eq!(
+            state.claim_summarization("recording-1"),
+            Err(BusyError::MeetingTranscribing {
+                meeting_id: "recording-1".to_string()
+            })
+        );
+        assert_eq!(
+            state.claim_transcription("recording-2"),
+            Err(BusyError::TranscriptionRunning {
+                meeting_id: "recording-1".to_string()
+            })
+        );
+        // Other meetings can still be summarized
+        state.claim_summarization("recording-2").unwrap();
+        assert_eq!(
+            state
+                .claim_summarization("recording-3")
+                .unwrap_err()
+                .to_string(),
+            "Another summarization is running"
+        );
+
+        // A job still registered for the meeting counts as well
+        let mut state = AppState::default();
+        let job_id = state
+            .jobs
+            .start_job(jobs::JobKind::Alignment, "recording-1");
+        assert!(state.claim_summarization("recording-1").is_err());
+        state.jobs.finish_job(&job_id);
+        state.claim_summarization("recording-1").unwrap();
+        assert_eq!(
+            state.claim_transcription("recording-1"),
+            Err(BusyError::MeetingSummarizing {
+                meeting_id: "recording-1".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_busy_meetings() {
+        let mut state = AppState::default();
+        state.claim_transcription("recording-1").unwrap();
+        state.claim_summarization("recording-2").unwrap();
+        let job_id = state
+            .jobs
+            .start_job(jobs::JobKind::Alignment, "recording-3");
+
+        for meeting_id in ["recording-1", "recording-2", "recording-3"] {
+            assert!(state.is_busy_with(meeting_id), "{}", meeting_id);
+        }
+        assert!(!state.is_busy_with("recording-4"));
+        state.jobs.finish_job(&job_id);
+        assert!(!state.is_busy_with("recording-3"));
+    }
+
+    #[test]
+    fn test_cancel_transcription() {
+        let mut state = AppState::default();
+        assert!(!state.cancel_transcription("recording-1"));
+
+        let cancellation = cancellation::CancellationToken::default();
+        state.claim_transcription("recording-1").unwrap();
+        state.transcription_cancellation = Some(cancellation.clone());
+        assert!(!state.cancel_transcription("recording-2"));
+        assert!(!cancellation.is_cancelled());
+        assert!(state.cancel_transcription("recording-1"));
+        assert!(cancellation.is_cancelled());
+
+        state.release_transcription();
+        assert!(state.currently_transcribing.is_none());
+        assert!(!state.cancel_transcription("recording-1"));
+    }
+
+    #[tokio::test]
+    async fn test_saving_llm_settings_keeps_the_others() {
+        let app = llm::test_harness::TestApp::new(
+            LlmConfig::default()
+                .with_chunk_size(4_000)
+                .with_timeout(300)
+                .with_retries(7),
+        );
+        let config_path = llm::config::get_llm_config_path(
+            &app.handle().path().app_local_data_dir().unwrap(),
+        );
+
+        set_llm_config(
+            app.handle(),
+            true,
+            None,
+            "http://localhost:8080".to_string(),
+            "qwen2.5".to_string(),
+            Some(false),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let saved = LlmConfig::load(&config_path);
+        assert_eq!(saved.external_model, "qwen2.5");
+        assert!(!saved.extract_keywords);
+        assert_eq!(saved.chunk_size, 4_000);
+        assert_eq!(saved.timeout_seconds, 300);
+        assert_eq!(saved.max_retries, 7);
+    }
+
+    #[tokio::test]
+    async fn test_saving_the_whisper_model_keeps_the_speaker_settings() {
+        let app = llm::test_harness::TestApp::new(LlmConfig::default());
+        app.handle()
+            .state::<Mutex<AppState>>()
+            .lock()
+            .await
+            .transcription_config = transcription_config::TranscriptionConfig {
+            diarize: false,
+            min_speakers: Some(2),
+            max_speakers: Some(4),
+            chunk_duration_seconds: 900,
+            ..Default::default()
+        };
+
+        set_transcription_config(
+            app.handle(),
+            Some(transcription_config::WhisperModelSize::Small),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let saved = transcription_config::TranscriptionConfig::load(
+            &transcription_config::get_transcription_config_path(
+                &app.handle().path().app_local_data_dir().unwrap(),
+            ),
+        );
+        assert_eq!(saved.model, Some(transcription_config::WhisperModelSize::Small));
+        assert!(!saved.diarize);
+        assert_eq!((saved.min_speakers, saved.max_speakers), (Some(2), Some(4)));
+        assert_eq!(saved.chunk_duration_seconds, 900);
+    }
+
+    #[tokio::test]
+    async fn test_overview_without_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_id = "recording-1700000000";
+        fs::write(
+            dir.path().join(format!("{}.txt", meeting_id)),
+            "[Anna]: Hallo",
+        )
+        .await
+        .unwrap();
+
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(!overview.has_audio);
+        assert_eq!(overview.metadata.id, meeting_id);
+
+        let json = serde_json::to_value(&overview).unwrap();
+        assert_eq!(json["has_audio"], false);
+        assert_eq!(json["id"], meeting_id);
+    }
+
+    #[tokio::test]
+    async fn test_no_meetings_before_the_first_upload() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let meetings = list_meetings(&dir.path().join("uploads"), MeetingSort::default())
+            .await
+            .unwrap();
+        assert!(meetings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_meeting_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        for meeting_id in ["recording-1700000000", "recording-1700000100"] {
+            fs::create_dir(dir.path().join(meeting_id)).await.unwrap();
+        }
+        fs::write(
+            dir.path().join("recording-1700000000").join("meeting.json"),
+            "{\"id\": \"recording-17",
+        )
+        .await
+        .unwrap();
+
+        let meetings = list_meetings(dir.path(), MeetingSort::default())
+            .await
+            .unwrap();
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].metadata.id, "recording-1700000100");
+    }
+
+    #[tokio::test]
+    async fn test_overview_status_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_id = "recording-1700000000";
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(!overview.has_transcript);
+        assert!(!overview.has_summary);
+        assert_eq!(overview.duration_seconds, None);
+        assert_eq!(overview.title, None);
+
+        fs::write(
+            dir.path().join(format!("{}.txt", meeting_id)),
+            "[Anna]: Hallo",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            dir.path().join("summary.json"),
+            r#"{"title": {"emoji": "💶", "text": "Haushalt 2025"}, "summary": "..."}"#,
+        )
+        .await
+        .unwrap();
+        meeting::update_meeting_metadata(dir.path(), meeting_id, |metadata| {
+            metadata.original_audio_duration_seconds = Some(1834.5);
+        })
+        .await
+        .unwrap();
+
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(overview.has_transcript);
+        assert!(overview.has_summary);
+        assert_eq!(overview.duration_seconds, Some(1834.5));
+        let json = serde_json::to_value(&overview).unwrap();
+        assert_eq!(json["title"], "Haushalt 2025");
+    }
+
+    #[tokio::test]
+    async fn test_overview_with_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_id = "recording-1700000000";
+        fs::write(dir.path().join(format!("{}.ogg", meeting_id)), b"OggS")
+            .await
+            .unwrap();
+
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(overview.has_audio);
+        assert!(!overview.has_notes);
+    }
+
+    #[tokio::test]
+    async fn test_overview_with_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_id = "recording-1700000000";
+        notes::write_notes(dir.path(), meeting_id, "- Folien schicken")
+            .await
+            .unwrap();
+
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(overview.has_notes);
+        assert!(overview.metadata.updated_at.is_some());
+
+        let json = serde_json::to_value(&overview).unwrap();
+        assert_eq!(json["has_notes"], true);
+    }
+
+    #[test]
+    fn test_date_from_unix_seconds_id() {
+        assert_eq!(
+            date_from_meeting_id("recording-1700000000").as_deref(),
+            Some("2023-11-14T22:13:20.000Z")
+        );
+    }
+
+    #[test]
+    fn test_date_from_iso_id() {
+        assert_eq!(
+            date_from_meeting_id("recording-2024-06-10T14-30-00-000Z").as_deref(),
+            Some("2024-06-10T14:30:00.000Z")
+        );
+        assert_eq!(
+            date_from_meeting_id("recording-2024-06-10T14-30-05-123Z").as_deref(),
+            Some("2024-06-10T14:30:05.123Z")
+        );
+        assert_eq!(
+            date_from_meeting_id("recording-2024-06-10T14-30-05Z").as_deref(),
+            Some("2024-06-10T14:30:05.000Z")
+        );
+    }
+
+    #[test]
+    fn test_date_from_garbage_id() {
+        for id in [
+            "meeting-1700000000",
+            "recording-",
+            "recording-abc",
+            "recording-2024-06-10",
+            "recording-2024-06-10T14-30-00-000",
+            "recording-2024-13-10T14-30-00-000Z",
+            "recording-2024-06-10T25-30-00-000Z",
+            "recording-2024-06-10T14-30-00-000-1Z",
+            "Teamrunde",
+        ] {
+            assert_eq!(date_from_meeting_id(id), None, "{}", id);
+        }
+    }
+
+    fn overview(id: &str, name: &str, created_at: &str, pinned: bool) -> MeetingOverview {
+        MeetingOverview {
+            metadata: MeetingMetadata {
+                id: id.to_string(),
+                name: Some(name.to_string()),
+                created_at: Some(created_at.to_string()),
+                pinned,
+                ..Default::default()
+            },
+            has_audio: false,
+            has_transcript: false,
+            has_summary: false,
+            has_notes: false,
+            duration_seconds: None,
+            title: None,
+            partial_summaries: Vec::new(),
+        }
+    }
+
+    fn ids(meetings: &[MeetingOverview]) -> Vec<&str> {
+        meetings.iter().map(|m| m.metadata.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_pinned_meetings_come_first() {
+        let mut meetings = vec![
+            overview("a", "Retro", "2024-01-01T10:00:00.000Z", false),
+            overview("b", "Jour fixe", "2024-01-02T10:00:00.000Z", true),
+            overview("c", "Kickoff", "2024-01-03T10:00:00.000Z", false),
+            overview("d", "Architektur", "2024-01-04T10:00:00.000Z", true),
+        ];
+
+        sort_meetings(&mut meetings, MeetingSort::NewestFirst);
+        assert_eq!(ids(&meetings), ["d", "b", "c", "a"]);
+
+        sort_meetings(&mut meetings, MeetingSort::OldestFirst);
+        assert_eq!(ids(&meetings), ["b", "d", "a", "c"]);
+
+        sort_meetings(&mut meetings, MeetingSort::Name);
+        assert_eq!(ids(&meetings), ["d", "b", "c", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_pin_survives_rename_and_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_id = "recording-1700000000";
+
+        meeting::set_pinned(dir.path(), meeting_id, true)
+            .await
+            .unwrap();
+        // rename_meeting
+        meeting::set_meeting_name(dir.path(), meeting_id, "Jour fixe".to_string(), None)
+            .await
+            .unwrap();
+        // FileManager::save_meeting_metadata after summarizing
+        llm::file_manager::save_summary_title(
+            dir.path(),
+            meeting_id,
+            &llm::models::Title {
+                emoji: "📅".to_string(),
+                text: "Jour fixe KW 12".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(overview.metadata.pinned);
+        assert_eq!(overview.metadata.name.as_deref(), Some("Jour fixe KW 12"));
+        assert_eq!(overview.metadata.emoji.as_deref(), Some("📅"));
+
+        meeting::set_pinned(dir.path(), meeting_id, false)
+            .await
+            .unwrap();
+        let overview = read_meeting_overview(dir.path(), meeting_id).await.unwrap();
+        assert!(!overview.metadata.pinned);
+        assert_eq!(overview.metadata.name.as_deref(), Some("Jour fixe KW 12"));
+        assert_eq!(overview.metadata.emoji.as_deref(), Some("📅"));
+    }
+
+    /// Command names registered in `generate_handler!`, read from this file
+    fn registered_commands() -> Vec<String> {
+        let source = include_str!("lib.rs");
+        let start = source.find("generate_handler![").unwrap() + "generate_handler![".len();
+        let end = start + source[start..].find(']').unwrap();
+        source[start..end]
+            .split(',')
+            .map(|path| path.trim().rsplit("::").next().unwrap().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Names passed to `invoke("...")` in a frontend source file
+    fn invoked_commands(source: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        for (index, _) in source.match_indices("invoke") {
+            let mut rest = &source[index + "invoke".len()..];
+            if let Some(generic) = rest.strip_prefix('<') {
+                match generic.find('>') {
+                    Some(end) => rest = &generic[end + 1..],
+                    None => continue,
+                }
+            }
+            let Some(args) = rest.strip_prefix('(') else {
+                continue;
+            };
+            let Some(name) = args.trim_start().strip_prefix('"') else {
+                continue;
+            };
+            if let Some(end) = name.find('"') {
+                names.push(name[..end].to_string());
+            }
+        }
+        names
+    }
+
+    fn frontend_sources(dir: &Path, sources: &mut Vec<(std::path::PathBuf, String)>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                frontend_sources(&path, sources);
+            } else if matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("ts" | "svelte")
+            ) {
+                sources.push((path.clone(), std::fs::read_to_string(&path).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invoked_commands_are_registered() {
+        let registered = registered_commands();
+        assert!(registered.contains(&"regenerate_final_summary".to_string()));
+
+        let frontend = Path::new(env!("CARGO_MANIFEST_DIR")).join("../src");
+        let mut sources = Vec::new();
+        frontend_sources(&frontend, &mut sources);
+        assert!(
+            !sources.is_empty(),
+            "No frontend sources in {}",
+            frontend.display()
+        );
+
+        let mut missing = Vec::new();
+        for (path, source) in &sources {
+            for name in invoked_commands(source) {
+                if !registered.contains(&name) {
+                    missing.push(format!("{} ({})", name, path.display()));
+                }
+            }
+        }
+        assert!(missing.is_empty(), "Commands not registered: {:?}", missing);
+    }
+
+    #[test]
+    fn test_invoked_commands_parsing() {
+        let source = r#"
+            await invoke("get_meetings");
+            const config = await invoke<LlmConfig>("get_llm_config");
+            summary = await invoke(
+              "regenerate_final_summary",
+              { meetingId },
+            );
+            // invoke without a literal name is skipped
+            invoke(command);
+        "#;
+        assert_eq!(
+            invoked_commands(source),
+            ["get_meetings", "get_llm_config", "regenerate_final_summary"]
+        );
+    }
 }
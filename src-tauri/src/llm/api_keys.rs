@@ -0,0 +1,60 @@
+use keyring::Entry;
+
+use crate::llm::{
+    config::LlmProvider,
+    error::{LlmError, LlmResult},
+};
+
+/// Keys live in the OS keyring under this service, never in a settings file
+pub(crate) const KEYRING_SERVICE: &str = "meeting-summariser";
+
+fn entry(provider: LlmProvider) -> LlmResult<Entry> {
+    let user = provider.key_name().ok_or_else(|| {
+        LlmError::ConfigError(format!(
+            "{} does not use an API key",
+            provider.display_name()
+        ))
+    })?;
+    Entry::new(KEYRING_SERVICE, user)
+        .map_err(|e| LlmError::ConfigError(format!("Failed to open keyring: {}", e)))
+}
+
+pub fn get_api_key(provider: LlmProvider) -> LlmResult<Option<String>> {
+    match entry(provider)?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(LlmError::ConfigError(format!(
+            "Failed to read API key from keyring: {}",
+            e
+        ))),
+    }
+}
+
+/// Store the key, or remove it when `key` is empty
+pub fn store_api_key(provider: LlmProvider, key: &str) -> LlmResult<()> {
+    let entry = entry(provider)?;
+    let key = key.trim();
+    let result = if key.is_empty() {
+        match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            result => result,
+        }
+    } else {
+        entry.set_password(key)
+    };
+
+    result.map_err(|e| LlmError::ConfigError(format!("Failed to write API key to keyring: {}", e)))
+}
+
+#[tauri::command]
+pub async fn set_provider_api_key(provider: LlmProvider, key: String) -> Result<(), String> {
+    store_api_key(provider, &key).map_err(|e| e.to_string())
+}
+
+/// Whether a key is stored, the key itself never goes back to the frontend
+#[tauri::command]
+pub async fn has_provider_api_key(provider: LlmProvider) -> Result<bool, String> {
+    get_api_key(provider)
+        .map(|key| key.is_some())
+        .map_err(|e| e.to_string())
+}
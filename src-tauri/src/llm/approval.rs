@@ -0,0 +1,302 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::fs;
+
+use crate::error::SummaryApproved;
+use crate::llm::file_manager::FileManager;
+use crate::meeting::{read_meeting_metadata, update_meeting_metadata, write_atomic};
+
+// Approved copies of a meeting directory:
+//   approved/<version>/summary.json, summary.md   the summary as approved
+//   approved/<version>/SHA256SUMS                 their checksums, `sha256sum` format
+const APPROVED_DIR_NAME: &str = "approved";
+const CHECKSUMS_FILE_NAME: &str = "SHA256SUMS";
+const SUMMARY_JSON: &str = "summary.json";
+const SUMMARY_MD: &str = "summary.md";
+
+/// The summary approved as the official minutes, `approved` in `meeting.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    /// RFC 3339
+    pub at: String,
+    pub by: String,
+    /// SHA-256 of the approved `summary.json`
+    pub checksum: String,
+    /// Directory of the frozen copy in `approved/`
+    pub version: String,
+    /// A newer summary replaced the approved one. The copy is never deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_at: Option<String>,
+}
+
+impl Approval {
+    /// Whether the approved copy is still the official record
+    pub fn is_active(&self) -> bool {
+        self.superseded_at.is_none()
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn approved_copy_dir(meeting_dir: &Path, version: &str) -> PathBuf {
+    meeting_dir.join(APPROVED_DIR_NAME).join(version)
+}
+
+async fn current_approval(meeting_dir: &Path) -> Result<Option<Approval>, String> {
+    Ok(read_meeting_metadata(meeting_dir)
+        .await?
+        .and_then(|metadata| metadata.approved))
+}
+
+/// Freeze the current summary in `approved/<version>/` and record the approval.
+/// An earlier approval is replaced in `meeting.json`, its copy stays.
+pub async fn approve_summary_in(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    approver_name: &str,
+) -> Result<Approval, String> {
+    let approver_name = approver_name.trim();
+    if approver_name.is_empty() {
+        return Err("The approval needs the name of the approver".to_string());
+    }
+
+    let now = Utc::now();
+    let version = now.format("%Y%m%dT%H%M%S%3f").to_string();
+    let copy_dir = approved_copy_dir(meeting_dir, &version);
+    fs::create_dir_all(&copy_dir)
+        .await
+        .map_err(|e| format!("Failed to create approved directory: {}", e))?;
+
+    let mut checksums = String::new();
+    let mut checksum = String::new();
+    for file_name in [SUMMARY_JSON, SUMMARY_MD] {
+        let content = fs::read(meeting_dir.join(file_name))
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+        let hash = sha256_hex(&content);
+        write_atomic(&copy_dir.join(file_name), &content)
+            .await
+            .map_err(|e| format!("Failed to copy {}: {}", file_name, e))?;
+        checksums.push_str(&format!("{}  {}\n", hash, file_name));
+        if file_name == SUMMARY_JSON {
+            checksum = hash;
+        }
+    }
+    write_atomic(&copy_dir.join(CHECKSUMS_FILE_NAME), checksums)
+        .await
+        .map_err(|e| format!("Failed to write checksums: {}", e))?;
+
+    let approval = Approval {
+        at: now.to_rfc3339(),
+        by: approver_name.to_string(),
+        checksum,
+        version,
+        superseded_at: None,
+    };
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.approved = Some(approval.clone());
+    })
+    .await?;
+    Ok(approval)
+}
+
+/// Files of an approved copy that don't match their checksums. `summary.json`
+/// is also checked against `meeting.json`, so rewriting `SHA256SUMS` doesn't
+/// hide a change to it.
+async fn tampered_files(copy_dir: &Path, approval: &Approval) -> Result<Vec<String>, String> {
+    let checksums = fs::read_to_string(copy_dir.join(CHECKSUMS_FILE_NAME))
+        .await
+        .map_err(|e| format!("Failed to read checksums of the approved summary: {}", e))?;
+
+    let mut tampered = Vec::new();
+    for file_name in [SUMMARY_JSON, SUMMARY_MD] {
+        let expected = checksums.lines().find_map(|line| {
+            let (hash, name) = line.split_once("  ")?;
+            (name == file_name).then_some(hash)
+        });
+        let actual = fs::read(copy_dir.join(file_name))
+            .await
+            .ok()
+            .map(|content| sha256_hex(&content));
+        let matches = actual.is_some()
+            && actual.as_deref() == expected
+            && (file_name != SUMMARY_JSON || actual.as_deref() == Some(&approval.checksum));
+        if !matches {
+            tampered.push(file_name.to_string());
+        }
+    }
+    Ok(tampered)
+}
+
+/// The markdown of the approved summary, after checking it wasn't changed since
+pub async fn read_approved_summary_in(meeting_dir: &Path) -> Result<String, String> {
+    let approval = current_approval(meeting_dir)
+        .await?
+        .ok_or("The summary of this meeting has not been approved")?;
+    let copy_dir = approved_copy_dir(meeting_dir, &approval.version);
+
+    let tampered = tampered_files(&copy_dir, &approval).await?;
+    if !tampered.is_empty() {
+        return Err(format!(
+            "The approved summary was modified after approval: {} no longer match the checksums",
+            tampered.join(", ")
+        ));
+    }
+
+    fs::read_to_string(copy_dir.join(SUMMARY_MD))
+        .await
+        .map_err(|e| format!("Failed to read approved summary: {}", e))
+}
+
+/// Fail while an approved summary is the official record, unless the caller
+/// asked to supersede it
+pub async fn ensure_summary_replaceable(
+    meeting_dir: &Path,
+    supersede_approved: bool,
+) -> Result<(), SummaryApproved> {
+    // Unreadable metadata is reported by whatever reads it next
+    let approval = current_approval(meeting_dir).await.ok().flatten();
+    match approval {
+        Some(approval) if approval.is_active() && !supersede_approved => Err(SummaryApproved {
+            by: approval.by,
+            at: approval.at,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Record that a new summary replaced the approved one. Call after the new
+/// summary is written, does nothing without an active approval.
+pub async fn supersede_approval(meeting_dir: &Path, meeting_id: &str) -> Result<(), String> {
+    if !current_approval(meeting_dir)
+        .await?
+        .is_some_and(|approval| approval.is_active())
+    {
+        return Ok(());
+    }
+
+    let superseded_at = Utc::now().to_rfc3339();
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        if let Some(approval) = &mut metadata.approved {
+            approval.superseded_at = Some(superseded_at);
+        }
+    })
+    .await?;
+    println!("📜 Approved summary of {} was superseded", meeting_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn approve_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    approver_name: &str,
+) -> Result<Approval, String> {
+    let meeting_dir = FileManager::new(app).get_meeting_dir(meeting_id)?;
+    approve_summary_in(&meeting_dir, meeting_id, approver_name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    fn meeting_with_summary() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(SUMMARY_JSON), r#"{"summary":"Haushalt"}"#).unwrap();
+        std::fs::write(dir.path().join(SUMMARY_MD), "# Plenum\n\nHaushalt\n").unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_approval_freezes_a_checksummed_copy() {
+        let dir = meeting_with_summary();
+        assert!(approve_summary_in(dir.path(), MEETING_ID, " ")
+            .await
+            .is_err());
+
+        let approval = approve_summary_in(dir.path(), MEETING_ID, " Anna Weber ")
+            .await
+            .unwrap();
+        assert_eq!(approval.by, "Anna Weber");
+        assert_eq!(approval.checksum, sha256_hex(br#"{"summary":"Haushalt"}"#));
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.approved, Some(approval.clone()));
+
+        // Later changes to the live summary don't reach the approved copy
+        std::fs::write(dir.path().join(SUMMARY_MD), "# Plenum\n\nNeu\n").unwrap();
+        assert_eq!(
+            read_approved_summary_in(dir.path()).await.unwrap(),
+            "# Plenum\n\nHaushalt\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tampered_copy_is_flagged() {
+        let dir = meeting_with_summary();
+        let approval = approve_summary_in(dir.path(), MEETING_ID, "Anna")
+            .await
+            .unwrap();
+        let copy_dir = approved_copy_dir(dir.path(), &approval.version);
+
+        std::fs::write(copy_dir.join(SUMMARY_MD), "# Plenum\n\nGeändert\n").unwrap();
+        let error = read_approved_summary_in(dir.path()).await.unwrap_err();
+        assert!(error.contains("summary.md"), "{}", error);
+        assert!(!error.contains("summary.json"), "{}", error);
+
+        // Rewriting the checksums along with the file doesn't hide it
+        let forged = r#"{"summary":"Gefälscht"}"#;
+        std::fs::write(copy_dir.join(SUMMARY_JSON), forged).unwrap();
+        std::fs::write(
+            copy_dir.join(CHECKSUMS_FILE_NAME),
+            format!(
+                "{}  {}\n{}  {}\n",
+                sha256_hex(forged.as_bytes()),
+                SUMMARY_JSON,
+                sha256_hex(b"# Plenum\n\nGe\xc3\xa4ndert\n"),
+                SUMMARY_MD
+            ),
+        )
+        .unwrap();
+        let error = read_approved_summary_in(dir.path()).await.unwrap_err();
+        assert!(error.contains("summary.json"), "{}", error);
+        assert!(!error.contains("summary.md"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_supersede_keeps_the_approved_copy() {
+        let dir = meeting_with_summary();
+        ensure_summary_replaceable(dir.path(), false).await.unwrap();
+        // Nothing to supersede, no meeting.json is created for it
+        supersede_approval(dir.path(), MEETING_ID).await.unwrap();
+        assert!(!dir.path().join("meeting.json").exists());
+
+        let approval = approve_summary_in(dir.path(), MEETING_ID, "Anna")
+            .await
+            .unwrap();
+        let refused = ensure_summary_replaceable(dir.path(), false)
+            .await
+            .unwrap_err();
+        assert_eq!(refused.by, "Anna");
+        ensure_summary_replaceable(dir.path(), true).await.unwrap();
+
+        supersede_approval(dir.path(), MEETING_ID).await.unwrap();
+        let superseded = read_meeting_metadata(dir.path())
+            .await
+            .unwrap()
+            .unwrap()
+            .approved
+            .unwrap();
+        assert!(superseded.superseded_at.is_some());
+        assert_eq!(superseded.checksum, approval.checksum);
+        // The next summary replaces the live one without asking again
+        ensure_summary_replaceable(dir.path(), false).await.unwrap();
+        assert!(read_approved_summary_in(dir.path()).await.is_ok());
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::llm::file_manager::{read_key_facts_snapshot, read_summary_from, FileManager};
+use crate::llm::key_facts::read_key_facts_override;
+use crate::speakers::read_speaker_renames;
+
+/// Whether `[n]` right after this char is code like `items[1]` or `m[0][1]`
+/// rather than a reference
+fn indexes_something(before: Option<char>) -> bool {
+    before.is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | ']' | ')'))
+}
+
+/// Replace attendee references like `[1] fragt …` with the names in `names`.
+/// Unknown ids, markdown links `[1](…)`, indexing and inline code stay as they are.
+pub fn replace_attendee_references(text: &str, names: &HashMap<usize, String>) -> String {
+    if names.is_empty() {
+        return text.to_string();
+    }
+
+    let bytes = text.as_bytes();
+    let mut resolved = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut in_code = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'`' => in_code = !in_code,
+            b'[' if !in_code => {
+                let digits = bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+                let close = i + 1 + digits;
+                let is_reference = digits > 0
+                    && bytes.get(close) == Some(&b']')
+                    && bytes.get(close + 1) != Some(&b'(')
+                    && !indexes_something(text[..i].chars().next_back());
+                let name = text[i + 1..close]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|id| names.get(&id));
+                if let Some(name) = name.filter(|_| is_reference) {
+                    resolved.push_str(&text[last..i]);
+                    resolved.push_str(name);
+                    last = close + 1;
+                    i = close;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    resolved.push_str(&text[last..]);
+    resolved
+}
+
+/// Attendee ids and the names to show for them: the key facts snapshot of the
+/// current run, or the summary's key facts for runs from before snapshots,
+/// then the user's corrections and speaker renames on top
+pub async fn attendee_names(meeting_dir: &Path) -> HashMap<usize, String> {
+    let snapshot = match read_key_facts_snapshot(meeting_dir).await {
+        Some(snapshot) => Some(snapshot),
+        None => read_summary_from(meeting_dir)
+            .await
+            .ok()
+            .map(|summary| summary.key_facts),
+    };
+    let corrected = read_key_facts_override(meeting_dir).await.ok().flatten();
+
+    let mut names = HashMap::new();
+    for key_facts in [snapshot, corrected].into_iter().flatten() {
+        for attendee in key_facts.attendees.into_iter().flatten() {
+            names.insert(attendee.id, attendee.name);
+        }
+    }
+
+    let renames = read_speaker_renames(meeting_dir).await;
+    for name in names.values_mut() {
+        if let Some(renamed) = renames.get(name.as_str()) {
+            *name = renamed.clone();
+        }
+    }
+    names
+}
+
+/// `text` with the attendee references of the meeting replaced by names
+pub async fn resolve_attendee_references_in(meeting_dir: &Path, text: &str) -> String {
+    replace_attendee_references(text, &attendee_names(meeting_dir).await)
+}
+
+#[tauri::command]
+pub async fn resolve_attendee_references(
+    app: AppHandle,
+    meeting_id: &str,
+    text: &str,
+) -> Result<String, String> {
+    let meeting_dir = FileManager::new(app).get_meeting_dir(meeting_id)?;
+    Ok(resolve_attendee_references_in(&meeting_dir, text).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::file_manager::{
+        finish_summarization_run, start_summarization_run, summarization_run_path,
+    };
+    use crate::llm::key_facts::write_key_facts_override;
+    use crate::llm::models::{Attendee, KeyFact};
+
+    fn names(pairs: &[(usize, &str)]) -> HashMap<usize, String> {
+        pairs
+            .iter()
+            .map(|(id, name)| (*id, name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_references_are_matched_by_whole_id() {
+        let names = names(&[(1, "Anna"), (11, "Ben")]);
+
+        assert_eq!(
+            replace_attendee_references("[1] fragt, [11] antwortet.", &names),
+            "Anna fragt, Ben antwortet."
+        );
+        assert_eq!(
+            replace_attendee_references("[1][11] und [2] und [1a] und [111]", &names),
+            "Anna[11] und [2] und [1a] und [111]"
+        );
+        assert_eq!(
+            replace_attendee_references("Zustimmung von [1]", &names),
+            "Zustimmung von Anna"
+        );
+    }
+
+    #[test]
+    fn test_code_like_text_is_left_alone() {
+        let names = names(&[(0, "Anna"), (1, "Ben")]);
+
+        assert_eq!(
+            replace_attendee_references("items[1] und m[0][1] und f()[1]", &names),
+            "items[1] und m[0][1] und f()[1]"
+        );
+        assert_eq!(
+            replace_attendee_references("`[1] bleibt` aber [1] nicht", &names),
+            "`[1] bleibt` aber Ben nicht"
+        );
+        assert_eq!(
+            replace_attendee_references("Siehe [1](https://example.org), Größe ([1])", &names),
+            "Siehe [1](https://example.org), Größe (Ben)"
+        );
+        assert_eq!(replace_attendee_references("[1]", &HashMap::new()), "[1]");
+    }
+
+    #[tokio::test]
+    async fn test_names_layer_corrections_and_renames() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(attendee_names(dir.path()).await.is_empty());
+
+        let key_facts = |attendees: &[(usize, &str)]| KeyFact {
+            attendees: Some(
+                attendees
+                    .iter()
+                    .map(|(id, name)| Attendee {
+                        id: *id,
+                        name: name.to_string(),
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let run_id = start_summarization_run(dir.path()).await.unwrap();
+        std::fs::write(
+            dir.path()
+                .join(summarization_run_path(&run_id))
+                .join("key_facts_snapshot.json"),
+            serde_json::to_string(&key_facts(&[(1, "SPEAKER_00"), (2, "Ben")])).unwrap(),
+        )
+        .unwrap();
+        finish_summarization_run(dir.path(), &run_id).await.unwrap();
+        write_key_facts_override(dir.path(), &key_facts(&[(2, "Benedikt")]))
+            .await
+            .unwrap();
+        std::fs::write(
+            dir.path().join("speaker_renames.json"),
+            r#"{"SPEAKER_00": "Anna Weber"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            resolve_attendee_references_in(dir.path(), "[1] fragt [2], [3] schweigt").await,
+            "Anna Weber fragt Benedikt, [3] schweigt"
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use serde::{Deserialize, Serialize};
+
+use crate::llm::models::{FinalSummaryFormat, FirstSummaryFormat, KeyFact, ToDo, Topic};
+
+/// Marks a removed attribution until the text around it is tidied up
+const REMOVED: char = '\u{0}';
+
+/// Name parts shorter than this are too likely to be ordinary words
+const MIN_NAME_PART_CHARS: usize = 3;
+
+/// Whether summaries say who said what
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeakerAttribution {
+    /// Name speakers in bullets and to-dos. Without it statements are not
+    /// attributed to individuals, the attendee list is still collected.
+    pub attribute_speakers: bool,
+    /// Keep the assignees of to-dos even when speakers are not attributed
+    pub keep_todo_assignees: bool,
+}
+
+impl Default for SpeakerAttribution {
+    fn default() -> Self {
+        Self {
+            attribute_speakers: true,
+            keep_todo_assignees: false,
+        }
+    }
+}
+
+/// Names to remove from the bullets: the attendees and the people holding a
+/// meeting role, each with its parts, longest first so full names go first
+pub fn attribution_names(key_facts: &[&KeyFact]) -> Vec<String> {
+    let mut names = Vec::new();
+    for key_facts in key_facts {
+        let attendees = key_facts
+            .attendees
+            .iter()
+            .flatten()
+            .map(|a| a.name.as_str());
+        let roles = [
+            &key_facts.responisible_for_moderation,
+            &key_facts.responisible_for_protocol,
+            &key_facts.responisible_for_timekeeping,
+        ]
+        .into_iter()
+        .flatten()
+        .map(String::as_str);
+
+        for name in attendees.chain(roles) {
+            let name = name.trim();
+            names.push(name.to_string());
+            names.extend(
+                name.split_whitespace()
+                    .filter(|part| part.chars().count() >= MIN_NAME_PART_CHARS)
+                    .filter(|part| part.chars().any(char::is_alphabetic))
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    names.retain(|name| !name.is_empty());
+    names.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    names.dedup();
+    names
+}
+
+fn is_word_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Length of an attendee id like `[3]` at the start of `text`
+fn attendee_id_len(text: &str) -> Option<usize> {
+    let digits = text.strip_prefix('[')?;
+    let digit_count = digits.chars().take_while(char::is_ascii_digit).count();
+    (digit_count > 0 && digits[digit_count..].starts_with(']')).then_some(digit_count + 2)
+}
+
+/// Length of a name from `names` at the start of `text`, only as a whole word
+fn name_len(text: &str, names: &[String]) -> Option<usize> {
+    names
+        .iter()
+        .find(|name| {
+            text.starts_with(name.as_str()) && !is_word_char(text[name.len()..].chars().next())
+        })
+        .map(|name| {
+            // "Anna's" and "Anna’s" go with the name
+            let rest = &text[name.len()..];
+            let possessive = ["'s", "’s"].iter().find(|suffix| {
+                rest.starts_with(*suffix) && !is_word_char(rest[suffix.len()..].chars().next())
+            });
+            name.len() + possessive.map_or(0, |suffix| suffix.len())
+        })
+}
+
+/// Join what is left after the attributions were cut out
+fn tidy(text: &str) -> String {
+    let removed = REMOVED.to_string();
+    let capitalize = text.starts_with(REMOVED);
+    let mut text = text.to_string();
+
+    // "Anna und [2]" is one attribution
+    loop {
+        let before = text.len();
+        for connector in [" und ", " and ", ", ", " & ", "/", ""] {
+            text = text.replace(&format!("{}{}{}", removed, connector, removed), &removed);
+        }
+        if text.len() == before {
+            break;
+        }
+    }
+    // `\0` is `REMOVED`
+    for (pattern, replacement) in [("(\0)", ""), ("\0:", ""), ("\0 ", "")] {
+        text = text.replace(pattern, replacement);
+    }
+    text = text.replace(REMOVED, "");
+
+    let text = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" ,", ",")
+        .replace(" .", ".")
+        .replace("()", "");
+    let text = text
+        .trim_start_matches(|c: char| matches!(c, ',' | ':' | ';' | '-') || c.is_whitespace())
+        .trim_end();
+
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if capitalize => first.to_uppercase().chain(chars).collect(),
+        _ => text.to_string(),
+    }
+}
+
+/// Remove attendee ids like `[1]` and the given names from `text`. Names
+/// only match as whole words, so "Anna" leaves "Annahme" alone.
+pub fn scrub_attributions(text: &str, names: &[String]) -> String {
+    let mut scrubbed = String::with_capacity(text.len());
+    let mut changed = false;
+    let mut previous = None;
+    let mut index = 0;
+
+    while index < text.len() {
+        let rest = &text[index..];
+        let attribution = attendee_id_len(rest).or_else(|| {
+            if is_word_char(previous) {
+                None
+            } else {
+                name_len(rest, names)
+            }
+        });
+
+        match attribution {
+            Some(len) => {
+                scrubbed.push(REMOVED);
+                changed = true;
+                previous = None;
+                index += len;
+            }
+            None => {
+                let c = rest.chars().next().expect("index is within the text");
+                scrubbed.push(c);
+                previous = Some(c);
+                index += c.len_utf8();
+            }
+        }
+    }
+
+    if changed {
+        tidy(&scrubbed)
+    } else {
+        text.to_string()
+    }
+}
+
+fn scrub_topics(topics: &mut [Topic], names: &[String]) {
+    for topic in topics {
+        for bullet in &mut topic.bullet_points {
+            *bullet = scrub_attributions(bullet, names);
+        }
+        topic.bullet_points.retain(|bullet| !bullet.is_empty());
+        if let Some(sub_topics) = topic.sub_topics.as_mut() {
+            scrub_topics(sub_topics, names);
+        }
+    }
+}
+
+fn scrub_todos(todos: &mut [ToDo], names: &[String], keep_assignees: bool) {
+    for todo in todos {
+        todo.task = scrub_attributions(&todo.task, names);
+        if !keep_assignees {
+            todo.assignees = None;
+        }
+    }
+}
+
+/// Remove attributions from a chunk summary. Its key facts stay as they are.
+pub fn anonymize_chunk_summary(
+    summary: &mut FirstSummaryFormat,
+    known: &KeyFact,
+    attribution: &SpeakerAttribution,
+) {
+    let names = attribution_names(&[known, &summary.key_facts]);
+    scrub_topics(&mut summary.topics, &names);
+    if let Some(todos) = summary.todos.as_mut() {
+        scrub_todos(todos, &names, attribution.keep_todo_assignees);
+    }
+}
+
+/// Remove attributions from the final summary, so `to_markdown` lists the
+/// to-dos without assignees unless they are kept. Its key facts stay as they are.
+pub fn anonymize_final_summary(
+    summary: &mut FinalSummaryFormat,
+    known: &KeyFact,
+    attribution: &SpeakerAttribution,
+) {
+    let names = attribution_names(&[known, &summary.key_facts]);
+    summary.summary = scrub_attributions(&summary.summary, &names);
+    scrub_topics(&mut summary.topics, &names);
+    scrub_todos(&mut summary.todos, &names, attribution.keep_todo_assignees);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::{Attendee, MeetingToMarkdown, Title};
+
+    fn key_facts() -> KeyFact {
+        KeyFact {
+            responisible_for_moderation: Some("Clara Weber".to_string()),
+            attendees: Some(vec![
+                Attendee {
+                    id: 1,
+                    name: "Anna Schmidt".to_string(),
+                },
+                Attendee {
+                    id: 2,
+                    name: "Ben".to_string(),
+                },
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn scrub(text: &str) -> String {
+        scrub_attributions(text, &attribution_names(&[&key_facts()]))
+    }
+
+    #[test]
+    fn test_ids_and_names_are_removed() {
+        assert_eq!(scrub("[1] fragt nach dem Budget"), "Fragt nach dem Budget");
+        assert_eq!(scrub("[1]: Budget reicht nicht"), "Budget reicht nicht");
+        assert_eq!(
+            scrub("Anna Schmidt schlägt eine Umfrage vor"),
+            "Schlägt eine Umfrage vor"
+        );
+        assert_eq!(
+            scrub("Vorschlag von Schmidt angenommen"),
+            "Vorschlag von angenommen"
+        );
+        assert_eq!(scrub("Anna und [2] sind dagegen"), "Sind dagegen");
+        assert_eq!(scrub("Raum 3 ist gebucht (Ben)"), "Raum 3 ist gebucht");
+        assert_eq!(
+            scrub("Clara Weber's agenda was accepted"),
+            "Agenda was accepted"
+        );
+    }
+
+    #[test]
+    fn test_scrubbing_is_word_boundary_safe() {
+        // Names inside other words, other bracketed text and lowercase words stay
+        for text in [
+            "Annahme: Das Budget reicht",
+            "Siehe Abschnitt [a] und [12a]",
+            "Wir laden Benjamin ein",
+            "Schmidts Vorlage fehlt noch",
+        ] {
+            assert_eq!(scrub(text), text);
+        }
+        assert_eq!(scrub(""), "");
+    }
+
+    #[test]
+    fn test_short_name_parts_are_not_removed() {
+        let key_facts = KeyFact {
+            attendees: Some(vec![Attendee {
+                id: 1,
+                name: "Jo Li".to_string(),
+            }]),
+            ..Default::default()
+        };
+        let names = attribution_names(&[&key_facts]);
+        assert_eq!(names, vec!["Jo Li".to_string()]);
+        assert_eq!(
+            scrub_attributions("Jo Li bringt Kaffee, Jo fährt", &names),
+            "Bringt Kaffee, Jo fährt"
+        );
+    }
+
+    #[test]
+    fn test_final_summary_is_anonymized() {
+        let mut summary = FinalSummaryFormat {
+            title: Title {
+                emoji: "📋".to_string(),
+                text: "Teamtreffen".to_string(),
+            },
+            key_facts: key_facts(),
+            summary: "Anna Schmidt stellt den Haushalt vor.".to_string(),
+            topics: vec![Topic {
+                title: "Haushalt".to_string(),
+                bullet_points: vec!["[1] fragt nach Annahmen".to_string(), "[2]".to_string()],
+                sub_topics: Some(vec![Topic {
+                    title: "Raum".to_string(),
+                    bullet_points: vec!["Ben bucht den Raum".to_string()],
+                    sub_topics: None,
+                }]),
+            }],
+            todos: vec![ToDo {
+                assignees: Some(vec!["Ben".to_string()]),
+                task: "Ben verschickt das Protokoll".to_string(),
+            }],
+        };
+
+        let mut kept = summary.clone();
+        let attribution = SpeakerAttribution {
+            attribute_speakers: false,
+            keep_todo_assignees: true,
+        };
+        anonymize_final_summary(&mut kept, &KeyFact::default(), &attribution);
+        assert_eq!(kept.todos[0].assignees, Some(vec!["Ben".to_string()]));
+
+        let attribution = SpeakerAttribution {
+            attribute_speakers: false,
+            ..Default::default()
+        };
+        anonymize_final_summary(&mut summary, &KeyFact::default(), &attribution);
+        assert_eq!(summary.summary, "Stellt den Haushalt vor.");
+        assert_eq!(summary.topics[0].bullet_points, vec!["Fragt nach Annahmen"]);
+        assert_eq!(
+            summary.topics[0].sub_topics.as_ref().unwrap()[0].bullet_points,
+            vec!["Bucht den Raum"]
+        );
+        assert_eq!(summary.todos[0].task, "Verschickt das Protokoll");
+        // The attendees are still listed
+        assert_eq!(
+            serde_json::to_value(&summary.key_facts).unwrap(),
+            serde_json::to_value(key_facts()).unwrap()
+        );
+
+        let markdown = summary.to_markdown();
+        assert!(markdown.contains("  - Anna Schmidt\n"));
+        assert!(markdown.ends_with("### Offen\n- Verschickt das Protokoll\n"));
+        assert!(!markdown.contains("### Ben"));
+    }
+}
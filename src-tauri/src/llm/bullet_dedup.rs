@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::llm::models::{FinalSummaryFormat, Topic};
+use crate::llm::topic_merge::STOP_WORDS;
+
+/// Words that change nothing about a bullet's content, adjacent chunks often
+/// phrase the same sentence with and without them
+const FILLER_WORDS: &[&str] = &[
+    "also",
+    "auch",
+    "bereits",
+    "dann",
+    "denn",
+    "doch",
+    "eben",
+    "eigentlich",
+    "einfach",
+    "etwa",
+    "ganz",
+    "gerade",
+    "halt",
+    "ja",
+    "jetzt",
+    "mal",
+    "nochmal",
+    "noch",
+    "nun",
+    "schon",
+    "sehr",
+    "so",
+    "wirklich",
+    "wohl",
+    "already",
+    "just",
+    "really",
+    "then",
+    "very",
+];
+/// A bullet with one of these says the opposite of the same bullet without it
+const NEGATIONS: &[&str] = &[
+    "nicht", "kein", "keine", "keinen", "nie", "not", "no", "never",
+];
+
+/// Dropping repeated bullets from the final summary before it is saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BulletDedupConfig {
+    pub enabled: bool,
+    /// Share of the words two bullets have in common (of the words in either)
+    /// from which the later one is dropped
+    pub token_overlap: f64,
+}
+
+impl Default for BulletDedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            token_overlap: 0.8,
+        }
+    }
+}
+
+/// Lowercase words without punctuation, emoji, stop and filler words
+fn bullet_words(bullet: &str) -> Vec<String> {
+    bullet
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| {
+            !word.is_empty() && !STOP_WORDS.contains(word) && !FILLER_WORDS.contains(word)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Equal words, or the words two bullets share make up `token_overlap`
+/// of all their words, both are negated or neither is, and both name the
+/// same numbers. An amount or year is what sets such bullets apart.
+fn is_near_duplicate(a: &[String], b: &[String], token_overlap: f64) -> bool {
+    if a == b {
+        return true;
+    }
+    let negated = |words: &[String]| words.iter().any(|word| NEGATIONS.contains(&word.as_str()));
+    if negated(a) != negated(b) {
+        return false;
+    }
+    let numbers = |words: &[String]| -> HashSet<String> {
+        words
+            .iter()
+            .filter(|word| word.chars().any(|c| c.is_ascii_digit()))
+            .cloned()
+            .collect()
+    };
+    if numbers(a) != numbers(b) {
+        return false;
+    }
+    let a: HashSet<&String> = a.iter().collect();
+    let b: HashSet<&String> = b.iter().collect();
+    let union = a.union(&b).count();
+    union > 0 && a.intersection(&b).count() as f64 / union as f64 >= token_overlap
+}
+
+fn dedup_topic(
+    topic: &mut Topic,
+    seen: &mut Vec<Vec<String>>,
+    config: &BulletDedupConfig,
+) -> usize {
+    let before = topic.bullet_points.len();
+    topic.bullet_points.retain(|bullet| {
+        let words = bullet_words(bullet);
+        // Nothing left to compare, e.g. a bullet of only emoji
+        if words.is_empty() {
+            return true;
+        }
+        if seen
+            .iter()
+            .any(|other| is_near_duplicate(&words, other, config.token_overlap))
+        {
+            return false;
+        }
+        seen.push(words);
+        true
+    });
+
+    let mut removed = before - topic.bullet_points.len();
+    for sub_topic in topic.sub_topics.iter_mut().flatten() {
+        removed += dedup_topic(sub_topic, seen, config);
+    }
+    removed
+}
+
+/// Drop bullets that repeat an earlier one anywhere in the summary, in the
+/// order topics and their sub topics are shown. Returns how many were dropped.
+pub fn dedup_bullets(summary: &mut FinalSummaryFormat, config: &BulletDedupConfig) -> usize {
+    if !config.enabled {
+        return 0;
+    }
+    let mut seen = Vec::new();
+    summary
+        .topics
+        .iter_mut()
+        .map(|topic| dedup_topic(topic, &mut seen, config))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::Title;
+
+    fn topic(title: &str, bullets: &[&str], sub_topics: Option<Vec<Topic>>) -> Topic {
+        Topic {
+            title: title.to_string(),
+            bullet_points: bullets.iter().map(|b| b.to_string()).collect(),
+            sub_topics,
+        }
+    }
+
+    fn summary(topics: Vec<Topic>) -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📅".to_string(),
+                text: "Vollversammlung".to_string(),
+            },
+            key_facts: Default::default(),
+            summary: String::new(),
+            topics,
+            todos: Vec::new(),
+        }
+    }
+
+    fn bullets(topic: &Topic) -> Vec<&str> {
+        topic.bullet_points.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_filler_words_do_not_make_a_new_bullet() {
+        let mut summary = summary(vec![
+            topic(
+                "Haushalt",
+                &[
+                    "Der Haushalt wird im März beschlossen.",
+                    "Die Kürzungen betreffen Reisekosten.",
+                ],
+                None,
+            ),
+            topic(
+                "Finanzen",
+                &[
+                    "💶 Der Haushalt wird dann ja auch im März beschlossen!",
+                    "Anna verschickt das Protokoll bis spätestens Freitag.",
+                    "Anna verschickt das Protokoll bis Freitag",
+                ],
+                None,
+            ),
+        ]);
+
+        let removed = dedup_bullets(&mut summary, &BulletDedupConfig::default());
+        assert_eq!(removed, 2);
+        assert_eq!(
+            bullets(&summary.topics[0]),
+            vec![
+                "Der Haushalt wird im März beschlossen.",
+                "Die Kürzungen betreffen Reisekosten."
+            ]
+        );
+        // The first occurrence is kept, even if it is the longer one
+        assert_eq!(
+            bullets(&summary.topics[1]),
+            vec!["Anna verschickt das Protokoll bis spätestens Freitag."]
+        );
+    }
+
+    #[test]
+    fn test_sub_topics_take_part() {
+        let mut summary = summary(vec![topic(
+            "Budget",
+            &["Der Entwurf liegt vor."],
+            Some(vec![topic(
+                "Budget 2025",
+                &[
+                    "Der Entwurf liegt jetzt schon vor",
+                    "Budget 2025 beschlossen",
+                ],
+                None,
+            )]),
+        )]);
+
+        assert_eq!(
+            dedup_bullets(&mut summary, &BulletDedupConfig::default()),
+            1
+        );
+        let sub_topic = &summary.topics[0].sub_topics.as_ref().unwrap()[0];
+        assert_eq!(bullets(sub_topic), vec!["Budget 2025 beschlossen"]);
+    }
+
+    #[test]
+    fn test_different_content_is_kept() {
+        let original = summary(vec![topic(
+            "Budget",
+            &[
+                "Budget 2024 beschlossen",
+                "Budget 2025 beschlossen",
+                "Raum 3 wird renoviert",
+                "Raum 4 wird nicht renoviert",
+                "Raum 3 wird nicht renoviert",
+                "Budget 5.000 € für 2024",
+                "Budget 7.000 € für 2025",
+                "Der Vorstand beantragt 5.000 € für die Renovierung von Raum 3 im Jahr 2025",
+                "Der Vorstand beantragt 7.000 € für die Renovierung von Raum 3 im Jahr 2025",
+                "✅",
+                "✅",
+            ],
+            None,
+        )]);
+
+        let mut deduped = original.clone();
+        assert_eq!(
+            dedup_bullets(&mut deduped, &BulletDedupConfig::default()),
+            0
+        );
+        assert_eq!(
+            deduped.topics[0].bullet_points,
+            original.topics[0].bullet_points
+        );
+
+        let mut disabled = summary(vec![topic("Budget", &["Entwurf", "Entwurf"], None)]);
+        let config = BulletDedupConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(dedup_bullets(&mut disabled, &config), 0);
+        assert_eq!(disabled.topics[0].bullet_points.len(), 2);
+    }
+}
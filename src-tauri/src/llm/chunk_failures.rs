@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::llm::error::{LlmError, LlmResult};
+
+/// A chunk whose summary still failed after all retries. It is missing from
+/// the final summary until `regenerate_chunk_summary` succeeds for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedChunk {
+    /// Index as used by `regenerate_chunk_summary`, starting at 0
+    pub chunk_index: usize,
+    pub error: String,
+}
+
+/// Keeps track of the failed chunks of one run. Without `tolerate` the first
+/// failure aborts the run, like before failures could be tolerated.
+pub struct ChunkFailures {
+    tolerate: bool,
+    failed: Vec<FailedChunk>,
+    last_error: Option<LlmError>,
+}
+
+impl ChunkFailures {
+    pub fn new(tolerate: bool) -> Self {
+        Self {
+            tolerate,
+            failed: Vec::new(),
+            last_error: None,
+        }
+    }
+
+    /// The summary of a chunk, `None` when it failed and is skipped
+    pub fn check<T>(&mut self, chunk_index: usize, result: LlmResult<T>) -> LlmResult<Option<T>> {
+        match result {
+            Ok(summary) => Ok(Some(summary)),
+            // Cancelling stops the run, it doesn't skip the chunk
+            Err(e) if self.tolerate && !matches!(e, LlmError::Cancelled) => {
+                println!("⚠️  Chunk {} failed, skipping it: {}", chunk_index + 1, e);
+                self.failed.push(FailedChunk {
+                    chunk_index,
+                    error: e.to_string(),
+                });
+                self.last_error = Some(e);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The skipped chunks. Fails with the last error when not a single chunk
+    /// could be summarized, there is nothing to combine then.
+    pub fn finish(self, chunk_count: usize) -> LlmResult<Vec<FailedChunk>> {
+        match self.last_error {
+            Some(e) if self.failed.len() >= chunk_count => Err(e),
+            _ => Ok(self.failed),
+        }
+    }
+}
+
+/// Indices of the chunks missing from a summary
+pub fn missing_chunk_indices(failed: &[FailedChunk]) -> Vec<usize> {
+    failed.iter().map(|chunk| chunk.chunk_index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::service::TextGenerator;
+
+    /// Fails the chunks at the given indices, the chunk text is its index
+    struct FailingChunks(Vec<usize>);
+
+    impl TextGenerator for FailingChunks {
+        async fn generate(
+            &self,
+            _system_prompt: &str,
+            user_prompt: &str,
+            _structure: Option<schemars::Schema>,
+        ) -> LlmResult<String> {
+            let index: usize = user_prompt.parse().unwrap();
+            if self.0.contains(&index) {
+                return Err(LlmError::TimeoutError(format!("chunk {} timed out", index)));
+            }
+            Ok(format!("summary {}", index))
+        }
+    }
+
+    /// The chunk loop of a summarization run
+    async fn summarize(
+        generator: &FailingChunks,
+        chunk_count: usize,
+        tolerate: bool,
+    ) -> LlmResult<(Vec<String>, Vec<FailedChunk>)> {
+        let mut failures = ChunkFailures::new(tolerate);
+        let mut summaries = Vec::new();
+        for i in 0..chunk_count {
+            let result = generator.generate("system", &i.to_string(), None).await;
+            if let Some(summary) = failures.check(i, result)? {
+                summaries.push(summary);
+            }
+        }
+        Ok((summaries, failures.finish(chunk_count)?))
+    }
+
+    #[tokio::test]
+    async fn test_failed_chunks_are_skipped_and_reported() {
+        let generator = FailingChunks(vec![1, 3]);
+        let (summaries, failed) = summarize(&generator, 5, true).await.unwrap();
+        assert_eq!(summaries, vec!["summary 0", "summary 2", "summary 4"]);
+        assert_eq!(missing_chunk_indices(&failed), vec![1, 3]);
+        assert_eq!(failed[0].error, "Timeout error: chunk 1 timed out");
+    }
+
+    #[tokio::test]
+    async fn test_failures_abort_unless_tolerated() {
+        let generator = FailingChunks(vec![2]);
+        assert!(matches!(
+            summarize(&generator, 4, false).await,
+            Err(LlmError::TimeoutError(_))
+        ));
+
+        let (summaries, failed) = summarize(&FailingChunks(Vec::new()), 2, true)
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert!(failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_all_chunks_failing_is_an_error() {
+        let generator = FailingChunks(vec![0, 1, 2]);
+        match summarize(&generator, 3, true).await {
+            Err(LlmError::TimeoutError(message)) => assert_eq!(message, "chunk 2 timed out"),
+            other => panic!("expected the last chunk error, got {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -1,30 +1,208 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::llm::bullet_dedup::BulletDedupConfig;
+use crate::llm::prompts::Language;
+use crate::llm::providers::{ANTHROPIC_DEFAULT_ENDPOINT, OPENAI_DEFAULT_ENDPOINT};
+use crate::llm::text_processing::{
+    plan_chunk_size, ChunkPlan, CHUNK_PROMPT_RESERVE_TOKENS, TARGET_MAX_CHUNKS,
+};
+use crate::llm::topic_merge::TopicMergeConfig;
+use crate::meeting::write_atomic;
+
+const LLM_CONFIG_FILE_NAME: &str = "llm_config.json";
+
+/// Which API answers the prompts. Cloud providers need an API key in the keyring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    #[default]
+    Ollama,
+    OpenAi,
+    Anthropic,
+    /// Any server speaking OpenAI's chat completions API at `external_endpoint`,
+    /// e.g. LM Studio or vLLM. The API key is optional.
+    #[serde(rename = "openai_compatible")]
+    OpenAiCompatible,
+}
+
+impl LlmProvider {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LlmProvider::Ollama => "Ollama",
+            LlmProvider::OpenAi => "OpenAI",
+            LlmProvider::Anthropic => "Anthropic",
+            LlmProvider::OpenAiCompatible => "OpenAI-compatible server",
+        }
+    }
+
+    /// Keyring entry of the API key, `None` for providers without one
+    pub fn key_name(&self) -> Option<&'static str> {
+        match self {
+            LlmProvider::Ollama => None,
+            LlmProvider::OpenAi => Some("openai"),
+            LlmProvider::Anthropic => Some("anthropic"),
+            LlmProvider::OpenAiCompatible => Some("openai_compatible"),
+        }
+    }
+}
+
+impl fmt::Display for LlmProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LlmProvider::Ollama => "ollama",
+            LlmProvider::OpenAi => "openai",
+            LlmProvider::Anthropic => "anthropic",
+            LlmProvider::OpenAiCompatible => "openai_compatible",
+        })
+    }
+}
+
+impl FromStr for LlmProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "ollama" => Ok(LlmProvider::Ollama),
+            "openai" => Ok(LlmProvider::OpenAi),
+            "anthropic" => Ok(LlmProvider::Anthropic),
+            "openai_compatible" => Ok(LlmProvider::OpenAiCompatible),
+            other => Err(format!("Unsupported provider: {}", other)),
+        }
+    }
+}
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct LlmConfig {
     pub use_external_api: bool,
+    #[serde(default)]
+    pub provider: LlmProvider,
+    /// Ollama or OpenAI-compatible server, cloud providers always use their
+    /// public endpoint
     pub external_endpoint: String,
     pub external_model: String,
     pub chunk_size: usize,
     pub max_retries: u32,
     pub timeout_seconds: u64,
+    /// Tag meetings with keywords from the final summary, costs one extra LLM call
+    #[serde(default = "default_extract_keywords")]
+    pub extract_keywords: bool,
+    /// Shorter transcripts are rejected before any LLM call
+    #[serde(default = "default_min_transcript_chars")]
+    pub min_transcript_chars: usize,
+    /// Clustering of similar chunk topics before the final summary call
+    #[serde(default)]
+    pub topic_merge: TopicMergeConfig,
+    /// Adapt `chunk_size` per transcript so the chunk summaries stay few
+    /// enough to combine and every chunk fits into the context window
+    #[serde(default)]
+    pub auto_chunk_sizing: bool,
+    /// Context window of the model, only used for automatic chunk sizing
+    #[serde(default = "default_context_window_tokens")]
+    pub context_window_tokens: usize,
+    /// Skip chunks that still fail after all retries instead of aborting the
+    /// summary, they are listed as missing in the result
+    #[serde(default)]
+    pub tolerate_chunk_failures: bool,
+    /// Dropping bullets that repeat across topics of the final summary
+    #[serde(default)]
+    pub bullet_dedup: BulletDedupConfig,
+    /// Send Ollama one merged prompt through `/api/generate`, for servers
+    /// too old for `/api/chat`
+    #[serde(default)]
+    pub ollama_legacy_generate: bool,
+    /// Show Ollama's answer as it is generated. Off sends one request per
+    /// answer and waits for all of it, which copes better with flaky networks.
+    #[serde(default = "default_stream_tokens")]
+    pub stream_tokens: bool,
+    /// Language of the summaries of meetings that don't choose their own.
+    /// Configs from before it was added keep the German summaries.
+    #[serde(default)]
+    pub language: Language,
+}
+
+fn default_extract_keywords() -> bool {
+    true
+}
+
+fn default_stream_tokens() -> bool {
+    true
+}
+
+pub fn default_min_transcript_chars() -> usize {
+    200
+}
+
+pub fn default_context_window_tokens() -> usize {
+    8_192
 }
 
 impl Default for LlmConfig {
     fn default() -> Self {
         Self {
             use_external_api: true,
+            provider: LlmProvider::default(),
             external_endpoint: "http://localhost:11434".to_string(),
             external_model: "llama3.1".to_string(),
             chunk_size: 10_000,
             max_retries: 3,
             timeout_seconds: 120,
+            extract_keywords: default_extract_keywords(),
+            min_transcript_chars: default_min_transcript_chars(),
+            topic_merge: TopicMergeConfig::default(),
+            auto_chunk_sizing: false,
+            context_window_tokens: default_context_window_tokens(),
+            tolerate_chunk_failures: false,
+            bullet_dedup: BulletDedupConfig::default(),
+            ollama_legacy_generate: false,
+            stream_tokens: default_stream_tokens(),
+            language: Language::default(),
         }
     }
 }
 
 impl LlmConfig {
-    #[allow(dead_code)]
+    /// Load the saved config. A missing or corrupt file is replaced with the defaults.
+    pub fn load(path: &Path) -> Self {
+        let loaded = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+        match loaded {
+            Ok(config) => config,
+            Err(e) => {
+                if path.exists() {
+                    println!("Warning: Failed to read LLM config, using defaults: {}", e);
+                }
+                let config = Self::default();
+                let written = serde_json::to_string_pretty(&config)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+                if let Err(e) = written {
+                    println!("Warning: Failed to write LLM config: {}", e);
+                }
+                config
+            }
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize LLM config: {}", e))?;
+        write_atomic(path, json)
+            .await
+            .map_err(|e| format!("Failed to write LLM config: {}", e))
+    }
+
+    pub fn endpoint(&self) -> &str {
+        match self.provider {
+            LlmProvider::Ollama | LlmProvider::OpenAiCompatible => &self.external_endpoint,
+            LlmProvider::OpenAi => OPENAI_DEFAULT_ENDPOINT,
+            LlmProvider::Anthropic => ANTHROPIC_DEFAULT_ENDPOINT,
+        }
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         if self.external_endpoint.is_empty() {
             return Err("External endpoint cannot be empty".to_string());
@@ -50,6 +228,13 @@ impl LlmConfig {
             return Err("Timeout too large (max 1 hour)".to_string());
         }
 
+        if self.auto_chunk_sizing && self.context_window_tokens <= CHUNK_PROMPT_RESERVE_TOKENS {
+            return Err(format!(
+                "Context window too small for automatic chunk sizing (min {} tokens)",
+                CHUNK_PROMPT_RESERVE_TOKENS + 1
+            ));
+        }
+
         // Validate URL format
         if !self.external_endpoint.starts_with("http://")
             && !self.external_endpoint.starts_with("https://")
@@ -60,6 +245,25 @@ impl LlmConfig {
         Ok(())
     }
 
+    /// Chunk size for a transcript, `None` when automatic sizing is off and
+    /// `chunk_size` is used as is
+    pub fn chunk_plan(&self, transcript: &str) -> Option<ChunkPlan> {
+        self.auto_chunk_sizing.then(|| {
+            plan_chunk_size(
+                transcript.chars().count(),
+                self.chunk_size,
+                self.context_window_tokens,
+                TARGET_MAX_CHUNKS,
+            )
+        })
+    }
+
+    /// Chunk size a summarization of `transcript` splits it into
+    pub fn chunk_size_for(&self, transcript: &str) -> usize {
+        self.chunk_plan(transcript)
+            .map_or(self.chunk_size, |plan| plan.chunk_size)
+    }
+
     #[allow(dead_code)]
     pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
         self.chunk_size = chunk_size;
@@ -79,13 +283,72 @@ impl LlmConfig {
     }
 }
 
+pub fn get_llm_config_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(LLM_CONFIG_FILE_NAME)
+}
+
 pub const DEFAULT_CONTEXT_SIZE: usize = 8096;
 pub const API_GENERATE_ENDPOINT: &str = "/api/generate";
+pub const API_CHAT_ENDPOINT: &str = "/api/chat";
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_config_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = get_llm_config_path(dir.path());
+
+        // First start writes the defaults
+        let config = LlmConfig::load(&path);
+        assert_eq!(config.external_model, LlmConfig::default().external_model);
+        assert!(path.exists());
+
+        let config = LlmConfig {
+            provider: LlmProvider::Anthropic,
+            external_model: "claude-sonnet".to_string(),
+            tolerate_chunk_failures: true,
+            ..config
+        };
+        config.save(&path).await.unwrap();
+        let loaded = LlmConfig::load(&path);
+        assert_eq!(loaded.provider, LlmProvider::Anthropic);
+        assert_eq!(loaded.external_model, "claude-sonnet");
+        assert!(loaded.tolerate_chunk_failures);
+    }
+
+    #[test]
+    fn test_language_defaults_to_german() {
+        let mut json = serde_json::to_value(LlmConfig {
+            language: Language::English,
+            ..LlmConfig::default()
+        })
+        .unwrap();
+        assert_eq!(json["language"], "en");
+
+        json.as_object_mut().unwrap().remove("language");
+        let config: LlmConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.language, Language::German);
+    }
+
+    #[test]
+    fn test_corrupt_config_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = get_llm_config_path(dir.path());
+        std::fs::write(&path, "{\"use_external_api\": tru").unwrap();
+
+        let config = LlmConfig::load(&path);
+        assert_eq!(
+            config.external_endpoint,
+            LlmConfig::default().external_endpoint
+        );
+        // The broken file is rewritten, the next start reads it cleanly
+        let rewritten: LlmConfig =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(rewritten.external_model, config.external_model);
+    }
+
     #[test]
     fn test_default_config_is_valid() {
         let config = LlmConfig::default();
@@ -119,6 +382,37 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_provider_endpoint() {
+        let mut config = LlmConfig::default();
+        assert_eq!(config.endpoint(), "http://localhost:11434");
+
+        config.provider = LlmProvider::Anthropic;
+        assert_eq!(config.endpoint(), ANTHROPIC_DEFAULT_ENDPOINT);
+
+        let provider: LlmProvider = serde_json::from_str("\"openai\"").unwrap();
+        assert_eq!(provider, LlmProvider::OpenAi);
+
+        config.provider = LlmProvider::OpenAiCompatible;
+        config.external_endpoint = "http://localhost:1234/v1".to_string();
+        assert_eq!(config.endpoint(), "http://localhost:1234/v1");
+
+        for provider in [
+            LlmProvider::Ollama,
+            LlmProvider::OpenAi,
+            LlmProvider::Anthropic,
+            LlmProvider::OpenAiCompatible,
+        ] {
+            let json = serde_json::to_string(&provider).unwrap();
+            assert_eq!(json, format!("\"{}\"", provider));
+            assert_eq!(
+                provider.to_string().parse::<LlmProvider>().unwrap(),
+                provider
+            );
+        }
+        assert!("gemini".parse::<LlmProvider>().is_err());
+    }
+
     #[test]
     fn test_builder_pattern() {
         let config = LlmConfig::default()
@@ -0,0 +1,415 @@
+use chrono::{DateTime, Utc};
+use schemars::schema_for;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::llm::{
+    endpoint_privacy::ensure_endpoint_confirmed,
+    error::{LlmError, LlmResult},
+    models::{DigestFormat, FinalSummaryFormat},
+    prompts::{Language, PromptManager},
+    service::{LlmService, TextGenerator},
+};
+use crate::meeting::read_meeting_metadata;
+use crate::AppState;
+
+/// A meeting that goes into the digest
+pub struct DigestMeeting {
+    pub meeting_id: String,
+    pub created_at: Option<String>,
+    pub summary: FinalSummaryFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedMeeting {
+    pub meeting_id: String,
+    pub reason: String,
+}
+
+/// Stored as `digest.json` next to the markdown
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DigestRecord {
+    pub created_at: String,
+    pub meeting_ids: Vec<String>,
+    pub skipped: Vec<SkippedMeeting>,
+    pub digest: DigestFormat,
+}
+
+/// Load the summaries of the selected meetings. Meetings without a usable
+/// summary are skipped instead of failing the digest.
+pub async fn load_digest_meetings(
+    uploads_dir: &Path,
+    meeting_ids: &[String],
+) -> (Vec<DigestMeeting>, Vec<SkippedMeeting>) {
+    let mut meetings = Vec::new();
+    let mut skipped = Vec::new();
+
+    for meeting_id in meeting_ids {
+        let meeting_dir = uploads_dir.join(meeting_id);
+        let summary_path = meeting_dir.join("summary.json");
+        if !summary_path.exists() {
+            skipped.push(SkippedMeeting {
+                meeting_id: meeting_id.clone(),
+                reason: "No summary".to_string(),
+            });
+            continue;
+        }
+
+        let summary = fs::read_to_string(&summary_path)
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<FinalSummaryFormat>(&json).map_err(|e| e.to_string())
+            });
+        match summary {
+            Ok(summary) => {
+                let created_at = read_meeting_metadata(&meeting_dir)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|metadata| metadata.created_at);
+                meetings.push(DigestMeeting {
+                    meeting_id: meeting_id.clone(),
+                    created_at,
+                    summary,
+                });
+            }
+            Err(e) => skipped.push(SkippedMeeting {
+                meeting_id: meeting_id.clone(),
+                reason: format!("Unreadable summary: {}", e),
+            }),
+        }
+    }
+
+    (meetings, skipped)
+}
+
+/// Titles, summaries and todos of every meeting, one section per meeting
+pub fn build_digest_prompt(meetings: &[DigestMeeting]) -> String {
+    let mut prompt = String::new();
+
+    for meeting in meetings {
+        prompt.push_str(&format!("## {}\n", meeting.summary.title.to_string()));
+        if let Some(created_at) = &meeting.created_at {
+            prompt.push_str(&format!("Date: {}\n", created_at));
+        }
+        prompt.push('\n');
+        prompt.push_str(meeting.summary.summary.trim());
+        prompt.push_str("\n\n");
+
+        if !meeting.summary.todos.is_empty() {
+            prompt.push_str("To-Dos:\n");
+            for todo in &meeting.summary.todos {
+                match todo.assignees.as_deref() {
+                    Some(assignees) if !assignees.is_empty() => {
+                        prompt.push_str(&format!("- {} ({})\n", todo.task, assignees.join(", ")))
+                    }
+                    _ => prompt.push_str(&format!("- {}\n", todo.task)),
+                }
+            }
+            prompt.push('\n');
+        }
+    }
+
+    prompt
+}
+
+pub fn digest_to_markdown(record: &DigestRecord) -> String {
+    let digest = &record.digest;
+    let mut markdown = format!("# Digest\n\n{}\n\n", digest.period_overview.trim());
+
+    if !digest.themes.is_empty() {
+        markdown.push_str("## Themes\n");
+        for theme in &digest.themes {
+            markdown.push_str(&format!("### {}\n{}\n", theme.title, theme.description));
+            if !theme.meetings.is_empty() {
+                markdown.push_str(&format!("- **Meetings:** {}\n", theme.meetings.join(", ")));
+            }
+            markdown.push('\n');
+        }
+    }
+
+    if !digest.open_todos.is_empty() {
+        markdown.push_str("## Open To-Dos\n");
+        for group in &digest.open_todos {
+            markdown.push_str(&format!("### {}\n", group.assignee));
+            for task in &group.tasks {
+                markdown.push_str(&format!("- {}\n", task));
+            }
+            markdown.push('\n');
+        }
+    }
+
+    if !record.skipped.is_empty() {
+        markdown.push_str("## Skipped Meetings\n");
+        for skipped in &record.skipped {
+            markdown.push_str(&format!("- {}: {}\n", skipped.meeting_id, skipped.reason));
+        }
+    }
+
+    markdown
+}
+
+/// Write `digest.json` and `digest.md` into `<digests_dir>/<timestamp>/`
+pub async fn save_digest(
+    digests_dir: &Path,
+    created: DateTime<Utc>,
+    record: &DigestRecord,
+    markdown: &str,
+) -> Result<PathBuf, String> {
+    let digest_dir = digests_dir.join(created.format("%Y%m%dT%H%M%S%3f").to_string());
+    fs::create_dir_all(&digest_dir)
+        .await
+        .map_err(|e| format!("Failed to create digest directory: {}", e))?;
+
+    let json = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize digest: {}", e))?;
+    fs::write(digest_dir.join("digest.json"), json)
+        .await
+        .map_err(|e| format!("Failed to save digest JSON: {}", e))?;
+    fs::write(digest_dir.join("digest.md"), markdown)
+        .await
+        .map_err(|e| format!("Failed to save digest markdown: {}", e))?;
+
+    Ok(digest_dir)
+}
+
+/// Build, generate and save a digest, returning its markdown
+pub async fn create_digest<G: TextGenerator>(
+    generator: &G,
+    language: &Language,
+    uploads_dir: &Path,
+    digests_dir: &Path,
+    meeting_ids: &[String],
+) -> LlmResult<String> {
+    let (meetings, skipped) = load_digest_meetings(uploads_dir, meeting_ids).await;
+    if meetings.is_empty() {
+        return Err(LlmError::FileError(
+            "None of the selected meetings has a summary".to_string(),
+        ));
+    }
+    println!(
+        "📰 Creating digest of {} meetings ({} skipped)",
+        meetings.len(),
+        skipped.len()
+    );
+
+    let response = generator
+        .generate(
+            PromptManager::digest(language),
+            &build_digest_prompt(&meetings),
+            Some(schema_for!(DigestFormat)),
+        )
+        .await?;
+    let digest: DigestFormat = serde_json::from_str(&response)
+        .map_err(|e| LlmError::ParseError(format!("Failed to parse digest JSON: {}", e)))?;
+
+    let created = Utc::now();
+    let record = DigestRecord {
+        created_at: created.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        meeting_ids: meetings.into_iter().map(|m| m.meeting_id).collect(),
+        skipped,
+        digest,
+    };
+    let markdown = digest_to_markdown(&record);
+    save_digest(digests_dir, created, &record, &markdown)
+        .await
+        .map_err(LlmError::FileError)?;
+
+    Ok(markdown)
+}
+
+#[tauri::command]
+pub async fn generate_digest(
+    app: AppHandle,
+    meeting_ids: Vec<String>,
+    language: Option<Language>,
+    allow_remote: Option<bool>,
+) -> Result<String, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+
+    let config = {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        state.llm_config.clone()
+    };
+    ensure_endpoint_confirmed(&app, &config, allow_remote.unwrap_or(false)).await?;
+    let llm_service = LlmService::from_config(&config)?;
+
+    create_digest(
+        &llm_service,
+        &language.unwrap_or(config.language),
+        &app_dir.join("uploads"),
+        &app_dir.join("digests"),
+        &meeting_ids,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::{KeyFact, Title, ToDo};
+    use crate::llm::service::stub::StubGenerator;
+
+    const DIGEST_RESPONSE: &str = r#"{
+        "period_overview": "Budget und Einstellungen bestimmten die Woche.",
+        "themes": [
+            {"title": "Budget", "description": "Kürzungen im Q3.", "meetings": ["Jour fixe", "Planung"]}
+        ],
+        "open_todos": [
+            {"assignee": "Anna", "tasks": ["Angebot prüfen"]}
+        ]
+    }"#;
+
+    fn summary(title: &str, todos: Vec<ToDo>) -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📅".to_string(),
+                text: title.to_string(),
+            },
+            key_facts: KeyFact {
+                responisible_for_moderation: None,
+                responisible_for_protocol: None,
+                responisible_for_timekeeping: None,
+                attendees: None,
+            },
+            summary: format!("Zusammenfassung von {}.", title),
+            topics: Vec::new(),
+            todos,
+        }
+    }
+
+    async fn write_meeting(uploads: &Path, meeting_id: &str, summary: Option<&FinalSummaryFormat>) {
+        let meeting_dir = uploads.join(meeting_id);
+        fs::create_dir_all(&meeting_dir).await.unwrap();
+        if let Some(summary) = summary {
+            fs::write(
+                meeting_dir.join("summary.json"),
+                serde_json::to_string(summary).unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_prompt_contains_titles_summaries_and_todos() {
+        let dir = tempfile::tempdir().unwrap();
+        let todos = vec![
+            ToDo {
+                assignees: Some(vec!["Anna".to_string(), "Ben".to_string()]),
+                task: "Angebot prüfen".to_string(),
+            },
+            ToDo {
+                assignees: None,
+                task: "Raum buchen".to_string(),
+            },
+        ];
+        write_meeting(
+            dir.path(),
+            "recording-1",
+            Some(&summary("Jour fixe", todos)),
+        )
+        .await;
+        write_meeting(
+            dir.path(),
+            "recording-2",
+            Some(&summary("Planung", Vec::new())),
+        )
+        .await;
+
+        let (meetings, skipped) =
+            load_digest_meetings(dir.path(), &ids(&["recording-1", "recording-2"])).await;
+        let prompt = build_digest_prompt(&meetings);
+
+        assert!(skipped.is_empty());
+        assert!(prompt.contains("## 📅 Jour fixe\n"));
+        assert!(prompt.contains("Zusammenfassung von Jour fixe."));
+        assert!(prompt.contains("- Angebot prüfen (Anna, Ben)\n"));
+        assert!(prompt.contains("- Raum buchen\n"));
+        assert!(prompt.contains("## 📅 Planung\n"));
+        // Meetings without todos get no empty to-do section
+        assert_eq!(prompt.matches("To-Dos:").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_digest_files_and_skipped_meetings() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let digests = dir.path().join("digests");
+        write_meeting(
+            &uploads,
+            "recording-1",
+            Some(&summary("Jour fixe", Vec::new())),
+        )
+        .await;
+        write_meeting(&uploads, "recording-2", None).await;
+
+        let generator = StubGenerator::responding(DIGEST_RESPONSE);
+        let markdown = create_digest(
+            &generator,
+            &Language::German,
+            &uploads,
+            &digests,
+            &ids(&["recording-1", "recording-2", "recording-3"]),
+        )
+        .await
+        .unwrap();
+
+        assert!(markdown.contains("### Budget\nKürzungen im Q3."));
+        assert!(markdown.contains("### Anna\n- Angebot prüfen"));
+        assert!(markdown.contains("- recording-2: No summary"));
+        assert!(markdown.contains("- recording-3: No summary"));
+
+        // digests/<timestamp>/digest.{json,md}
+        let mut entries = fs::read_dir(&digests).await.unwrap();
+        let digest_dir = entries.next_entry().await.unwrap().unwrap().path();
+        assert!(entries.next_entry().await.unwrap().is_none());
+
+        let saved_markdown = fs::read_to_string(digest_dir.join("digest.md"))
+            .await
+            .unwrap();
+        assert_eq!(saved_markdown, markdown);
+
+        let json = fs::read_to_string(digest_dir.join("digest.json"))
+            .await
+            .unwrap();
+        let record: DigestRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(record.meeting_ids, ids(&["recording-1"]));
+        assert_eq!(record.skipped.len(), 2);
+        assert_eq!(record.digest.open_todos[0].assignee, "Anna");
+    }
+
+    #[tokio::test]
+    async fn test_no_summaries_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let digests = dir.path().join("digests");
+        write_meeting(dir.path(), "recording-1", None).await;
+
+        let generator = StubGenerator::responding(DIGEST_RESPONSE);
+        let result = create_digest(
+            &generator,
+            &Language::German,
+            dir.path(),
+            &digests,
+            &ids(&["recording-1"]),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(generator.last_prompt.lock().unwrap().is_none());
+        assert!(!digests.exists());
+    }
+}
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use tauri::AppHandle;
+use tauri_plugin_http::reqwest::Url;
+
+use crate::llm::config::LlmConfig;
+use crate::llm::error::{LlmError, LlmResult};
+use crate::settings::{load_settings, save_settings};
+
+/// Where an LLM endpoint runs, as far as meeting content is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostLocation {
+    ThisMachine,
+    /// Private, link-local and `.local` addresses
+    LocalNetwork,
+    Remote,
+}
+
+/// Which endpoints may receive transcripts without asking first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EndpointPrivacy {
+    /// Treat the local network like this machine, e.g. an Ollama server on another PC
+    pub allow_local_network: bool,
+    /// Host names or addresses that never need a confirmation
+    pub trusted_hosts: Vec<String>,
+    /// Origins the user agreed to send meeting content to, see `endpoint_key`
+    pub confirmed_endpoints: Vec<String>,
+}
+
+impl Default for EndpointPrivacy {
+    fn default() -> Self {
+        Self {
+            allow_local_network: true,
+            trusted_hosts: Vec::new(),
+            confirmed_endpoints: Vec::new(),
+        }
+    }
+}
+
+fn classify_ipv4(ip: Ipv4Addr) -> HostLocation {
+    if ip.is_loopback() || ip.is_unspecified() {
+        HostLocation::ThisMachine
+    } else if ip.is_private() || ip.is_link_local() {
+        HostLocation::LocalNetwork
+    } else {
+        HostLocation::Remote
+    }
+}
+
+fn classify_ipv6(ip: Ipv6Addr) -> HostLocation {
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return classify_ipv4(ip);
+    }
+
+    let first_segment = ip.segments()[0];
+    let unique_local = first_segment & 0xfe00 == 0xfc00;
+    let link_local = first_segment & 0xffc0 == 0xfe80;
+    if ip.is_loopback() || ip.is_unspecified() {
+        HostLocation::ThisMachine
+    } else if unique_local || link_local {
+        HostLocation::LocalNetwork
+    } else {
+        HostLocation::Remote
+    }
+}
+
+/// Classify a host as it appears in a URL, IPv6 addresses may be in brackets
+pub fn classify_host(host: &str) -> HostLocation {
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => classify_ipv4(ip),
+        Ok(IpAddr::V6(ip)) => classify_ipv6(ip),
+        Err(_) if host == "localhost" || host.ends_with(".localhost") => HostLocation::ThisMachine,
+        Err(_) if host.ends_with(".local") => HostLocation::LocalNetwork,
+        Err(_) => HostLocation::Remote,
+    }
+}
+
+/// Consent is given per origin, so another path or model on the same server needs none
+pub fn endpoint_key(endpoint: &str) -> Option<String> {
+    let url = Url::parse(endpoint.trim()).ok()?;
+    url.host_str()?;
+    Some(url.origin().ascii_serialization())
+}
+
+impl EndpointPrivacy {
+    /// Whether sending meeting content to `endpoint` has to be confirmed first.
+    /// Endpoints that can't be parsed are never trusted.
+    pub fn needs_confirmation(&self, endpoint: &str) -> bool {
+        let Ok(url) = Url::parse(endpoint.trim()) else {
+            return true;
+        };
+        let Some(host) = url.host_str() else {
+            return true;
+        };
+
+        match classify_host(host) {
+            HostLocation::ThisMachine => return false,
+            HostLocation::LocalNetwork if self.allow_local_network => return false,
+            _ => {}
+        }
+
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let trusted = self
+            .trusted_hosts
+            .iter()
+            .any(|trusted| trusted.trim().eq_ignore_ascii_case(host));
+        let confirmed =
+            endpoint_key(endpoint).is_some_and(|key| self.confirmed_endpoints.contains(&key));
+        !trusted && !confirmed
+    }
+}
+
+/// Fail with `RemoteEndpointNotConfirmed` before meeting content would go to a
+/// remote endpoint the user hasn't agreed to. `allow_remote` agrees once.
+pub async fn ensure_endpoint_confirmed(
+    app: &AppHandle,
+    config: &LlmConfig,
+    allow_remote: bool,
+) -> LlmResult<()> {
+    if allow_remote {
+        return Ok(());
+    }
+
+    let privacy = load_settings(app)
+        .await
+        .map_err(LlmError::ConfigError)?
+        .endpoint_privacy;
+    let endpoint = config.endpoint();
+    if privacy.needs_confirmation(endpoint) {
+        return Err(LlmError::RemoteEndpointNotConfirmed {
+            endpoint: endpoint_key(endpoint).unwrap_or_else(|| endpoint.to_string()),
+        });
+    }
+    Ok(())
+}
+
+/// Remember or withdraw the consent to send meeting content to `endpoint`
+#[tauri::command]
+pub async fn set_llm_endpoint_consent(
+    app: AppHandle,
+    endpoint: &str,
+    confirmed: bool,
+) -> Result<(), String> {
+    let key = endpoint_key(endpoint).ok_or_else(|| format!("Invalid endpoint: {}", endpoint))?;
+    let mut settings = load_settings(&app).await?;
+    let confirmed_endpoints = &mut settings.endpoint_privacy.confirmed_endpoints;
+    confirmed_endpoints.retain(|existing| *existing != key);
+    if confirmed {
+        confirmed_endpoints.push(key);
+    }
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_endpoint_privacy(
+    app: AppHandle,
+    allow_local_network: bool,
+    trusted_hosts: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.endpoint_privacy.allow_local_network = allow_local_network;
+    settings.endpoint_privacy.trusted_hosts = trusted_hosts
+        .into_iter()
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .collect();
+    save_settings(&app, &settings).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_host() {
+        for host in [
+            "localhost",
+            "LOCALHOST",
+            "ollama.localhost",
+            "localhost.",
+            "127.0.0.1",
+            "127.8.9.10",
+            "0.0.0.0",
+            "::1",
+            "[::1]",
+            "[::ffff:127.0.0.1]",
+            "[::]",
+        ] {
+            assert_eq!(classify_host(host), HostLocation::ThisMachine, "{}", host);
+        }
+
+        for host in [
+            "10.0.0.5",
+            "172.16.0.1",
+            "172.31.255.255",
+            "192.168.1.20",
+            "169.254.3.4",
+            "[fd12:3456::1]",
+            "[fe80::1]",
+            "[::ffff:192.168.0.2]",
+            "gpu-box.local",
+        ] {
+            assert_eq!(classify_host(host), HostLocation::LocalNetwork, "{}", host);
+        }
+
+        for host in [
+            "api.openai.com",
+            "localhost.example.com",
+            "mylocalhost",
+            "172.32.0.1",
+            "8.8.8.8",
+            "[2001:db8::1]",
+            "[::ffff:8.8.8.8]",
+            "gpu-box",
+        ] {
+            assert_eq!(classify_host(host), HostLocation::Remote, "{}", host);
+        }
+    }
+
+    #[test]
+    fn test_needs_confirmation() {
+        let privacy = EndpointPrivacy::default();
+        assert!(!privacy.needs_confirmation("http://localhost:11434"));
+        assert!(!privacy.needs_confirmation("http://[::1]:11434"));
+        // The URL parser normalizes short and hex forms of 127.0.0.1
+        assert!(!privacy.needs_confirmation("http://127.1:11434"));
+        assert!(!privacy.needs_confirmation("http://0x7f000001:11434"));
+        assert!(!privacy.needs_confirmation("http://192.168.1.20:11434"));
+        assert!(privacy.needs_confirmation("https://api.openai.com/v1"));
+        assert!(privacy.needs_confirmation("http://localhost@evil.example.com"));
+        assert!(privacy.needs_confirmation("not a url"));
+
+        let privacy = EndpointPrivacy {
+            allow_local_network: false,
+            trusted_hosts: vec!["GPU-Box".to_string(), "fd00::7".to_string()],
+            confirmed_endpoints: vec!["https://api.openai.com".to_string()],
+        };
+        assert!(privacy.needs_confirmation("http://192.168.1.20:11434"));
+        assert!(!privacy.needs_confirmation("http://localhost:11434"));
+        assert!(!privacy.needs_confirmation("http://gpu-box:11434"));
+        assert!(!privacy.needs_confirmation("http://[fd00::7]:11434"));
+        assert!(!privacy.needs_confirmation("https://api.openai.com/v1"));
+        // Consent is per origin
+        assert!(privacy.needs_confirmation("http://api.openai.com/v1"));
+        assert!(privacy.needs_confirmation("https://api.openai.com:8443/v1"));
+    }
+
+    #[test]
+    fn test_endpoint_key() {
+        assert_eq!(
+            endpoint_key("https://API.openai.com/v1/").as_deref(),
+            Some("https://api.openai.com")
+        );
+        assert_eq!(
+            endpoint_key(" http://gpu-box:11434 ").as_deref(),
+            Some("http://gpu-box:11434")
+        );
+        assert_eq!(endpoint_key("gpu-box:11434"), None);
+        assert_eq!(endpoint_key("file:///tmp/x"), None);
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::error::{BusyError, ClaimError, InsufficientDiskSpace, StaleChunks, SummaryApproved};
+
 #[derive(Debug)]
 pub enum LlmError {
     NetworkError(String),
@@ -8,6 +10,33 @@ pub enum LlmError {
     ConfigError(String),
     TimeoutError(String),
     SerializationError(String),
+    /// The server doesn't know the configured model
+    ModelNotFound(String),
+    /// The prompt doesn't fit into the model's context window
+    ContextLengthExceeded(String),
+    /// The server answered with an error status
+    ServerError(String),
+    /// The server refused the request itself, e.g. a wrong API key
+    RequestRejected(String),
+    /// The transcript has too little text to summarize, usually silent or unreadable audio
+    TranscriptTooShort {
+        chars: usize,
+        distinct_words: usize,
+    },
+    /// Meeting content would leave this machine, the user has to agree first
+    RemoteEndpointNotConfirmed {
+        endpoint: String,
+    },
+    /// Checked before the summary is written, so no truncated files are left behind
+    InsufficientDiskSpace(InsufficientDiskSpace),
+    /// A summarization or a transcription of the same meeting is running
+    Busy(BusyError),
+    /// The saved chunk summaries don't fit the current transcript or chunk size
+    StaleChunks(StaleChunks),
+    /// The summary to replace was approved and superseding it wasn't asked for
+    SummaryApproved(SummaryApproved),
+    /// The user stopped the summarization
+    Cancelled,
 }
 
 impl fmt::Display for LlmError {
@@ -19,12 +48,85 @@ impl fmt::Display for LlmError {
             LlmError::ConfigError(msg) => write!(f, "Configuration error: {}", msg),
             LlmError::TimeoutError(msg) => write!(f, "Timeout error: {}", msg),
             LlmError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
+            LlmError::ModelNotFound(msg) => write!(
+                f,
+                "Model not available: {}. Check the model name in the settings.",
+                msg
+            ),
+            LlmError::ContextLengthExceeded(msg) => write!(
+                f,
+                "Context length exceeded: {}. Try a smaller chunk size.",
+                msg
+            ),
+            LlmError::ServerError(msg) => write!(f, "Server error: {}", msg),
+            LlmError::RequestRejected(msg) => write!(f, "Request rejected: {}", msg),
+            LlmError::TranscriptTooShort {
+                chars,
+                distinct_words,
+            } => write!(
+                f,
+                "Transcript too short to summarize ({} characters, {} distinct words). Check that the recording contains speech and transcribe it again.",
+                chars, distinct_words
+            ),
+            LlmError::RemoteEndpointNotConfirmed { endpoint } => write!(
+                f,
+                "Remote endpoint not confirmed: the meeting would be sent to {}, which is not on this machine. Confirm the endpoint to continue.",
+                endpoint
+            ),
+            LlmError::InsufficientDiskSpace(error) => write!(f, "{}", error),
+            LlmError::Busy(error) => write!(f, "{}", error),
+            LlmError::StaleChunks(error) => write!(f, "{}", error),
+            LlmError::SummaryApproved(error) => write!(f, "{}", error),
+            LlmError::Cancelled => write!(f, "Summarization cancelled"),
         }
     }
 }
 
 impl std::error::Error for LlmError {}
 
+impl LlmError {
+    /// Whether sending the same request again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            LlmError::NetworkError(_) | LlmError::TimeoutError(_) | LlmError::ServerError(_)
+        )
+    }
+}
+
+impl From<BusyError> for LlmError {
+    fn from(error: BusyError) -> Self {
+        LlmError::Busy(error)
+    }
+}
+
+impl From<ClaimError> for LlmError {
+    fn from(error: ClaimError) -> Self {
+        match error {
+            ClaimError::Busy(error) => LlmError::Busy(error),
+            ClaimError::Cancelled => LlmError::Cancelled,
+        }
+    }
+}
+
+impl From<StaleChunks> for LlmError {
+    fn from(error: StaleChunks) -> Self {
+        LlmError::StaleChunks(error)
+    }
+}
+
+impl From<SummaryApproved> for LlmError {
+    fn from(error: SummaryApproved) -> Self {
+        LlmError::SummaryApproved(error)
+    }
+}
+
+impl From<InsufficientDiskSpace> for LlmError {
+    fn from(error: InsufficientDiskSpace) -> Self {
+        LlmError::InsufficientDiskSpace(error)
+    }
+}
+
 impl From<LlmError> for String {
     fn from(error: LlmError) -> Self {
         error.to_string()
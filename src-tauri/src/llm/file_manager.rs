@@ -1,15 +1,599 @@
-use crate::llm::models::{FinalSummaryFormat, MeetingToMarkdown};
-use crate::MeetingMetadata;
-use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use crate::chunk_manifest::{read_manifest, write_manifest, MANIFEST_FILE_NAME};
+use crate::disk_space::write_error;
+use crate::error::StaleChunks;
+use crate::llm::attribution::SpeakerAttribution;
+use crate::llm::models::{
+    FinalSummaryFormat, FirstSummaryFormat, KeyFact, MeetingToMarkdown, Title,
+};
+use crate::llm::prompts::ChunkRequest;
+use crate::meeting::{set_meeting_name, write_atomic};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime, Wry};
 use tokio::fs;
 
-pub struct FileManager {
-    app_handle: AppHandle,
+/// Version id of the live `summary.json`
+pub const CURRENT_SUMMARY_VERSION: &str = "current";
+
+// Chunk layout of a meeting directory:
+//   chunks/transcription/             audio chunks `<id>_chunk_NN.ogg` and their whisper output
+//   chunks/summarization/<run_id>/    text chunks and chunk summaries of one summarization run,
+//                                     the `chunk_hash` of each summary in `chunk_NNN.hash`,
+//                                     with what it was split from in `manifest.json` and
+//                                     the attendee ids the summaries use in `key_facts_snapshot.json`,
+//                                     and a `cancelled` marker if the user stopped it
+//   chunks/summarization/current_run  id of the last complete run
+pub const CHUNKS_DIR_NAME: &str = "chunks";
+const TRANSCRIPTION_DIR_NAME: &str = "transcription";
+const SUMMARIZATION_DIR_NAME: &str = "summarization";
+const CURRENT_RUN_FILE_NAME: &str = "current_run";
+const RUN_MANIFEST_FILE_NAME: &str = "manifest.json";
+const KEY_FACTS_SNAPSHOT_FILE_NAME: &str = "key_facts_snapshot.json";
+const CANCELLED_MARKER_FILE_NAME: &str = "cancelled";
+const ALL_CHUNK_SUMMARIES_FILE_NAME: &str = "all_chunk_summaries.md";
+/// Run id given to chunk summaries moved over from the flat `chunks/` layout
+const LEGACY_RUN_ID: &str = "legacy";
+
+pub fn transcription_chunks_dir(meeting_dir: &Path) -> PathBuf {
+    meeting_dir
+        .join(CHUNKS_DIR_NAME)
+        .join(TRANSCRIPTION_DIR_NAME)
+}
+
+pub fn summarization_dir(meeting_dir: &Path) -> PathBuf {
+    meeting_dir
+        .join(CHUNKS_DIR_NAME)
+        .join(SUMMARIZATION_DIR_NAME)
+}
+
+/// Directory of a summarization run, relative to the meeting directory as
+/// stored in the chunk manifest
+pub fn summarization_run_path(run_id: &str) -> String {
+    format!("{}/{}/{}", CHUNKS_DIR_NAME, SUMMARIZATION_DIR_NAME, run_id)
+}
+
+pub fn chunk_file_name(chunk_index: usize) -> String {
+    format!("chunk_{:03}.txt", chunk_index + 1)
+}
+
+pub fn chunk_summary_file_name(chunk_index: usize) -> String {
+    format!("chunk_{:03}_summary.json", chunk_index + 1)
+}
+
+pub fn chunk_hash_file_name(chunk_index: usize) -> String {
+    format!("chunk_{:03}.hash", chunk_index + 1)
+}
+
+/// SHA-256 of what a chunk summary is made from: the prompts sent for the
+/// chunk, the model answering them and how the answer is anonymized afterwards
+pub fn chunk_hash(request: &ChunkRequest, attribution: &SpeakerAttribution, model: &str) -> String {
+    let flags = format!(
+        "attribute_speakers={} keep_todo_assignees={}",
+        attribution.attribute_speakers, attribution.keep_todo_assignees
+    );
+    let mut hasher = Sha256::new();
+    for part in [
+        model,
+        flags.as_str(),
+        &request.system_prompt,
+        &request.user_prompt,
+    ] {
+        hasher.update(part.len().to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Chunk summaries of the current run by their `chunk_hash`, for the next run
+/// to take over instead of asking the LLM again. Summaries without a hash,
+/// written before hashes were, are left out.
+pub async fn reusable_chunk_summaries(meeting_dir: &Path) -> HashMap<String, FirstSummaryFormat> {
+    let mut summaries = HashMap::new();
+    let Some(run_dir) = current_summarization_run_dir(meeting_dir).await else {
+        return summaries;
+    };
+    let Ok(mut entries) = fs::read_dir(&run_dir).await else {
+        return summaries;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(stem) = file_name.strip_suffix(".hash") else {
+            continue;
+        };
+        let Ok(hash) = fs::read_to_string(entry.path()).await else {
+            continue;
+        };
+        let summary = fs::read_to_string(run_dir.join(format!("{}_summary.json", stem)))
+            .await
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        if let Some(summary) = summary {
+            summaries.insert(hash.trim().to_string(), summary);
+        }
+    }
+    summaries
+}
+
+/// Create the directory of a new summarization run. Runs never share a
+/// directory, so a run with fewer chunks can't pick up an older run's files.
+pub async fn start_summarization_run(meeting_dir: &Path) -> Result<String, String> {
+    let run_id = Utc::now().format("%Y%m%dT%H%M%S%3f").to_string();
+    fs::create_dir_all(summarization_dir(meeting_dir).join(&run_id))
+        .await
+        .map_err(|e| format!("Failed to create chunks directory: {}", e))?;
+    Ok(run_id)
+}
+
+/// Make a run the current one once all of its chunk summaries are written and
+/// drop the other runs, including unfinished ones
+pub async fn finish_summarization_run(meeting_dir: &Path, run_id: &str) -> Result<(), String> {
+    let summarization_dir = summarization_dir(meeting_dir);
+    write_atomic(&summarization_dir.join(CURRENT_RUN_FILE_NAME), run_id)
+        .await
+        .map_err(|e| format!("Failed to record summarization run: {}", e))?;
+
+    let mut entries = fs::read_dir(&summarization_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        if entry.file_name() != run_id && entry.path().is_dir() {
+            if let Err(e) = fs::remove_dir_all(entry.path()).await {
+                println!("⚠️  Failed to remove old summarization run: {}", e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// What a summarization run split, to tell whether its chunk summaries still
+/// fit the meeting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// See `transcript_hash`
+    pub transcript_hash: String,
+    pub chunk_size: usize,
+    pub chunk_count: usize,
+}
+
+/// SHA-256 of the transcript. Summaries are made from the trimmed transcript,
+/// so surrounding whitespace doesn't count.
+pub fn transcript_hash(transcript: &str) -> String {
+    format!("{:x}", Sha256::digest(transcript.trim().as_bytes()))
+}
+
+impl RunManifest {
+    pub fn new(transcript: &str, chunk_size: usize, chunk_count: usize) -> Self {
+        Self {
+            transcript_hash: transcript_hash(transcript),
+            chunk_size,
+            chunk_count,
+        }
+    }
+
+    /// How the run differs from splitting `transcript` into chunks of
+    /// `chunk_size` now, `None` if it doesn't
+    pub fn staleness(&self, transcript: &str, chunk_size: usize) -> Option<StaleChunks> {
+        let transcript_changed = self.transcript_hash != transcript_hash(transcript);
+        (transcript_changed || self.chunk_size != chunk_size).then_some(StaleChunks {
+            transcript_changed,
+            run_chunk_size: self.chunk_size,
+            current_chunk_size: chunk_size,
+        })
+    }
+}
+
+pub async fn save_run_manifest(
+    meeting_dir: &Path,
+    run_id: &str,
+    manifest: &RunManifest,
+) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    let path = meeting_dir
+        .join(summarization_run_path(run_id))
+        .join(RUN_MANIFEST_FILE_NAME);
+    write_atomic(&path, json)
+        .await
+        .map_err(|e| format!("Failed to write run manifest: {}", e))
 }
 
-impl FileManager {
-    pub fn new(app_handle: AppHandle) -> Self {
+/// Let the next run over the same transcript continue where this one was cancelled
+pub async fn mark_run_cancelled(meeting_dir: &Path, run_id: &str) -> Result<(), String> {
+    let marker = meeting_dir
+        .join(summarization_run_path(run_id))
+        .join(CANCELLED_MARKER_FILE_NAME);
+    write_atomic(&marker, Utc::now().to_rfc3339())
+        .await
+        .map_err(|e| format!("Failed to mark run as cancelled: {}", e))
+}
+
+/// The latest cancelled run that split the transcript like `manifest`. Its
+/// marker is removed, the run goes on as the new one.
+pub async fn resume_cancelled_run(meeting_dir: &Path, manifest: &RunManifest) -> Option<String> {
+    let mut entries = fs::read_dir(summarization_dir(meeting_dir)).await.ok()?;
+    let mut resumable = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let run_dir = entry.path();
+        if !run_dir.join(CANCELLED_MARKER_FILE_NAME).exists() {
+            continue;
+        }
+        let run_manifest = fs::read_to_string(run_dir.join(RUN_MANIFEST_FILE_NAME))
+            .await
+            .ok()
+            .and_then(|json| serde_json::from_str::<RunManifest>(&json).ok());
+        if run_manifest.as_ref() == Some(manifest) {
+            resumable.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+
+    // Run ids are timestamps
+    let run_id = resumable.into_iter().max()?;
+    let marker = meeting_dir
+        .join(summarization_run_path(&run_id))
+        .join(CANCELLED_MARKER_FILE_NAME);
+    fs::remove_file(marker).await.ok()?;
+    Some(run_id)
+}
+
+/// Summary of one chunk of a run, `None` if it wasn't written or can't be read
+pub async fn read_run_chunk_summary(
+    meeting_dir: &Path,
+    run_id: &str,
+    chunk_index: usize,
+) -> Option<FirstSummaryFormat> {
+    let summary_file = meeting_dir
+        .join(summarization_run_path(run_id))
+        .join(chunk_summary_file_name(chunk_index));
+    let json = fs::read_to_string(summary_file).await.ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// How the chunk summaries of the current run differ from a new run over
+/// `transcript`. Runs from before manifests were written can't be checked
+/// and count as up to date.
+pub async fn stale_chunks(
+    meeting_dir: &Path,
+    transcript: &str,
+    chunk_size: usize,
+) -> Option<StaleChunks> {
+    let run_dir = current_summarization_run_dir(meeting_dir).await?;
+    let json = fs::read_to_string(run_dir.join(RUN_MANIFEST_FILE_NAME))
+        .await
+        .ok()?;
+    let manifest: RunManifest = serde_json::from_str(&json).ok()?;
+    manifest.staleness(transcript, chunk_size)
+}
+
+/// Key facts collected over all chunks of the current run. The `[n]`
+/// references in its summaries are ids of these attendees.
+pub async fn read_key_facts_snapshot(meeting_dir: &Path) -> Option<KeyFact> {
+    let run_dir = current_summarization_run_dir(meeting_dir).await?;
+    let json = fs::read_to_string(run_dir.join(KEY_FACTS_SNAPSHOT_FILE_NAME))
+        .await
+        .ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Directory of the last complete summarization run, if there is one
+pub async fn current_summarization_run_dir(meeting_dir: &Path) -> Option<PathBuf> {
+    let summarization_dir = summarization_dir(meeting_dir);
+    let run_id = fs::read_to_string(summarization_dir.join(CURRENT_RUN_FILE_NAME))
+        .await
+        .ok()?;
+    let run_id = run_id.trim();
+    if run_id.is_empty() || !run_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let run_dir = summarization_dir.join(run_id);
+    run_dir.is_dir().then_some(run_dir)
+}
+
+/// Chunk summaries of the current run, in order. Chunks that failed have
+/// their text saved but no summary, they are skipped.
+pub async fn read_chunk_summaries_from(
+    meeting_dir: &Path,
+) -> Result<Vec<FirstSummaryFormat>, String> {
+    let run_dir = current_summarization_run_dir(meeting_dir)
+        .await
+        .ok_or_else(|| "No chunk summaries found - no complete summarization run".to_string())?;
+
+    let mut chunk_summaries = Vec::new();
+    for chunk_index in 0.. {
+        let summary_file = run_dir.join(chunk_summary_file_name(chunk_index));
+        if !summary_file.exists() {
+            if run_dir.join(chunk_file_name(chunk_index)).exists() {
+                continue;
+            }
+            break;
+        }
+
+        let summary_json = fs::read_to_string(&summary_file)
+            .await
+            .map_err(|e| format!("Failed to read chunk summary {}: {}", chunk_index + 1, e))?;
+        let chunk_summary: FirstSummaryFormat =
+            serde_json::from_str(&summary_json).map_err(|e| {
+                format!(
+                    "Failed to parse chunk summary {} JSON: {}",
+                    chunk_index + 1,
+                    e
+                )
+            })?;
+        chunk_summaries.push(chunk_summary);
+    }
+
+    if chunk_summaries.is_empty() {
+        return Err("No chunk summaries found".to_string());
+    }
+
+    Ok(chunk_summaries)
+}
+
+/// `chunk_NNN.txt`, `chunk_NNN_summary.json` or the combined summaries of the flat layout
+fn is_flat_chunk_file(file_name: &str) -> bool {
+    file_name == ALL_CHUNK_SUMMARIES_FILE_NAME
+        || file_name
+            .strip_prefix("chunk_")
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Move chunk files of the flat layout into `chunks/transcription/` and
+/// `chunks/summarization/legacy/`. Does nothing for meetings already migrated.
+/// Returns whether anything was moved.
+pub async fn migrate_chunk_layout(meeting_dir: &Path, meeting_id: &str) -> Result<bool, String> {
+    let mut manifest = read_manifest(meeting_dir).await?;
+    let mut migrated = false;
+
+    // Audio chunks and their whisper output used to sit next to the recording
+    let chunk_prefix = format!("{}_chunk_", meeting_id);
+    let transcription_dir = transcription_chunks_dir(meeting_dir);
+    let mut entries = fs::read_dir(meeting_dir).await.map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if !file_name.starts_with(&chunk_prefix) || !entry.path().is_file() {
+            continue;
+        }
+        fs::create_dir_all(&transcription_dir)
+            .await
+            .map_err(|e| format!("Failed to create chunks directory: {}", e))?;
+        fs::rename(entry.path(), transcription_dir.join(&file_name))
+            .await
+            .map_err(|e| format!("Failed to move {}: {}", file_name, e))?;
+        migrated = true;
+    }
+    for audio_chunk in &mut manifest.audio_chunks {
+        if audio_chunk.file.starts_with(&chunk_prefix) {
+            audio_chunk.file = format!(
+                "{}/{}/{}",
+                CHUNKS_DIR_NAME, TRANSCRIPTION_DIR_NAME, audio_chunk.file
+            );
+        }
+    }
+
+    // Summarization chunks used to be written straight into `chunks/`, runs
+    // with fewer chunks left the summaries of older runs behind. Other files
+    // there, e.g. a `.DS_Store`, aren't chunks.
+    let chunks_dir = meeting_dir.join(CHUNKS_DIR_NAME);
+    let mut flat_files = Vec::new();
+    if let Ok(mut entries) = fs::read_dir(&chunks_dir).await {
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_file() && is_flat_chunk_file(&file_name) {
+                flat_files.push(file_name);
+            }
+        }
+    }
+    // A meeting summarized since then has runs of its own, the current one
+    // must not be replaced by the leftovers
+    let has_current_run = current_summarization_run_dir(meeting_dir).await.is_some();
+
+    if !flat_files.is_empty() && !has_current_run {
+        let chunk_count = if manifest.text_chunks.is_empty() {
+            (0..)
+                .take_while(|i| flat_files.contains(&chunk_summary_file_name(*i)))
+                .count()
+        } else {
+            manifest.text_chunks.len()
+        };
+
+        let run_dir = summarization_dir(meeting_dir).join(LEGACY_RUN_ID);
+        fs::create_dir_all(&run_dir)
+            .await
+            .map_err(|e| format!("Failed to create chunks directory: {}", e))?;
+
+        let mut current: Vec<String> = (0..chunk_count)
+            .flat_map(|i| [chunk_file_name(i), chunk_summary_file_name(i)])
+            .collect();
+        current.push(ALL_CHUNK_SUMMARIES_FILE_NAME.to_string());
+
+        let mut moved_summaries = 0;
+        for file_name in &flat_files {
+            let path = chunks_dir.join(file_name);
+            if current.contains(file_name) {
+                fs::rename(&path, run_dir.join(file_name))
+                    .await
+                    .map_err(|e| format!("Failed to move {}: {}", file_name, e))?;
+                if file_name.ends_with("_summary.json") {
+                    moved_summaries += 1;
+                }
+            } else if file_name.starts_with("chunk_") {
+                // Left over from an older run
+                fs::remove_file(&path)
+                    .await
+                    .map_err(|e| format!("Failed to remove {}: {}", file_name, e))?;
+            }
+        }
+        if moved_summaries > 0 {
+            finish_summarization_run(meeting_dir, LEGACY_RUN_ID).await?;
+        }
+
+        let run_path = summarization_run_path(LEGACY_RUN_ID);
+        for text_chunk in &mut manifest.text_chunks {
+            text_chunk.file = format!("{}/{}", run_path, chunk_file_name(text_chunk.index));
+            text_chunk.summary_file =
+                format!("{}/{}", run_path, chunk_summary_file_name(text_chunk.index));
+        }
+        migrated = true;
+    }
+
+    if migrated && meeting_dir.join(MANIFEST_FILE_NAME).exists() {
+        write_manifest(meeting_dir, &manifest).await?;
+    }
+    Ok(migrated)
+}
+
+/// Migrate every meeting in `uploads/` to the current chunk layout
+pub async fn migrate_chunk_layouts(uploads: &Path) -> Result<(), String> {
+    let mut entries = match fs::read_dir(uploads).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let meeting_id = entry.file_name().to_string_lossy().into_owned();
+        match migrate_chunk_layout(&entry.path(), &meeting_id).await {
+            Ok(true) => println!("📦 Moved chunk files of {} to the new layout", meeting_id),
+            Ok(false) => {}
+            Err(e) => println!("⚠️  Failed to migrate chunks of {}: {}", meeting_id, e),
+        }
+    }
+    Ok(())
+}
+
+pub struct FileManager<R: Runtime = Wry> {
+    app_handle: AppHandle<R>,
+}
+
+/// Store a generated title as the meeting name and emoji. Goes through the shared
+/// metadata update so pins, notes timestamps and unknown fields are kept.
+pub async fn save_summary_title(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    title: &Title,
+) -> Result<(), String> {
+    let emoji = Some(title.emoji.clone()).filter(|emoji| !emoji.trim().is_empty());
+    set_meeting_name(meeting_dir, meeting_id, title.text.clone(), emoji).await
+}
+
+/// The transcript slice a chunk summary was generated from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkText {
+    /// 1-based, as in `chunk_001.txt`
+    pub chunk_number: usize,
+    pub text: String,
+    /// `text` was cut off at the requested size
+    pub truncated: bool,
+    pub total_chars: usize,
+    /// Character range in `<id>.txt` from the chunk manifest
+    pub char_range: Option<(usize, usize)>,
+    /// SHA-256 of the full chunk text
+    pub hash: String,
+    /// Whether the transcript still has this text at `char_range`, `None` when
+    /// either is unknown. False after the transcript was edited or re-split.
+    pub matches_transcript: Option<bool>,
+}
+
+fn char_slice(text: &str, (start, end): (usize, usize)) -> String {
+    text.chars()
+        .skip(start)
+        .take(end.saturating_sub(start))
+        .collect()
+}
+
+/// Read `chunk_NNN.txt` of the current summarization run, at most `max_chars` of it
+pub async fn read_chunk_text_from(
+    meeting_dir: &Path,
+    chunk_number: usize,
+    max_chars: Option<usize>,
+    transcript: Option<&str>,
+) -> Result<ChunkText, String> {
+    let Some(chunk_index) = chunk_number.checked_sub(1) else {
+        return Err("Chunk numbers start at 1".to_string());
+    };
+    let run_dir = current_summarization_run_dir(meeting_dir)
+        .await
+        .ok_or("No chunks found, the meeting has not been summarized yet")?;
+    let chunk_path = run_dir.join(chunk_file_name(chunk_index));
+    if !chunk_path.is_file() {
+        return Err(format!("Chunk {} not found", chunk_number));
+    }
+    let text = fs::read_to_string(&chunk_path)
+        .await
+        .map_err(|e| format!("Failed to read chunk {}: {}", chunk_number, e))?;
+
+    // Only an entry of the current run describes this file
+    let run_id = run_dir.file_name().unwrap_or_default().to_string_lossy();
+    let chunk_file = format!(
+        "{}/{}",
+        summarization_run_path(&run_id),
+        chunk_file_name(chunk_index)
+    );
+    let char_range = read_manifest(meeting_dir)
+        .await?
+        .text_chunks
+        .into_iter()
+        .find(|entry| entry.index == chunk_index && entry.file == chunk_file)
+        .map(|entry| entry.char_range);
+    let matches_transcript = transcript
+        .zip(char_range)
+        .map(|(transcript, range)| char_slice(transcript, range) == text);
+
+    let total_chars = text.chars().count();
+    let hash = format!("{:x}", Sha256::digest(text.as_bytes()));
+    let truncated = max_chars.is_some_and(|max_chars| total_chars > max_chars);
+    let text = match max_chars {
+        Some(max_chars) if truncated => text.chars().take(max_chars).collect(),
+        _ => text,
+    };
+
+    Ok(ChunkText {
+        chunk_number,
+        text,
+        truncated,
+        total_chars,
+        char_range,
+        hash,
+        matches_transcript,
+    })
+}
+
+/// Read the live `summary.json` of a meeting directory
+pub async fn read_summary_from(meeting_dir: &Path) -> Result<FinalSummaryFormat, String> {
+    let summary_json = fs::read_to_string(meeting_dir.join("summary.json"))
+        .await
+        .map_err(|e| format!("Failed to read summary file: {}", e))?;
+
+    serde_json::from_str(&summary_json).map_err(|e| format!("Failed to parse summary JSON: {}", e))
+}
+
+/// Write `summary.json`. A failed write leaves the previous file in place
+/// instead of a truncated one.
+async fn write_summary_json(
+    meeting_dir: &Path,
+    content: &FinalSummaryFormat,
+) -> Result<(), String> {
+    let json = serde_json::to_string(content)
+        .map_err(|e| format!("Failed to serialize summary: {}", e))?;
+    write_atomic(&meeting_dir.join("summary.json"), json)
+        .await
+        .map_err(|e| write_error("Failed to save summary JSON", e))
+}
+
+async fn write_summary_markdown(
+    meeting_dir: &Path,
+    content: &FinalSummaryFormat,
+) -> Result<(), String> {
+    write_atomic(&meeting_dir.join("summary.md"), content.to_markdown())
+        .await
+        .map_err(|e| write_error("Failed to save summary markdown", e))
+}
+
+impl<R: Runtime> FileManager<R> {
+    pub fn new(app_handle: AppHandle<R>) -> Self {
         Self { app_handle }
     }
 
@@ -22,64 +606,157 @@ impl FileManager {
         Ok(app_dir.join("uploads").join(meeting_id))
     }
 
-    pub fn get_chunks_dir(&self, meeting_id: &str) -> Result<PathBuf, String> {
-        Ok(self.get_meeting_dir(meeting_id)?.join("chunks"))
+    /// Create the directory of a new summarization run and return its id
+    pub async fn start_summarization_run(&self, meeting_id: &str) -> Result<String, String> {
+        start_summarization_run(&self.get_meeting_dir(meeting_id)?).await
     }
 
-    pub async fn ensure_chunks_dir_exists(&self, meeting_id: &str) -> Result<(), String> {
-        let chunks_dir = self.get_chunks_dir(meeting_id)?;
-        fs::create_dir_all(&chunks_dir)
+    pub async fn save_run_manifest(
+        &self,
+        meeting_id: &str,
+        run_id: &str,
+        manifest: &RunManifest,
+    ) -> Result<(), String> {
+        save_run_manifest(&self.get_meeting_dir(meeting_id)?, run_id, manifest).await
+    }
+
+    pub async fn save_key_facts_snapshot(
+        &self,
+        meeting_id: &str,
+        run_id: &str,
+        key_facts: &KeyFact,
+    ) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(key_facts).map_err(|e| e.to_string())?;
+        let snapshot_file = self
+            .get_run_dir(meeting_id, run_id)?
+            .join(KEY_FACTS_SNAPSHOT_FILE_NAME);
+        write_atomic(&snapshot_file, json)
             .await
-            .map_err(|e| format!("Failed to create chunks directory: {}", e))
+            .map_err(|e| write_error("Failed to save key facts snapshot", e))
+    }
+
+    pub async fn mark_run_cancelled(&self, meeting_id: &str, run_id: &str) -> Result<(), String> {
+        mark_run_cancelled(&self.get_meeting_dir(meeting_id)?, run_id).await
+    }
+
+    /// Id of a cancelled run to continue, see `resume_cancelled_run`
+    pub async fn resume_cancelled_run(
+        &self,
+        meeting_id: &str,
+        manifest: &RunManifest,
+    ) -> Option<String> {
+        resume_cancelled_run(&self.get_meeting_dir(meeting_id).ok()?, manifest).await
+    }
+
+    pub async fn read_run_chunk_summary(
+        &self,
+        meeting_id: &str,
+        run_id: &str,
+        chunk_index: usize,
+    ) -> Option<FirstSummaryFormat> {
+        read_run_chunk_summary(&self.get_meeting_dir(meeting_id).ok()?, run_id, chunk_index).await
+    }
+
+    pub async fn finish_summarization_run(
+        &self,
+        meeting_id: &str,
+        run_id: &str,
+    ) -> Result<(), String> {
+        finish_summarization_run(&self.get_meeting_dir(meeting_id)?, run_id).await
+    }
+
+    fn get_run_dir(&self, meeting_id: &str, run_id: &str) -> Result<PathBuf, String> {
+        Ok(self
+            .get_meeting_dir(meeting_id)?
+            .join(summarization_run_path(run_id)))
+    }
+
+    pub async fn read_chunk_text(
+        &self,
+        meeting_id: &str,
+        chunk_number: usize,
+        max_chars: Option<usize>,
+        transcript: Option<&str>,
+    ) -> Result<ChunkText, String> {
+        read_chunk_text_from(
+            &self.get_meeting_dir(meeting_id)?,
+            chunk_number,
+            max_chars,
+            transcript,
+        )
+        .await
     }
 
     pub async fn save_chunk(
         &self,
         meeting_id: &str,
+        run_id: &str,
         chunk_index: usize,
         content: &str,
     ) -> Result<(), String> {
-        self.ensure_chunks_dir_exists(meeting_id).await?;
-        let chunks_dir = self.get_chunks_dir(meeting_id)?;
-        let chunk_file = chunks_dir.join(format!("chunk_{:03}.txt", chunk_index + 1));
+        let chunk_file = self
+            .get_run_dir(meeting_id, run_id)?
+            .join(chunk_file_name(chunk_index));
 
-        fs::write(&chunk_file, content)
+        write_atomic(&chunk_file, content)
             .await
-            .map_err(|e| format!("Failed to save chunk {}: {}", chunk_index + 1, e))
+            .map_err(|e| write_error(&format!("Failed to save chunk {}", chunk_index + 1), e))
     }
 
     pub async fn save_chunk_summary(
         &self,
         meeting_id: &str,
+        run_id: &str,
         chunk_index: usize,
         summary: &str,
     ) -> Result<(), String> {
-        let chunks_dir = self.get_chunks_dir(meeting_id)?;
-        let summary_file = chunks_dir.join(format!("chunk_{:03}_summary.json", chunk_index + 1));
+        let summary_file = self
+            .get_run_dir(meeting_id, run_id)?
+            .join(chunk_summary_file_name(chunk_index));
+
+        write_atomic(&summary_file, summary).await.map_err(|e| {
+            write_error(
+                &format!("Failed to save chunk summary {}", chunk_index + 1),
+                e,
+            )
+        })
+    }
 
-        fs::write(&summary_file, summary)
+    pub async fn save_chunk_hash(
+        &self,
+        meeting_id: &str,
+        run_id: &str,
+        chunk_index: usize,
+        hash: &str,
+    ) -> Result<(), String> {
+        let hash_file = self
+            .get_run_dir(meeting_id, run_id)?
+            .join(chunk_hash_file_name(chunk_index));
+
+        write_atomic(&hash_file, hash)
             .await
-            .map_err(|e| format!("Failed to save chunk summary {}: {}", chunk_index + 1, e))
+            .map_err(|e| write_error(&format!("Failed to save chunk hash {}", chunk_index + 1), e))
     }
 
     pub async fn save_all_chunk_summaries(
         &self,
         meeting_id: &str,
-        summaries: &[String],
+        run_id: &str,
+        summaries: &[(usize, String)],
     ) -> Result<(), String> {
-        let chunks_dir = self.get_chunks_dir(meeting_id)?;
-        let all_chunks_summary_file = chunks_dir.join("all_chunk_summaries.md");
+        let all_chunks_summary_file = self
+            .get_run_dir(meeting_id, run_id)?
+            .join(ALL_CHUNK_SUMMARIES_FILE_NAME);
 
         let all_summaries_content = summaries
             .iter()
-            .enumerate()
             .map(|(i, summary)| format!("# Chunk {} Summary\n\n{}", i + 1, summary))
             .collect::<Vec<_>>()
             .join("\n\n---\n\n");
 
-        fs::write(&all_chunks_summary_file, &all_summaries_content)
+        write_atomic(&all_chunks_summary_file, &all_summaries_content)
             .await
-            .map_err(|e| format!("Failed to save all chunk summaries: {}", e))
+            .map_err(|e| write_error("Failed to save all chunk summaries", e))
     }
 
     pub async fn save_final_summary(
@@ -88,112 +765,641 @@ impl FileManager {
         content: &FinalSummaryFormat,
     ) -> Result<(), String> {
         let meeting_dir = self.get_meeting_dir(meeting_id)?;
-        let summary_path = meeting_dir.join("summary.md");
-        let summary_json_path = meeting_dir.join("summary.json");
+        let archived = self.archive_current_summary(meeting_id).await?;
 
-        let markdown = content.to_markdown();
-        fs::write(summary_path, markdown)
-            .await
-            .map_err(|e| format!("Failed to save summary markdown: {}", e))?;
+        if let Err(e) = write_summary_json(&meeting_dir, content).await {
+            // The current summary is still in place, it's no new version
+            if let Some(archived) = archived {
+                let _ = fs::remove_file(archived).await;
+            }
+            return Err(e);
+        }
+        // summary.json was replaced, the archive is the only copy of the old version
+        write_summary_markdown(&meeting_dir, content).await
+    }
+
+    pub async fn read_summary(&self, meeting_id: &str) -> Result<FinalSummaryFormat, String> {
+        read_summary_from(&self.get_meeting_dir(meeting_id)?).await
+    }
+
+    pub fn get_summary_versions_dir(&self, meeting_id: &str) -> Result<PathBuf, String> {
+        Ok(self.get_meeting_dir(meeting_id)?.join("summary_versions"))
+    }
+
+    /// Keep the current `summary.json` in `summary_versions/` before it is overwritten.
+    /// The version id is the time the summary was written. It stays in place
+    /// until the new summary replaces it, so a failed write loses nothing.
+    async fn archive_current_summary(&self, meeting_id: &str) -> Result<Option<PathBuf>, String> {
+        let summary_json_path = self.get_meeting_dir(meeting_id)?.join("summary.json");
+        let modified = match fs::metadata(&summary_json_path).await {
+            Ok(metadata) => metadata
+                .modified()
+                .map_err(|e| format!("Failed to read summary timestamp: {}", e))?,
+            Err(_) => return Ok(None),
+        };
 
-        let json = serde_json::to_string(content)
-            .map_err(|e| format!("Failed to serialize summary: {}", e))?;
-        fs::write(summary_json_path, json)
+        let versions_dir = self.get_summary_versions_dir(meeting_id)?;
+        fs::create_dir_all(&versions_dir)
             .await
-            .map_err(|e| format!("Failed to save summary JSON: {}", e))?;
+            .map_err(|e| format!("Failed to create summary versions directory: {}", e))?;
 
-        Ok(())
+        let version = DateTime::<Utc>::from(modified)
+            .format("%Y%m%dT%H%M%S%3f")
+            .to_string();
+        let archived = versions_dir.join(format!("summary_{}.json", version));
+        // A hard link needs no space, copy where the file system has none
+        if fs::hard_link(&summary_json_path, &archived).await.is_err() {
+            if let Err(e) = fs::copy(&summary_json_path, &archived).await {
+                let _ = fs::remove_file(&archived).await;
+                return Err(write_error("Failed to archive previous summary", e));
+            }
+        }
+        Ok(Some(archived))
     }
 
-    pub async fn read_summary(&self, meeting_id: &str) -> Result<FinalSummaryFormat, String> {
-        let meeting_dir = self.get_meeting_dir(meeting_id)?;
-        let summary_path = meeting_dir.join("summary.json");
+    /// Archived summary versions, oldest first. The current summary is `"current"`.
+    pub async fn list_summary_versions(&self, meeting_id: &str) -> Result<Vec<String>, String> {
+        let mut versions = Vec::new();
 
-        let summary_json = fs::read_to_string(summary_path)
+        let versions_dir = self.get_summary_versions_dir(meeting_id)?;
+        if let Ok(mut entries) = fs::read_dir(&versions_dir).await {
+            while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+                let file_name = entry.file_name().to_string_lossy().into_owned();
+                if let Some(version) = file_name
+                    .strip_prefix("summary_")
+                    .and_then(|rest| rest.strip_suffix(".json"))
+                {
+                    versions.push(version.to_string());
+                }
+            }
+        }
+        versions.sort();
+
+        if self
+            .get_meeting_dir(meeting_id)?
+            .join("summary.json")
+            .exists()
+        {
+            versions.push(CURRENT_SUMMARY_VERSION.to_string());
+        }
+
+        Ok(versions)
+    }
+
+    pub async fn read_summary_version(
+        &self,
+        meeting_id: &str,
+        version: &str,
+    ) -> Result<FinalSummaryFormat, String> {
+        if version == CURRENT_SUMMARY_VERSION {
+            return self.read_summary(meeting_id).await;
+        }
+        if !version.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("Invalid summary version: {}", version));
+        }
+
+        let version_path = self
+            .get_summary_versions_dir(meeting_id)?
+            .join(format!("summary_{}.json", version));
+        let summary_json = fs::read_to_string(version_path)
             .await
-            .map_err(|e| format!("Failed to read summary file: {}", e))?;
+            .map_err(|e| format!("Failed to read summary version {}: {}", version, e))?;
 
         serde_json::from_str(&summary_json)
             .map_err(|e| format!("Failed to parse summary JSON: {}", e))
     }
 
-    /// Read all saved chunk summaries from disk
+    /// Read the chunk summaries of the last complete run
     pub async fn read_chunk_summaries(
         &self,
         meeting_id: &str,
-    ) -> Result<Vec<crate::llm::models::FirstSummaryFormat>, String> {
-        let chunks_dir = self.get_chunks_dir(meeting_id)?;
+    ) -> Result<Vec<FirstSummaryFormat>, String> {
+        read_chunk_summaries_from(&self.get_meeting_dir(meeting_id)?).await
+    }
+
+    /// Use the summary title as the meeting name
+    pub async fn save_meeting_metadata(
+        &self,
+        meeting_id: &str,
+        title: &Title,
+    ) -> Result<(), String> {
+        let meeting_dir = self.get_meeting_dir(meeting_id)?;
+        save_summary_title(&meeting_dir, meeting_id, title).await
+    }
+}
 
-        // Check if chunks directory exists
-        if !chunks_dir.exists() {
-            return Err("No chunk summaries found - chunks directory doesn't exist".to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_manifest::{AudioChunkEntry, ChunkManifest, TextChunkEntry};
+    use crate::llm::models::Topic;
+    use crate::llm::prompts::{Language, SummaryStyle};
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    fn chunk_summary(title: &str) -> String {
+        serde_json::to_string(&FirstSummaryFormat {
+            key_facts: Default::default(),
+            topics: vec![Topic {
+                title: title.to_string(),
+                bullet_points: Vec::new(),
+                sub_topics: None,
+            }],
+            todos: None,
+        })
+        .unwrap()
+    }
+
+    async fn write_run(meeting_dir: &Path, prefix: &str, chunk_count: usize) -> String {
+        let run_id = start_summarization_run(meeting_dir).await.unwrap();
+        let run_dir = meeting_dir.join(summarization_run_path(&run_id));
+        for i in 0..chunk_count {
+            std::fs::write(
+                run_dir.join(chunk_summary_file_name(i)),
+                chunk_summary(&format!("{} {}", prefix, i)),
+            )
+            .unwrap();
         }
+        // Run ids have millisecond resolution
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        run_id
+    }
 
-        let mut chunk_summaries = Vec::new();
-        let mut chunk_index = 1;
+    fn final_summary(text: &str) -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📋".to_string(),
+                text: "Teamtreffen".to_string(),
+            },
+            key_facts: Default::default(),
+            summary: text.to_string(),
+            topics: Vec::new(),
+            todos: Vec::new(),
+        }
+    }
 
-        // Read chunk summaries in order
-        loop {
-            let summary_file = chunks_dir.join(format!("chunk_{:03}_summary.json", chunk_index));
+    /// `/dev/full` fails every write with ENOSPC. Linking the temporary file
+    /// of `write_atomic` to it simulates a disk that fills up mid-write.
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_failed_summary_write_leaves_no_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp_path = dir.path().join(".summary.json.tmp");
+        std::os::unix::fs::symlink("/dev/full", &temp_path).unwrap();
 
-            if !summary_file.exists() {
-                break;
-            }
+        let error = write_summary_json(dir.path(), &final_summary("Neu"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "Failed to save summary JSON: the disk is full. Free up some space and try again."
+        );
+        assert!(!dir.path().join("summary.json").exists());
+        assert!(!dir.path().join("summary.md").exists());
+        assert!(std::fs::symlink_metadata(&temp_path).is_err());
+
+        // An existing summary is kept as it was
+        write_summary_json(dir.path(), &final_summary("Alt"))
+            .await
+            .unwrap();
+        std::os::unix::fs::symlink("/dev/full", &temp_path).unwrap();
+        assert!(write_summary_json(dir.path(), &final_summary("Neu"))
+            .await
+            .is_err());
+        assert_eq!(read_summary_from(dir.path()).await.unwrap().summary, "Alt");
+        assert!(std::fs::symlink_metadata(&temp_path).is_err());
+    }
+
+    fn titles(summaries: &[FirstSummaryFormat]) -> Vec<String> {
+        summaries
+            .iter()
+            .map(|summary| summary.topics[0].title.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_shorter_run_does_not_pick_up_older_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_run = write_run(dir.path(), "old", 5).await;
+        finish_summarization_run(dir.path(), &old_run)
+            .await
+            .unwrap();
+
+        let new_run = write_run(dir.path(), "new", 3).await;
+        // Unfinished runs are never read
+        assert_eq!(
+            titles(&read_chunk_summaries_from(dir.path()).await.unwrap()).len(),
+            5
+        );
+
+        finish_summarization_run(dir.path(), &new_run)
+            .await
+            .unwrap();
+        assert_eq!(
+            titles(&read_chunk_summaries_from(dir.path()).await.unwrap()),
+            vec!["new 0", "new 1", "new 2"]
+        );
+        assert!(!dir.path().join(summarization_run_path(&old_run)).exists());
+    }
 
-            let summary_json = fs::read_to_string(&summary_file)
+    #[tokio::test]
+    async fn test_run_manifest_staleness() {
+        let transcript = "Wir beschließen den Haushalt.";
+        let manifest = RunManifest::new(transcript, 4_000, 1);
+        assert_eq!(manifest.staleness(transcript, 4_000), None);
+        assert_eq!(
+            manifest.staleness(&format!("\n{}\n", transcript), 4_000),
+            None
+        );
+
+        let edited = manifest
+            .staleness("Wir vertagen den Haushalt.", 4_000)
+            .unwrap();
+        assert!(edited.transcript_changed);
+        assert_eq!(
+            manifest.staleness(transcript, 6_000),
+            Some(StaleChunks {
+                transcript_changed: false,
+                run_chunk_size: 4_000,
+                current_chunk_size: 6_000,
+            })
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(stale_chunks(dir.path(), transcript, 4_000).await, None);
+        // Runs from before manifests were written can't be checked
+        let run_id = write_run(dir.path(), "Alt", 1).await;
+        finish_summarization_run(dir.path(), &run_id).await.unwrap();
+        assert_eq!(stale_chunks(dir.path(), "Anders", 4_000).await, None);
+
+        save_run_manifest(dir.path(), &run_id, &manifest)
+            .await
+            .unwrap();
+        assert_eq!(stale_chunks(dir.path(), transcript, 4_000).await, None);
+        assert!(
+            stale_chunks(dir.path(), "Anders", 4_000)
                 .await
-                .map_err(|e| format!("Failed to read chunk summary {}: {}", chunk_index, e))?;
+                .unwrap()
+                .transcript_changed
+        );
+    }
 
-            let chunk_summary: crate::llm::models::FirstSummaryFormat =
-                serde_json::from_str(&summary_json).map_err(|e| {
-                    format!("Failed to parse chunk summary {} JSON: {}", chunk_index, e)
-                })?;
+    fn request(
+        language: &Language,
+        style: &SummaryStyle,
+        attribute_speakers: bool,
+    ) -> ChunkRequest {
+        ChunkRequest::new(
+            language,
+            style,
+            attribute_speakers,
+            "Wir beschließen den Haushalt.",
+            &KeyFact::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reusable_chunk_summaries_need_a_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(reusable_chunk_summaries(dir.path()).await.is_empty());
+
+        let run_id = write_run(dir.path(), "run", 3).await;
+        let run_dir = dir.path().join(summarization_run_path(&run_id));
+        let attribution = SpeakerAttribution::default();
+        let german = request(&Language::German, &SummaryStyle::default(), true);
+        let hash = chunk_hash(&german, &attribution, "llama3.1");
+        std::fs::write(run_dir.join(chunk_hash_file_name(1)), &hash).unwrap();
+        // A failed chunk keeps its hash but has no summary
+        std::fs::write(run_dir.join(chunk_hash_file_name(2)), "other").unwrap();
+        std::fs::remove_file(run_dir.join(chunk_summary_file_name(2))).unwrap();
+        // Only the current run is reused
+        assert!(reusable_chunk_summaries(dir.path()).await.is_empty());
+        finish_summarization_run(dir.path(), &run_id).await.unwrap();
+
+        let summaries = reusable_chunk_summaries(dir.path()).await;
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[&hash].topics[0].title, "run 1");
+    }
 
-            chunk_summaries.push(chunk_summary);
-            chunk_index += 1;
+    #[test]
+    fn test_chunk_hash_follows_everything_the_summary_depends_on() {
+        let attribution = SpeakerAttribution::default();
+        let german = request(&Language::German, &SummaryStyle::default(), true);
+        let hash = chunk_hash(&german, &attribution, "llama3.1");
+        assert_eq!(hash, chunk_hash(&german.clone(), &attribution, "llama3.1"));
+
+        let changed = [
+            chunk_hash(
+                &request(&Language::English, &SummaryStyle::default(), true),
+                &attribution,
+                "llama3.1",
+            ),
+            chunk_hash(&german, &attribution, "qwen2.5"),
+            chunk_hash(
+                &request(&Language::German, &SummaryStyle::Complete, true),
+                &attribution,
+                "llama3.1",
+            ),
+            chunk_hash(
+                &german,
+                &SpeakerAttribution {
+                    keep_todo_assignees: !attribution.keep_todo_assignees,
+                    ..attribution
+                },
+                "llama3.1",
+            ),
+            chunk_hash(
+                &german
+                    .clone()
+                    .with_authoritative_key_facts(&Language::German, Some(&KeyFact::default())),
+                &attribution,
+                "llama3.1",
+            ),
+        ];
+        for other in changed {
+            assert_ne!(hash, other);
         }
+    }
 
-        if chunk_summaries.is_empty() {
-            return Err("No chunk summaries found".to_string());
+    #[tokio::test]
+    async fn test_no_complete_run() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_chunk_summaries_from(dir.path()).await.is_err());
+
+        write_run(dir.path(), "aborted", 2).await;
+        assert!(current_summarization_run_dir(dir.path()).await.is_none());
+        assert!(read_chunk_summaries_from(dir.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_chunks_leave_gaps() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_id = write_run(dir.path(), "run", 4).await;
+        let run_dir = dir.path().join(summarization_run_path(&run_id));
+        for i in 0..4 {
+            std::fs::write(run_dir.join(chunk_file_name(i)), "Text").unwrap();
         }
+        std::fs::remove_file(run_dir.join(chunk_summary_file_name(1))).unwrap();
+        finish_summarization_run(dir.path(), &run_id).await.unwrap();
 
-        Ok(chunk_summaries)
+        assert_eq!(
+            titles(&read_chunk_summaries_from(dir.path()).await.unwrap()),
+            vec!["run 0", "run 2", "run 3"]
+        );
     }
 
-    pub fn save_meeting_metadata(&self, meeting_id: &str, name: String) -> Result<(), String> {
-        let meeting_dir = self.get_meeting_dir(meeting_id)?;
-        let metadata_path = meeting_dir.join("meeting.json");
-
-        // Try to read existing metadata to preserve created_at
-        let created_at = if let Ok(content) = std::fs::read_to_string(&metadata_path) {
-            if let Ok(existing_metadata) = serde_json::from_str::<MeetingMetadata>(&content) {
-                existing_metadata.created_at
-            } else {
-                Some(
-                    chrono::Utc::now()
-                        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-                        .to_string(),
-                )
-            }
-        } else {
-            Some(
-                chrono::Utc::now()
-                    .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-                    .to_string(),
+    #[tokio::test]
+    async fn test_failed_markdown_write_keeps_the_previous_version() {
+        let app = crate::llm::test_harness::TestApp::new(Default::default());
+        let meeting_dir = app.meeting_dir(MEETING_ID);
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        let file_manager = FileManager::new(app.handle());
+        file_manager
+            .save_final_summary(MEETING_ID, &final_summary("alt"))
+            .await
+            .unwrap();
+
+        // summary.md can't be replaced by a file anymore
+        std::fs::remove_file(meeting_dir.join("summary.md")).unwrap();
+        std::fs::create_dir_all(meeting_dir.join("summary.md").join("blocked")).unwrap();
+        assert!(file_manager
+            .save_final_summary(MEETING_ID, &final_summary("neu"))
+            .await
+            .is_err());
+
+        assert_eq!(
+            read_summary_from(&meeting_dir).await.unwrap().summary,
+            "neu"
+        );
+        let versions = file_manager
+            .list_summary_versions(MEETING_ID)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+        let previous = file_manager
+            .read_summary_version(MEETING_ID, &versions[0])
+            .await
+            .unwrap();
+        assert_eq!(previous.summary, "alt");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_flat_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path();
+        std::fs::write(meeting_dir.join(format!("{}.ogg", MEETING_ID)), b"OggS").unwrap();
+        std::fs::write(meeting_dir.join(format!("{}.txt", MEETING_ID)), "a b").unwrap();
+        std::fs::write(
+            meeting_dir.join(format!("{}_chunk_00.ogg", MEETING_ID)),
+            b"OggS",
+        )
+        .unwrap();
+        std::fs::write(
+            meeting_dir.join(format!("{}_chunk_00.txt", MEETING_ID)),
+            "a",
+        )
+        .unwrap();
+
+        // The last run had two chunks, an older one left a third summary behind
+        let chunks_dir = meeting_dir.join(CHUNKS_DIR_NAME);
+        std::fs::create_dir_all(&chunks_dir).unwrap();
+        for i in 0..3 {
+            std::fs::write(chunks_dir.join(chunk_file_name(i)), "chunk").unwrap();
+            std::fs::write(
+                chunks_dir.join(chunk_summary_file_name(i)),
+                chunk_summary(&format!("legacy {}", i)),
             )
+            .unwrap();
+        }
+        std::fs::write(chunks_dir.join(ALL_CHUNK_SUMMARIES_FILE_NAME), "").unwrap();
+        // As written by the flat layout
+        let manifest = ChunkManifest {
+            audio_chunks: vec![AudioChunkEntry {
+                index: 0,
+                start_time: 0.0,
+                end_time: 600.0,
+                file: format!("{}_chunk_00.ogg", MEETING_ID),
+                transcript_range: Some((0, 1)),
+            }],
+            text_chunks: (0..2)
+                .map(|i| TextChunkEntry {
+                    index: i,
+                    file: format!("chunks/{}", chunk_file_name(i)),
+                    summary_file: format!("chunks/{}", chunk_summary_file_name(i)),
+                    char_range: (i * 2, i * 2 + 1),
+                    time_range: Some((0.0, 600.0)),
+                })
+                .collect(),
         };
+        write_manifest(meeting_dir, &manifest).await.unwrap();
 
-        let metadata = MeetingMetadata {
-            id: meeting_id.to_string(),
-            name: Some(name),
-            created_at,
-        };
+        assert!(migrate_chunk_layout(meeting_dir, MEETING_ID).await.unwrap());
 
-        let json = serde_json::to_string(&metadata)
-            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
-        std::fs::write(metadata_path, json)
-            .map_err(|e| format!("Failed to write metadata: {}", e))?;
+        let transcription_dir = transcription_chunks_dir(meeting_dir);
+        assert!(transcription_dir
+            .join(format!("{}_chunk_00.ogg", MEETING_ID))
+            .exists());
+        assert!(transcription_dir
+            .join(format!("{}_chunk_00.txt", MEETING_ID))
+            .exists());
+        assert!(!meeting_dir
+            .join(format!("{}_chunk_00.ogg", MEETING_ID))
+            .exists());
+        // The recording and transcript stay where they are
+        assert!(meeting_dir.join(format!("{}.ogg", MEETING_ID)).exists());
+        assert!(meeting_dir.join(format!("{}.txt", MEETING_ID)).exists());
 
-        Ok(())
+        assert_eq!(
+            titles(&read_chunk_summaries_from(meeting_dir).await.unwrap()),
+            vec!["legacy 0", "legacy 1"]
+        );
+        assert!(!chunks_dir.join(chunk_summary_file_name(2)).exists());
+
+        let migrated = read_manifest(meeting_dir).await.unwrap();
+        assert_eq!(
+            migrated.audio_chunks[0].file,
+            format!("chunks/transcription/{}_chunk_00.ogg", MEETING_ID)
+        );
+        for text_chunk in &migrated.text_chunks {
+            assert!(meeting_dir.join(&text_chunk.file).exists());
+            assert!(meeting_dir.join(&text_chunk.summary_file).exists());
+        }
+
+        // Already migrated
+        assert!(!migrate_chunk_layout(meeting_dir, MEETING_ID).await.unwrap());
+        assert_eq!(read_manifest(meeting_dir).await.unwrap(), migrated);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_without_manifest_counts_consecutive_summaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let chunks_dir = dir.path().join(CHUNKS_DIR_NAME);
+        std::fs::create_dir_all(&chunks_dir).unwrap();
+        for i in [0, 1, 3] {
+            std::fs::write(
+                chunks_dir.join(chunk_summary_file_name(i)),
+                chunk_summary(&format!("legacy {}", i)),
+            )
+            .unwrap();
+        }
+
+        assert!(migrate_chunk_layout(dir.path(), MEETING_ID).await.unwrap());
+        assert_eq!(
+            titles(&read_chunk_summaries_from(dir.path()).await.unwrap()),
+            vec!["legacy 0", "legacy 1"]
+        );
+        assert!(!dir.path().join(MANIFEST_FILE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_keeps_the_current_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path();
+        let run_id = write_run(meeting_dir, "run", 2).await;
+        finish_summarization_run(meeting_dir, &run_id)
+            .await
+            .unwrap();
+        let chunks_dir = meeting_dir.join(CHUNKS_DIR_NAME);
+        std::fs::write(chunks_dir.join(".DS_Store"), "").unwrap();
+
+        assert!(!migrate_chunk_layout(meeting_dir, MEETING_ID).await.unwrap());
+        assert!(chunks_dir.join(".DS_Store").exists());
+        assert_eq!(
+            current_summarization_run_dir(meeting_dir).await,
+            Some(meeting_dir.join(summarization_run_path(&run_id)))
+        );
+
+        // Flat leftovers don't replace a run either
+        std::fs::write(
+            chunks_dir.join(chunk_summary_file_name(0)),
+            chunk_summary("legacy 0"),
+        )
+        .unwrap();
+        assert!(!migrate_chunk_layout(meeting_dir, MEETING_ID).await.unwrap());
+        assert_eq!(
+            titles(&read_chunk_summaries_from(meeting_dir).await.unwrap()),
+            vec!["run 0", "run 1"]
+        );
+    }
+
+    async fn write_chunk_run(meeting_dir: &Path, transcript: &str, chunks: &[String]) {
+        let run_id = start_summarization_run(meeting_dir).await.unwrap();
+        let run_dir = meeting_dir.join(summarization_run_path(&run_id));
+        for (i, chunk) in chunks.iter().enumerate() {
+            std::fs::write(run_dir.join(chunk_file_name(i)), chunk).unwrap();
+        }
+        crate::chunk_manifest::save_text_chunks(meeting_dir, transcript, chunks, &run_id)
+            .await
+            .unwrap();
+        finish_summarization_run(meeting_dir, &run_id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_chunk_text_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let transcript = "Äpfel eins.\n\nBirnen zwei.\n\nKürbis drei.";
+        let chunks: Vec<String> = transcript.split("\n\n").map(str::to_string).collect();
+        write_chunk_run(dir.path(), transcript, &chunks).await;
+
+        let mut previous_end = None;
+        for (i, chunk) in chunks.iter().enumerate() {
+            let chunk_text = read_chunk_text_from(dir.path(), i + 1, None, Some(transcript))
+                .await
+                .unwrap();
+            let (start, end) = chunk_text.char_range.unwrap();
+
+            assert_eq!(&chunk_text.text, chunk);
+            assert_eq!(end - start, chunk.chars().count());
+            assert_eq!(chunk_text.matches_transcript, Some(true));
+            assert!(!chunk_text.truncated);
+            // Chunks are separated by the two newlines they were split at
+            if let Some(previous_end) = previous_end {
+                assert_eq!(start, previous_end + 2);
+            }
+            previous_end = Some(end);
+        }
+        assert_eq!(previous_end, Some(transcript.chars().count()));
+
+        assert!(read_chunk_text_from(dir.path(), 0, None, None)
+            .await
+            .is_err());
+        let error = read_chunk_text_from(dir.path(), 4, None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error, "Chunk 4 not found");
+    }
+
+    #[tokio::test]
+    async fn test_chunk_text_cap_and_staleness() {
+        let dir = tempfile::tempdir().unwrap();
+        let transcript = "Kürbis drei.";
+        write_chunk_run(dir.path(), transcript, &[transcript.to_string()]).await;
+
+        let full = read_chunk_text_from(dir.path(), 1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(full.matches_transcript, None);
+
+        let capped = read_chunk_text_from(dir.path(), 1, Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(capped.text, "Kü");
+        assert!(capped.truncated);
+        assert_eq!(capped.total_chars, 12);
+        assert_eq!(capped.hash, full.hash);
+
+        // The transcript was edited after the split
+        let stale = read_chunk_text_from(dir.path(), 1, None, Some("Ein Kürbis drei."))
+            .await
+            .unwrap();
+        assert_eq!(stale.matches_transcript, Some(false));
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_chunk_text_from(dir.path(), 1, None, None)
+            .await
+            .unwrap_err()
+            .contains("not been summarized"));
     }
 }
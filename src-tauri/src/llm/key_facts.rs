@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::AppHandle;
+use tokio::fs;
+
+use crate::llm::approval::{ensure_summary_replaceable, supersede_approval};
+use crate::llm::file_manager::FileManager;
+use crate::llm::models::{KeyFact, MeetingToMarkdown};
+use crate::meeting::write_atomic;
+
+/// Key facts corrected by the user, kept apart from `summary.json` so a full
+/// re-summarization can use them again
+const KEY_FACTS_OVERRIDE_FILE: &str = "key_facts_override.json";
+
+fn clean_role(role: Option<String>) -> Option<String> {
+    role.map(|role| role.trim().to_string())
+        .filter(|role| !role.is_empty())
+}
+
+/// Trim names and drop empty roles. Attendees need a name and distinct ids.
+pub fn validate_key_facts(key_facts: KeyFact) -> Result<KeyFact, String> {
+    let attendees = match key_facts.attendees {
+        Some(mut attendees) => {
+            let mut ids = HashSet::new();
+            for attendee in &mut attendees {
+                attendee.name = attendee.name.trim().to_string();
+                if attendee.name.is_empty() {
+                    return Err(format!("Attendee {} has no name", attendee.id));
+                }
+                if !ids.insert(attendee.id) {
+                    return Err(format!("Attendee id {} is used twice", attendee.id));
+                }
+            }
+            (!attendees.is_empty()).then_some(attendees)
+        }
+        None => None,
+    };
+
+    Ok(KeyFact {
+        responisible_for_moderation: clean_role(key_facts.responisible_for_moderation),
+        responisible_for_protocol: clean_role(key_facts.responisible_for_protocol),
+        responisible_for_timekeeping: clean_role(key_facts.responisible_for_timekeeping),
+        attendees,
+    })
+}
+
+pub async fn read_key_facts_override(meeting_dir: &Path) -> Result<Option<KeyFact>, String> {
+    match fs::read_to_string(meeting_dir.join(KEY_FACTS_OVERRIDE_FILE)).await {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse corrected key facts: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read corrected key facts: {}", e)),
+    }
+}
+
+pub async fn write_key_facts_override(
+    meeting_dir: &Path,
+    key_facts: &KeyFact,
+) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(key_facts)
+        .map_err(|e| format!("Failed to serialize key facts: {}", e))?;
+    write_atomic(&meeting_dir.join(KEY_FACTS_OVERRIDE_FILE), json)
+        .await
+        .map_err(|e| format!("Failed to save corrected key facts: {}", e))
+}
+
+/// The corrected key facts a summary run has to respect. With `required`
+/// it is an error when the user hasn't corrected any.
+pub async fn authoritative_key_facts_for(
+    meeting_dir: &Path,
+    required: bool,
+) -> Result<Option<KeyFact>, String> {
+    let key_facts = read_key_facts_override(meeting_dir).await?;
+    if required && key_facts.is_none() {
+        return Err("No corrected key facts saved for this meeting".to_string());
+    }
+    Ok(key_facts)
+}
+
+/// Replace the key facts of the current summary with the user's corrections.
+/// The previous summary is archived like any other new version.
+#[tauri::command]
+pub async fn update_key_facts(
+    app: AppHandle,
+    meeting_id: &str,
+    key_facts: KeyFact,
+    supersede_approved: Option<bool>,
+) -> Result<String, String> {
+    let key_facts = validate_key_facts(key_facts)?;
+    let file_manager = FileManager::new(app);
+    let meeting_dir = file_manager.get_meeting_dir(meeting_id)?;
+    ensure_summary_replaceable(&meeting_dir, supersede_approved.unwrap_or(false)).await?;
+
+    let mut summary = file_manager.read_summary(meeting_id).await?;
+    summary.key_facts = key_facts.clone();
+    file_manager
+        .save_final_summary(meeting_id, &summary)
+        .await?;
+    write_key_facts_override(&meeting_dir, &key_facts).await?;
+    supersede_approval(&meeting_dir, meeting_id).await?;
+
+    Ok(summary.to_markdown())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::Attendee;
+
+    fn attendee(id: usize, name: &str) -> Attendee {
+        Attendee {
+            id,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_key_facts() {
+        let key_facts = validate_key_facts(KeyFact {
+            responisible_for_moderation: Some(" Clara Weber ".to_string()),
+            responisible_for_protocol: Some("  ".to_string()),
+            responisible_for_timekeeping: None,
+            attendees: Some(vec![attendee(1, " Anna "), attendee(2, "Ben")]),
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&key_facts).unwrap(),
+            serde_json::json!({
+                "responisible_for_moderation": "Clara Weber",
+                "responisible_for_protocol": null,
+                "responisible_for_timekeeping": null,
+                "attendees": [{"id": 1, "name": "Anna"}, {"id": 2, "name": "Ben"}]
+            })
+        );
+
+        let empty = validate_key_facts(KeyFact {
+            attendees: Some(Vec::new()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(empty.attendees.is_none());
+
+        let error = validate_key_facts(KeyFact {
+            attendees: Some(vec![attendee(1, "Anna"), attendee(1, "Ben")]),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(error, "Attendee id 1 is used twice");
+        assert!(validate_key_facts(KeyFact {
+            attendees: Some(vec![attendee(3, " ")]),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_key_facts_override_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_key_facts_override(dir.path()).await.unwrap().is_none());
+        assert!(authoritative_key_facts_for(dir.path(), false)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(authoritative_key_facts_for(dir.path(), true).await.is_err());
+
+        let key_facts = KeyFact {
+            responisible_for_protocol: Some("Anna".to_string()),
+            attendees: Some(vec![attendee(1, "Anna")]),
+            ..Default::default()
+        };
+        write_key_facts_override(dir.path(), &key_facts)
+            .await
+            .unwrap();
+
+        let read = read_key_facts_override(dir.path()).await.unwrap().unwrap();
+        assert_eq!(
+            serde_json::to_value(&read).unwrap(),
+            serde_json::to_value(&key_facts).unwrap()
+        );
+        assert!(authoritative_key_facts_for(dir.path(), false)
+            .await
+            .unwrap()
+            .is_some());
+
+        std::fs::write(dir.path().join(KEY_FACTS_OVERRIDE_FILE), "{").unwrap();
+        assert!(read_key_facts_override(dir.path()).await.is_err());
+    }
+}
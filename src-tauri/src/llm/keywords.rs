@@ -0,0 +1,201 @@
+use schemars::schema_for;
+use std::path::Path;
+
+use crate::filename::is_emoji_component;
+use crate::llm::{
+    error::{LlmError, LlmResult},
+    models::{FinalSummaryFormat, MeetingToMarkdown},
+    prompts::{Language, PromptManager},
+    service::TextGenerator,
+};
+use crate::meeting::update_meeting_metadata;
+
+const MAX_AUTO_TAGS: usize = 5;
+const MAX_TAG_CHARS: usize = 40;
+
+/// Lowercase, strip emoji and surrounding punctuation, drop duplicates and
+/// keep at most `MAX_AUTO_TAGS`, in the order the model returned them
+pub fn normalize_keywords(keywords: Vec<String>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for keyword in keywords {
+        let without_emoji: String = keyword
+            .chars()
+            .filter(|c| !is_emoji_component(*c) && !c.is_control())
+            .collect();
+        let tag = without_emoji
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_CHARS || tags.contains(&tag) {
+            continue;
+        }
+        tags.push(tag);
+
+        if tags.len() == MAX_AUTO_TAGS {
+            break;
+        }
+    }
+
+    tags
+}
+
+/// Ask the model for keywords describing the summary
+pub async fn extract_keywords<G: TextGenerator>(
+    generator: &G,
+    language: &Language,
+    summary: &FinalSummaryFormat,
+) -> LlmResult<Vec<String>> {
+    let response = generator
+        .generate(
+            PromptManager::extract_keywords(language),
+            &summary.to_markdown(),
+            Some(schema_for!(Vec<String>)),
+        )
+        .await?;
+
+    let keywords: Vec<String> = serde_json::from_str(&response)
+        .map_err(|e| LlmError::ParseError(format!("Failed to parse keywords JSON: {}", e)))?;
+
+    Ok(normalize_keywords(keywords))
+}
+
+/// Store keywords from the summary as `auto_tags`. This step is optional, so
+/// failures are logged and the previous tags are kept.
+pub async fn update_auto_tags<G: TextGenerator>(
+    generator: &G,
+    language: &Language,
+    meeting_dir: &Path,
+    meeting_id: &str,
+    summary: &FinalSummaryFormat,
+) {
+    let tags = match extract_keywords(generator, language, summary).await {
+        Ok(tags) => tags,
+        Err(e) => {
+            println!("⚠️  Skipping keyword extraction: {}", e);
+            return;
+        }
+    };
+
+    println!("🏷️  Auto tags: {}", tags.join(", "));
+    if let Err(e) = update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.auto_tags = tags;
+    })
+    .await
+    {
+        println!("⚠️  Failed to save auto tags: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::{KeyFact, Title};
+    use crate::llm::service::stub::StubGenerator;
+    use crate::meeting::read_meeting_metadata;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    fn summary() -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "💶".to_string(),
+                text: "Budgetplanung".to_string(),
+            },
+            key_facts: KeyFact {
+                responisible_for_moderation: None,
+                responisible_for_protocol: None,
+                responisible_for_timekeeping: None,
+                attendees: None,
+            },
+            summary: "Das Budget für 2025 wurde besprochen.".to_string(),
+            topics: Vec::new(),
+            todos: Vec::new(),
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_keywords() {
+        let keywords = strings(&[
+            "Budget",
+            "💶 budget",
+            "  Büro  Umzug ",
+            "Hiring 🚀",
+            "\"Roadmap\".",
+            "🎉",
+        ]);
+
+        assert_eq!(
+            normalize_keywords(keywords),
+            strings(&["budget", "büro umzug", "hiring", "roadmap"])
+        );
+    }
+
+    #[test]
+    fn test_normalize_keeps_at_most_five() {
+        let keywords = strings(&["a1", "a2", "a3", "a4", "a5", "a6"]);
+        assert_eq!(normalize_keywords(keywords).len(), MAX_AUTO_TAGS);
+    }
+
+    #[tokio::test]
+    async fn test_auto_tags_are_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let generator = StubGenerator::responding(r#"["Budget", "Q1 Planung ✅", "budget"]"#);
+
+        update_auto_tags(
+            &generator,
+            &Language::German,
+            dir.path(),
+            MEETING_ID,
+            &summary(),
+        )
+        .await;
+
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.auto_tags, strings(&["budget", "q1 planung"]));
+    }
+
+    #[tokio::test]
+    async fn test_failed_extraction_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        update_meeting_metadata(dir.path(), MEETING_ID, |metadata| {
+            metadata.auto_tags = strings(&["budget"]);
+        })
+        .await
+        .unwrap();
+
+        let failing = StubGenerator::failing("connection refused");
+        update_auto_tags(
+            &failing,
+            &Language::German,
+            dir.path(),
+            MEETING_ID,
+            &summary(),
+        )
+        .await;
+
+        let invalid = StubGenerator::responding("budget, planung");
+        assert!(matches!(
+            extract_keywords(&invalid, &Language::German, &summary()).await,
+            Err(LlmError::ParseError(_))
+        ));
+        update_auto_tags(
+            &invalid,
+            &Language::German,
+            dir.path(),
+            MEETING_ID,
+            &summary(),
+        )
+        .await;
+
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.auto_tags, strings(&["budget"]));
+    }
+}
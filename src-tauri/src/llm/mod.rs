@@ -1,30 +1,52 @@
+pub mod api_keys;
+pub mod approval;
+pub mod attendee_refs;
+pub mod attribution;
+pub mod bullet_dedup;
+pub mod chunk_failures;
 pub mod config;
+pub mod digest;
+pub mod endpoint_privacy;
 pub mod error;
 pub mod file_manager;
+pub mod key_facts;
+pub mod keywords;
+pub mod model_list;
 pub mod models;
+pub mod outline;
+pub mod partial;
 pub mod performance;
 pub mod progress;
+pub mod prompt_preview;
 pub mod prompts;
+pub mod providers;
 pub mod service;
 pub mod summary;
+pub mod summary_diff;
+#[cfg(test)]
+pub mod test_harness;
 pub mod text_processing;
+pub mod todo_groups;
+pub mod topic_merge;
+pub mod usage;
 pub mod utils;
 
 // Re-export commonly used items
 pub use config::LlmConfig;
-#[allow(unused_imports)]
-pub use error::{LlmError, LlmResult};
-#[allow(unused_imports)]
-pub use models::*;
-#[allow(unused_imports)]
-pub use prompts::{Language, PromptManager};
-#[allow(unused_imports)]
-pub use service::LlmService;
-#[allow(unused_imports)]
-pub use summary::SummaryGenerator;
+pub use prompts::get_supported_languages;
 
 // Re-export the public API tauri commands
-pub use summary::{generate_summary, get_meeting_summary, is_summarizing, test_llm_connection, regenerate_final_summary};
+pub use summary::{generate_summary, get_meeting_summary, is_summarizing, test_llm_connection, regenerate_final_summary, regenerate_chunk_summary, cancel_summarization};
+
+pub use summary_diff::{diff_summaries, list_summary_versions};
+
+pub use digest::generate_digest;
+
+pub use api_keys::{has_provider_api_key, set_provider_api_key};
+
+pub use usage::get_usage_report;
+
+pub use prompt_preview::preview_summary_prompts;
 
 // Make sure the tauri command macros are available
 pub use summary::{
@@ -32,5 +54,13 @@ pub use summary::{
     __cmd__get_meeting_summary, 
     __cmd__is_summarizing, 
     __cmd__test_llm_connection,
-    __cmd__regenerate_final_summary
+    __cmd__regenerate_final_summary,
+    __cmd__regenerate_chunk_summary,
+    __cmd__cancel_summarization
 };
+pub use summary_diff::{__cmd__diff_summaries, __cmd__list_summary_versions};
+pub use digest::__cmd__generate_digest;
+pub use api_keys::{__cmd__has_provider_api_key, __cmd__set_provider_api_key};
+pub use usage::__cmd__get_usage_report;
+pub use prompt_preview::__cmd__preview_summary_prompts;
+pub use prompts::__cmd__get_supported_languages;
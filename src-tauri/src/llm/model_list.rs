@@ -0,0 +1,337 @@
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest::Client;
+use tokio::sync::Mutex;
+
+use crate::llm::{
+    api_keys::get_api_key,
+    config::{LlmConfig, LlmProvider},
+    error::{IntoLlmError, LlmError, LlmResult},
+    providers::{ollama_error, openai_api_url},
+};
+use crate::AppState;
+
+const OLLAMA_TAGS_ENDPOINT: &str = "/api/tags";
+const OPENAI_MODELS_ENDPOINT: &str = "/v1/models";
+/// The settings dropdown asks again on every render
+const MODEL_LIST_TTL: Duration = Duration::from_secs(60);
+/// A stopped or unreachable server must not keep the dropdown loading
+const MODEL_LIST_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MODEL_LIST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A model the endpoint can answer with
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LlmModel {
+    pub name: String,
+    /// Size on disk, only reported by Ollama
+    pub size_bytes: Option<u64>,
+    /// RFC 3339, when the model was pulled or created
+    pub modified_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTags {
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTag {
+    name: String,
+    #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
+    modified_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiModels {
+    data: Vec<OpenAiModel>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiModel {
+    id: String,
+    /// Unix timestamp, not sent by every server
+    #[serde(default)]
+    created: Option<i64>,
+}
+
+/// Models of the last listing, valid for a minute and only for the endpoint
+/// they were listed from
+#[derive(Default)]
+pub struct ModelListCache {
+    entry: Option<(LlmProvider, String, Instant, Vec<LlmModel>)>,
+}
+
+impl ModelListCache {
+    pub fn get(&self, provider: LlmProvider, endpoint: &str, now: Instant) -> Option<&[LlmModel]> {
+        match &self.entry {
+            Some((cached_provider, cached_endpoint, fetched_at, models))
+                if *cached_provider == provider
+                    && cached_endpoint == endpoint
+                    && now.duration_since(*fetched_at) < MODEL_LIST_TTL =>
+            {
+                Some(models)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn store(
+        &mut self,
+        provider: LlmProvider,
+        endpoint: &str,
+        now: Instant,
+        models: Vec<LlmModel>,
+    ) {
+        self.entry = Some((provider, endpoint.to_string(), now, models));
+    }
+}
+
+fn offline_error(endpoint: &str) -> impl FnOnce(LlmError) -> LlmError + '_ {
+    move |e| LlmError::NetworkError(format!("Endpoint {} is offline: {}", endpoint, e))
+}
+
+pub async fn list_ollama_models(client: &Client, base_url: &str) -> LlmResult<Vec<LlmModel>> {
+    let response = client
+        .get(format!("{}{}", base_url, OLLAMA_TAGS_ENDPOINT))
+        .send()
+        .await
+        .map_network_err("Failed to reach Ollama")
+        .map_err(offline_error(base_url))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_network_err("Failed to read Ollama response")?;
+    if !status.is_success() {
+        return Err(ollama_error(status.as_u16(), &body));
+    }
+
+    let tags: OllamaTags =
+        serde_json::from_str(&body).map_parse_err("Failed to parse Ollama model list")?;
+    Ok(tags
+        .models
+        .into_iter()
+        .map(|tag| LlmModel {
+            name: tag.name,
+            size_bytes: tag.size,
+            modified_at: tag.modified_at,
+        })
+        .collect())
+}
+
+/// `/v1/models` of OpenAI or a server with its API, `base_url` may end in `/v1`
+pub async fn list_openai_models(
+    client: &Client,
+    base_url: &str,
+    api_key: Option<&str>,
+) -> LlmResult<Vec<LlmModel>> {
+    let mut request = client.get(openai_api_url(base_url, OPENAI_MODELS_ENDPOINT));
+    if let Some(api_key) = api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let response = request
+        .send()
+        .await
+        .map_network_err("Failed to reach the model list")
+        .map_err(offline_error(base_url))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_network_err("Failed to read the model list")?;
+    if !status.is_success() {
+        return Err(LlmError::ServerError(format!(
+            "{} returned {}: {}",
+            base_url,
+            status,
+            body.trim()
+        )));
+    }
+
+    let models: OpenAiModels =
+        serde_json::from_str(&body).map_parse_err("Failed to parse the model list")?;
+    Ok(models
+        .data
+        .into_iter()
+        .map(|model| LlmModel {
+            name: model.id,
+            size_bytes: None,
+            modified_at: model
+                .created
+                .and_then(|created| DateTime::from_timestamp(created, 0))
+                .map(|created| created.to_rfc3339()),
+        })
+        .collect())
+}
+
+/// Models of the configured endpoint, sorted by name
+pub async fn list_models(config: &LlmConfig) -> LlmResult<Vec<LlmModel>> {
+    let client = Client::builder()
+        .connect_timeout(MODEL_LIST_CONNECT_TIMEOUT)
+        .timeout(MODEL_LIST_TIMEOUT)
+        .build()
+        .expect("Failed to build HTTP client");
+    let endpoint = config.endpoint();
+    let mut models = match config.provider {
+        LlmProvider::Ollama => list_ollama_models(&client, endpoint).await?,
+        LlmProvider::OpenAi | LlmProvider::OpenAiCompatible => {
+            let api_key = get_api_key(config.provider).unwrap_or_default();
+            list_openai_models(&client, endpoint, api_key.as_deref()).await?
+        }
+        LlmProvider::Anthropic => {
+            return Err(LlmError::ConfigError(
+                "Listing models is not supported for Anthropic".to_string(),
+            ))
+        }
+    };
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
+/// Models to pick from in the settings. `refresh` skips the cached list.
+#[tauri::command]
+pub async fn list_llm_models(
+    app: AppHandle,
+    refresh: Option<bool>,
+) -> Result<Vec<LlmModel>, String> {
+    let state = app.state::<Mutex<AppState>>();
+    let config = {
+        let state = state.lock().await;
+        if !refresh.unwrap_or(false) {
+            let config = &state.llm_config;
+            if let Some(models) =
+                state
+                    .llm_models
+                    .get(config.provider, config.endpoint(), Instant::now())
+            {
+                return Ok(models.to_vec());
+            }
+        }
+        state.llm_config.clone()
+    };
+
+    // Not holding the lock while the server answers
+    let models = list_models(&config).await?;
+    state.lock().await.llm_models.store(
+        config.provider,
+        config.endpoint(),
+        Instant::now(),
+        models.clone(),
+    );
+    Ok(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(name: &str) -> LlmModel {
+        LlmModel {
+            name: name.to_string(),
+            size_bytes: None,
+            modified_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ollama_models() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/tags")
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "models": [{
+                        "name": "llama3.1:latest",
+                        "model": "llama3.1:latest",
+                        "modified_at": "2025-03-01T10:00:00.000000+01:00",
+                        "size": 4_920_753_328u64,
+                        "digest": "46e0c10c039e",
+                        "details": {"family": "llama", "parameter_size": "8.0B"},
+                    }],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let models = list_ollama_models(&Client::new(), &server.url())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            models,
+            [LlmModel {
+                name: "llama3.1:latest".to_string(),
+                size_bytes: Some(4_920_753_328),
+                modified_at: Some("2025-03-01T10:00:00.000000+01:00".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_models() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/v1/models")
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"object":"list","data":[{"id":"qwen2.5-7b-instruct","object":"model","created":1740823200,"owned_by":"organization_owner"},{"id":"text-embedding-nomic","object":"model"}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let models = list_openai_models(&Client::new(), &format!("{}/v1", server.url()), None)
+            .await
+            .unwrap();
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].name, "qwen2.5-7b-instruct");
+        assert_eq!(
+            models[0].modified_at.as_deref(),
+            Some("2025-03-01T10:00:00+00:00")
+        );
+        assert_eq!(models[1].modified_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_offline_endpoint_is_a_network_error() {
+        // Nothing listens on a port that was just released
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = list_ollama_models(&Client::new(), &url).await.unwrap_err();
+
+        assert!(matches!(error, LlmError::NetworkError(_)));
+        assert!(error.to_string().contains("offline"));
+    }
+
+    #[test]
+    fn test_cache_expires_and_follows_the_endpoint() {
+        let mut cache = ModelListCache::default();
+        let now = Instant::now();
+        let endpoint = "http://localhost:11434";
+        assert!(cache.get(LlmProvider::Ollama, endpoint, now).is_none());
+
+        cache.store(LlmProvider::Ollama, endpoint, now, vec![model("llama3.1")]);
+        let later = now + Duration::from_secs(30);
+        assert_eq!(
+            cache.get(LlmProvider::Ollama, endpoint, later),
+            Some(&[model("llama3.1")][..])
+        );
+        assert!(cache
+            .get(LlmProvider::Ollama, "http://gpu-box:11434", later)
+            .is_none());
+        assert!(cache
+            .get(LlmProvider::OpenAiCompatible, endpoint, later)
+            .is_none());
+        assert!(cache
+            .get(LlmProvider::Ollama, endpoint, now + MODEL_LIST_TTL)
+            .is_none());
+    }
+}
@@ -1,5 +1,11 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::llm::chunk_failures::FailedChunk;
+use crate::llm::outline::OutlineEntry;
+use crate::llm::prompts::Language;
+use crate::llm::todo_groups::{group_todos_by_assignee, is_shared, unassigned_heading};
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Attendee {
@@ -7,7 +13,7 @@ pub struct Attendee {
     pub name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct KeyFact {
     pub responisible_for_moderation: Option<String>,
     pub responisible_for_protocol: Option<String>,
@@ -22,7 +28,7 @@ pub struct Topic {
     pub sub_topics: Option<Vec<Topic>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ToDo {
     pub assignees: Option<Vec<String>>,
     pub task: String,
@@ -56,13 +62,144 @@ pub struct FinalSummaryFormat {
     pub todos: Vec<ToDo>,
 }
 
+/// Outcome of `generate_summary`. Returned to the frontend, emitted as
+/// `summarization-finished` and kept in `summary_meta.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryResult {
+    pub meeting_id: String,
+    pub markdown: String,
+    pub title: String,
+    pub duration_seconds: f64,
+    pub chunk_count: usize,
+    /// Chunk summaries taken over from an earlier run instead of asking the LLM
+    pub reused_chunks: usize,
+    /// Transcription warnings of the meeting, the summary may be affected
+    pub warnings: Vec<String>,
+    /// Chunks skipped because they kept failing, their content is missing
+    #[serde(default)]
+    pub failed_chunks: Vec<FailedChunk>,
+    /// Progress events the frontend didn't receive, e.g. after its window was closed
+    #[serde(default)]
+    pub failed_progress_events: usize,
+}
+
+impl SummaryResult {
+    pub fn new(
+        meeting_id: &str,
+        summary: &FinalSummaryFormat,
+        duration: Duration,
+        chunk_count: usize,
+        warnings: Vec<String>,
+    ) -> Self {
+        Self {
+            meeting_id: meeting_id.to_string(),
+            markdown: summary.to_markdown(),
+            title: summary.title.to_string(),
+            duration_seconds: duration.as_secs_f64(),
+            chunk_count,
+            // Set by the run, like `failed_chunks`
+            reused_chunks: 0,
+            warnings,
+            failed_chunks: Vec::new(),
+            failed_progress_events: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DigestTheme {
+    pub title: String,
+    pub description: String,
+    /// Titles of the meetings the theme came up in
+    pub meetings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AssigneeTodos {
+    pub assignee: String,
+    pub tasks: Vec<String>,
+}
+
+/// Digest across several meetings, e.g. for a week
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DigestFormat {
+    pub period_overview: String,
+    pub themes: Vec<DigestTheme>,
+    pub open_todos: Vec<AssigneeTodos>,
+}
+
+/// Layout of long summaries in `to_markdown`. The default renders everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownOptions {
+    /// List at most this many attendees, the rest become "and X more"
+    pub max_attendees: Option<usize>,
+    /// Bullets per topic, the rest go into a "Further points" sub-list
+    pub max_bullets_per_topic: Option<usize>,
+    /// Add a table of contents to summaries longer than this many characters
+    pub toc_min_chars: Option<usize>,
+    /// List the to-dos in the model's order instead of grouped by assignee
+    pub flat_todos: bool,
+    /// Language of the headings the renderer adds itself
+    pub language: Language,
+}
+
 pub trait MeetingToMarkdown {
-    fn to_markdown(&self) -> String;
+    fn to_markdown_with(&self, options: &MarkdownOptions) -> String;
+
+    fn to_markdown(&self) -> String {
+        self.to_markdown_with(&MarkdownOptions::default())
+    }
+}
+
+impl FinalSummaryFormat {
+    /// Links to the sections and topics
+    fn table_of_contents(&self, outline: &[OutlineEntry]) -> String {
+        let mut toc = String::from("## Contents\n");
+        toc.push_str("- [Key Facts](#key-facts)\n");
+        toc.push_str("- [Topics](#topics)\n");
+        for entry in outline {
+            toc.push_str(&format!("  - [{}](#{})\n", entry.title, entry.anchor));
+        }
+        toc.push_str("- [To-Dos](#to-dos)\n");
+        toc.push('\n');
+        toc
+    }
+}
+
+/// A topic heading with the anchor from the outline, its bullets and sub topics
+fn push_topic(
+    markdown: &mut String,
+    topic: &Topic,
+    entry: &OutlineEntry,
+    options: &MarkdownOptions,
+) {
+    markdown.push_str(&format!(
+        "{} {} <a id=\"{}\"></a>\n",
+        "#".repeat((entry.level + 2).min(6)),
+        entry.title,
+        entry.anchor
+    ));
+    let shown = options
+        .max_bullets_per_topic
+        .unwrap_or(topic.bullet_points.len());
+    for bullet in topic.bullet_points.iter().take(shown) {
+        markdown.push_str(&format!("- {}\n", bullet));
+    }
+    if topic.bullet_points.len() > shown {
+        markdown.push_str("- Further points:\n");
+        for bullet in &topic.bullet_points[shown..] {
+            markdown.push_str(&format!("  - {}\n", bullet));
+        }
+    }
+    for (sub_topic, entry) in topic.sub_topics.iter().flatten().zip(&entry.children) {
+        push_topic(markdown, sub_topic, entry, options);
+    }
 }
 
 impl MeetingToMarkdown for FinalSummaryFormat {
-    fn to_markdown(&self) -> String {
-        let mut markdown = format!("# {}\n\n", self.title.text);
+    fn to_markdown_with(&self, options: &MarkdownOptions) -> String {
+        let mut markdown = String::new();
         markdown.push_str(self.summary.as_str());
         markdown.push_str("\n\n");
         markdown.push_str("## Key Facts\n");
@@ -77,42 +214,256 @@ impl MeetingToMarkdown for FinalSummaryFormat {
         }
         if let Some(attendees) = &self.key_facts.attendees {
             markdown.push_str("- **Attendees:**\n");
-            for attendee in attendees {
+            let shown = options.max_attendees.unwrap_or(attendees.len());
+            for attendee in attendees.iter().take(shown) {
                 markdown.push_str(&format!("  - {}\n", attendee.name));
             }
+            if attendees.len() > shown {
+                markdown.push_str(&format!("  - and {} more\n", attendees.len() - shown));
+            }
         }
         markdown.push_str("## Topics\n");
-        for topic in &self.topics {
-            markdown.push_str(&format!("### {} \n", topic.title));
-            for bullet in &topic.bullet_points {
-                markdown.push_str(&format!("- {}\n", bullet));
-            }
+        let outline = self.outline();
+        for (topic, entry) in self.topics.iter().zip(&outline) {
+            push_topic(&mut markdown, topic, entry, options);
         }
         markdown.push_str("## To-Dos\n");
-        for todo in &self.todos {
-            markdown.push_str(&format!("### {} \n", todo.task));
-            if let Some(assignees) = &todo.assignees {
-                markdown.push_str("  - **Assignees:** ");
-                markdown.push_str(&assignees.join(", "));
-                markdown.push('\n');
+        if options.flat_todos {
+            for todo in &self.todos {
+                markdown.push_str(&format!("### {} \n", todo.task));
+                if let Some(assignees) = &todo.assignees {
+                    markdown.push_str("  - **Assignees:** ");
+                    markdown.push_str(&assignees.join(", "));
+                    markdown.push('\n');
+                }
+            }
+        } else {
+            for group in group_todos_by_assignee(&self.todos) {
+                let heading = group
+                    .assignee
+                    .unwrap_or_else(|| unassigned_heading(&options.language));
+                markdown.push_str(&format!("### {}\n", heading));
+                for todo in group.todos {
+                    if is_shared(todo) {
+                        markdown.push_str(&format!("- {} (shared)\n", todo.task));
+                    } else {
+                        markdown.push_str(&format!("- {}\n", todo.task));
+                    }
+                }
             }
         }
-        markdown
+
+        let mut document = format!("# {}\n\n", self.title.text);
+        if options
+            .toc_min_chars
+            .is_some_and(|min_chars| markdown.chars().count() > min_chars)
+        {
+            document.push_str(&self.table_of_contents(&outline));
+        }
+        document.push_str(&markdown);
+        document
     }
 }
 
+/// Answer of `/api/chat`, or of `/api/generate` with the text in `response`
 #[derive(Serialize, Deserialize)]
 pub struct OllamaResponse {
     pub model: String,
     pub created_at: String,
-    pub response: String,
+    #[serde(default)]
+    pub response: Option<String>,
+    #[serde(default)]
+    pub message: Option<OllamaMessage>,
     pub done: bool,
+    #[serde(default)]
     pub done_reason: String,
+    /// Only sent by `/api/generate`
+    #[serde(default)]
     pub context: Vec<i64>,
+    #[serde(default)]
     pub total_duration: i64,
+    #[serde(default)]
     pub load_duration: i64,
+    #[serde(default)]
     pub prompt_eval_count: i64,
+    #[serde(default)]
     pub prompt_eval_duration: i64,
+    #[serde(default)]
     pub eval_count: i64,
+    #[serde(default)]
     pub eval_duration: i64,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct OllamaMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl OllamaResponse {
+    /// The generated text of either endpoint
+    pub fn content(self) -> Option<String> {
+        self.message
+            .map(|message| message.content)
+            .or(self.response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(attendees: usize, bullets: usize) -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📅".to_string(),
+                text: "Vollversammlung".to_string(),
+            },
+            key_facts: KeyFact {
+                attendees: Some(
+                    (0..attendees)
+                        .map(|id| Attendee {
+                            id,
+                            name: format!("Person {}", id),
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            summary: "Quartalsrückblick und Planung.".to_string(),
+            topics: vec![
+                Topic {
+                    title: "Budget & Finanzen".to_string(),
+                    bullet_points: (0..bullets).map(|i| format!("Punkt {}", i)).collect(),
+                    sub_topics: None,
+                },
+                Topic {
+                    title: "Topics".to_string(),
+                    bullet_points: vec!["Doppelte Überschrift".to_string()],
+                    sub_topics: None,
+                },
+            ],
+            todos: vec![
+                ToDo {
+                    assignees: Some(vec!["Anna".to_string()]),
+                    task: "Protokoll verschicken".to_string(),
+                },
+                ToDo {
+                    assignees: None,
+                    task: "Getränke besorgen".to_string(),
+                },
+                ToDo {
+                    assignees: Some(vec!["Ben".to_string(), "Anna".to_string()]),
+                    task: "Kasse prüfen".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_summary_result() {
+        let summary = summary(3, 2);
+        let result = SummaryResult::new(
+            "meeting-1",
+            &summary,
+            Duration::from_millis(12_500),
+            4,
+            vec!["Chunk 2 failed".to_string()],
+        );
+
+        assert_eq!(result.meeting_id, "meeting-1");
+        assert_eq!(result.markdown, summary.to_markdown());
+        assert_eq!(result.title, "📅 Vollversammlung");
+        assert_eq!(result.duration_seconds, 12.5);
+        assert_eq!(result.chunk_count, 4);
+        assert_eq!(result.reused_chunks, 0);
+        assert_eq!(result.warnings, vec!["Chunk 2 failed"]);
+        assert!(result.failed_chunks.is_empty());
+    }
+
+    #[test]
+    fn test_default_options_render_everything() {
+        let markdown = summary(45, 30).to_markdown();
+
+        assert!(markdown.contains("  - Person 44\n"));
+        assert!(markdown.contains("- Punkt 29\n"));
+        assert!(!markdown.contains("more\n"));
+        assert!(!markdown.contains("Further points"));
+        assert!(!markdown.contains("## Contents"));
+    }
+
+    #[test]
+    fn test_collapse_attendees_and_bullets() {
+        let options = MarkdownOptions {
+            max_attendees: Some(10),
+            max_bullets_per_topic: Some(5),
+            ..Default::default()
+        };
+        let markdown = summary(45, 30).to_markdown_with(&options);
+
+        assert!(markdown.contains("  - Person 9\n  - and 35 more\n"));
+        assert!(!markdown.contains("Person 10\n"));
+        assert!(markdown.contains("- Punkt 4\n- Further points:\n  - Punkt 5\n"));
+        assert!(markdown.contains("  - Punkt 29\n"));
+        // Nothing to collapse when the list fits
+        assert!(summary(10, 5)
+            .to_markdown_with(&options)
+            .contains("  - Person 9\n## Topics"));
+    }
+
+    #[test]
+    fn test_todos_grouped_by_assignee() {
+        let markdown = summary(1, 1).to_markdown();
+        assert!(markdown.ends_with(
+            "## To-Dos\n\
+             ### Anna\n\
+             - Protokoll verschicken\n\
+             - Kasse prüfen (shared)\n\
+             ### Ben\n\
+             - Kasse prüfen (shared)\n\
+             ### Offen\n\
+             - Getränke besorgen\n"
+        ));
+
+        let english = MarkdownOptions {
+            language: Language::English,
+            ..Default::default()
+        };
+        assert!(summary(1, 1)
+            .to_markdown_with(&english)
+            .ends_with("### Unassigned\n- Getränke besorgen\n"));
+
+        let flat = MarkdownOptions {
+            flat_todos: true,
+            ..Default::default()
+        };
+        assert!(summary(1, 1).to_markdown_with(&flat).ends_with(
+            "## To-Dos\n\
+             ### Protokoll verschicken \n  - **Assignees:** Anna\n\
+             ### Getränke besorgen \n\
+             ### Kasse prüfen \n  - **Assignees:** Ben, Anna\n"
+        ));
+    }
+
+    #[test]
+    fn test_table_of_contents_above_threshold() {
+        let options = MarkdownOptions {
+            toc_min_chars: Some(500),
+            ..Default::default()
+        };
+
+        let short = summary(1, 1).to_markdown_with(&options);
+        assert!(!short.contains("## Contents"));
+
+        let long = summary(45, 30).to_markdown_with(&options);
+        assert!(long.starts_with(
+            "# Vollversammlung\n\n## Contents\n\
+             - [Key Facts](#key-facts)\n\
+             - [Topics](#topics)\n  \
+             - [Budget & Finanzen](#budget-finanzen)\n  \
+             - [Topics](#topics-1)\n\
+             - [To-Dos](#to-dos)\n\n\
+             Quartalsrückblick"
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+use crate::llm::file_manager::FileManager;
+use crate::llm::models::{FinalSummaryFormat, Topic};
+
+/// Headings every summary has, topics never get their anchors
+const SECTION_HEADINGS: &[&str] = &["Contents", "Key Facts", "Topics", "To-Dos"];
+
+fn transliterate(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ä' | 'æ' => "ae",
+        'ö' | 'œ' => "oe",
+        'ü' => "ue",
+        'ß' => "ss",
+        'à' | 'á' | 'â' | 'ã' | 'å' => "a",
+        'ç' => "c",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'ñ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ø' => "o",
+        'ù' | 'ú' | 'û' => "u",
+        'ý' | 'ÿ' => "y",
+        _ => return None,
+    })
+}
+
+/// Lowercase letters and digits joined by single dashes. Umlauts are
+/// transliterated ("Übergabe" is "uebergabe"), emoji and punctuation dropped.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    let mut separated = false;
+    for c in text.to_lowercase().chars() {
+        let part = match transliterate(c) {
+            Some(part) => part.to_string(),
+            None if c.is_alphanumeric() => c.to_string(),
+            None => {
+                separated = true;
+                continue;
+            }
+        };
+        if separated && !slug.is_empty() {
+            slug.push('-');
+        }
+        separated = false;
+        slug.push_str(&part);
+    }
+
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Hands out unique anchors, repeats get `-1`, `-2`, ... appended
+#[derive(Default)]
+pub struct AnchorSlugger {
+    taken: HashSet<String>,
+}
+
+impl AnchorSlugger {
+    /// Anchors of a summary: its title and the fixed sections come first, so
+    /// a topic's anchor doesn't depend on whether a table of contents is shown
+    pub fn for_summary(summary: &FinalSummaryFormat) -> Self {
+        let mut slugger = Self::default();
+        slugger.slug(&summary.title.text);
+        for heading in SECTION_HEADINGS {
+            slugger.slug(heading);
+        }
+        slugger
+    }
+
+    pub fn slug(&mut self, heading: &str) -> String {
+        let base = slugify(heading);
+        let mut slug = base.clone();
+        let mut count = 0;
+        while self.taken.contains(&slug) {
+            count += 1;
+            slug = format!("{}-{}", base, count);
+        }
+        self.taken.insert(slug.clone());
+        slug
+    }
+}
+
+/// A topic heading of a summary, for navigation without loading the summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutlineEntry {
+    pub title: String,
+    /// `id` of the heading in the rendered markdown
+    pub anchor: String,
+    /// 1 for topics, 2 for their sub topics
+    pub level: usize,
+    pub bullet_count: usize,
+    pub children: Vec<OutlineEntry>,
+}
+
+fn outline_topics(
+    topics: &[Topic],
+    level: usize,
+    slugger: &mut AnchorSlugger,
+) -> Vec<OutlineEntry> {
+    topics
+        .iter()
+        .map(|topic| OutlineEntry {
+            title: topic.title.trim().to_string(),
+            anchor: slugger.slug(&topic.title),
+            level,
+            bullet_count: topic.bullet_points.len(),
+            children: outline_topics(
+                topic.sub_topics.as_deref().unwrap_or_default(),
+                level + 1,
+                slugger,
+            ),
+        })
+        .collect()
+}
+
+impl FinalSummaryFormat {
+    /// Topics and sub topics in the order they are rendered. The markdown
+    /// takes its heading anchors from here, so both always agree.
+    pub fn outline(&self) -> Vec<OutlineEntry> {
+        outline_topics(&self.topics, 1, &mut AnchorSlugger::for_summary(self))
+    }
+}
+
+#[tauri::command]
+pub async fn get_summary_outline(
+    app: AppHandle,
+    meeting_id: &str,
+) -> Result<Vec<OutlineEntry>, String> {
+    let summary = FileManager::new(app).read_summary(meeting_id).await?;
+    Ok(summary.outline())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::{MarkdownOptions, MeetingToMarkdown, Title};
+
+    fn topic(title: &str, bullets: usize, sub_topics: Option<Vec<Topic>>) -> Topic {
+        Topic {
+            title: title.to_string(),
+            bullet_points: (0..bullets).map(|i| format!("Punkt {}", i)).collect(),
+            sub_topics,
+        }
+    }
+
+    fn summary() -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📅".to_string(),
+                text: "Vollversammlung".to_string(),
+            },
+            key_facts: Default::default(),
+            summary: "Rückblick".to_string(),
+            topics: vec![
+                topic(
+                    "💶 Budget",
+                    2,
+                    Some(vec![
+                        topic("Budget 2025", 1, None),
+                        topic("💶 Budget", 3, None),
+                    ]),
+                ),
+                topic("Topics", 1, None),
+                topic("📌 Übergabe: Q3 / Q4", 0, None),
+                topic("budget-1", 1, None),
+            ],
+            todos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("📌 Übergabe: Q3 / Q4 "), "uebergabe-q3-q4");
+        assert_eq!(slugify("💶 Budget & Finanzen"), "budget-finanzen");
+        assert_eq!(slugify("Straßenfest – Größe"), "strassenfest-groesse");
+        assert_eq!(slugify("Café Crème"), "cafe-creme");
+        assert_eq!(slugify("To-Dos"), "to-dos");
+        assert_eq!(slugify("会议"), "会议");
+        assert_eq!(slugify("🎉🎉"), "section");
+    }
+
+    #[test]
+    fn test_collisions_are_numbered() {
+        let mut slugger = AnchorSlugger::default();
+        assert_eq!(slugger.slug("💶 Budget"), "budget");
+        assert_eq!(slugger.slug("Budget"), "budget-1");
+        // A title that looks like a suffixed one doesn't take over an anchor
+        assert_eq!(slugger.slug("Budget 1"), "budget-1-1");
+        assert_eq!(slugger.slug("budget"), "budget-2");
+    }
+
+    #[test]
+    fn test_outline() {
+        let outline = summary().outline();
+        let anchors: Vec<&str> = outline.iter().map(|entry| entry.anchor.as_str()).collect();
+        assert_eq!(
+            anchors,
+            vec!["budget", "topics-1", "uebergabe-q3-q4", "budget-1-1"]
+        );
+
+        let budget = &outline[0];
+        assert_eq!(budget.title, "💶 Budget");
+        assert_eq!(budget.level, 1);
+        assert_eq!(budget.bullet_count, 2);
+        let children: Vec<(&str, usize, usize)> = budget
+            .children
+            .iter()
+            .map(|entry| (entry.anchor.as_str(), entry.level, entry.bullet_count))
+            .collect();
+        assert_eq!(children, vec![("budget-2025", 2, 1), ("budget-1", 2, 3)]);
+        assert!(outline[1].children.is_empty());
+    }
+
+    fn assert_headings_match(markdown: &str, entries: &[OutlineEntry]) {
+        for entry in entries {
+            let heading = format!(
+                "{} {} <a id=\"{}\"></a>\n",
+                "#".repeat(entry.level + 2),
+                entry.title,
+                entry.anchor
+            );
+            assert!(markdown.contains(&heading), "missing {:?}", heading);
+            assert_headings_match(markdown, &entry.children);
+        }
+    }
+
+    #[test]
+    fn test_outline_matches_markdown() {
+        let summary = summary();
+        let outline = summary.outline();
+        let options = MarkdownOptions {
+            toc_min_chars: Some(0),
+            max_bullets_per_topic: Some(1),
+            ..Default::default()
+        };
+
+        for markdown in [summary.to_markdown(), summary.to_markdown_with(&options)] {
+            assert_headings_match(&markdown, &outline);
+            assert_eq!(markdown.matches("<a id=").count(), 6);
+        }
+
+        let toc = summary.to_markdown_with(&options);
+        for entry in &outline {
+            assert!(toc.contains(&format!("  - [{}](#{})\n", entry.title, entry.anchor)));
+        }
+    }
+}
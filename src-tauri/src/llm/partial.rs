@@ -0,0 +1,455 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::export::format_timestamp;
+use crate::llm::file_manager::read_chunk_text_from;
+use crate::llm::models::{FinalSummaryFormat, MeetingToMarkdown};
+use crate::meeting::write_atomic;
+use crate::transcript::Transcript;
+
+const PARTIAL_SUMMARY_PREFIX: &str = "summary.partial.";
+
+/// Part of a meeting to summarize on its own
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SummaryRange {
+    /// Transcript segments overlapping `[start_seconds, end_seconds)`
+    Time {
+        start_seconds: f64,
+        end_seconds: f64,
+    },
+    /// Text chunks of the current summarization run, 1-based as in `chunk_001.txt`
+    Chunks { chunk_numbers: Vec<usize> },
+}
+
+impl SummaryRange {
+    /// Sorted chunk numbers without duplicates, so equal ranges get the same key
+    fn normalized(&self) -> SummaryRange {
+        match self {
+            SummaryRange::Chunks { chunk_numbers } => {
+                let mut chunk_numbers = chunk_numbers.clone();
+                chunk_numbers.sort_unstable();
+                chunk_numbers.dedup();
+                SummaryRange::Chunks { chunk_numbers }
+            }
+            range => range.clone(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SummaryRange::Time {
+                start_seconds,
+                end_seconds,
+            } => {
+                if !start_seconds.is_finite() || !end_seconds.is_finite() || *start_seconds < 0.0 {
+                    return Err("Invalid time range".to_string());
+                }
+                if start_seconds >= end_seconds {
+                    return Err("The time range has to end after it starts".to_string());
+                }
+            }
+            SummaryRange::Chunks { chunk_numbers } => {
+                if chunk_numbers.is_empty() {
+                    return Err("Select at least one chunk".to_string());
+                }
+                if chunk_numbers.contains(&0) {
+                    return Err("Chunk numbers start at 1".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Short hash naming the files of the partial summary
+    pub fn key(&self) -> String {
+        let json = serde_json::to_string(&self.normalized()).unwrap_or_default();
+        let hash = format!("{:x}", Sha256::digest(json.as_bytes()));
+        hash[..12].to_string()
+    }
+
+    pub fn describe(&self) -> String {
+        match self.normalized() {
+            SummaryRange::Time {
+                start_seconds,
+                end_seconds,
+            } => format!(
+                "{} to {}",
+                format_timestamp(start_seconds),
+                format_timestamp(end_seconds)
+            ),
+            SummaryRange::Chunks { chunk_numbers } => {
+                let numbers: Vec<String> = chunk_numbers.iter().map(usize::to_string).collect();
+                let label = if numbers.len() == 1 {
+                    "chunk"
+                } else {
+                    "chunks"
+                };
+                format!("{} {}", label, numbers.join(", "))
+            }
+        }
+    }
+}
+
+/// Text of the segments overlapping `[start_seconds, end_seconds)`. A segment
+/// that ends exactly at the start or starts exactly at the end is left out.
+pub fn text_in_time_range(transcript: &Transcript, start_seconds: f64, end_seconds: f64) -> String {
+    Transcript {
+        segments: transcript
+            .segments
+            .iter()
+            .filter(|segment| segment.end > start_seconds && segment.start < end_seconds)
+            .cloned()
+            .collect(),
+        ..Default::default()
+    }
+    .to_text()
+}
+
+/// The transcript text covered by `range`
+pub async fn extract_range_text(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    range: &SummaryRange,
+) -> Result<String, String> {
+    range.validate()?;
+
+    let text = match range.normalized() {
+        SummaryRange::Time {
+            start_seconds,
+            end_seconds,
+        } => {
+            let json = fs::read_to_string(meeting_dir.join(format!("{}.json", meeting_id)))
+                .await
+                .map_err(|_| "The meeting has no transcript with timestamps".to_string())?;
+            let transcript: Transcript = serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to parse transcript: {}", e))?;
+            text_in_time_range(&transcript, start_seconds, end_seconds)
+        }
+        SummaryRange::Chunks { chunk_numbers } => {
+            let mut chunks = Vec::new();
+            for chunk_number in chunk_numbers {
+                let chunk = read_chunk_text_from(meeting_dir, chunk_number, None, None).await?;
+                chunks.push(chunk.text);
+            }
+            chunks.join("\n\n")
+        }
+    };
+
+    if text.trim().is_empty() {
+        return Err(format!("No transcript in {}", range.describe()));
+    }
+    Ok(text)
+}
+
+/// `summary.partial.<key>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialSummary {
+    pub range: SummaryRange,
+    pub created_at: String,
+    pub summary: FinalSummaryFormat,
+}
+
+/// A partial summary as listed in the meeting overview
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartialSummaryInfo {
+    pub key: String,
+    pub range: SummaryRange,
+    pub description: String,
+    pub title: String,
+    pub created_at: String,
+}
+
+fn partial_summary_file_name(key: &str, extension: &str) -> String {
+    format!("{}{}.{}", PARTIAL_SUMMARY_PREFIX, key, extension)
+}
+
+/// Markdown of a partial summary, noting below the title which part of the meeting it covers
+pub fn partial_summary_markdown(partial: &PartialSummary) -> String {
+    partial.summary.to_markdown().replacen(
+        "\n\n",
+        &format!("\n\n_Partial summary: {}_\n\n", partial.range.describe()),
+        1,
+    )
+}
+
+/// Write the JSON and markdown of a partial summary, replacing one of the same
+/// range. The full summary is not touched. Returns the markdown.
+pub async fn save_partial_summary(
+    meeting_dir: &Path,
+    range: &SummaryRange,
+    summary: FinalSummaryFormat,
+    now: DateTime<Utc>,
+) -> Result<String, String> {
+    let key = range.key();
+    let partial = PartialSummary {
+        range: range.normalized(),
+        created_at: now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        summary,
+    };
+    let json = serde_json::to_string_pretty(&partial)
+        .map_err(|e| format!("Failed to serialize partial summary: {}", e))?;
+    let markdown = partial_summary_markdown(&partial);
+
+    write_atomic(
+        &meeting_dir.join(partial_summary_file_name(&key, "json")),
+        &json,
+    )
+    .await
+    .map_err(|e| format!("Failed to save partial summary: {}", e))?;
+    write_atomic(
+        &meeting_dir.join(partial_summary_file_name(&key, "md")),
+        &markdown,
+    )
+    .await
+    .map_err(|e| format!("Failed to save partial summary: {}", e))?;
+
+    Ok(markdown)
+}
+
+/// Partial summaries of a meeting, oldest first. Unreadable files are skipped.
+pub async fn list_partial_summaries_in(meeting_dir: &Path) -> Vec<PartialSummaryInfo> {
+    let mut partials = Vec::new();
+    let Ok(mut entries) = fs::read_dir(meeting_dir).await else {
+        return partials;
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(key) = file_name
+            .strip_prefix(PARTIAL_SUMMARY_PREFIX)
+            .and_then(|rest| rest.strip_suffix(".json"))
+        else {
+            continue;
+        };
+
+        let partial = match fs::read_to_string(entry.path()).await {
+            Ok(json) => serde_json::from_str::<PartialSummary>(&json),
+            Err(e) => {
+                println!("Warning: Failed to read {}: {}", file_name, e);
+                continue;
+            }
+        };
+        match partial {
+            Ok(partial) => partials.push(PartialSummaryInfo {
+                key: key.to_string(),
+                description: partial.range.describe(),
+                title: partial.summary.title.text,
+                range: partial.range,
+                created_at: partial.created_at,
+            }),
+            Err(e) => println!("Warning: Failed to parse {}: {}", file_name, e),
+        }
+    }
+
+    partials.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    partials
+}
+
+#[tauri::command]
+pub async fn get_partial_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    key: &str,
+) -> Result<String, String> {
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid partial summary: {}", key));
+    }
+    let meeting_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join("uploads")
+        .join(meeting_id);
+
+    fs::read_to_string(meeting_dir.join(partial_summary_file_name(key, "md")))
+        .await
+        .map_err(|_| format!("Partial summary {} not found", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::Title;
+
+    /// whisperx output with word timings, speakers and a segment without one
+    const FIXTURE: &str = r#"{
+        "segments": [
+            {"start": 0.0, "end": 4.2, "text": " Guten Morgen zusammen.", "speaker": "SPEAKER_00",
+             "words": [{"word": "Guten", "start": 0.0, "end": 0.4}]},
+            {"start": 4.2, "end": 9.8, "text": " Erstes Thema ist der Haushalt.", "speaker": "SPEAKER_00"},
+            {"start": 9.8, "end": 15.0, "text": " Der Haushalt reicht nicht.", "speaker": "SPEAKER_01"},
+            {"start": 15.5, "end": 20.0, "text": " Dann zum Raum."},
+            {"start": 20.0, "end": 26.3, "text": " Der Raum ist gebucht.", "speaker": "SPEAKER_01"}
+        ],
+        "language": "de"
+    }"#;
+
+    fn fixture() -> Transcript {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    fn summary(title: &str) -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📋".to_string(),
+                text: title.to_string(),
+            },
+            key_facts: Default::default(),
+            summary: "Zusammenfassung".to_string(),
+            topics: Vec::new(),
+            todos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_time_range_boundaries() {
+        let transcript = fixture();
+
+        // Segments touching the range only at its edges are left out
+        assert_eq!(
+            text_in_time_range(&transcript, 4.2, 9.8),
+            "[SPEAKER_00]: Erstes Thema ist der Haushalt."
+        );
+        // Partly covered segments are included whole
+        assert_eq!(
+            text_in_time_range(&transcript, 9.0, 16.0),
+            "[SPEAKER_00]: Erstes Thema ist der Haushalt.\n[SPEAKER_01]: Der Haushalt reicht nicht.\nDann zum Raum."
+        );
+        // A gap between segments
+        assert_eq!(text_in_time_range(&transcript, 15.0, 15.5), "");
+        // Beyond the end of the recording
+        assert_eq!(
+            text_in_time_range(&transcript, 20.0, 3600.0),
+            "[SPEAKER_01]: Der Raum ist gebucht."
+        );
+        assert_eq!(
+            text_in_time_range(&transcript, 0.0, 1000.0),
+            transcript.to_text()
+        );
+    }
+
+    #[test]
+    fn test_range_validation_and_key() {
+        assert!(SummaryRange::Time {
+            start_seconds: 10.0,
+            end_seconds: 10.0
+        }
+        .validate()
+        .is_err());
+        assert!(SummaryRange::Time {
+            start_seconds: f64::NAN,
+            end_seconds: 10.0
+        }
+        .validate()
+        .is_err());
+        assert!(SummaryRange::Chunks {
+            chunk_numbers: vec![]
+        }
+        .validate()
+        .is_err());
+        assert!(SummaryRange::Chunks {
+            chunk_numbers: vec![0, 1]
+        }
+        .validate()
+        .is_err());
+
+        let chunks = SummaryRange::Chunks {
+            chunk_numbers: vec![3, 2, 3],
+        };
+        assert_eq!(
+            chunks.key(),
+            SummaryRange::Chunks {
+                chunk_numbers: vec![2, 3]
+            }
+            .key()
+        );
+        assert_eq!(chunks.key().len(), 12);
+        assert_eq!(chunks.describe(), "chunks 2, 3");
+
+        let time = SummaryRange::Time {
+            start_seconds: 1800.0,
+            end_seconds: 3725.0,
+        };
+        assert_ne!(time.key(), chunks.key());
+        assert_eq!(time.describe(), "00:30:00 to 01:02:05");
+    }
+
+    #[tokio::test]
+    async fn test_extract_time_range_from_meeting() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("meeting-1.json"), FIXTURE).unwrap();
+
+        let range = SummaryRange::Time {
+            start_seconds: 20.0,
+            end_seconds: 30.0,
+        };
+        assert_eq!(
+            extract_range_text(dir.path(), "meeting-1", &range)
+                .await
+                .unwrap(),
+            "[SPEAKER_01]: Der Raum ist gebucht."
+        );
+
+        let empty = SummaryRange::Time {
+            start_seconds: 100.0,
+            end_seconds: 200.0,
+        };
+        let error = extract_range_text(dir.path(), "meeting-1", &empty)
+            .await
+            .unwrap_err();
+        assert_eq!(error, "No transcript in 00:01:40 to 00:03:20");
+
+        assert!(extract_range_text(dir.path(), "meeting-2", &range)
+            .await
+            .unwrap_err()
+            .contains("no transcript with timestamps"));
+    }
+
+    #[tokio::test]
+    async fn test_save_and_list_partial_summaries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("summary.json"), "{}").unwrap();
+        let first = SummaryRange::Chunks {
+            chunk_numbers: vec![2],
+        };
+        let second = SummaryRange::Time {
+            start_seconds: 0.0,
+            end_seconds: 60.0,
+        };
+
+        let earlier = Utc::now() - chrono::Duration::minutes(5);
+        save_partial_summary(dir.path(), &first, summary("Alt"), earlier)
+            .await
+            .unwrap();
+        let markdown = save_partial_summary(dir.path(), &second, summary("Anfang"), Utc::now())
+            .await
+            .unwrap();
+        assert!(markdown.starts_with(
+            "# Anfang\n\n_Partial summary: 00:00:00 to 00:01:00_\n\nZusammenfassung\n\n"
+        ));
+        // Same range again replaces the earlier one
+        save_partial_summary(dir.path(), &first, summary("Zweiter Teil"), earlier)
+            .await
+            .unwrap();
+
+        let partials = list_partial_summaries_in(dir.path()).await;
+        assert_eq!(partials.len(), 2);
+        assert_eq!(partials[0].title, "Zweiter Teil");
+        assert_eq!(partials[0].key, first.key());
+        assert_eq!(partials[1].description, "00:00:00 to 00:01:00");
+        assert!(dir
+            .path()
+            .join(format!("summary.partial.{}.md", second.key()))
+            .exists());
+        // The full summary is untouched
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("summary.json")).unwrap(),
+            "{}"
+        );
+    }
+}
@@ -1,18 +1,50 @@
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::llm::{file_manager::current_summarization_run_dir, usage::TokenUsage};
+use crate::meeting::write_atomic;
+use crate::AppState;
+
+/// Metrics of the run that made the current summary, next to its chunk summaries
+const METRICS_FILE_NAME: &str = "metrics.json";
+
+/// Durations as seconds, easier to read in `metrics.json` and the frontend
+mod duration_secs {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Duration::try_from_secs_f64(f64::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
+    #[serde(with = "duration_secs")]
     pub total_duration: Duration,
     pub chunk_count: usize,
+    #[serde(with = "duration_secs")]
     pub average_chunk_time: Duration,
+    #[serde(with = "duration_secs")]
     pub fastest_chunk: Duration,
+    #[serde(with = "duration_secs")]
     pub slowest_chunk: Duration,
     pub api_calls: usize,
     pub failed_api_calls: usize,
     pub total_characters_processed: usize,
     pub characters_per_second: f64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Completion tokens per second spent waiting for answers
+    pub tokens_per_second: f64,
 }
 
 #[derive(Debug)]
@@ -23,6 +55,7 @@ pub struct PerformanceTracker {
     api_call_times: Vec<Duration>,
     failed_calls: usize,
     total_characters: usize,
+    usage: TokenUsage,
     metrics: HashMap<String, Duration>,
 }
 
@@ -34,6 +67,7 @@ impl PerformanceTracker {
             api_call_times: Vec::new(),
             failed_calls: 0,
             total_characters: 0,
+            usage: TokenUsage::default(),
             metrics: HashMap::new(),
         }
     }
@@ -43,7 +77,6 @@ impl PerformanceTracker {
         Instant::now()
     }
 
-    #[allow(dead_code)]
     pub fn end_chunk(&mut self, start_time: Instant, characters: usize) {
         let duration = start_time.elapsed();
         self.chunk_times.push(duration);
@@ -55,7 +88,6 @@ impl PerformanceTracker {
         Instant::now()
     }
 
-    #[allow(dead_code)]
     pub fn end_api_call(&mut self, start_time: Instant, success: bool) {
         let duration = start_time.elapsed();
         self.api_call_times.push(duration);
@@ -64,12 +96,16 @@ impl PerformanceTracker {
         }
     }
 
+    /// Tokens the provider reported for an answered call
+    pub fn record_usage(&mut self, usage: &TokenUsage) {
+        self.usage.add(usage);
+    }
+
     #[allow(dead_code)]
     pub fn record_metric(&mut self, name: &str, duration: Duration) {
         self.metrics.insert(name.to_string(), duration);
     }
 
-    #[allow(dead_code)]
     pub fn get_metrics(&self) -> PerformanceMetrics {
         let total_duration = self.start_time.elapsed();
         let chunk_count = self.chunk_times.len();
@@ -89,6 +125,13 @@ impl PerformanceTracker {
             0.0
         };
 
+        let api_time = self.api_call_times.iter().sum::<Duration>().as_secs_f64();
+        let tokens_per_second = if api_time > 0.0 {
+            self.usage.completion_tokens as f64 / api_time
+        } else {
+            0.0
+        };
+
         PerformanceMetrics {
             total_duration,
             chunk_count,
@@ -99,10 +142,12 @@ impl PerformanceTracker {
             failed_api_calls: self.failed_calls,
             total_characters_processed: self.total_characters,
             characters_per_second,
+            prompt_tokens: self.usage.prompt_tokens,
+            completion_tokens: self.usage.completion_tokens,
+            tokens_per_second,
         }
     }
 
-    #[allow(dead_code)]
     pub fn print_summary(&self) {
         let metrics = self.get_metrics();
         println!("📊 Performance Summary:");
@@ -114,6 +159,10 @@ impl PerformanceTracker {
         println!("   API calls: {} ({} failed)", metrics.api_calls, metrics.failed_api_calls);
         println!("   Characters processed: {}", metrics.total_characters_processed);
         println!("   Processing speed: {:.1} chars/sec", metrics.characters_per_second);
+        println!(
+            "   Tokens: {} prompt, {} completion ({:.1} tokens/sec)",
+            metrics.prompt_tokens, metrics.completion_tokens, metrics.tokens_per_second
+        );
         
         if !self.metrics.is_empty() {
             println!("   Custom metrics:");
@@ -130,6 +179,89 @@ impl Default for PerformanceTracker {
     }
 }
 
+/// One tracker shared by every `LlmService` of a summarization run, like `UsageMeter`
+#[derive(Debug, Clone, Default)]
+pub struct SharedPerformanceTracker(Arc<Mutex<PerformanceTracker>>);
+
+impl SharedPerformanceTracker {
+    /// Forget earlier calls and chunks, the run starts now
+    pub fn restart(&self) {
+        *self.0.lock().unwrap() = PerformanceTracker::new();
+    }
+
+    /// A chunk summarized since `start_time`, returns how long it took
+    pub fn record_chunk(&self, start_time: Instant, characters: usize) -> Duration {
+        let mut tracker = self.0.lock().unwrap();
+        tracker.end_chunk(start_time, characters);
+        *tracker.chunk_times.last().unwrap()
+    }
+
+    /// Times of the chunks summarized so far, oldest first
+    pub fn chunk_times(&self) -> Vec<Duration> {
+        self.0.lock().unwrap().chunk_times.clone()
+    }
+
+    /// A call that started at `start_time`, with the tokens of its answer
+    pub fn record_api_call(&self, start_time: Instant, usage: Option<&TokenUsage>) {
+        let mut tracker = self.0.lock().unwrap();
+        tracker.end_api_call(start_time, usage.is_some());
+        if let Some(usage) = usage {
+            tracker.record_usage(usage);
+        }
+    }
+
+    pub fn metrics(&self) -> PerformanceMetrics {
+        self.0.lock().unwrap().get_metrics()
+    }
+
+    pub fn print_summary(&self) {
+        self.0.lock().unwrap().print_summary();
+    }
+}
+
+pub async fn save_metrics(run_dir: &Path, metrics: &PerformanceMetrics) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(metrics)
+        .map_err(|e| format!("Failed to serialize metrics: {}", e))?;
+    write_atomic(&run_dir.join(METRICS_FILE_NAME), json)
+        .await
+        .map_err(|e| format!("Failed to write metrics: {}", e))
+}
+
+/// Metrics of the current summary, `None` for summaries made before they were kept
+pub async fn read_metrics(meeting_dir: &Path) -> Option<PerformanceMetrics> {
+    let run_dir = current_summarization_run_dir(meeting_dir).await?;
+    let json = tokio::fs::read_to_string(run_dir.join(METRICS_FILE_NAME))
+        .await
+        .ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Tokens and time the current summary took
+#[tauri::command]
+pub async fn get_summary_metrics(
+    app: AppHandle,
+    meeting_id: &str,
+) -> Result<Option<PerformanceMetrics>, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    Ok(read_metrics(&meeting_dir).await)
+}
+
+/// Metrics of the running summarization, or of the last one since the app started
+#[tauri::command]
+pub async fn get_performance_metrics(app: AppHandle) -> Result<Option<PerformanceMetrics>, String> {
+    let state = app.state::<tokio::sync::Mutex<AppState>>();
+    let state = state.lock().await;
+    Ok(state
+        .summarization_performance
+        .as_ref()
+        .map(SharedPerformanceTracker::metrics))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +300,55 @@ mod tests {
         assert_eq!(metrics.failed_api_calls, 1);
     }
 
+    #[test]
+    fn test_token_speed() {
+        let shared = SharedPerformanceTracker::default();
+        let start = Instant::now();
+        thread::sleep(Duration::from_millis(20));
+        shared.record_api_call(start, Some(&TokenUsage::request(1000, 40)));
+        shared.record_api_call(start, Some(&TokenUsage::request(500, 10)));
+        shared.record_api_call(Instant::now(), None);
+
+        let metrics = shared.metrics();
+        assert_eq!(metrics.api_calls, 3);
+        assert_eq!(metrics.failed_api_calls, 1);
+        assert_eq!((metrics.prompt_tokens, metrics.completion_tokens), (1500, 50));
+        // 50 tokens in at least 40ms of calls
+        assert!(metrics.tokens_per_second > 0.0);
+        assert!(metrics.tokens_per_second <= 50.0 / 0.04);
+    }
+
+    #[test]
+    fn test_restart_forgets_earlier_chunks() {
+        let shared = SharedPerformanceTracker::default();
+        shared.record_chunk(Instant::now(), 400);
+        shared.record_api_call(Instant::now(), None);
+
+        shared.restart();
+        let duration = shared.record_chunk(Instant::now(), 100);
+
+        assert_eq!(shared.chunk_times(), [duration]);
+        let metrics = shared.metrics();
+        assert_eq!(metrics.total_characters_processed, 100);
+        assert_eq!(metrics.api_calls, 0);
+    }
+
+    #[test]
+    fn test_metrics_store_durations_as_seconds() {
+        let mut tracker = PerformanceTracker::new();
+        let start = tracker.start_chunk();
+        tracker.end_chunk(start, 10);
+        let mut metrics = tracker.get_metrics();
+        metrics.total_duration = Duration::from_millis(1500);
+
+        let json = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(json["total_duration"], 1.5);
+        assert_eq!(
+            serde_json::from_value::<PerformanceMetrics>(json).unwrap(),
+            metrics
+        );
+    }
+
     #[test]
     fn test_custom_metrics() {
         let mut tracker = PerformanceTracker::new();
@@ -1,8 +1,195 @@
-use std::time::Instant;
-use tauri::{AppHandle, Emitter};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::llm::performance::PerformanceMetrics;
+
+/// An estimate never claims more than this before the step really completes
+const MAX_ESTIMATED_PERCENT: f64 = 95.0;
+/// Share of the bar covered linearly while the step takes as long as expected
+const ON_TIME_PERCENT: f64 = 90.0;
+const ESTIMATE_INTERVAL: Duration = Duration::from_millis(500);
+/// Chunk times averaged for the next estimate
+const ROLLING_WINDOW: usize = 3;
+/// Attempts for the events that end a job, the frontend must not miss them
+const TERMINAL_EMIT_ATTEMPTS: u32 = 3;
+const TERMINAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// At most one `llm-token` event in this time, a fast model would flood the window
+const TOKEN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Where progress events go, the app's windows outside of tests
+pub trait ProgressSink: Send + Sync {
+    fn send(&self, event: &str, payload: Value) -> Result<(), String>;
+}
+
+impl<R: Runtime> ProgressSink for AppHandle<R> {
+    fn send(&self, event: &str, payload: Value) -> Result<(), String> {
+        self.emit(event, payload).map_err(|e| e.to_string())
+    }
+}
+
+/// Progress events are best effort: a window closed mid-run must not abort a
+/// long job. Failures are logged and counted, never returned.
+#[derive(Clone)]
+pub struct ProgressEvents {
+    sink: Arc<dyn ProgressSink>,
+    failures: Arc<AtomicUsize>,
+}
+
+impl ProgressEvents {
+    pub fn new(sink: impl ProgressSink + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            failures: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn try_send(&self, event: &str, payload: &impl Serialize) -> Result<(), String> {
+        let payload = serde_json::to_value(payload).map_err(|e| e.to_string())?;
+        self.sink.send(event, payload)
+    }
+
+    fn record_failure(&self, event: &str, error: &str) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        println!("⚠️  Failed to emit {}: {}", event, error);
+    }
+
+    /// Emit `event`, returns whether it was delivered
+    pub fn emit(&self, event: &str, payload: impl Serialize) -> bool {
+        match self.try_send(event, &payload) {
+            Ok(()) => true,
+            Err(e) => {
+                self.record_failure(event, &e);
+                false
+            }
+        }
+    }
+
+    /// Emit an event that ends a job (finished, failed), retried a few times
+    /// before it counts as a failure
+    pub async fn emit_terminal(&self, event: &str, payload: impl Serialize) -> bool {
+        let mut attempt = 1;
+        loop {
+            match self.try_send(event, &payload) {
+                Ok(()) => return true,
+                Err(e) if attempt >= TERMINAL_EMIT_ATTEMPTS => {
+                    self.record_failure(event, &e);
+                    return false;
+                }
+                Err(e) => {
+                    println!("🔁 Retrying {} after: {}", event, e);
+                    tokio::time::sleep(TERMINAL_RETRY_DELAY * attempt).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Events that couldn't be delivered so far
+    pub fn failures(&self) -> usize {
+        self.failures.load(Ordering::Relaxed)
+    }
+}
+
+/// Synthetic progress within one LLM call, which reports nothing until it returns
+#[derive(Debug, Clone, Copy)]
+pub struct EstimatedPhase {
+    started: Instant,
+    expected: Duration,
+}
+
+impl EstimatedPhase {
+    pub fn new(started: Instant, expected: Duration) -> Self {
+        Self { started, expected }
+    }
+
+    /// Linear up to `ON_TIME_PERCENT` at the expected duration, then slowly
+    /// approaching `MAX_ESTIMATED_PERCENT` for calls that take longer
+    pub fn percent_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        let expected = self.expected.as_secs_f64();
+        if expected <= 0.0 {
+            return MAX_ESTIMATED_PERCENT;
+        }
+
+        let ratio = elapsed / expected;
+        let percent = if ratio <= 1.0 {
+            ON_TIME_PERCENT * ratio
+        } else {
+            let overtime = ratio - 1.0;
+            ON_TIME_PERCENT + (MAX_ESTIMATED_PERCENT - ON_TIME_PERCENT) * overtime / (overtime + 1.0)
+        };
+        percent.min(MAX_ESTIMATED_PERCENT)
+    }
+}
+
+/// Expected duration of the next chunk, from the last few chunk times
+pub fn expected_chunk_duration(chunk_times: &[Duration]) -> Option<Duration> {
+    let recent = &chunk_times[chunk_times.len().saturating_sub(ROLLING_WINDOW)..];
+    if recent.is_empty() {
+        return None;
+    }
+    Some(recent.iter().sum::<Duration>() / recent.len() as u32)
+}
+
+#[derive(Clone, Serialize)]
+struct StepEstimate {
+    step: usize,
+    percent: f64,
+}
+
+/// Stops emitting estimates when dropped, so an error in the step cancels it
+pub struct EstimateHandle {
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl Drop for EstimateHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Serialize)]
+struct TokenProgress<'a> {
+    /// The step, the number of chunks for the final summary
+    chunk_index: usize,
+    /// Everything the model generated so far
+    text: &'a str,
+}
+
+/// Emits `llm-token` with the text a streaming model generated so far
+pub struct TokenStream {
+    events: ProgressEvents,
+    chunk_index: usize,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+impl TokenStream {
+    pub fn push(&self, text: &str) {
+        self.push_at(text, Instant::now());
+    }
+
+    fn push_at(&self, text: &str, now: Instant) {
+        let mut last_emit = self.last_emit.lock().unwrap();
+        if last_emit.is_some_and(|last| now.saturating_duration_since(last) < TOKEN_INTERVAL) {
+            return;
+        }
+        *last_emit = Some(now);
+        self.events.emit(
+            "llm-token",
+            TokenProgress {
+                chunk_index: self.chunk_index,
+                text,
+            },
+        );
+    }
+}
 
 pub struct ProgressTracker {
-    app_handle: AppHandle,
+    events: ProgressEvents,
     #[allow(dead_code)]
     start_time: Instant,
     total_steps: usize,
@@ -10,44 +197,29 @@ pub struct ProgressTracker {
 }
 
 impl ProgressTracker {
-    pub fn new(app_handle: AppHandle, total_steps: usize) -> Self {
+    pub fn new(events: ProgressEvents, total_steps: usize) -> Self {
         Self {
-            app_handle,
+            events,
             start_time: Instant::now(),
             total_steps,
             current_step: 0,
         }
     }
 
-    pub fn start_summarization(&self, meeting_id: &str) -> Result<(), String> {
-        self.app_handle
-            .emit("summarization-started", meeting_id)
-            .map_err(|e| format!("Failed to emit summarization-started: {}", e))?;
-        
-        self.app_handle
-            .emit("summarization-chunk-start", self.total_steps)
-            .map_err(|e| format!("Failed to emit summarization-chunk-start: {}", e))?;
-
-        Ok(())
+    pub fn start_summarization(&self, meeting_id: &str) {
+        self.events.emit("summarization-started", meeting_id);
+        self.events.emit("summarization-chunk-start", self.total_steps);
     }
 
-    pub fn update_progress(&mut self, message: &str) -> Result<(), String> {
+    pub fn update_progress(&mut self, message: &str) {
         self.current_step += 1;
-        
-        self.app_handle
-            .emit("summarization-chunk-progress", self.current_step - 1)
-            .map_err(|e| format!("Failed to emit chunk progress: {}", e))?;
+        self.events.emit("summarization-chunk-progress", self.current_step - 1);
 
         let progress_message = format!(
             "Step {}/{}: {}",
             self.current_step, self.total_steps, message
         );
-        
-        self.app_handle
-            .emit("llm-progress", &progress_message)
-            .map_err(|e| format!("Failed to emit progress: {}", e))?;
-
-        Ok(())
+        self.events.emit("llm-progress", &progress_message);
     }
 
     pub fn log_chunk_completed(&self, chunk_index: usize, duration: std::time::Duration) {
@@ -58,44 +230,24 @@ impl ProgressTracker {
         );
     }
 
-    pub fn log_timing_stats(&self, chunk_times: &[std::time::Duration]) -> Result<(), String> {
-        if chunk_times.is_empty() {
-            return Ok(());
+    pub fn log_timing_stats(&self, metrics: &PerformanceMetrics) {
+        if metrics.chunk_count == 0 {
+            return;
         }
 
-        let total_chunk_time: std::time::Duration = chunk_times.iter().sum();
-        let average_chunk_time = total_chunk_time / chunk_times.len() as u32;
-        let min_chunk_time = chunk_times.iter().min().unwrap();
-        let max_chunk_time = chunk_times.iter().max().unwrap();
-
-        println!("📊 Chunk timing statistics:");
-        println!(
-            "   Total chunk processing time: {:.2}s",
-            total_chunk_time.as_secs_f64()
-        );
-        println!(
-            "   Average chunk time: {:.2}s",
-            average_chunk_time.as_secs_f64()
-        );
-        println!("   Fastest chunk: {:.2}s", min_chunk_time.as_secs_f64());
-        println!("   Slowest chunk: {:.2}s", max_chunk_time.as_secs_f64());
-
         let stats_message = format!(
             "📊 Chunk stats: Avg {:.1}s/chunk, Total {:.1}s for {} chunks",
-            average_chunk_time.as_secs_f64(),
-            total_chunk_time.as_secs_f64(),
-            chunk_times.len()
+            metrics.average_chunk_time.as_secs_f64(),
+            (metrics.average_chunk_time * metrics.chunk_count as u32).as_secs_f64(),
+            metrics.chunk_count
         );
+        println!("{}", stats_message);
 
-        self.app_handle
-            .emit("llm-progress", &stats_message)
-            .map_err(|e| format!("Failed to emit timing stats: {}", e))?;
-
-        Ok(())
+        self.events.emit("llm-progress", &stats_message);
     }
 
     #[allow(dead_code)]
-    pub fn complete(&self, message: &str) -> Result<(), String> {
+    pub fn complete(&self, message: &str) {
         let total_duration = self.start_time.elapsed();
         
         println!("🎉 {}", message);
@@ -110,16 +262,183 @@ impl ProgressTracker {
             total_duration.as_secs_f64()
         );
 
-        self.app_handle
-            .emit("llm-progress", &completion_message)
-            .map_err(|e| format!("Failed to emit completion: {}", e))?;
+        self.events.emit("llm-progress", &completion_message);
+    }
 
-        Ok(())
+    /// Emit `summarization-chunk-estimate` updates for the current step until
+    /// the returned handle is dropped
+    pub fn begin_estimated_phase(&self, expected_duration: Duration) -> EstimateHandle {
+        let events = self.events.clone();
+        let step = self.current_step.saturating_sub(1);
+        let phase = EstimatedPhase::new(Instant::now(), expected_duration);
+
+        let task = tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(ESTIMATE_INTERVAL).await;
+                let estimate = StepEstimate {
+                    step,
+                    percent: phase.percent_at(Instant::now()),
+                };
+                if !events.emit("summarization-chunk-estimate", estimate) {
+                    break;
+                }
+            }
+        });
+
+        EstimateHandle { task }
     }
 
-    pub fn emit_api_status(&self, message: &str) -> Result<(), String> {
-        self.app_handle
-            .emit("llm-progress", message)
-            .map_err(|e| format!("Failed to emit API status: {}", e))
+    pub fn emit_api_status(&self, message: &str) {
+        self.events.emit("llm-progress", message);
+    }
+
+    /// Token events for the LLM call of the current step
+    pub fn token_stream(&self) -> TokenStream {
+        TokenStream {
+            events: self.events.clone(),
+            chunk_index: self.current_step.saturating_sub(1),
+            last_emit: Mutex::new(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails the first `failing` events, then delivers them
+    struct FlakySink {
+        failing: AtomicUsize,
+        delivered: Arc<Mutex<Vec<String>>>,
+    }
+
+    fn flaky_events(failing: usize) -> (ProgressEvents, Arc<Mutex<Vec<String>>>) {
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let sink = FlakySink {
+            failing: AtomicUsize::new(failing),
+            delivered: delivered.clone(),
+        };
+        (ProgressEvents::new(sink), delivered)
+    }
+
+    impl ProgressSink for FlakySink {
+        fn send(&self, event: &str, payload: Value) -> Result<(), String> {
+            if self.failing.load(Ordering::Relaxed) > 0 {
+                self.failing.fetch_sub(1, Ordering::Relaxed);
+                return Err("window closed".to_string());
+            }
+            self.delivered.lock().unwrap().push(format!("{}: {}", event, payload));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_failed_events_are_counted_not_returned() {
+        let (events, delivered) = flaky_events(1);
+        let mut tracker = ProgressTracker::new(events.clone(), 2);
+
+        tracker.update_progress("Summarizing chunk 1 of 1");
+        tracker.emit_api_status("✅ External API successful");
+
+        assert_eq!(events.failures(), 1);
+        assert_eq!(
+            *delivered.lock().unwrap(),
+            vec![
+                "llm-progress: \"Step 1/2: Summarizing chunk 1 of 1\"",
+                "llm-progress: \"✅ External API successful\"",
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_terminal_events_are_retried() {
+        let (events, delivered) = flaky_events(TERMINAL_EMIT_ATTEMPTS as usize - 1);
+        assert!(events.emit_terminal("summarization-finished", 1).await);
+        assert_eq!(events.failures(), 0);
+        assert_eq!(*delivered.lock().unwrap(), vec!["summarization-finished: 1"]);
+
+        let (events, delivered) = flaky_events(TERMINAL_EMIT_ATTEMPTS as usize);
+        assert!(!events.emit_terminal("summarization-finished", 1).await);
+        assert_eq!(events.failures(), 1);
+        assert!(delivered.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_token_events_are_throttled() {
+        let (events, delivered) = flaky_events(0);
+        let mut tracker = ProgressTracker::new(events, 3);
+        tracker.update_progress("Summarizing chunk 1 of 2");
+        tracker.update_progress("Summarizing chunk 2 of 2");
+        delivered.lock().unwrap().clear();
+
+        let tokens = tracker.token_stream();
+        let start = Instant::now();
+        tokens.push_at("{\"ti", start);
+        tokens.push_at("{\"title", start + Duration::from_millis(100));
+        tokens.push_at("{\"title\": ", start + TOKEN_INTERVAL);
+
+        assert_eq!(
+            *delivered.lock().unwrap(),
+            vec![
+                r#"llm-token: {"chunk_index":1,"text":"{\"ti"}"#,
+                r#"llm-token: {"chunk_index":1,"text":"{\"title\": "}"#,
+            ]
+        );
+    }
+
+    fn percent_after(phase: &EstimatedPhase, start: Instant, millis: u64) -> f64 {
+        phase.percent_at(start + Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn test_estimate_is_linear_until_expected() {
+        let start = Instant::now();
+        let phase = EstimatedPhase::new(start, Duration::from_secs(10));
+
+        assert_eq!(percent_after(&phase, start, 0), 0.0);
+        assert!((percent_after(&phase, start, 5_000) - 45.0).abs() < 1e-9);
+        assert!((percent_after(&phase, start, 10_000) - ON_TIME_PERCENT).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_never_exceeds_cap() {
+        let start = Instant::now();
+        let phase = EstimatedPhase::new(start, Duration::from_secs(10));
+
+        let mut previous = 0.0;
+        for seconds in [11, 20, 60, 600, 36_000] {
+            let percent = percent_after(&phase, start, seconds * 1000);
+            assert!(percent > previous);
+            assert!(percent < MAX_ESTIMATED_PERCENT);
+            previous = percent;
+        }
+
+        let instant = EstimatedPhase::new(start, Duration::ZERO);
+        assert_eq!(percent_after(&instant, start, 1), MAX_ESTIMATED_PERCENT);
+    }
+
+    #[test]
+    fn test_clock_before_start_is_zero() {
+        let start = Instant::now() + Duration::from_secs(1);
+        let phase = EstimatedPhase::new(start, Duration::from_secs(10));
+
+        assert_eq!(phase.percent_at(Instant::now()), 0.0);
+    }
+
+    #[test]
+    fn test_expected_chunk_duration_uses_recent_chunks() {
+        let secs = |values: &[u64]| -> Vec<Duration> {
+            values.iter().map(|s| Duration::from_secs(*s)).collect()
+        };
+
+        assert_eq!(expected_chunk_duration(&[]), None);
+        assert_eq!(
+            expected_chunk_duration(&secs(&[10])),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            expected_chunk_duration(&secs(&[100, 10, 20, 30])),
+            Some(Duration::from_secs(20))
+        );
     }
 }
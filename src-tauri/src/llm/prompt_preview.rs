@@ -0,0 +1,237 @@
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::llm::{
+    error::{LlmError, LlmResult},
+    models::KeyFact,
+    prompts::{ChunkRequest, Language, SummaryStyle},
+    summary::{language_for, speaker_attribution_for, summary_style_for},
+    text_processing::{split_text_into_chunks, validate_transcript},
+};
+use crate::{get_meeting_transcript, AppState};
+
+pub const PROMPT_PREVIEW_DIR_NAME: &str = "prompt_preview";
+
+/// Rough token count, about four characters per token for German and English
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkPreview {
+    pub index: usize,
+    pub chars: usize,
+    pub estimated_tokens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptPreview {
+    pub chunk_count: usize,
+    pub first_request: ChunkRequest,
+    pub chunks: Vec<ChunkPreview>,
+    pub total_estimated_tokens: usize,
+}
+
+/// The chunk requests of a summarization run. Every chunk gets the empty key
+/// facts of the first chunk, the real run fills them from earlier answers.
+pub fn build_chunk_requests(
+    transcript: &str,
+    chunk_size: usize,
+    language: &Language,
+    style: &SummaryStyle,
+    attribute_speakers: bool,
+) -> Vec<ChunkRequest> {
+    let key_facts = KeyFact::default();
+    split_text_into_chunks(transcript, chunk_size)
+        .iter()
+        .map(|chunk| ChunkRequest::new(language, style, attribute_speakers, chunk, &key_facts))
+        .collect()
+}
+
+/// Write one JSON file per request to `prompt_preview/`, replacing an older preview
+pub async fn write_prompt_preview(
+    meeting_dir: &Path,
+    requests: &[ChunkRequest],
+) -> LlmResult<PromptPreview> {
+    let first_request = requests
+        .first()
+        .cloned()
+        .ok_or_else(|| LlmError::ConfigError("Transcript produced no chunks".to_string()))?;
+
+    let preview_dir = meeting_dir.join(PROMPT_PREVIEW_DIR_NAME);
+    if preview_dir.exists() {
+        fs::remove_dir_all(&preview_dir)
+            .await
+            .map_err(|e| LlmError::FileError(format!("Failed to clear prompt preview: {}", e)))?;
+    }
+    fs::create_dir_all(&preview_dir)
+        .await
+        .map_err(|e| LlmError::FileError(format!("Failed to create prompt preview: {}", e)))?;
+
+    let schema = serde_json::Value::from(ChunkRequest::schema());
+    let mut chunks = Vec::new();
+    for (index, request) in requests.iter().enumerate() {
+        let json = serde_json::to_string_pretty(&json!({
+            "system_prompt": request.system_prompt,
+            "user_prompt": request.user_prompt,
+            "schema": schema,
+        }))
+        .map_err(|e| LlmError::SerializationError(format!("Failed to serialize request: {}", e)))?;
+        fs::write(
+            preview_dir.join(format!("chunk_{:03}.json", index + 1)),
+            json,
+        )
+        .await
+        .map_err(|e| LlmError::FileError(format!("Failed to write prompt preview: {}", e)))?;
+
+        chunks.push(ChunkPreview {
+            index,
+            chars: request.user_prompt.chars().count(),
+            estimated_tokens: estimate_tokens(&request.system_prompt)
+                + estimate_tokens(&request.user_prompt),
+        });
+    }
+
+    Ok(PromptPreview {
+        chunk_count: chunks.len(),
+        first_request,
+        total_estimated_tokens: chunks.iter().map(|chunk| chunk.estimated_tokens).sum(),
+        chunks,
+    })
+}
+
+/// Run the preparation of a summary up to the first LLM call and write the
+/// chunk requests to disk. Does not claim the summarization slot.
+#[tauri::command]
+pub async fn preview_summary_prompts(
+    app: AppHandle,
+    meeting_id: &str,
+) -> Result<PromptPreview, String> {
+    let config = {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        state.llm_config.clone()
+    };
+
+    let transcript = get_meeting_transcript(app.clone(), meeting_id).await?;
+    let transcript = validate_transcript(&transcript, config.min_transcript_chars)?;
+
+    let meeting_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join("uploads")
+        .join(meeting_id);
+    let style = summary_style_for(&app, &meeting_dir).await;
+    let chunk_size = config
+        .chunk_plan(transcript)
+        .map_or(config.chunk_size, |plan| plan.chunk_size);
+    let attribution = speaker_attribution_for(&app, &meeting_dir).await;
+    let language = language_for(&meeting_dir, &config).await;
+    let requests = build_chunk_requests(
+        transcript,
+        chunk_size,
+        &language,
+        &style,
+        attribution.attribute_speakers,
+    );
+    Ok(write_prompt_preview(&meeting_dir, &requests).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::prompts::PromptManager;
+
+    fn transcript() -> String {
+        (0..40)
+            .map(|i| {
+                format!(
+                    "Punkt {}: Wir besprechen den Haushalt und die Raumplanung.",
+                    i
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_preview_matches_first_chunk_request() {
+        let transcript = transcript();
+        let requests = build_chunk_requests(
+            &transcript,
+            500,
+            &Language::German,
+            &SummaryStyle::Strict,
+            true,
+        );
+        let chunks = split_text_into_chunks(&transcript, 500);
+
+        assert_eq!(requests.len(), chunks.len());
+        // process_chunk starts with empty key facts and sends them along with the chunk
+        assert_eq!(
+            requests[0],
+            ChunkRequest::new(
+                &Language::German,
+                &SummaryStyle::Strict,
+                true,
+                &chunks[0],
+                &KeyFact::default()
+            )
+        );
+        assert_eq!(
+            requests[0].system_prompt,
+            PromptManager::chunk_summarization(&Language::German, &SummaryStyle::Strict, true)
+        );
+        assert!(requests[0].user_prompt.ends_with(&chunks[0]));
+    }
+
+    #[tokio::test]
+    async fn test_write_prompt_preview() {
+        let dir = tempfile::tempdir().unwrap();
+        let preview_dir = dir.path().join(PROMPT_PREVIEW_DIR_NAME);
+        std::fs::create_dir_all(&preview_dir).unwrap();
+        std::fs::write(preview_dir.join("chunk_009.json"), "{}").unwrap();
+
+        let requests = build_chunk_requests(
+            &transcript(),
+            500,
+            &Language::German,
+            &SummaryStyle::Complete,
+            true,
+        );
+        let preview = write_prompt_preview(dir.path(), &requests).await.unwrap();
+
+        assert_eq!(preview.chunk_count, requests.len());
+        assert_eq!(preview.first_request, requests[0]);
+        assert_eq!(
+            preview.total_estimated_tokens,
+            preview
+                .chunks
+                .iter()
+                .map(|c| c.estimated_tokens)
+                .sum::<usize>()
+        );
+        assert!(!preview_dir.join("chunk_009.json").exists());
+
+        let written: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(preview_dir.join("chunk_001.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written["user_prompt"], requests[0].user_prompt);
+        assert_eq!(written["system_prompt"], requests[0].system_prompt);
+        assert!(written["schema"]["properties"]["key_facts"].is_object());
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens("äöüß"), 1);
+    }
+}
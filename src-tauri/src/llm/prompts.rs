@@ -1,50 +1,381 @@
-use crate::llm::models::KeyFact;
-use serde_json::json;
+use crate::llm::models::{FirstSummaryFormat, KeyFact};
+use schemars::schema_for;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone)]
+/// Language of the prompts and the summary. Serialized as its ISO 639-1 code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
-#[derive(Default)]
 pub enum Language {
+    #[serde(rename = "en", alias = "english")]
     English,
     #[default]
+    #[serde(rename = "de", alias = "german")]
     German,
 }
 
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    /// Name in the language itself, for the settings dropdown
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "de" | "german" | "deutsch" => Ok(Language::German),
+            other => Err(format!("Unsupported language: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageOption {
+    pub code: Language,
+    pub name: &'static str,
+}
+
+/// Languages the prompts exist in, for the settings UI
+#[tauri::command]
+pub fn get_supported_languages() -> Vec<LanguageOption> {
+    Language::ALL
+        .iter()
+        .map(|language| LanguageOption {
+            code: *language,
+            name: language.display_name(),
+        })
+        .collect()
+}
+
+/// Meeting-internal content that the strict style leaves out of the summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatterSection {
+    /// Audio, video or connection problems during the meeting
+    TechnicalIssues,
+    PersonalAnecdotes,
+    /// How moderation, minutes and timekeeping were handed out
+    RoleAssignment,
+}
+
+impl ChatterSection {
+    fn name(&self, language: &Language) -> &'static str {
+        match (self, language) {
+            (ChatterSection::TechnicalIssues, Language::English) => "technical problems",
+            (ChatterSection::TechnicalIssues, Language::German) => "technische Probleme",
+            (ChatterSection::PersonalAnecdotes, Language::English) => "personal anecdotes",
+            (ChatterSection::PersonalAnecdotes, Language::German) => "persönliche Anekdoten",
+            (ChatterSection::RoleAssignment, Language::English) => "role assignments",
+            (ChatterSection::RoleAssignment, Language::German) => "Rollenvergaben",
+        }
+    }
+}
+
+/// Which meeting-internal chatter ends up in the summary
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SummaryStyle {
+    /// Only the content of the meeting
+    #[default]
+    Strict,
+    /// Everything, including technical problems and anecdotes
+    Complete,
+    Custom {
+        included_sections: Vec<ChatterSection>,
+    },
+}
+
+impl SummaryStyle {
+    pub fn includes(&self, section: ChatterSection) -> bool {
+        match self {
+            SummaryStyle::Strict => false,
+            SummaryStyle::Complete => true,
+            SummaryStyle::Custom { included_sections } => included_sections.contains(&section),
+        }
+    }
+}
+
 pub struct PromptManager;
 
+/// Attendees carried over into the next chunk's prompt. Beyond this the
+/// oldest are left out, so the key facts can't crowd out the transcript.
+pub const MAX_PROMPT_ATTENDEES: usize = 30;
+
+/// Exactly what is sent to the LLM to summarize one chunk
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkRequest {
+    pub system_prompt: String,
+    pub user_prompt: String,
+}
+
+impl ChunkRequest {
+    pub fn new(
+        language: &Language,
+        style: &SummaryStyle,
+        attribute_speakers: bool,
+        chunk: &str,
+        key_facts: &KeyFact,
+    ) -> Self {
+        Self {
+            system_prompt: PromptManager::chunk_summarization(language, style, attribute_speakers),
+            user_prompt: PromptManager::chunk_user_prompt(language, chunk, key_facts),
+        }
+    }
+
+    /// Add the key facts the user corrected, the same for every chunk
+    pub fn with_authoritative_key_facts(
+        mut self,
+        language: &Language,
+        key_facts: Option<&KeyFact>,
+    ) -> Self {
+        if let Some(key_facts) = key_facts {
+            self.system_prompt
+                .push_str(&PromptManager::authoritative_key_facts(language, key_facts));
+        }
+        self
+    }
+
+    pub fn schema() -> schemars::Schema {
+        schema_for!(FirstSummaryFormat)
+    }
+
+    /// The same request again, with the answer that wasn't valid JSON and
+    /// the parser's complaint about it
+    pub fn with_invalid_answer(&self, language: &Language, answer: &str, error: &str) -> Self {
+        Self {
+            system_prompt: self.system_prompt.clone(),
+            user_prompt: format!(
+                "{}{}",
+                self.user_prompt,
+                PromptManager::invalid_json(language, answer, error)
+            ),
+        }
+    }
+}
+
 impl PromptManager {
-    pub fn chunk_summarization(language: &Language, key_facts: Option<&KeyFact>) -> String {
-        match language {
-            Language::English => "
-You are a meeting summarization assistant. Summarize the provided meeting transcript chunk in a structured format:
+    /// Whether to leave out how the meeting roles were handed out
+    fn role_assignment_instruction(language: &Language, style: &SummaryStyle) -> &'static str {
+        match (language, style.includes(ChatterSection::RoleAssignment)) {
+            (Language::English, false) => "Do not describe how roles such as moderation or minute-taking were assigned.",
+            (Language::English, true) => "Briefly note how roles such as moderation or minute-taking were assigned.",
+            (Language::German, false) => "Wie der Entscheidungsprozess der Protokollführung ablief und welche Gründe es für diese Entscheidung gab müssen nicht Erwähnt werden.",
+            (Language::German, true) => "Wie die Rollen für Moderation, Protokoll und Zeitnahme vergeben wurden, soll kurz festgehalten werden.",
+        }
+    }
 
-- 📌 Introduction: Brief context about what was discussed
-- 📝 Key Points: Main topics and decisions (use bullet points)
-- ✅ Action Items: Tasks, assignments, or next steps mentioned (format: • [Person]: Task description)
+    /// How speakers are referred to in the bullets
+    fn speaker_instruction(language: &Language, attribute_speakers: bool) -> &'static str {
+        match (language, attribute_speakers) {
+            (Language::English, true) => "Maintain any speaker names or roles mentioned.",
+            (Language::English, false) => "Do not attribute statements or tasks to individuals: write the bullets without names or attendee ids.",
+            (Language::German, true) => "Statt Namen zu erwähnen, nutze die ID der Attendees aus den Key Facts (z. B. `[1] fragt …`).",
+            (Language::German, false) => "Ordnen Sie Aussagen und Aufgaben keinen einzelnen Personen zu: Die Stichpunkte enthalten weder Namen noch IDs der Attendees.",
+        }
+    }
 
-Keep the summary concise but comprehensive. Maintain any speaker names or roles mentioned. if abbreviations are used, do not explain them.".to_string(),
+    /// Technical problems and anecdotes: the excluded ones are to be ignored,
+    /// the included ones captured. `lead` names the excluded content.
+    fn chatter_instructions(language: &Language, style: &SummaryStyle, lead: &str) -> String {
+        let (included, excluded): (Vec<_>, Vec<_>) = [
+            ChatterSection::TechnicalIssues,
+            ChatterSection::PersonalAnecdotes,
+        ]
+        .into_iter()
+        .partition(|section| style.includes(*section));
+        let names = |sections: &[ChatterSection], conjunction: &str| {
+            sections
+                .iter()
+                .map(|section| section.name(language))
+                .collect::<Vec<_>>()
+                .join(conjunction)
+        };
 
+        let mut sentences = Vec::new();
+        if !excluded.is_empty() {
+            sentences.push(match language {
+                Language::English => format!(
+                    "{} such as {} can be ignored.",
+                    lead,
+                    names(&excluded, " or ")
+                ),
+                Language::German => format!(
+                    "{} wie {} müssen nicht beachtet werden.",
+                    lead,
+                    names(&excluded, " oder ")
+                ),
+            });
+        }
+        if !included.is_empty() {
+            sentences.push(match language {
+                Language::English => format!("Also capture {}.", names(&included, " and ")),
+                Language::German => format!(
+                    "Auch {} sollen festgehalten werden.",
+                    names(&included, " und ")
+                ),
+            });
+        }
+        sentences.join(" ")
+    }
+
+    /// Key facts with at most `max_attendees` attendees, keeping the most
+    /// recently added ones. Returns how many were left out.
+    pub fn capped_key_facts(key_facts: &KeyFact, max_attendees: usize) -> (KeyFact, usize) {
+        let mut capped = key_facts.clone();
+        let mut dropped = 0;
+        if let Some(attendees) = capped.attendees.as_mut() {
+            dropped = attendees.len().saturating_sub(max_attendees);
+            attendees.drain(..dropped);
+        }
+        (capped, dropped)
+    }
+
+    /// The key facts so far as a labeled section, followed by the transcript chunk
+    pub fn chunk_user_prompt(language: &Language, chunk: &str, key_facts: &KeyFact) -> String {
+        let (key_facts, dropped) = Self::capped_key_facts(key_facts, MAX_PROMPT_ATTENDEES);
+        let key_facts_json = serde_json::to_string(&key_facts).unwrap_or_default();
+
+        match language {
+            Language::English => {
+                let note = if dropped > 0 {
+                    format!("\n({} earlier attendees are not listed.)", dropped)
+                } else {
+                    String::new()
+                };
+                format!(
+                    "Key facts so far:\n{}{}\n\nTranscript chunk:\n{}",
+                    key_facts_json, note, chunk
+                )
+            }
             Language::German => {
-                let key_facts_str: String = if let Some(key_facts) = key_facts {
-                    json!(key_facts).to_string()
+                let note = if dropped > 0 {
+                    format!("\n({} frühere Attendees sind nicht aufgeführt.)", dropped)
                 } else {
-                    "Noch keine vorhandenen Key Facts.".into()
+                    String::new()
                 };
+                format!(
+                    "Bisherige Key Facts:\n{}{}\n\nTranskript-Abschnitt:\n{}",
+                    key_facts_json, note, chunk
+                )
+            }
+        }
+    }
+
+    /// Key facts corrected by the user, appended to a system prompt. They
+    /// are never capped, the model must not contradict any of them.
+    pub fn authoritative_key_facts(language: &Language, key_facts: &KeyFact) -> String {
+        let key_facts_json = serde_json::to_string(key_facts).unwrap_or_default();
+        match language {
+            Language::English => format!(
+                "\n\nThe following key facts were checked and corrected by the user. Use the attendees and roles exactly as given and do not contradict them:\n{}",
+                key_facts_json
+            ),
+            Language::German => format!(
+                "\n\nDie folgenden Key Facts wurden vom Nutzer geprüft und korrigiert. Übernehmen Sie Teilnehmende und Rollen genau so und widersprechen Sie ihnen nicht:\n{}",
+                key_facts_json
+            ),
+        }
+    }
+
+    fn invalid_json(language: &Language, answer: &str, error: &str) -> String {
+        match language {
+            Language::English => format!(
+                "\n\nYour previous answer was not valid JSON ({}):\n{}\n\nAnswer again with the complete summary as valid JSON in the required format.",
+                error, answer
+            ),
+            Language::German => format!(
+                "\n\nIhre vorherige Antwort war kein gültiges JSON ({}):\n{}\n\nAntworten Sie erneut mit der vollständigen Zusammenfassung als gültiges JSON im geforderten Format.",
+                error, answer
+            ),
+        }
+    }
+
+    /// Appended to the final summary prompt when chunks were skipped, so the
+    /// summary says what is missing instead of papering over it
+    pub fn missing_chunks(language: &Language, missing: &[usize], chunk_count: usize) -> String {
+        let parts = missing
+            .iter()
+            .map(|index| (index + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match language {
+            Language::English => format!(
+                "\n\nParts {} of {} of the transcript could not be summarized and are missing. Do not invent content for them and mention in the summary that these sections are missing.",
+                parts, chunk_count
+            ),
+            Language::German => format!(
+                "\n\nDie Abschnitte {} von {} des Transkripts konnten nicht zusammengefasst werden und fehlen. Erfinden Sie keine Inhalte dafür und weisen Sie in der Zusammenfassung darauf hin, dass diese Abschnitte fehlen.",
+                parts, chunk_count
+            ),
+        }
+    }
 
-                format!("
+    /// Instructions only, the same for every chunk. Key facts and the
+    /// transcript go into the user prompt.
+    pub fn chunk_summarization(
+        language: &Language,
+        style: &SummaryStyle,
+        attribute_speakers: bool,
+    ) -> String {
+        let action_item_format = if attribute_speakers {
+            "• [Person]: Task description"
+        } else {
+            "• Task description"
+        };
+        match language {
+            Language::English => format!("
+You are a meeting summarization assistant. Summarize the provided meeting transcript chunk in a structured format:
+
+- 📌 Introduction: Brief context about what was discussed
+- 📝 Key Points: Main topics and decisions (use bullet points)
+- ✅ Action Items: Tasks, assignments, or next steps mentioned (format: {})
+
+Keep the summary concise but comprehensive. {} if abbreviations are used, do not explain them.
+The key facts collected from earlier chunks are given before the transcript chunk.
+{}
+{}",
+                action_item_format,
+                Self::speaker_instruction(language, attribute_speakers),
+                Self::role_assignment_instruction(language, style),
+                Self::chatter_instructions(language, style, "Side matters"),
+            ),
+
+            Language::German => format!("
 Sie sind ein Assistent für Meeting-Zusammenfassungen. 
 Fassen Sie den bereitgestellten Abschnitt eines Meeting-Transkripts möglichst vollständig zusammen:
 
+Die bisherigen Key Facts stehen in der Nachricht vor dem Transkript-Abschnitt.
 Falls eine Person noch nicht in den vorherigen Key Facts erwähnt wurde, erwähnen Sie sie im Abschnitt Key Facts.
 
 {}
-
-Statt Namen zu erwähnen, nutze die ID der Attendees aus den Key Facts (z. B. `[1] fragt …`).
 Bei den Keyfacts sollen folgende Punkte beachtet werden:
 ´attendees´ enthält eine Liste von Personen, die am Meeting teilgenommen haben.
 ´responisible_for_moderation´ enthält den Namen einer oder meherer Personen, die das Meeting moderiert hat.
 ´responisible_for_protocol´ enthält den Namen einer oder meherer Personen, die für das Protokoll zuständig sind.
-Wie der Entscheidungsprozess der Protokollführung ablief und welche Gründe es für diese Entscheidung gab müssen nicht Erwähnt werden.
+{}
 ´responisible_for_timekeeping´ enthält den Namen einer oder meherer Personen, die für die Zeitmessung verantwortig sind.
 
 Verkürzen Sie nichts zu stark. 
@@ -55,33 +386,52 @@ Verben und unnötige Füllwörter sollen vermieden werden.
 Halten Sie Redebeiträge einzelner Personen getrennt, wenn möglich. 
 Wenn abkürzungen genannt werden, erklären Sie diese nicht. 
 Inhaltliche Wiederholungen können zusammengefasst werden. 
-Nebensächlichkeiten wie technische Probleme oder persönliche Anekdoten müssen nicht beachtet werden.
+{}
 Unter ´ToDo´ sollen die wichtigsten Aufgaben (´tasks´), die im Meeting besprochen wurden, mit Bezug auf die jeweilige Person(´ateendee´), in das Feld ´asignee´ aufgelistet werden.
-Ergänze keine Kommentare oder Erklärungen, sondern gebe nur den finalen Output ohne Kommentare an.", key_facts_str)
-            },
+Ergänze keine Kommentare oder Erklärungen, sondern gebe nur den finalen Output ohne Kommentare an.",
+                Self::speaker_instruction(language, attribute_speakers),
+                Self::role_assignment_instruction(language, style),
+                Self::chatter_instructions(language, style, "Nebensächlichkeiten"),
+            ),
         }
     }
 
-    pub fn final_summary(language: &Language) -> &'static str {
+    pub fn final_summary(
+        language: &Language,
+        style: &SummaryStyle,
+        attribute_speakers: bool,
+    ) -> String {
         match language {
-            Language::English => "
+            Language::English => format!("
 Summarize the following transcript chunk. Focus on:
 
 1. What was discussed?
 2. What was decided?
 3. What needs to happen next?
 
-Preserve speaker names. Use bullet points. Do not use \"Introduction\"/\"Key Points\"/\"Action Items\" as section headers.",
+{} Use bullet points. Do not use \"Introduction\"/\"Key Points\"/\"Action Items\" as section headers. {}",
+                match attribute_speakers {
+                    true => "Preserve speaker names.",
+                    false => "Do not name speakers or attendee ids, statements are not attributed to individuals.",
+                },
+                Self::chatter_instructions(language, style, "Meeting-internal matters"),
+            ),
 
-            Language::German => "
+            Language::German => format!("
 Fassen Sie die folgenden Abschnittszusammenfassungen zu einer vollständigen und detaillierten Meeting-Zusammenfassung zusammen. Aufgaben wie Moderation, Protokollführung oder Zeiterfassung sollen zu Beginn des Protokolls stichpunktartig aufgeführt werden. Sie sind keine weiterführenden Aktionspunkte und dürfen daher nicht im Abschnitt zu den To-Dos oder nächsten Schritten erscheinen. 
 
 Als `summary` geben Sie eine kurze Zusammenfassung des Meetings an, die den Zweck des Meetings und die wichtigsten Ergebnisse zusammenfasst.
 Es soll möglichst der gesamte Inhalt des Meetings zusammengefasst werden, ohne dass wichtige Details verloren gehen. 
 In erster Linie sollst du die Stichpunkte gruppieren, ohne sie zu verändern oder zu kürzen.
 
-Die `topics` enthalten die wichtigsten Themen des Meetings, die in den einzelnen Abschnitten behandelt wurden. Diese sollten in einer strukturierten Form mit Stichpunkten und gegebenenfalls Unterpunkten dargestellt werden. Kombinieren Sie überlappende Themen und bewahren Sie Details. Vermeiden Sie Wiederholungen und konzentrieren Sie sich auf relevante Punkte. Meetinginterne Inhalte wie technische Probleme oder persönliche Anekdoten müssen nicht beachtet werden.
-Die `todos` enthalten die wichtigsten Aufgaben, die im Meeting besprochen wurden. Falls eine oder mehrere Personen für eine Aufgabe verantwortlich sind, listen Sie diese in der `assignees`-Liste auf. Die Aufgaben sollten klar und präzise formuliert sein. Aufgaben, die sich nur auf das Meetings beziehen, sollten nicht in den To-Dos auftauchen, sondern nur die Aufgaben, die für die Zukunft relevant sind. Bei unklarer Verantwortlichkeit oder fehlender Zuweisung, `assignees` schreibe sie mehrer Namen hin oder lassen Sie das Feld.",
+Die `topics` enthalten die wichtigsten Themen des Meetings, die in den einzelnen Abschnitten behandelt wurden. Diese sollten in einer strukturierten Form mit Stichpunkten und gegebenenfalls Unterpunkten dargestellt werden. Kombinieren Sie überlappende Themen und bewahren Sie Details. Vermeiden Sie Wiederholungen und konzentrieren Sie sich auf relevante Punkte. {}
+Die `todos` enthalten die wichtigsten Aufgaben, die im Meeting besprochen wurden. Falls eine oder mehrere Personen für eine Aufgabe verantwortlich sind, listen Sie diese in der `assignees`-Liste auf. Die Aufgaben sollten klar und präzise formuliert sein. Aufgaben, die sich nur auf das Meetings beziehen, sollten nicht in den To-Dos auftauchen, sondern nur die Aufgaben, die für die Zukunft relevant sind. Bei unklarer Verantwortlichkeit oder fehlender Zuweisung, `assignees` schreibe sie mehrer Namen hin oder lassen Sie das Feld.{}",
+                Self::chatter_instructions(language, style, "Meetinginterne Inhalte"),
+                match attribute_speakers {
+                    true => "",
+                    false => " Die `summary` und die Stichpunkte nennen keine Namen oder IDs von Personen, Aussagen werden keinen einzelnen Personen zugeordnet.",
+                },
+            ),
         }
     }
 
@@ -122,6 +472,38 @@ Wiederholen Sie NICHT die Überschriften aus den Eingabe-Abschnitten.",
         }
     }
 
+    pub fn extract_keywords(language: &Language) -> &'static str {
+        match language {
+            Language::English => "
+You receive the summary of a meeting. Return 3 to 5 short keywords (one or two words each) describing the topics of the meeting, suitable for filtering a list of meetings. Use nouns, no names of people, no emoji. Return only a JSON array of strings.",
+
+            Language::German => "
+Sie erhalten die Zusammenfassung eines Meetings. Geben Sie 3 bis 5 kurze Schlagwörter (jeweils ein oder zwei Wörter) zurück, die die Themen des Meetings beschreiben und sich zum Filtern einer Meeting-Liste eignen. Verwenden Sie Substantive, keine Personennamen und keine Emojis. Geben Sie nur ein JSON-Array aus Strings zurück.",
+        }
+    }
+
+    pub fn digest(language: &Language) -> &'static str {
+        match language {
+            Language::English => "
+You receive the summaries of several meetings from the same period. Write a digest for people who did not attend:
+
+The `period_overview` briefly describes what happened across all meetings.
+The `themes` are topics that came up in more than one meeting or connect the meetings. List the titles of the meetings each theme appeared in under `meetings`.
+The `open_todos` consolidate the to-dos of all meetings, grouped by assignee. Merge duplicate tasks. Put tasks without an assignee under \"Unassigned\".
+
+Do not invent content that is not in the summaries.",
+
+            Language::German => "
+Sie erhalten die Zusammenfassungen mehrerer Meetings aus demselben Zeitraum. Schreiben Sie einen Wochenrückblick für Personen, die nicht teilgenommen haben:
+
+Der `period_overview` beschreibt kurz, was in allen Meetings zusammen passiert ist.
+Die `themes` sind Themen, die in mehreren Meetings vorkamen oder die Meetings verbinden. Listen Sie unter `meetings` die Titel der Meetings auf, in denen das Thema vorkam.
+Die `open_todos` fassen die To-Dos aller Meetings nach verantwortlicher Person gruppiert zusammen. Doppelte Aufgaben werden zusammengeführt. Aufgaben ohne verantwortliche Person stehen unter \"Nicht zugewiesen\".
+
+Ergänzen Sie keine Inhalte, die nicht in den Zusammenfassungen stehen.",
+        }
+    }
+
     pub fn test_connection(language: &Language) -> &'static str {
         match language {
             Language::English => "You are a helpful assistant. Respond concisely.",
@@ -136,3 +518,250 @@ Wiederholen Sie NICHT die Überschriften aus den Eingabe-Abschnitten.",
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::Attendee;
+
+    const GERMAN_CHATTER_EXCLUSION: &str =
+        "Nebensächlichkeiten wie technische Probleme oder persönliche Anekdoten müssen nicht beachtet werden.";
+    const GERMAN_FINAL_CHATTER_EXCLUSION: &str =
+        "Meetinginterne Inhalte wie technische Probleme oder persönliche Anekdoten müssen nicht beachtet werden.";
+    const GERMAN_ROLE_EXCLUSION: &str = "Wie der Entscheidungsprozess der Protokollführung ablief";
+
+    fn chunk_prompt(language: &Language, style: &SummaryStyle) -> String {
+        PromptManager::chunk_summarization(language, style, true)
+    }
+
+    #[test]
+    fn test_strict_excludes_chatter() {
+        let prompt = chunk_prompt(&Language::German, &SummaryStyle::Strict);
+        assert!(prompt.contains(GERMAN_CHATTER_EXCLUSION));
+        assert!(prompt.contains(GERMAN_ROLE_EXCLUSION));
+        assert!(!prompt.contains("festgehalten werden"));
+
+        let final_prompt =
+            PromptManager::final_summary(&Language::German, &SummaryStyle::Strict, true);
+        assert!(final_prompt.contains(GERMAN_FINAL_CHATTER_EXCLUSION));
+
+        let english = chunk_prompt(&Language::English, &SummaryStyle::Strict);
+        assert!(english.contains(
+            "Side matters such as technical problems or personal anecdotes can be ignored."
+        ));
+    }
+
+    #[test]
+    fn test_complete_captures_chatter() {
+        let prompt = chunk_prompt(&Language::German, &SummaryStyle::Complete);
+        assert!(!prompt.contains("müssen nicht beachtet werden"));
+        assert!(!prompt.contains(GERMAN_ROLE_EXCLUSION));
+        assert!(prompt.contains(
+            "Auch technische Probleme und persönliche Anekdoten sollen festgehalten werden."
+        ));
+
+        let final_prompt =
+            PromptManager::final_summary(&Language::German, &SummaryStyle::Complete, true);
+        assert!(!final_prompt.contains("Meetinginterne Inhalte wie"));
+
+        let english = chunk_prompt(&Language::English, &SummaryStyle::Complete);
+        assert!(!english.contains("can be ignored"));
+        assert!(english.contains("Also capture technical problems and personal anecdotes."));
+    }
+
+    #[test]
+    fn test_custom_includes_only_selected_sections() {
+        let style = SummaryStyle::Custom {
+            included_sections: vec![ChatterSection::TechnicalIssues],
+        };
+        let prompt = chunk_prompt(&Language::German, &style);
+
+        assert!(!prompt.contains(GERMAN_CHATTER_EXCLUSION));
+        assert!(prompt.contains(
+            "Nebensächlichkeiten wie persönliche Anekdoten müssen nicht beachtet werden."
+        ));
+        assert!(prompt.contains("Auch technische Probleme sollen festgehalten werden."));
+        assert!(prompt.contains(GERMAN_ROLE_EXCLUSION));
+    }
+
+    #[test]
+    fn test_speaker_attribution_instructions() {
+        let style = SummaryStyle::Strict;
+        let attributed = PromptManager::chunk_summarization(&Language::German, &style, true);
+        assert!(attributed.contains("nutze die ID der Attendees"));
+        assert!(!attributed.contains("keinen einzelnen Personen"));
+
+        let anonymous = PromptManager::chunk_summarization(&Language::German, &style, false);
+        assert!(!anonymous.contains("nutze die ID der Attendees"));
+        assert!(anonymous.contains("weder Namen noch IDs der Attendees"));
+        // The attendees are still collected
+        assert!(anonymous.contains("´attendees´ enthält eine Liste von Personen"));
+
+        let english = PromptManager::chunk_summarization(&Language::English, &style, false);
+        assert!(!english.contains("[Person]"));
+        assert!(!english.contains("Maintain any speaker names"));
+        assert!(english.contains("without names or attendee ids"));
+
+        let final_prompt = PromptManager::final_summary(&Language::German, &style, false);
+        assert!(final_prompt.contains("nennen keine Namen oder IDs von Personen"));
+        assert!(
+            !PromptManager::final_summary(&Language::German, &style, true)
+                .contains("nennen keine Namen")
+        );
+        assert!(
+            !PromptManager::final_summary(&Language::English, &style, false)
+                .contains("Preserve speaker names")
+        );
+
+        let request = ChunkRequest::new(&Language::German, &style, false, "Text", &key_facts(2));
+        assert_eq!(request.system_prompt, anonymous);
+        assert!(request.user_prompt.contains("\"Person 1\""));
+    }
+
+    #[test]
+    fn test_language_serde_round_trip() {
+        for language in Language::ALL {
+            let json = serde_json::to_string(&language).unwrap();
+            assert_eq!(json, format!("\"{}\"", language.code()));
+            assert_eq!(serde_json::from_str::<Language>(&json).unwrap(), language);
+            assert_eq!(language.to_string().parse::<Language>().unwrap(), language);
+        }
+
+        // Names from before the codes were introduced still load
+        assert_eq!(
+            serde_json::from_str::<Language>("\"german\"").unwrap(),
+            Language::German
+        );
+        assert!("fr".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_all_languages_listed() {
+        let variants = [Language::English, Language::German];
+        for language in &variants {
+            // No wildcard: a new variant does not compile until it is listed above
+            match language {
+                Language::English | Language::German => {}
+            }
+        }
+        assert_eq!(Language::ALL, variants);
+
+        let options = get_supported_languages();
+        assert_eq!(options.len(), Language::ALL.len());
+        assert_eq!(options[1].name, "Deutsch");
+    }
+
+    fn key_facts(attendees: usize) -> KeyFact {
+        KeyFact {
+            attendees: Some(
+                (0..attendees)
+                    .map(|id| Attendee {
+                        id,
+                        name: format!("Person {}", id),
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_system_prompt_is_constant_across_chunks() {
+        let style = SummaryStyle::Strict;
+        let first = ChunkRequest::new(
+            &Language::German,
+            &style,
+            true,
+            "Erster Abschnitt",
+            &key_facts(0),
+        );
+        let later = ChunkRequest::new(&Language::German, &style, true, "Später", &key_facts(20));
+
+        assert_eq!(first.system_prompt, later.system_prompt);
+        assert!(later.user_prompt.contains("\"Person 19\""));
+        assert!(later.user_prompt.ends_with("Transkript-Abschnitt:\nSpäter"));
+    }
+
+    #[test]
+    fn test_authoritative_key_facts_are_injected() {
+        let style = SummaryStyle::Strict;
+        let corrected = KeyFact {
+            responisible_for_protocol: Some("Anna Schmidt".to_string()),
+            ..key_facts(40)
+        };
+
+        let plain = ChunkRequest::new(&Language::English, &style, true, "Text", &key_facts(1));
+        let request = ChunkRequest::new(&Language::English, &style, true, "Text", &key_facts(1))
+            .with_authoritative_key_facts(&Language::English, Some(&corrected));
+        assert!(request.system_prompt.starts_with(&plain.system_prompt));
+        assert!(request.system_prompt.contains("do not contradict them"));
+        assert!(request
+            .system_prompt
+            .contains("\"responisible_for_protocol\":\"Anna Schmidt\""));
+        // All corrected attendees, the cap is only for collected key facts
+        assert!(request.system_prompt.contains("\"Person 0\""));
+        assert!(request.system_prompt.contains("\"Person 39\""));
+        assert_eq!(request.user_prompt, plain.user_prompt);
+
+        let unchanged = ChunkRequest::new(&Language::English, &style, true, "Text", &key_facts(1))
+            .with_authoritative_key_facts(&Language::English, None);
+        assert_eq!(unchanged, plain);
+
+        assert!(
+            PromptManager::authoritative_key_facts(&Language::German, &corrected)
+                .contains("widersprechen Sie ihnen nicht")
+        );
+    }
+
+    #[test]
+    fn test_missing_chunks_note_counts_from_one() {
+        let note = PromptManager::missing_chunks(&Language::English, &[1, 4], 12);
+        assert!(note.contains("Parts 2, 5 of 12"));
+        assert!(PromptManager::missing_chunks(&Language::German, &[0], 3)
+            .contains("Abschnitte 1 von 3"));
+    }
+
+    #[test]
+    fn test_key_facts_are_capped() {
+        let (capped, dropped) =
+            PromptManager::capped_key_facts(&key_facts(50), MAX_PROMPT_ATTENDEES);
+        let attendees = capped.attendees.unwrap();
+        assert_eq!(dropped, 50 - MAX_PROMPT_ATTENDEES);
+        assert_eq!(attendees.len(), MAX_PROMPT_ATTENDEES);
+        // The oldest are left out
+        assert_eq!(attendees[0].id, 50 - MAX_PROMPT_ATTENDEES);
+
+        let prompt = PromptManager::chunk_user_prompt(&Language::German, "Text", &key_facts(50));
+        assert!(prompt.contains("(20 frühere Attendees sind nicht aufgeführt.)"));
+        assert!(!prompt.contains("\"Person 19\""));
+        assert!(prompt.contains("\"Person 49\""));
+        // Further attendees don't grow the prompt
+        assert_eq!(
+            prompt.len(),
+            PromptManager::chunk_user_prompt(&Language::German, "Text", &key_facts(60)).len()
+        );
+
+        let (_, dropped) = PromptManager::capped_key_facts(&key_facts(3), MAX_PROMPT_ATTENDEES);
+        assert_eq!(dropped, 0);
+        assert!(
+            !PromptManager::chunk_user_prompt(&Language::English, "Text", &key_facts(3))
+                .contains("earlier attendees")
+        );
+    }
+
+    #[test]
+    fn test_summary_style_serialization() {
+        let style: SummaryStyle = serde_json::from_str(
+            r#"{"kind":"custom","included_sections":["technical_issues","role_assignment"]}"#,
+        )
+        .unwrap();
+        assert!(style.includes(ChatterSection::TechnicalIssues));
+        assert!(style.includes(ChatterSection::RoleAssignment));
+        assert!(!style.includes(ChatterSection::PersonalAnecdotes));
+
+        assert_eq!(
+            serde_json::to_string(&SummaryStyle::Strict).unwrap(),
+            r#"{"kind":"strict"}"#
+        );
+    }
+}
@@ -0,0 +1,1006 @@
+use serde_json::{json, Value};
+use tauri_plugin_http::reqwest::{self, Client, RequestBuilder, StatusCode};
+
+use crate::llm::{
+    config::{API_CHAT_ENDPOINT, API_GENERATE_ENDPOINT, DEFAULT_CONTEXT_SIZE},
+    error::{IntoLlmError, LlmError, LlmResult},
+    models::OllamaResponse,
+    usage::TokenUsage,
+};
+
+pub const OPENAI_DEFAULT_ENDPOINT: &str = "https://api.openai.com";
+pub const ANTHROPIC_DEFAULT_ENDPOINT: &str = "https://api.anthropic.com";
+const OPENAI_CHAT_ENDPOINT: &str = "/v1/chat/completions";
+const ANTHROPIC_MESSAGES_ENDPOINT: &str = "/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Enough for a final summary of a long meeting
+const ANTHROPIC_MAX_TOKENS: u32 = 8192;
+/// Anthropic has no JSON mode, structured output is requested as a forced tool call
+const STRUCTURED_TOOL_NAME: &str = "respond";
+
+/// Body for `/api/chat`, with the prompts as system and user message
+pub fn ollama_chat_body(
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    structure: Option<schemars::Schema>,
+) -> Value {
+    let mut body = json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": system_prompt},
+            {"role": "user", "content": user_prompt},
+        ],
+        "stream": false,
+        "options": {"num_ctx": DEFAULT_CONTEXT_SIZE},
+    });
+
+    if let Some(schema) = structure {
+        body["format"] = Value::from(schema);
+    }
+
+    body
+}
+
+/// Body for `/api/generate`, which has no roles. Only for Ollama versions
+/// without `/api/chat`, instructions are followed worse this way.
+pub fn ollama_request_body(
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    structure: Option<schemars::Schema>,
+) -> Value {
+    // Merge system and user prompts into one string
+    let full_prompt = format!("System: {}\nUser: {}", system_prompt, user_prompt);
+
+    let mut body = json!({
+        "model": model,
+        "prompt": full_prompt,
+        "stream": false,
+        "options": {"num_ctx": DEFAULT_CONTEXT_SIZE},
+    });
+
+    if let Some(schema) = structure {
+        body["format"] = Value::from(schema);
+    }
+
+    body
+}
+
+/// Ollama reports errors as `{"error": "..."}`, e.g. a 404 with
+/// `model 'llamaX' not found` for a wrong model name
+pub fn ollama_error(status: u16, body: &str) -> LlmError {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|body| body["error"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.trim().to_string());
+    let lower = message.to_lowercase();
+
+    if status == 404 || (lower.contains("model") && lower.contains("not found")) {
+        LlmError::ModelNotFound(message)
+    } else if lower.contains("context") && (lower.contains("length") || lower.contains("exceed")) {
+        LlmError::ContextLengthExceeded(message)
+    } else if (400..500).contains(&status) {
+        LlmError::RequestRejected(format!("Ollama returned {}: {}", status, message))
+    } else {
+        LlmError::ServerError(format!("Ollama returned {}: {}", status, message))
+    }
+}
+
+fn has_error_field(body: &str) -> bool {
+    serde_json::from_str::<Value>(body).is_ok_and(|body| body["error"].is_string())
+}
+
+/// Parses the answers of both `/api/chat` and `/api/generate`
+pub fn parse_ollama_response(status: u16, body: &str) -> LlmResult<(String, TokenUsage)> {
+    match serde_json::from_str::<OllamaResponse>(body) {
+        Ok(response) => {
+            let usage = TokenUsage::request(
+                response.prompt_eval_count.max(0) as u64,
+                response.eval_count.max(0) as u64,
+            );
+            let content = response.content().ok_or_else(|| {
+                LlmError::ParseError("Ollama response has no message content".to_string())
+            })?;
+            Ok((content, usage))
+        }
+        // Errors that happen after the response started arrive with a success status
+        Err(_) if has_error_field(body) => Err(ollama_error(status, body)),
+        Err(e) => Err(LlmError::ParseError(format!(
+            "Failed to parse Ollama response: {}",
+            e
+        ))),
+    }
+}
+
+pub async fn ollama_chat(
+    client: &Client,
+    base_url: &str,
+    body: &Value,
+) -> LlmResult<(String, TokenUsage)> {
+    ollama_post(client, base_url, API_CHAT_ENDPOINT, body).await
+}
+
+pub async fn ollama_generate(
+    client: &Client,
+    base_url: &str,
+    body: &Value,
+) -> LlmResult<(String, TokenUsage)> {
+    ollama_post(client, base_url, API_GENERATE_ENDPOINT, body).await
+}
+
+async fn ollama_post(
+    client: &Client,
+    base_url: &str,
+    endpoint: &str,
+    body: &Value,
+) -> LlmResult<(String, TokenUsage)> {
+    let response = client
+        .post(format!("{}{}", base_url, endpoint))
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| request_error(e, "Failed to send request to Ollama"))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| request_error(e, "Failed to read Ollama response"))?;
+
+    if !status.is_success() {
+        return Err(ollama_error(status.as_u16(), &body));
+    }
+    parse_ollama_response(status.as_u16(), &body)
+}
+
+/// Text and token counts collected from the lines of a streamed answer
+#[derive(Default)]
+struct OllamaStream {
+    text: String,
+    usage: TokenUsage,
+    lines: usize,
+}
+
+impl OllamaStream {
+    /// Returns whether the line added text
+    fn push_line(&mut self, status: u16, line: &[u8]) -> LlmResult<bool> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(false);
+        }
+        let (piece, usage) = parse_ollama_response(status, line)?;
+        self.lines += 1;
+        self.text.push_str(&piece);
+        // The counts come with the last line, the ones before have none
+        self.usage = usage;
+        Ok(!piece.is_empty())
+    }
+
+    fn finish(self) -> LlmResult<(String, TokenUsage)> {
+        if self.lines == 0 {
+            return Err(LlmError::ParseError(
+                "Ollama response has no message content".to_string(),
+            ));
+        }
+        Ok((self.text, self.usage))
+    }
+}
+
+/// Send the request with `"stream": true` to `/api/chat` or `/api/generate`
+/// and read the answer line by line. `on_text` gets the text so far whenever
+/// a line adds to it.
+pub async fn ollama_stream(
+    client: &Client,
+    base_url: &str,
+    endpoint: &str,
+    body: &Value,
+    on_text: &(dyn Fn(&str) + Sync),
+) -> LlmResult<(String, TokenUsage)> {
+    let mut body = body.clone();
+    body["stream"] = Value::Bool(true);
+    let mut response = client
+        .post(format!("{}{}", base_url, endpoint))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| request_error(e, "Failed to send request to Ollama"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .map_err(|e| request_error(e, "Failed to read Ollama response"))?;
+        return Err(ollama_error(status.as_u16(), &body));
+    }
+
+    let mut stream = OllamaStream::default();
+    let mut buffer = Vec::new();
+    while let Some(bytes) = response
+        .chunk()
+        .await
+        .map_err(|e| request_error(e, "Failed to read Ollama response"))?
+    {
+        buffer.extend_from_slice(&bytes);
+        while let Some(end) = buffer.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            if stream.push_line(status.as_u16(), &line)? {
+                on_text(&stream.text);
+            }
+        }
+    }
+    // The last line may come without a newline
+    if stream.push_line(status.as_u16(), &buffer)? {
+        on_text(&stream.text);
+    }
+    stream.finish()
+}
+
+pub fn openai_request_body(
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    structure: Option<schemars::Schema>,
+) -> Value {
+    let mut body = json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": system_prompt},
+            {"role": "user", "content": user_prompt},
+        ],
+    });
+
+    if let Some(schema) = structure {
+        // Not strict: schemars schemas don't meet the strict mode rules
+        body["response_format"] = json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "response",
+                "schema": Value::from(schema),
+                "strict": false,
+            },
+        });
+    }
+
+    body
+}
+
+/// JSON mode for servers without `json_schema` support. The schema goes into
+/// the system prompt instead, so the answer still parses into the same type.
+pub fn openai_json_mode_body(
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    schema: schemars::Schema,
+) -> Value {
+    let system_prompt = format!(
+        "{}\n\nRespond with a JSON object matching this JSON schema:\n{}",
+        system_prompt,
+        Value::from(schema)
+    );
+    let mut body = openai_request_body(model, &system_prompt, user_prompt, None);
+    body["response_format"] = json!({"type": "json_object"});
+    body
+}
+
+/// `endpoint` (starting with `/v1`) below `base_url`, which may already end
+/// in `/v1` the way LM Studio and vLLM show their address
+pub fn openai_api_url(base_url: &str, endpoint: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let base_url = base_url.strip_suffix("/v1").unwrap_or(base_url);
+    format!("{}{}", base_url, endpoint)
+}
+
+/// Chat completions below `base_url`, see `openai_api_url`
+pub fn chat_completions_url(base_url: &str) -> String {
+    openai_api_url(base_url, OPENAI_CHAT_ENDPOINT)
+}
+
+/// Servers that can't do structured output name the field they reject
+fn rejects_schema(error: &LlmError) -> bool {
+    let (LlmError::RequestRejected(message) | LlmError::ServerError(message)) = error else {
+        return false;
+    };
+    let message = message.to_lowercase();
+    message.contains("response_format") || message.contains("json_schema")
+}
+
+pub fn parse_openai_response(response: &Value) -> LlmResult<String> {
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| LlmError::ParseError("OpenAI response has no message content".to_string()))
+}
+
+pub fn parse_openai_usage(response: &Value) -> TokenUsage {
+    TokenUsage::request(
+        response["usage"]["prompt_tokens"].as_u64().unwrap_or(0),
+        response["usage"]["completion_tokens"].as_u64().unwrap_or(0),
+    )
+}
+
+pub fn anthropic_request_body(
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    structure: Option<schemars::Schema>,
+) -> Value {
+    let mut body = json!({
+        "model": model,
+        "max_tokens": ANTHROPIC_MAX_TOKENS,
+        "system": system_prompt,
+        "messages": [
+            {"role": "user", "content": user_prompt},
+        ],
+    });
+
+    if let Some(schema) = structure {
+        body["tools"] = json!([{
+            "name": STRUCTURED_TOOL_NAME,
+            "description": "Respond with the requested structure",
+            "input_schema": Value::from(schema),
+        }]);
+        body["tool_choice"] = json!({"type": "tool", "name": STRUCTURED_TOOL_NAME});
+    }
+
+    body
+}
+
+/// The tool input as JSON for structured requests, the text otherwise
+pub fn parse_anthropic_response(response: &Value) -> LlmResult<String> {
+    let content = response["content"]
+        .as_array()
+        .ok_or_else(|| LlmError::ParseError("Anthropic response has no content".to_string()))?;
+
+    if let Some(tool_use) = content
+        .iter()
+        .find(|block| block["type"] == "tool_use" && block["name"] == STRUCTURED_TOOL_NAME)
+    {
+        return serde_json::to_string(&tool_use["input"])
+            .map_parse_err("Failed to serialize Anthropic tool input");
+    }
+
+    let text: String = content
+        .iter()
+        .filter(|block| block["type"] == "text")
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    if text.is_empty() {
+        return Err(LlmError::ParseError(
+            "Anthropic response has no text".to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+pub fn parse_anthropic_usage(response: &Value) -> TokenUsage {
+    TokenUsage::request(
+        response["usage"]["input_tokens"].as_u64().unwrap_or(0),
+        response["usage"]["output_tokens"].as_u64().unwrap_or(0),
+    )
+}
+
+/// A request without an answer in time is a timeout, not a network failure
+fn request_error(error: reqwest::Error, context: &str) -> LlmError {
+    if error.is_timeout() {
+        LlmError::TimeoutError(format!("{}: {}", context, error))
+    } else {
+        LlmError::NetworkError(format!("{}: {}", context, error))
+    }
+}
+
+/// Send the request and return the JSON body, turning error statuses into
+/// errors that carry the provider's message (e.g. an invalid API key)
+async fn send(request: RequestBuilder, provider: &str) -> LlmResult<Value> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| request_error(e, &format!("Failed to send request to {}", provider)))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| request_error(e, &format!("Failed to read {} response", provider)))?;
+
+    if !status.is_success() {
+        let message = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|body| body["error"]["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| body.trim().to_string());
+        let message = format!("{} returned {}: {}", provider, status, message);
+        // Rate limits and overloaded servers are worth another attempt
+        return Err(
+            if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+                LlmError::ServerError(message)
+            } else {
+                LlmError::RequestRejected(message)
+            },
+        );
+    }
+
+    serde_json::from_str(&body).map_parse_err(&format!("Failed to parse {} response", provider))
+}
+
+async fn post_chat_completion(
+    client: &Client,
+    url: &str,
+    api_key: Option<&str>,
+    body: &Value,
+    provider: &str,
+) -> LlmResult<(String, TokenUsage)> {
+    let mut request = client.post(url).json(body);
+    if let Some(api_key) = api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let response = send(request, provider).await?;
+    Ok((
+        parse_openai_response(&response)?,
+        parse_openai_usage(&response),
+    ))
+}
+
+pub async fn openai_chat(
+    client: &Client,
+    base_url: &str,
+    api_key: &str,
+    body: &Value,
+) -> LlmResult<(String, TokenUsage)> {
+    let url = format!("{}{}", base_url, OPENAI_CHAT_ENDPOINT);
+    post_chat_completion(client, &url, Some(api_key), body, "OpenAI").await
+}
+
+/// Chat completion on a server with OpenAI's API. A schema it rejects is
+/// requested again in JSON mode.
+pub async fn openai_compatible_chat(
+    client: &Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    structure: Option<schemars::Schema>,
+) -> LlmResult<(String, TokenUsage)> {
+    const PROVIDER: &str = "OpenAI-compatible server";
+    let url = chat_completions_url(base_url);
+    let body = openai_request_body(model, system_prompt, user_prompt, structure.clone());
+
+    match (
+        post_chat_completion(client, &url, api_key, &body, PROVIDER).await,
+        structure,
+    ) {
+        (Err(e), Some(schema)) if rejects_schema(&e) => {
+            println!("⚠️  JSON schema rejected, falling back to JSON mode: {}", e);
+            let body = openai_json_mode_body(model, system_prompt, user_prompt, schema);
+            post_chat_completion(client, &url, api_key, &body, PROVIDER).await
+        }
+        (result, _) => result,
+    }
+}
+
+pub async fn anthropic_messages(
+    client: &Client,
+    base_url: &str,
+    api_key: &str,
+    body: &Value,
+) -> LlmResult<(String, TokenUsage)> {
+    let request = client
+        .post(format!("{}{}", base_url, ANTHROPIC_MESSAGES_ENDPOINT))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .json(body);
+    let response = send(request, "Anthropic").await?;
+    Ok((
+        parse_anthropic_response(&response)?,
+        parse_anthropic_usage(&response),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+    use schemars::{schema_for, JsonSchema};
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Answer {
+        title: String,
+    }
+
+    #[tokio::test]
+    async fn test_openai_structured_request() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer sk-test")
+            .match_body(Matcher::PartialJson(json!({
+                "model": "gpt-4o",
+                "messages": [
+                    {"role": "system", "content": "Fasse zusammen."},
+                    {"role": "user", "content": "Transkript"},
+                ],
+                "response_format": {"type": "json_schema"},
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": "chatcmpl-1",
+                    "choices": [{
+                        "index": 0,
+                        "message": {"role": "assistant", "content": "{\"title\":\"Planung\"}"},
+                        "finish_reason": "stop",
+                    }],
+                    "usage": {"prompt_tokens": 1200, "completion_tokens": 80, "total_tokens": 1280},
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let body = openai_request_body(
+            "gpt-4o",
+            "Fasse zusammen.",
+            "Transkript",
+            Some(schema_for!(Answer)),
+        );
+        let (response, usage) = openai_chat(&Client::new(), &server.url(), "sk-test", &body)
+            .await
+            .unwrap();
+
+        assert_eq!(response, "{\"title\":\"Planung\"}");
+        assert_eq!(usage, TokenUsage::request(1200, 80));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_openai_error_message() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"error": {"message": "Incorrect API key provided", "type": "invalid_request_error"}})
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let body = openai_request_body("gpt-4o", "system", "user", None);
+        let error = openai_chat(&Client::new(), &server.url(), "wrong", &body)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, LlmError::RequestRejected(_)));
+        assert!(error.to_string().contains("Incorrect API key provided"));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_falls_back_to_json_mode() {
+        let mut server = mockito::Server::new_async().await;
+        let schema_request = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({
+                "response_format": {"type": "json_schema"},
+            })))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"error": {"message": "'response_format.type' must be 'json_object' or 'text'"}})
+                    .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let json_mode_request = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({
+                "response_format": {"type": "json_object"},
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"choices": [{"message": {"content": "{\"title\":\"Planung\"}"}}]})
+                    .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let (response, _) = openai_compatible_chat(
+            &Client::new(),
+            &format!("{}/v1/", server.url()),
+            None,
+            "qwen2.5-7b-instruct",
+            "Fasse zusammen.",
+            "Transkript",
+            Some(schema_for!(Answer)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response, "{\"title\":\"Planung\"}");
+        schema_request.assert_async().await;
+        json_mode_request.assert_async().await;
+    }
+
+    #[test]
+    fn test_json_mode_prompt_contains_the_schema() {
+        let body = openai_json_mode_body(
+            "model",
+            "Fasse zusammen.",
+            "Transkript",
+            schema_for!(Answer),
+        );
+        let system_prompt = body["messages"][0]["content"].as_str().unwrap();
+        assert!(system_prompt.starts_with("Fasse zusammen."));
+        assert!(system_prompt.contains("\"title\""));
+        assert_eq!(body["messages"][1]["content"], "Transkript");
+    }
+
+    #[test]
+    fn test_chat_completions_url() {
+        for base_url in [
+            "http://localhost:1234",
+            "http://localhost:1234/",
+            "http://localhost:1234/v1",
+            "http://localhost:1234/v1/",
+        ] {
+            assert_eq!(
+                chat_completions_url(base_url),
+                "http://localhost:1234/v1/chat/completions"
+            );
+            assert_eq!(
+                openai_api_url(base_url, "/v1/models"),
+                "http://localhost:1234/v1/models"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_tool_request() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "sk-ant-test")
+            .match_header("anthropic-version", ANTHROPIC_VERSION)
+            .match_body(Matcher::PartialJson(json!({
+                "model": "claude-sonnet-4-5",
+                "system": "Fasse zusammen.",
+                "tool_choice": {"type": "tool", "name": STRUCTURED_TOOL_NAME},
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": "msg_1",
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{
+                        "type": "tool_use",
+                        "id": "toolu_1",
+                        "name": STRUCTURED_TOOL_NAME,
+                        "input": {"title": "Planung"},
+                    }],
+                    "stop_reason": "tool_use",
+                    "usage": {"input_tokens": 2048, "output_tokens": 64},
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let body = anthropic_request_body(
+            "claude-sonnet-4-5",
+            "Fasse zusammen.",
+            "Transkript",
+            Some(schema_for!(Answer)),
+        );
+        let (response, usage) =
+            anthropic_messages(&Client::new(), &server.url(), "sk-ant-test", &body)
+                .await
+                .unwrap();
+
+        assert_eq!(usage, TokenUsage::request(2048, 64));
+        let answer: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(answer, json!({"title": "Planung"}));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_text_request() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::PartialJson(json!({
+                "messages": [{"role": "user", "content": "Hallo?"}],
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "content": [
+                        {"type": "text", "text": "Hallo, "},
+                        {"type": "text", "text": "alles gut."},
+                    ],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let body = anthropic_request_body("claude-sonnet-4-5", "system", "Hallo?", None);
+        assert!(body.get("tools").is_none());
+
+        let (response, usage) = anthropic_messages(&Client::new(), &server.url(), "key", &body)
+            .await
+            .unwrap();
+        assert_eq!(response, "Hallo, alles gut.");
+        // Still counts as a request when the provider sends no usage
+        assert_eq!(usage, TokenUsage::request(0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_ollama_chat() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .match_body(Matcher::PartialJson(json!({
+                "model": "llama3.1",
+                "messages": [
+                    {"role": "system", "content": "Fasse zusammen."},
+                    {"role": "user", "content": "Transkript"},
+                ],
+                "stream": false,
+                "format": {"title": "Answer"},
+                "options": {"num_ctx": DEFAULT_CONTEXT_SIZE},
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "model": "llama3.1",
+                    "created_at": "2025-03-01T10:00:00Z",
+                    "message": {"role": "assistant", "content": "{\"title\":\"Planung\"}"},
+                    "done": true,
+                    "done_reason": "stop",
+                    "total_duration": 1,
+                    "load_duration": 1,
+                    "prompt_eval_count": 900,
+                    "prompt_eval_duration": 1,
+                    "eval_count": 12,
+                    "eval_duration": 1,
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let body = ollama_chat_body(
+            "llama3.1",
+            "Fasse zusammen.",
+            "Transkript",
+            Some(schema_for!(Answer)),
+        );
+        let (response, usage) = ollama_chat(&Client::new(), &server.url(), &body)
+            .await
+            .unwrap();
+
+        assert_eq!(response, "{\"title\":\"Planung\"}");
+        assert_eq!(usage, TokenUsage::request(900, 12));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_ollama_generate() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .match_body(Matcher::PartialJson(
+                json!({"model": "llama3", "stream": false}),
+            ))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "model": "llama3",
+                    "created_at": "2025-03-01T10:00:00Z",
+                    "response": "Hallo",
+                    "done": true,
+                    "done_reason": "stop",
+                    "context": [1, 2],
+                    "total_duration": 1,
+                    "load_duration": 1,
+                    "prompt_eval_count": 120,
+                    "prompt_eval_duration": 1,
+                    "eval_count": 8,
+                    "eval_duration": 1,
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let body = ollama_request_body("llama3", "system", "user", None);
+        let (response, usage) = ollama_generate(&Client::new(), &server.url(), &body)
+            .await
+            .unwrap();
+        assert_eq!(response, "Hallo");
+        assert_eq!(usage, TokenUsage::request(120, 8));
+    }
+
+    fn stream_line(content: &str, done: bool) -> String {
+        let mut line = json!({
+            "model": "llama3",
+            "created_at": "2025-03-01T10:00:00Z",
+            "message": {"role": "assistant", "content": content},
+            "done": done,
+        });
+        if done {
+            line["prompt_eval_count"] = json!(120);
+            line["eval_count"] = json!(3);
+        }
+        format!("{}\n", line)
+    }
+
+    #[tokio::test]
+    async fn test_ollama_stream_collects_the_pieces() {
+        let mut server = mockito::Server::new_async().await;
+        let body = [
+            stream_line("{\"title\":", false),
+            stream_line(" \"Plenum", false),
+            stream_line(" März\"}", false),
+            stream_line("", true),
+        ]
+        .concat();
+        let mock = server
+            .mock("POST", "/api/chat")
+            .match_body(Matcher::PartialJson(json!({"stream": true})))
+            .with_header("content-type", "application/x-ndjson")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let seen = std::sync::Mutex::new(Vec::new());
+        let on_text = |text: &str| seen.lock().unwrap().push(text.to_string());
+        let body = ollama_chat_body("llama3", "system", "user", None);
+        let (response, usage) = ollama_stream(
+            &Client::new(),
+            &server.url(),
+            API_CHAT_ENDPOINT,
+            &body,
+            &on_text,
+        )
+        .await
+        .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response, "{\"title\": \"Plenum März\"}");
+        assert_eq!(usage, TokenUsage::request(120, 3));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                "{\"title\":",
+                "{\"title\": \"Plenum",
+                "{\"title\": \"Plenum März\"}"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ollama_stream_error_after_the_start() {
+        let mut server = mockito::Server::new_async().await;
+        let body = format!(
+            "{}{}",
+            stream_line("{\"title\":", false),
+            r#"{"error":"llama runner process has terminated"}"#
+        );
+        server
+            .mock("POST", "/api/chat")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let body = ollama_chat_body("llama3", "system", "user", None);
+        let error = ollama_stream(
+            &Client::new(),
+            &server.url(),
+            API_CHAT_ENDPOINT,
+            &body,
+            &|_| {},
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(error, LlmError::ServerError(_)));
+        assert!(error
+            .to_string()
+            .contains("llama runner process has terminated"));
+    }
+
+    #[tokio::test]
+    async fn test_ollama_unknown_model() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error":"model 'llamaX' not found"}"#)
+            .create_async()
+            .await;
+
+        let body = ollama_request_body("llamaX", "system", "user", None);
+        let error = ollama_generate(&Client::new(), &server.url(), &body)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, LlmError::ModelNotFound(_)));
+        assert!(error.to_string().contains("model 'llamaX' not found"));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_ollama_server_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .with_status(500)
+            .with_body("Internal Server Error")
+            .create_async()
+            .await;
+
+        let body = ollama_request_body("llama3", "system", "user", None);
+        let error = ollama_generate(&Client::new(), &server.url(), &body)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, LlmError::ServerError(_)));
+        assert!(error.to_string().contains("500"));
+        assert!(error.to_string().contains("Internal Server Error"));
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_ollama_truncated_response() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"model":"llama3","created_at":"2025-03-01T10:00:00Z","respon"#)
+            .create_async()
+            .await;
+
+        let body = ollama_request_body("llama3", "system", "user", None);
+        let error = ollama_generate(&Client::new(), &server.url(), &body)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, LlmError::ParseError(_)));
+        assert!(error
+            .to_string()
+            .contains("Failed to parse Ollama response"));
+    }
+
+    #[test]
+    fn test_ollama_error_kinds() {
+        assert!(matches!(
+            ollama_error(
+                400,
+                r#"{"error":"input length exceeds the context length"}"#
+            ),
+            LlmError::ContextLengthExceeded(_)
+        ));
+        // An error after the response started comes with status 200
+        assert!(matches!(
+            parse_ollama_response(200, r#"{"error":"model 'llamaX' not found"}"#),
+            Err(LlmError::ModelNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_responses() {
+        assert!(matches!(
+            parse_ollama_response(200, r#"{"model":"llama3","created_at":"","done":true}"#),
+            Err(LlmError::ParseError(_))
+        ));
+        assert!(parse_openai_response(&json!({"choices": []})).is_err());
+        assert!(parse_anthropic_response(&json!({"content": []})).is_err());
+        assert!(parse_anthropic_response(&json!({"type": "error"})).is_err());
+    }
+}
@@ -1,29 +1,188 @@
-use serde_json::json;
 use tauri_plugin_http::reqwest::Client;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use crate::cancellation::CancellationToken;
 use crate::llm::{
-    config::{DEFAULT_CONTEXT_SIZE, API_GENERATE_ENDPOINT},
-    error::{LlmError, LlmResult, IntoLlmError},
-    models::OllamaResponse,
-    progress::ProgressTracker,
+    api_keys::get_api_key,
+    config::{LlmConfig, LlmProvider, API_CHAT_ENDPOINT, API_GENERATE_ENDPOINT},
+    error::{LlmError, LlmResult},
+    performance::SharedPerformanceTracker,
+    progress::{ProgressTracker, TokenStream},
+    providers,
+    usage::{TokenUsage, UsageMeter},
 };
 
+/// Answers a prompt. Implemented by `LlmService`, and by stubs in tests so the
+/// steps around an LLM call can be tested without a running model.
+#[allow(async_fn_in_trait)]
+pub trait TextGenerator {
+    async fn generate(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        structure: Option<schemars::Schema>,
+    ) -> LlmResult<String>;
+}
+
+/// Wait before the first retry, doubled for every retry after it
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Stop doubling at 32s between attempts
+const MAX_RETRY_DOUBLINGS: u32 = 6;
+
+/// Every request gets an answer in this time or fails with a timeout
+fn http_client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+/// For streamed answers, which may take far longer than `timeout` in total.
+/// Only a stream that stays silent for `timeout` fails.
+fn streaming_http_client(timeout: Duration) -> Client {
+    Client::builder()
+        .connect_timeout(timeout)
+        .read_timeout(timeout)
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
 pub struct LlmService {
     client: Client,
+    /// `client` with a timeout between reads instead of one for the whole answer
+    streaming_client: Client,
     base_url: String,
     model: String,
+    provider: LlmProvider,
+    api_key: Option<String>,
+    usage: UsageMeter,
+    /// Times and tokens of every call, for the metrics of a summarization run
+    performance: Option<SharedPerformanceTracker>,
+    /// Further attempts after a failed request that may succeed when repeated
+    max_retries: u32,
+    /// Time one attempt may take, the client enforces it. Streamed answers
+    /// may take longer as long as they keep coming.
+    timeout: Duration,
+    /// `use_external_api` of the config. There is no local inference, so
+    /// without it every request fails before reaching the network.
+    use_external_api: bool,
+    /// Aborts the request in flight and the ones after it
+    cancellation: Option<CancellationToken>,
+    /// Use Ollama's `/api/generate` instead of `/api/chat`
+    ollama_legacy_generate: bool,
+    /// Stream Ollama's answers of calls with a progress tracker as `llm-token` events
+    stream_tokens: bool,
 }
 
 impl LlmService {
     pub fn new(base_url: String, model: String) -> Self {
+        Self::with_provider(LlmProvider::Ollama, base_url, model, None)
+    }
+
+    pub fn with_provider(
+        provider: LlmProvider,
+        base_url: String,
+        model: String,
+        api_key: Option<String>,
+    ) -> Self {
+        let timeout = Duration::from_secs(LlmConfig::default().timeout_seconds);
         Self {
-            client: Client::new(),
+            client: http_client(timeout),
+            streaming_client: streaming_http_client(timeout),
             base_url,
             model,
+            provider,
+            api_key,
+            usage: UsageMeter::default(),
+            performance: None,
+            max_retries: 0,
+            timeout,
+            use_external_api: true,
+            cancellation: None,
+            ollama_legacy_generate: false,
+            stream_tokens: false,
         }
     }
 
+    pub fn with_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = http_client(timeout);
+        self.streaming_client = streaming_http_client(timeout);
+        self.timeout = timeout;
+        self
+    }
+
+    /// Further attempts a caller may make when an answer is unusable
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn with_external_api(mut self, use_external_api: bool) -> Self {
+        self.use_external_api = use_external_api;
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn with_legacy_generate(mut self, ollama_legacy_generate: bool) -> Self {
+        self.ollama_legacy_generate = ollama_legacy_generate;
+        self
+    }
+
+    pub fn with_streaming(mut self, stream_tokens: bool) -> Self {
+        self.stream_tokens = stream_tokens;
+        self
+    }
+
+    /// Count the tokens of every answered request on `usage`
+    pub fn with_usage_meter(mut self, usage: UsageMeter) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub fn with_performance_tracker(mut self, performance: SharedPerformanceTracker) -> Self {
+        self.performance = Some(performance);
+        self
+    }
+
+    /// Service for the configured provider, with its API key from the keyring
+    pub fn from_config(config: &LlmConfig) -> LlmResult<Self> {
+        let api_key = match config.provider {
+            _ if !config.use_external_api => None,
+            LlmProvider::Ollama => None,
+            // Local servers usually take any key or none
+            LlmProvider::OpenAiCompatible => get_api_key(config.provider).unwrap_or_else(|e| {
+                println!("⚠️  Sending no API key: {}", e);
+                None
+            }),
+            provider => Some(get_api_key(provider)?.ok_or_else(|| {
+                LlmError::ConfigError(format!(
+                    "No API key stored for {}",
+                    provider.display_name()
+                ))
+            })?),
+        };
+
+        Ok(Self::with_provider(
+            config.provider,
+            config.endpoint().to_string(),
+            config.external_model.clone(),
+            api_key,
+        )
+        .with_retries(config.max_retries)
+        .with_timeout(Duration::from_secs(config.timeout_seconds))
+        .with_external_api(config.use_external_api)
+        .with_legacy_generate(config.ollama_legacy_generate)
+        .with_streaming(config.stream_tokens))
+    }
+
     pub async fn generate_text(
         &self,
         system_prompt: &str,
@@ -34,14 +193,72 @@ impl LlmService {
         let start_time = Instant::now();
         println!("🚀 Starting LLM text generation...");
 
+        if !self.use_external_api {
+            println!("⛔ External API is disabled, no request was sent");
+            if let Some(tracker) = progress_tracker {
+                tracker.emit_api_status("⛔ External API is disabled, nothing was generated");
+            }
+            return Err(LlmError::ConfigError(
+                "The external API is disabled and there is no local inference. Enable the API in the LLM settings to use Ollama or a cloud provider.".to_string(),
+            ));
+        }
+
         if let Some(tracker) = progress_tracker {
-            tracker.emit_api_status("🔄 Trying external API...")
-                .map_err(|e| LlmError::NetworkError(e))?;
+            tracker.emit_api_status(&format!("🔄 Sending request to {}...", self.provider.display_name()));
         }
 
+        let tokens = progress_tracker
+            .filter(|_| self.stream_tokens)
+            .map(ProgressTracker::token_stream);
         let api_start = Instant::now();
-        match self.try_external_api(system_prompt, user_prompt, structure).await {
-            Ok(response) => {
+        let attempts = async {
+            let mut attempt = 0;
+            loop {
+                match self
+                    .try_external_api(system_prompt, user_prompt, structure.clone(), tokens.as_ref())
+                    .await
+                {
+                    Err(e) if e.is_retryable() && attempt < self.max_retries => {
+                        attempt += 1;
+                        println!("🔁 Retry {}/{} after: {}", attempt, self.max_retries, e);
+                        if let Some(tracker) = progress_tracker {
+                            tracker.emit_api_status(&format!(
+                                "🔁 Retrying {} request ({}/{}): {}",
+                                self.provider.display_name(),
+                                attempt,
+                                self.max_retries,
+                                e
+                            ));
+                        }
+                        tokio::time::sleep(RETRY_DELAY * 2u32.pow((attempt - 1).min(MAX_RETRY_DOUBLINGS))).await;
+                    }
+                    Err(LlmError::TimeoutError(_)) => {
+                        break Err(LlmError::TimeoutError(format!(
+                            "{} gave no answer within {}s in {} attempts",
+                            self.provider.display_name(),
+                            self.timeout.as_secs(),
+                            attempt + 1
+                        )))
+                    }
+                    result => break result,
+                }
+            }
+        };
+        // Dropping the attempts aborts the request in flight
+        let result = match &self.cancellation {
+            Some(cancellation) => tokio::select! {
+                biased;
+                _ = cancellation.cancelled() => Err(LlmError::Cancelled),
+                result = attempts => result,
+            },
+            None => attempts.await,
+        };
+        match result {
+            Ok((response, usage)) => {
+                self.usage.record(&usage);
+                if let Some(performance) = &self.performance {
+                    performance.record_api_call(api_start, Some(&usage));
+                }
                 let api_duration = api_start.elapsed();
                 let total_duration = start_time.elapsed();
                 println!(
@@ -51,23 +268,31 @@ impl LlmService {
                 );
                 
                 if let Some(tracker) = progress_tracker {
-                    tracker.emit_api_status("✅ External API successful")
-                        .map_err(|e| LlmError::NetworkError(e))?;
+                    tracker.emit_api_status(&format!("✅ {} answered", self.provider.display_name()));
                 }
                 
                 Ok(response)
             }
+            Err(LlmError::Cancelled) => {
+                println!("⏹️  Request cancelled after {:.2}s", api_start.elapsed().as_secs_f64());
+                if let Some(tracker) = progress_tracker {
+                    tracker.emit_api_status("⏹️ Summarization cancelled");
+                }
+                Err(LlmError::Cancelled)
+            }
             Err(e) => {
+                if let Some(performance) = &self.performance {
+                    performance.record_api_call(api_start, None);
+                }
                 let api_duration = api_start.elapsed();
                 println!(
-                    "❌ API failed after {:.2}s: {}, falling back to local model",
+                    "❌ API failed after {:.2}s: {}",
                     api_duration.as_secs_f64(),
                     e
                 );
                 
                 if let Some(tracker) = progress_tracker {
-                    tracker.emit_api_status("❌ External API failed, switching to local model...")
-                        .map_err(|e| LlmError::NetworkError(e))?;
+                    tracker.emit_api_status(&format!("❌ {} request failed: {}", self.provider.display_name(), e));
                 }
                 
                 Err(e)
@@ -80,40 +305,119 @@ impl LlmService {
         system_prompt: &str,
         user_prompt: &str,
         structure: Option<schemars::Schema>,
-    ) -> LlmResult<String> {
+        tokens: Option<&TokenStream>,
+    ) -> LlmResult<(String, TokenUsage)> {
+        let api_key = self.api_key.as_deref().unwrap_or_default();
+        match self.provider {
+            LlmProvider::Ollama => {
+                self.try_ollama(system_prompt, user_prompt, structure, tokens)
+                    .await
+            }
+            LlmProvider::OpenAi => {
+                println!("Trying OpenAI API");
+                let body = providers::openai_request_body(&self.model, system_prompt, user_prompt, structure);
+                providers::openai_chat(&self.client, &self.base_url, api_key, &body).await
+            }
+            LlmProvider::OpenAiCompatible => {
+                println!("Trying OpenAI-compatible API");
+                providers::openai_compatible_chat(
+                    &self.client,
+                    &self.base_url,
+                    self.api_key.as_deref(),
+                    &self.model,
+                    system_prompt,
+                    user_prompt,
+                    structure,
+                )
+                .await
+            }
+            LlmProvider::Anthropic => {
+                println!("Trying Anthropic API");
+                let body = providers::anthropic_request_body(&self.model, system_prompt, user_prompt, structure);
+                providers::anthropic_messages(&self.client, &self.base_url, api_key, &body).await
+            }
+        }
+    }
+
+    async fn try_ollama(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        structure: Option<schemars::Schema>,
+        tokens: Option<&TokenStream>,
+    ) -> LlmResult<(String, TokenUsage)> {
         println!("Trying external Ollama API");
+        let (endpoint, body) = if self.ollama_legacy_generate {
+            let body = providers::ollama_request_body(&self.model, system_prompt, user_prompt, structure);
+            (API_GENERATE_ENDPOINT, body)
+        } else {
+            let body = providers::ollama_chat_body(&self.model, system_prompt, user_prompt, structure);
+            (API_CHAT_ENDPOINT, body)
+        };
+        match tokens {
+            Some(tokens) => {
+                let on_text = |text: &str| tokens.push(text);
+                providers::ollama_stream(&self.streaming_client, &self.base_url, endpoint, &body, &on_text).await
+            }
+            None if self.ollama_legacy_generate => {
+                providers::ollama_generate(&self.client, &self.base_url, &body).await
+            }
+            None => providers::ollama_chat(&self.client, &self.base_url, &body).await,
+        }
+    }
+}
 
-        // Merge system and user prompts into one string
-        let full_prompt = format!("System: {}\nUser: {}", system_prompt, user_prompt);
+impl TextGenerator for LlmService {
+    async fn generate(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        structure: Option<schemars::Schema>,
+    ) -> LlmResult<String> {
+        self.generate_text(system_prompt, user_prompt, structure, None::<&ProgressTracker>)
+            .await
+    }
+}
 
-        let mut json = json!({
-            "model": self.model,
-            "prompt": full_prompt,
-            "stream": false,
-            "num_ctx": DEFAULT_CONTEXT_SIZE,
-        });
+/// Test double for code that calls the LLM through `TextGenerator`
+#[cfg(test)]
+pub mod stub {
+    use super::*;
+    use std::sync::Mutex;
 
-        if let Some(schema) = structure {
-            json.as_object_mut().unwrap().insert(
-                "format".to_string(),
-                serde_json::Value::from(schema),
-            );
+    pub struct StubGenerator {
+        response: Result<String, String>,
+        /// User prompt of the last call
+        pub last_prompt: Mutex<Option<String>>,
+    }
+
+    impl StubGenerator {
+        pub fn responding(response: &str) -> Self {
+            Self {
+                response: Ok(response.to_string()),
+                last_prompt: Mutex::new(None),
+            }
         }
 
-        let url = format!("{}{}", self.base_url, API_GENERATE_ENDPOINT);
-        
-        let response = self
-            .client
-            .post(&url)
-            .json(&json)
-            .send()
-            .await
-            .map_network_err("Failed to send request to Ollama")?
-            .json::<OllamaResponse>()
-            .await
-            .map_parse_err("Failed to parse Ollama response")?;
+        /// Fails every call with a network error
+        pub fn failing(message: &str) -> Self {
+            Self {
+                response: Err(message.to_string()),
+                last_prompt: Mutex::new(None),
+            }
+        }
+    }
 
-        Ok(response.response)
+    impl TextGenerator for StubGenerator {
+        async fn generate(
+            &self,
+            _system_prompt: &str,
+            user_prompt: &str,
+            _structure: Option<schemars::Schema>,
+        ) -> LlmResult<String> {
+            *self.last_prompt.lock().unwrap() = Some(user_prompt.to_string());
+            self.response.clone().map_err(LlmError::NetworkError)
+        }
     }
 }
 
@@ -129,5 +433,306 @@ mod tests {
         );
         assert_eq!(service.base_url, "http://localhost:11434");
         assert_eq!(service.model, "llama3.1");
+        assert_eq!(service.provider, LlmProvider::Ollama);
+    }
+
+    #[tokio::test]
+    async fn test_generate_dispatches_to_provider() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "sk-ant-test")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"content":[{"type":"text","text":"OK"}]}"#)
+            .create_async()
+            .await;
+
+        let service = LlmService::with_provider(
+            LlmProvider::Anthropic,
+            server.url(),
+            "claude-sonnet-4-5".to_string(),
+            Some("sk-ant-test".to_string()),
+        );
+        let response = service.generate("system", "user", None).await.unwrap();
+
+        assert_eq!(response, "OK");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_usage_is_metered_across_services() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/chat/completions")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices":[{"message":{"content":"OK"}}],"usage":{"prompt_tokens":30,"completion_tokens":2}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let meter = UsageMeter::default();
+        for _ in 0..2 {
+            let service = LlmService::with_provider(
+                LlmProvider::OpenAi,
+                server.url(),
+                "gpt-4o".to_string(),
+                Some("sk-test".to_string()),
+            )
+            .with_usage_meter(meter.clone());
+            service.generate("system", "user", None).await.unwrap();
+        }
+
+        assert_eq!(
+            meter.total(),
+            TokenUsage {
+                prompt_tokens: 60,
+                completion_tokens: 4,
+                requests: 2,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_server_without_key() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "model": "qwen2.5-7b-instruct",
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices":[{"message":{"content":"OK"}}]}"#)
+            .create_async()
+            .await;
+
+        let service = LlmService::with_provider(
+            LlmProvider::OpenAiCompatible,
+            format!("{}/v1", server.url()),
+            "qwen2.5-7b-instruct".to_string(),
+            None,
+        );
+        let response = service.generate("system", "user", None).await.unwrap();
+
+        assert_eq!(response, "OK");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_disabled_api_sends_no_request() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let config = LlmConfig {
+            use_external_api: false,
+            external_endpoint: server.url(),
+            ..LlmConfig::default()
+        };
+        let service = LlmService::from_config(&config).unwrap();
+        let error = service.generate("system", "user", None).await.unwrap_err();
+
+        assert!(matches!(error, LlmError::ConfigError(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_aborts_the_request_in_flight() {
+        // Accepts the connection and never answers
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let _connection = listener.accept().await;
+            std::future::pending::<()>().await;
+        });
+
+        let cancellation = CancellationToken::default();
+        let service = LlmService::new(url, "llama3.1".to_string()).with_cancellation(cancellation.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancellation.cancel();
+        });
+
+        let error = tokio::time::timeout(Duration::from_secs(5), service.generate("system", "user", None))
+            .await
+            .expect("the request wasn't aborted")
+            .unwrap_err();
+        assert!(matches!(error, LlmError::Cancelled));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_not_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error":"model 'llamaX' not found"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = LlmService::new(server.url(), "llamaX".to_string()).with_retries(3);
+        let error = service.generate("system", "user", None).await.unwrap_err();
+
+        assert!(matches!(error, LlmError::ModelNotFound(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .with_status(500)
+            .with_body(r#"{"error":"llama runner process has terminated"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let service = LlmService::new(server.url(), "llama3".to_string()).with_retries(1);
+        let error = service.generate("system", "user", None).await.unwrap_err();
+
+        assert!(matches!(error, LlmError::ServerError(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_hung_requests_time_out_after_every_attempt() {
+        // Accepts connections but never answers, like a stuck model runner
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+
+        let service = LlmService::new(url, "llama3".to_string())
+            .with_timeout(Duration::from_millis(200))
+            .with_retries(2);
+        let started = Instant::now();
+        let error = service.generate("system", "user", None).await.unwrap_err();
+
+        assert!(matches!(error, LlmError::TimeoutError(_)));
+        assert!(error.to_string().contains("3 attempts"));
+        // Three timeouts plus 0.5s and 1s of backoff
+        assert!(started.elapsed() >= Duration::from_millis(2100));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_streams_may_outlast_the_timeout() {
+        // Sends a line every 100ms, 500ms in total
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = [0; 4096];
+                    let _ = stream.read(&mut request).await;
+                    let head = "HTTP/1.1 200 OK\r\ncontent-type: application/x-ndjson\r\n\
+                                transfer-encoding: chunked\r\n\r\n";
+                    stream.write_all(head.as_bytes()).await.unwrap();
+                    for (content, done) in [("Ple", false), ("num", false), ("", true)] {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        let line = format!(
+                            "{}\n",
+                            serde_json::json!({
+                                "model": "llama3",
+                                "created_at": "2025-03-01T10:00:00Z",
+                                "message": {"role": "assistant", "content": content},
+                                "done": done,
+                            })
+                        );
+                        let chunk = format!("{:x}\r\n{}\r\n", line.len(), line);
+                        let _ = stream.write_all(chunk.as_bytes()).await;
+                    }
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    let _ = stream.write_all(b"0\r\n\r\n").await;
+                });
+            }
+        });
+
+        let timeout = Duration::from_millis(250);
+        let body = providers::ollama_chat_body("llama3", "system", "user", None);
+        let (response, _) = providers::ollama_stream(
+            &streaming_http_client(timeout),
+            &url,
+            API_CHAT_ENDPOINT,
+            &body,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(response, "Plenum");
+
+        let error = providers::ollama_stream(&http_client(timeout), &url, API_CHAT_ENDPOINT, &body, &|_| {})
+            .await
+            .unwrap_err();
+        assert!(matches!(error, LlmError::TimeoutError(_)), "{}", error);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_requests_are_not_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error":"invalid options"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = LlmService::new(server.url(), "llama3".to_string()).with_retries(3);
+        let error = service.generate("system", "user", None).await.unwrap_err();
+
+        assert!(matches!(error, LlmError::RequestRejected(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_legacy_generate_merges_the_prompts() {
+        let mut server = mockito::Server::new_async().await;
+        let chat = server
+            .mock("POST", "/api/chat")
+            .expect(0)
+            .create_async()
+            .await;
+        let generate = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "prompt": "System: system\nUser: user",
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"model":"llama3","created_at":"2025-03-01T10:00:00Z","response":"OK","done":true}"#)
+            .create_async()
+            .await;
+
+        let config = LlmConfig {
+            external_endpoint: server.url(),
+            ollama_legacy_generate: true,
+            ..LlmConfig::default()
+        };
+        let service = LlmService::from_config(&config).unwrap();
+        let response = service.generate("system", "user", None).await.unwrap();
+
+        assert_eq!(response, "OK");
+        chat.assert_async().await;
+        generate.assert_async().await;
+    }
+
+    #[test]
+    fn test_ollama_needs_no_api_key() {
+        let service = LlmService::from_config(&LlmConfig::default()).unwrap();
+        assert!(service.api_key.is_none());
     }
 }
@@ -1,101 +1,529 @@
+use chrono::Utc;
 use schemars::schema_for;
 use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
 use std::time::Instant;
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager, Runtime, Wry};
 use tokio::sync::Mutex;
 
+use crate::cancellation::CancellationToken;
+use crate::chunk_manifest::{read_manifest, save_text_chunks};
+use crate::disk_space::{ensure_free_space, SystemSpaceProbe, SUMMARY_SPACE_BYTES};
+use crate::error::StaleChunks;
+use crate::job_queue::claim_or_queue;
+use crate::job_state::{JobStage, JobStateFile};
+use crate::jobs::{self, JobKind};
 use crate::llm::{
+    approval::{ensure_summary_replaceable, read_approved_summary_in, supersede_approval},
+    attendee_refs::resolve_attendee_references_in,
+    attribution::{anonymize_chunk_summary, anonymize_final_summary, SpeakerAttribution},
+    bullet_dedup::dedup_bullets,
+    chunk_failures::{missing_chunk_indices, ChunkFailures, FailedChunk},
     config::LlmConfig,
+    endpoint_privacy::ensure_endpoint_confirmed,
     error::{LlmError, LlmResult},
-    file_manager::FileManager,
-    models::{FinalSummaryFormat, FirstSummaryFormat, KeyFact, MeetingToMarkdown},
-    progress::ProgressTracker,
-    prompts::{Language, PromptManager},
+    file_manager::{
+        chunk_file_name, chunk_hash, chunk_hash_file_name, current_summarization_run_dir,
+        reusable_chunk_summaries, stale_chunks, FileManager, RunManifest,
+    },
+    key_facts::authoritative_key_facts_for,
+    keywords::update_auto_tags,
+    models::{FinalSummaryFormat, FirstSummaryFormat, KeyFact, MeetingToMarkdown, SummaryResult},
+    partial::{extract_range_text, save_partial_summary, SummaryRange},
+    performance::{save_metrics, PerformanceMetrics, SharedPerformanceTracker},
+    progress::{expected_chunk_duration, ProgressEvents, ProgressTracker},
+    prompt_preview::estimate_tokens,
+    prompts::{ChunkRequest, Language, PromptManager, SummaryStyle},
     service::LlmService,
-    text_processing::split_text_into_chunks,
+    text_processing::{split_text_into_chunks, validate_transcript, ChunkPlan},
+    topic_merge::{merge_topics, TopicMergeConfig},
+    usage::{read_summary_meta, record_usage_run, update_summary_meta, UsageMeter},
 };
+use crate::meeting::{read_meeting_metadata, write_atomic};
+use crate::settings::load_settings;
 use crate::{get_meeting_transcript, AppState};
 
-pub struct SummaryGenerator {
-    app_handle: AppHandle,
-    file_manager: FileManager,
+/// The meeting's own summary style, or the one from the settings
+pub async fn summary_style_for(app: &AppHandle, meeting_dir: &Path) -> SummaryStyle {
+    let meeting_style = read_meeting_metadata(meeting_dir)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|metadata| metadata.summary_style);
+    match meeting_style {
+        Some(style) => style,
+        None => load_settings(app)
+            .await
+            .map(|settings| settings.summary_style)
+            .unwrap_or_default(),
+    }
+}
+
+/// The meeting's own summary language, or the one from the LLM config
+pub async fn language_for(meeting_dir: &Path, config: &LlmConfig) -> Language {
+    read_meeting_metadata(meeting_dir)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|metadata| metadata.language)
+        .unwrap_or(config.language)
+}
+
+/// Whether the meeting's summary names speakers, falling back to the settings
+pub async fn speaker_attribution_for(app: &AppHandle, meeting_dir: &Path) -> SpeakerAttribution {
+    let meeting_choice = read_meeting_metadata(meeting_dir)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|metadata| metadata.attribute_speakers);
+    let settings = load_settings(app).await.unwrap_or_default();
+    SpeakerAttribution {
+        attribute_speakers: meeting_choice.unwrap_or(settings.attribute_speakers),
+        keep_todo_assignees: settings.keep_todo_assignees,
+    }
+}
+
+pub struct SummaryGenerator<R: Runtime = Wry> {
+    app_handle: AppHandle<R>,
+    /// Progress for the frontend, never fails the run
+    events: ProgressEvents,
+    file_manager: FileManager<R>,
     language: Language,
+    style: SummaryStyle,
+    attribution: SpeakerAttribution,
+    /// Key facts corrected by the user, the model must not contradict them
+    authoritative_key_facts: Option<KeyFact>,
+    /// Replace an approved summary, see `llm::approval`
+    supersede_approved: bool,
+    /// Summarize unchanged chunks again instead of reusing their summaries
+    force: bool,
+    usage: UsageMeter,
+    /// Calls, tokens and times of this run, kept as the summary's metrics
+    performance: SharedPerformanceTracker,
+    /// Set by `cancel_summarization` through the token kept in `AppState`
+    cancellation: CancellationToken,
 }
 
-impl SummaryGenerator {
-    pub fn new(app_handle: AppHandle, language: Language) -> Self {
+impl<R: Runtime> SummaryGenerator<R> {
+    pub fn new(app_handle: AppHandle<R>, language: Language) -> Self {
         let file_manager = FileManager::new(app_handle.clone());
         Self {
+            events: ProgressEvents::new(app_handle.clone()),
             app_handle,
             file_manager,
             language,
+            style: SummaryStyle::default(),
+            attribution: SpeakerAttribution::default(),
+            authoritative_key_facts: None,
+            supersede_approved: false,
+            force: false,
+            usage: UsageMeter::default(),
+            performance: SharedPerformanceTracker::default(),
+            cancellation: CancellationToken::default(),
         }
     }
 
-    pub async fn generate_summary(&self, meeting_id: &str) -> LlmResult<String> {
-        let summary_start_time = Instant::now();
-        println!("🚀 Starting full meeting summary generation...");
+    pub fn with_style(mut self, style: SummaryStyle) -> Self {
+        self.style = style;
+        self
+    }
 
-        // Check if another summarization is running
-        self.check_and_set_summarization_state(meeting_id).await?;
+    pub fn with_attribution(mut self, attribution: SpeakerAttribution) -> Self {
+        self.attribution = attribution;
+        self
+    }
+
+    pub fn with_authoritative_key_facts(mut self, key_facts: Option<KeyFact>) -> Self {
+        self.authoritative_key_facts = key_facts;
+        self
+    }
+
+    pub fn with_supersede_approved(mut self, supersede_approved: bool) -> Self {
+        self.supersede_approved = supersede_approved;
+        self
+    }
+
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Record the run's times and tokens in a tracker the caller keeps
+    pub fn with_performance_tracker(mut self, performance: SharedPerformanceTracker) -> Self {
+        self.performance = performance;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Send progress somewhere other than the app's windows
+    #[cfg(test)]
+    pub fn with_progress_sink(
+        mut self,
+        sink: impl crate::llm::progress::ProgressSink + 'static,
+    ) -> Self {
+        self.events = ProgressEvents::new(sink);
+        self
+    }
+
+    /// Refuse to replace an approved summary unless superseding it was asked for
+    async fn ensure_summary_replaceable(&self, meeting_id: &str) -> LlmResult<()> {
+        let meeting_dir = self
+            .file_manager
+            .get_meeting_dir(meeting_id)
+            .map_err(LlmError::FileError)?;
+        ensure_summary_replaceable(&meeting_dir, self.supersede_approved).await?;
+        Ok(())
+    }
+
+    /// After a new summary was saved, the approved one is no longer in force
+    async fn supersede_approval(&self, meeting_id: &str) {
+        let result = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => supersede_approval(&meeting_dir, meeting_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            println!("⚠️  Failed to record superseded approval: {}", e);
+        }
+    }
+
+    /// Remember the style of the summary that was just saved
+    async fn record_style(&self, meeting_id: &str) {
+        let result = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => {
+                update_summary_meta(&meeting_dir, |meta| meta.style = Some(self.style.clone()))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            println!("⚠️  Failed to record summary style: {}", e);
+        }
+    }
+
+    /// Drop repeated bullets before the summary is saved and remember how many
+    async fn dedup_bullets(
+        &self,
+        meeting_id: &str,
+        content: &mut FinalSummaryFormat,
+        config: &LlmConfig,
+    ) {
+        let removed = dedup_bullets(content, &config.bullet_dedup);
+        if removed > 0 {
+            println!("🧹 Removed {} repeated bullet points", removed);
+        }
+
+        let removed = config.bullet_dedup.enabled.then_some(removed);
+        let result = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => {
+                update_summary_meta(&meeting_dir, |meta| {
+                    meta.removed_duplicate_bullets = removed
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            println!("⚠️  Failed to record removed bullet points: {}", e);
+        }
+    }
+
+    /// Remember the chunk size decision, `None` clears the one of an earlier run
+    async fn record_chunk_plan(&self, meeting_id: &str, chunk_plan: Option<ChunkPlan>) {
+        let result = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => {
+                update_summary_meta(&meeting_dir, |meta| meta.chunk_plan = chunk_plan).await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            println!("⚠️  Failed to record chunk plan: {}", e);
+        }
+    }
+
+    /// Service for `config` that counts its tokens towards this run
+    fn llm_service(&self, config: &LlmConfig) -> LlmResult<LlmService> {
+        Ok(LlmService::from_config(config)?
+            .with_usage_meter(self.usage.clone())
+            .with_performance_tracker(self.performance.clone())
+            .with_cancellation(self.cancellation.clone()))
+    }
+
+    /// Store the tokens spent by this run in `summary_meta.json`. Called for
+    /// failed runs too, the tokens were paid for either way.
+    pub async fn record_usage(&self, meeting_id: &str) {
+        let usage = self.usage.total();
+        if usage.requests == 0 {
+            return;
+        }
+
+        let config = match self.get_llm_config().await {
+            Ok(config) => config,
+            Err(_) => return,
+        };
+        let result = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => {
+                record_usage_run(&meeting_dir, config.provider, &config.external_model, usage).await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => println!(
+                "🧮 Used {} tokens ({} prompt, {} completion) in {} requests",
+                usage.total_tokens(),
+                usage.prompt_tokens,
+                usage.completion_tokens,
+                usage.requests
+            ),
+            Err(e) => println!("⚠️  Failed to record token usage: {}", e),
+        }
+    }
+
+    /// Keep the tokens and times of this run with its chunk summaries
+    async fn record_metrics(&self, meeting_id: &str, metrics: &PerformanceMetrics) {
+        let result = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => match current_summarization_run_dir(&meeting_dir).await {
+                Some(run_dir) => save_metrics(&run_dir, metrics).await,
+                None => Err("The summarization run has no directory".to_string()),
+            },
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => println!(
+                "📈 {} tokens in {:.1}s, {:.1} tokens/s",
+                metrics.prompt_tokens + metrics.completion_tokens,
+                metrics.total_duration.as_secs_f64(),
+                metrics.tokens_per_second
+            ),
+            Err(e) => println!("⚠️  Failed to record metrics: {}", e),
+        }
+    }
+
+    /// Keep the result in `summary_meta.json` and tell the frontend the summary is done
+    async fn record_result(&self, result: &SummaryResult) {
+        let saved = match self.file_manager.get_meeting_dir(&result.meeting_id) {
+            Ok(meeting_dir) => {
+                update_summary_meta(&meeting_dir, |meta| meta.last_result = Some(result.clone()))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = saved {
+            println!("⚠️  Failed to record summary result: {}", e);
+        }
+        self.events
+            .emit_terminal("summarization-finished", result)
+            .await;
+    }
+
+    pub async fn generate_summary(&self, meeting_id: &str) -> LlmResult<SummaryResult> {
+        println!("🚀 Starting full meeting summary generation...");
 
         let transcript = get_meeting_transcript(self.app_handle.clone(), meeting_id)
             .await
             .map_err(|e| LlmError::FileError(format!("Failed to get transcript: {}", e)))?;
 
-        if transcript.is_empty() {
-            return Err(LlmError::FileError(
-                "No transcript to summarize".to_string(),
-            ));
-        }
+        // Validate before claiming the summarization slot, nothing to clean up on rejection
+        let config = self.get_llm_config().await?;
+        let transcript = validate_transcript(&transcript, config.min_transcript_chars)?;
+        self.ensure_summary_space(meeting_id)?;
+        self.ensure_summary_replaceable(meeting_id).await?;
 
-        let content = if transcript.len() > 10_000 {
-            self.summarize_long_transcript(&transcript, meeting_id)
-                .await?
-        } else {
-            return Err(LlmError::ConfigError(
-                "Direct summarization not implemented yet".to_string(),
-            ));
-        };
+        // Check if another summarization is running
+        self.check_and_set_summarization_state(meeting_id).await?;
+        // Time spent waiting in the queue is not part of the run
+        self.performance.restart();
+
+        let result = self
+            .summarize_transcript(transcript, meeting_id, &config)
+            .await;
+        self.release_summarization(meeting_id, &result).await;
+        result
+    }
+
+    /// Everything of `generate_summary` that runs while it holds the summarization slot
+    async fn summarize_transcript(
+        &self,
+        transcript: &str,
+        meeting_id: &str,
+        config: &LlmConfig,
+    ) -> LlmResult<SummaryResult> {
+        let warnings = self.emit_transcription_warnings(meeting_id).await?;
+
+        // A short transcript is a single chunk
+        let (mut content, chunk_count, failed_chunks, reused_chunks) = self
+            .summarize_long_transcript(transcript, meeting_id)
+            .await?;
+        self.dedup_bullets(meeting_id, &mut content, config).await;
 
         // Save the summary
         self.file_manager
             .save_final_summary(meeting_id, &content)
             .await
             .map_err(|e| LlmError::FileError(e))?;
+        self.supersede_approval(meeting_id).await;
 
         self.file_manager
-            .save_meeting_metadata(meeting_id, content.title.to_string())
+            .save_meeting_metadata(meeting_id, &content.title)
+            .await
             .map_err(|e| LlmError::FileError(e))?;
 
-        let total_duration = summary_start_time.elapsed();
+        self.record_style(meeting_id).await;
+        self.extract_auto_tags(meeting_id, &content).await;
+
+        let metrics = self.performance.metrics();
+        self.record_metrics(meeting_id, &metrics).await;
+        let total_duration = metrics.total_duration;
         println!("🎉 Full meeting summary completed!");
-        println!(
-            "⏱️  Total summary generation time: {:.2}s",
-            total_duration.as_secs_f64()
+        self.performance.print_summary();
+        self.events.emit("summarization-metrics", &metrics);
+        self.events.emit(
+            "llm-progress",
+            format!(
+                "✅ Summary completed in {:.1}s",
+                total_duration.as_secs_f64()
+            ),
         );
 
-        self.app_handle
-            .emit(
+        let mut result =
+            SummaryResult::new(meeting_id, &content, total_duration, chunk_count, warnings);
+        result.failed_chunks = failed_chunks;
+        result.reused_chunks = reused_chunks;
+        result.failed_progress_events = self.events.failures();
+        self.record_result(&result).await;
+        Ok(result)
+    }
+
+    /// Optional keyword step after the summary was saved, never fails the summary
+    async fn extract_auto_tags(&self, meeting_id: &str, content: &FinalSummaryFormat) {
+        let config = match self.get_llm_config().await {
+            Ok(config) if config.extract_keywords => config,
+            _ => return,
+        };
+        let meeting_dir = match self.file_manager.get_meeting_dir(meeting_id) {
+            Ok(meeting_dir) => meeting_dir,
+            Err(e) => {
+                println!("⚠️  Skipping keyword extraction: {}", e);
+                return;
+            }
+        };
+
+        let llm_service = match self.llm_service(&config) {
+            Ok(llm_service) => llm_service,
+            Err(e) => {
+                println!("⚠️  Skipping keyword extraction: {}", e);
+                return;
+            }
+        };
+        update_auto_tags(
+            &llm_service,
+            &self.language,
+            &meeting_dir,
+            meeting_id,
+            content,
+        )
+        .await;
+    }
+
+    /// Surface warnings recorded during transcription before spending time on the summary
+    async fn emit_transcription_warnings(&self, meeting_id: &str) -> LlmResult<Vec<String>> {
+        let meeting_dir = self
+            .file_manager
+            .get_meeting_dir(meeting_id)
+            .map_err(LlmError::FileError)?;
+        let metadata = read_meeting_metadata(&meeting_dir)
+            .await
+            .map_err(LlmError::FileError)?;
+
+        let warnings: Vec<String> = metadata
+            .map(|m| m.transcription_warnings)
+            .unwrap_or_default()
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect();
+        for warning in &warnings {
+            println!("⚠️  Transcription warning: {}", warning);
+            self.events.emit(
                 "llm-progress",
-                &format!(
-                    "✅ Summary completed in {:.1}s",
-                    total_duration.as_secs_f64()
-                ),
-            )
-            .map_err(|e| LlmError::NetworkError(format!("Failed to emit progress: {}", e)))?;
+                format!("⚠️ Transcript warning: {}", warning),
+            );
+        }
 
-        Ok(content.to_markdown())
+        Ok(warnings)
+    }
+
+    /// How the saved chunk summaries differ from a new run over the current
+    /// transcript, `None` when they fit or can't be checked
+    async fn stale_chunks(&self, meeting_id: &str, config: &LlmConfig) -> Option<StaleChunks> {
+        let meeting_dir = self.file_manager.get_meeting_dir(meeting_id).ok()?;
+        let transcript = get_meeting_transcript(self.app_handle.clone(), meeting_id)
+            .await
+            .ok()?;
+        let transcript = transcript.trim();
+        stale_chunks(&meeting_dir, transcript, config.chunk_size_for(transcript)).await
     }
 
-    /// Regenerate only the final summary using existing chunk summaries
-    pub async fn regenerate_final_summary(&self, meeting_id: &str) -> LlmResult<String> {
+    /// Regenerate only the final summary using existing chunk summaries.
+    /// Chunk summaries that don't fit the current transcript or chunk size are
+    /// refused unless `allow_stale` is set.
+    pub async fn regenerate_final_summary(
+        &self,
+        meeting_id: &str,
+        allow_stale: bool,
+    ) -> LlmResult<String> {
         let summary_start_time = Instant::now();
         println!("🔄 Starting final summary regeneration from existing chunks...");
+        self.ensure_summary_space(meeting_id)?;
+        self.ensure_summary_replaceable(meeting_id).await?;
+
+        let config = self.get_llm_config().await?;
+        if let Some(stale) = self.stale_chunks(meeting_id, &config).await {
+            if !allow_stale {
+                return Err(stale.into());
+            }
+            println!("⚠️  Regenerating anyway: {}", stale);
+            self.events.emit(
+                "llm-progress",
+                "⚠️ Using chunk summaries of an older transcript or chunk size",
+            );
+        }
 
         // Check if another summarization is running
         self.check_and_set_summarization_state(meeting_id).await?;
 
+        let result = self.summarize_saved_chunks(meeting_id, &config).await;
+        self.release_summarization(meeting_id, &result).await;
+        let content = result?;
+
+        let total_duration = summary_start_time.elapsed();
+        println!("🎉 Final summary regeneration completed!");
+        println!(
+            "⏱️  Total regeneration time: {:.2}s",
+            total_duration.as_secs_f64()
+        );
+
+        self.events.emit(
+            "llm-progress",
+            format!(
+                "✅ Final summary regenerated in {:.1}s",
+                total_duration.as_secs_f64()
+            ),
+        );
+
+        Ok(content.to_markdown())
+    }
+
+    /// Everything of `regenerate_final_summary` that runs while it holds the summarization slot
+    async fn summarize_saved_chunks(
+        &self,
+        meeting_id: &str,
+        config: &LlmConfig,
+    ) -> LlmResult<FinalSummaryFormat> {
         // Read existing chunk summaries from disk
         let chunk_summaries = self
             .file_manager
@@ -104,102 +532,370 @@ impl SummaryGenerator {
             .map_err(|e| LlmError::FileError(format!("Failed to read chunk summaries: {}", e)))?;
 
         println!("📦 Found {} saved chunk summaries", chunk_summaries.len());
+        let missing_chunks = self.missing_chunks(meeting_id).await;
 
-        // Get LLM config
-        let config = self.get_llm_config().await?;
-        let llm_service = LlmService::new(config.external_endpoint, config.external_model);
+        let llm_service = self.llm_service(config)?;
 
         // Generate final summary from existing chunk summaries
-        let mut progress_tracker = ProgressTracker::new(self.app_handle.clone(), 1);
-        progress_tracker
-            .start_summarization(meeting_id)
-            .map_err(|e| LlmError::NetworkError(e))?;
-
-        let content = self
-            .generate_final_summary(chunk_summaries, &llm_service, &mut progress_tracker)
+        let mut progress_tracker = ProgressTracker::new(self.events.clone(), 1);
+        progress_tracker.start_summarization(meeting_id);
+
+        let mut content = self
+            .generate_final_summary(
+                chunk_summaries,
+                &missing_chunks,
+                &llm_service,
+                &mut progress_tracker,
+            )
             .await?;
+        self.dedup_bullets(meeting_id, &mut content, config).await;
 
         // Save the regenerated summary
         self.file_manager
             .save_final_summary(meeting_id, &content)
             .await
             .map_err(|e| LlmError::FileError(e))?;
+        self.supersede_approval(meeting_id).await;
 
         self.file_manager
-            .save_meeting_metadata(meeting_id, content.title.to_string())
+            .save_meeting_metadata(meeting_id, &content.title)
+            .await
             .map_err(|e| LlmError::FileError(e))?;
 
-        // Reset summarization state
-        {
-            let state = self.app_handle.state::<Mutex<AppState>>();
-            let mut state = state.lock().await;
-            state.currently_summarizing = None;
-        }
+        self.record_style(meeting_id).await;
+        self.extract_auto_tags(meeting_id, &content).await;
+        Ok(content)
+    }
+
+    /// Summarize only part of the meeting. The result goes to
+    /// `summary.partial.<key>.json/md`, the full summary and its chunks stay as they are.
+    pub async fn generate_partial_summary(
+        &self,
+        meeting_id: &str,
+        range: &SummaryRange,
+    ) -> LlmResult<String> {
+        let summary_start_time = Instant::now();
+        println!("🚀 Starting partial summary of {}...", range.describe());
+
+        let meeting_dir = self
+            .file_manager
+            .get_meeting_dir(meeting_id)
+            .map_err(LlmError::FileError)?;
+        let text = extract_range_text(&meeting_dir, meeting_id, range)
+            .await
+            .map_err(LlmError::FileError)?;
+
+        let config = self.get_llm_config().await?;
+        let text = validate_transcript(&text, config.min_transcript_chars)?;
+        self.ensure_summary_space(meeting_id)?;
+
+        self.check_and_set_summarization_state(meeting_id).await?;
+
+        let result = self.summarize_partial_text(text, meeting_id, &config).await;
+        self.release_summarization(meeting_id, &result).await;
+
+        let markdown = save_partial_summary(&meeting_dir, range, result?, Utc::now())
+            .await
+            .map_err(LlmError::FileError)?;
 
         let total_duration = summary_start_time.elapsed();
-        println!("🎉 Final summary regeneration completed!");
         println!(
-            "⏱️  Total regeneration time: {:.2}s",
+            "🎉 Partial summary completed in {:.2}s",
             total_duration.as_secs_f64()
         );
+        self.events.emit(
+            "llm-progress",
+            format!(
+                "✅ Partial summary completed in {:.1}s",
+                total_duration.as_secs_f64()
+            ),
+        );
 
-        self.app_handle
-            .emit(
-                "llm-progress",
-                &format!(
-                    "✅ Final summary regenerated in {:.1}s",
-                    total_duration.as_secs_f64()
-                ),
+        Ok(markdown)
+    }
+
+    /// Chunked pipeline without a run, so nothing of the full summary is replaced
+    async fn summarize_partial_text(
+        &self,
+        text: &str,
+        meeting_id: &str,
+        config: &LlmConfig,
+    ) -> LlmResult<FinalSummaryFormat> {
+        let llm_service = self.llm_service(config)?;
+        let chunks = split_text_into_chunks(text, config.chunk_size_for(text));
+        println!("📦 Split partial transcript into {} chunks", chunks.len());
+
+        // Missing parts are pointed out by the summary itself
+        let (mut summary, _, _) = self
+            .summarize_chunks(
+                &chunks,
+                None,
+                meeting_id,
+                &llm_service,
+                &HashMap::new(),
+                None,
             )
-            .map_err(|e| LlmError::NetworkError(format!("Failed to emit progress: {}", e)))?;
+            .await?;
+        dedup_bullets(&mut summary, &config.bullet_dedup);
+        Ok(summary)
+    }
 
-        Ok(content.to_markdown())
+    /// Re-summarize a single chunk, located through the chunk manifest
+    pub async fn regenerate_chunk_summary(
+        &self,
+        meeting_id: &str,
+        chunk_index: usize,
+    ) -> LlmResult<String> {
+        self.ensure_summary_space(meeting_id)?;
+        self.check_and_set_summarization_state(meeting_id).await?;
+
+        let result = self.summarize_manifest_chunk(meeting_id, chunk_index).await;
+        self.release_summarization(meeting_id, &result).await;
+        result
     }
 
-    async fn check_and_set_summarization_state(&self, meeting_id: &str) -> LlmResult<()> {
-        let state = self.app_handle.state::<Mutex<AppState>>();
-        let mut state = state.lock().await;
+    async fn summarize_manifest_chunk(
+        &self,
+        meeting_id: &str,
+        chunk_index: usize,
+    ) -> LlmResult<String> {
+        let meeting_dir = self
+            .file_manager
+            .get_meeting_dir(meeting_id)
+            .map_err(LlmError::FileError)?;
+        let manifest = read_manifest(&meeting_dir)
+            .await
+            .map_err(LlmError::FileError)?;
+        let entry = manifest
+            .text_chunks
+            .iter()
+            .find(|entry| entry.index == chunk_index)
+            .ok_or_else(|| {
+                LlmError::FileError(format!("Chunk {} is not in the manifest", chunk_index))
+            })?;
 
-        if state.currently_summarizing.is_some() {
-            return Err(LlmError::ConfigError(
-                "Another summarization is running".to_string(),
-            ));
+        let chunk = tokio::fs::read_to_string(meeting_dir.join(&entry.file))
+            .await
+            .map_err(|e| LlmError::FileError(format!("Failed to read chunk: {}", e)))?;
+
+        let config = self.get_llm_config().await?;
+        let llm_service = self.llm_service(&config)?;
+        let progress_tracker = ProgressTracker::new(self.events.clone(), 1);
+
+        let key_facts = self.authoritative_key_facts.clone().unwrap_or_default();
+        let chunk_summary = self
+            .process_chunk(
+                chunk_index,
+                &chunk,
+                &key_facts,
+                &llm_service,
+                &progress_tracker,
+            )
+            .await?;
+
+        let chunk_summary_json = serde_json::to_string_pretty(&chunk_summary).map_err(|e| {
+            LlmError::SerializationError(format!("Failed to serialize chunk summary: {}", e))
+        })?;
+        tokio::fs::write(meeting_dir.join(&entry.summary_file), &chunk_summary_json)
+            .await
+            .map_err(|e| LlmError::FileError(format!("Failed to save chunk summary: {}", e)))?;
+        let hash = chunk_hash(
+            &self.chunk_request(&chunk, &key_facts),
+            &self.attribution,
+            &config.external_model,
+        );
+        let hash_file = meeting_dir
+            .join(&entry.summary_file)
+            .with_file_name(chunk_hash_file_name(chunk_index));
+        if let Err(e) = write_atomic(&hash_file, hash).await {
+            println!("⚠️  Failed to save chunk hash: {}", e);
         }
 
-        state.currently_summarizing = Some(meeting_id.to_string());
+        // The chunk is no longer missing once the final summary is regenerated
+        let filled = update_summary_meta(&meeting_dir, |meta| {
+            if let Some(result) = &mut meta.last_result {
+                result
+                    .failed_chunks
+                    .retain(|failed| failed.chunk_index != chunk_index);
+            }
+        })
+        .await;
+        if let Err(e) = filled {
+            println!("⚠️  Failed to update missing chunks: {}", e);
+        }
 
-        self.app_handle
-            .emit("summarization-started", meeting_id)
-            .map_err(|e| {
-                LlmError::NetworkError(format!("Failed to emit summarization-started: {}", e))
-            })?;
+        Ok(chunk_summary_json)
+    }
 
+    /// Chunks of the current run that are still missing their summary
+    async fn missing_chunks(&self, meeting_id: &str) -> Vec<usize> {
+        let Ok(meeting_dir) = self.file_manager.get_meeting_dir(meeting_id) else {
+            return Vec::new();
+        };
+        read_summary_meta(&meeting_dir)
+            .await
+            .ok()
+            .and_then(|meta| meta.last_result)
+            .map(|result| missing_chunk_indices(&result.failed_chunks))
+            .unwrap_or_default()
+    }
+
+    /// Fail before the LLM is asked when the summary could not be written
+    fn ensure_summary_space(&self, meeting_id: &str) -> LlmResult<()> {
+        let meeting_dir = self
+            .file_manager
+            .get_meeting_dir(meeting_id)
+            .map_err(LlmError::FileError)?;
+        ensure_free_space(&SystemSpaceProbe, &meeting_dir, SUMMARY_SPACE_BYTES)?;
         Ok(())
     }
 
+    async fn check_and_set_summarization_state(&self, meeting_id: &str) -> LlmResult<()> {
+        let state = self.app_handle.state::<Mutex<AppState>>();
+        // Waits in the queue while another summarization or a transcription of the meeting runs
+        let mut state = claim_or_queue(&state, JobKind::Summarization, meeting_id).await?;
+        state.summarization_cancellation = Some(self.cancellation.clone());
+        state.summarization_performance = Some(self.performance.clone());
+
+        self.events.emit("summarization-started", meeting_id);
+
+        Ok(())
+    }
+
+    /// Give up the summarization slot and tell the frontend when the run was cancelled.
+    /// Chunk summaries written so far stay on disk for the next run.
+    async fn release_summarization<T>(&self, meeting_id: &str, result: &LlmResult<T>) {
+        {
+            let state = self.app_handle.state::<Mutex<AppState>>();
+            let mut state = state.lock().await;
+            state.release_summarization();
+        }
+
+        if matches!(result, Err(LlmError::Cancelled)) {
+            println!("⏹️  Summarization of {} cancelled", meeting_id);
+            self.events
+                .emit_terminal("summarization-cancelled", meeting_id)
+                .await;
+        }
+    }
+
     async fn summarize_long_transcript(
         &self,
         transcript: &str,
         meeting_id: &str,
-    ) -> LlmResult<FinalSummaryFormat> {
-        self.app_handle
-            .emit(
-                "llm-progress",
-                "📄 Transcript is long, splitting into chunks for processing...",
-            )
-            .map_err(|e| LlmError::NetworkError(format!("Failed to emit progress: {}", e)))?;
-
+    ) -> LlmResult<(FinalSummaryFormat, usize, Vec<FailedChunk>, usize)> {
         // Get LLM config
         let config = self.get_llm_config().await?;
-        let llm_service = LlmService::new(config.external_endpoint, config.external_model);
+        let llm_service = self.llm_service(&config)?;
+
+        let chunk_plan = config.chunk_plan(transcript);
+        let chunk_size = match &chunk_plan {
+            Some(plan) => {
+                println!(
+                    "📐 Chunk size {} ({:?}, configured {}, at least {} chunks)",
+                    plan.chunk_size, plan.reason, plan.configured_chunk_size, plan.expected_chunks
+                );
+                plan.chunk_size
+            }
+            None => config.chunk_size,
+        };
+        self.record_chunk_plan(meeting_id, chunk_plan).await;
 
         // Split transcript into manageable chunks
-        let chunks = split_text_into_chunks(transcript, config.chunk_size);
+        let chunks = split_text_into_chunks(transcript, chunk_size);
         println!("📦 Split transcript into {} chunks", chunks.len());
+        if chunks.len() > 1 {
+            self.events.emit(
+                "llm-progress",
+                "📄 Transcript is long, splitting into chunks for processing...",
+            );
+        }
+
+        let meeting_dir = self
+            .file_manager
+            .get_meeting_dir(meeting_id)
+            .map_err(LlmError::FileError)?;
+        // Read before the new run replaces the current one
+        let reusable = if self.force {
+            HashMap::new()
+        } else {
+            reusable_chunk_summaries(&meeting_dir).await
+        };
+
+        // A cancelled run over the same chunks keeps its chunk summaries
+        let run_manifest = RunManifest::new(transcript, chunk_size, chunks.len());
+        let run_id = match self
+            .file_manager
+            .resume_cancelled_run(meeting_id, &run_manifest)
+            .await
+        {
+            Some(run_id) => {
+                println!("♻️  Resuming cancelled run {}", run_id);
+                run_id
+            }
+            None => {
+                let run_id = self
+                    .file_manager
+                    .start_summarization_run(meeting_id)
+                    .await
+                    .map_err(LlmError::FileError)?;
+                // Without it the run's chunks can't be checked against later edits
+                if let Err(e) = self
+                    .file_manager
+                    .save_run_manifest(meeting_id, &run_id, &run_manifest)
+                    .await
+                {
+                    println!("⚠️  Failed to write run manifest: {}", e);
+                }
+                run_id
+            }
+        };
 
         // Summarize chunks and combine
-        self.summarize_chunks(chunks, meeting_id, &llm_service)
+        let mut job_state =
+            JobStateFile::start(&meeting_dir, JobKind::Summarization, chunks.len()).await;
+        job_state.set_run_id(&run_id).await;
+        let summarized = self
+            .summarize_chunks(
+                &chunks,
+                Some(&run_id),
+                meeting_id,
+                &llm_service,
+                &reusable,
+                Some(&mut job_state),
+            )
+            .await;
+        job_state.finish().await;
+        if matches!(summarized, Err(LlmError::Cancelled)) {
+            if let Err(e) = self
+                .file_manager
+                .mark_run_cancelled(meeting_id, &run_id)
+                .await
+            {
+                println!("⚠️  {}", e);
+            }
+        }
+        let (summary, failed_chunks, reused_chunks) = summarized?;
+        if !failed_chunks.is_empty() {
+            self.events.emit(
+                "llm-progress",
+                format!(
+                    "⚠️ {} of {} chunks failed and are missing from the summary",
+                    failed_chunks.len(),
+                    chunks.len()
+                ),
+            );
+        }
+
+        self.file_manager
+            .finish_summarization_run(meeting_id, &run_id)
             .await
+            .map_err(LlmError::FileError)?;
+        if let Err(e) = save_text_chunks(&meeting_dir, transcript, &chunks, &run_id).await {
+            println!("⚠️  Failed to write chunk manifest: {}", e);
+        }
+
+        Ok((summary, chunks.len(), failed_chunks, reused_chunks))
     }
 
     async fn get_llm_config(&self) -> LlmResult<LlmConfig> {
@@ -208,106 +904,265 @@ impl SummaryGenerator {
         Ok(state.llm_config.clone())
     }
 
+    /// Summarize each chunk and combine them. Chunks and their summaries are
+    /// only kept on disk with a `run_id`, partial summaries leave them alone.
+    /// Chunks with a summary in `reusable`, by `chunk_hash`, aren't sent to the LLM.
+    /// Returns the chunks skipped with `tolerate_chunk_failures` and how many
+    /// summaries were reused.
     async fn summarize_chunks(
         &self,
-        chunks: Vec<String>,
+        chunks: &[String],
+        run_id: Option<&str>,
         meeting_id: &str,
         llm_service: &LlmService,
-    ) -> LlmResult<FinalSummaryFormat> {
+        reusable: &HashMap<String, FirstSummaryFormat>,
+        mut job_state: Option<&mut JobStateFile>,
+    ) -> LlmResult<(FinalSummaryFormat, Vec<FailedChunk>, usize)> {
+        let config = self.get_llm_config().await?;
+        let mut failures = ChunkFailures::new(config.tolerate_chunk_failures);
         let mut chunk_summaries = Vec::new();
-        let mut chunk_times = Vec::new();
-        let mut key_facts = KeyFact {
-            responisible_for_moderation: None,
-            responisible_for_protocol: None,
-            responisible_for_timekeeping: None,
-            attendees: None,
-        };
+        let mut reused_chunks = 0;
+        let mut key_facts = self.authoritative_key_facts.clone().unwrap_or_default();
 
         let total_steps = chunks.len() + 1;
-        let mut progress_tracker = ProgressTracker::new(self.app_handle.clone(), total_steps);
-        progress_tracker
-            .start_summarization(meeting_id)
-            .map_err(|e| LlmError::NetworkError(e))?;
+        let mut progress_tracker = ProgressTracker::new(self.events.clone(), total_steps);
+        progress_tracker.start_summarization(meeting_id);
 
         // Process each chunk
         for (i, chunk) in chunks.iter().enumerate() {
+            if self.cancellation.is_cancelled() {
+                return Err(LlmError::Cancelled);
+            }
             let chunk_start_time = Instant::now();
 
-            progress_tracker
-                .update_progress(&format!("Summarizing chunk {} of {}", i + 1, chunks.len()))
-                .map_err(|e| LlmError::NetworkError(e))?;
-
-            let chunk_summary = self
-                .process_chunk(chunk, &key_facts, llm_service, &progress_tracker)
-                .await?;
+            progress_tracker.update_progress(&format!(
+                "Summarizing chunk {} of {}",
+                i + 1,
+                chunks.len()
+            ));
 
-            let chunk_duration = chunk_start_time.elapsed();
-            chunk_times.push(chunk_duration);
-            progress_tracker.log_chunk_completed(i, chunk_duration);
+            // Left by the cancelled run this one resumes
+            let saved = match run_id {
+                Some(run_id) => {
+                    self.file_manager
+                        .read_run_chunk_summary(meeting_id, run_id, i)
+                        .await
+                }
+                None => None,
+            };
+            if let Some(chunk_summary) = saved {
+                println!("♻️  Reusing the summary of chunk {}", i + 1);
+                if self.authoritative_key_facts.is_none() {
+                    self.update_key_facts(&mut key_facts, &chunk_summary);
+                }
+                chunk_summaries.push((i, chunk_summary));
+                reused_chunks += 1;
+                if let Some(job_state) = job_state.as_deref_mut() {
+                    job_state.complete_chunk().await;
+                }
+                continue;
+            }
 
-            // Update key facts from chunk summary
-            self.update_key_facts(&mut key_facts, &chunk_summary);
+            // Unchanged since the last run
+            let hash = chunk_hash(
+                &self.chunk_request(chunk, &key_facts),
+                &self.attribution,
+                &config.external_model,
+            );
+            let unchanged = reusable.get(&hash).cloned();
+            let is_reused = unchanged.is_some();
+            let result = match unchanged {
+                Some(chunk_summary) => {
+                    println!("♻️  Chunk {} is unchanged, reusing its summary", i + 1);
+                    reused_chunks += 1;
+                    Ok(chunk_summary)
+                }
+                None => {
+                    let estimate = expected_chunk_duration(&self.performance.chunk_times())
+                        .map(|expected| progress_tracker.begin_estimated_phase(expected));
+                    let result = self
+                        .process_chunk(i, chunk, &key_facts, llm_service, &progress_tracker)
+                        .await;
+                    drop(estimate);
+                    result
+                }
+            };
+
+            // The chunk itself is saved either way, so a failed one can be regenerated
+            if let Some(run_id) = run_id {
+                self.file_manager
+                    .save_chunk(meeting_id, run_id, i, chunk)
+                    .await
+                    .map_err(|e| LlmError::FileError(e))?;
+            }
+            if let Some(job_state) = job_state.as_deref_mut() {
+                job_state.complete_chunk().await;
+            }
+            let Some(chunk_summary) = failures.check(i, result)? else {
+                continue;
+            };
 
-            // Save chunk and summary
-            self.file_manager
-                .save_chunk(meeting_id, i, chunk)
-                .await
-                .map_err(|e| LlmError::FileError(e))?;
+            if !is_reused {
+                let chunk_duration = self.performance.record_chunk(chunk_start_time, chunk.len());
+                progress_tracker.log_chunk_completed(i, chunk_duration);
+            }
 
-            let chunk_summary_json = serde_json::to_string_pretty(&chunk_summary).map_err(|e| {
-                LlmError::SerializationError(format!("Failed to serialize chunk summary: {}", e))
-            })?;
+            // Update key facts from chunk summary, corrected ones stay as they are
+            if self.authoritative_key_facts.is_none() {
+                self.update_key_facts(&mut key_facts, &chunk_summary);
+            }
 
-            self.file_manager
-                .save_chunk_summary(meeting_id, i, &chunk_summary_json)
-                .await
-                .map_err(|e| LlmError::FileError(e))?;
+            // Save the summary
+            if let Some(run_id) = run_id {
+                let chunk_summary_json =
+                    serde_json::to_string_pretty(&chunk_summary).map_err(|e| {
+                        LlmError::SerializationError(format!(
+                            "Failed to serialize chunk summary: {}",
+                            e
+                        ))
+                    })?;
+
+                self.file_manager
+                    .save_chunk_summary(meeting_id, run_id, i, &chunk_summary_json)
+                    .await
+                    .map_err(|e| LlmError::FileError(e))?;
+                // Without it the next run summarizes the chunk again
+                if let Err(e) = self
+                    .file_manager
+                    .save_chunk_hash(meeting_id, run_id, i, &hash)
+                    .await
+                {
+                    println!("⚠️  {}", e);
+                }
+            }
 
-            chunk_summaries.push(chunk_summary);
+            chunk_summaries.push((i, chunk_summary));
         }
+        let failed_chunks = failures.finish(chunks.len())?;
 
         // Log timing statistics
-        progress_tracker
-            .log_timing_stats(&chunk_times)
-            .map_err(|e| LlmError::NetworkError(e))?;
+        progress_tracker.log_timing_stats(&self.performance.metrics());
 
         // Save all chunk summaries
-        let summary_strings: Vec<String> = chunk_summaries
-            .iter()
-            .map(|s| serde_json::to_string_pretty(s).unwrap_or_default())
-            .collect();
+        if let Some(run_id) = run_id {
+            // The ids the chunk summaries refer to, later renames can't change them
+            if let Err(e) = self
+                .file_manager
+                .save_key_facts_snapshot(meeting_id, run_id, &key_facts)
+                .await
+            {
+                println!("⚠️  Failed to save key facts snapshot: {}", e);
+            }
 
-        self.file_manager
-            .save_all_chunk_summaries(meeting_id, &summary_strings)
-            .await
-            .map_err(|e| LlmError::FileError(e))?;
+            let summary_strings: Vec<(usize, String)> = chunk_summaries
+                .iter()
+                .map(|(i, s)| (*i, serde_json::to_string_pretty(s).unwrap_or_default()))
+                .collect();
+
+            self.file_manager
+                .save_all_chunk_summaries(meeting_id, run_id, &summary_strings)
+                .await
+                .map_err(|e| LlmError::FileError(e))?;
+        }
 
         // Generate final summary
-        self.generate_final_summary(chunk_summaries, llm_service, &mut progress_tracker)
-            .await
+        if let Some(job_state) = job_state {
+            job_state.set_stage(JobStage::Combining).await;
+        }
+        let chunk_summaries = chunk_summaries
+            .into_iter()
+            .map(|(_, summary)| summary)
+            .collect();
+        let summary = self
+            .generate_final_summary(
+                chunk_summaries,
+                &missing_chunk_indices(&failed_chunks),
+                llm_service,
+                &mut progress_tracker,
+            )
+            .await?;
+        Ok((summary, failed_chunks, reused_chunks))
+    }
+
+    /// The prompts sent to summarize `chunk`
+    fn chunk_request(&self, chunk: &str, key_facts: &KeyFact) -> ChunkRequest {
+        ChunkRequest::new(
+            &self.language,
+            &self.style,
+            self.attribution.attribute_speakers,
+            chunk,
+            key_facts,
+        )
+        .with_authoritative_key_facts(&self.language, self.authoritative_key_facts.as_ref())
     }
 
     async fn process_chunk(
         &self,
+        chunk_index: usize,
         chunk: &str,
         key_facts: &KeyFact,
         llm_service: &LlmService,
         progress_tracker: &ProgressTracker,
     ) -> LlmResult<FirstSummaryFormat> {
-        let chunk_system_prompt =
-            PromptManager::chunk_summarization(&self.language, Some(key_facts));
-
-        let chunk_summary_json = llm_service
-            .generate_text(
-                &chunk_system_prompt,
-                chunk,
-                Some(schema_for!(FirstSummaryFormat)),
-                Some(progress_tracker),
-            )
-            .await?;
+        let request = self.chunk_request(chunk, key_facts);
+        println!(
+            "📏 Chunk prompt: system {} chars, user {} chars (~{} tokens)",
+            request.system_prompt.chars().count(),
+            request.user_prompt.chars().count(),
+            estimate_tokens(&request.system_prompt) + estimate_tokens(&request.user_prompt)
+        );
 
-        serde_json::from_str(&chunk_summary_json)
-            .map_err(|e| LlmError::ParseError(format!("Failed to parse chunk summary JSON: {}", e)))
+        // Even with the schema, models now and then cut the JSON short
+        let mut prompt = request.clone();
+        let mut attempt = 0;
+        let mut chunk_summary: FirstSummaryFormat = loop {
+            let chunk_summary_json = llm_service
+                .generate_text(
+                    &prompt.system_prompt,
+                    &prompt.user_prompt,
+                    Some(ChunkRequest::schema()),
+                    Some(progress_tracker),
+                )
+                .await?;
+            match serde_json::from_str(&chunk_summary_json) {
+                Ok(chunk_summary) => break chunk_summary,
+                Err(e) if attempt < llm_service.max_retries() => {
+                    attempt += 1;
+                    println!(
+                        "🔁 Chunk {} answered invalid JSON, asking again ({}/{}): {}",
+                        chunk_index + 1,
+                        attempt,
+                        llm_service.max_retries(),
+                        e
+                    );
+                    progress_tracker.emit_api_status(&format!(
+                        "🔁 Invalid JSON for chunk {}, asking again",
+                        chunk_index + 1
+                    ));
+                    prompt = request.with_invalid_answer(
+                        &self.language,
+                        &chunk_summary_json,
+                        &e.to_string(),
+                    );
+                }
+                Err(e) => {
+                    return Err(LlmError::ParseError(format!(
+                        "Failed to parse chunk summary JSON of chunk {} ({}) after {} attempts: {}",
+                        chunk_index + 1,
+                        chunk_file_name(chunk_index),
+                        attempt + 1,
+                        e
+                    )))
+                }
+            }
+        };
+        if let Some(authoritative) = &self.authoritative_key_facts {
+            chunk_summary.key_facts = authoritative.clone();
+        }
+        if !self.attribution.attribute_speakers {
+            anonymize_chunk_summary(&mut chunk_summary, key_facts, &self.attribution);
+        }
+        Ok(chunk_summary)
     }
 
     fn update_key_facts(&self, key_facts: &mut KeyFact, chunk_summary: &FirstSummaryFormat) {
@@ -342,34 +1197,63 @@ impl SummaryGenerator {
         }
     }
 
+    /// `missing_chunks` are the indices of chunks without a summary
     async fn generate_final_summary(
         &self,
         chunk_summaries: Vec<FirstSummaryFormat>,
+        missing_chunks: &[usize],
         llm_service: &LlmService,
         progress_tracker: &mut ProgressTracker,
     ) -> LlmResult<FinalSummaryFormat> {
         let final_summary_start_time = Instant::now();
 
-        progress_tracker
-            .update_progress("Combining chunk summaries into final summary...")
-            .map_err(|e| LlmError::NetworkError(e))?;
+        progress_tracker.update_progress("Combining chunk summaries into final summary...");
 
-        let final_system_prompt = PromptManager::final_summary(&self.language);
-        let combined_summaries = self.combine_structured_first_summaries(chunk_summaries);
+        let mut final_system_prompt = PromptManager::final_summary(
+            &self.language,
+            &self.style,
+            self.attribution.attribute_speakers,
+        );
+        if let Some(key_facts) = &self.authoritative_key_facts {
+            final_system_prompt.push_str(&PromptManager::authoritative_key_facts(
+                &self.language,
+                key_facts,
+            ));
+        }
+        if !missing_chunks.is_empty() {
+            final_system_prompt.push_str(&PromptManager::missing_chunks(
+                &self.language,
+                missing_chunks,
+                chunk_summaries.len() + missing_chunks.len(),
+            ));
+        }
+        let merge_config = self.get_llm_config().await?.topic_merge;
+        let combined_summaries =
+            self.combine_structured_first_summaries(chunk_summaries, &merge_config);
 
         let final_string = llm_service
             .generate_text(
-                final_system_prompt,
+                &final_system_prompt,
                 &json!(combined_summaries).to_string(),
                 Some(schema_for!(FinalSummaryFormat)),
                 Some(progress_tracker),
             )
             .await?;
 
-        let final_summary: FinalSummaryFormat =
+        let mut final_summary: FinalSummaryFormat =
             serde_json::from_str(&final_string).map_err(|e| {
                 LlmError::ParseError(format!("Failed to parse final summary JSON: {}", e))
             })?;
+        if let Some(authoritative) = &self.authoritative_key_facts {
+            final_summary.key_facts = authoritative.clone();
+        }
+        if !self.attribution.attribute_speakers {
+            anonymize_final_summary(
+                &mut final_summary,
+                &combined_summaries.key_facts,
+                &self.attribution,
+            );
+        }
 
         let final_summary_duration = final_summary_start_time.elapsed();
         println!(
@@ -383,6 +1267,7 @@ impl SummaryGenerator {
     fn combine_structured_first_summaries(
         &self,
         summaries: Vec<FirstSummaryFormat>,
+        merge_config: &TopicMergeConfig,
     ) -> FirstSummaryFormat {
         let mut combined = FirstSummaryFormat {
             key_facts: KeyFact {
@@ -445,18 +1330,94 @@ impl SummaryGenerator {
             }
         }
 
+        // The final call only has to polish clusters instead of finding them
+        let topic_count = combined.topics.len();
+        combined.topics = merge_topics(combined.topics, merge_config);
+        if combined.topics.len() < topic_count {
+            println!(
+                "🧩 Merged {} chunk topics into {}",
+                topic_count,
+                combined.topics.len()
+            );
+        }
+
         combined
     }
 }
 
+/// Generator with the language, summary style and speaker attribution configured
+/// for the meeting, once sending the meeting to the configured endpoint is allowed
+/// `authoritative_key_facts` is `None` for runs that use corrected key facts
+/// whenever there are some, regenerations ask for them explicitly
+async fn new_generator(
+    app: &AppHandle,
+    meeting_id: &str,
+    allow_remote: Option<bool>,
+    authoritative_key_facts: Option<bool>,
+) -> Result<SummaryGenerator, String> {
+    let config = {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        state.llm_config.clone()
+    };
+    ensure_endpoint_confirmed(app, &config, allow_remote.unwrap_or(false)).await?;
+
+    let meeting_dir = FileManager::new(app.clone()).get_meeting_dir(meeting_id)?;
+    let language = language_for(&meeting_dir, &config).await;
+    let generator = SummaryGenerator::new(app.clone(), language);
+    let style = summary_style_for(app, &meeting_dir).await;
+    let attribution = speaker_attribution_for(app, &meeting_dir).await;
+    let key_facts = match authoritative_key_facts {
+        None => authoritative_key_facts_for(&meeting_dir, false).await?,
+        Some(true) => authoritative_key_facts_for(&meeting_dir, true).await?,
+        Some(false) => None,
+    };
+    Ok(generator
+        .with_style(style)
+        .with_attribution(attribution)
+        .with_authoritative_key_facts(key_facts))
+}
+
 // Public API functions
+/// `force` summarizes every chunk again, also the ones unchanged since the last run
 #[tauri::command]
-pub async fn generate_summary(app: AppHandle, meeting_id: &str) -> Result<String, String> {
-    let generator = SummaryGenerator::new(app, Language::default());
-    generator
+pub async fn generate_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    allow_remote: Option<bool>,
+    supersede_approved: Option<bool>,
+    force: Option<bool>,
+) -> Result<SummaryResult, String> {
+    let generator = new_generator(&app, meeting_id, allow_remote, None)
+        .await?
+        .with_supersede_approved(supersede_approved.unwrap_or(false))
+        .with_force(force.unwrap_or(false));
+    let job_id = jobs::start_job(&app, JobKind::Summarization, meeting_id).await;
+    let result = generator
         .generate_summary(meeting_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string());
+    generator.record_usage(meeting_id).await;
+    jobs::finish_job(&app, &job_id).await;
+    result
+}
+
+#[tauri::command]
+pub async fn generate_partial_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    range: SummaryRange,
+    allow_remote: Option<bool>,
+) -> Result<String, String> {
+    let generator = new_generator(&app, meeting_id, allow_remote, None).await?;
+    let job_id = jobs::start_job(&app, JobKind::Summarization, meeting_id).await;
+    let result = generator
+        .generate_partial_summary(meeting_id, &range)
+        .await
+        .map_err(|e| e.to_string());
+    generator.record_usage(meeting_id).await;
+    jobs::finish_job(&app, &job_id).await;
+    result
 }
 
 #[tauri::command]
@@ -466,23 +1427,84 @@ pub async fn is_summarizing(app: AppHandle) -> Result<Option<String>, String> {
     Ok(state.currently_summarizing.clone())
 }
 
+/// Stop the running summarization of `meeting_id`. The run ends with the
+/// `summarization-cancelled` event, its saved chunk summaries are kept.
+#[tauri::command]
+pub async fn cancel_summarization(app: AppHandle, meeting_id: &str) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    state.cancel_summarization(meeting_id)
+}
+
 #[tauri::command]
-pub async fn get_meeting_summary(app: AppHandle, meeting_id: &str) -> Result<String, String> {
+pub async fn get_meeting_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    approved: Option<bool>,
+) -> Result<String, String> {
     let file_manager = FileManager::new(app);
+    if approved.unwrap_or(false) {
+        // The frozen markdown as approved, nothing is resolved or re-rendered
+        return read_approved_summary_in(&file_manager.get_meeting_dir(meeting_id)?).await;
+    }
     let summary = file_manager
         .read_summary(meeting_id)
         .await
         .map_err(|e| e.to_string())?;
-    Ok(summary.to_markdown())
+    let meeting_dir = file_manager.get_meeting_dir(meeting_id)?;
+    Ok(resolve_attendee_references_in(&meeting_dir, &summary.to_markdown()).await)
 }
 
 #[tauri::command]
-pub async fn regenerate_final_summary(app: AppHandle, meeting_id: &str) -> Result<String, String> {
-    let generator = SummaryGenerator::new(app, Language::default());
-    generator
-        .regenerate_final_summary(meeting_id)
+pub async fn regenerate_chunk_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    chunk_index: usize,
+    allow_remote: Option<bool>,
+    authoritative_key_facts: Option<bool>,
+) -> Result<String, String> {
+    let generator = new_generator(
+        &app,
+        meeting_id,
+        allow_remote,
+        Some(authoritative_key_facts.unwrap_or(false)),
+    )
+    .await?;
+    let job_id = jobs::start_job(&app, JobKind::Summarization, meeting_id).await;
+    let result = generator
+        .regenerate_chunk_summary(meeting_id, chunk_index)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string());
+    generator.record_usage(meeting_id).await;
+    jobs::finish_job(&app, &job_id).await;
+    result
+}
+
+#[tauri::command]
+pub async fn regenerate_final_summary(
+    app: AppHandle,
+    meeting_id: &str,
+    allow_remote: Option<bool>,
+    authoritative_key_facts: Option<bool>,
+    allow_stale: Option<bool>,
+    supersede_approved: Option<bool>,
+) -> Result<String, String> {
+    let generator = new_generator(
+        &app,
+        meeting_id,
+        allow_remote,
+        Some(authoritative_key_facts.unwrap_or(false)),
+    )
+    .await?
+    .with_supersede_approved(supersede_approved.unwrap_or(false));
+    let job_id = jobs::start_job(&app, JobKind::Summarization, meeting_id).await;
+    let result = generator
+        .regenerate_final_summary(meeting_id, allow_stale.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string());
+    generator.record_usage(meeting_id).await;
+    jobs::finish_job(&app, &job_id).await;
+    result
 }
 
 #[tauri::command]
@@ -498,18 +1520,16 @@ pub async fn test_llm_connection(app: AppHandle) -> Result<String, String> {
         state.llm_config.clone()
     };
 
-    let llm_service = LlmService::new(config.external_endpoint, config.external_model);
-    let progress_tracker = ProgressTracker::new(app.clone(), 1);
+    let llm_service =
+        LlmService::from_config(&config).map_err(|e| format!("Test failed: {}", e))?;
+    let events = ProgressEvents::new(app.clone());
+    let progress_tracker = ProgressTracker::new(events.clone(), 1);
 
-    progress_tracker
-        .emit_api_status("Starting LLM connection test...")
-        .map_err(|e| format!("Failed to emit progress: {}", e))?;
+    progress_tracker.emit_api_status("Starting LLM connection test...");
 
     // Reset progress indicators
-    app.emit("llm-download-progress", 0)
-        .map_err(|e| e.to_string())?;
-    app.emit("llm-loading-progress", 0)
-        .map_err(|e| e.to_string())?;
+    events.emit("llm-download-progress", 0);
+    events.emit("llm-loading-progress", 0);
 
     match llm_service
         .generate_text(
@@ -521,16 +1541,725 @@ pub async fn test_llm_connection(app: AppHandle) -> Result<String, String> {
         .await
     {
         Ok(response) => {
-            progress_tracker
-                .emit_api_status("LLM test completed successfully!")
-                .map_err(|e| format!("Failed to emit progress: {}", e))?;
+            progress_tracker.emit_api_status("LLM test completed successfully!");
             Ok(format!("Test successful! Response: {}", response.trim()))
         }
         Err(e) => {
-            progress_tracker
-                .emit_api_status(&format!("LLM test failed: {}", e))
-                .map_err(|e| format!("Failed to emit progress: {}", e))?;
+            progress_tracker.emit_api_status(&format!("LLM test failed: {}", e));
             Err(format!("Test failed: {}", e))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::approval::approve_summary_in;
+    use crate::llm::file_manager::{
+        chunk_file_name, chunk_summary_file_name, current_summarization_run_dir, read_summary_from,
+        summarization_dir,
+    };
+    use crate::llm::performance::read_metrics;
+    use crate::llm::progress::ProgressSink;
+    use crate::llm::test_harness::{read_fixture, FakeOllama, Reply, RequestKind, TestApp};
+    use crate::meeting::update_meeting_metadata;
+    use tauri::test::MockRuntime;
+
+    const MEETING: &str = "plenum-maerz";
+
+    fn chunk_replies() -> Vec<Reply> {
+        ["chunk_1.json", "chunk_2.json", "chunk_3.json"]
+            .into_iter()
+            .map(Reply::fixture)
+            .collect()
+    }
+
+    fn generator(app: &TestApp) -> SummaryGenerator<MockRuntime> {
+        SummaryGenerator::new(app.handle(), Language::default())
+    }
+
+    fn final_prompt(ollama: &FakeOllama) -> String {
+        ollama
+            .requests(RequestKind::FinalSummary)
+            .last()
+            .expect("no final summary was requested")
+            .prompt
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn test_summary_end_to_end() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+        assert_eq!(result.title, "🏠 Plenum März");
+        assert_eq!(result.chunk_count, 3);
+        assert!(result.failed_chunks.is_empty());
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 3);
+        assert!(final_prompt(&ollama).contains("🛠️ Renovierung Raum 3"));
+
+        // The repeated bullet of the answer is gone from both files
+        let summary = read_summary_from(&meeting_dir).await.unwrap();
+        assert_eq!(summary.topics[0].bullet_points.len(), 2);
+        let markdown = std::fs::read_to_string(meeting_dir.join("summary.md")).unwrap();
+        assert_eq!(markdown, summary.to_markdown());
+        assert_eq!(result.markdown, markdown);
+
+        let run_dir = current_summarization_run_dir(&meeting_dir).await.unwrap();
+        for i in 0..3 {
+            assert!(run_dir.join(chunk_file_name(i)).exists());
+            assert!(run_dir.join(chunk_summary_file_name(i)).exists());
+        }
+        let manifest = read_manifest(&meeting_dir).await.unwrap();
+        assert_eq!(manifest.text_chunks.len(), 3);
+
+        let meta = read_summary_meta(&meeting_dir).await.unwrap();
+        assert_eq!(meta.last_result.as_ref(), Some(&result));
+        assert_eq!(meta.removed_duplicate_bullets, Some(1));
+
+        // Three chunks and the final summary, 100 prompt and 50 completion tokens each
+        let metrics = read_metrics(&meeting_dir).await.unwrap();
+        assert_eq!(metrics.api_calls, 4);
+        assert_eq!(
+            (metrics.prompt_tokens, metrics.completion_tokens),
+            (400, 200)
+        );
+        assert_eq!(metrics.chunk_count, 3);
+        assert!(metrics.total_characters_processed > 10_000);
+        let sent = app.events("summarization-metrics");
+        assert_eq!(sent.len(), 1);
+        let sent: PerformanceMetrics = serde_json::from_str(&sent[0]).unwrap();
+        assert_eq!(
+            (sent.api_calls, sent.chunk_count, sent.completion_tokens),
+            (4, 3, 200)
+        );
+        let metadata = read_meeting_metadata(&meeting_dir).await.unwrap().unwrap();
+        assert_eq!(metadata.emoji.as_deref(), Some("🏠"));
+
+        assert_eq!(app.events("summarization-started")[0], "\"plenum-maerz\"");
+        assert_eq!(app.events("summarization-chunk-start"), vec!["4"]);
+        assert_eq!(
+            app.events("summarization-chunk-progress"),
+            vec!["0", "1", "2", "3"]
+        );
+        // One streamed line per answer, each is a first piece for its step
+        let tokens = app.events("llm-token");
+        assert_eq!(tokens.len(), 4);
+        assert!(tokens[3].starts_with(r#"{"chunk_index":3,"#));
+        let finished = app.events("summarization-finished");
+        assert_eq!(finished.len(), 1);
+        assert_eq!(
+            serde_json::from_str::<SummaryResult>(&finished[0]).unwrap(),
+            result
+        );
+        assert!(app
+            .events("llm-progress")
+            .last()
+            .unwrap()
+            .contains("Summary completed"));
+        assert_eq!(app.currently_summarizing().await, None);
+    }
+
+    /// Like a window that was closed during the run
+    struct ClosedWindow;
+
+    impl ProgressSink for ClosedWindow {
+        fn send(&self, _event: &str, _payload: serde_json::Value) -> Result<(), String> {
+            Err("window closed".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_short_transcript_is_one_chunk() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, [Reply::fixture("chunk_1.json")]);
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+        let transcript_path = meeting_dir.join(format!("{}.txt", MEETING));
+        let transcript = std::fs::read_to_string(&transcript_path).unwrap();
+        let short: String = transcript.chars().take(3_000).collect();
+        std::fs::write(&transcript_path, &short).unwrap();
+
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+        assert_eq!(result.chunk_count, 1);
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 1);
+        assert!(ollama.requests(RequestKind::Chunk)[0]
+            .prompt
+            .contains(short.trim()));
+        assert!(meeting_dir.join("summary.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_queue_wait_is_not_timed() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        app.add_meeting(MEETING);
+        let handle = app.handle();
+        let state = handle.state::<Mutex<AppState>>();
+        state.lock().await.claim_summarization("other").unwrap();
+
+        let summary = tokio::spawn({
+            let generator = generator(&app);
+            async move { generator.generate_summary(MEETING).await }
+        });
+        while state.lock().await.job_queue.jobs().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let waited = std::time::Duration::from_secs(2);
+        tokio::time::sleep(waited).await;
+        {
+            let mut state = state.lock().await;
+            state.release_summarization();
+            state.start_queued_jobs();
+        }
+
+        let result = summary.await.unwrap().unwrap();
+        assert!(
+            result.duration_seconds < waited.as_secs_f64(),
+            "{}",
+            result.duration_seconds
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failing_progress_events_dont_abort_the_summary() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let result = generator(&app)
+            .with_progress_sink(ClosedWindow)
+            .generate_summary(MEETING)
+            .await
+            .unwrap();
+        assert_eq!(result.title, "🏠 Plenum März");
+        assert!(meeting_dir.join("summary.json").exists());
+        // Started, chunk start, 4 steps with their messages, API status per call, ...
+        assert!(
+            result.failed_progress_events > 10,
+            "{}",
+            result.failed_progress_events
+        );
+        let meta = read_summary_meta(&meeting_dir).await.unwrap();
+        assert_eq!(meta.last_result, Some(result));
+        assert_eq!(app.currently_summarizing().await, None);
+    }
+
+    /// Cancels the run when the second chunk starts, like the cancel button
+    struct CancelOnSecondChunk {
+        cancellation: CancellationToken,
+        events: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl ProgressSink for CancelOnSecondChunk {
+        fn send(&self, event: &str, payload: serde_json::Value) -> Result<(), String> {
+            if event == "summarization-chunk-progress" && payload == 1 {
+                self.cancellation.cancel();
+            }
+            self.events.lock().unwrap().push(event.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_summary_keeps_finished_chunks() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let cancellation = CancellationToken::default();
+        let events = std::sync::Arc::default();
+        let sink = CancelOnSecondChunk {
+            cancellation: cancellation.clone(),
+            events: std::sync::Arc::clone(&events),
+        };
+        let error = generator(&app)
+            .with_cancellation(cancellation)
+            .with_progress_sink(sink)
+            .generate_summary(MEETING)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, LlmError::Cancelled), "{}", error);
+
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 1);
+        assert!(ollama.requests(RequestKind::FinalSummary).is_empty());
+        assert!(!meeting_dir.join("summary.json").exists());
+        // Not complete, so not the current run
+        assert!(current_summarization_run_dir(&meeting_dir).await.is_none());
+        let run_dir = std::fs::read_dir(summarization_dir(&meeting_dir))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        assert!(run_dir.join(chunk_summary_file_name(0)).exists());
+        assert!(!run_dir.join(chunk_summary_file_name(1)).exists());
+
+        let events: Vec<String> = events.lock().unwrap().clone();
+        assert_eq!(
+            events.last().map(String::as_str),
+            Some("summarization-cancelled")
+        );
+        assert!(!events.iter().any(|event| event == "summarization-finished"));
+        assert_eq!(app.currently_summarizing().await, None);
+        // Nothing left to cancel
+        let handle = app.handle();
+        let state = handle.state::<Mutex<AppState>>();
+        assert!(state.lock().await.cancel_summarization(MEETING).is_err());
+
+        // The next run picks up after the first chunk
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+        assert_eq!(result.title, "🏠 Plenum März");
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 3);
+        assert_eq!(
+            current_summarization_run_dir(&meeting_dir).await.as_ref(),
+            Some(&run_dir)
+        );
+        assert!(!run_dir.join("cancelled").exists());
+    }
+
+    #[tokio::test]
+    async fn test_failing_chunk() {
+        let failing_chunks = || {
+            let mut replies = chunk_replies();
+            replies[1] = Reply::Error(500, "llama runner process has terminated".to_string());
+            replies
+        };
+
+        // Skipped and reported when failures are tolerated
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, failing_chunks());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(LlmConfig {
+            tolerate_chunk_failures: true,
+            ..ollama.config()
+        });
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+        assert_eq!(
+            result.failed_chunks,
+            vec![FailedChunk {
+                chunk_index: 1,
+                error: "Server error: Ollama returned 500: llama runner process has terminated"
+                    .to_string(),
+            }]
+        );
+        assert!(
+            final_prompt(&ollama).contains(&PromptManager::missing_chunks(
+                &Language::default(),
+                &[1],
+                3
+            ))
+        );
+        // The chunk is kept for a regeneration, its summary is missing
+        let run_dir = current_summarization_run_dir(&meeting_dir).await.unwrap();
+        assert!(run_dir.join(chunk_file_name(1)).exists());
+        assert!(!run_dir.join(chunk_summary_file_name(1)).exists());
+        assert!(app
+            .events("llm-progress")
+            .iter()
+            .any(|message| message.contains("1 of 3 chunks failed")));
+
+        // Otherwise the run stops at the chunk and leaves no summary behind
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, failing_chunks());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let error = generator(&app).generate_summary(MEETING).await.unwrap_err();
+        assert!(matches!(error, LlmError::ServerError(_)));
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 2);
+        assert!(ollama.requests(RequestKind::FinalSummary).is_empty());
+        assert!(!meeting_dir.join("summary.json").exists());
+        assert!(app.events("summarization-finished").is_empty());
+        assert_eq!(app.currently_summarizing().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_rerun_repairs_unparsable_answer() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        // Cut off in the middle, like a model that hit its output limit
+        let truncated = Reply::Text(r#"{"title": {"emoji": "🏠", "text": "Plenum"#.to_string());
+        ollama.reply(RequestKind::FinalSummary, [truncated]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let error = generator(&app).generate_summary(MEETING).await.unwrap_err();
+        assert!(matches!(error, LlmError::ParseError(_)));
+        assert!(!meeting_dir.join("summary.json").exists());
+        assert!(current_summarization_run_dir(&meeting_dir).await.is_none());
+        assert_eq!(app.currently_summarizing().await, None);
+
+        ollama.clear(RequestKind::Chunk);
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.clear(RequestKind::FinalSummary);
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+
+        assert_eq!(result.title, "🏠 Plenum März");
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 6);
+        assert_eq!(
+            read_summary_from(&meeting_dir).await.unwrap().title.text,
+            "Plenum März"
+        );
+        // The unfinished run of the failed attempt is gone
+        let runs = std::fs::read_dir(summarization_dir(&meeting_dir))
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().path().is_dir())
+            .count();
+        assert_eq!(runs, 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_chunk_json_is_sent_back_to_the_model() {
+        let ollama = FakeOllama::start().await;
+        let truncated = r#"{"title": {"emoji": "🏠", "text": "Plenum"#;
+        let mut replies = vec![Reply::Text(truncated.to_string())];
+        replies.extend(chunk_replies());
+        ollama.reply(RequestKind::Chunk, replies);
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(LlmConfig {
+            max_retries: 1,
+            ..ollama.config()
+        });
+        app.add_meeting(MEETING);
+
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+
+        assert_eq!(result.chunk_count, 3);
+        assert!(result.failed_chunks.is_empty());
+        let requests = ollama.requests(RequestKind::Chunk);
+        assert_eq!(requests.len(), 4);
+        assert!(!requests[0].prompt.contains("kein gültiges JSON"));
+        assert!(requests[1].prompt.contains("kein gültiges JSON"));
+        assert!(requests[1].prompt.contains(truncated));
+        assert!(requests[1].prompt.starts_with(&requests[0].prompt));
+    }
+
+    #[tokio::test]
+    async fn test_chunk_json_failure_names_the_chunk() {
+        let ollama = FakeOllama::start().await;
+        let mut replies = chunk_replies();
+        replies.insert(1, Reply::Text("Hier ist die Zusammenfassung:".to_string()));
+        replies.insert(1, Reply::Text("{\"title\": ".to_string()));
+        ollama.reply(RequestKind::Chunk, replies);
+        let app = TestApp::new(LlmConfig {
+            max_retries: 1,
+            ..ollama.config()
+        });
+        app.add_meeting(MEETING);
+
+        let error = generator(&app).generate_summary(MEETING).await.unwrap_err();
+
+        let LlmError::ParseError(message) = error else {
+            panic!("expected a parse error, got {}", error);
+        };
+        assert!(
+            message.contains("chunk 2 (chunk_002.txt) after 2 attempts"),
+            "{}",
+            message
+        );
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_meeting_language_overrides_the_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let english = LlmConfig {
+            language: Language::English,
+            ..LlmConfig::default()
+        };
+        // Meetings and configs without a language keep German summaries
+        assert_eq!(
+            language_for(dir.path(), &LlmConfig::default()).await,
+            Language::German
+        );
+        assert_eq!(language_for(dir.path(), &english).await, Language::English);
+
+        update_meeting_metadata(dir.path(), MEETING, |metadata| {
+            metadata.language = Some(Language::German)
+        })
+        .await
+        .unwrap();
+        assert_eq!(language_for(dir.path(), &english).await, Language::German);
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_chunks_are_reused() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+        generator(&app).generate_summary(MEETING).await.unwrap();
+        let first_prompt = final_prompt(&ollama);
+
+        // Only the final summary is asked for again
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+        assert_eq!(result.reused_chunks, 3);
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 3);
+        assert_eq!(final_prompt(&ollama), first_prompt);
+        // The new run keeps the hashes for the one after it
+        assert_eq!(reusable_chunk_summaries(&meeting_dir).await.len(), 3);
+
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let result = generator(&app)
+            .with_force(true)
+            .generate_summary(MEETING)
+            .await
+            .unwrap();
+        assert_eq!(result.reused_chunks, 0);
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_chunks_are_summarized_again_when_attribution_changes() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        app.add_meeting(MEETING);
+        generator(&app).generate_summary(MEETING).await.unwrap();
+
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let result = generator(&app)
+            .with_attribution(SpeakerAttribution {
+                attribute_speakers: false,
+                ..SpeakerAttribution::default()
+            })
+            .generate_summary(MEETING)
+            .await
+            .unwrap();
+        assert_eq!(result.reused_chunks, 0);
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_failed_chunk() {
+        let ollama = FakeOllama::start().await;
+        // The answer for the second chunk comes last, for its regeneration
+        ollama.reply(
+            RequestKind::Chunk,
+            [
+                Reply::fixture("chunk_1.json"),
+                Reply::Error(500, "out of memory".to_string()),
+                Reply::fixture("chunk_3.json"),
+                Reply::fixture("chunk_2.json"),
+            ],
+        );
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(LlmConfig {
+            tolerate_chunk_failures: true,
+            ..ollama.config()
+        });
+        let meeting_dir = app.add_meeting(MEETING);
+
+        let result = generator(&app).generate_summary(MEETING).await.unwrap();
+        assert_eq!(missing_chunk_indices(&result.failed_chunks), vec![1]);
+
+        let chunk_summary = generator(&app)
+            .regenerate_chunk_summary(MEETING, 1)
+            .await
+            .unwrap();
+        assert!(chunk_summary.contains("🎉 Sommerfest"));
+        let run_dir = current_summarization_run_dir(&meeting_dir).await.unwrap();
+        assert!(run_dir.join(chunk_summary_file_name(1)).exists());
+        let meta = read_summary_meta(&meeting_dir).await.unwrap();
+        assert!(meta.last_result.unwrap().failed_chunks.is_empty());
+
+        generator(&app)
+            .regenerate_final_summary(MEETING, false)
+            .await
+            .unwrap();
+        let prompt = final_prompt(&ollama);
+        assert!(prompt.contains("🎉 Sommerfest"));
+        assert!(!prompt.contains(&PromptManager::missing_chunks(
+            &Language::default(),
+            &[1],
+            3
+        )));
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 4);
+        assert_eq!(app.currently_summarizing().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_final_summary_refuses_stale_chunks() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+        generator(&app).generate_summary(MEETING).await.unwrap();
+        let run_dir = current_summarization_run_dir(&meeting_dir).await.unwrap();
+        assert!(run_dir.join("manifest.json").exists());
+        assert!(run_dir.join("key_facts_snapshot.json").exists());
+
+        // The transcript was corrected after the summary
+        let transcript_path = meeting_dir.join(format!("{}.txt", MEETING));
+        let transcript = std::fs::read_to_string(&transcript_path).unwrap();
+        std::fs::write(
+            &transcript_path,
+            transcript.replacen("Plenum", "Treffen", 1),
+        )
+        .unwrap();
+
+        let error = generator(&app)
+            .regenerate_final_summary(MEETING, false)
+            .await
+            .unwrap_err();
+        let LlmError::StaleChunks(stale) = error else {
+            panic!("expected stale chunks, got {:?}", error);
+        };
+        assert!(stale.transcript_changed);
+        assert_eq!(stale.run_chunk_size, stale.current_chunk_size);
+        assert_eq!(ollama.requests(RequestKind::FinalSummary).len(), 1);
+        assert_eq!(app.currently_summarizing().await, None);
+
+        // Back to the summarized transcript, but with another chunk size
+        std::fs::write(&transcript_path, &transcript).unwrap();
+        {
+            let handle = app.handle();
+            let state = handle.state::<Mutex<AppState>>();
+            state.lock().await.llm_config.chunk_size = 6_000;
+        }
+        let Err(LlmError::StaleChunks(stale)) = generator(&app)
+            .regenerate_final_summary(MEETING, false)
+            .await
+        else {
+            panic!("expected stale chunks");
+        };
+        assert!(!stale.transcript_changed);
+        assert_eq!(
+            (stale.run_chunk_size, stale.current_chunk_size),
+            (4_000, 6_000)
+        );
+
+        generator(&app)
+            .regenerate_final_summary(MEETING, true)
+            .await
+            .unwrap();
+        assert_eq!(ollama.requests(RequestKind::FinalSummary).len(), 2);
+        assert!(app
+            .events("llm-progress")
+            .iter()
+            .any(|event| event.contains("older transcript or chunk size")));
+        assert_eq!(app.currently_summarizing().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_approved_summary_needs_superseding() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+        generator(&app).generate_summary(MEETING).await.unwrap();
+        let approved_markdown = std::fs::read_to_string(meeting_dir.join("summary.md")).unwrap();
+        approve_summary_in(&meeting_dir, MEETING, "Anna")
+            .await
+            .unwrap();
+
+        let error = generator(&app)
+            .regenerate_final_summary(MEETING, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, LlmError::SummaryApproved(_)), "{:?}", error);
+        let error = generator(&app).generate_summary(MEETING).await.unwrap_err();
+        assert!(matches!(error, LlmError::SummaryApproved(_)), "{:?}", error);
+        assert_eq!(ollama.requests(RequestKind::FinalSummary).len(), 1);
+        assert_eq!(app.currently_summarizing().await, None);
+
+        let mut revised: FinalSummaryFormat =
+            serde_json::from_str(&read_fixture("ollama/final.json")).unwrap();
+        revised.title.text = "Plenum im März".to_string();
+        ollama.clear(RequestKind::FinalSummary);
+        ollama.reply(
+            RequestKind::FinalSummary,
+            [Reply::Text(serde_json::to_string(&revised).unwrap())],
+        );
+        generator(&app)
+            .with_supersede_approved(true)
+            .regenerate_final_summary(MEETING, false)
+            .await
+            .unwrap();
+
+        let approval = read_meeting_metadata(&meeting_dir)
+            .await
+            .unwrap()
+            .unwrap()
+            .approved
+            .unwrap();
+        assert!(approval.superseded_at.is_some());
+        assert_eq!(
+            read_approved_summary_in(&meeting_dir).await.unwrap(),
+            approved_markdown
+        );
+        assert_ne!(
+            std::fs::read_to_string(meeting_dir.join("summary.md")).unwrap(),
+            approved_markdown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_final_summary_from_saved_chunks() {
+        let ollama = FakeOllama::start().await;
+        ollama.reply(RequestKind::Chunk, chunk_replies());
+        ollama.reply(RequestKind::FinalSummary, [Reply::fixture("final.json")]);
+        let app = TestApp::new(ollama.config());
+        let meeting_dir = app.add_meeting(MEETING);
+        generator(&app).generate_summary(MEETING).await.unwrap();
+
+        let mut revised: FinalSummaryFormat =
+            serde_json::from_str(&read_fixture("ollama/final.json")).unwrap();
+        revised.title.text = "Plenum im März".to_string();
+        ollama.clear(RequestKind::FinalSummary);
+        ollama.reply(
+            RequestKind::FinalSummary,
+            [Reply::Text(serde_json::to_string(&revised).unwrap())],
+        );
+
+        let markdown = generator(&app)
+            .regenerate_final_summary(MEETING, false)
+            .await
+            .unwrap();
+        assert_eq!(ollama.requests(RequestKind::Chunk).len(), 3);
+        assert_eq!(ollama.requests(RequestKind::FinalSummary).len(), 2);
+        // The saved chunk summaries are sent again
+        let prompts: Vec<String> = ollama
+            .requests(RequestKind::FinalSummary)
+            .into_iter()
+            .map(|request| request.prompt)
+            .collect();
+        assert_eq!(prompts[0], prompts[1]);
+
+        let summary = read_summary_from(&meeting_dir).await.unwrap();
+        assert_eq!(summary.title.text, "Plenum im März");
+        assert_eq!(
+            std::fs::read_to_string(meeting_dir.join("summary.md")).unwrap(),
+            markdown
+        );
+        let versions = FileManager::new(app.handle())
+            .list_summary_versions(MEETING)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(app.currently_summarizing().await, None);
+    }
+}
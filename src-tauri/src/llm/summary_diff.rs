@@ -0,0 +1,497 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+use crate::llm::{
+    file_manager::FileManager,
+    models::{FinalSummaryFormat, KeyFact, ToDo, Topic},
+};
+
+/// Minimum title or bullet point similarity for two topics to count as the same topic
+const TOPIC_MATCH_THRESHOLD: f64 = 0.4;
+
+/// Structured difference between two versions of a final summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryDiff {
+    pub title: Option<ValueChange>,
+    pub summary_changed: bool,
+    pub topics: Vec<TopicChange>,
+    pub todos: TodoDiff,
+    pub key_facts: KeyFactDiff,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueChange {
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum TopicChange {
+    Added {
+        title: String,
+        bullet_points: Vec<String>,
+    },
+    Removed {
+        title: String,
+        bullet_points: Vec<String>,
+    },
+    /// Matched topic whose title and/or bullet points changed
+    Modified {
+        old_title: String,
+        new_title: String,
+        added_bullet_points: Vec<String>,
+        removed_bullet_points: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TodoDiff {
+    pub added: Vec<ToDo>,
+    pub removed: Vec<ToDo>,
+    pub assignees_changed: Vec<AssigneeChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AssigneeChange {
+    pub task: String,
+    pub old_assignees: Vec<String>,
+    pub new_assignees: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct KeyFactDiff {
+    pub moderation: Option<ValueChange>,
+    pub protocol: Option<ValueChange>,
+    pub timekeeping: Option<ValueChange>,
+    pub attendees_added: Vec<String>,
+    pub attendees_removed: Vec<String>,
+}
+
+pub fn diff_summary(old: &FinalSummaryFormat, new: &FinalSummaryFormat) -> SummaryDiff {
+    SummaryDiff {
+        title: value_change(Some(&old.title.to_string()), Some(&new.title.to_string())),
+        summary_changed: normalize(&old.summary) != normalize(&new.summary),
+        topics: diff_topics(&old.topics, &new.topics),
+        todos: diff_todos(&old.todos, &new.todos),
+        key_facts: diff_key_facts(&old.key_facts, &new.key_facts),
+    }
+}
+
+/// Match topics by title or bullet similarity (so retitled topics are still
+/// recognised by their content), then diff the bullet points of matched pairs.
+///
+/// Matching is greedy on the highest score with ties broken by position, so the
+/// result only depends on the input. Changes are listed in the order of the new
+/// version, followed by removed topics in the order of the old one.
+pub fn diff_topics(old: &[Topic], new: &[Topic]) -> Vec<TopicChange> {
+    let mut candidates = Vec::new();
+    for (i, old_topic) in old.iter().enumerate() {
+        for (j, new_topic) in new.iter().enumerate() {
+            let score = topic_similarity(old_topic, new_topic);
+            if score >= TOPIC_MATCH_THRESHOLD {
+                candidates.push((score, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut old_match: Vec<Option<usize>> = vec![None; old.len()];
+    let mut new_match: Vec<Option<usize>> = vec![None; new.len()];
+    for (_, i, j) in candidates {
+        if old_match[i].is_none() && new_match[j].is_none() {
+            old_match[i] = Some(j);
+            new_match[j] = Some(i);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (j, new_topic) in new.iter().enumerate() {
+        match new_match[j] {
+            Some(i) => {
+                let old_topic = &old[i];
+                let added = set_difference(&new_topic.bullet_points, &old_topic.bullet_points);
+                let removed = set_difference(&old_topic.bullet_points, &new_topic.bullet_points);
+                let retitled = normalize(&old_topic.title) != normalize(&new_topic.title);
+
+                if retitled || !added.is_empty() || !removed.is_empty() {
+                    changes.push(TopicChange::Modified {
+                        old_title: old_topic.title.clone(),
+                        new_title: new_topic.title.clone(),
+                        added_bullet_points: added,
+                        removed_bullet_points: removed,
+                    });
+                }
+            }
+            None => changes.push(TopicChange::Added {
+                title: new_topic.title.clone(),
+                bullet_points: new_topic.bullet_points.clone(),
+            }),
+        }
+    }
+    for (i, old_topic) in old.iter().enumerate() {
+        if old_match[i].is_none() {
+            changes.push(TopicChange::Removed {
+                title: old_topic.title.clone(),
+                bullet_points: old_topic.bullet_points.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+fn topic_similarity(a: &Topic, b: &Topic) -> f64 {
+    let title_similarity = jaccard(&word_set(&a.title), &word_set(&b.title));
+
+    let bullets_a: HashSet<String> = a.bullet_points.iter().map(|s| normalize(s)).collect();
+    let bullets_b: HashSet<String> = b.bullet_points.iter().map(|s| normalize(s)).collect();
+    let bullet_similarity = jaccard(&bullets_a, &bullets_b);
+
+    title_similarity.max(bullet_similarity)
+}
+
+pub fn diff_todos(old: &[ToDo], new: &[ToDo]) -> TodoDiff {
+    let find = |todos: &[ToDo], task: &str| {
+        todos
+            .iter()
+            .find(|todo| normalize(&todo.task) == task)
+            .cloned()
+    };
+
+    let mut diff = TodoDiff::default();
+    for todo in new {
+        match find(old, &normalize(&todo.task)) {
+            Some(old_todo) => {
+                let old_assignees = sorted_assignees(&old_todo);
+                let new_assignees = sorted_assignees(todo);
+                if old_assignees != new_assignees {
+                    diff.assignees_changed.push(AssigneeChange {
+                        task: todo.task.clone(),
+                        old_assignees,
+                        new_assignees,
+                    });
+                }
+            }
+            None => diff.added.push(todo.clone()),
+        }
+    }
+    for todo in old {
+        if find(new, &normalize(&todo.task)).is_none() {
+            diff.removed.push(todo.clone());
+        }
+    }
+
+    diff
+}
+
+fn sorted_assignees(todo: &ToDo) -> Vec<String> {
+    let mut assignees = todo.assignees.clone().unwrap_or_default();
+    assignees.sort();
+    assignees
+}
+
+pub fn diff_key_facts(old: &KeyFact, new: &KeyFact) -> KeyFactDiff {
+    let names = |key_fact: &KeyFact| -> Vec<String> {
+        key_fact
+            .attendees
+            .iter()
+            .flatten()
+            .map(|attendee| attendee.name.clone())
+            .collect()
+    };
+    let old_names = names(old);
+    let new_names = names(new);
+
+    KeyFactDiff {
+        moderation: value_change(
+            old.responisible_for_moderation.as_ref(),
+            new.responisible_for_moderation.as_ref(),
+        ),
+        protocol: value_change(
+            old.responisible_for_protocol.as_ref(),
+            new.responisible_for_protocol.as_ref(),
+        ),
+        timekeeping: value_change(
+            old.responisible_for_timekeeping.as_ref(),
+            new.responisible_for_timekeeping.as_ref(),
+        ),
+        attendees_added: set_difference(&new_names, &old_names),
+        attendees_removed: set_difference(&old_names, &new_names),
+    }
+}
+
+fn value_change(old: Option<&String>, new: Option<&String>) -> Option<ValueChange> {
+    let changed = old.map(|s| normalize(s)) != new.map(|s| normalize(s));
+    changed.then(|| ValueChange {
+        old: old.cloned(),
+        new: new.cloned(),
+    })
+}
+
+/// Items of `a` whose normalized text is not in `b`, in their original order
+fn set_difference(a: &[String], b: &[String]) -> Vec<String> {
+    let b: HashSet<String> = b.iter().map(|s| normalize(s)).collect();
+    let mut seen = HashSet::new();
+    a.iter()
+        .filter(|item| {
+            let key = normalize(item);
+            !b.contains(&key) && seen.insert(key)
+        })
+        .cloned()
+        .collect()
+}
+
+fn word_set(text: &str) -> HashSet<String> {
+    normalize(text)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    intersection as f64 / a.union(b).count() as f64
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[tauri::command]
+pub async fn list_summary_versions(
+    app: AppHandle,
+    meeting_id: &str,
+) -> Result<Vec<String>, String> {
+    FileManager::new(app)
+        .list_summary_versions(meeting_id)
+        .await
+}
+
+#[tauri::command]
+pub async fn diff_summaries(
+    app: AppHandle,
+    meeting_id: &str,
+    version_a: &str,
+    version_b: &str,
+) -> Result<SummaryDiff, String> {
+    let file_manager = FileManager::new(app);
+    let old = file_manager
+        .read_summary_version(meeting_id, version_a)
+        .await?;
+    let new = file_manager
+        .read_summary_version(meeting_id, version_b)
+        .await?;
+
+    Ok(diff_summary(&old, &new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models::{Attendee, Title};
+
+    fn topic(title: &str, bullets: &[&str]) -> Topic {
+        Topic {
+            title: title.to_string(),
+            bullet_points: bullets.iter().map(|s| s.to_string()).collect(),
+            sub_topics: None,
+        }
+    }
+
+    fn todo(task: &str, assignees: &[&str]) -> ToDo {
+        ToDo {
+            assignees: Some(assignees.iter().map(|s| s.to_string()).collect()),
+            task: task.to_string(),
+        }
+    }
+
+    fn key_facts(moderation: Option<&str>, attendees: &[&str]) -> KeyFact {
+        KeyFact {
+            responisible_for_moderation: moderation.map(str::to_string),
+            responisible_for_protocol: None,
+            responisible_for_timekeeping: None,
+            attendees: Some(
+                attendees
+                    .iter()
+                    .enumerate()
+                    .map(|(id, name)| Attendee {
+                        id,
+                        name: name.to_string(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn summary(topics: Vec<Topic>, todos: Vec<ToDo>) -> FinalSummaryFormat {
+        FinalSummaryFormat {
+            title: Title {
+                emoji: "📅".to_string(),
+                text: "Teamsitzung".to_string(),
+            },
+            key_facts: key_facts(Some("Anna"), &["Anna", "Jonas"]),
+            summary: "Kurze Zusammenfassung.".to_string(),
+            topics,
+            todos,
+        }
+    }
+
+    #[test]
+    fn test_identical_summaries_have_no_changes() {
+        let a = summary(
+            vec![topic("Budget", &["Budget steht fest"])],
+            vec![todo("Angebot einholen", &["Anna"])],
+        );
+        let diff = diff_summary(&a, &a.clone());
+
+        assert_eq!(diff.title, None);
+        assert!(!diff.summary_changed);
+        assert!(diff.topics.is_empty());
+        assert_eq!(diff.todos, TodoDiff::default());
+        assert_eq!(diff.key_facts, KeyFactDiff::default());
+    }
+
+    #[test]
+    fn test_reordered_topics_are_unchanged() {
+        let old = vec![
+            topic("Budget", &["Budget steht fest"]),
+            topic("Sommerfest", &["Termin im Juli"]),
+        ];
+        let new = vec![
+            topic("Sommerfest", &["Termin im Juli"]),
+            topic("Budget", &["Budget steht fest"]),
+        ];
+
+        assert!(diff_topics(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_renamed_topic_is_matched_by_bullets() {
+        let old = vec![
+            topic("Budget", &["Budget steht fest", "Mehr Geld für Marketing"]),
+            topic("Sommerfest", &["Termin im Juli"]),
+        ];
+        let new = vec![
+            topic("Sommerfest", &["Termin im Juli"]),
+            topic(
+                "Finanzplanung Q3",
+                &[
+                    "Budget steht fest",
+                    "Mehr Geld für Marketing",
+                    "Neue Stelle",
+                ],
+            ),
+        ];
+
+        assert_eq!(
+            diff_topics(&old, &new),
+            vec![TopicChange::Modified {
+                old_title: "Budget".to_string(),
+                new_title: "Finanzplanung Q3".to_string(),
+                added_bullet_points: vec!["Neue Stelle".to_string()],
+                removed_bullet_points: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed_topics() {
+        let old = vec![topic("Budget", &["Budget steht fest"])];
+        let new = vec![topic("Sommerfest", &["Termin im Juli"])];
+
+        assert_eq!(
+            diff_topics(&old, &new),
+            vec![
+                TopicChange::Added {
+                    title: "Sommerfest".to_string(),
+                    bullet_points: vec!["Termin im Juli".to_string()],
+                },
+                TopicChange::Removed {
+                    title: "Budget".to_string(),
+                    bullet_points: vec!["Budget steht fest".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bullet_comparison_ignores_case_and_punctuation() {
+        let old = vec![topic("Budget", &["Budget steht fest.", "Alt"])];
+        let new = vec![topic("Budget", &["budget steht fest", "Neu"])];
+
+        assert_eq!(
+            diff_topics(&old, &new),
+            vec![TopicChange::Modified {
+                old_title: "Budget".to_string(),
+                new_title: "Budget".to_string(),
+                added_bullet_points: vec!["Neu".to_string()],
+                removed_bullet_points: vec!["Alt".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_matching_is_deterministic_for_equal_scores() {
+        let old = vec![topic("Planung", &[]), topic("Planung", &[])];
+        let new = vec![topic("Planung", &["Neu"])];
+
+        let first = diff_topics(&old, &new);
+        assert_eq!(first, diff_topics(&old, &new));
+        // The earlier old topic wins the tie, the later one is removed
+        assert!(matches!(first.last(), Some(TopicChange::Removed { .. })));
+        assert_eq!(first.len(), 2);
+    }
+
+    #[test]
+    fn test_todo_changes() {
+        let old = vec![
+            todo("Angebot einholen", &["Anna"]),
+            todo("Raum buchen", &["Jonas"]),
+        ];
+        let new = vec![
+            todo("Angebot einholen", &["Jonas", "Anna"]),
+            todo("Protokoll verschicken", &["Anna"]),
+        ];
+
+        let diff = diff_todos(&old, &new);
+        assert_eq!(diff.added[0].task, "Protokoll verschicken");
+        assert_eq!(diff.removed[0].task, "Raum buchen");
+        assert_eq!(
+            diff.assignees_changed,
+            vec![AssigneeChange {
+                task: "Angebot einholen".to_string(),
+                old_assignees: vec!["Anna".to_string()],
+                new_assignees: vec!["Anna".to_string(), "Jonas".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_key_fact_changes() {
+        let old = key_facts(Some("Anna"), &["Anna", "Jonas"]);
+        let new = key_facts(Some("Jonas"), &["Jonas", "Mia"]);
+
+        let diff = diff_key_facts(&old, &new);
+        assert_eq!(
+            diff.moderation,
+            Some(ValueChange {
+                old: Some("Anna".to_string()),
+                new: Some("Jonas".to_string()),
+            })
+        );
+        assert_eq!(diff.protocol, None);
+        assert_eq!(diff.attendees_added, vec!["Mia".to_string()]);
+        assert_eq!(diff.attendees_removed, vec!["Anna".to_string()]);
+    }
+}
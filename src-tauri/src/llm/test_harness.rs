@@ -0,0 +1,319 @@
+//! End-to-end harness for the summarization pipeline: a fake Ollama serving
+//! canned answers, a mock app with its own app data directory, and the
+//! fixture meetings under `tests/fixtures/`.
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+use tauri::{App, AppHandle, Listener, Manager};
+use tempfile::TempDir;
+use tokio::task::JoinHandle;
+
+use crate::llm::config::{LlmConfig, API_CHAT_ENDPOINT, API_GENERATE_ENDPOINT};
+use crate::AppState;
+
+/// Events of a summarization run the frontend listens to
+const PIPELINE_EVENTS: &[&str] = &[
+    "summarization-started",
+    "summarization-chunk-start",
+    "summarization-chunk-progress",
+    "summarization-finished",
+    "summarization-metrics",
+    "llm-progress",
+    "llm-token",
+];
+
+pub fn fixture_path(relative: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(relative)
+}
+
+pub fn read_fixture(relative: &str) -> String {
+    std::fs::read_to_string(fixture_path(relative))
+        .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", relative, e))
+}
+
+/// Step of the pipeline a request belongs to, told apart by the title of the
+/// schema in `format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    Chunk,
+    FinalSummary,
+    /// Keywords, connection tests and everything else
+    Other,
+}
+
+impl RequestKind {
+    fn of(body: &Value) -> Self {
+        match body["format"]["title"].as_str() {
+            Some("FirstSummaryFormat") => RequestKind::Chunk,
+            Some("FinalSummaryFormat") => RequestKind::FinalSummary,
+            _ => RequestKind::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Reply {
+    /// The model answers with this text
+    Text(String),
+    /// Ollama fails with this status and `{"error": ...}`
+    Error(u16, String),
+}
+
+impl Reply {
+    /// Answer with a canned response from `tests/fixtures/ollama/`
+    pub fn fixture(name: &str) -> Self {
+        Reply::Text(read_fixture(&format!("ollama/{}", name)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub kind: RequestKind,
+    /// System and user prompt, one after the other
+    pub prompt: String,
+}
+
+#[derive(Default)]
+struct FakeState {
+    replies: HashMap<RequestKind, VecDeque<Reply>>,
+    requests: Vec<RecordedRequest>,
+}
+
+impl FakeState {
+    /// Replies are used up in order, the last one keeps being served
+    fn next_reply(&mut self, kind: RequestKind) -> Option<Reply> {
+        let queue = self.replies.get_mut(&kind)?;
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+    }
+}
+
+/// Ollama's `/api/chat` and `/api/generate` on a free local port
+pub struct FakeOllama {
+    url: String,
+    state: Arc<Mutex<FakeState>>,
+    task: JoinHandle<()>,
+}
+
+impl FakeOllama {
+    pub async fn start() -> Self {
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("Failed to bind fake Ollama");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(FakeState::default()));
+        let router = Router::new()
+            .route(API_CHAT_ENDPOINT, post(generate))
+            .route(API_GENERATE_ENDPOINT, post(generate))
+            .with_state(state.clone());
+
+        let task = tokio::spawn(async move {
+            axum::serve(listener, router).await.ok();
+        });
+        Self { url, state, task }
+    }
+
+    /// Queue replies for requests of `kind`
+    pub fn reply(&self, kind: RequestKind, replies: impl IntoIterator<Item = Reply>) {
+        self.state
+            .lock()
+            .unwrap()
+            .replies
+            .entry(kind)
+            .or_default()
+            .extend(replies);
+    }
+
+    /// Drop the queued replies for `kind`, e.g. to answer differently on a rerun
+    pub fn clear(&self, kind: RequestKind) {
+        self.state.lock().unwrap().replies.remove(&kind);
+    }
+
+    pub fn requests(&self, kind: RequestKind) -> Vec<RecordedRequest> {
+        self.state
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .filter(|request| request.kind == kind)
+            .cloned()
+            .collect()
+    }
+
+    /// Config of an app that only talks to this server. Without retries, so a
+    /// queued error reply fails exactly one request.
+    pub fn config(&self) -> LlmConfig {
+        LlmConfig {
+            external_endpoint: self.url.clone(),
+            external_model: "fake-llama".to_string(),
+            chunk_size: 4_000,
+            max_retries: 0,
+            extract_keywords: false,
+            ..Default::default()
+        }
+    }
+}
+
+impl Drop for FakeOllama {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn generate(
+    State(state): State<Arc<Mutex<FakeState>>>,
+    Json(body): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let kind = RequestKind::of(&body);
+    // `/api/chat` sends messages, `/api/generate` one merged prompt
+    let chat = body["messages"].is_array();
+    let prompt = match body["messages"].as_array() {
+        Some(messages) => messages
+            .iter()
+            .filter_map(|message| message["content"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => body["prompt"].as_str().unwrap_or_default().to_string(),
+    };
+    let mut state = state.lock().unwrap();
+    state.requests.push(RecordedRequest { kind, prompt });
+
+    match state.next_reply(kind) {
+        Some(Reply::Text(response)) if chat => (
+            StatusCode::OK,
+            Json(json!({
+                "model": body["model"],
+                "created_at": "2025-03-11T19:00:00Z",
+                "message": {"role": "assistant", "content": response},
+                "done": true,
+                "done_reason": "stop",
+                "total_duration": 0,
+                "load_duration": 0,
+                "prompt_eval_count": 100,
+                "prompt_eval_duration": 0,
+                "eval_count": 50,
+                "eval_duration": 0,
+            })),
+        ),
+        Some(Reply::Text(response)) => (
+            StatusCode::OK,
+            Json(json!({
+                "model": body["model"],
+                "created_at": "2025-03-11T19:00:00Z",
+                "response": response,
+                "done": true,
+                "done_reason": "stop",
+                "context": [],
+                "total_duration": 0,
+                "load_duration": 0,
+                "prompt_eval_count": 100,
+                "prompt_eval_duration": 0,
+                "eval_count": 50,
+                "eval_duration": 0,
+            })),
+        ),
+        Some(Reply::Error(status, message)) => (
+            StatusCode::from_u16(status).unwrap(),
+            Json(json!({ "error": message })),
+        ),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": format!("no canned reply for {:?}", kind) })),
+        ),
+    }
+}
+
+/// A mock app whose app data directory is a temporary one
+pub struct TestApp {
+    app: App<MockRuntime>,
+    data_dir: TempDir,
+    events: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl TestApp {
+    pub fn new(config: LlmConfig) -> Self {
+        let data_dir = tempfile::tempdir().unwrap();
+        // The app data directory is the identifier joined onto the platform
+        // data directory, an absolute identifier replaces the latter
+        let mut context = mock_context(noop_assets());
+        context.config_mut().identifier = data_dir.path().to_string_lossy().into_owned();
+
+        let app = mock_builder()
+            .manage(tokio::sync::Mutex::new(AppState {
+                llm_config: config,
+                ..Default::default()
+            }))
+            .build(context)
+            .expect("Failed to build mock app");
+        assert_eq!(
+            app.path().app_local_data_dir().unwrap(),
+            data_dir.path(),
+            "the mock app must not touch the real app data directory"
+        );
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        for name in PIPELINE_EVENTS {
+            let events = events.clone();
+            app.listen_any(*name, move |event| {
+                events
+                    .lock()
+                    .unwrap()
+                    .push((name.to_string(), event.payload().to_string()));
+            });
+        }
+
+        Self {
+            app,
+            data_dir,
+            events,
+        }
+    }
+
+    pub fn handle(&self) -> AppHandle<MockRuntime> {
+        self.app.handle().clone()
+    }
+
+    pub fn meeting_dir(&self, meeting_id: &str) -> PathBuf {
+        self.data_dir.path().join("uploads").join(meeting_id)
+    }
+
+    /// Copy `tests/fixtures/meetings/<meeting_id>` into the uploads directory
+    pub fn add_meeting(&self, meeting_id: &str) -> PathBuf {
+        let meeting_dir = self.meeting_dir(meeting_id);
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        let fixture = fixture_path(&format!("meetings/{}", meeting_id));
+        for entry in std::fs::read_dir(fixture).unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), meeting_dir.join(entry.file_name())).unwrap();
+        }
+        meeting_dir
+    }
+
+    /// Payloads emitted as `name` so far, as JSON
+    pub fn events(&self, name: &str) -> Vec<String> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(event, _)| event == name)
+            .map(|(_, payload)| payload.clone())
+            .collect()
+    }
+
+    pub async fn currently_summarizing(&self) -> Option<String> {
+        let state = self.app.state::<tokio::sync::Mutex<AppState>>();
+        let state = state.lock().await;
+        state.currently_summarizing.clone()
+    }
+}
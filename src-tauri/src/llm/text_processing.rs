@@ -1,8 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::llm::error::{LlmError, LlmResult};
+
+/// Whisper hallucinates short repeated phrases on silence, a real meeting has more words
+pub const MIN_DISTINCT_WORDS: usize = 5;
+
+/// Check that a transcript has enough text to be worth summarizing and return
+/// it trimmed. Silent audio gives a transcript of newlines or a repeated phrase.
+pub fn validate_transcript(transcript: &str, min_chars: usize) -> LlmResult<&str> {
+    let trimmed = transcript.trim();
+    let chars = trimmed.chars().count();
+    let distinct_words = trimmed
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<HashSet<_>>()
+        .len();
+
+    if chars < min_chars || distinct_words < MIN_DISTINCT_WORDS {
+        return Err(LlmError::TranscriptTooShort {
+            chars,
+            distinct_words,
+        });
+    }
+
+    Ok(trimmed)
+}
+
+/// Chunk summaries the final summary request can still combine comfortably
+pub const TARGET_MAX_CHUNKS: usize = 12;
+/// Tokens of the context window kept free for the system prompt, the key facts and the answer
+pub const CHUNK_PROMPT_RESERVE_TOKENS: usize = 2_048;
+/// Same estimate as the prompt preview
+const CHARS_PER_TOKEN: usize = 4;
+/// Even a tiny context window gets chunks of at least this size
+const MIN_PLANNED_CHUNK_SIZE: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkSizeReason {
+    /// The configured size already gives few enough chunks
+    Configured,
+    /// Larger chunks, the configured size would give too many summaries to combine
+    Enlarged,
+    /// Smaller chunks, the configured size doesn't fit the context window
+    Shrunk,
+}
+
+/// Chunk size chosen for a transcript by `plan_chunk_size`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkPlan {
+    pub chunk_size: usize,
+    pub configured_chunk_size: usize,
+    /// Lower bound, the splitter ends chunks early at natural breaks
+    pub expected_chunks: usize,
+    pub reason: ChunkSizeReason,
+}
+
+/// Pick the chunk size for a transcript of `transcript_chars` characters: the
+/// configured size, unless it produces more than `target_max_chunks` chunks
+/// or a chunk doesn't fit into `context_window_tokens`
+pub fn plan_chunk_size(
+    transcript_chars: usize,
+    configured_chunk_size: usize,
+    context_window_tokens: usize,
+    target_max_chunks: usize,
+) -> ChunkPlan {
+    let configured_chunk_size = configured_chunk_size.max(1);
+    let max_chunk_size = (context_window_tokens.saturating_sub(CHUNK_PROMPT_RESERVE_TOKENS)
+        * CHARS_PER_TOKEN)
+        .max(MIN_PLANNED_CHUNK_SIZE);
+
+    let (chunk_size, reason) = if configured_chunk_size > max_chunk_size {
+        (max_chunk_size, ChunkSizeReason::Shrunk)
+    } else {
+        let needed = transcript_chars.div_ceil(target_max_chunks.max(1));
+        if needed > configured_chunk_size {
+            (needed.min(max_chunk_size), ChunkSizeReason::Enlarged)
+        } else {
+            (configured_chunk_size, ChunkSizeReason::Configured)
+        }
+    };
+
+    ChunkPlan {
+        chunk_size,
+        configured_chunk_size,
+        expected_chunks: transcript_chars.div_ceil(chunk_size).max(1),
+        reason,
+    }
+}
+
 /// Split text into chunks at natural breaking points
 pub fn split_text_into_chunks(text: &str, max_chars: usize) -> Vec<String> {
     if text.chars().count() <= max_chars {
-        return vec![text.to_string()];
+        return vec![text.trim().to_string()];
     }
+    // Every chunk takes at least one character, or the loop never ends
+    let max_chars = max_chars.max(1);
 
     let mut chunks = Vec::new();
     let mut current_pos = 0;
@@ -18,48 +113,148 @@ pub fn split_text_into_chunks(text: &str, max_chars: usize) -> Vec<String> {
         }
 
         let chunk: String = chars[current_pos..break_pos].iter().collect();
-        chunks.push(chunk.trim().to_string());
+        // A stretch of nothing but whitespace is no chunk
+        let chunk = chunk.trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
         current_pos = break_pos;
     }
 
     chunks
 }
 
-fn find_optimal_break_point(chars: &[char], start: usize, max_end: usize) -> usize {
-    let chunk_text: String = chars[start..max_end].iter().collect();
+/// Boundaries a chunk may end at, best first. Whisper output without
+/// punctuation often has nothing but speaker turns, line breaks or commas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BreakKind {
+    SentenceEnd,
+    ParagraphBreak,
+    SpeakerTurn,
+    LineBreak,
+    Comma,
+    Whitespace,
+}
 
-    // Look for sentence end first
-    if let Some(sentence_end) = find_sentence_end(&chunk_text) {
-        let prefix: String = chunk_text.chars().take(sentence_end + 1).collect();
-        return start + prefix.chars().count();
-    }
+const BREAK_PRIORITY: [BreakKind; 6] = [
+    BreakKind::SentenceEnd,
+    BreakKind::ParagraphBreak,
+    BreakKind::SpeakerTurn,
+    BreakKind::LineBreak,
+    BreakKind::Comma,
+    BreakKind::Whitespace,
+];
 
-    // If no sentence end found, look for paragraph break
-    if let Some(para_break) = chunk_text.rfind("\n\n") {
-        let prefix: String = chunk_text.chars().take(para_break + 2).collect();
-        return start + prefix.chars().count();
-    }
+/// Longest speaker label looked at, e.g. `[Anna Weber]: `
+const MAX_SPEAKER_LABEL_CHARS: usize = 60;
+
+/// Whether a chunk may end right before `chars[pos]`
+fn is_break(chars: &[char], pos: usize, kind: BreakKind) -> bool {
+    let at = |i: usize| chars.get(i).copied();
+    let before = |n: usize| pos.checked_sub(n).and_then(at);
+    let next_is_space = at(pos).is_some_and(char::is_whitespace);
 
-    // Finally, look for any whitespace
-    if let Some(space) = chunk_text.rfind(' ') {
-        let prefix: String = chunk_text.chars().take(space + 1).collect();
-        return start + prefix.chars().count();
+    match kind {
+        BreakKind::SentenceEnd => matches!(before(1), Some('.' | '?' | '!')) && next_is_space,
+        BreakKind::ParagraphBreak => before(1) == Some('\n') && before(2) == Some('\n'),
+        BreakKind::SpeakerTurn => {
+            at(pos) == Some('[')
+                && before(1).is_some_and(char::is_whitespace)
+                && chars[pos..]
+                    .iter()
+                    .take(MAX_SPEAKER_LABEL_CHARS)
+                    .take_while(|c| **c != '\n')
+                    .collect::<String>()
+                    .contains("]:")
+        }
+        BreakKind::LineBreak => before(1) == Some('\n'),
+        BreakKind::Comma => before(1) == Some(',') && next_is_space,
+        BreakKind::Whitespace => before(1).is_some_and(char::is_whitespace) || next_is_space,
     }
+}
 
-    max_end
+/// Last position in `from..=to` a chunk may end at with the given kind
+fn rfind_break(chars: &[char], from: usize, to: usize, kind: BreakKind) -> Option<usize> {
+    (from..=to).rev().find(|pos| is_break(chars, *pos, kind))
 }
 
-fn find_sentence_end(text: &str) -> Option<usize> {
-    text.rfind(". ")
-        .or_else(|| text.rfind(".\n"))
-        .or_else(|| text.rfind("? "))
-        .or_else(|| text.rfind("! "))
+/// Where the chunk starting at `start` ends, at the latest at `max_end`.
+/// Always after `start`, falling back to a hard cut, so no chunk is empty.
+fn find_optimal_break_point(chars: &[char], start: usize, max_end: usize) -> usize {
+    // Better boundaries only count in the second half, so chunks don't get tiny
+    let min_end = start + max_end.saturating_sub(start) / 2;
+    let found = BREAK_PRIORITY
+        .into_iter()
+        .find_map(|kind| rfind_break(chars, min_end.max(start + 1), max_end, kind))
+        // A short chunk is still better than a word cut in half
+        .or_else(|| rfind_break(chars, start + 1, max_end, BreakKind::Whitespace));
+    match found {
+        Some(pos) if pos > start => return pos,
+        _ if max_end <= start => return (start + 1).min(chars.len()),
+        _ => {}
+    }
+
+    println!(
+        "⚠️  No whitespace within {} characters, splitting the transcript mid-word",
+        max_end - start
+    );
+    max_end
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_plan_keeps_configured_size() {
+        for transcript_chars in [0, 1, 10_000, 50_000, 120_000] {
+            let plan = plan_chunk_size(transcript_chars, 10_000, 32_768, 12);
+            assert_eq!(plan.chunk_size, 10_000, "{}", transcript_chars);
+            assert_eq!(plan.reason, ChunkSizeReason::Configured);
+            assert!(plan.expected_chunks <= 12);
+        }
+        assert_eq!(plan_chunk_size(0, 10_000, 32_768, 12).expected_chunks, 1);
+        assert_eq!(
+            plan_chunk_size(25_000, 10_000, 32_768, 12).expected_chunks,
+            3
+        );
+    }
+
+    #[test]
+    fn test_plan_enlarges_chunks_of_long_transcripts() {
+        // 32k tokens leave (32_768 - 2_048) * 4 = 122_880 characters per chunk
+        for (transcript_chars, chunk_size) in [
+            (120_001, 10_001),
+            (240_000, 20_000),
+            (600_000, 50_000),
+            (1_474_560, 122_880),
+        ] {
+            let plan = plan_chunk_size(transcript_chars, 10_000, 32_768, 12);
+            assert_eq!(plan.chunk_size, chunk_size, "{}", transcript_chars);
+            assert_eq!(plan.reason, ChunkSizeReason::Enlarged);
+            assert_eq!(plan.expected_chunks, 12);
+        }
+    }
+
+    #[test]
+    fn test_plan_is_capped_by_context_window() {
+        let plan = plan_chunk_size(3_000_000, 10_000, 32_768, 12);
+        assert_eq!(plan.chunk_size, 122_880);
+        assert_eq!(plan.reason, ChunkSizeReason::Enlarged);
+        assert_eq!(plan.expected_chunks, 25);
+
+        // A 4k window leaves room for 8k characters, less than configured
+        let plan = plan_chunk_size(30_000, 10_000, 4_096, 12);
+        assert_eq!(plan.chunk_size, 8_192);
+        assert_eq!(plan.reason, ChunkSizeReason::Shrunk);
+        assert_eq!(plan.expected_chunks, 4);
+
+        // Never below the minimum, even if the window is smaller than the reserve
+        let plan = plan_chunk_size(30_000, 10_000, 1_024, 12);
+        assert_eq!(plan.chunk_size, MIN_PLANNED_CHUNK_SIZE);
+        assert_eq!(plan.expected_chunks, 15);
+    }
+
     #[test]
     fn test_split_small_text() {
         let text = "Short text";
@@ -103,12 +298,92 @@ mod tests {
     }
 
     #[test]
-    fn test_find_sentence_end() {
-        assert_eq!(find_sentence_end("Hello. World"), Some(5));
-        assert_eq!(find_sentence_end("Hello? World"), Some(5));
-        assert_eq!(find_sentence_end("Hello! World"), Some(5));
-        assert_eq!(find_sentence_end("Hello.\nWorld"), Some(5));
-        assert_eq!(find_sentence_end("No sentence end"), None);
+    fn test_sentence_end_breaks() {
+        let is_sentence_end =
+            |text: &str| is_break(&text.chars().collect::<Vec<_>>(), 6, BreakKind::SentenceEnd);
+        assert!(is_sentence_end("Hello. World"));
+        assert!(is_sentence_end("Hello? World"));
+        assert!(is_sentence_end("Hello! World"));
+        assert!(is_sentence_end("Hello.\nWorld"));
+        assert!(!is_sentence_end("No sentence end"));
+        assert!(!is_sentence_end("Hello.World"));
+    }
+
+    /// Whisper output for a German meeting without a single period
+    const UNPUNCTUATED_GERMAN: &str = "[SPEAKER_00]: ja also dann fangen wir mal an wir haben heute drei punkte auf der tagesordnung erstens den haushalt zweitens die raumplanung für das nächste semester und drittens die anfrage vom fachschaftsrat wegen der erstiwoche [SPEAKER_01]: genau und zum haushalt muss ich sagen dass wir im moment etwa zweitausend euro übrig haben die wir bis ende des jahres ausgeben sollten sonst verfallen die mittel [SPEAKER_00]: okay gibt es da schon vorschläge wofür wir das geld nutzen wollen vielleicht für neue stühle im sitzungsraum oder für die verpflegung bei der nächsten vollversammlung [SPEAKER_02]: ich würde vorschlagen dass wir einen teil für die erstiwoche zurücklegen weil da letztes jahr das geld knapp war und wir viele sachen selbst bezahlen mussten [SPEAKER_01]: das klingt sinnvoll dann machen wir das so und besprechen den rest beim nächsten treffen";
+
+    fn words(text: &str) -> Vec<&str> {
+        text.split_whitespace().collect()
+    }
+
+    #[test]
+    fn test_unpunctuated_transcript_splits_between_words() {
+        for max_chars in [80, 150, 333, 500] {
+            let chunks = split_text_into_chunks(UNPUNCTUATED_GERMAN, max_chars);
+            assert!(chunks.len() > 1);
+
+            let rejoined: Vec<&str> = chunks.iter().flat_map(|chunk| words(chunk)).collect();
+            assert_eq!(
+                rejoined,
+                words(UNPUNCTUATED_GERMAN),
+                "max_chars {}",
+                max_chars
+            );
+            for chunk in &chunks {
+                assert!(chunk.chars().count() <= max_chars);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unpunctuated_transcript_splits_at_speaker_turns() {
+        let chunks = split_text_into_chunks(UNPUNCTUATED_GERMAN, 333);
+        for chunk in &chunks {
+            assert!(chunk.starts_with("[SPEAKER_"), "{}", chunk);
+        }
+    }
+
+    #[test]
+    fn test_break_priority_without_periods() {
+        let text = "erstens der haushalt, zweitens die räume\nund drittens noch etwas";
+        let chars: Vec<char> = text.chars().collect();
+        let line_end = text.chars().position(|c| c == '\n').unwrap() + 1;
+        assert_eq!(
+            find_optimal_break_point(&chars, 0, chars.len() - 1),
+            line_end
+        );
+
+        let text = "erstens der haushalt, zweitens die räume und noch etwas";
+        let chars: Vec<char> = text.chars().collect();
+        let after_comma = text.chars().position(|c| c == ',').unwrap() + 1;
+        assert_eq!(find_optimal_break_point(&chars, 0, 30), after_comma);
+    }
+
+    #[test]
+    fn test_split_mid_word_only_without_whitespace() {
+        // A single space early on is still better than cutting a word
+        let text = format!("ab {}", "c".repeat(200));
+        let chunks = split_text_into_chunks(&text, 100);
+        assert_eq!(chunks[0], "ab");
+
+        let chunks = split_text_into_chunks(&"ä".repeat(250), 100);
+        assert_eq!(
+            chunks.iter().map(|c| c.chars().count()).collect::<Vec<_>>(),
+            vec![100, 100, 50]
+        );
+    }
+
+    #[test]
+    fn test_chunks_are_never_empty() {
+        let text = format!("Anfang.{}Ende.", " ".repeat(250));
+        let chunks = split_text_into_chunks(&text, 100);
+        assert_eq!(chunks, vec!["Anfang.", "Ende."]);
+
+        let chunks = split_text_into_chunks("ab cd", 0);
+        assert_eq!(chunks, vec!["a", "b", "c", "d"]);
+
+        let chars: Vec<char> = "ab cd".chars().collect();
+        assert_eq!(find_optimal_break_point(&chars, 2, 2), 3);
     }
 
     #[test]
@@ -118,6 +393,53 @@ mod tests {
         assert_eq!(chunks[0], "");
     }
 
+    #[test]
+    fn test_validate_transcript_min_chars() {
+        let words = "eins zwei drei vier fünf sechs";
+        assert!(validate_transcript(words, words.chars().count()).is_ok());
+
+        match validate_transcript(words, words.chars().count() + 1) {
+            Err(LlmError::TranscriptTooShort {
+                chars,
+                distinct_words,
+            }) => {
+                assert_eq!(chars, 30);
+                assert_eq!(distinct_words, 6);
+            }
+            other => panic!("unexpected result: {:?}", other.map(str::to_string)),
+        }
+    }
+
+    #[test]
+    fn test_validate_transcript_ignores_surrounding_whitespace() {
+        let transcript = format!("\n\n  {}  \n\n", "Wir fangen mit dem Haushalt an.");
+        assert_eq!(
+            validate_transcript(&transcript, 31).unwrap(),
+            "Wir fangen mit dem Haushalt an."
+        );
+        assert!(validate_transcript(&transcript, 32).is_err());
+    }
+
+    #[test]
+    fn test_validate_transcript_rejects_silence() {
+        assert!(matches!(
+            validate_transcript("\n\n\n   \n", 0),
+            Err(LlmError::TranscriptTooShort {
+                chars: 0,
+                distinct_words: 0,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_validate_transcript_requires_distinct_words() {
+        let hallucination = "Vielen Dank fürs Zuschauen. ".repeat(20);
+        assert!(validate_transcript(&hallucination, 200).is_err());
+
+        let meeting = format!("{} Danke, dann zum nächsten Punkt.", hallucination);
+        assert!(validate_transcript(&meeting, 200).is_ok());
+    }
+
     #[test]
     fn test_whitespace_only() {
         let chunks = split_text_into_chunks("   \n\t  ", 100);
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use crate::llm::models::ToDo;
+use crate::llm::prompts::Language;
+
+/// To-dos of one person, or the ones nobody was assigned to
+#[derive(Debug, PartialEq)]
+pub struct TodoGroup<'a> {
+    /// `None` for the unassigned to-dos
+    pub assignee: Option<&'a str>,
+    pub todos: Vec<&'a ToDo>,
+}
+
+/// Heading of the group without assignee
+pub fn unassigned_heading(language: &Language) -> &'static str {
+    match language {
+        Language::English => "Unassigned",
+        Language::German => "Offen",
+    }
+}
+
+/// Names a to-do is assigned to, blank and repeated ones dropped
+fn assignees(todo: &ToDo) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for name in todo.assignees.iter().flatten().map(|name| name.trim()) {
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Whether more than one person is assigned to the to-do
+pub fn is_shared(todo: &ToDo) -> bool {
+    assignees(todo).len() > 1
+}
+
+/// Group to-dos by assignee, sorted by name with the unassigned ones last.
+/// A shared to-do is in the group of each of its assignees. Within a group
+/// the to-dos keep the order of the summary.
+pub fn group_todos_by_assignee(todos: &[ToDo]) -> Vec<TodoGroup<'_>> {
+    // Sorted case-insensitively, names that only differ in case share a group
+    let mut by_assignee: BTreeMap<String, TodoGroup> = BTreeMap::new();
+    let mut unassigned = Vec::new();
+
+    for todo in todos {
+        let names = assignees(todo);
+        if names.is_empty() {
+            unassigned.push(todo);
+        }
+        for name in names {
+            by_assignee
+                .entry(name.to_lowercase())
+                .or_insert_with(|| TodoGroup {
+                    assignee: Some(name),
+                    todos: Vec::new(),
+                })
+                .todos
+                .push(todo);
+        }
+    }
+
+    let mut groups: Vec<TodoGroup> = by_assignee.into_values().collect();
+    if !unassigned.is_empty() {
+        groups.push(TodoGroup {
+            assignee: None,
+            todos: unassigned,
+        });
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(task: &str, assignees: Option<&[&str]>) -> ToDo {
+        ToDo {
+            assignees: assignees.map(|names| names.iter().map(|name| name.to_string()).collect()),
+            task: task.to_string(),
+        }
+    }
+
+    fn tasks<'a>(group: &TodoGroup<'a>) -> Vec<&'a str> {
+        group.todos.iter().map(|todo| todo.task.as_str()).collect()
+    }
+
+    #[test]
+    fn test_groups_are_sorted_with_unassigned_last() {
+        let todos = vec![
+            todo("Getränke besorgen", None),
+            todo("Protokoll verschicken", Some(&["Martin"])),
+            todo("Raum buchen", Some(&["anna"])),
+            todo("Aushang machen", Some(&[])),
+            todo("Budget prüfen", Some(&["Martin", " "])),
+            todo("Schlüssel zählen", Some(&["Anna"])),
+        ];
+        let groups = group_todos_by_assignee(&todos);
+
+        let assignees: Vec<Option<&str>> = groups.iter().map(|group| group.assignee).collect();
+        assert_eq!(assignees, vec![Some("anna"), Some("Martin"), None]);
+        assert_eq!(tasks(&groups[0]), vec!["Raum buchen", "Schlüssel zählen"]);
+        assert_eq!(
+            tasks(&groups[1]),
+            vec!["Protokoll verschicken", "Budget prüfen"]
+        );
+        assert_eq!(
+            tasks(&groups[2]),
+            vec!["Getränke besorgen", "Aushang machen"]
+        );
+        assert!(!is_shared(&todos[4]));
+    }
+
+    #[test]
+    fn test_shared_todos_are_in_every_group() {
+        let todos = vec![
+            todo("Sommerfest planen", Some(&["Lea", "Jonas", "Lea"])),
+            todo("Musik organisieren", Some(&["Sara"])),
+        ];
+        let groups = group_todos_by_assignee(&todos);
+
+        let assignees: Vec<Option<&str>> = groups.iter().map(|group| group.assignee).collect();
+        assert_eq!(assignees, vec![Some("Jonas"), Some("Lea"), Some("Sara")]);
+        assert_eq!(tasks(&groups[0]), vec!["Sommerfest planen"]);
+        assert_eq!(tasks(&groups[1]), vec!["Sommerfest planen"]);
+        assert!(is_shared(&todos[0]));
+        assert!(!is_shared(&todos[1]));
+
+        assert!(group_todos_by_assignee(&[]).is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::llm::models::Topic;
+
+/// Words ignored when comparing titles
+pub(crate) const STOP_WORDS: &[&str] = &[
+    "der", "die", "das", "den", "dem", "des", "ein", "eine", "und", "oder", "für", "zur", "zum",
+    "von", "vom", "im", "in", "am", "an", "auf", "mit", "zu", "bei", "über", "the", "a", "and",
+    "or", "of", "for", "to", "on", "with",
+];
+/// Shorter words only match exactly, "Rat" is not the start of "Rathaus"
+const MIN_COMPOUND_PREFIX: usize = 4;
+
+/// Thresholds of the topic pre-merge before the final summary call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TopicMergeConfig {
+    pub enabled: bool,
+    /// Jaro-Winkler similarity from which two title words count as the same word
+    pub word_similarity: f64,
+    /// Share of the shorter title's words that have to match the other title
+    pub title_overlap: f64,
+}
+
+impl Default for TopicMergeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            word_similarity: 0.92,
+            title_overlap: 0.6,
+        }
+    }
+}
+
+/// Lowercase words of a title without punctuation and stop words
+fn title_words(title: &str) -> Vec<String> {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !STOP_WORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Equal, one is the start of a compound word ("budget" in "budgetplanung"),
+/// or similar enough for a different inflection. Numbers only match exactly,
+/// "2024" and "2025" are different budgets.
+fn words_match(a: &str, b: &str, config: &TopicMergeConfig) -> bool {
+    if a == b {
+        return true;
+    }
+    let is_number = |word: &str| word.chars().all(|c| c.is_ascii_digit());
+    if is_number(a) || is_number(b) {
+        return false;
+    }
+
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if shorter.chars().count() >= MIN_COMPOUND_PREFIX && longer.starts_with(shorter) {
+        return true;
+    }
+    strsim::jaro_winkler(a, b) >= config.word_similarity
+}
+
+/// Share of the shorter title's words that match a word of the other title
+pub fn title_similarity(a: &str, b: &str, config: &TopicMergeConfig) -> f64 {
+    let (a, b) = (title_words(a), title_words(b));
+    let (shorter, longer) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if shorter.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    let matched = shorter
+        .iter()
+        .filter(|word| longer.iter().any(|other| words_match(word, other, config)))
+        .count();
+    matched as f64 / shorter.len() as f64
+}
+
+/// Key for finding the same bullet point in two chunks
+fn bullet_key(bullet: &str) -> String {
+    title_words(bullet).join(" ")
+}
+
+fn push_unique(bullets: &mut Vec<String>, seen: &mut HashSet<String>, new: Vec<String>) {
+    for bullet in new {
+        if seen.insert(bullet_key(&bullet)) {
+            bullets.push(bullet);
+        }
+    }
+}
+
+/// One topic for a cluster. Topics with the cluster's title are merged into
+/// it, the others keep their title as sub topics so no detail is lost.
+fn merge_cluster(cluster: Vec<Topic>) -> Topic {
+    // The shortest title is usually the most general one
+    let title = cluster
+        .iter()
+        .map(|topic| &topic.title)
+        .min_by_key(|title| (title_words(title).len(), title.chars().count()))
+        .cloned()
+        .unwrap_or_default();
+    let title_key = title_words(&title);
+
+    let (same, different): (Vec<Topic>, Vec<Topic>) = cluster
+        .into_iter()
+        .partition(|topic| title_words(&topic.title) == title_key);
+
+    let mut seen = HashSet::new();
+    let mut bullet_points = Vec::new();
+    let mut sub_topics = Vec::new();
+    for topic in same {
+        push_unique(&mut bullet_points, &mut seen, topic.bullet_points);
+        sub_topics.extend(topic.sub_topics.unwrap_or_default());
+    }
+    for mut topic in different {
+        let bullets = std::mem::take(&mut topic.bullet_points);
+        push_unique(&mut topic.bullet_points, &mut seen, bullets);
+        sub_topics.push(topic);
+    }
+
+    Topic {
+        title,
+        bullet_points,
+        sub_topics: (!sub_topics.is_empty()).then_some(sub_topics),
+    }
+}
+
+/// Cluster topics from different chunks with the same or a similar title,
+/// so weaker models don't end up with "Budget", "Budget 2025" and
+/// "Budgetplanung" as three topics. Clusters keep the order in which their
+/// first topic appeared.
+pub fn merge_topics(topics: Vec<Topic>, config: &TopicMergeConfig) -> Vec<Topic> {
+    if !config.enabled {
+        return topics;
+    }
+
+    let mut clusters: Vec<Vec<Topic>> = Vec::new();
+    for topic in topics {
+        let cluster = clusters.iter_mut().find(|cluster| {
+            cluster.iter().any(|member| {
+                title_similarity(&member.title, &topic.title, config) >= config.title_overlap
+            })
+        });
+        match cluster {
+            Some(cluster) => cluster.push(topic),
+            None => clusters.push(vec![topic]),
+        }
+    }
+
+    clusters
+        .into_iter()
+        .map(|mut cluster| {
+            if cluster.len() == 1 {
+                cluster.remove(0)
+            } else {
+                merge_cluster(cluster)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(title: &str, bullets: &[&str]) -> Topic {
+        Topic {
+            title: title.to_string(),
+            bullet_points: bullets.iter().map(|b| b.to_string()).collect(),
+            sub_topics: None,
+        }
+    }
+
+    fn titles(topics: &[Topic]) -> Vec<&str> {
+        topics.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_budget_variants_are_clustered() {
+        let merged = merge_topics(
+            vec![
+                topic("Budget 2025", &["Der Entwurf liegt vor."]),
+                topic("Raumplanung", &["Raum 3 wird renoviert."]),
+                topic(
+                    "Budget",
+                    &["Der Entwurf liegt vor", "Kürzungen bei Reisen."],
+                ),
+                topic("Budgetplanung", &["Abstimmung im März."]),
+            ],
+            &TopicMergeConfig::default(),
+        );
+
+        assert_eq!(titles(&merged), vec!["Budget", "Raumplanung"]);
+        let budget = &merged[0];
+        assert_eq!(
+            budget.bullet_points,
+            vec!["Der Entwurf liegt vor", "Kürzungen bei Reisen."]
+        );
+        let sub_topics = budget.sub_topics.as_ref().unwrap();
+        assert_eq!(titles(sub_topics), vec!["Budget 2025", "Budgetplanung"]);
+        // Same bullet from two chunks, only punctuation differs
+        assert!(sub_topics[0].bullet_points.is_empty());
+        assert_eq!(sub_topics[1].bullet_points, vec!["Abstimmung im März."]);
+    }
+
+    #[test]
+    fn test_compounds_with_the_same_head_stay_apart() {
+        let config = TopicMergeConfig::default();
+        // Same last part, different subject
+        assert!(title_similarity("Raumplanung", "Budgetplanung", &config) < config.title_overlap);
+        assert!(title_similarity("Haushaltsplanung", "Haushalt", &config) >= config.title_overlap);
+        assert!(title_similarity("Rat", "Rathaus", &config) < config.title_overlap);
+    }
+
+    #[test]
+    fn test_exact_duplicates_merge_without_sub_topics() {
+        let merged = merge_topics(
+            vec![
+                topic("Sommerfest", &["Termin: 12. Juli"]),
+                topic(
+                    "Das Sommerfest",
+                    &["Termin: 12. Juli", "Anna plant das Catering"],
+                ),
+            ],
+            &TopicMergeConfig::default(),
+        );
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].title, "Sommerfest");
+        assert_eq!(
+            merged[0].bullet_points,
+            vec!["Termin: 12. Juli", "Anna plant das Catering"]
+        );
+        assert!(merged[0].sub_topics.is_none());
+    }
+
+    #[test]
+    fn test_thresholds_are_configurable() {
+        let topics = vec![
+            topic("Budget 2024", &["Abgeschlossen"]),
+            topic("Budget 2025", &["Offen"]),
+        ];
+
+        let merged = merge_topics(topics.clone(), &TopicMergeConfig::default());
+        assert_eq!(merged.len(), 2);
+
+        let loose = TopicMergeConfig {
+            title_overlap: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(merge_topics(topics.clone(), &loose).len(), 1);
+
+        let disabled = TopicMergeConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&merge_topics(topics, &disabled)),
+            vec!["Budget 2024", "Budget 2025"]
+        );
+    }
+
+    #[test]
+    fn test_inflections_match() {
+        let config = TopicMergeConfig::default();
+        assert!(words_match("sitzung", "sitzungen", &config));
+        assert!(words_match("protokoll", "protokolle", &config));
+        assert!(!words_match("2024", "2025", &config));
+    }
+}
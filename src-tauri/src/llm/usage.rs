@@ -0,0 +1,508 @@
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::llm::{
+    config::LlmProvider, models::SummaryResult, prompts::SummaryStyle, text_processing::ChunkPlan,
+};
+use crate::settings::load_settings;
+
+pub const SUMMARY_META_FILE_NAME: &str = "summary_meta.json";
+
+/// Tokens reported by the provider, summed over one or more requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub requests: u32,
+}
+
+impl TokenUsage {
+    /// Usage of a single answered request
+    pub fn request(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+            requests: 1,
+        }
+    }
+
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.requests += other.requests;
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Running total shared by every `LlmService` of a summarization run, so
+/// chunk, final summary, keyword and retried requests all end up in one sum
+#[derive(Debug, Clone, Default)]
+pub struct UsageMeter(Arc<Mutex<TokenUsage>>);
+
+impl UsageMeter {
+    pub fn record(&self, usage: &TokenUsage) {
+        self.0.lock().unwrap().add(usage);
+    }
+
+    pub fn total(&self) -> TokenUsage {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// One summarization run as stored in `summary_meta.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRun {
+    pub date: String,
+    pub provider: LlmProvider,
+    pub model: String,
+    pub usage: TokenUsage,
+}
+
+/// `summary_meta.json`: every run that spent tokens on the meeting and their sum
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SummaryMeta {
+    pub runs: Vec<UsageRun>,
+    pub total: TokenUsage,
+    /// Style the current summary was generated with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<SummaryStyle>,
+    /// Result of the last successful `generate_summary`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_result: Option<SummaryResult>,
+    /// Chunk size picked by automatic chunk sizing for the current summary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_plan: Option<ChunkPlan>,
+    /// Repeated bullets dropped from the current summary, `None` with deduplication off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_duplicate_bullets: Option<usize>,
+}
+
+pub async fn read_summary_meta(meeting_dir: &Path) -> Result<SummaryMeta, String> {
+    let path = meeting_dir.join(SUMMARY_META_FILE_NAME);
+    if !path.exists() {
+        return Ok(SummaryMeta::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read summary meta: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse summary meta: {}", e))
+}
+
+/// Read-modify-write `summary_meta.json`
+pub async fn update_summary_meta<F>(meeting_dir: &Path, update: F) -> Result<(), String>
+where
+    F: FnOnce(&mut SummaryMeta),
+{
+    let mut meta = read_summary_meta(meeting_dir).await?;
+    update(&mut meta);
+
+    let json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize summary meta: {}", e))?;
+    fs::write(meeting_dir.join(SUMMARY_META_FILE_NAME), json)
+        .await
+        .map_err(|e| format!("Failed to write summary meta: {}", e))
+}
+
+/// Append a finished run and update the meeting total
+pub async fn record_usage_run(
+    meeting_dir: &Path,
+    provider: LlmProvider,
+    model: &str,
+    usage: TokenUsage,
+) -> Result<(), String> {
+    update_summary_meta(meeting_dir, |meta| {
+        meta.runs.push(UsageRun {
+            date: Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            provider,
+            model: model.to_string(),
+            usage,
+        });
+        meta.total.add(&usage);
+    })
+    .await
+}
+
+/// Price of a model in the currency the user entered it in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub prompt_per_1k: f64,
+    pub completion_per_1k: f64,
+}
+
+impl ModelPrice {
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        usage.prompt_tokens as f64 / 1000.0 * self.prompt_per_1k
+            + usage.completion_tokens as f64 / 1000.0 * self.completion_per_1k
+    }
+}
+
+/// Prices by model name
+pub type PriceTable = HashMap<String, ModelPrice>;
+
+/// Cost of a run, `None` when its model has no price. Local Ollama models are
+/// free unless the user priced them.
+fn run_cost(run: &UsageRun, prices: &PriceTable) -> Option<f64> {
+    match prices.get(&run.model) {
+        Some(price) => Some(price.cost(&run.usage)),
+        None if run.provider == LlmProvider::Ollama => Some(0.0),
+        None => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeetingUsage {
+    pub meeting_id: String,
+    pub usage: TokenUsage,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageReport {
+    pub meetings: Vec<MeetingUsage>,
+    pub total: TokenUsage,
+    /// Sum over the priced runs only
+    pub total_cost: f64,
+    /// Models that were used in the range but have no price
+    pub unpriced_models: Vec<String>,
+}
+
+fn run_date(run: &UsageRun) -> Option<NaiveDate> {
+    run.date
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+/// Sum the runs between `from` and `to` (both inclusive) per meeting.
+/// Meetings without runs in the range are left out.
+pub fn build_usage_report(
+    meetings: &[(String, SummaryMeta)],
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    prices: &PriceTable,
+) -> UsageReport {
+    let mut report = UsageReport::default();
+    let mut unpriced = BTreeSet::new();
+
+    for (meeting_id, meta) in meetings {
+        let mut meeting = MeetingUsage {
+            meeting_id: meeting_id.clone(),
+            usage: TokenUsage::default(),
+            cost: 0.0,
+        };
+
+        for run in &meta.runs {
+            let in_range = match run_date(run) {
+                Some(date) => {
+                    from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to)
+                }
+                None => from.is_none() && to.is_none(),
+            };
+            if !in_range {
+                continue;
+            }
+
+            meeting.usage.add(&run.usage);
+            match run_cost(run, prices) {
+                Some(cost) => meeting.cost += cost,
+                None => {
+                    unpriced.insert(run.model.clone());
+                }
+            }
+        }
+
+        if meeting.usage.requests > 0 {
+            report.total.add(&meeting.usage);
+            report.total_cost += meeting.cost;
+            report.meetings.push(meeting);
+        }
+    }
+
+    report.unpriced_models = unpriced.into_iter().collect();
+    report
+}
+
+fn parse_date(date: Option<String>) -> Result<Option<NaiveDate>, String> {
+    date.filter(|date| !date.is_empty())
+        .map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date {}: {}", date, e))
+        })
+        .transpose()
+}
+
+/// Tokens and estimated cost per meeting, dates as `YYYY-MM-DD`
+#[tauri::command]
+pub async fn get_usage_report(
+    app: AppHandle,
+    from_date: Option<String>,
+    to_date: Option<String>,
+) -> Result<UsageReport, String> {
+    let from = parse_date(from_date)?;
+    let to = parse_date(to_date)?;
+    let prices = load_settings(&app).await?.model_prices;
+
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let uploads = app_dir.join("uploads");
+    if !uploads.exists() {
+        return Ok(UsageReport::default());
+    }
+
+    let mut meetings = Vec::new();
+    let mut entries = fs::read_dir(uploads).await.map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        if !entry.file_type().await.map_err(|e| e.to_string())?.is_dir() {
+            continue;
+        }
+        let meeting_id = entry.file_name().to_string_lossy().into_owned();
+        match read_summary_meta(&entry.path()).await {
+            Ok(meta) => meetings.push((meeting_id, meta)),
+            Err(e) => println!("⚠️  Skipping usage of {}: {}", meeting_id, e),
+        }
+    }
+    meetings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(build_usage_report(&meetings, from, to, &prices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::chunk_failures::FailedChunk;
+    use tempfile::TempDir;
+
+    fn run(
+        date: &str,
+        provider: LlmProvider,
+        model: &str,
+        prompt: u64,
+        completion: u64,
+    ) -> UsageRun {
+        UsageRun {
+            date: date.to_string(),
+            provider,
+            model: model.to_string(),
+            usage: TokenUsage::request(prompt, completion),
+        }
+    }
+
+    fn meta(runs: Vec<UsageRun>) -> SummaryMeta {
+        let mut total = TokenUsage::default();
+        for run in &runs {
+            total.add(&run.usage);
+        }
+        SummaryMeta {
+            runs,
+            total,
+            style: None,
+            last_result: None,
+            chunk_plan: None,
+            removed_duplicate_bullets: None,
+        }
+    }
+
+    fn prices() -> PriceTable {
+        HashMap::from([(
+            "gpt-4o".to_string(),
+            ModelPrice {
+                prompt_per_1k: 0.0025,
+                completion_per_1k: 0.01,
+            },
+        )])
+    }
+
+    fn date(date: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    fn test_price_per_1k_tokens() {
+        let price = ModelPrice {
+            prompt_per_1k: 0.003,
+            completion_per_1k: 0.015,
+        };
+        let cost = price.cost(&TokenUsage::request(12_000, 2_000));
+        assert!((cost - 0.066).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_meter_sums_requests() {
+        let meter = UsageMeter::default();
+        let shared = meter.clone();
+        meter.record(&TokenUsage::request(100, 20));
+        shared.record(&TokenUsage::request(50, 5));
+
+        assert_eq!(
+            meter.total(),
+            TokenUsage {
+                prompt_tokens: 150,
+                completion_tokens: 25,
+                requests: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_report_filters_dates_and_prices_runs() {
+        let meetings = vec![
+            (
+                "a".to_string(),
+                meta(vec![
+                    run(
+                        "2025-03-01T10:00:00.000Z",
+                        LlmProvider::OpenAi,
+                        "gpt-4o",
+                        10_000,
+                        1_000,
+                    ),
+                    run(
+                        "2025-04-02T10:00:00.000Z",
+                        LlmProvider::OpenAi,
+                        "gpt-4o",
+                        20_000,
+                        2_000,
+                    ),
+                ]),
+            ),
+            (
+                "b".to_string(),
+                meta(vec![run(
+                    "2025-03-15T09:30:00.000Z",
+                    LlmProvider::Anthropic,
+                    "claude-sonnet-4-5",
+                    5_000,
+                    500,
+                )]),
+            ),
+            (
+                "c".to_string(),
+                meta(vec![run(
+                    "2025-03-20T08:00:00.000Z",
+                    LlmProvider::Ollama,
+                    "llama3",
+                    8_000,
+                    800,
+                )]),
+            ),
+        ];
+
+        let report =
+            build_usage_report(&meetings, date("2025-03-01"), date("2025-03-31"), &prices());
+
+        let ids: Vec<&str> = report
+            .meetings
+            .iter()
+            .map(|m| m.meeting_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(report.meetings[0].usage, TokenUsage::request(10_000, 1_000));
+        assert!((report.meetings[0].cost - 0.035).abs() < 1e-9);
+        assert_eq!(report.meetings[1].cost, 0.0);
+        assert_eq!(report.meetings[2].cost, 0.0);
+        assert_eq!(report.total.prompt_tokens, 23_000);
+        assert_eq!(report.total.requests, 3);
+        assert!((report.total_cost - 0.035).abs() < 1e-9);
+        assert_eq!(report.unpriced_models, vec!["claude-sonnet-4-5"]);
+    }
+
+    #[test]
+    fn test_report_leaves_out_meetings_outside_range() {
+        let meetings = vec![(
+            "a".to_string(),
+            meta(vec![run(
+                "2025-03-01T10:00:00.000Z",
+                LlmProvider::OpenAi,
+                "gpt-4o",
+                1_000,
+                100,
+            )]),
+        )];
+
+        let report = build_usage_report(&meetings, date("2025-04-01"), None, &prices());
+        assert!(report.meetings.is_empty());
+        assert_eq!(report.total, TokenUsage::default());
+
+        let report = build_usage_report(&meetings, None, None, &prices());
+        assert_eq!(report.meetings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_record_usage_run_accumulates() {
+        let dir = TempDir::new().unwrap();
+        record_usage_run(
+            dir.path(),
+            LlmProvider::OpenAi,
+            "gpt-4o",
+            TokenUsage::request(100, 10),
+        )
+        .await
+        .unwrap();
+        record_usage_run(
+            dir.path(),
+            LlmProvider::OpenAi,
+            "gpt-4o",
+            TokenUsage {
+                prompt_tokens: 300,
+                completion_tokens: 30,
+                requests: 3,
+            },
+        )
+        .await
+        .unwrap();
+
+        let meta = read_summary_meta(dir.path()).await.unwrap();
+        assert_eq!(meta.runs.len(), 2);
+        assert_eq!(meta.total.prompt_tokens, 400);
+        assert_eq!(meta.total.requests, 4);
+    }
+
+    #[tokio::test]
+    async fn test_last_result_is_persisted() {
+        let dir = TempDir::new().unwrap();
+        // Files written before results were recorded still load
+        std::fs::write(dir.path().join(SUMMARY_META_FILE_NAME), "{\"runs\": []}").unwrap();
+        assert_eq!(
+            read_summary_meta(dir.path()).await.unwrap().last_result,
+            None
+        );
+
+        let result = SummaryResult {
+            meeting_id: "meeting-1".to_string(),
+            markdown: "# 📅 Planung".to_string(),
+            title: "📅 Planung".to_string(),
+            duration_seconds: 42.0,
+            chunk_count: 3,
+            reused_chunks: 0,
+            warnings: Vec::new(),
+            failed_chunks: vec![FailedChunk {
+                chunk_index: 1,
+                error: "Timeout error: no answer".to_string(),
+            }],
+            failed_progress_events: 0,
+        };
+        update_summary_meta(dir.path(), |meta| meta.last_result = Some(result.clone()))
+            .await
+            .unwrap();
+
+        let meta = read_summary_meta(dir.path()).await.unwrap();
+        assert_eq!(meta.last_result, Some(result));
+    }
+
+    #[test]
+    fn test_invalid_date_is_rejected() {
+        assert!(parse_date(Some("03/01/2025".to_string())).is_err());
+        assert_eq!(parse_date(Some(String::new())).unwrap(), None);
+    }
+}
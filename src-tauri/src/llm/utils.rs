@@ -3,7 +3,8 @@ use crate::llm::{
     config::LlmConfig,
     error::{LlmError, LlmResult},
     file_manager::FileManager,
-    performance::PerformanceTracker,
+    performance::SharedPerformanceTracker,
+    progress::ProgressTracker,
     prompts::{Language, PromptManager},
     service::LlmService,
     summary::SummaryGenerator,
@@ -69,12 +70,16 @@ impl LlmUtils {
         let service = LlmService::new(
             config.external_endpoint.clone(),
             config.external_model.clone(),
-        );
+        )
+        .with_external_api(config.use_external_api);
 
         let test_prompt = PromptManager::test_connection(&Language::English);
         let test_message = PromptManager::test_user_message(&Language::English);
 
-        match service.generate_text(test_prompt, test_message, None, None).await {
+        match service
+            .generate_text(test_prompt, test_message, None, None::<&ProgressTracker>)
+            .await
+        {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -126,7 +131,7 @@ impl LlmUtils {
 pub struct LlmSession {
     pub app_handle: AppHandle,
     pub config: LlmConfig,
-    pub performance_tracker: Option<PerformanceTracker>,
+    pub performance_tracker: Option<SharedPerformanceTracker>,
     pub file_manager: FileManager,
 }
 
@@ -147,27 +152,27 @@ impl LlmSession {
 
     #[allow(dead_code)]
     pub fn with_performance_tracking(mut self) -> Self {
-        self.performance_tracker = Some(PerformanceTracker::new());
+        self.performance_tracker = Some(SharedPerformanceTracker::default());
         self
     }
 
     #[allow(dead_code)]
     pub async fn generate_summary(&mut self, meeting_id: &str) -> LlmResult<String> {
-        let generator = SummaryGenerator::new(self.app_handle.clone(), Language::default());
-        
-        let result = generator.generate_summary(meeting_id).await;
-        
-        // Print performance summary if tracking is enabled
-        if let Some(ref tracker) = self.performance_tracker {
-            tracker.print_summary();
+        let mut generator = SummaryGenerator::new(self.app_handle.clone(), Language::default());
+        if let Some(tracker) = &self.performance_tracker {
+            generator = generator.with_performance_tracker(tracker.clone());
         }
-        
-        result
+
+        // The generator prints the performance summary of a finished run
+        generator
+            .generate_summary(meeting_id)
+            .await
+            .map(|result| result.markdown)
     }
 
     #[allow(dead_code)]
     pub fn get_performance_metrics(&self) -> Option<crate::llm::performance::PerformanceMetrics> {
-        self.performance_tracker.as_ref().map(|t| t.get_metrics())
+        self.performance_tracker.as_ref().map(|t| t.metrics())
     }
 }
 
@@ -1,46 +1,723 @@
+use chrono::Utc;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
 use tauri::{AppHandle, Manager};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::chunk_manifest::{read_manifest, write_manifest};
+use crate::error::MeetingError;
+use crate::llm::file_manager::transcription_chunks_dir;
+use crate::llm::{
+    prompts::{Language, SummaryStyle},
+    text_processing::validate_transcript,
+};
+use crate::vtt::parse_vtt;
+use crate::{AppState, MeetingMetadata};
+
+static MEETING_LOCKS: LazyLock<std::sync::Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+/// Meeting ids are directory names, anything that could leave `uploads` is rejected
+pub fn is_valid_meeting_id(meeting_id: &str) -> bool {
+    !meeting_id.is_empty()
+        && !meeting_id.starts_with('.')
+        && meeting_id
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' '))
+}
+
+/// `recording-<timestamp>`, with a counter if a meeting of the same second exists
+pub fn new_meeting_id(uploads: &Path) -> String {
+    let timestamp = Utc::now().timestamp();
+    let mut meeting_id = format!("recording-{}", timestamp);
+    let mut suffix = 1;
+    while uploads.join(&meeting_id).exists() {
+        meeting_id = format!("recording-{}-{}", timestamp, suffix);
+        suffix += 1;
+    }
+    meeting_id
+}
+
+/// Create the directory of a new meeting under `uploads`. `create_dir` fails
+/// if the directory exists, so two imports of the same second never share
+/// one and the caller owns whatever it returns.
+pub async fn create_meeting_dir(uploads: &Path) -> Result<(String, PathBuf), String> {
+    tokio::fs::create_dir_all(uploads)
+        .await
+        .map_err(|e| format!("Failed to create uploads directory: {}", e))?;
+    loop {
+        let meeting_id = new_meeting_id(uploads);
+        let meeting_dir = uploads.join(&meeting_id);
+        match tokio::fs::create_dir(&meeting_dir).await {
+            Ok(()) => return Ok((meeting_id, meeting_dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create meeting directory: {}", e)),
+        }
+    }
+}
+
+/// Serialize writes to the files of one meeting, e.g. from two open windows.
+/// Hold the guard for the whole read-modify-write; the lock is not reentrant.
+pub async fn lock_meeting(meeting_dir: &Path) -> OwnedMutexGuard<()> {
+    let lock = MEETING_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(meeting_dir.to_path_buf())
+        .or_default()
+        .clone();
+    lock.lock_owned().await
+}
+
+/// Write to a temporary file next to `path` and rename it into place, so
+/// readers never see a partially written file. A failed write, e.g. on a full
+/// disk, leaves `path` as it was and removes the temporary file.
+pub async fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let written = match tokio::fs::write(&temp_path, contents).await {
+        Ok(()) => tokio::fs::rename(&temp_path, path).await,
+        Err(e) => Err(e),
+    };
+    if written.is_err() {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+    }
+    written
+}
+
+/// Path of the original recording, or `NoAudio` if the meeting has none on disk
+pub async fn get_audio_path(meeting_dir: &Path, meeting_id: &str) -> Result<PathBuf, MeetingError> {
+    let metadata = read_meeting_metadata(meeting_dir)
+        .await
+        .map_err(MeetingError::FileError)?;
+    if metadata.is_some_and(|metadata| metadata.audio_less || metadata.audio_deleted) {
+        return Err(MeetingError::NoAudio(meeting_id.to_string()));
+    }
+
+    let audio_path = meeting_dir.join(format!("{}.ogg", meeting_id));
+    if !audio_path.exists() {
+        return Err(MeetingError::NoAudio(meeting_id.to_string()));
+    }
+
+    Ok(audio_path)
+}
+
+pub async fn has_audio(meeting_dir: &Path, meeting_id: &str) -> bool {
+    get_audio_path(meeting_dir, meeting_id).await.is_ok()
+}
+
+/// Read `meeting.json` from a meeting directory, `None` if it doesn't exist yet
+pub async fn read_meeting_metadata(meeting_dir: &Path) -> Result<Option<MeetingMetadata>, String> {
+    let metadata_path = meeting_dir.join("meeting.json");
+    if !metadata_path.exists() {
+        return Ok(None);
+    }
+
+    let content = tokio::fs::read_to_string(&metadata_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut metadata: MeetingMetadata = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse meeting metadata: {}", e))?;
+    migrate_name_emoji(&mut metadata);
+    Ok(Some(metadata))
+}
+
+/// Read-modify-write `meeting.json`, keeping every field the update doesn't touch
+pub async fn update_meeting_metadata<F>(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    update: F,
+) -> Result<MeetingMetadata, String>
+where
+    F: FnOnce(&mut MeetingMetadata),
+{
+    let _guard = lock_meeting(meeting_dir).await;
+    let mut metadata = read_meeting_metadata(meeting_dir)
+        .await?
+        .unwrap_or_else(|| MeetingMetadata {
+            id: meeting_id.to_string(),
+            created_at: Some(Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
+            ..Default::default()
+        });
+
+    update(&mut metadata);
+    write_meeting_metadata(meeting_dir, &metadata).await?;
+
+    Ok(metadata)
+}
+
+async fn write_meeting_metadata(
+    meeting_dir: &Path,
+    metadata: &MeetingMetadata,
+) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(metadata).map_err(|e| e.to_string())?;
+    write_atomic(&meeting_dir.join("meeting.json"), json)
+        .await
+        .map_err(|e| format!("Failed to write metadata: {}", e))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeetingRepair {
+    /// Id from `meeting.json`, `None` if it already matched the folder
+    pub old_id: Option<String>,
+    /// Files renamed from `<old_id>…` to `<id>…`, relative to the meeting directory
+    pub renamed_files: Vec<String>,
+}
+
+/// Files named after `old_id`, in the meeting directory and among the audio chunks
+async fn files_named_after(meeting_dir: &Path, old_id: &str) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for dir in [
+        meeting_dir.to_path_buf(),
+        transcription_chunks_dir(meeting_dir),
+    ] {
+        let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let named_after = file_name
+                .strip_prefix(old_id)
+                .is_some_and(|rest| rest.starts_with('.') || rest.starts_with("_chunk_"));
+            if named_after && entry.path().is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Match `meeting.json` and the `<id>.*` files to the folder name, e.g. after a
+/// sync tool created `recording-123 (conflicted copy)`. The old id is kept in
+/// `aliases`. Nothing is renamed if any target already exists.
+pub async fn repair_meeting_in(
+    meeting_dir: &Path,
+    meeting_id: &str,
+) -> Result<MeetingRepair, String> {
+    let _guard = lock_meeting(meeting_dir).await;
+    let Some(mut metadata) = read_meeting_metadata(meeting_dir).await? else {
+        return Ok(MeetingRepair {
+            old_id: None,
+            renamed_files: Vec::new(),
+        });
+    };
+    if metadata.id == meeting_id {
+        return Ok(MeetingRepair {
+            old_id: None,
+            renamed_files: Vec::new(),
+        });
+    }
+    let old_id = metadata.id.clone();
+
+    let renames: Vec<(PathBuf, PathBuf)> = files_named_after(meeting_dir, &old_id)
+        .await?
+        .into_iter()
+        .map(|path| {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let target =
+                path.with_file_name(format!("{}{}", meeting_id, &file_name[old_id.len()..]));
+            (path, target)
+        })
+        .collect();
+    if let Some((_, target)) = renames.iter().find(|(_, target)| target.exists()) {
+        return Err(format!(
+            "{} already exists, not renaming anything",
+            target.display()
+        ));
+    }
+
+    let mut renamed_files = Vec::new();
+    for (source, target) in &renames {
+        tokio::fs::rename(source, target)
+            .await
+            .map_err(|e| format!("Failed to rename {}: {}", source.display(), e))?;
+        renamed_files.push(
+            target
+                .strip_prefix(meeting_dir)
+                .unwrap_or(target)
+                .to_string_lossy()
+                .into_owned(),
+        );
+    }
+
+    // Audio chunk entries refer to the renamed files
+    let mut manifest = read_manifest(meeting_dir).await?;
+    if !manifest.audio_chunks.is_empty() {
+        let old_prefix = format!("{}_chunk_", old_id);
+        let new_prefix = format!("{}_chunk_", meeting_id);
+        for audio_chunk in &mut manifest.audio_chunks {
+            audio_chunk.file = audio_chunk.file.replace(&old_prefix, &new_prefix);
+        }
+        write_manifest(meeting_dir, &manifest).await?;
+    }
+
+    if !metadata.aliases.contains(&old_id) {
+        metadata.aliases.push(old_id.clone());
+    }
+    metadata.id = meeting_id.to_string();
+    write_meeting_metadata(meeting_dir, &metadata).await?;
+
+    println!(
+        "Repaired meeting {} (was {}), renamed {} files",
+        meeting_id,
+        old_id,
+        renamed_files.len()
+    );
+    Ok(MeetingRepair {
+        old_id: Some(old_id),
+        renamed_files,
+    })
+}
+
+/// Whether a grapheme cluster is an emoji, including ZWJ sequences, flags,
+/// skin tones and keycaps. Text characters only count with an emoji
+/// presentation selector, so "©" or "1" stay part of the name.
+fn is_emoji_grapheme(grapheme: &str) -> bool {
+    let Some(first) = grapheme.chars().next() else {
+        return false;
+    };
+    let pictographic = matches!(
+        u32::from(first),
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x2300..=0x23FF
+    );
+    pictographic || grapheme.contains(['\u{FE0F}', '\u{20E3}'])
+}
+
+/// Split a leading emoji off a name like "🔥 Budget Meeting". `None` if the
+/// name doesn't start with one or is nothing but the emoji.
+pub fn split_leading_emoji(name: &str) -> Option<(String, String)> {
+    let name = name.trim_start();
+    let emoji = name.graphemes(true).next()?;
+    if !is_emoji_grapheme(emoji) {
+        return None;
+    }
+    let rest = name[emoji.len()..].trim();
+    (!rest.is_empty()).then(|| (emoji.to_string(), rest.to_string()))
+}
+
+/// Names from before the emoji was stored on its own start with it
+pub fn migrate_name_emoji(metadata: &mut MeetingMetadata) {
+    if metadata.emoji.is_some() {
+        return;
+    }
+    if let Some((emoji, name)) = metadata.name.as_deref().and_then(split_leading_emoji) {
+        metadata.emoji = Some(emoji);
+        metadata.name = Some(name);
+    }
+}
+
+/// Set the display name, keeping everything else in `meeting.json`. The emoji
+/// is kept unless a new one is given, or the name starts with one. An empty
+/// emoji removes it.
+pub async fn set_meeting_name(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    name: String,
+    emoji: Option<String>,
+) -> Result<(), String> {
+    let (emoji, name) = match (emoji, split_leading_emoji(&name)) {
+        (Some(emoji), _) => (Some(emoji.trim().to_string()), name.trim().to_string()),
+        (None, Some((emoji, name))) => (Some(emoji), name),
+        (None, None) => (None, name.trim().to_string()),
+    };
+
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.name = Some(name);
+        if let Some(emoji) = emoji {
+            metadata.emoji = Some(emoji).filter(|emoji| !emoji.is_empty());
+        }
+    })
+    .await
+    .map(|_| ())
+}
+
+pub async fn set_pinned(meeting_dir: &Path, meeting_id: &str, pinned: bool) -> Result<(), String> {
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.pinned = pinned;
+    })
+    .await
+    .map(|_| ())
+}
 
 #[tauri::command]
-pub async fn save_speaker_names(
+pub async fn set_meeting_pinned(
     app: AppHandle,
     meeting_id: &str,
-    names: HashMap<String, String>,
+    pinned: bool,
 ) -> Result<(), String> {
     let app_dir = app
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
-    let base_dir = app_dir.join("uploads").join(meeting_id);
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    set_pinned(&meeting_dir, meeting_id, pinned).await
+}
+
+#[tauri::command]
+pub async fn repair_meeting(app: AppHandle, meeting_id: &str) -> Result<MeetingRepair, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
 
-    let transcript_txt_path = base_dir.join(format!("{}.txt", meeting_id));
-    let transcript_json_path = base_dir.join(format!("{}.json", meeting_id));
+    repair_meeting_in(&meeting_dir, meeting_id).await
+}
 
-    let mut transcript_txt = tokio::fs::read_to_string(&transcript_txt_path)
+/// Give the meeting its own summary style, `None` goes back to the settings
+#[tauri::command]
+pub async fn set_meeting_summary_style(
+    app: AppHandle,
+    meeting_id: &str,
+    style: Option<SummaryStyle>,
+) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    update_meeting_metadata(&meeting_dir, meeting_id, |metadata| {
+        metadata.summary_style = style;
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Decide for the meeting whether its summary names speakers, `None` goes back to the settings
+#[tauri::command]
+pub async fn set_meeting_attribute_speakers(
+    app: AppHandle,
+    meeting_id: &str,
+    attribute_speakers: Option<bool>,
+) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    update_meeting_metadata(&meeting_dir, meeting_id, |metadata| {
+        metadata.attribute_speakers = attribute_speakers;
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Write the meeting's summaries in `language`, `None` goes back to the LLM config
+#[tauri::command]
+pub async fn set_meeting_language(
+    app: AppHandle,
+    meeting_id: &str,
+    language: Option<Language>,
+) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    update_meeting_metadata(&meeting_dir, meeting_id, |metadata| {
+        metadata.language = language;
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Create a meeting without audio from a Teams/Zoom VTT transcript
+#[tauri::command]
+pub async fn create_meeting_from_transcript(
+    app: AppHandle,
+    file_path: &str,
+    name: Option<String>,
+) -> Result<String, String> {
+    let source_path = Path::new(file_path);
+    let is_vtt = source_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("vtt"));
+    if !is_vtt {
+        return Err(format!("Unsupported transcript format: {}", file_path));
+    }
+
+    let content = tokio::fs::read_to_string(source_path)
         .await
-        .map_err(|e| e.to_string())?;
-    let mut transcript_json = tokio::fs::read_to_string(&transcript_json_path)
+        .map_err(|e| format!("Failed to read transcript file: {}", e))?;
+    let transcript = parse_vtt(&content)?;
+    let text = transcript.to_text();
+
+    // Same check as before summarizing, an empty import would only fail later
+    let min_chars = {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        state.llm_config.min_transcript_chars
+    };
+    validate_transcript(&text, min_chars)?;
+
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let uploads = app_dir.join("uploads");
+
+    let meeting_id = new_meeting_id(&uploads);
+    let meeting_dir = uploads.join(&meeting_id);
+    tokio::fs::create_dir_all(&meeting_dir)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| format!("Failed to create meeting directory: {}", e))?;
 
-    for (key, value) in &names {
-        //     Key:     Old Name
-        //     Value:   New Name
-        // Replace old name with new name in transcript text
-        transcript_txt = transcript_txt.replace(key.as_str(), value.as_str());
+    let json = serde_json::to_string(&transcript).map_err(|e| e.to_string())?;
+    tokio::fs::write(meeting_dir.join(format!("{}.json", meeting_id)), json)
+        .await
+        .map_err(|e| format!("Failed to write transcript JSON: {}", e))?;
+    tokio::fs::write(meeting_dir.join(format!("{}.txt", meeting_id)), text)
+        .await
+        .map_err(|e| format!("Failed to write transcript: {}", e))?;
 
-        // Replace old name with new name in transcript JSON
-        transcript_json = transcript_json.replace(key.as_str(), value.as_str());
+    let name = name.or_else(|| {
+        source_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    });
+    update_meeting_metadata(&meeting_dir, &meeting_id, |metadata| {
+        metadata.name = name;
+        metadata.audio_less = true;
+    })
+    .await?;
+
+    println!(
+        "Imported transcript with {} segments as {}",
+        transcript.segments.len(),
+        meeting_id
+    );
+
+    Ok(meeting_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    /// A meeting directory with a transcript but without `<id>.ogg`
+    async fn fixture_without_audio() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(
+            dir.path().join(format!("{}.txt", MEETING_ID)),
+            "[Anna]: Hallo",
+        )
+        .await
+        .unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_meeting_dirs_of_the_same_second_differ() {
+        let uploads = tempfile::tempdir().unwrap();
+        let uploads = uploads.path().join("uploads");
+
+        let (first, _) = create_meeting_dir(&uploads).await.unwrap();
+        let (second, second_dir) = create_meeting_dir(&uploads).await.unwrap();
+
+        assert_ne!(first, second);
+        assert!(second_dir.is_dir());
+        assert_eq!(second_dir, uploads.join(&second));
     }
 
-    // Write the updated transcript text back to the file
-    tokio::fs::write(&transcript_txt_path, transcript_txt)
+    #[test]
+    fn test_split_leading_emoji() {
+        let split = |name: &str| split_leading_emoji(name);
+        let pair = |emoji: &str, name: &str| Some((emoji.to_string(), name.to_string()));
+
+        assert_eq!(split("🔥 Budget Meeting"), pair("🔥", "Budget Meeting"));
+        // ZWJ sequences, skin tones, flags and keycaps are one emoji
+        assert_eq!(split("👩‍💻 Dev Sync"), pair("👩‍💻", "Dev Sync"));
+        assert_eq!(split("👨‍👩‍👧 Elternabend"), pair("👨‍👩‍👧", "Elternabend"));
+        assert_eq!(split("🏳️‍🌈 Pride Planung"), pair("🏳️‍🌈", "Pride Planung"));
+        assert_eq!(split("👍🏽 Abnahme"), pair("👍🏽", "Abnahme"));
+        assert_eq!(split("🇩🇪 Länderrunde"), pair("🇩🇪", "Länderrunde"));
+        assert_eq!(split("1️⃣ Erste Runde"), pair("1️⃣", "Erste Runde"));
+        assert_eq!(split("❤️ Feedback"), pair("❤️", "Feedback"));
+        assert_eq!(split("📅Jour fixe"), pair("📅", "Jour fixe"));
+
+        for name in [
+            "Budget Meeting",
+            "1. Quartal",
+            "© Recht",
+            "Über 🔥",
+            "🔥",
+            "",
+        ] {
+            assert_eq!(split(name), None, "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_keeps_the_emoji() {
+        let dir = tempfile::tempdir().unwrap();
+        // Written before the emoji had its own field
+        std::fs::write(
+            dir.path().join("meeting.json"),
+            r#"{"id": "recording-1700000000", "name": "👩‍💻 Dev Sync", "created_at": null}"#,
+        )
+        .unwrap();
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.emoji.as_deref(), Some("👩‍💻"));
+        assert_eq!(metadata.name.as_deref(), Some("Dev Sync"));
+
+        let read = || async {
+            let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+            (metadata.emoji, metadata.name.unwrap())
+        };
+        set_meeting_name(dir.path(), MEETING_ID, "Team Sync".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            read().await,
+            (Some("👩‍💻".to_string()), "Team Sync".to_string())
+        );
+
+        set_meeting_name(dir.path(), MEETING_ID, "🚀 Launch".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(read().await, (Some("🚀".to_string()), "Launch".to_string()));
+
+        set_meeting_name(
+            dir.path(),
+            MEETING_ID,
+            "Launch".to_string(),
+            Some(String::new()),
+        )
         .await
-        .map_err(|e| e.to_string())?;
-    // Write the updated transcript JSON back to the file
-    tokio::fs::write(&transcript_json_path, transcript_json)
+        .unwrap();
+        assert_eq!(read().await, (None, "Launch".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_missing_ogg_is_no_audio() {
+        let dir = fixture_without_audio().await;
+
+        let result = get_audio_path(dir.path(), MEETING_ID).await;
+        assert!(matches!(result, Err(MeetingError::NoAudio(_))));
+        assert!(!has_audio(dir.path(), MEETING_ID).await);
+    }
+
+    #[tokio::test]
+    async fn test_audio_less_meeting_is_no_audio() {
+        let dir = fixture_without_audio().await;
+        update_meeting_metadata(dir.path(), MEETING_ID, |metadata| {
+            metadata.audio_less = true;
+        })
         .await
-        .map_err(|e| e.to_string())?;
+        .unwrap();
+
+        let result = get_audio_path(dir.path(), MEETING_ID).await;
+        assert!(matches!(result, Err(MeetingError::NoAudio(_))));
+
+        // Commands surface the error as a string
+        let message: String = result.unwrap_err().into();
+        assert!(message.contains("No audio"));
+    }
+
+    #[tokio::test]
+    async fn test_existing_ogg_is_returned() {
+        let dir = fixture_without_audio().await;
+        let ogg_path = dir.path().join(format!("{}.ogg", MEETING_ID));
+        tokio::fs::write(&ogg_path, b"OggS").await.unwrap();
+
+        assert_eq!(
+            get_audio_path(dir.path(), MEETING_ID).await.unwrap(),
+            ogg_path
+        );
+        assert!(has_audio(dir.path(), MEETING_ID).await);
+    }
+
+    /// A sync conflict copy of `MEETING_ID` whose files still carry the old id
+    async fn conflicted_copy() -> (tempfile::TempDir, PathBuf, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let copy_id = format!("{} (conflicted copy)", MEETING_ID);
+        let meeting_dir = dir.path().join(&copy_id);
+        let chunks_dir = transcription_chunks_dir(&meeting_dir);
+        std::fs::create_dir_all(&chunks_dir).unwrap();
+
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            format!("{{\"id\": \"{}\", \"name\": \"Planung\"}}", MEETING_ID),
+        )
+        .unwrap();
+        std::fs::write(meeting_dir.join(format!("{}.ogg", MEETING_ID)), b"OggS").unwrap();
+        std::fs::write(
+            meeting_dir.join(format!("{}.txt", MEETING_ID)),
+            "[Anna]: Hallo",
+        )
+        .unwrap();
+        std::fs::write(meeting_dir.join(format!("{}.json", MEETING_ID)), "{}").unwrap();
+        std::fs::write(
+            chunks_dir.join(format!("{}_chunk_00.ogg", MEETING_ID)),
+            b"OggS",
+        )
+        .unwrap();
+        std::fs::write(meeting_dir.join("notes.md"), "Notizen").unwrap();
 
-    Ok(())
+        (dir, meeting_dir, copy_id)
+    }
+
+    #[tokio::test]
+    async fn test_repair_conflicted_copy() {
+        let (_dir, meeting_dir, copy_id) = conflicted_copy().await;
+        assert!(!has_audio(&meeting_dir, &copy_id).await);
+
+        let repair = repair_meeting_in(&meeting_dir, &copy_id).await.unwrap();
+        assert_eq!(repair.old_id.as_deref(), Some(MEETING_ID));
+        assert_eq!(repair.renamed_files.len(), 4);
+
+        // Lookups by the folder name work again
+        assert!(has_audio(&meeting_dir, &copy_id).await);
+        assert_eq!(
+            crate::read_transcript(&meeting_dir, &copy_id)
+                .await
+                .unwrap(),
+            "[Anna]: Hallo"
+        );
+        assert!(meeting_dir.join(format!("{}.json", copy_id)).exists());
+        assert!(transcription_chunks_dir(&meeting_dir)
+            .join(format!("{}_chunk_00.ogg", copy_id))
+            .exists());
+        assert!(meeting_dir.join("notes.md").exists());
+
+        let metadata = read_meeting_metadata(&meeting_dir).await.unwrap().unwrap();
+        assert_eq!(metadata.id, copy_id);
+        assert_eq!(metadata.aliases, vec![MEETING_ID.to_string()]);
+        assert_eq!(metadata.name.as_deref(), Some("Planung"));
+
+        // Nothing left to do
+        let repair = repair_meeting_in(&meeting_dir, &copy_id).await.unwrap();
+        assert_eq!(repair.old_id, None);
+        assert!(repair.renamed_files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_repair_does_not_overwrite() {
+        let (_dir, meeting_dir, copy_id) = conflicted_copy().await;
+        std::fs::write(meeting_dir.join(format!("{}.txt", copy_id)), "Neuer").unwrap();
+
+        assert!(repair_meeting_in(&meeting_dir, &copy_id).await.is_err());
+        assert!(meeting_dir.join(format!("{}.ogg", MEETING_ID)).exists());
+        assert_eq!(
+            read_meeting_metadata(&meeting_dir)
+                .await
+                .unwrap()
+                .unwrap()
+                .id,
+            MEETING_ID
+        );
+    }
 }
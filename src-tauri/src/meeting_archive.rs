@@ -0,0 +1,700 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::llm::file_manager::{
+    current_summarization_run_dir, finish_summarization_run, start_summarization_run,
+    summarization_dir,
+};
+use crate::llm::progress::ProgressEvents;
+use crate::meeting::{is_valid_meeting_id, new_meeting_id, update_meeting_metadata};
+use crate::AppState;
+
+const MANIFEST_ENTRY_NAME: &str = "manifest.json";
+const CHUNKS_ENTRY_DIR: &str = "chunks";
+/// Files from this size on report their progress while they are written
+const PROGRESS_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024;
+const COPY_BUFFER_BYTES: usize = 64 * 1024;
+
+/// A file of the meeting directory and its name in the archive
+#[derive(Debug, Clone, PartialEq)]
+struct ArchiveEntry {
+    name: String,
+    source: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveManifest {
+    meeting_id: String,
+    app_version: String,
+    exported_at: String,
+    files: Vec<String>,
+}
+
+/// Sent as `meeting-export-progress` while a large file is written
+#[derive(Debug, Clone, Serialize)]
+struct ExportProgress<'a> {
+    meeting_id: &'a str,
+    file: &'a str,
+    percent: u32,
+}
+
+/// Files of the meeting to archive, in archive order. Missing ones are left
+/// out, the chunk summaries are those of the current summarization run.
+async fn archive_entries(meeting_dir: &Path, meeting_id: &str) -> Vec<ArchiveEntry> {
+    let mut entries: Vec<ArchiveEntry> = [
+        ("meeting.json".to_string(), "meeting.json".to_string()),
+        ("audio.ogg".to_string(), format!("{}.ogg", meeting_id)),
+        ("transcript.txt".to_string(), format!("{}.txt", meeting_id)),
+        (
+            "transcript.json".to_string(),
+            format!("{}.json", meeting_id),
+        ),
+        ("summary.md".to_string(), "summary.md".to_string()),
+        ("summary.json".to_string(), "summary.json".to_string()),
+    ]
+    .into_iter()
+    .map(|(name, file_name)| ArchiveEntry {
+        name,
+        source: meeting_dir.join(file_name),
+    })
+    .filter(|entry| entry.source.is_file())
+    .collect();
+
+    if let Some(run_dir) = current_summarization_run_dir(meeting_dir).await {
+        let mut chunk_files: Vec<ArchiveEntry> = std::fs::read_dir(&run_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                file_name.starts_with("chunk_").then(|| ArchiveEntry {
+                    name: format!("{}/{}", CHUNKS_ENTRY_DIR, file_name),
+                    source: entry.path(),
+                })
+            })
+            .collect();
+        chunk_files.sort_by(|a, b| a.name.cmp(&b.name));
+        entries.extend(chunk_files);
+    }
+
+    entries
+}
+
+/// Copy `source` into the open archive entry, reporting progress for large files
+fn copy_entry<W: Write>(
+    source: &Path,
+    writer: &mut W,
+    progress: Option<(&ProgressEvents, &str, &str)>,
+) -> io::Result<()> {
+    let mut file = File::open(source)?;
+    let total = file.metadata()?.len();
+    let progress = progress.filter(|_| total >= PROGRESS_THRESHOLD_BYTES);
+
+    let mut buffer = vec![0; COPY_BUFFER_BYTES];
+    let mut written = 0u64;
+    let mut last_percent = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        writer.write_all(&buffer[..read])?;
+        written += read as u64;
+
+        if let Some((events, meeting_id, name)) = progress {
+            let percent = (written * 100 / total).min(100) as u32;
+            if percent > last_percent {
+                last_percent = percent;
+                events.emit(
+                    "meeting-export-progress",
+                    ExportProgress {
+                        meeting_id,
+                        file: name,
+                        percent,
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn write_archive(
+    target: &Path,
+    meeting_id: &str,
+    entries: &[ArchiveEntry],
+    manifest: &ArchiveManifest,
+    events: Option<&ProgressEvents>,
+) -> Result<(), String> {
+    let file = File::create(target)
+        .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    // Ogg is compressed already, deflating it again only costs time
+    let stored = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(true);
+
+    for entry in entries {
+        let options = if entry.name.ends_with(".ogg") {
+            stored
+        } else {
+            deflated
+        };
+        zip.start_file(entry.name.as_str(), options)
+            .map_err(|e| format!("Failed to add {}: {}", entry.name, e))?;
+        let progress = events.map(|events| (events, meeting_id, entry.name.as_str()));
+        copy_entry(&entry.source, &mut zip, progress)
+            .map_err(|e| format!("Failed to add {}: {}", entry.name, e))?;
+    }
+
+    let manifest = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize export manifest: {}", e))?;
+    zip.start_file(MANIFEST_ENTRY_NAME, deflated)
+        .and_then(|_| zip.write_all(&manifest).map_err(Into::into))
+        .map_err(|e| format!("Failed to add {}: {}", MANIFEST_ENTRY_NAME, e))?;
+
+    zip.finish()
+        .and_then(|writer| writer.into_inner().map_err(|e| e.into_error().into()))
+        .and_then(|file| file.sync_all().map_err(Into::into))
+        .map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+}
+
+/// Package the meeting in `meeting_dir` into the zip file `target`. The
+/// archive is written next to `target` first, a failed export leaves no
+/// partial file behind. Returns the archived file names.
+pub async fn export_meeting_archive(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    target: &Path,
+    app_version: &str,
+    exported_at: DateTime<Utc>,
+    events: Option<ProgressEvents>,
+) -> Result<Vec<String>, String> {
+    if !meeting_dir.is_dir() {
+        return Err(format!("Meeting {} not found", meeting_id));
+    }
+
+    let entries = archive_entries(meeting_dir, meeting_id).await;
+    let mut files: Vec<String> = entries.iter().map(|entry| entry.name.clone()).collect();
+
+    let meeting_id = meeting_id.to_string();
+    let app_version = app_version.to_string();
+    let manifest_files = files.clone();
+    let target = target.to_path_buf();
+    let partial = target.with_extension("zip.part");
+    let partial_path = partial.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let manifest = ArchiveManifest {
+            meeting_id: meeting_id.clone(),
+            app_version,
+            exported_at: exported_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            files: manifest_files,
+        };
+        write_archive(
+            &partial_path,
+            &meeting_id,
+            &entries,
+            &manifest,
+            events.as_ref(),
+        )?;
+        std::fs::rename(&partial_path, &target)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))
+    .and_then(|result| result);
+
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&partial).await;
+        return Err(e);
+    }
+
+    files.push(MANIFEST_ENTRY_NAME.to_string());
+    Ok(files)
+}
+
+/// Write the meeting with its audio, transcript, summary and chunk summaries
+/// into one zip file, refused while the meeting is transcribed
+#[tauri::command]
+pub async fn export_meeting(
+    app: AppHandle,
+    meeting_id: &str,
+    target_path: &str,
+) -> Result<Vec<String>, String> {
+    if !is_valid_meeting_id(meeting_id) {
+        return Err(format!("Invalid meeting id: {}", meeting_id));
+    }
+    {
+        let state = app.state::<tokio::sync::Mutex<AppState>>();
+        let state = state.lock().await;
+        if state.is_transcribing(meeting_id) {
+            return Err(format!(
+                "Meeting {} is still being transcribed, export it when the transcript is done",
+                meeting_id
+            ));
+        }
+    }
+
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    export_meeting_archive(
+        &meeting_dir,
+        meeting_id,
+        Path::new(target_path),
+        &app.package_info().version.to_string(),
+        Utc::now(),
+        Some(ProgressEvents::new(app.clone())),
+    )
+    .await
+}
+
+/// Entry names that stay inside the directory they are unpacked into
+fn is_safe_entry_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains('\\')
+        && Path::new(name)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Read and check the manifest of an exported archive. Every entry has to be
+/// a relative path without `..` and every file it lists has to be there.
+fn read_archive_manifest<R: Read + io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<ArchiveManifest, String> {
+    if let Some(name) = archive.file_names().find(|name| !is_safe_entry_name(name)) {
+        return Err(format!("Archive contains an unsafe path: {}", name));
+    }
+
+    let manifest: ArchiveManifest = {
+        let entry = archive
+            .by_name(MANIFEST_ENTRY_NAME)
+            .map_err(|_| "Not a meeting export, manifest.json is missing".to_string())?;
+        serde_json::from_reader(entry).map_err(|e| format!("Invalid manifest.json: {}", e))?
+    };
+    if !is_valid_meeting_id(&manifest.meeting_id) {
+        return Err(format!(
+            "Invalid meeting id in manifest.json: {}",
+            manifest.meeting_id
+        ));
+    }
+    if let Some(missing) = manifest
+        .files
+        .iter()
+        .find(|file| archive.index_for_name(file).is_none())
+    {
+        return Err(format!("Archive is missing {}", missing));
+    }
+    Ok(manifest)
+}
+
+/// Where an archive entry is unpacked to, relative to the meeting directory.
+/// `None` for entries export doesn't write and chunks not listed in the manifest.
+fn import_path(name: &str, meeting_id: &str, run_dir: Option<&Path>) -> Option<PathBuf> {
+    let file_name = match name {
+        "meeting.json" | "summary.md" | "summary.json" => name.to_string(),
+        "audio.ogg" => format!("{}.ogg", meeting_id),
+        "transcript.txt" => format!("{}.txt", meeting_id),
+        "transcript.json" => format!("{}.json", meeting_id),
+        _ => {
+            let chunk_file = name.strip_prefix(CHUNKS_ENTRY_DIR)?.strip_prefix('/')?;
+            if chunk_file.contains('/') {
+                return None;
+            }
+            return Some(run_dir?.join(chunk_file));
+        }
+    };
+    Some(PathBuf::from(file_name))
+}
+
+fn unpack_archive(
+    zip_path: &Path,
+    meeting_dir: &Path,
+    meeting_id: &str,
+    run_dir: Option<&Path>,
+) -> Result<(), String> {
+    let file = File::open(zip_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Invalid archive: {}", e))?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Invalid archive: {}", e))?;
+        if entry.is_dir() || entry.name() == MANIFEST_ENTRY_NAME {
+            continue;
+        }
+        let Some(relative) = import_path(entry.name(), meeting_id, run_dir) else {
+            println!("Warning: Skipping unknown archive entry {}", entry.name());
+            continue;
+        };
+
+        let path = meeting_dir.join(relative);
+        let mut output = File::create(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        io::copy(&mut entry, &mut output)
+            .map_err(|e| format!("Failed to unpack {}: {}", entry.name(), e))?;
+    }
+    Ok(())
+}
+
+/// Unpack an archive written by `export_meeting_archive` into a new meeting
+/// directory in `uploads`. The meeting keeps its id unless a meeting with
+/// that id exists, then it gets a fresh one and its files are named after it.
+pub async fn import_meeting_archive(zip_path: &Path, uploads: &Path) -> Result<String, String> {
+    let path = zip_path.to_path_buf();
+    let manifest = tokio::task::spawn_blocking(move || {
+        let file = File::open(&path).map_err(|e| format!("Failed to open archive: {}", e))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| format!("Invalid archive: {}", e))?;
+        read_archive_manifest(&mut archive)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))??;
+
+    let meeting_id = if uploads.join(&manifest.meeting_id).exists() {
+        new_meeting_id(uploads)
+    } else {
+        manifest.meeting_id.clone()
+    };
+    let meeting_dir = uploads.join(&meeting_id);
+    tokio::fs::create_dir_all(&meeting_dir)
+        .await
+        .map_err(|e| format!("Failed to create meeting directory: {}", e))?;
+
+    let result = unpack_into(zip_path, &meeting_dir, &meeting_id, &manifest).await;
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_dir_all(&meeting_dir).await;
+        return Err(e);
+    }
+
+    println!(
+        "Imported meeting {} exported by version {} as {}",
+        manifest.meeting_id, manifest.app_version, meeting_id
+    );
+    Ok(meeting_id)
+}
+
+async fn unpack_into(
+    zip_path: &Path,
+    meeting_dir: &Path,
+    meeting_id: &str,
+    manifest: &ArchiveManifest,
+) -> Result<(), String> {
+    let has_chunks = manifest
+        .files
+        .iter()
+        .any(|file| file.starts_with(CHUNKS_ENTRY_DIR));
+    let run_id = if has_chunks {
+        Some(start_summarization_run(meeting_dir).await?)
+    } else {
+        None
+    };
+    let run_dir = run_id
+        .as_ref()
+        .map(|run_id| summarization_dir(meeting_dir).join(run_id));
+
+    {
+        let zip_path = zip_path.to_path_buf();
+        let meeting_dir = meeting_dir.to_path_buf();
+        let meeting_id = meeting_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            unpack_archive(&zip_path, &meeting_dir, &meeting_id, run_dir.as_deref())
+        })
+        .await
+        .map_err(|e| format!("Import task failed: {}", e))??;
+    }
+
+    if let Some(run_id) = run_id {
+        finish_summarization_run(meeting_dir, &run_id).await?;
+    }
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.id = meeting_id.to_string();
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Import a meeting exported with `export_meeting`, returns its id
+#[tauri::command]
+pub async fn import_meeting(app: AppHandle, zip_path: &str) -> Result<String, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+
+    let meeting_id = import_meeting_archive(Path::new(zip_path), &app_dir.join("uploads")).await?;
+    if let Err(e) = app.emit("meeting-imported", &meeting_id) {
+        println!("Warning: Failed to emit meeting-imported: {}", e);
+    }
+    Ok(meeting_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::progress::ProgressSink;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+    use zip::ZipArchive;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    struct RecordingSink(Arc<Mutex<Vec<Value>>>);
+
+    impl ProgressSink for RecordingSink {
+        fn send(&self, _event: &str, payload: Value) -> Result<(), String> {
+            self.0.lock().unwrap().push(payload);
+            Ok(())
+        }
+    }
+
+    fn write_meeting(meeting_dir: &Path, audio_bytes: usize) {
+        let run_dir = meeting_dir.join("chunks/summarization/run1");
+        std::fs::create_dir_all(&run_dir).unwrap();
+        std::fs::write(meeting_dir.join("chunks/summarization/current_run"), "run1").unwrap();
+        std::fs::write(run_dir.join("chunk_002.txt"), "Zweiter Teil").unwrap();
+        std::fs::write(run_dir.join("chunk_001.txt"), "Erster Teil").unwrap();
+        std::fs::write(run_dir.join("chunk_001_summary.json"), "{}").unwrap();
+        std::fs::write(run_dir.join("manifest.json"), "{}").unwrap();
+
+        std::fs::write(meeting_dir.join("meeting.json"), "{}").unwrap();
+        std::fs::write(
+            meeting_dir.join(format!("{}.ogg", MEETING_ID)),
+            vec![7u8; audio_bytes],
+        )
+        .unwrap();
+        std::fs::write(
+            meeting_dir.join(format!("{}.txt", MEETING_ID)),
+            "[Anna]: Hallo",
+        )
+        .unwrap();
+        std::fs::write(meeting_dir.join("summary.md"), "# Plenum").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_archive_has_fixed_entry_names_and_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path().join(MEETING_ID);
+        write_meeting(&meeting_dir, 1024);
+        let target = dir.path().join("export.zip");
+        let exported_at = DateTime::parse_from_rfc3339("2025-03-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let files = export_meeting_archive(
+            &meeting_dir,
+            MEETING_ID,
+            &target,
+            "1.2.3",
+            exported_at,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&target).unwrap()).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        assert_eq!(names.len(), files.len());
+        assert_eq!(
+            files,
+            vec![
+                "meeting.json",
+                "audio.ogg",
+                "transcript.txt",
+                "summary.md",
+                "chunks/chunk_001.txt",
+                "chunks/chunk_001_summary.json",
+                "chunks/chunk_002.txt",
+                "manifest.json",
+            ]
+        );
+
+        let mut transcript = String::new();
+        archive
+            .by_name("transcript.txt")
+            .unwrap()
+            .read_to_string(&mut transcript)
+            .unwrap();
+        assert_eq!(transcript, "[Anna]: Hallo");
+        assert_eq!(archive.by_name("audio.ogg").unwrap().size(), 1024);
+
+        let manifest: Value =
+            serde_json::from_reader(archive.by_name("manifest.json").unwrap()).unwrap();
+        assert_eq!(manifest["app_version"], "1.2.3");
+        assert_eq!(manifest["exported_at"], "2025-03-01T10:00:00Z");
+        assert_eq!(manifest["files"][1], "audio.ogg");
+        assert!(!dir.path().join("export.zip.part").exists());
+    }
+
+    #[tokio::test]
+    async fn test_large_audio_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path().join(MEETING_ID);
+        write_meeting(&meeting_dir, PROGRESS_THRESHOLD_BYTES as usize);
+        let sent = Arc::new(Mutex::new(Vec::new()));
+
+        export_meeting_archive(
+            &meeting_dir,
+            MEETING_ID,
+            &dir.path().join("export.zip"),
+            "1.2.3",
+            Utc::now(),
+            Some(ProgressEvents::new(RecordingSink(sent.clone()))),
+        )
+        .await
+        .unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert!(sent.iter().all(|event| event["file"] == "audio.ogg"));
+        assert_eq!(sent.last().unwrap()["percent"], 100);
+        assert_eq!(sent.len(), 100, "one event per whole percent");
+    }
+
+    #[tokio::test]
+    async fn test_missing_meeting_leaves_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("export.zip");
+
+        let error = export_meeting_archive(
+            &dir.path().join(MEETING_ID),
+            MEETING_ID,
+            &target,
+            "1.2.3",
+            Utc::now(),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("not found"), "{}", error);
+
+        let missing_target = dir.path().join("missing").join("export.zip");
+        let meeting_dir = dir.path().join(MEETING_ID);
+        write_meeting(&meeting_dir, 16);
+        assert!(export_meeting_archive(
+            &meeting_dir,
+            MEETING_ID,
+            &missing_target,
+            "1.2.3",
+            Utc::now(),
+            None
+        )
+        .await
+        .is_err());
+        assert!(!target.exists());
+    }
+
+    async fn exported_archive(dir: &Path) -> PathBuf {
+        let meeting_dir = dir.join("uploads").join(MEETING_ID);
+        write_meeting(&meeting_dir, 1024);
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            format!(r#"{{"id":"{}","name":"Plenum"}}"#, MEETING_ID),
+        )
+        .unwrap();
+        let target = dir.join("export.zip");
+        export_meeting_archive(&meeting_dir, MEETING_ID, &target, "1.2.3", Utc::now(), None)
+            .await
+            .unwrap();
+        target
+    }
+
+    #[tokio::test]
+    async fn test_import_renames_a_taken_meeting_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = exported_archive(dir.path()).await;
+        let uploads = dir.path().join("uploads");
+
+        let meeting_id = import_meeting_archive(&archive, &uploads).await.unwrap();
+        assert_ne!(meeting_id, MEETING_ID);
+
+        let meeting_dir = uploads.join(&meeting_id);
+        assert_eq!(
+            crate::read_transcript(&meeting_dir, &meeting_id)
+                .await
+                .unwrap(),
+            "[Anna]: Hallo"
+        );
+        assert!(meeting_dir.join(format!("{}.ogg", meeting_id)).exists());
+        let metadata = crate::meeting::read_meeting_metadata(&meeting_dir)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.id, meeting_id);
+        assert_eq!(metadata.name.as_deref(), Some("Plenum"));
+        let run_dir = current_summarization_run_dir(&meeting_dir).await.unwrap();
+        assert!(run_dir.join("chunk_002.txt").exists());
+
+        // Into an empty library the id is kept
+        let other_uploads = dir.path().join("other");
+        std::fs::create_dir_all(&other_uploads).unwrap();
+        assert_eq!(
+            import_meeting_archive(&archive, &other_uploads)
+                .await
+                .unwrap(),
+            MEETING_ID
+        );
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unsafe_archives() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        std::fs::create_dir_all(&uploads).unwrap();
+        let manifest =
+            r#"{"meeting_id":"recording-1","app_version":"1","exported_at":"","files":[]}"#;
+
+        for (entries, expected) in [
+            (vec![("transcript.txt", "x")], "manifest.json is missing"),
+            (vec![("manifest.json", "{}")], "Invalid manifest.json"),
+            (
+                vec![("manifest.json", manifest), ("../evil.txt", "x")],
+                "unsafe path",
+            ),
+            (
+                vec![("manifest.json", manifest), ("/etc/evil", "x")],
+                "unsafe path",
+            ),
+            (
+                vec![(
+                    "manifest.json",
+                    r#"{"meeting_id":"../up","app_version":"1","exported_at":"","files":[]}"#,
+                )],
+                "Invalid meeting id",
+            ),
+            (
+                vec![(
+                    "manifest.json",
+                    r#"{"meeting_id":"recording-1","app_version":"1","exported_at":"","files":["summary.md"]}"#,
+                )],
+                "missing summary.md",
+            ),
+        ] {
+            let path = dir.path().join("import.zip");
+            write_zip(&path, &entries);
+            let error = import_meeting_archive(&path, &uploads).await.unwrap_err();
+            assert!(error.contains(expected), "{}", error);
+        }
+        assert_eq!(std::fs::read_dir(&uploads).unwrap().count(), 0);
+        assert!(!dir.path().join("evil.txt").exists());
+    }
+}
@@ -0,0 +1,456 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::jobs::tracked_output;
+use crate::settings::load_settings;
+use crate::whisperx::mlx_whisper_command;
+
+/// Where models are cached when the cache is redirected into the app data dir
+const MODEL_CACHE_DIR_NAME: &str = "model-cache";
+/// Prefix of model directories in the Hugging Face hub cache, `models--org--name`
+const HF_MODEL_PREFIX: &str = "models--";
+/// openai-whisper keeps each model as one checkpoint file, `large-v3.pt`
+const WHISPER_CHECKPOINT_EXTENSION: &str = "pt";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CachedModel {
+    /// `org/name` for hub models, the directory or file name otherwise
+    pub name: String,
+    /// What `delete_cached_model` removes, a hub model with all its snapshots
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelCacheInfo {
+    /// Every directory that was searched, whether it exists or not
+    pub cache_dirs: Vec<String>,
+    pub models: Vec<CachedModel>,
+    pub total_bytes: u64,
+}
+
+/// Environment for spawned transcription commands. Empty unless the cache is
+/// redirected, in which case downloads end up in the app data dir.
+pub async fn cache_env(app: &AppHandle) -> Vec<(&'static str, PathBuf)> {
+    let redirect = load_settings(app)
+        .await
+        .map(|settings| settings.redirect_model_cache)
+        .unwrap_or_default();
+    if !redirect {
+        return Vec::new();
+    }
+
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    redirected_env(&app_dir.join(MODEL_CACHE_DIR_NAME))
+}
+
+fn redirected_env(cache_root: &Path) -> Vec<(&'static str, PathBuf)> {
+    vec![
+        ("HF_HOME", cache_root.join("huggingface")),
+        ("XDG_CACHE_HOME", cache_root.to_path_buf()),
+    ]
+}
+
+/// Cache directories used by whisperx and mlx_whisper, following the same
+/// environment variables the Python libraries read
+pub fn cache_dirs(env: impl Fn(&str) -> Option<String>, home: Option<&Path>) -> Vec<PathBuf> {
+    let var = |name: &str| {
+        env(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let Some(cache_home) = var("XDG_CACHE_HOME").or_else(|| home.map(|home| home.join(".cache")))
+    else {
+        return Vec::new();
+    };
+
+    let hf_hub = var("HF_HUB_CACHE")
+        .or_else(|| var("HF_HOME").map(|hf_home| hf_home.join("hub")))
+        .unwrap_or_else(|| cache_home.join("huggingface").join("hub"));
+    // Alignment and diarization models of whisperx
+    let torch = var("TORCH_HOME").unwrap_or_else(|| cache_home.join("torch"));
+    // Models of the original openai-whisper package
+    let whisper = cache_home.join("whisper");
+
+    vec![hf_hub, torch, whisper]
+}
+
+/// Size of all regular files below `path`. Symlinks are not followed, so the
+/// snapshots of the hub cache, which link into `blobs`, are not counted twice.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_file() {
+        return metadata.len();
+    }
+    if !metadata.is_dir() {
+        return 0;
+    }
+
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| dir_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Model directories, and the checkpoint files of openai-whisper
+fn is_model_entry(entry: &std::fs::DirEntry) -> bool {
+    match entry.file_type() {
+        Ok(file_type) if file_type.is_dir() => true,
+        Ok(file_type) if file_type.is_file() => Path::new(&entry.file_name())
+            .extension()
+            .is_some_and(|extension| extension == WHISPER_CHECKPOINT_EXTENSION),
+        _ => false,
+    }
+}
+
+/// Models in one cache directory, largest first
+pub fn scan_cache_dir(dir: &Path) -> Vec<CachedModel> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut models: Vec<CachedModel> = entries
+        .flatten()
+        .filter(is_model_entry)
+        .filter_map(|entry| {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            // Hub cache bookkeeping like `.locks`
+            if dir_name.starts_with('.') {
+                return None;
+            }
+            let name = match dir_name.strip_prefix(HF_MODEL_PREFIX) {
+                Some(repo) => repo.replacen("--", "/", 1),
+                None => dir_name,
+            };
+            let path = entry.path();
+            Some(CachedModel {
+                name,
+                size_bytes: dir_size(&path),
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+    models.sort_by_key(|model| std::cmp::Reverse(model.size_bytes));
+    models
+}
+
+pub fn model_cache_info(dirs: &[PathBuf]) -> ModelCacheInfo {
+    let models: Vec<CachedModel> = dirs.iter().flat_map(|dir| scan_cache_dir(dir)).collect();
+    ModelCacheInfo {
+        cache_dirs: dirs
+            .iter()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .collect(),
+        total_bytes: models.iter().map(|model| model.size_bytes).sum(),
+        models,
+    }
+}
+
+pub async fn current_cache_dirs(app: &AppHandle) -> Vec<PathBuf> {
+    let overrides = cache_env(app).await;
+    let home = app.path().home_dir().ok();
+    cache_dirs(
+        |name| {
+            overrides
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string_lossy().to_string())
+                .or_else(|| std::env::var(name).ok())
+        },
+        home.as_deref(),
+    )
+}
+
+/// A 1 second silent 16 kHz mono WAV, enough to make the model load
+fn silent_wav() -> Vec<u8> {
+    const SAMPLE_RATE: u32 = 16_000;
+    let data_len = SAMPLE_RATE * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+    wav
+}
+
+#[tauri::command]
+pub async fn get_model_cache_info(app: AppHandle) -> Result<ModelCacheInfo, String> {
+    let dirs = current_cache_dirs(&app).await;
+    tokio::task::spawn_blocking(move || model_cache_info(&dirs))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete the entry of `models` at `path`, exactly the directory or file
+/// that was listed and nothing around it
+pub fn remove_cached_model(models: Vec<CachedModel>, path: &str) -> Result<CachedModel, String> {
+    let model = models
+        .into_iter()
+        .find(|model| model.path == path)
+        .ok_or_else(|| format!("{} is not a cached model", path))?;
+
+    let is_dir = std::fs::symlink_metadata(&model.path)
+        .map_err(|e| format!("Failed to delete model {}: {}", model.name, e))?
+        .is_dir();
+    let removed = if is_dir {
+        std::fs::remove_dir_all(&model.path)
+    } else {
+        std::fs::remove_file(&model.path)
+    };
+    removed.map_err(|e| format!("Failed to delete model {}: {}", model.name, e))?;
+    Ok(model)
+}
+
+/// Delete a model returned by `get_model_cache_info`, by its `path`. Only
+/// paths found in the cache are accepted, so this can't be used to delete
+/// arbitrary paths.
+#[tauri::command]
+pub async fn delete_cached_model(app: AppHandle, path: String) -> Result<(), String> {
+    let dirs = current_cache_dirs(&app).await;
+    let model = tokio::task::spawn_blocking(move || {
+        remove_cached_model(model_cache_info(&dirs).models, &path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    println!(
+        "Deleted cached model {} ({} bytes)",
+        model.name, model.size_bytes
+    );
+    Ok(())
+}
+
+/// Download a model ahead of time by transcribing a second of silence
+#[tauri::command]
+pub async fn download_whisper_model(app: AppHandle, model: String) -> Result<(), String> {
+    if model.trim().is_empty() {
+        return Err("No model given".to_string());
+    }
+
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let work_dir = app_dir.join("model-download");
+    tokio::fs::create_dir_all(&work_dir)
+        .await
+        .map_err(|e| format!("Failed to create download directory: {}", e))?;
+    let audio_path = work_dir.join("silence.wav");
+    tokio::fs::write(&audio_path, silent_wav())
+        .await
+        .map_err(|e| format!("Failed to write sample audio: {}", e))?;
+
+    println!("Downloading whisper model {}", model);
+    let mut command = mlx_whisper_command(&model, &work_dir, &audio_path);
+    command.envs(cache_env(&app).await);
+    let result = tracked_output(&app, &mut command).await;
+
+    if let Err(e) = tokio::fs::remove_dir_all(&work_dir).await {
+        println!("Warning: Failed to clean up model download: {}", e);
+    }
+
+    let output = result.map_err(|e| format!("Failed to run mlx_whisper: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Model download failed: {}", stderr.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn write_file(path: &Path, len: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    #[test]
+    fn test_default_cache_dirs() {
+        let dirs = cache_dirs(env(&[]), Some(Path::new("/home/anna")));
+
+        assert_eq!(
+            dirs,
+            [
+                PathBuf::from("/home/anna/.cache/huggingface/hub"),
+                PathBuf::from("/home/anna/.cache/torch"),
+                PathBuf::from("/home/anna/.cache/whisper"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cache_dirs_follow_environment() {
+        let dirs = cache_dirs(
+            env(&[("XDG_CACHE_HOME", "/data/cache"), ("HF_HOME", "/data/hf")]),
+            Some(Path::new("/home/anna")),
+        );
+        assert_eq!(dirs[0], PathBuf::from("/data/hf/hub"));
+        assert_eq!(dirs[1], PathBuf::from("/data/cache/torch"));
+
+        let dirs = cache_dirs(
+            env(&[("HF_HOME", "/data/hf"), ("HF_HUB_CACHE", "/data/hub")]),
+            None,
+        );
+        assert!(dirs.is_empty());
+
+        let dirs = cache_dirs(
+            env(&[
+                ("HF_HOME", "/data/hf"),
+                ("HF_HUB_CACHE", "/data/hub"),
+                ("TORCH_HOME", ""),
+            ]),
+            Some(Path::new("/home/anna")),
+        );
+        assert_eq!(dirs[0], PathBuf::from("/data/hub"));
+        assert_eq!(dirs[1], PathBuf::from("/home/anna/.cache/torch"));
+    }
+
+    #[test]
+    fn test_redirected_cache_is_discovered() {
+        let root = Path::new("/app/model-cache");
+        let redirected = redirected_env(root);
+        let dirs = cache_dirs(
+            |name| {
+                redirected
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string_lossy().to_string())
+            },
+            Some(Path::new("/home/anna")),
+        );
+
+        assert!(dirs.iter().all(|dir| dir.starts_with(root)));
+    }
+
+    #[test]
+    fn test_scan_hub_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let hub = dir.path();
+        let turbo = hub.join("models--mlx-community--whisper-turbo");
+        write_file(&turbo.join("blobs").join("abc"), 3000);
+        write_file(&turbo.join("refs").join("main"), 40);
+        let small = hub.join("models--openai--whisper-small");
+        write_file(&small.join("blobs").join("def"), 1000);
+        std::fs::create_dir_all(hub.join(".locks")).unwrap();
+        write_file(&hub.join("version.txt"), 1);
+
+        #[cfg(unix)]
+        {
+            // Snapshots link into blobs and must not count twice
+            let snapshot = turbo.join("snapshots").join("123");
+            std::fs::create_dir_all(&snapshot).unwrap();
+            std::os::unix::fs::symlink(turbo.join("blobs").join("abc"), snapshot.join("model"))
+                .unwrap();
+        }
+
+        let models = scan_cache_dir(hub);
+        let names: Vec<&str> = models.iter().map(|model| model.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            ["mlx-community/whisper-turbo", "openai/whisper-small"]
+        );
+        assert_eq!(models[0].size_bytes, 3040);
+        assert_eq!(models[1].size_bytes, 1000);
+    }
+
+    #[test]
+    fn test_cache_info_totals() {
+        let dir = tempfile::tempdir().unwrap();
+        let hub = dir.path().join("hub");
+        let torch = dir.path().join("torch");
+        write_file(&hub.join("models--a--b").join("blobs").join("x"), 10);
+        write_file(&torch.join("pyannote").join("model.bin"), 5);
+
+        let info = model_cache_info(&[hub, torch, dir.path().join("missing")]);
+
+        assert_eq!(info.cache_dirs.len(), 3);
+        assert_eq!(info.models.len(), 2);
+        assert_eq!(info.total_bytes, 15);
+        assert_eq!(info.models[1].name, "pyannote");
+    }
+
+    #[test]
+    fn test_whisper_checkpoints_are_listed() {
+        let dir = tempfile::tempdir().unwrap();
+        let whisper = dir.path();
+        write_file(&whisper.join("large-v3.pt"), 30);
+        write_file(&whisper.join("small.pt"), 10);
+        write_file(&whisper.join("notes.txt"), 50);
+
+        let models = scan_cache_dir(whisper);
+        let names: Vec<&str> = models.iter().map(|model| model.name.as_str()).collect();
+
+        assert_eq!(names, ["large-v3.pt", "small.pt"]);
+        assert_eq!(models[0].size_bytes, 30);
+    }
+
+    #[test]
+    fn test_delete_removes_only_the_listed_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let hub = dir.path().join("hub");
+        let whisper = dir.path().join("whisper");
+        write_file(&hub.join("models--a--b").join("blobs").join("x"), 10);
+        write_file(&hub.join("models--a--c").join("blobs").join("y"), 10);
+        write_file(&whisper.join("small.pt"), 10);
+        write_file(&whisper.join("notes.txt"), 10);
+        let models = || model_cache_info(&[hub.clone(), whisper.clone()]).models;
+
+        let small = whisper.join("small.pt").to_string_lossy().to_string();
+        let removed = remove_cached_model(models(), &small).unwrap();
+        assert_eq!(removed.name, "small.pt");
+        assert!(!whisper.join("small.pt").exists());
+        assert!(whisper.join("notes.txt").exists());
+
+        let b = hub.join("models--a--b").to_string_lossy().to_string();
+        remove_cached_model(models(), &b).unwrap();
+        assert!(!hub.join("models--a--b").exists());
+        assert!(hub.join("models--a--c").exists());
+
+        // Not listed, so not deleted
+        for path in [
+            &whisper.join("notes.txt"),
+            &whisper,
+            &hub.join("models--a--c").join("blobs"),
+        ] {
+            let path = path.to_string_lossy().to_string();
+            assert!(remove_cached_model(models(), &path).is_err());
+            assert!(Path::new(&path).exists());
+        }
+    }
+
+    #[test]
+    fn test_silent_wav_is_one_second() {
+        let wav = silent_wav();
+
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + 32_000);
+    }
+}
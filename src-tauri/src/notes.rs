@@ -0,0 +1,146 @@
+use chrono::Utc;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::meeting::{lock_meeting, update_meeting_metadata, write_atomic};
+
+/// The user's own notes. Summary regeneration never touches this file.
+const NOTES_FILE_NAME: &str = "notes.md";
+
+/// Notes of a meeting as markdown, empty if none were written yet
+pub async fn read_notes(meeting_dir: &Path) -> Result<String, String> {
+    let path = meeting_dir.join(NOTES_FILE_NAME);
+    if !path.exists() {
+        return Ok(String::new());
+    }
+
+    tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read notes: {}", e))
+}
+
+pub async fn has_notes(meeting_dir: &Path) -> bool {
+    read_notes(meeting_dir)
+        .await
+        .is_ok_and(|notes| !notes.trim().is_empty())
+}
+
+/// Replace the notes and record the edit in `meeting.json`. Concurrent writes
+/// are serialized, the last one wins.
+pub async fn write_notes(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    markdown: &str,
+) -> Result<(), String> {
+    {
+        let _guard = lock_meeting(meeting_dir).await;
+        write_atomic(&meeting_dir.join(NOTES_FILE_NAME), markdown)
+            .await
+            .map_err(|e| format!("Failed to write notes: {}", e))?;
+    }
+
+    let updated_at = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.updated_at = Some(updated_at);
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_meeting_notes(app: AppHandle, meeting_id: &str) -> Result<String, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    read_notes(&meeting_dir).await
+}
+
+#[tauri::command]
+pub async fn set_meeting_notes(
+    app: AppHandle,
+    meeting_id: &str,
+    markdown: &str,
+) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    write_notes(&meeting_dir, meeting_id, markdown).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meeting::read_meeting_metadata;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    #[tokio::test]
+    async fn test_missing_notes_are_empty() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(read_notes(dir.path()).await.unwrap(), "");
+        assert!(!has_notes(dir.path()).await);
+    }
+
+    #[tokio::test]
+    async fn test_notes_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = "# Nächste Schritte\n\n- Angebot prüfen\n- **Jürgen** fragen\n";
+
+        write_notes(dir.path(), MEETING_ID, notes).await.unwrap();
+
+        assert_eq!(read_notes(dir.path()).await.unwrap(), notes);
+        assert!(has_notes(dir.path()).await);
+
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.id, MEETING_ID);
+        assert!(metadata.updated_at.is_some());
+
+        // Clearing the notes keeps an empty file
+        write_notes(dir.path(), MEETING_ID, "  \n").await.unwrap();
+        assert!(!has_notes(dir.path()).await);
+    }
+
+    #[tokio::test]
+    async fn test_notes_keep_existing_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        update_meeting_metadata(dir.path(), MEETING_ID, |metadata| {
+            metadata.name = Some("Jour fixe".to_string());
+        })
+        .await
+        .unwrap();
+
+        write_notes(dir.path(), MEETING_ID, "Notiz").await.unwrap();
+
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Jour fixe"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writes_do_not_interleave() {
+        let dir = tempfile::tempdir().unwrap();
+        let contents: Vec<String> = ('a'..='h')
+            .map(|c| c.to_string().repeat(256 * 1024))
+            .collect();
+
+        let writers = contents.iter().cloned().map(|content| {
+            let meeting_dir = dir.path().to_path_buf();
+            tokio::spawn(async move { write_notes(&meeting_dir, MEETING_ID, &content).await })
+        });
+        for writer in writers.collect::<Vec<_>>() {
+            writer.await.unwrap().unwrap();
+        }
+
+        // One of the writes won completely
+        let notes = read_notes(dir.path()).await.unwrap();
+        assert!(contents.contains(&notes));
+        assert!(read_meeting_metadata(dir.path()).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,385 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+
+use crate::audio::check_ffmpeg_installation;
+use crate::llm::test_llm_connection;
+use crate::settings::{load_settings, save_settings};
+use crate::whisperx::{
+    check_python_installation, check_whisperx_installation, download_python, download_whisperx,
+};
+use crate::AppState;
+
+/// Persisted first-run setup progress
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingState {
+    pub python_installed: bool,
+    pub whisperx_installed: bool,
+    pub ffmpeg_available: bool,
+    pub llm_configured: bool,
+    pub completed_at: Option<String>, // ISO 8601 date string
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    Python,
+    Whisperx,
+    Ffmpeg,
+    Llm,
+}
+
+impl OnboardingStep {
+    /// All steps in the order they have to be completed
+    pub const ALL: [OnboardingStep; 4] = [
+        OnboardingStep::Python,
+        OnboardingStep::Whisperx,
+        OnboardingStep::Ffmpeg,
+        OnboardingStep::Llm,
+    ];
+
+    /// Steps that have to be completed before this one can be attempted
+    fn requires(&self) -> &'static [OnboardingStep] {
+        match self {
+            OnboardingStep::Whisperx => &[OnboardingStep::Python],
+            _ => &[],
+        }
+    }
+}
+
+impl OnboardingState {
+    pub fn is_done(&self, step: OnboardingStep) -> bool {
+        match step {
+            OnboardingStep::Python => self.python_installed,
+            OnboardingStep::Whisperx => self.whisperx_installed,
+            OnboardingStep::Ffmpeg => self.ffmpeg_available,
+            OnboardingStep::Llm => self.llm_configured,
+        }
+    }
+
+    /// Set the flag for a step and keep `completed_at` in sync with the flags
+    pub fn set_done(&mut self, step: OnboardingStep, done: bool) {
+        match step {
+            OnboardingStep::Python => self.python_installed = done,
+            OnboardingStep::Whisperx => self.whisperx_installed = done,
+            OnboardingStep::Ffmpeg => self.ffmpeg_available = done,
+            OnboardingStep::Llm => self.llm_configured = done,
+        }
+
+        if self.missing_steps().is_empty() {
+            if self.completed_at.is_none() {
+                self.completed_at = Some(Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string());
+            }
+        } else {
+            self.completed_at = None;
+        }
+    }
+
+    pub fn missing_steps(&self) -> Vec<OnboardingStep> {
+        OnboardingStep::ALL
+            .into_iter()
+            .filter(|step| !self.is_done(*step))
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some() && self.missing_steps().is_empty()
+    }
+}
+
+/// Checks and installers backing the onboarding steps.
+///
+/// Abstracted so the state transitions can be tested without touching the system.
+pub trait SetupChecks {
+    /// Whether the step is currently satisfied on this machine
+    async fn verify(&self, step: OnboardingStep) -> bool;
+    /// Try to bring the step into a satisfied state (download, test connection, ...)
+    async fn perform(&self, step: OnboardingStep) -> Result<(), String>;
+}
+
+/// Setup checks that reuse the existing check/download commands
+pub struct AppSetupChecks {
+    app: AppHandle,
+}
+
+impl AppSetupChecks {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl SetupChecks for AppSetupChecks {
+    async fn verify(&self, step: OnboardingStep) -> bool {
+        match step {
+            OnboardingStep::Python => check_python_installation(self.app.clone()).await.is_ok(),
+            OnboardingStep::Whisperx => check_whisperx_installation(self.app.clone()).await.is_ok(),
+            OnboardingStep::Ffmpeg => check_ffmpeg_installation().await.is_ok(),
+            // Only validate the configuration here, the connection test happens when
+            // the step is advanced explicitly so startup doesn't depend on the server.
+            OnboardingStep::Llm => {
+                let state = self.app.state::<Mutex<AppState>>();
+                let state = state.lock().await;
+                state.llm_config.validate().is_ok()
+            }
+        }
+    }
+
+    async fn perform(&self, step: OnboardingStep) -> Result<(), String> {
+        match step {
+            OnboardingStep::Python => download_python(self.app.clone()).await,
+            OnboardingStep::Whisperx => download_whisperx(self.app.clone()).await,
+            OnboardingStep::Ffmpeg => check_ffmpeg_installation()
+                .await
+                .map_err(|e| format!("ffmpeg has to be installed on the system: {}", e)),
+            OnboardingStep::Llm => test_llm_connection(self.app.clone()).await.map(|_| ()),
+        }
+    }
+}
+
+/// Re-check every step that is marked as done and flip it back if it no longer holds
+pub async fn revalidate<C: SetupChecks>(state: &OnboardingState, checks: &C) -> OnboardingState {
+    let mut state = state.clone();
+    for step in OnboardingStep::ALL {
+        if state.is_done(step) && !checks.verify(step).await {
+            println!("Onboarding step {:?} is no longer satisfied", step);
+            state.set_done(step, false);
+        }
+    }
+    state
+}
+
+/// Run a single onboarding step and return the resulting state
+pub async fn advance<C: SetupChecks>(
+    state: &OnboardingState,
+    step: OnboardingStep,
+    checks: &C,
+) -> Result<OnboardingState, String> {
+    let mut state = state.clone();
+
+    if let Some(missing) = step.requires().iter().find(|s| !state.is_done(**s)) {
+        return Err(format!(
+            "Onboarding step {:?} requires {:?} to be completed first",
+            step, missing
+        ));
+    }
+
+    if !checks.verify(step).await {
+        checks.perform(step).await?;
+
+        if !checks.verify(step).await {
+            state.set_done(step, false);
+            return Err(format!("Onboarding step {:?} could not be verified", step));
+        }
+    }
+
+    state.set_done(step, true);
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn get_onboarding_state(app: AppHandle) -> Result<OnboardingState, String> {
+    let settings = load_settings(&app).await?;
+    Ok(settings.onboarding)
+}
+
+#[tauri::command]
+pub async fn advance_onboarding(
+    app: AppHandle,
+    step: OnboardingStep,
+) -> Result<OnboardingState, String> {
+    let mut settings = load_settings(&app).await?;
+    let checks = AppSetupChecks::new(app.clone());
+
+    let result = advance(&settings.onboarding, step, &checks).await;
+
+    // Persist the flipped flag even when the step failed
+    settings.onboarding = match &result {
+        Ok(state) => state.clone(),
+        Err(_) => {
+            let mut state = settings.onboarding.clone();
+            state.set_done(step, false);
+            state
+        }
+    };
+    save_settings(&app, &settings).await?;
+
+    result
+}
+
+/// Re-validate the persisted onboarding state at startup and notify the frontend
+/// when setup is incomplete
+pub async fn check_onboarding_on_startup(app: AppHandle) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    let checks = AppSetupChecks::new(app.clone());
+
+    let state = revalidate(&settings.onboarding, &checks).await;
+    if state != settings.onboarding {
+        settings.onboarding = state.clone();
+        save_settings(&app, &settings).await?;
+    }
+
+    if !state.is_complete() {
+        app.emit("onboarding-required", state.missing_steps())
+            .map_err(|e| format!("Failed to emit onboarding-required: {}", e))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex as StdMutex;
+
+    /// Fake machine where `satisfied` steps pass their checks and `installable`
+    /// steps become satisfied once performed
+    struct MockChecks {
+        satisfied: StdMutex<HashSet<OnboardingStep>>,
+        installable: HashSet<OnboardingStep>,
+        performed: StdMutex<Vec<OnboardingStep>>,
+    }
+
+    impl MockChecks {
+        fn new(satisfied: &[OnboardingStep], installable: &[OnboardingStep]) -> Self {
+            Self {
+                satisfied: StdMutex::new(satisfied.iter().copied().collect()),
+                installable: installable.iter().copied().collect(),
+                performed: StdMutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl SetupChecks for MockChecks {
+        async fn verify(&self, step: OnboardingStep) -> bool {
+            self.satisfied.lock().unwrap().contains(&step)
+        }
+
+        async fn perform(&self, step: OnboardingStep) -> Result<(), String> {
+            self.performed.lock().unwrap().push(step);
+            if self.installable.contains(&step) {
+                self.satisfied.lock().unwrap().insert(step);
+                Ok(())
+            } else {
+                Err(format!("{:?} failed", step))
+            }
+        }
+    }
+
+    fn completed_state() -> OnboardingState {
+        let mut state = OnboardingState::default();
+        for step in OnboardingStep::ALL {
+            state.set_done(step, true);
+        }
+        state
+    }
+
+    #[test]
+    fn test_default_state_requires_all_steps() {
+        let state = OnboardingState::default();
+        assert_eq!(state.missing_steps(), OnboardingStep::ALL.to_vec());
+        assert!(!state.is_complete());
+    }
+
+    #[test]
+    fn test_completed_at_follows_flags() {
+        let mut state = completed_state();
+        assert!(state.is_complete());
+        assert!(state.completed_at.is_some());
+
+        state.set_done(OnboardingStep::Ffmpeg, false);
+        assert!(state.completed_at.is_none());
+        assert_eq!(state.missing_steps(), vec![OnboardingStep::Ffmpeg]);
+    }
+
+    #[tokio::test]
+    async fn test_advance_skips_perform_when_already_satisfied() {
+        let checks = MockChecks::new(&[OnboardingStep::Ffmpeg], &[]);
+        let state = advance(&OnboardingState::default(), OnboardingStep::Ffmpeg, &checks)
+            .await
+            .unwrap();
+
+        assert!(state.ffmpeg_available);
+        assert!(checks.performed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_advance_performs_missing_step() {
+        let checks = MockChecks::new(&[], &[OnboardingStep::Python]);
+        let state = advance(&OnboardingState::default(), OnboardingStep::Python, &checks)
+            .await
+            .unwrap();
+
+        assert!(state.python_installed);
+        assert_eq!(
+            *checks.performed.lock().unwrap(),
+            vec![OnboardingStep::Python]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_advance_fails_when_perform_fails() {
+        let checks = MockChecks::new(&[], &[]);
+        let result = advance(&OnboardingState::default(), OnboardingStep::Llm, &checks).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_whisperx_requires_python() {
+        let checks = MockChecks::new(&[], &[OnboardingStep::Whisperx]);
+        let result = advance(
+            &OnboardingState::default(),
+            OnboardingStep::Whisperx,
+            &checks,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(checks.performed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_last_step_sets_completed_at() {
+        let mut state = completed_state();
+        state.set_done(OnboardingStep::Llm, false);
+
+        let checks = MockChecks::new(&[], &[OnboardingStep::Llm]);
+        let state = advance(&state, OnboardingStep::Llm, &checks).await.unwrap();
+
+        assert!(state.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_flips_removed_step() {
+        // Python directory was deleted, so python and whisperx no longer pass
+        let checks = MockChecks::new(&[OnboardingStep::Ffmpeg, OnboardingStep::Llm], &[]);
+        let state = revalidate(&completed_state(), &checks).await;
+
+        assert!(!state.python_installed);
+        assert!(!state.whisperx_installed);
+        assert!(state.ffmpeg_available);
+        assert!(state.llm_configured);
+        assert!(state.completed_at.is_none());
+        assert_eq!(
+            state.missing_steps(),
+            vec![OnboardingStep::Python, OnboardingStep::Whisperx]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_does_not_mark_unfinished_steps() {
+        let checks = MockChecks::new(&OnboardingStep::ALL, &[]);
+        let state = revalidate(&OnboardingState::default(), &checks).await;
+
+        assert_eq!(state, OnboardingState::default());
+    }
+
+    #[test]
+    fn test_state_deserializes_with_missing_fields() {
+        let state: OnboardingState = serde_json::from_str(r#"{"python_installed":true}"#).unwrap();
+        assert!(state.python_installed);
+        assert!(!state.whisperx_installed);
+    }
+}
@@ -0,0 +1,428 @@
+//! Minimal streaming PDF writer shared by the document exports.
+//!
+//! Pages are written to the output as soon as they are full, so memory use
+//! doesn't grow with the document length. Text uses the standard Helvetica
+//! fonts with WinAnsi encoding, which covers German umlauts and common
+//! punctuation without embedding font files.
+
+use std::io::{self, Write};
+
+const PAGE_WIDTH: f32 = 595.28; // A4
+const PAGE_HEIGHT: f32 = 841.89;
+const MARGIN: f32 = 56.0;
+const FOOTER_SIZE: f32 = 9.0;
+const LINE_SPACING: f32 = 1.35;
+
+const CATALOG_ID: usize = 1;
+const PAGES_ID: usize = 2;
+const REGULAR_FONT_ID: usize = 3;
+const BOLD_FONT_ID: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// A piece of text in a single font, paragraphs are made of one or more spans
+pub struct Span<'a> {
+    pub font: Font,
+    pub text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    pub fn regular(text: &'a str) -> Self {
+        Self {
+            font: Font::Regular,
+            text,
+        }
+    }
+
+    pub fn bold(text: &'a str) -> Self {
+        Self {
+            font: Font::Bold,
+            text,
+        }
+    }
+}
+
+/// Counts written bytes for the cross-reference table
+struct CountingWriter<W: Write> {
+    inner: W,
+    position: usize,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.position += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub struct PdfDocument<W: Write> {
+    out: CountingWriter<W>,
+    /// Byte offset of every object, indexed by object id - 1
+    offsets: Vec<usize>,
+    page_ids: Vec<usize>,
+    content: Vec<u8>,
+    cursor_y: f32,
+    page_open: bool,
+}
+
+impl<W: Write> PdfDocument<W> {
+    pub fn new(writer: W) -> io::Result<Self> {
+        let mut document = Self {
+            out: CountingWriter {
+                inner: writer,
+                position: 0,
+            },
+            offsets: vec![0; BOLD_FONT_ID],
+            page_ids: Vec::new(),
+            content: Vec::new(),
+            cursor_y: 0.0,
+            page_open: false,
+        };
+
+        document.out.write_all(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n")?;
+        document.write_font(REGULAR_FONT_ID, "Helvetica")?;
+        document.write_font(BOLD_FONT_ID, "Helvetica-Bold")?;
+        Ok(document)
+    }
+
+    /// Width available for text between the margins
+    pub fn text_width(&self) -> f32 {
+        PAGE_WIDTH - 2.0 * MARGIN
+    }
+
+    /// Write a word-wrapped paragraph, starting new pages as needed
+    pub fn paragraph(&mut self, spans: &[Span], size: f32) -> io::Result<()> {
+        let line_height = size * LINE_SPACING;
+        for line in wrap_spans(spans, size, self.text_width()) {
+            self.ensure_space(line_height)?;
+            self.cursor_y -= line_height;
+
+            let mut text = format!("BT {} {} Td ", MARGIN, self.cursor_y).into_bytes();
+            for (font, words) in line {
+                text.extend_from_slice(
+                    format!("/{} {} Tf (", font.resource_name(), size).as_bytes(),
+                );
+                text.extend(encode_text(&words));
+                text.extend_from_slice(b") Tj ");
+            }
+            text.extend_from_slice(b"ET\n");
+            self.content.extend(text);
+        }
+        Ok(())
+    }
+
+    /// Vertical space, never carried over to the next page
+    pub fn spacer(&mut self, height: f32) {
+        if self.page_open && self.cursor_y - height > MARGIN {
+            self.cursor_y -= height;
+        }
+    }
+
+    pub fn new_page(&mut self) -> io::Result<()> {
+        if self.page_open {
+            self.finish_page()?;
+        }
+        self.page_open = true;
+        self.cursor_y = PAGE_HEIGHT - MARGIN;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<usize> {
+        if self.page_open || self.page_ids.is_empty() {
+            self.page_open = true;
+            self.finish_page()?;
+        }
+
+        let kids = self
+            .page_ids
+            .iter()
+            .map(|id| format!("{} 0 R", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.write_object(
+            PAGES_ID,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids,
+                self.page_ids.len()
+            )
+            .as_bytes(),
+        )?;
+        self.write_object(
+            CATALOG_ID,
+            format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES_ID).as_bytes(),
+        )?;
+
+        let xref_offset = self.out.position;
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            xref.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1,
+            CATALOG_ID,
+            xref_offset
+        ));
+        self.out.write_all(xref.as_bytes())?;
+        self.out.flush()?;
+
+        Ok(self.page_ids.len())
+    }
+
+    fn ensure_space(&mut self, height: f32) -> io::Result<()> {
+        if !self.page_open || self.cursor_y - height < MARGIN {
+            self.new_page()?;
+        }
+        Ok(())
+    }
+
+    /// Write the current page with its page number and release its content
+    fn finish_page(&mut self) -> io::Result<()> {
+        let page_number = self.page_ids.len() + 1;
+        let footer = format!("Page {}", page_number);
+        let footer_x = PAGE_WIDTH - MARGIN - text_width(&footer, Font::Regular, FOOTER_SIZE);
+        self.content.extend_from_slice(
+            format!(
+                "BT /F1 {} Tf {} {} Td ({}) Tj ET\n",
+                FOOTER_SIZE,
+                footer_x,
+                MARGIN / 2.0,
+                footer
+            )
+            .as_bytes(),
+        );
+
+        let content = std::mem::take(&mut self.content);
+        let content_id = self.allocate_id();
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        self.write_object(content_id, &stream)?;
+
+        let page_id = self.allocate_id();
+        self.write_object(
+            page_id,
+            format!(
+                "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 {} 0 R /F2 {} 0 R >> >> /Contents {} 0 R >>",
+                PAGES_ID, PAGE_WIDTH, PAGE_HEIGHT, REGULAR_FONT_ID, BOLD_FONT_ID, content_id
+            )
+            .as_bytes(),
+        )?;
+
+        self.page_ids.push(page_id);
+        self.page_open = false;
+        Ok(())
+    }
+
+    fn write_font(&mut self, id: usize, base_font: &str) -> io::Result<()> {
+        self.write_object(
+            id,
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                base_font
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn allocate_id(&mut self) -> usize {
+        self.offsets.push(0);
+        self.offsets.len()
+    }
+
+    fn write_object(&mut self, id: usize, body: &[u8]) -> io::Result<()> {
+        self.offsets[id - 1] = self.out.position;
+        self.out.write_all(format!("{} 0 obj\n", id).as_bytes())?;
+        self.out.write_all(body)?;
+        self.out.write_all(b"\nendobj\n")
+    }
+}
+
+/// Break spans into lines no wider than `max_width`, each line being a list of
+/// runs in a single font
+fn wrap_spans(spans: &[Span], size: f32, max_width: f32) -> Vec<Vec<(Font, String)>> {
+    let space_width = text_width(" ", Font::Regular, size);
+    let mut lines = Vec::new();
+    let mut line: Vec<(Font, String)> = Vec::new();
+    let mut line_width = 0.0;
+
+    for span in spans {
+        for word in span.text.split_whitespace() {
+            let word_width = text_width(word, span.font, size);
+            let needed = if line.is_empty() {
+                word_width
+            } else {
+                space_width + word_width
+            };
+
+            if !line.is_empty() && line_width + needed > max_width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+
+            let separator = if line.is_empty() { "" } else { " " };
+            match line.last_mut() {
+                Some((font, text)) if *font == span.font => {
+                    text.push_str(separator);
+                    text.push_str(word);
+                }
+                _ => line.push((span.font, format!("{}{}", separator, word))),
+            }
+            line_width += if separator.is_empty() {
+                word_width
+            } else {
+                needed
+            };
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+pub fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let units: u32 = text.chars().map(|c| glyph_width(c, font)).sum();
+    units as f32 * size / 1000.0
+}
+
+/// Helvetica advance widths in 1/1000 em
+fn glyph_width(c: char, font: Font) -> u32 {
+    const ASCII_WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+        278, // ' '../
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // 0..9
+        278, 278, 584, 584, 584, 556, 1015, // :..@
+        667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // A..M
+        722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // N..Z
+        278, 278, 278, 469, 556, 333, // [..`
+        556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // a..m
+        556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // n..z
+        334, 260, 334, 584, // {..~
+    ];
+
+    let regular = match c {
+        ' '..='~' => u32::from(ASCII_WIDTHS[c as usize - 32]),
+        'Ä' | 'Ö' | 'Ü' => 722,
+        'ß' => 611,
+        '—' | '…' | '€' => 1000,
+        _ => 556,
+    };
+
+    match font {
+        Font::Regular => regular,
+        // Helvetica-Bold is roughly 10% wider, overestimating only wraps earlier
+        Font::Bold => regular * 11 / 10,
+    }
+}
+
+/// Encode text as a WinAnsi PDF string body, escaping delimiters
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '\u{A0}'..='\u{FF}' => c as u32 as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '™' => 0x99,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraps_long_paragraphs() {
+        let text = "wort ".repeat(200);
+        let lines = wrap_spans(&[Span::regular(&text)], 10.0, 200.0);
+
+        assert!(lines.len() > 1);
+        for line in &lines {
+            let width: f32 = line
+                .iter()
+                .map(|(font, text)| text_width(text, *font, 10.0))
+                .sum();
+            assert!(width <= 200.0);
+        }
+    }
+
+    #[test]
+    fn test_keeps_fonts_per_run() {
+        let lines = wrap_spans(
+            &[Span::bold("Anna:"), Span::regular("Guten Morgen")],
+            10.0,
+            500.0,
+        );
+
+        assert_eq!(
+            lines,
+            vec![vec![
+                (Font::Bold, "Anna:".to_string()),
+                (Font::Regular, " Guten Morgen".to_string()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_encodes_umlauts_and_escapes() {
+        assert_eq!(encode_text("Größe (ä)"), b"Gr\xF6\xDFe \\(\xE4\\)".to_vec());
+        assert_eq!(encode_text("🚀 – ok"), b"? \x96 ok".to_vec());
+    }
+
+    #[test]
+    fn test_writes_valid_structure() {
+        let mut output = Vec::new();
+        let mut document = PdfDocument::new(&mut output).unwrap();
+        document.new_page().unwrap();
+        document
+            .paragraph(&[Span::regular("Hallo Welt")], 12.0)
+            .unwrap();
+        let pages = document.finish().unwrap();
+
+        assert_eq!(pages, 1);
+        let text = String::from_utf8_lossy(&output);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 1"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+    }
+}
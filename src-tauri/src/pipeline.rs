@@ -0,0 +1,342 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::{Arc, Mutex as StdMutex};
+use tauri::{AppHandle, Listener, Manager};
+use tokio::sync::Mutex;
+
+use crate::audio::convert_user_audio;
+use crate::error::{PipelineError, PipelineStage};
+use crate::llm::generate_summary;
+use crate::llm::models::SummaryResult;
+use crate::llm::progress::ProgressEvents;
+use crate::meeting::is_valid_meeting_id;
+use crate::whisperx::transcribe_with_chunking;
+use crate::AppState;
+
+/// Progress events of the single steps, forwarded as `pipeline-progress`
+const STAGE_EVENTS: &[&str] = &[
+    "audio-conversion-progress",
+    "transcription-progress",
+    "summarization-chunk-start",
+    "summarization-chunk-progress",
+];
+
+/// Sent as `pipeline-progress`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineProgress {
+    /// `None` while converting, the meeting is created at the end of it
+    pub meeting_id: Option<String>,
+    pub stage: PipelineStage,
+    /// Of the current stage, 0 to 100
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineResult {
+    pub meeting_id: String,
+    pub summary: SummaryResult,
+}
+
+/// Turns the events of the running step into progress of its stage. Only
+/// whole percents going forward are passed on.
+#[derive(Debug)]
+struct PipelineTracker {
+    meeting_id: Option<String>,
+    stage: PipelineStage,
+    /// Steps the summarization announced in `summarization-chunk-start`
+    summary_steps: usize,
+    last_percent: Option<u32>,
+}
+
+impl PipelineTracker {
+    fn new() -> Self {
+        Self {
+            meeting_id: None,
+            stage: PipelineStage::Converting,
+            summary_steps: 0,
+            last_percent: None,
+        }
+    }
+
+    fn progress(&mut self, percent: f64) -> Option<PipelineProgress> {
+        let percent = percent.clamp(0.0, 100.0);
+        let whole_percent = percent.floor() as u32;
+        if self.last_percent.is_some_and(|last| whole_percent <= last) {
+            return None;
+        }
+        self.last_percent = Some(whole_percent);
+        Some(PipelineProgress {
+            meeting_id: self.meeting_id.clone(),
+            stage: self.stage,
+            percent: whole_percent as f64,
+        })
+    }
+
+    fn start_stage(&mut self, stage: PipelineStage) -> Option<PipelineProgress> {
+        self.stage = stage;
+        self.last_percent = None;
+        self.progress(0.0)
+    }
+
+    fn finish_stage(&mut self) -> Option<PipelineProgress> {
+        self.progress(100.0)
+    }
+
+    fn observe(&mut self, event: &str, payload: &Value) -> Option<PipelineProgress> {
+        let percent = match (self.stage, event) {
+            (PipelineStage::Converting, "audio-conversion-progress") => {
+                payload["percent"].as_f64()?
+            }
+            (PipelineStage::Transcribing, "transcription-progress") => {
+                if payload["meeting_id"].as_str() != self.meeting_id.as_deref() {
+                    return None;
+                }
+                payload["percent"].as_f64()?
+            }
+            (PipelineStage::Summarizing, "summarization-chunk-start") => {
+                self.summary_steps = payload.as_u64()? as usize;
+                return None;
+            }
+            (PipelineStage::Summarizing, "summarization-chunk-progress") => {
+                if self.summary_steps == 0 {
+                    return None;
+                }
+                // Sent when a step starts, with the number of steps done before it
+                payload.as_u64()? as f64 / self.summary_steps as f64 * 100.0
+            }
+            _ => return None,
+        };
+        self.progress(percent)
+    }
+}
+
+struct Pipeline {
+    app: AppHandle,
+    events: ProgressEvents,
+    tracker: Arc<StdMutex<PipelineTracker>>,
+}
+
+impl Pipeline {
+    fn update(&self, update: impl FnOnce(&mut PipelineTracker) -> Option<PipelineProgress>) {
+        // The lock is released before emitting, listeners of the event may update too
+        let progress = update(&mut self.tracker.lock().unwrap_or_else(|e| e.into_inner()));
+        if let Some(progress) = progress {
+            self.events.emit("pipeline-progress", progress);
+        }
+    }
+
+    async fn run(&self, source: &str) -> Result<PipelineResult, PipelineError> {
+        let app_dir = self
+            .app
+            .path()
+            .app_local_data_dir()
+            .expect("Failed to get app local data directory");
+        let uploads = app_dir.join("uploads");
+
+        let meeting_id = if is_valid_meeting_id(source) && uploads.join(source).is_dir() {
+            self.update(|tracker| {
+                tracker.meeting_id = Some(source.to_string());
+                None
+            });
+            source.to_string()
+        } else {
+            self.update(|tracker| tracker.start_stage(PipelineStage::Converting));
+            let meeting_id =
+                convert_user_audio(self.app.clone(), source)
+                    .await
+                    .map_err(|message| PipelineError {
+                        stage: PipelineStage::Converting,
+                        meeting_id: None,
+                        message,
+                    })?;
+            self.update(|tracker| {
+                tracker.meeting_id = Some(meeting_id.clone());
+                tracker.finish_stage()
+            });
+            meeting_id
+        };
+        let failed = |stage| {
+            let meeting_id = meeting_id.clone();
+            move |message| PipelineError {
+                stage,
+                meeting_id: Some(meeting_id),
+                message,
+            }
+        };
+
+        // A meeting that is transcribed already, or imported as a transcript, goes on to the summary
+        let transcript_path = uploads
+            .join(&meeting_id)
+            .join(format!("{}.txt", meeting_id));
+        if !transcript_path.exists() {
+            self.update(|tracker| tracker.start_stage(PipelineStage::Transcribing));
+            let state = self.app.state::<Mutex<AppState>>();
+            transcribe_with_chunking(self.app.clone(), &meeting_id, None, state)
+                .await
+                .map_err(failed(PipelineStage::Transcribing))?;
+            self.update(PipelineTracker::finish_stage);
+        }
+
+        self.update(|tracker| tracker.start_stage(PipelineStage::Summarizing));
+        let summary = generate_summary(self.app.clone(), &meeting_id, None, None, None)
+            .await
+            .map_err(failed(PipelineStage::Summarizing))?;
+        self.update(PipelineTracker::finish_stage);
+
+        Ok(PipelineResult {
+            meeting_id,
+            summary,
+        })
+    }
+}
+
+/// Convert, transcribe and summarize a recording in one call. `source` is the
+/// path of an audio file or the id of a meeting, whose conversion and, if it
+/// has a transcript, transcription are skipped. Each stage claims its slot
+/// like the single commands, so `cancel_transcription` and
+/// `cancel_summarization` stop the pipeline as well.
+#[tauri::command]
+pub async fn process_meeting(
+    app: AppHandle,
+    source: &str,
+) -> Result<PipelineResult, PipelineError> {
+    let pipeline = Pipeline {
+        app: app.clone(),
+        events: ProgressEvents::new(app.clone()),
+        tracker: Arc::new(StdMutex::new(PipelineTracker::new())),
+    };
+
+    let listeners: Vec<_> = STAGE_EVENTS
+        .iter()
+        .map(|&event| {
+            let tracker = pipeline.tracker.clone();
+            let events = pipeline.events.clone();
+            app.listen(event, move |message| {
+                let Ok(payload) = serde_json::from_str::<Value>(message.payload()) else {
+                    return;
+                };
+                let progress = tracker
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .observe(event, &payload);
+                if let Some(progress) = progress {
+                    events.emit("pipeline-progress", progress);
+                }
+            })
+        })
+        .collect();
+
+    let result = pipeline.run(source).await;
+    for listener in listeners {
+        app.unlisten(listener);
+    }
+
+    match &result {
+        Ok(result) => {
+            pipeline
+                .events
+                .emit_terminal("pipeline-finished", result)
+                .await;
+        }
+        Err(e) => {
+            println!("Pipeline stopped: {}", e);
+            pipeline.events.emit_terminal("pipeline-failed", e).await;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn percents(tracker: &mut PipelineTracker, events: &[(&str, Value)]) -> Vec<f64> {
+        events
+            .iter()
+            .filter_map(|(event, payload)| tracker.observe(event, payload))
+            .map(|progress| progress.percent)
+            .collect()
+    }
+
+    #[test]
+    fn test_stage_events_become_stage_progress() {
+        let mut tracker = PipelineTracker::new();
+        assert_eq!(
+            tracker
+                .start_stage(PipelineStage::Converting)
+                .unwrap()
+                .percent,
+            0.0
+        );
+        let converting = [
+            (
+                "audio-conversion-progress",
+                json!({"meeting_id": "recording-1", "percent": 12.4}),
+            ),
+            (
+                "audio-conversion-progress",
+                json!({"meeting_id": "recording-1", "percent": 12.9}),
+            ),
+            (
+                "transcription-progress",
+                json!({"meeting_id": "recording-1", "percent": 50.0}),
+            ),
+            (
+                "audio-conversion-progress",
+                json!({"meeting_id": "recording-1", "percent": 80.0}),
+            ),
+        ];
+        assert_eq!(percents(&mut tracker, &converting), vec![12.0, 80.0]);
+
+        tracker.meeting_id = Some("recording-1".to_string());
+        let progress = tracker.finish_stage().unwrap();
+        assert_eq!(progress.meeting_id.as_deref(), Some("recording-1"));
+        assert_eq!(progress.percent, 100.0);
+
+        tracker.start_stage(PipelineStage::Transcribing);
+        let transcribing = [
+            (
+                "transcription-progress",
+                json!({"meeting_id": "recording-2", "percent": 40.0}),
+            ),
+            (
+                "transcription-progress",
+                json!({"meeting_id": "recording-1", "percent": 25.5}),
+            ),
+        ];
+        assert_eq!(percents(&mut tracker, &transcribing), vec![25.0]);
+
+        let progress = tracker.start_stage(PipelineStage::Summarizing).unwrap();
+        assert_eq!(progress.stage, PipelineStage::Summarizing);
+        let summarizing = [
+            ("summarization-chunk-progress", json!(0)),
+            ("summarization-chunk-start", json!(4)),
+            ("summarization-chunk-progress", json!(0)),
+            ("summarization-chunk-progress", json!(1)),
+            ("summarization-chunk-progress", json!(3)),
+        ];
+        assert_eq!(percents(&mut tracker, &summarizing), vec![25.0, 75.0]);
+    }
+
+    #[test]
+    fn test_error_names_the_stage() {
+        let error = PipelineError {
+            stage: PipelineStage::Transcribing,
+            meeting_id: Some("recording-1".to_string()),
+            message: "Transcription cancelled".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Transcribing failed: Transcription cancelled"
+        );
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({
+                "stage": "transcribing",
+                "meeting_id": "recording-1",
+                "message": "Transcription cancelled",
+            })
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+use crate::whisperx::{check_python_installation, get_python_executable_path};
+use crate::AppState;
+
+/// Prints the inventory of the embedded environment as one line of JSON.
+/// Packages that are missing or fail to import are reported as `null`.
+const INVENTORY_SCRIPT: &str = r#"
+import json, platform, sysconfig
+from importlib import metadata
+
+packages = []
+for name in ["whisperx", "torch", "torchaudio", "pyannote.audio", "faster-whisper", "ctranslate2", "mlx-whisper"]:
+    try:
+        version = metadata.version(name)
+    except metadata.PackageNotFoundError:
+        version = None
+    packages.append({"name": name, "version": version})
+
+cuda_available = None
+cuda_version = None
+try:
+    import torch
+    cuda_available = torch.cuda.is_available()
+    cuda_version = torch.version.cuda
+except Exception:
+    pass
+
+print(json.dumps({
+    "python_version": platform.python_version(),
+    "platform": sysconfig.get_platform(),
+    "machine": platform.machine(),
+    "packages": packages,
+    "cuda_available": cuda_available,
+    "cuda_version": cuda_version,
+}))
+"#;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageVersion {
+    pub name: String,
+    /// `None` when the package is not installed
+    pub version: Option<String>,
+}
+
+/// Versions in the embedded Python environment, for bug reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PythonEnvironmentInfo {
+    pub python_version: String,
+    /// Platform tag, e.g. `macosx-11.0-arm64` or `linux-x86_64`
+    pub platform: String,
+    pub machine: String,
+    pub packages: Vec<PackageVersion>,
+    /// `None` when torch could not be imported
+    pub cuda_available: Option<bool>,
+    pub cuda_version: Option<String>,
+}
+
+/// Read the JSON line of the inventory script. Importing torch may print
+/// warnings to stdout first, so the last line holding JSON counts.
+pub fn parse_environment_info(stdout: &str) -> Result<PythonEnvironmentInfo, String> {
+    let line = stdout
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with('{'))
+        .ok_or_else(|| "The Python environment printed no inventory".to_string())?;
+    serde_json::from_str(line).map_err(|e| format!("Failed to parse Python inventory: {}", e))
+}
+
+/// Last modification of the environment. Installing or removing a package
+/// changes `site-packages`, replacing the interpreter changes the root.
+pub fn environment_stamp(python_dir: &Path) -> Option<SystemTime> {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let root = modified(python_dir)?;
+    let site_packages = modified(&site_packages_dir(python_dir));
+    Some(site_packages.map_or(root, |site_packages| root.max(site_packages)))
+}
+
+fn site_packages_dir(python_dir: &Path) -> std::path::PathBuf {
+    python_dir
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages")
+}
+
+/// The inventory of the last run, valid while the environment is unchanged.
+/// Running the script takes seconds because it imports torch.
+#[derive(Default)]
+pub struct EnvironmentInfoCache {
+    entry: Option<(SystemTime, PythonEnvironmentInfo)>,
+}
+
+impl EnvironmentInfoCache {
+    pub fn get(&self, stamp: SystemTime) -> Option<&PythonEnvironmentInfo> {
+        match &self.entry {
+            Some((cached_stamp, info)) if *cached_stamp == stamp => Some(info),
+            _ => None,
+        }
+    }
+
+    pub fn store(&mut self, stamp: SystemTime, info: PythonEnvironmentInfo) {
+        self.entry = Some((stamp, info));
+    }
+}
+
+async fn run_inventory(python_dir: &Path) -> Result<PythonEnvironmentInfo, String> {
+    let output = Command::new(get_python_executable_path(python_dir))
+        .env("PYTHONPATH", site_packages_dir(python_dir))
+        .arg("-c")
+        .arg(INVENTORY_SCRIPT)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run Python: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Python inventory failed: {}", stderr.trim()));
+    }
+    parse_environment_info(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[tauri::command]
+pub async fn get_python_environment_info(app: AppHandle) -> Result<PythonEnvironmentInfo, String> {
+    check_python_installation(app.clone()).await?;
+    let python_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join("python");
+
+    let stamp = environment_stamp(&python_dir);
+    let state = app.state::<Mutex<AppState>>();
+    if let Some(stamp) = stamp {
+        if let Some(info) = state.lock().await.python_env_cache.get(stamp) {
+            return Ok(info.clone());
+        }
+    }
+
+    let info = run_inventory(&python_dir).await?;
+    if let Some(stamp) = stamp {
+        state
+            .lock()
+            .await
+            .python_env_cache
+            .store(stamp, info.clone());
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const OUTPUT: &str = r#"{"python_version": "3.12.10", "platform": "linux-x86_64", "machine": "x86_64", "packages": [{"name": "whisperx", "version": "3.3.1"}, {"name": "torch", "version": "2.5.1"}, {"name": "mlx-whisper", "version": null}], "cuda_available": false, "cuda_version": null}"#;
+
+    #[test]
+    fn test_parse_environment_info() {
+        let stdout = format!(
+            "/python/lib/torchaudio/_backend.py:42: UserWarning: no backend\n{}\n",
+            OUTPUT
+        );
+        let info = parse_environment_info(&stdout).unwrap();
+        assert_eq!(info.python_version, "3.12.10");
+        assert_eq!(info.platform, "linux-x86_64");
+        assert_eq!(
+            info.packages[1],
+            PackageVersion {
+                name: "torch".to_string(),
+                version: Some("2.5.1".to_string()),
+            }
+        );
+        assert_eq!(info.packages[2].version, None);
+        assert_eq!(info.cuda_available, Some(false));
+        assert_eq!(info.cuda_version, None);
+
+        assert!(parse_environment_info("").is_err());
+        assert!(parse_environment_info("Traceback (most recent call last):").is_err());
+        assert!(parse_environment_info("{\"python_version\": 3}").is_err());
+    }
+
+    #[test]
+    fn test_cache_is_invalidated_by_a_changed_environment() {
+        let info = parse_environment_info(OUTPUT).unwrap();
+        let installed = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut cache = EnvironmentInfoCache::default();
+        assert!(cache.get(installed).is_none());
+
+        cache.store(installed, info.clone());
+        assert_eq!(cache.get(installed), Some(&info));
+        assert!(cache.get(installed + Duration::from_secs(60)).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_environment_stamp_follows_site_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(environment_stamp(&dir.path().join("python")).is_none());
+
+        let python_dir = dir.path().join("python");
+        std::fs::create_dir_all(site_packages_dir(&python_dir)).unwrap();
+        let before = environment_stamp(&python_dir).unwrap();
+
+        // Like pip installing a package a minute later
+        let later = SystemTime::now() + Duration::from_secs(60);
+        std::fs::File::open(site_packages_dir(&python_dir))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let after = environment_stamp(&python_dir).unwrap();
+        assert_ne!(before, after);
+        assert_eq!(after, later);
+    }
+}
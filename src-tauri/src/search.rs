@@ -0,0 +1,386 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::llm::file_manager::read_summary_from;
+use crate::{list_meetings, MeetingSort};
+
+/// Snippets returned per meeting
+const MAX_MATCHES_PER_MEETING: usize = 5;
+/// Characters kept on either side of a match in its snippet
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Where in a meeting the query was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchField {
+    Name,
+    /// A task in the action items of `summary.json`
+    Todo,
+    Summary,
+    Transcript,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchMatch {
+    pub field: SearchField,
+    /// The matching line, shortened to the text around the match
+    pub snippet: String,
+    /// Character offset of the match in the name, task or file. Summary
+    /// offsets leave out the heading anchors.
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchResult {
+    pub meeting_id: String,
+    pub name: Option<String>,
+    pub matches: Vec<SearchMatch>,
+}
+
+/// Character offsets of `query` in `line`, ignoring case. `query` must
+/// already be lowercase.
+fn match_offsets(line: &str, query: &[char]) -> Vec<usize> {
+    // Lowercasing can turn one character into several, keep where each came from
+    let folded: Vec<(char, usize)> = line
+        .chars()
+        .enumerate()
+        .flat_map(|(index, c)| c.to_lowercase().map(move |lower| (lower, index)))
+        .collect();
+
+    let mut offsets = Vec::new();
+    let mut next_free = 0;
+    for start in 0..folded.len() {
+        let offset = folded[start].1;
+        if offset < next_free || folded.len() - start < query.len() {
+            continue;
+        }
+        if folded[start..start + query.len()]
+            .iter()
+            .zip(query)
+            .all(|((c, _), q)| c == q)
+        {
+            offsets.push(offset);
+            next_free = folded[start + query.len() - 1].1 + 1;
+        }
+    }
+    offsets
+}
+
+/// `line` around the match at character `offset`, with an ellipsis where it was cut
+fn snippet(line: &str, offset: usize, query_len: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let from = offset.saturating_sub(SNIPPET_CONTEXT);
+    let to = (offset + query_len + SNIPPET_CONTEXT).min(chars.len());
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[from..to]);
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    snippet.trim().to_string()
+}
+
+/// Matches in `text`, counting offsets from `base`, stopping at `limit`
+fn matches_in(
+    text: &str,
+    base: usize,
+    query: &[char],
+    field: SearchField,
+    limit: usize,
+) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+    let mut line_start = base;
+    for line in text.split_inclusive('\n') {
+        for offset in match_offsets(line, query) {
+            if matches.len() >= limit {
+                return matches;
+            }
+            matches.push(SearchMatch {
+                field,
+                snippet: snippet(line, offset, query.len()),
+                offset: line_start + offset,
+            });
+        }
+        line_start += line.chars().count();
+    }
+    matches
+}
+
+/// `line` without the ` <a id="..."></a>` anchors of the topic headings in
+/// `summary.md`, the slugs would otherwise match as if they were text
+fn strip_anchors(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("<a id=\"") {
+        let Some(end) = rest[start..].find("\"></a>") else {
+            break;
+        };
+        stripped.push_str(rest[..start].strip_suffix(' ').unwrap_or(&rest[..start]));
+        rest = &rest[start + end + "\"></a>".len()..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Matches in the file at `path`, read line by line so that only the first
+/// `limit` matches are ever looked for. A missing file has none.
+async fn matches_in_file(
+    path: &Path,
+    query: &[char],
+    field: SearchField,
+    limit: usize,
+) -> Vec<SearchMatch> {
+    let Ok(file) = File::open(path).await else {
+        return Vec::new();
+    };
+    let mut reader = BufReader::new(file);
+    let mut matches = Vec::new();
+    let mut line = String::new();
+    let mut line_start = 0;
+    while matches.len() < limit {
+        line.clear();
+        match reader.read_line(&mut line).await {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                println!("Warning: Failed to search {}: {}", path.display(), e);
+                break;
+            }
+        }
+        if field == SearchField::Summary {
+            line = strip_anchors(&line);
+        }
+        matches.extend(matches_in(
+            &line,
+            line_start,
+            query,
+            field,
+            limit - matches.len(),
+        ));
+        line_start += line.chars().count();
+    }
+    matches
+}
+
+/// Case-insensitive search through meeting names, action items, summaries and
+/// transcripts. Meetings are in the order of the meeting list.
+pub async fn search_meetings_in(uploads: &Path, query: &str) -> Result<Vec<SearchResult>, String> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+    for meeting in list_meetings(uploads, MeetingSort::default()).await? {
+        let id = meeting.metadata.id;
+        let meeting_dir = uploads.join(&id);
+        let remaining = |matches: &Vec<SearchMatch>| MAX_MATCHES_PER_MEETING - matches.len();
+
+        let mut matches: Vec<SearchMatch> = Vec::new();
+        if let Some(name) = &meeting.metadata.name {
+            matches.extend(matches_in(
+                name,
+                0,
+                &query,
+                SearchField::Name,
+                MAX_MATCHES_PER_MEETING,
+            ));
+        }
+        if meeting.has_summary && remaining(&matches) > 0 {
+            if let Ok(summary) = read_summary_from(&meeting_dir).await {
+                for todo in summary.todos {
+                    let limit = remaining(&matches);
+                    matches.extend(matches_in(&todo.task, 0, &query, SearchField::Todo, limit));
+                }
+            }
+        }
+        if remaining(&matches) > 0 {
+            let limit = remaining(&matches);
+            let summary = meeting_dir.join("summary.md");
+            matches.extend(matches_in_file(&summary, &query, SearchField::Summary, limit).await);
+        }
+        if remaining(&matches) > 0 {
+            let limit = remaining(&matches);
+            let transcript = meeting_dir.join(format!("{}.txt", id));
+            matches
+                .extend(matches_in_file(&transcript, &query, SearchField::Transcript, limit).await);
+        }
+
+        if !matches.is_empty() {
+            results.push(SearchResult {
+                meeting_id: id,
+                name: meeting.metadata.name,
+                matches,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn search_meetings(app: AppHandle, query: &str) -> Result<Vec<SearchResult>, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+
+    search_meetings_in(&app_dir.join("uploads"), query).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_meetings() {
+        let dir = tempfile::tempdir().unwrap();
+        for (id, name, transcript) in [
+            (
+                "recording-1",
+                "Haushalt 2025",
+                "[Anna]: Das Budget steht.\n[Ben]: Gut.",
+            ),
+            (
+                "recording-2",
+                "Jour fixe",
+                "[Anna]: Der HAUSHALT ist offen.",
+            ),
+            ("recording-3", "Retro", "[Ben]: Nichts Neues."),
+        ] {
+            let meeting_dir = dir.path().join(id);
+            std::fs::create_dir_all(&meeting_dir).unwrap();
+            std::fs::write(
+                meeting_dir.join("meeting.json"),
+                format!(
+                    r#"{{"id":"{}","name":"{}","created_at":"2025-01-0{}T10:00:00.000Z"}}"#,
+                    id,
+                    name,
+                    &id[10..]
+                ),
+            )
+            .unwrap();
+            std::fs::write(meeting_dir.join(format!("{}.txt", id)), transcript).unwrap();
+        }
+
+        let results = search_meetings_in(dir.path(), " haushalt ").await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].meeting_id, "recording-2");
+        assert_eq!(
+            results[0].matches,
+            vec![SearchMatch {
+                field: SearchField::Transcript,
+                snippet: "[Anna]: Der HAUSHALT ist offen.".to_string(),
+                offset: 12,
+            }]
+        );
+        assert_eq!(results[1].matches[0].field, SearchField::Name);
+
+        assert!(search_meetings_in(dir.path(), "  ")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_match_offsets_count_characters() {
+        let query: Vec<char> = "straße".chars().collect();
+        let line = "Die STRAẞE und die Straße: straßestraße";
+        assert_eq!(match_offsets(line, &query), vec![4, 19, 27, 33]);
+        // "İ" lowercases to two characters, offsets stay on the original text
+        let query: Vec<char> = "bul".chars().collect();
+        assert_eq!(match_offsets("İstanbul", &query), vec![5]);
+    }
+
+    #[test]
+    fn test_snippet_is_cut_around_the_match() {
+        let line = format!("{}Haushalt{}", "a".repeat(100), "b".repeat(100));
+        let snippet = snippet(&line, 100, 8);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert_eq!(snippet.chars().count(), SNIPPET_CONTEXT * 2 + 8 + 2);
+        assert!(snippet.contains("Haushalt"));
+    }
+
+    #[tokio::test]
+    async fn test_anchors_are_not_searched() {
+        assert_eq!(
+            strip_anchors("### Budget <a id=\"budget-2025\"></a>\n"),
+            "### Budget\n"
+        );
+        assert_eq!(strip_anchors("a <a id=\"x\"></a> b<a id=\"y\"></a>"), "a b");
+        assert_eq!(strip_anchors("<a id=\"offen"), "<a id=\"offen");
+
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_dir = dir.path().join("recording-1");
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            r#"{"id":"recording-1","created_at":"2025-01-01T10:00:00.000Z"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            meeting_dir.join("summary.md"),
+            "### Haushalt <a id=\"haushalt-2025\"></a>\n- steht\n",
+        )
+        .unwrap();
+
+        for query in ["2025", "a id"] {
+            let results = search_meetings_in(dir.path(), query).await.unwrap();
+            assert!(results.is_empty(), "{}: {:?}", query, results);
+        }
+        let results = search_meetings_in(dir.path(), "haushalt").await.unwrap();
+        assert_eq!(results[0].matches.len(), 1);
+        assert_eq!(results[0].matches[0].snippet, "### Haushalt");
+    }
+
+    #[tokio::test]
+    async fn test_todos_are_a_separate_match_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = "recording-1";
+        let meeting_dir = dir.path().join(id);
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            r#"{"id":"recording-1","created_at":"2025-01-01T10:00:00.000Z"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            meeting_dir.join("summary.json"),
+            crate::llm::test_harness::read_fixture("ollama/final.json"),
+        )
+        .unwrap();
+        let summary = read_summary_from(&meeting_dir).await.unwrap();
+        let task = summary.todos[0].task.clone();
+        std::fs::write(
+            meeting_dir.join("summary.md"),
+            format!("# Notes\n- {}\n", task),
+        )
+        .unwrap();
+        let transcript = format!("{}\n", task).repeat(10);
+        std::fs::write(meeting_dir.join(format!("{}.txt", id)), transcript).unwrap();
+
+        let results = search_meetings_in(dir.path(), &task).await.unwrap();
+        let matches = &results[0].matches;
+        assert_eq!(matches.len(), MAX_MATCHES_PER_MEETING);
+        assert_eq!(matches[0].field, SearchField::Todo);
+        assert_eq!(matches[0].offset, 0);
+        let summary_match = matches
+            .iter()
+            .find(|m| m.field == SearchField::Summary)
+            .unwrap();
+        assert_eq!(summary_match.offset, "# Notes\n- ".chars().count());
+        let transcript_offsets: Vec<usize> = matches
+            .iter()
+            .filter(|m| m.field == SearchField::Transcript)
+            .map(|m| m.offset)
+            .collect();
+        let line_length = task.chars().count() + 1;
+        assert_eq!(transcript_offsets[1], line_length);
+    }
+}
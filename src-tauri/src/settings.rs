@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::llm::{endpoint_privacy::EndpointPrivacy, prompts::SummaryStyle, usage::ModelPrice};
+use crate::onboarding::OnboardingState;
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+/// Format of `settings.json` written by this build
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Application settings persisted as `settings.json` in the app local data directory.
+///
+/// Every field uses serde defaults so that older settings files keep loading
+/// after new fields are added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub onboarding: OnboardingState,
+    pub delete_audio_after_transcription: AudioDeletionMode,
+    /// How long running child processes may keep going after the app was
+    /// asked to quit before they are killed
+    pub shutdown_grace_period_seconds: u64,
+    /// A transcription without any output for this long is considered hung and killed
+    pub transcription_idle_timeout_minutes: u64,
+    /// Shorter audio is rejected on import and not transcribed
+    pub min_audio_duration_seconds: u64,
+    /// Keep downloaded whisper models in the app data dir instead of `~/.cache`
+    pub redirect_model_cache: bool,
+    /// Per-model token prices for the usage report's cost estimates
+    pub model_prices: HashMap<String, ModelPrice>,
+    /// Default for meetings without their own summary style
+    pub summary_style: SummaryStyle,
+    /// Default for meetings that don't decide themselves whether bullets name speakers
+    pub attribute_speakers: bool,
+    /// Keep to-do assignees in summaries that don't name speakers
+    pub keep_todo_assignees: bool,
+    /// Read-only HTTP API for scripts on this machine
+    pub api_server: ApiServerSettings,
+    /// LLM endpoints that may receive meeting content without asking
+    pub endpoint_privacy: EndpointPrivacy,
+    /// Deleted meetings older than this are purged at startup, 0 keeps them forever
+    pub trash_retention_days: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            onboarding: OnboardingState::default(),
+            delete_audio_after_transcription: AudioDeletionMode::default(),
+            shutdown_grace_period_seconds: 5,
+            transcription_idle_timeout_minutes: 10,
+            min_audio_duration_seconds: 15,
+            redirect_model_cache: false,
+            model_prices: HashMap::new(),
+            summary_style: SummaryStyle::default(),
+            attribute_speakers: true,
+            keep_todo_assignees: false,
+            api_server: ApiServerSettings::default(),
+            endpoint_privacy: EndpointPrivacy::default(),
+            trash_retention_days: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiServerSettings {
+    pub enabled: bool,
+    /// Port on 127.0.0.1
+    pub port: u16,
+    /// Bearer token clients have to send, generated when the API is first enabled
+    pub token: String,
+}
+
+impl Default for ApiServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+            token: String::new(),
+        }
+    }
+}
+
+/// What to do with the recording once its transcript has been written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioDeletionMode {
+    /// Keep the audio
+    #[default]
+    Off,
+    /// Ask the user via the `audio-deletion-requested` event
+    Ask,
+    /// Delete the audio right away
+    Auto,
+}
+
+pub fn get_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app local data directory: {}", e))?;
+    Ok(app_dir.join(SETTINGS_FILE_NAME))
+}
+
+/// Load settings from disk, falling back to defaults when the file is missing or corrupt
+pub async fn load_settings_from(path: &Path) -> Settings {
+    match fs::read_to_string(path).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            println!("Warning: Failed to parse settings, using defaults: {}", e);
+            Settings::default()
+        }),
+        Err(_) => Settings::default(),
+    }
+}
+
+pub async fn save_settings_to(path: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(path, json)
+        .await
+        .map_err(|e| format!("Failed to write settings: {}", e))
+}
+
+pub async fn load_settings(app: &AppHandle) -> Result<Settings, String> {
+    let path = get_settings_path(app)?;
+    Ok(load_settings_from(&path).await)
+}
+
+pub async fn save_settings(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = get_settings_path(app)?;
+    save_settings_to(&path, settings).await
+}
+
+#[tauri::command]
+pub async fn get_settings(app: AppHandle) -> Result<Settings, String> {
+    load_settings(&app).await
+}
+
+#[tauri::command]
+pub async fn set_delete_audio_after_transcription(
+    app: AppHandle,
+    mode: AudioDeletionMode,
+) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.delete_audio_after_transcription = mode;
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_shutdown_grace_period(app: AppHandle, seconds: u64) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.shutdown_grace_period_seconds = seconds;
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_transcription_idle_timeout(app: AppHandle, minutes: u64) -> Result<(), String> {
+    if minutes == 0 {
+        return Err("The timeout must be at least one minute".to_string());
+    }
+    let mut settings = load_settings(&app).await?;
+    settings.transcription_idle_timeout_minutes = minutes;
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_redirect_model_cache(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.redirect_model_cache = enabled;
+    save_settings(&app, &settings).await
+}
+
+/// Set the price of a model, or remove it when `price` is `None`
+#[tauri::command]
+pub async fn set_model_price(
+    app: AppHandle,
+    model: String,
+    price: Option<ModelPrice>,
+) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    match price {
+        Some(price) => settings.model_prices.insert(model, price),
+        None => settings.model_prices.remove(&model),
+    };
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_min_audio_duration_seconds(app: AppHandle, seconds: u64) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.min_audio_duration_seconds = seconds;
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_trash_retention_days(app: AppHandle, days: u64) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.trash_retention_days = days;
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_speaker_attribution(
+    app: AppHandle,
+    attribute_speakers: bool,
+    keep_todo_assignees: bool,
+) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.attribute_speakers = attribute_speakers;
+    settings.keep_todo_assignees = keep_todo_assignees;
+    save_settings(&app, &settings).await
+}
+
+#[tauri::command]
+pub async fn set_summary_style(app: AppHandle, style: SummaryStyle) -> Result<(), String> {
+    let mut settings = load_settings(&app).await?;
+    settings.summary_style = style;
+    save_settings(&app, &settings).await
+}
@@ -0,0 +1,328 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::api_server::{apply_api_settings, generate_token};
+use crate::llm::config::get_llm_config_path;
+use crate::llm::LlmConfig;
+use crate::settings::{load_settings, save_settings, Settings, SETTINGS_VERSION};
+use crate::AppState;
+
+/// Format of the bundle written by `export_settings`
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Settings that stay on this machine: secrets, and the onboarding progress,
+/// which describes what is installed here. API keys are not listed because
+/// they live in the OS keyring and never are part of `Settings` or `LlmConfig`.
+const MACHINE_LOCAL_SETTINGS: &[&str] = &["/api_server/token", "/onboarding"];
+
+/// Everything needed to set up another machine the same way
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub bundle_version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    /// `settings.json` without the machine-local settings
+    pub settings: Value,
+    pub llm: Value,
+}
+
+fn remove_pointer(value: &mut Value, pointer: &str) -> Option<Value> {
+    let (parent, key) = pointer.rsplit_once('/')?;
+    value.pointer_mut(parent)?.as_object_mut()?.remove(key)
+}
+
+/// Set the value at `pointer`, creating the objects on the way
+fn set_pointer(value: &mut Value, pointer: &str, new_value: Value) {
+    let mut target = value;
+    for key in pointer.split('/').skip(1) {
+        if !target.is_object() {
+            *target = Value::Object(Default::default());
+        }
+        target = target
+            .as_object_mut()
+            .expect("target is an object")
+            .entry(key)
+            .or_insert(Value::Null);
+    }
+    *target = new_value;
+}
+
+/// Overlay `overlay` onto `base`. Objects are merged key by key, so e.g. model
+/// prices of both machines are kept, every other value is replaced.
+fn merge_json(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+pub fn build_bundle(
+    settings: &Settings,
+    llm: &LlmConfig,
+    now: DateTime<Utc>,
+) -> Result<SettingsBundle, String> {
+    let mut settings = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    for pointer in MACHINE_LOCAL_SETTINGS {
+        remove_pointer(&mut settings, pointer);
+    }
+
+    Ok(SettingsBundle {
+        bundle_version: BUNDLE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        settings,
+        llm: serde_json::to_value(llm)
+            .map_err(|e| format!("Failed to serialize LLM config: {}", e))?,
+    })
+}
+
+/// Parse a bundle, rejecting versions this build doesn't know before looking
+/// at the rest, which may have a different shape
+pub fn parse_bundle(content: &str) -> Result<SettingsBundle, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("Not a settings bundle: {}", e))?;
+
+    let bundle_version = value["bundle_version"]
+        .as_u64()
+        .ok_or("Not a settings bundle: bundle_version is missing")?;
+    if bundle_version > BUNDLE_VERSION as u64 {
+        return Err(format!(
+            "The settings bundle has version {}, this app only reads up to version {}. Update the app and import again.",
+            bundle_version, BUNDLE_VERSION
+        ));
+    }
+    if let Some(settings_version) = value["settings"]["version"].as_u64() {
+        if settings_version > SETTINGS_VERSION as u64 {
+            return Err(format!(
+                "The bundled settings have version {}, this app only reads up to version {}. Update the app and import again.",
+                settings_version, SETTINGS_VERSION
+            ));
+        }
+    }
+
+    serde_json::from_value(value).map_err(|e| format!("Invalid settings bundle: {}", e))
+}
+
+/// The settings and LLM config after importing `bundle`. With `merge` the
+/// bundle is laid over the current configuration, otherwise it replaces it.
+/// Secrets and the onboarding progress always stay as they are on this machine.
+pub fn apply_bundle(
+    bundle: &SettingsBundle,
+    current_settings: &Settings,
+    current_llm: &LlmConfig,
+    merge: bool,
+) -> Result<(Settings, LlmConfig), String> {
+    let current = serde_json::to_value(current_settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let mut incoming = bundle.settings.clone();
+    for pointer in MACHINE_LOCAL_SETTINGS {
+        remove_pointer(&mut incoming, pointer);
+    }
+
+    let mut settings = if merge {
+        let mut settings = current.clone();
+        merge_json(&mut settings, incoming);
+        settings
+    } else {
+        incoming
+    };
+    for pointer in MACHINE_LOCAL_SETTINGS {
+        if let Some(local) = current.pointer(pointer) {
+            set_pointer(&mut settings, pointer, local.clone());
+        }
+    }
+    let mut settings: Settings = serde_json::from_value(settings)
+        .map_err(|e| format!("Invalid settings in bundle: {}", e))?;
+    settings.version = SETTINGS_VERSION;
+
+    let mut llm = if merge {
+        serde_json::to_value(current_llm)
+            .map_err(|e| format!("Failed to serialize LLM config: {}", e))?
+    } else {
+        Value::Object(Default::default())
+    };
+    merge_json(&mut llm, bundle.llm.clone());
+    let llm: LlmConfig =
+        serde_json::from_value(llm).map_err(|e| format!("Invalid LLM config in bundle: {}", e))?;
+    llm.validate()
+        .map_err(|e| format!("Invalid LLM config in bundle: {}", e))?;
+
+    Ok((settings, llm))
+}
+
+/// Write all settings except secrets to one JSON file
+#[tauri::command]
+pub async fn export_settings(app: AppHandle, destination_path: &str) -> Result<(), String> {
+    let settings = load_settings(&app).await?;
+    let llm = {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        state.llm_config.clone()
+    };
+
+    let bundle = build_bundle(&settings, &llm, Utc::now())?;
+    let json = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize settings bundle: {}", e))?;
+    fs::write(Path::new(destination_path), json)
+        .await
+        .map_err(|e| format!("Failed to write settings bundle: {}", e))
+}
+
+/// Read a bundle written by `export_settings` and replace or merge the settings
+#[tauri::command]
+pub async fn import_settings(app: AppHandle, path: &str, merge: bool) -> Result<(), String> {
+    let content = fs::read_to_string(Path::new(path))
+        .await
+        .map_err(|e| format!("Failed to read settings bundle: {}", e))?;
+    let bundle = parse_bundle(&content)?;
+
+    let current_settings = load_settings(&app).await?;
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    let (mut settings, llm) = apply_bundle(&bundle, &current_settings, &state.llm_config, merge)?;
+
+    if settings.api_server.enabled && settings.api_server.token.is_empty() {
+        settings.api_server.token = generate_token();
+    }
+    save_settings(&app, &settings).await?;
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app local data directory: {}", e))?;
+    llm.save(&get_llm_config_path(&app_dir)).await?;
+    state.llm_config = llm;
+    drop(state);
+
+    apply_api_settings(&app, &settings.api_server).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::config::LlmProvider;
+    use crate::llm::usage::ModelPrice;
+
+    fn settings() -> Settings {
+        let mut settings = Settings {
+            shutdown_grace_period_seconds: 12,
+            ..Default::default()
+        };
+        settings.api_server.enabled = true;
+        settings.api_server.token = "local-secret".to_string();
+        settings.onboarding.whisperx_installed = true;
+        settings.onboarding.completed_at = Some("2026-10-01T09:00:00Z".to_string());
+        settings.model_prices.insert(
+            "gpt-4o".to_string(),
+            ModelPrice {
+                prompt_per_1k: 0.0025,
+                completion_per_1k: 0.01,
+            },
+        );
+        settings
+    }
+
+    fn llm() -> LlmConfig {
+        LlmConfig {
+            provider: LlmProvider::Ollama,
+            external_endpoint: "http://gpu-box:11434".to_string(),
+            external_model: "qwen2.5".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn json<T: Serialize>(value: &T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bundle = build_bundle(&settings(), &llm(), Utc::now()).unwrap();
+        let content = serde_json::to_string_pretty(&bundle).unwrap();
+        assert!(!content.contains("local-secret"));
+        assert!(!content.contains("onboarding"));
+
+        let parsed = parse_bundle(&content).unwrap();
+        assert_eq!(parsed, bundle);
+
+        let mut other_machine = Settings::default();
+        other_machine.api_server.token = "other-secret".to_string();
+        let (imported, imported_llm) =
+            apply_bundle(&parsed, &other_machine, &LlmConfig::default(), false).unwrap();
+
+        let mut expected = settings();
+        expected.api_server.token = "other-secret".to_string();
+        // Onboarding has to run again for what this machine is missing
+        expected.onboarding = other_machine.onboarding.clone();
+        assert_eq!(json(&imported), json(&expected));
+        assert_eq!(json(&imported_llm), json(&llm()));
+    }
+
+    #[test]
+    fn test_merge_keeps_local_entries() {
+        let bundle = build_bundle(&settings(), &llm(), Utc::now()).unwrap();
+
+        let mut local = Settings::default();
+        local.model_prices.insert(
+            "claude-sonnet-4-5".to_string(),
+            ModelPrice {
+                prompt_per_1k: 0.003,
+                completion_per_1k: 0.015,
+            },
+        );
+        local.api_server.token = "other-secret".to_string();
+
+        let (merged, _) = apply_bundle(&bundle, &local, &LlmConfig::default(), true).unwrap();
+        assert_eq!(merged.model_prices.len(), 2);
+        assert_eq!(merged.shutdown_grace_period_seconds, 12);
+        assert_eq!(merged.api_server.token, "other-secret");
+
+        let (replaced, _) = apply_bundle(&bundle, &local, &LlmConfig::default(), false).unwrap();
+        assert_eq!(replaced.model_prices.len(), 1);
+    }
+
+    #[test]
+    fn test_imported_llm_config_is_validated() {
+        let mut bundle = build_bundle(&settings(), &llm(), Utc::now()).unwrap();
+        bundle.llm["external_endpoint"] = Value::from("gpu-box:11434");
+
+        let Err(error) = apply_bundle(&bundle, &Settings::default(), &LlmConfig::default(), true)
+        else {
+            panic!("invalid endpoint was imported");
+        };
+        assert!(error.contains("HTTP/HTTPS"));
+    }
+
+    #[test]
+    fn test_newer_bundle_is_rejected() {
+        let mut bundle = json(&build_bundle(&settings(), &llm(), Utc::now()).unwrap());
+        bundle["bundle_version"] = Value::from(BUNDLE_VERSION + 1);
+        // A future format may look entirely different
+        bundle["settings"] = Value::from("compressed");
+
+        let error = parse_bundle(&bundle.to_string()).unwrap_err();
+        assert!(error.contains("Update the app"), "{}", error);
+
+        let mut bundle = json(&build_bundle(&settings(), &llm(), Utc::now()).unwrap());
+        bundle["settings"]["version"] = Value::from(SETTINGS_VERSION + 1);
+        assert!(parse_bundle(&bundle.to_string())
+            .unwrap_err()
+            .contains("Update the app"));
+
+        assert!(parse_bundle("{\"theme\": \"dark\"}").is_err());
+    }
+}
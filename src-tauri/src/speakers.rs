@@ -0,0 +1,494 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::meeting::{lock_meeting, write_atomic};
+
+/// Snippets returned per name by `preview_speaker_rename`
+const MAX_SNIPPETS: usize = 5;
+/// Bytes of context on each side of a match, long JSON lines are cut to this
+const SNIPPET_CONTEXT: usize = 80;
+/// Every name ever renamed and its current name, for texts written before the rename
+const SPEAKER_RENAMES_FILE: &str = "speaker_renames.json";
+
+/// What `save_speaker_names` changed, so the UI can confirm the rename
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct SpeakerRenameReport {
+    /// File names of the transcripts that were rewritten
+    pub files_updated: Vec<String>,
+    /// Occurrences of each old name, 0 if it wasn't found in any transcript
+    pub replacement_counts: HashMap<String, usize>,
+}
+
+/// A line around a match, `match_start..match_end` are byte offsets into `line`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snippet {
+    pub line: String,
+    pub match_start: usize,
+    pub match_end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenamePreview {
+    pub old_name: String,
+    pub new_name: String,
+    pub count: usize,
+    pub snippets: Vec<Snippet>,
+}
+
+/// An occurrence of an old name, byte offsets into the transcript
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct NameMatch<'a> {
+    start: usize,
+    end: usize,
+    old: &'a str,
+}
+
+/// All occurrences of the names that would be renamed, in text order and
+/// without overlaps. Both the preview and the rename go through this, so
+/// their counts can't diverge. Matches are taken from the original text in
+/// one pass, so `A → B, B → C` doesn't turn A into C.
+fn find_matches<'a>(text: &str, names: &'a HashMap<String, String>) -> Vec<NameMatch<'a>> {
+    let mut matches: Vec<NameMatch> = names
+        .iter()
+        .filter(|(old, new)| !old.is_empty() && old != new)
+        .flat_map(|(old, _)| {
+            text.match_indices(old.as_str())
+                // SPEAKER_00 of the first chunk is not chunk2_SPEAKER_00
+                .filter(|(start, _)| !text[..*start].ends_with('_'))
+                .map(|(start, _)| NameMatch {
+                    start,
+                    end: start + old.len(),
+                    old: old.as_str(),
+                })
+        })
+        .collect();
+
+    // On a tie the longer name wins, e.g. SPEAKER_10 over SPEAKER_1
+    matches.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut end = 0;
+    matches.retain(|m| {
+        let keep = m.start >= end;
+        if keep {
+            end = m.end;
+        }
+        keep
+    });
+    matches
+}
+
+fn count_matches(matches: &[NameMatch]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for m in matches {
+        *counts.entry(m.old.to_string()).or_default() += 1;
+    }
+    counts
+}
+
+fn apply_matches(text: &str, matches: &[NameMatch], names: &HashMap<String, String>) -> String {
+    let mut renamed = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in matches {
+        renamed.push_str(&text[last..m.start]);
+        renamed.push_str(&names[m.old]);
+        last = m.end;
+    }
+    renamed.push_str(&text[last..]);
+    renamed
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+/// The line containing `start..end`, cut to `SNIPPET_CONTEXT` bytes around the
+/// match for single-line files like the JSON transcript
+fn snippet(text: &str, start: usize, end: usize) -> Snippet {
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[end..].find('\n').map_or(text.len(), |i| end + i);
+
+    let from = floor_char_boundary(text, line_start.max(start.saturating_sub(SNIPPET_CONTEXT)));
+    let to = ceil_char_boundary(text, line_end.min(end + SNIPPET_CONTEXT));
+
+    Snippet {
+        line: text[from..to].trim_end_matches('\r').to_string(),
+        match_start: start - from,
+        match_end: end - from,
+    }
+}
+
+/// The transcript files of a meeting that exist. Some backends only write
+/// one of them, so each is handled on its own.
+async fn read_transcripts(
+    meeting_dir: &Path,
+    meeting_id: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let mut transcripts = Vec::new();
+    for extension in ["txt", "json"] {
+        let file_name = format!("{}.{}", meeting_id, extension);
+        let path = meeting_dir.join(&file_name);
+        if !path.exists() {
+            continue;
+        }
+
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+        transcripts.push((file_name, content));
+    }
+
+    if transcripts.is_empty() {
+        return Err(format!("No transcript found for meeting {}", meeting_id));
+    }
+    Ok(transcripts)
+}
+
+/// Occurrences per old name. Both files hold the same segments, so each
+/// occurrence counts once: the file with the most matches wins.
+fn merge_counts(totals: &mut HashMap<String, usize>, counts: HashMap<String, usize>) {
+    for (old, count) in counts {
+        let total = totals.entry(old).or_default();
+        *total = (*total).max(count);
+    }
+}
+
+/// Names renamed by `rename_speakers` and what they are called now
+pub async fn read_speaker_renames(meeting_dir: &Path) -> HashMap<String, String> {
+    match tokio::fs::read_to_string(meeting_dir.join(SPEAKER_RENAMES_FILE)).await {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Add `names` to the recorded renames. Earlier renames follow along, so
+/// after `A → B` and `B → C` both A and B map to C.
+async fn record_speaker_renames(
+    meeting_dir: &Path,
+    names: &HashMap<String, String>,
+) -> Result<(), String> {
+    let mut renames = read_speaker_renames(meeting_dir).await;
+    for new in renames.values_mut() {
+        if let Some(newer) = names.get(new.as_str()) {
+            *new = newer.clone();
+        }
+    }
+    for (old, new) in names {
+        if !old.is_empty() && old != new {
+            renames.insert(old.clone(), new.clone());
+        }
+    }
+    renames.retain(|old, new| old != new);
+
+    let json = serde_json::to_string_pretty(&renames).map_err(|e| e.to_string())?;
+    write_atomic(&meeting_dir.join(SPEAKER_RENAMES_FILE), json)
+        .await
+        .map_err(|e| format!("Failed to record speaker renames: {}", e))
+}
+
+/// Replace speaker names in the `.txt` and `.json` transcripts
+pub async fn rename_speakers(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    names: &HashMap<String, String>,
+) -> Result<SpeakerRenameReport, String> {
+    let _guard = lock_meeting(meeting_dir).await;
+
+    let mut report = SpeakerRenameReport {
+        replacement_counts: names.keys().map(|old| (old.clone(), 0)).collect(),
+        ..Default::default()
+    };
+
+    for (file_name, transcript) in read_transcripts(meeting_dir, meeting_id).await? {
+        let matches = find_matches(&transcript, names);
+        if matches.is_empty() {
+            continue;
+        }
+
+        write_atomic(
+            &meeting_dir.join(&file_name),
+            apply_matches(&transcript, &matches, names),
+        )
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+
+        merge_counts(&mut report.replacement_counts, count_matches(&matches));
+        report.files_updated.push(file_name);
+    }
+    record_speaker_renames(meeting_dir, names).await?;
+
+    Ok(report)
+}
+
+/// What `rename_speakers` would change, without writing anything. Snippets
+/// come from the text transcript when there is one.
+pub async fn preview_rename(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    names: &HashMap<String, String>,
+) -> Result<Vec<RenamePreview>, String> {
+    let transcripts = read_transcripts(meeting_dir, meeting_id).await?;
+
+    let mut counts = HashMap::new();
+    for (_, transcript) in &transcripts {
+        merge_counts(&mut counts, count_matches(&find_matches(transcript, names)));
+    }
+
+    let (_, snippet_source) = &transcripts[0];
+    let snippet_matches = find_matches(snippet_source, names);
+
+    let mut previews: Vec<RenamePreview> = names
+        .iter()
+        .map(|(old, new)| RenamePreview {
+            old_name: old.clone(),
+            new_name: new.clone(),
+            count: counts.get(old).copied().unwrap_or(0),
+            snippets: snippet_matches
+                .iter()
+                .filter(|m| m.old == old)
+                .take(MAX_SNIPPETS)
+                .map(|m| snippet(snippet_source, m.start, m.end))
+                .collect(),
+        })
+        .collect();
+    previews.sort_by(|a, b| a.old_name.cmp(&b.old_name));
+
+    Ok(previews)
+}
+
+#[tauri::command]
+pub async fn save_speaker_names(
+    app: AppHandle,
+    meeting_id: &str,
+    names: HashMap<String, String>,
+) -> Result<SpeakerRenameReport, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    rename_speakers(&meeting_dir, meeting_id, &names).await
+}
+
+#[tauri::command]
+pub async fn preview_speaker_rename(
+    app: AppHandle,
+    meeting_id: &str,
+    names: HashMap<String, String>,
+) -> Result<Vec<RenamePreview>, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    preview_rename(&meeting_dir, meeting_id, &names).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEETING_ID: &str = "recording-1700000000";
+
+    fn names(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(old, new)| (old.to_string(), new.to_string()))
+            .collect()
+    }
+
+    async fn fixture(txt: Option<&str>, json: Option<&str>) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        if let Some(txt) = txt {
+            tokio::fs::write(dir.path().join(format!("{}.txt", MEETING_ID)), txt)
+                .await
+                .unwrap();
+        }
+        if let Some(json) = json {
+            tokio::fs::write(dir.path().join(format!("{}.json", MEETING_ID)), json)
+                .await
+                .unwrap();
+        }
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_rename_speakers_in_both_files() {
+        let dir = fixture(
+            Some("[Anna]: Hallo"),
+            Some(r#"{"segments":[{"speaker":"Anna"}]}"#),
+        )
+        .await;
+
+        let report = rename_speakers(dir.path(), MEETING_ID, &names(&[("Anna", "Anna Weber")]))
+            .await
+            .unwrap();
+
+        assert_eq!(report.files_updated.len(), 2);
+        assert_eq!(report.replacement_counts["Anna"], 1);
+        let json = tokio::fs::read_to_string(dir.path().join(format!("{}.json", MEETING_ID)))
+            .await
+            .unwrap();
+        assert!(json.contains("Anna Weber"));
+    }
+
+    #[tokio::test]
+    async fn test_rename_speakers_without_json() {
+        let dir = fixture(Some("[Anna]: Hallo"), None).await;
+
+        let report = rename_speakers(
+            dir.path(),
+            MEETING_ID,
+            &names(&[("Anna", "Anna Weber"), ("SPEAKER_03", "Ben")]),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.files_updated, vec![format!("{}.txt", MEETING_ID)]);
+        // Names that don't occur are reported with 0 instead of left out
+        assert_eq!(report.replacement_counts["SPEAKER_03"], 0);
+        assert_eq!(report.replacement_counts["Anna"], 1);
+
+        let txt = tokio::fs::read_to_string(dir.path().join(format!("{}.txt", MEETING_ID)))
+            .await
+            .unwrap();
+        assert_eq!(txt, "[Anna Weber]: Hallo");
+        assert!(!dir.path().join(format!("{}.json", MEETING_ID)).exists());
+    }
+
+    #[tokio::test]
+    async fn test_rename_speakers_without_transcript() {
+        let dir = fixture(None, None).await;
+
+        let result = rename_speakers(dir.path(), MEETING_ID, &names(&[("Anna", "Ben")])).await;
+        assert!(result.unwrap_err().contains("No transcript"));
+    }
+
+    #[tokio::test]
+    async fn test_renames_are_recorded_and_chained() {
+        let dir = fixture(Some("[SPEAKER_00]: Hallo\n[SPEAKER_01]: Moin"), None).await;
+
+        rename_speakers(
+            dir.path(),
+            MEETING_ID,
+            &names(&[("SPEAKER_00", "Anna"), ("SPEAKER_01", "SPEAKER_01")]),
+        )
+        .await
+        .unwrap();
+        rename_speakers(dir.path(), MEETING_ID, &names(&[("Anna", "Anna Weber")]))
+            .await
+            .unwrap();
+
+        let renames = read_speaker_renames(dir.path()).await;
+        assert_eq!(
+            renames,
+            names(&[("SPEAKER_00", "Anna Weber"), ("Anna", "Anna Weber")])
+        );
+    }
+
+    #[test]
+    fn test_matches_are_taken_in_one_pass() {
+        let text = "[SPEAKER_1]: Ja\n[SPEAKER_10]: Nein\n[Anna]: Gut";
+        let names = names(&[
+            ("SPEAKER_1", "Anna"),
+            ("SPEAKER_10", "Ben"),
+            ("Anna", "Clara"),
+        ]);
+
+        let matches = find_matches(text, &names);
+        assert_eq!(
+            apply_matches(text, &matches, &names),
+            "[Anna]: Ja\n[Ben]: Nein\n[Clara]: Gut"
+        );
+        assert_eq!(count_matches(&matches)["SPEAKER_1"], 1);
+    }
+
+    #[test]
+    fn test_chunk_speakers_are_renamed_separately() {
+        let text = "[SPEAKER_00]: Hallo\n[chunk2_SPEAKER_00]: Moin";
+
+        let first = names(&[("SPEAKER_00", "Anna")]);
+        let matches = find_matches(text, &first);
+        assert_eq!(
+            apply_matches(text, &matches, &first),
+            "[Anna]: Hallo\n[chunk2_SPEAKER_00]: Moin"
+        );
+
+        let second = names(&[("chunk2_SPEAKER_00", "Anna")]);
+        let matches = find_matches(text, &second);
+        assert_eq!(
+            apply_matches(text, &matches, &second),
+            "[SPEAKER_00]: Hallo\n[Anna]: Moin"
+        );
+    }
+
+    #[test]
+    fn test_snippet_highlights_match_in_line() {
+        let text = "[Anna]: Hallo\n[SPEAKER_00]: Wir fangen an.\n[Anna]: Gut";
+        let start = text.find("SPEAKER_00").unwrap();
+        let snippet = snippet(text, start, start + "SPEAKER_00".len());
+
+        assert_eq!(snippet.line, "[SPEAKER_00]: Wir fangen an.");
+        assert_eq!(
+            &snippet.line[snippet.match_start..snippet.match_end],
+            "SPEAKER_00"
+        );
+    }
+
+    #[test]
+    fn test_snippet_cuts_long_lines_on_char_boundaries() {
+        let text = format!("{}SPEAKER_00{}", "ä".repeat(100), "ö".repeat(100));
+        let start = text.find("SPEAKER_00").unwrap();
+        let snippet = snippet(&text, start, start + "SPEAKER_00".len());
+
+        assert!(snippet.line.len() <= "SPEAKER_00".len() + 2 * SNIPPET_CONTEXT + 2);
+        assert_eq!(
+            &snippet.line[snippet.match_start..snippet.match_end],
+            "SPEAKER_00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preview_matches_rename_without_writing() {
+        let txt = (0..8)
+            .map(|i| format!("[SPEAKER_00]: Punkt {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let dir = fixture(Some(&txt), None).await;
+        let names = names(&[("SPEAKER_00", "Anna"), ("SPEAKER_05", "Ben")]);
+
+        let previews = preview_rename(dir.path(), MEETING_ID, &names)
+            .await
+            .unwrap();
+        assert_eq!(previews[0].count, 8);
+        assert_eq!(previews[0].snippets.len(), MAX_SNIPPETS);
+        assert_eq!(previews[0].snippets[1].line, "[SPEAKER_00]: Punkt 1");
+        assert_eq!(previews[1].count, 0);
+        assert!(previews[1].snippets.is_empty());
+
+        let on_disk = tokio::fs::read_to_string(dir.path().join(format!("{}.txt", MEETING_ID)))
+            .await
+            .unwrap();
+        assert_eq!(on_disk, txt);
+
+        let report = rename_speakers(dir.path(), MEETING_ID, &names)
+            .await
+            .unwrap();
+        for preview in &previews {
+            assert_eq!(report.replacement_counts[&preview.old_name], preview.count);
+        }
+    }
+}
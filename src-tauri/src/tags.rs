@@ -0,0 +1,200 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+
+use crate::meeting::{read_meeting_metadata, update_meeting_metadata};
+use crate::MeetingMetadata;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TagEntry {
+    pub name: String,
+    /// Extracted from the summary rather than set by the user
+    pub automatic: bool,
+    pub meeting_count: usize,
+}
+
+/// All tags used by the given meetings, most used first
+pub fn collect_tags<'a>(meetings: impl IntoIterator<Item = &'a MeetingMetadata>) -> Vec<TagEntry> {
+    let mut counts: BTreeMap<(&str, bool), usize> = BTreeMap::new();
+    for metadata in meetings {
+        for tag in &metadata.tags {
+            *counts.entry((tag.as_str(), false)).or_default() += 1;
+        }
+        for tag in &metadata.auto_tags {
+            *counts.entry((tag.as_str(), true)).or_default() += 1;
+        }
+    }
+
+    let mut tags: Vec<TagEntry> = counts
+        .into_iter()
+        .map(|((name, automatic), meeting_count)| TagEntry {
+            name: name.to_string(),
+            automatic,
+            meeting_count,
+        })
+        .collect();
+    // Stable, so tags with the same count stay sorted by name
+    tags.sort_by_key(|tag| std::cmp::Reverse(tag.meeting_count));
+    tags
+}
+
+/// Whether the meeting carries `tag`, set by the user or extracted. Case is ignored.
+pub fn has_tag(metadata: &MeetingMetadata, tag: &str) -> bool {
+    let tag = tag.trim();
+    metadata
+        .tags
+        .iter()
+        .chain(&metadata.auto_tags)
+        .any(|candidate| candidate.eq_ignore_ascii_case(tag))
+}
+
+/// Trimmed tags without empty ones or repeats, in the order given
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|seen| seen.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+pub async fn set_tags(
+    meeting_dir: &Path,
+    meeting_id: &str,
+    tags: Vec<String>,
+) -> Result<(), String> {
+    let tags = normalize_tags(tags);
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.tags = tags;
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Replace the tags the user set, extracted tags are left alone
+#[tauri::command]
+pub async fn set_meeting_tags(
+    app: AppHandle,
+    meeting_id: &str,
+    tags: Vec<String>,
+) -> Result<(), String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let meeting_dir = app_dir.join("uploads").join(meeting_id);
+
+    set_tags(&meeting_dir, meeting_id, tags).await
+}
+
+/// Every tag with the number of meetings using it, for the filter of `get_meetings`
+#[tauri::command]
+pub async fn list_tags(app: AppHandle) -> Result<Vec<TagEntry>, String> {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    let uploads = app_dir.join("uploads");
+    if !uploads.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut meetings = Vec::new();
+    let mut entries = fs::read_dir(uploads).await.map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        if !entry.file_type().await.map_err(|e| e.to_string())?.is_dir() {
+            continue;
+        }
+        match read_meeting_metadata(&entry.path()).await {
+            Ok(Some(metadata)) => meetings.push(metadata),
+            Ok(None) => {}
+            Err(e) => println!("Warning: Skipping tags of {:?}: {}", entry.file_name(), e),
+        }
+    }
+
+    Ok(collect_tags(&meetings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting(auto_tags: &[&str]) -> MeetingMetadata {
+        MeetingMetadata {
+            auto_tags: auto_tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn strings(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn test_user_tags_are_counted_apart() {
+        let meetings = vec![
+            MeetingMetadata {
+                tags: strings(&["1:1", "budget"]),
+                ..meeting(&["budget"])
+            },
+            MeetingMetadata {
+                tags: strings(&["1:1"]),
+                ..Default::default()
+            },
+        ];
+
+        let tags = collect_tags(&meetings);
+        assert_eq!(tags[0].name, "1:1");
+        assert_eq!(tags[0].meeting_count, 2);
+        assert!(!tags[0].automatic);
+        assert_eq!(tags.len(), 3);
+        assert!(has_tag(&meetings[0], "Budget"));
+        assert!(has_tag(&meetings[1], " 1:1"));
+        assert!(!has_tag(&meetings[1], "budget"));
+    }
+
+    #[tokio::test]
+    async fn test_set_tags_keeps_other_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let meeting_id = "recording-1700000000";
+        update_meeting_metadata(dir.path(), meeting_id, |metadata| {
+            metadata.name = Some("Weekly".to_string());
+            metadata.auto_tags = strings(&["roadmap"]);
+        })
+        .await
+        .unwrap();
+
+        set_tags(
+            dir.path(),
+            meeting_id,
+            strings(&[" Team ", "", "team", "Kunde A"]),
+        )
+        .await
+        .unwrap();
+
+        let metadata = read_meeting_metadata(dir.path()).await.unwrap().unwrap();
+        assert_eq!(metadata.tags, strings(&["Team", "Kunde A"]));
+        assert_eq!(metadata.auto_tags, strings(&["roadmap"]));
+        assert_eq!(metadata.name.as_deref(), Some("Weekly"));
+    }
+
+    #[test]
+    fn test_collect_tags() {
+        let meetings = vec![
+            meeting(&["budget", "hiring"]),
+            meeting(&["roadmap", "budget"]),
+            meeting(&[]),
+        ];
+
+        let tags = collect_tags(&meetings);
+        let names: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+
+        assert_eq!(names, ["budget", "hiring", "roadmap"]);
+        assert_eq!(tags[0].meeting_count, 2);
+        assert!(tags.iter().all(|tag| tag.automatic));
+    }
+}
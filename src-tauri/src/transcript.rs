@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A single transcript segment in the whisperx JSON layout
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    /// Fields we don't interpret (e.g. word timings) are kept as-is
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The `<meeting_id>.json` transcript document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub segments: Vec<TranscriptSegment>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Transcript {
+    /// Render the transcript in the same layout whisperx uses for its .txt output
+    pub fn to_text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match &segment.speaker {
+                Some(speaker) => format!("[{}]: {}", speaker, segment.text.trim()),
+                None => segment.text.trim().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Move every segment and word `offset` seconds later
+    pub fn shift(&mut self, offset: f64) {
+        for segment in &mut self.segments {
+            segment.start += offset;
+            segment.end += offset;
+            if let Some(words) = segment.extra.get_mut("words") {
+                shift_timings(words, offset);
+            }
+        }
+        // whisperx also lists every word at the top level
+        if let Some(words) = self.extra.get_mut("word_segments") {
+            shift_timings(words, offset);
+        }
+    }
+
+    pub fn has_speakers(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| segment.speaker.is_some())
+    }
+
+    /// Put `prefix` in front of every speaker label, including those of words
+    fn prefix_speakers(&mut self, prefix: &str) {
+        for segment in &mut self.segments {
+            if let Some(speaker) = &mut segment.speaker {
+                speaker.insert_str(0, prefix);
+            }
+            if let Some(words) = segment.extra.get_mut("words") {
+                prefix_speaker_fields(words, prefix);
+            }
+        }
+        if let Some(words) = self.extra.get_mut("word_segments") {
+            prefix_speaker_fields(words, prefix);
+        }
+    }
+}
+
+/// Whisper's transcript of one audio chunk of a recording
+pub struct ChunkTranscript {
+    pub chunk_index: usize,
+    /// Start of the chunk in the recording, in seconds
+    pub start_time: f64,
+    pub transcript: Transcript,
+}
+
+/// Label prefix of the speakers found in a chunk. Every chunk is diarized on
+/// its own, so SPEAKER_00 of the second chunk need not be SPEAKER_00 of the first.
+fn speaker_prefix(chunk_index: usize) -> String {
+    match chunk_index {
+        0 => String::new(),
+        _ => format!("chunk{}_", chunk_index + 1),
+    }
+}
+
+fn prefix_speaker_fields(items: &mut Value, prefix: &str) {
+    let Some(items) = items.as_array_mut() else {
+        return;
+    };
+    for item in items.iter_mut().filter_map(Value::as_object_mut) {
+        if let Some(Value::String(speaker)) = item.get_mut("speaker") {
+            speaker.insert_str(0, prefix);
+        }
+    }
+}
+
+/// Shift `start` and `end` of each object in an array, words whisper could
+/// not align have neither
+fn shift_timings(items: &mut Value, offset: f64) {
+    let Some(items) = items.as_array_mut() else {
+        return;
+    };
+    for item in items.iter_mut().filter_map(Value::as_object_mut) {
+        for key in ["start", "end"] {
+            if let Some(time) = item.get(key).and_then(Value::as_f64) {
+                item.insert(key.to_string(), Value::from(time + offset));
+            }
+        }
+    }
+}
+
+/// One transcript from the transcripts of consecutive audio chunks. Each
+/// segment records its `chunk_index`, speakers of later chunks are prefixed
+/// as listed in `speaker_prefixes`.
+pub fn merge_chunk_transcripts(chunks: Vec<ChunkTranscript>) -> Transcript {
+    let mut merged = Transcript::default();
+    let mut texts = Vec::new();
+    let mut speaker_prefixes = Vec::new();
+    for ChunkTranscript {
+        chunk_index,
+        start_time,
+        transcript: mut chunk,
+    } in chunks
+    {
+        let prefix = speaker_prefix(chunk_index);
+        if !prefix.is_empty() && chunk.has_speakers() {
+            chunk.prefix_speakers(&prefix);
+            speaker_prefixes.push(serde_json::json!({
+                "chunk_index": chunk_index,
+                "prefix": prefix,
+            }));
+        }
+        chunk.shift(start_time);
+        for segment in &mut chunk.segments {
+            segment
+                .extra
+                .insert("chunk_index".to_string(), Value::from(chunk_index));
+        }
+        merged.segments.append(&mut chunk.segments);
+
+        for (key, value) in chunk.extra {
+            match (key.as_str(), value) {
+                ("text", Value::String(text)) => texts.push(text.trim().to_string()),
+                ("word_segments", Value::Array(mut words)) => {
+                    if let Some(Value::Array(merged_words)) = merged.extra.get_mut(&key) {
+                        merged_words.append(&mut words);
+                    } else {
+                        merged.extra.insert(key, Value::Array(words));
+                    }
+                }
+                // e.g. the language, the first chunk decides
+                (_, value) => {
+                    merged.extra.entry(key).or_insert(value);
+                }
+            }
+        }
+    }
+
+    if !texts.is_empty() {
+        merged
+            .extra
+            .insert("text".to_string(), Value::from(texts.join(" ")));
+    }
+    if !speaker_prefixes.is_empty() {
+        merged.extra.insert(
+            "speaker_prefixes".to_string(),
+            Value::Array(speaker_prefixes),
+        );
+    }
+    // mlx_whisper numbers the segments of each chunk from 0
+    for (id, segment) in merged.segments.iter_mut().enumerate() {
+        if segment.extra.contains_key("id") {
+            segment.extra.insert("id".to_string(), Value::from(id));
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::test_harness::read_fixture;
+
+    fn fixture(name: &str) -> Transcript {
+        serde_json::from_str(&read_fixture(&format!("whisper/{}", name))).unwrap()
+    }
+
+    fn chunk(chunk_index: usize, start_time: f64, transcript: Transcript) -> ChunkTranscript {
+        ChunkTranscript {
+            chunk_index,
+            start_time,
+            transcript,
+        }
+    }
+
+    #[test]
+    fn test_merge_offsets_the_later_chunks() {
+        let merged = merge_chunk_transcripts(vec![
+            chunk(0, 0.0, fixture("chunk_00.json")),
+            chunk(1, 1800.0, fixture("chunk_01.json")),
+        ]);
+
+        assert_eq!(merged.segments.len(), 3);
+        assert_eq!(merged.segments[1].start, 1795.2);
+        let second_chunk = &merged.segments[2];
+        assert!(second_chunk.start >= 1800.0, "{}", second_chunk.start);
+        assert_eq!(second_chunk.end, 1803.0);
+        assert_eq!(second_chunk.extra["words"][1]["start"], 1800.5);
+        assert_eq!(second_chunk.extra["id"], 2);
+        assert_eq!(second_chunk.extra["chunk_index"], 1);
+        // Without diarization there is nothing to relabel
+        assert!(!merged.extra.contains_key("speaker_prefixes"));
+        assert_eq!(merged.extra["language"], "de");
+        assert_eq!(
+            merged.extra["text"],
+            "Guten Morgen zusammen. Fangen wir mit dem Haushalt an. Der Haushalt ist beschlossen."
+        );
+
+        // The result is one document again
+        let json = serde_json::to_string(&merged).unwrap();
+        let reparsed: Transcript = serde_json::from_str(&json).unwrap();
+        assert_eq!(reparsed, merged);
+    }
+
+    #[test]
+    fn test_word_segments_are_shifted() {
+        let words = |word: &str| {
+            let word_segments = serde_json::json!([
+                {"word": word, "start": 1.0, "end": 1.5},
+                {"word": "42"},
+            ]);
+            Transcript {
+                extra: [("word_segments".to_string(), word_segments)]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            }
+        };
+        let merged = merge_chunk_transcripts(vec![
+            chunk(0, 0.0, words("eins")),
+            chunk(1, 600.0, words("zwei")),
+        ]);
+
+        let words = merged.extra["word_segments"].as_array().unwrap();
+        assert_eq!(words.len(), 4);
+        assert_eq!(words[2]["start"], 601.0);
+        assert_eq!(words[2]["word"], "zwei");
+        assert!(words[3].get("start").is_none());
+    }
+
+    #[test]
+    fn test_speakers_of_later_chunks_are_namespaced() {
+        let diarized = |text: &str| {
+            let words = serde_json::json!([{"word": text, "speaker": "SPEAKER_00"}]);
+            Transcript {
+                segments: vec![TranscriptSegment {
+                    start: 0.0,
+                    end: 2.0,
+                    text: text.to_string(),
+                    speaker: Some("SPEAKER_00".to_string()),
+                    extra: [("words".to_string(), words)].into_iter().collect(),
+                }],
+                ..Default::default()
+            }
+        };
+        // The second chunk failed, the third keeps its own number
+        let merged = merge_chunk_transcripts(vec![
+            chunk(0, 0.0, diarized("Hallo")),
+            chunk(2, 1200.0, diarized("Tschüss")),
+        ]);
+
+        assert_eq!(merged.segments[0].speaker.as_deref(), Some("SPEAKER_00"));
+        assert_eq!(
+            merged.segments[1].speaker.as_deref(),
+            Some("chunk3_SPEAKER_00")
+        );
+        assert_eq!(
+            merged.segments[1].extra["words"][0]["speaker"],
+            "chunk3_SPEAKER_00"
+        );
+        assert_eq!(
+            merged.extra["speaker_prefixes"],
+            serde_json::json!([{"chunk_index": 2, "prefix": "chunk3_"}])
+        );
+        assert_eq!(
+            merged.to_text(),
+            "[SPEAKER_00]: Hallo\n[chunk3_SPEAKER_00]: Tschüss"
+        );
+    }
+
+    #[test]
+    fn test_to_text_with_and_without_speakers() {
+        let transcript = Transcript {
+            segments: vec![
+                TranscriptSegment {
+                    start: 0.0,
+                    end: 1.0,
+                    text: " Hallo zusammen.".to_string(),
+                    speaker: Some("Anna".to_string()),
+                    ..Default::default()
+                },
+                TranscriptSegment {
+                    start: 1.0,
+                    end: 2.0,
+                    text: "Ohne Sprecher".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transcript.to_text(),
+            "[Anna]: Hallo zusammen.\nOhne Sprecher"
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_are_preserved() {
+        let json = r#"{"segments":[{"start":0.5,"end":1.5,"text":"hi","words":[{"word":"hi"}]}],"language":"de"}"#;
+        let transcript: Transcript = serde_json::from_str(json).unwrap();
+
+        assert!(transcript.segments[0].extra.contains_key("words"));
+        assert_eq!(transcript.extra["language"], "de");
+
+        let round_trip = serde_json::to_value(&transcript).unwrap();
+        assert_eq!(round_trip["segments"][0]["words"][0]["word"], "hi");
+    }
+}
@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+use crate::meeting::update_meeting_metadata;
+
+/// Conversational speech is usually 100-160 words per minute. Far below means
+/// whisperx missed most of the audio, far above means it hallucinated text.
+const MIN_WORDS_PER_MINUTE: f64 = 20.0;
+const MAX_WORDS_PER_MINUTE: f64 = 300.0;
+/// The rate is meaningless for very short recordings
+const MIN_DURATION_FOR_RATE_CHECK: f64 = 60.0;
+/// Share of all sentences a single phrase needs to be flagged as a repetition loop
+const REPEATED_PHRASE_SHARE: f64 = 0.5;
+const MIN_SENTENCES_FOR_REPETITION_CHECK: usize = 8;
+
+/// Signs that whisperx succeeded but produced a transcript that can't be right
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranscriptionWarning {
+    LowWordRate { words_per_minute: f64 },
+    HighWordRate { words_per_minute: f64 },
+    RepeatedPhrase { phrase: String, share: f64 },
+}
+
+impl fmt::Display for TranscriptionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptionWarning::LowWordRate { words_per_minute } => write!(
+                f,
+                "Transcript is unusually short ({:.0} words per minute), parts of the audio may be missing",
+                words_per_minute
+            ),
+            TranscriptionWarning::HighWordRate { words_per_minute } => write!(
+                f,
+                "Transcript is unusually long ({:.0} words per minute)",
+                words_per_minute
+            ),
+            TranscriptionWarning::RepeatedPhrase { phrase, share } => write!(
+                f,
+                "\"{}\" makes up {:.0}% of the transcript, this is likely a transcription error",
+                phrase,
+                share * 100.0
+            ),
+        }
+    }
+}
+
+/// Run all plausibility checks on a whisperx `.txt` transcript
+pub fn check_transcript(transcript: &str, duration_seconds: f64) -> Vec<TranscriptionWarning> {
+    let spoken = spoken_text(transcript);
+    let mut warnings = Vec::new();
+
+    let word_count = spoken.split_whitespace().count();
+    if word_count == 0 {
+        warnings.push(TranscriptionWarning::LowWordRate {
+            words_per_minute: 0.0,
+        });
+        return warnings;
+    }
+
+    if let Some(rate) = words_per_minute(word_count, duration_seconds) {
+        if rate < MIN_WORDS_PER_MINUTE {
+            warnings.push(TranscriptionWarning::LowWordRate {
+                words_per_minute: rate,
+            });
+        } else if rate > MAX_WORDS_PER_MINUTE {
+            warnings.push(TranscriptionWarning::HighWordRate {
+                words_per_minute: rate,
+            });
+        }
+    }
+
+    if let Some((phrase, share)) = find_repeated_phrase(&spoken) {
+        warnings.push(TranscriptionWarning::RepeatedPhrase { phrase, share });
+    }
+
+    warnings
+}
+
+/// Words per minute, or `None` when the recording is too short to judge
+fn words_per_minute(word_count: usize, duration_seconds: f64) -> Option<f64> {
+    if duration_seconds < MIN_DURATION_FOR_RATE_CHECK {
+        return None;
+    }
+    Some(word_count as f64 / (duration_seconds / 60.0))
+}
+
+/// The most common sentence if it dominates the transcript
+fn find_repeated_phrase(text: &str) -> Option<(String, f64)> {
+    let sentences: Vec<String> = text
+        .split(['.', '!', '?', '\n'])
+        .map(normalize_sentence)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if sentences.len() < MIN_SENTENCES_FOR_REPETITION_CHECK {
+        return None;
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for sentence in &sentences {
+        *counts.entry(sentence.as_str()).or_default() += 1;
+    }
+
+    let (phrase, count) = counts.into_iter().max_by_key(|(_, count)| *count)?;
+    let share = count as f64 / sentences.len() as f64;
+
+    (share >= REPEATED_PHRASE_SHARE).then(|| (phrase.to_string(), share))
+}
+
+/// Strip the `[Speaker]: ` prefixes whisperx writes so they don't count as words
+fn spoken_text(transcript: &str) -> String {
+    transcript
+        .lines()
+        .map(|line| match line.split_once("]: ") {
+            Some((speaker, text)) if speaker.starts_with('[') => text,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn normalize_sentence(sentence: &str) -> String {
+    sentence
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Store the warnings in `meeting.json` and notify the UI if there are any
+pub async fn record_transcription_warnings(
+    app: &AppHandle,
+    meeting_dir: &Path,
+    meeting_id: &str,
+    warnings: &[TranscriptionWarning],
+) -> Result<(), String> {
+    update_meeting_metadata(meeting_dir, meeting_id, |metadata| {
+        metadata.transcription_warnings = warnings.to_vec();
+    })
+    .await?;
+
+    if !warnings.is_empty() {
+        for warning in warnings {
+            println!("⚠️  Transcription warning for {}: {}", meeting_id, warning);
+        }
+        let messages: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+        app.emit(
+            "transcription-warning",
+            json!({ "meeting_id": meeting_id, "warnings": messages }),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(count: usize) -> String {
+        (0..count)
+            .map(|i| format!("[SPEAKER_00]: Das ist Satz Nummer {} im Meeting.", i))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_words_per_minute() {
+        assert_eq!(words_per_minute(120, 120.0), Some(60.0));
+        assert_eq!(words_per_minute(120, 30.0), None);
+    }
+
+    #[test]
+    fn test_plausible_transcript_has_no_warnings() {
+        // 60 sentences * 7 words over 3 minutes = 140 wpm
+        assert!(check_transcript(&sentences(60), 180.0).is_empty());
+    }
+
+    #[test]
+    fn test_near_empty_transcript() {
+        let warnings = check_transcript("[SPEAKER_00]: Hallo.", 1800.0);
+        assert!(matches!(
+            warnings.as_slice(),
+            [TranscriptionWarning::LowWordRate { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_empty_transcript_is_flagged_regardless_of_duration() {
+        let warnings = check_transcript("", 10.0);
+        assert_eq!(
+            warnings,
+            vec![TranscriptionWarning::LowWordRate {
+                words_per_minute: 0.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_too_many_words() {
+        // 60 sentences * 7 words in one minute
+        let warnings = check_transcript(&sentences(60), 60.0);
+        assert!(matches!(
+            warnings.as_slice(),
+            [TranscriptionWarning::HighWordRate { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_speaker_labels_are_not_counted() {
+        assert_eq!(spoken_text("[SPEAKER_00]: Hallo Welt"), "Hallo Welt");
+        assert_eq!(spoken_text("Kein Sprecher: hier"), "Kein Sprecher: hier");
+    }
+
+    #[test]
+    fn test_detects_repeated_phrase() {
+        let transcript = "[SPEAKER_00]: Vielen Dank. Vielen Dank! vielen dank.\n\
+                          [SPEAKER_00]: Vielen Dank. Vielen Dank. Vielen Dank.\n\
+                          [SPEAKER_01]: Hallo. Wie geht es. Gut.";
+
+        let (phrase, share) = find_repeated_phrase(&spoken_text(transcript)).unwrap();
+        assert_eq!(phrase, "vielen dank");
+        assert!((share - 6.0 / 9.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_varied_sentences_are_not_repetition() {
+        assert_eq!(find_repeated_phrase(&spoken_text(&sentences(20))), None);
+    }
+
+    #[test]
+    fn test_short_transcripts_skip_repetition_check() {
+        assert_eq!(find_repeated_phrase("Ja. Ja. Ja."), None);
+    }
+}
@@ -0,0 +1,404 @@
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::llm::api_keys::KEYRING_SERVICE;
+use crate::meeting::write_atomic;
+use crate::model_cache::CachedModel;
+use crate::whisperx::DEFAULT_WHISPER_MODEL;
+
+const TRANSCRIPTION_CONFIG_FILE_NAME: &str = "transcription_config.json";
+/// Keyring entry of the Hugging Face token, like the LLM API keys it never
+/// ends up in a config file
+const HF_TOKEN_KEYRING_USER: &str = "huggingface";
+
+/// Length of the audio chunks long recordings are split into
+pub const DEFAULT_CHUNK_DURATION_SECONDS: u64 = 600;
+const MIN_CHUNK_DURATION_SECONDS: u64 = 60;
+const MAX_CHUNK_DURATION_SECONDS: u64 = 2 * 60 * 60;
+
+/// Whisper model sizes, from fastest to most accurate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WhisperModelSize {
+    Tiny,
+    Base,
+    Small,
+    Medium,
+    LargeV3,
+}
+
+impl WhisperModelSize {
+    pub const ALL: [WhisperModelSize; 5] = [
+        WhisperModelSize::Tiny,
+        WhisperModelSize::Base,
+        WhisperModelSize::Small,
+        WhisperModelSize::Medium,
+        WhisperModelSize::LargeV3,
+    ];
+
+    /// Value of whisperx' `--model`
+    pub fn whisperx_model(&self) -> &'static str {
+        match self {
+            WhisperModelSize::Tiny => "tiny",
+            WhisperModelSize::Base => "base",
+            WhisperModelSize::Small => "small",
+            WhisperModelSize::Medium => "medium",
+            WhisperModelSize::LargeV3 => "large-v3",
+        }
+    }
+
+    /// Hugging Face repo whisperx downloads through faster-whisper
+    fn whisperx_repo(&self) -> String {
+        format!("Systran/faster-whisper-{}", self.whisperx_model())
+    }
+
+    /// Hugging Face repo passed to mlx_whisper
+    pub fn mlx_model(&self) -> &'static str {
+        match self {
+            WhisperModelSize::Tiny => "mlx-community/whisper-tiny-mlx",
+            WhisperModelSize::Base => "mlx-community/whisper-base-mlx",
+            WhisperModelSize::Small => "mlx-community/whisper-small-mlx",
+            WhisperModelSize::Medium => "mlx-community/whisper-medium-mlx",
+            WhisperModelSize::LargeV3 => "mlx-community/whisper-large-v3-mlx",
+        }
+    }
+
+    /// Whether either backend already downloaded this size
+    fn is_cached(&self, cached: &[CachedModel]) -> bool {
+        let whisperx_repo = self.whisperx_repo();
+        cached
+            .iter()
+            .any(|model| model.name == whisperx_repo || model.name == self.mlx_model())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionConfig {
+    /// `None` keeps the defaults: whisper-turbo for mlx_whisper and
+    /// whatever whisperx picks itself
+    #[serde(default)]
+    pub model: Option<WhisperModelSize>,
+    /// Speaker diarization with pyannote, needs the Hugging Face token
+    #[serde(default = "default_diarize")]
+    pub diarize: bool,
+    /// Speaker count hints for pyannote, only used with diarization
+    #[serde(default)]
+    pub min_speakers: Option<u32>,
+    #[serde(default)]
+    pub max_speakers: Option<u32>,
+    /// Shorter chunks need less memory, longer ones give whisper more context
+    #[serde(default = "default_chunk_duration_seconds")]
+    pub chunk_duration_seconds: u64,
+}
+
+fn default_chunk_duration_seconds() -> u64 {
+    DEFAULT_CHUNK_DURATION_SECONDS
+}
+
+fn default_diarize() -> bool {
+    true
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            model: None,
+            diarize: default_diarize(),
+            min_speakers: None,
+            max_speakers: None,
+            chunk_duration_seconds: default_chunk_duration_seconds(),
+        }
+    }
+}
+
+/// What `get_transcription_config` returns, the token only as a hint
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionSettings {
+    #[serde(flatten)]
+    pub config: TranscriptionConfig,
+    /// e.g. `hf_…wxyz`, `None` when no token is stored
+    pub hf_token: Option<String>,
+}
+
+impl TranscriptionConfig {
+    /// Load the saved config. A missing, corrupt or invalid file is replaced
+    /// with the defaults, a chunk duration out of range is clamped.
+    pub fn load(path: &Path) -> Self {
+        let loaded = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<Self>(&content).map_err(|e| e.to_string()))
+            .and_then(|mut config| {
+                // A hand-edited chunk duration of 0 can't split the audio
+                config.chunk_duration_seconds = config
+                    .chunk_duration_seconds
+                    .clamp(MIN_CHUNK_DURATION_SECONDS, MAX_CHUNK_DURATION_SECONDS);
+                config.validate().map(|()| config)
+            });
+        match loaded {
+            Ok(config) => config,
+            Err(e) => {
+                if path.exists() {
+                    println!(
+                        "Warning: Failed to read transcription config, using defaults: {}",
+                        e
+                    );
+                }
+                let config = Self::default();
+                let written = serde_json::to_string_pretty(&config)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+                if let Err(e) = written {
+                    println!("Warning: Failed to write transcription config: {}", e);
+                }
+                config
+            }
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize transcription config: {}", e))?;
+        write_atomic(path, json)
+            .await
+            .map_err(|e| format!("Failed to write transcription config: {}", e))
+    }
+
+    /// This config with the diarization settings given for one transcription
+    pub fn with_overrides(
+        &self,
+        diarize: Option<bool>,
+        min_speakers: Option<u32>,
+        max_speakers: Option<u32>,
+    ) -> Self {
+        Self {
+            diarize: diarize.unwrap_or(self.diarize),
+            min_speakers: min_speakers.or(self.min_speakers),
+            max_speakers: max_speakers.or(self.max_speakers),
+            ..self.clone()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_speakers == Some(0) || self.max_speakers == Some(0) {
+            return Err("Speaker counts must be at least 1".to_string());
+        }
+        if let (Some(min), Some(max)) = (self.min_speakers, self.max_speakers) {
+            if min > max {
+                return Err(format!(
+                    "Minimum speakers ({}) is more than the maximum ({})",
+                    min, max
+                ));
+            }
+        }
+        if !(MIN_CHUNK_DURATION_SECONDS..=MAX_CHUNK_DURATION_SECONDS)
+            .contains(&self.chunk_duration_seconds)
+        {
+            return Err(format!(
+                "Chunk duration must be between {} and {} seconds",
+                MIN_CHUNK_DURATION_SECONDS, MAX_CHUNK_DURATION_SECONDS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Model argument of whisperx, `None` leaves the choice to whisperx
+    pub fn whisperx_model(&self) -> Option<&'static str> {
+        self.model.map(|model| model.whisperx_model())
+    }
+
+    pub fn mlx_model(&self) -> &'static str {
+        self.model
+            .map(|model| model.mlx_model())
+            .unwrap_or(DEFAULT_WHISPER_MODEL)
+    }
+
+    /// Whether the next transcription has to download its model first
+    pub fn needs_download(&self, cached: &[CachedModel]) -> bool {
+        match self.model {
+            Some(model) => !model.is_cached(cached),
+            None => !cached
+                .iter()
+                .any(|model| model.name == DEFAULT_WHISPER_MODEL),
+        }
+    }
+}
+
+fn hf_token_entry() -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, HF_TOKEN_KEYRING_USER)
+        .map_err(|e| format!("Failed to open keyring: {}", e))
+}
+
+pub fn get_hf_token() -> Result<Option<String>, String> {
+    match hf_token_entry()?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!(
+            "Failed to read Hugging Face token from keyring: {}",
+            e
+        )),
+    }
+}
+
+/// Store the token, or remove it when `token` is empty
+pub fn store_hf_token(token: &str) -> Result<(), String> {
+    let entry = hf_token_entry()?;
+    let token = token.trim();
+    let result = if token.is_empty() {
+        match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            result => result,
+        }
+    } else {
+        entry.set_password(token)
+    };
+
+    result.map_err(|e| format!("Failed to write Hugging Face token to keyring: {}", e))
+}
+
+/// Enough of the token to recognise it, short tokens are hidden completely
+pub fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "…".to_string();
+    }
+    let prefix: String = chars[..3].iter().collect();
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", prefix, suffix)
+}
+
+/// Sizes that are in the model cache for whisperx or mlx_whisper
+pub fn downloaded_models(cached: &[CachedModel]) -> Vec<WhisperModelSize> {
+    WhisperModelSize::ALL
+        .into_iter()
+        .filter(|model| model.is_cached(cached))
+        .collect()
+}
+
+pub fn get_transcription_config_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(TRANSCRIPTION_CONFIG_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(name: &str) -> CachedModel {
+        CachedModel {
+            name: name.to_string(),
+            path: format!("/cache/{}", name),
+            size_bytes: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_config_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = get_transcription_config_path(dir.path());
+
+        let config = TranscriptionConfig::load(&path);
+        assert_eq!(config, TranscriptionConfig::default());
+        assert_eq!(config.mlx_model(), DEFAULT_WHISPER_MODEL);
+        assert!(path.exists());
+
+        TranscriptionConfig {
+            model: Some(WhisperModelSize::LargeV3),
+            diarize: false,
+            ..Default::default()
+        }
+        .save(&path)
+        .await
+        .unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"large-v3\""));
+        let loaded = TranscriptionConfig::load(&path);
+        assert_eq!(loaded.whisperx_model(), Some("large-v3"));
+        assert_eq!(loaded.mlx_model(), "mlx-community/whisper-large-v3-mlx");
+        assert!(!loaded.diarize);
+
+        // Configs saved before diarization could be turned off keep it on
+        std::fs::write(&path, "{\"model\": \"tiny\"}").unwrap();
+        let loaded = TranscriptionConfig::load(&path);
+        assert!(loaded.diarize);
+        assert_eq!(
+            loaded.chunk_duration_seconds,
+            DEFAULT_CHUNK_DURATION_SECONDS
+        );
+
+        std::fs::write(
+            &path,
+            "{\"model\": \"tiny\", \"chunk_duration_seconds\": 0}",
+        )
+        .unwrap();
+        let loaded = TranscriptionConfig::load(&path);
+        assert_eq!(loaded.model, Some(WhisperModelSize::Tiny));
+        assert_eq!(loaded.chunk_duration_seconds, MIN_CHUNK_DURATION_SECONDS);
+
+        std::fs::write(&path, "{\"min_speakers\": 5, \"max_speakers\": 2}").unwrap();
+        assert_eq!(
+            TranscriptionConfig::load(&path),
+            TranscriptionConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_overrides_and_speaker_hints() {
+        let config = TranscriptionConfig {
+            min_speakers: Some(2),
+            ..Default::default()
+        };
+        let solo = config.with_overrides(Some(false), None, None);
+        assert!(!solo.diarize);
+        assert_eq!(solo.min_speakers, Some(2));
+
+        let meeting = config.with_overrides(None, None, Some(6));
+        assert!(meeting.diarize);
+        assert!(meeting.validate().is_ok());
+        assert!(config
+            .with_overrides(None, Some(4), Some(3))
+            .validate()
+            .is_err());
+        assert!(config
+            .with_overrides(None, Some(0), None)
+            .validate()
+            .is_err());
+
+        let huge_chunks = TranscriptionConfig {
+            chunk_duration_seconds: 24 * 60 * 60,
+            ..Default::default()
+        };
+        assert!(huge_chunks.validate().is_err());
+    }
+
+    #[test]
+    fn test_token_is_masked() {
+        assert_eq!(mask_token("hf_abcdefghijklmnopqrstuvwxyz"), "hf_…wxyz");
+        assert_eq!(mask_token("hf_short"), "…");
+    }
+
+    #[test]
+    fn test_downloaded_models_from_either_backend() {
+        let cache = vec![
+            cached("Systran/faster-whisper-small"),
+            cached("mlx-community/whisper-tiny-mlx"),
+            cached("pyannote/speaker-diarization-3.1"),
+        ];
+        assert_eq!(
+            downloaded_models(&cache),
+            vec![WhisperModelSize::Tiny, WhisperModelSize::Small]
+        );
+
+        let medium = TranscriptionConfig {
+            model: Some(WhisperModelSize::Medium),
+            ..Default::default()
+        };
+        assert!(medium.needs_download(&cache));
+        let small = TranscriptionConfig {
+            model: Some(WhisperModelSize::Small),
+            ..Default::default()
+        };
+        assert!(!small.needs_download(&cache));
+        assert!(TranscriptionConfig::default().needs_download(&cache));
+    }
+}
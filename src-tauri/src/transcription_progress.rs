@@ -0,0 +1,232 @@
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+
+use crate::llm::progress::ProgressEvents;
+
+/// What a line printed by whisperx or mlx_whisper says about its progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressLine {
+    /// Share of the input done, 0 to 100
+    Percent(f64),
+    /// End of the last transcribed segment, in seconds of the input
+    Timestamp(f64),
+}
+
+/// Progress of a line of output, `None` for log lines without any
+pub fn parse_progress_line(line: &str) -> Option<ProgressLine> {
+    // tqdm redraws its bar with carriage returns, the last frame is the current one
+    line.split('\r').rev().find_map(parse_frame)
+}
+
+fn parse_frame(frame: &str) -> Option<ProgressLine> {
+    let frame = frame.trim();
+    // whisperx --print_progress: "Progress: 45.32%..."
+    if let Some(rest) = frame.strip_prefix("Progress:") {
+        let (percent, _) = rest.split_once('%')?;
+        return parse_percent(percent);
+    }
+
+    // whisperx: "Transcript: [12.345 --> 15.678]  text"
+    // mlx_whisper: "[01:02.000 --> 01:05.500]  text"
+    let segment = frame
+        .strip_prefix("Transcript:")
+        .map(str::trim_start)
+        .unwrap_or(frame);
+    if let Some(rest) = segment.strip_prefix('[') {
+        let (range, _) = rest.split_once(']')?;
+        let (_, end) = range.split_once("-->")?;
+        return parse_timestamp(end.trim()).map(ProgressLine::Timestamp);
+    }
+
+    // tqdm: " 45%|####5     | 27000/60000 [00:37<00:45, 724.10frames/s]"
+    let (percent, _) = frame.split_once("%|")?;
+    parse_percent(percent)
+}
+
+fn parse_percent(text: &str) -> Option<ProgressLine> {
+    let percent: f64 = text.trim().parse().ok()?;
+    percent
+        .is_finite()
+        .then(|| ProgressLine::Percent(percent.clamp(0.0, 100.0)))
+}
+
+/// `12.345`, `01:02.000` or `1:01:02.000` in seconds
+fn parse_timestamp(text: &str) -> Option<f64> {
+    let seconds = text.split(':').try_fold(0.0, |total: f64, part| {
+        part.parse::<f64>().ok().map(|value| total * 60.0 + value)
+    })?;
+    (seconds.is_finite() && seconds >= 0.0).then_some(seconds)
+}
+
+#[derive(Clone, Serialize)]
+struct TranscriptionProgress<'a> {
+    meeting_id: &'a str,
+    /// `None` when the recording is transcribed in one piece
+    chunk_index: Option<usize>,
+    /// Of the whole recording, chunks before this one count as done
+    percent: f64,
+    /// Seconds into the recording
+    current_timestamp: f64,
+}
+
+/// Turns the output of one whisper run into `transcription-progress` events.
+/// Events only move forward and at most one is sent per whole percent.
+pub struct ProgressReporter {
+    events: ProgressEvents,
+    meeting_id: String,
+    chunk_index: Option<usize>,
+    /// Part of the recording the run transcribes, in seconds
+    start: f64,
+    end: f64,
+    total_duration: f64,
+    last_percent: StdMutex<Option<u32>>,
+}
+
+impl ProgressReporter {
+    /// Progress of transcribing the whole recording in one run
+    pub fn new(events: ProgressEvents, meeting_id: &str, total_duration: f64) -> Self {
+        Self {
+            events,
+            meeting_id: meeting_id.to_string(),
+            chunk_index: None,
+            start: 0.0,
+            end: total_duration,
+            total_duration,
+            last_percent: StdMutex::new(None),
+        }
+    }
+
+    /// Progress of the run over one audio chunk of the recording
+    pub fn for_chunk(mut self, chunk_index: usize, start_time: f64, end_time: f64) -> Self {
+        self.chunk_index = Some(chunk_index);
+        self.start = start_time;
+        self.end = end_time;
+        self
+    }
+
+    /// Seconds into the recording after `line`
+    fn position(&self, line: &str) -> Option<f64> {
+        let length = (self.end - self.start).max(0.0);
+        let offset = match parse_progress_line(line)? {
+            ProgressLine::Percent(percent) => length * percent / 100.0,
+            ProgressLine::Timestamp(seconds) => seconds.min(length),
+        };
+        Some(self.start + offset)
+    }
+
+    pub fn observe(&self, line: &str) {
+        let Some(position) = self.position(line) else {
+            return;
+        };
+        let percent = if self.total_duration > 0.0 {
+            (position / self.total_duration * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        };
+
+        let whole_percent = percent.floor() as u32;
+        {
+            let mut last_percent = self.last_percent.lock().unwrap_or_else(|e| e.into_inner());
+            if last_percent.is_some_and(|last| whole_percent <= last) {
+                return;
+            }
+            *last_percent = Some(whole_percent);
+        }
+
+        self.events.emit(
+            "transcription-progress",
+            TranscriptionProgress {
+                meeting_id: &self.meeting_id,
+                chunk_index: self.chunk_index,
+                percent: (percent * 10.0).round() / 10.0,
+                current_timestamp: (position * 1000.0).round() / 1000.0,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::progress::ProgressSink;
+    use crate::llm::test_harness::read_fixture;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    fn parse_fixture(name: &str) -> Vec<ProgressLine> {
+        read_fixture(&format!("whisper/{}", name))
+            .lines()
+            .filter_map(parse_progress_line)
+            .collect()
+    }
+
+    #[test]
+    fn test_mlx_whisper_segments() {
+        assert_eq!(
+            parse_fixture("mlx_whisper.txt"),
+            vec![
+                ProgressLine::Timestamp(4.64),
+                ProgressLine::Timestamp(9.12),
+                ProgressLine::Timestamp(15.0),
+                ProgressLine::Timestamp(307.25),
+                ProgressLine::Timestamp(600.0),
+            ]
+        );
+        assert_eq!(
+            parse_progress_line("[01:00:01.200 --> 01:00:05.000]  Pause"),
+            Some(ProgressLine::Timestamp(3605.0))
+        );
+    }
+
+    #[test]
+    fn test_whisperx_progress() {
+        let lines = parse_fixture("whisperx.txt");
+        assert_eq!(lines[0], ProgressLine::Timestamp(4.64));
+        assert_eq!(lines[1], ProgressLine::Percent(12.5));
+        assert_eq!(lines[4], ProgressLine::Timestamp(307.25));
+        // Transcription and alignment each count up to 100
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[7], ProgressLine::Percent(100.0));
+    }
+
+    #[test]
+    fn test_tqdm_bar_uses_the_last_frame() {
+        assert_eq!(
+            parse_fixture("mlx_whisper_tqdm.txt"),
+            vec![ProgressLine::Percent(45.0)]
+        );
+        assert_eq!(parse_progress_line(">>Performing transcription..."), None);
+        assert_eq!(parse_progress_line("Progress: nan%..."), None);
+    }
+
+    struct RecordingSink(Arc<Mutex<Vec<Value>>>);
+
+    impl ProgressSink for RecordingSink {
+        fn send(&self, _event: &str, payload: Value) -> Result<(), String> {
+            self.0.lock().unwrap().push(payload);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_chunk_progress_covers_the_whole_recording() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let events = ProgressEvents::new(RecordingSink(sent.clone()));
+        // Third of four 10 minute chunks
+        let reporter =
+            ProgressReporter::new(events, "recording-1", 2400.0).for_chunk(2, 1200.0, 1800.0);
+
+        reporter.observe("[05:00.000 --> 05:00.000]  Haushalt");
+        reporter.observe("[05:00.400 --> 05:00.700]  ja");
+        reporter.observe("Progress: 10.00%...");
+        reporter.observe("Progress: 100.00%...");
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 2, "{:?}", sent);
+        assert_eq!(sent[0]["chunk_index"], 2);
+        assert_eq!(sent[0]["percent"], 62.5);
+        assert_eq!(sent[0]["current_timestamp"], 1500.0);
+        assert_eq!(sent[1]["percent"], 75.0);
+        assert_eq!(sent[1]["meeting_id"], "recording-1");
+    }
+}
@@ -0,0 +1,448 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::jobs::JobKind;
+use crate::meeting::{
+    is_valid_meeting_id, lock_meeting, read_meeting_metadata, repair_meeting_in,
+    update_meeting_metadata,
+};
+use crate::settings::load_settings;
+use crate::AppState;
+
+pub const TRASH_DIR_NAME: &str = "trash";
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A deleted meeting in `trash/<meeting_id>-<unix seconds>`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrashedMeeting {
+    pub trash_id: String,
+    pub meeting_id: String,
+    pub name: Option<String>,
+    pub deleted_at: String,
+}
+
+/// Split a trash folder name into the meeting id and the deletion time
+fn parse_trash_id(trash_id: &str) -> Option<(&str, DateTime<Utc>)> {
+    let (meeting_id, timestamp) = trash_id.rsplit_once('-')?;
+    let deleted_at = DateTime::from_timestamp(timestamp.parse().ok()?, 0)?;
+    if meeting_id.is_empty() {
+        return None;
+    }
+    Some((meeting_id, deleted_at))
+}
+
+/// Trash ids come from the frontend, only plain folder names are accepted
+fn trash_entry(trash: &Path, trash_id: &str) -> Result<PathBuf, String> {
+    if !is_valid_meeting_id(trash_id) || parse_trash_id(trash_id).is_none() {
+        return Err(format!("Invalid trash id: {}", trash_id));
+    }
+
+    let path = trash.join(trash_id);
+    if !path.is_dir() {
+        return Err(format!("{} is not in the trash", trash_id));
+    }
+    Ok(path)
+}
+
+/// Move a meeting folder to the trash and return its trash id
+pub async fn move_to_trash(
+    uploads: &Path,
+    trash: &Path,
+    meeting_id: &str,
+    now: DateTime<Utc>,
+) -> Result<String, String> {
+    // Meeting ids come from the frontend, `..` would take the uploads directory along
+    if !is_valid_meeting_id(meeting_id) {
+        return Err(format!("Invalid meeting id: {}", meeting_id));
+    }
+    let meeting_dir = uploads.join(meeting_id);
+    if !meeting_dir.is_dir() {
+        return Err(format!("Meeting {} not found", meeting_id));
+    }
+
+    let trash_id = format!("{}-{}", meeting_id, now.timestamp());
+    let target = trash.join(&trash_id);
+    if target.exists() {
+        return Err(format!("{} is already in the trash", trash_id));
+    }
+    fs::create_dir_all(trash)
+        .await
+        .map_err(|e| format!("Failed to create trash: {}", e))?;
+
+    let _guard = lock_meeting(&meeting_dir).await;
+    fs::rename(&meeting_dir, &target)
+        .await
+        .map_err(|e| format!("Failed to move meeting to trash: {}", e))?;
+
+    Ok(trash_id)
+}
+
+/// Trashed meetings, most recently deleted first
+pub async fn list_trash(trash: &Path) -> Result<Vec<TrashedMeeting>, String> {
+    let mut meetings = Vec::new();
+    let mut entries = match fs::read_dir(trash).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(meetings),
+    };
+
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let trash_id = entry.file_name().to_string_lossy().into_owned();
+        let Some((meeting_id, deleted_at)) = parse_trash_id(&trash_id) else {
+            continue;
+        };
+        if !entry.path().is_dir() {
+            continue;
+        }
+
+        let name = read_meeting_metadata(&entry.path())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|metadata| metadata.name);
+        meetings.push(TrashedMeeting {
+            meeting_id: meeting_id.to_string(),
+            name,
+            deleted_at: deleted_at.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            trash_id,
+        });
+    }
+
+    meetings.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(meetings)
+}
+
+/// Move a meeting back to `uploads/` and return its id. If the original id was
+/// taken in the meantime, the meeting gets a new one and its files are renamed.
+pub async fn restore_from_trash(
+    uploads: &Path,
+    trash: &Path,
+    trash_id: &str,
+) -> Result<String, String> {
+    let source = trash_entry(trash, trash_id)?;
+    let (original_id, _) = parse_trash_id(trash_id).expect("trash id was validated");
+
+    let mut meeting_id = original_id.to_string();
+    let mut suffix = 1;
+    while uploads.join(&meeting_id).exists() {
+        meeting_id = format!("{}-{}", original_id, suffix);
+        suffix += 1;
+    }
+
+    if meeting_id != original_id {
+        // Without meeting.json the repair has no old id to rename the files from
+        if read_meeting_metadata(&source).await?.is_none() {
+            update_meeting_metadata(&source, original_id, |metadata| metadata.created_at = None)
+                .await?;
+        }
+    }
+
+    fs::create_dir_all(uploads)
+        .await
+        .map_err(|e| format!("Failed to create uploads: {}", e))?;
+    let meeting_dir = uploads.join(&meeting_id);
+    fs::rename(&source, &meeting_dir)
+        .await
+        .map_err(|e| format!("Failed to restore meeting: {}", e))?;
+
+    if meeting_id != original_id {
+        repair_meeting_in(&meeting_dir, &meeting_id).await?;
+    }
+
+    Ok(meeting_id)
+}
+
+/// Delete trash entries deleted more than `older_than_days` ago, all of them for 0.
+/// Returns the number of removed meetings.
+pub async fn purge_trash(
+    trash: &Path,
+    older_than_days: u64,
+    now: DateTime<Utc>,
+) -> Result<usize, String> {
+    let cutoff = now.timestamp() - older_than_days as i64 * SECONDS_PER_DAY;
+    let mut purged = 0;
+
+    for meeting in list_trash(trash).await? {
+        let Some((_, deleted_at)) = parse_trash_id(&meeting.trash_id) else {
+            continue;
+        };
+        if older_than_days > 0 && deleted_at.timestamp() > cutoff {
+            continue;
+        }
+
+        match fs::remove_dir_all(trash.join(&meeting.trash_id)).await {
+            Ok(()) => purged += 1,
+            Err(e) => println!("Warning: Failed to purge {}: {}", meeting.trash_id, e),
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Apply the configured retention, called once at startup
+pub async fn purge_trash_on_startup(app: AppHandle) -> Result<(), String> {
+    let retention_days = load_settings(&app).await?.trash_retention_days;
+    let trash = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory")
+        .join(TRASH_DIR_NAME);
+
+    // 0 would mean "purge everything", for the retention it means "keep forever"
+    if retention_days == 0 {
+        return Ok(());
+    }
+    let purged = purge_trash(&trash, retention_days, Utc::now()).await?;
+    if purged > 0 {
+        println!("Purged {} meetings from the trash", purged);
+    }
+    Ok(())
+}
+
+fn data_dirs(app: &AppHandle) -> (PathBuf, PathBuf) {
+    let app_dir = app
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get app local data directory");
+    (app_dir.join("uploads"), app_dir.join(TRASH_DIR_NAME))
+}
+
+/// Move a meeting to the trash, it can be restored until the trash is purged
+#[tauri::command]
+pub async fn delete_meeting(app: AppHandle, meeting_id: &str) -> Result<String, String> {
+    {
+        let state = app.state::<Mutex<AppState>>();
+        let state = state.lock().await;
+        let is_queued = [JobKind::Transcription, JobKind::Summarization]
+            .into_iter()
+            .any(|kind| state.job_queue.is_queued(kind, meeting_id));
+        if state.is_busy_with(meeting_id) || is_queued {
+            return Err(format!(
+                "Meeting {} is still being processed, try again when it is done",
+                meeting_id
+            ));
+        }
+    }
+
+    let (uploads, trash) = data_dirs(&app);
+    move_to_trash(&uploads, &trash, meeting_id, Utc::now()).await
+}
+
+#[tauri::command]
+pub async fn list_trashed_meetings(app: AppHandle) -> Result<Vec<TrashedMeeting>, String> {
+    let (_, trash) = data_dirs(&app);
+    list_trash(&trash).await
+}
+
+/// Returns the id the meeting was restored under
+#[tauri::command]
+pub async fn restore_meeting(app: AppHandle, trash_id: &str) -> Result<String, String> {
+    let (uploads, trash) = data_dirs(&app);
+    restore_from_trash(&uploads, &trash, trash_id).await
+}
+
+#[tauri::command]
+pub async fn empty_trash(app: AppHandle, older_than_days: u64) -> Result<usize, String> {
+    let (_, trash) = data_dirs(&app);
+    purge_trash(&trash, older_than_days, Utc::now()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    fn meeting(uploads: &Path, id: &str, name: &str) {
+        let meeting_dir = uploads.join(id);
+        std::fs::create_dir_all(&meeting_dir).unwrap();
+        std::fs::write(
+            meeting_dir.join("meeting.json"),
+            format!("{{\"id\": \"{}\", \"name\": \"{}\"}}", id, name),
+        )
+        .unwrap();
+        std::fs::write(meeting_dir.join(format!("{}.txt", id)), name).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_move_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let trash = dir.path().join(TRASH_DIR_NAME);
+        meeting(&uploads, "recording-1", "Planung");
+
+        let trash_id = move_to_trash(&uploads, &trash, "recording-1", at(1_700_000_000))
+            .await
+            .unwrap();
+        assert_eq!(trash_id, "recording-1-1700000000");
+        assert!(!uploads.join("recording-1").exists());
+
+        let trashed = list_trash(&trash).await.unwrap();
+        assert_eq!(
+            trashed,
+            vec![TrashedMeeting {
+                trash_id: trash_id.clone(),
+                meeting_id: "recording-1".to_string(),
+                name: Some("Planung".to_string()),
+                deleted_at: "2023-11-14T22:13:20.000Z".to_string(),
+            }]
+        );
+
+        let restored = restore_from_trash(&uploads, &trash, &trash_id)
+            .await
+            .unwrap();
+        assert_eq!(restored, "recording-1");
+        assert!(uploads.join("recording-1").join("recording-1.txt").exists());
+        assert!(list_trash(&trash).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_move_rejects_ids_outside_uploads() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let trash = dir.path().join(TRASH_DIR_NAME);
+        meeting(&uploads, "recording-1", "Planung");
+
+        for meeting_id in ["..", ".", "", "../uploads", "recording-1/..", ".hidden"] {
+            let error = move_to_trash(&uploads, &trash, meeting_id, at(1_700_000_000))
+                .await
+                .unwrap_err();
+            assert!(error.starts_with("Invalid meeting id"), "{}", error);
+        }
+        assert_eq!(
+            move_to_trash(&uploads, &trash, "recording-2", at(1_700_000_000)).await,
+            Err("Meeting recording-2 not found".to_string())
+        );
+        assert!(uploads.join("recording-1").is_dir());
+        assert!(!trash.exists());
+    }
+
+    #[tokio::test]
+    async fn test_restore_into_reused_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let trash = dir.path().join(TRASH_DIR_NAME);
+        meeting(&uploads, "recording-1", "Planung");
+        let trash_id = move_to_trash(&uploads, &trash, "recording-1", at(1_700_000_000))
+            .await
+            .unwrap();
+        meeting(&uploads, "recording-1", "Retro");
+
+        let restored = restore_from_trash(&uploads, &trash, &trash_id)
+            .await
+            .unwrap();
+        assert_eq!(restored, "recording-1-1");
+
+        // The meeting that took the id is untouched
+        let meeting_dir = uploads.join("recording-1");
+        assert_eq!(
+            std::fs::read_to_string(meeting_dir.join("recording-1.txt")).unwrap(),
+            "Retro"
+        );
+
+        let meeting_dir = uploads.join("recording-1-1");
+        assert_eq!(
+            std::fs::read_to_string(meeting_dir.join("recording-1-1.txt")).unwrap(),
+            "Planung"
+        );
+        let metadata = read_meeting_metadata(&meeting_dir).await.unwrap().unwrap();
+        assert_eq!(metadata.id, "recording-1-1");
+        assert_eq!(metadata.aliases, vec!["recording-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_restore_without_metadata_into_reused_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let trash = dir.path().join(TRASH_DIR_NAME);
+        std::fs::create_dir_all(trash.join("recording-1-1700000000")).unwrap();
+        std::fs::write(
+            trash.join("recording-1-1700000000").join("recording-1.txt"),
+            "Hallo",
+        )
+        .unwrap();
+        meeting(&uploads, "recording-1", "Retro");
+
+        let restored = restore_from_trash(&uploads, &trash, "recording-1-1700000000")
+            .await
+            .unwrap();
+        assert_eq!(restored, "recording-1-1");
+        let meeting_dir = uploads.join("recording-1-1");
+        assert!(meeting_dir.join("recording-1-1.txt").exists());
+        let metadata = read_meeting_metadata(&meeting_dir).await.unwrap().unwrap();
+        assert_eq!(metadata.created_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let trash = dir.path().join(TRASH_DIR_NAME);
+        meeting(&uploads, "recording-1", "Planung");
+
+        for trash_id in [
+            "../uploads/recording-1",
+            "recording-1",
+            "missing-1700000000",
+        ] {
+            assert!(
+                restore_from_trash(&uploads, &trash, trash_id)
+                    .await
+                    .is_err(),
+                "{}",
+                trash_id
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purge_keeps_recent_meetings() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = dir.path().join("uploads");
+        let trash = dir.path().join(TRASH_DIR_NAME);
+        let now = 1_700_000_000;
+        meeting(&uploads, "recording-old", "Alt");
+        meeting(&uploads, "recording-new", "Neu");
+        move_to_trash(
+            &uploads,
+            &trash,
+            "recording-old",
+            at(now - 31 * SECONDS_PER_DAY),
+        )
+        .await
+        .unwrap();
+        move_to_trash(
+            &uploads,
+            &trash,
+            "recording-new",
+            at(now - 2 * SECONDS_PER_DAY),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(purge_trash(&trash, 30, at(now)).await.unwrap(), 1);
+        let remaining = list_trash(&trash).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].meeting_id, "recording-new");
+
+        assert_eq!(purge_trash(&trash, 0, at(now)).await.unwrap(), 1);
+        assert!(list_trash(&trash).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_trash_id() {
+        assert_eq!(
+            parse_trash_id("recording-1-1700000000"),
+            Some(("recording-1", at(1_700_000_000)))
+        );
+        assert_eq!(parse_trash_id("recording"), None);
+        assert_eq!(parse_trash_id("-1700000000"), None);
+        assert_eq!(parse_trash_id("recording-abc"), None);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::transcript::{Transcript, TranscriptSegment};
+
+/// Parse a WebVTT file (as exported by Teams or Zoom) into a transcript.
+///
+/// Speakers are taken from `<v Name>` voice tags (Teams). Cues without a voice
+/// tag fall back to a `Name: text` prefix (Zoom), otherwise the segment has no speaker.
+pub fn parse_vtt(content: &str) -> Result<Transcript, String> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+
+    let mut blocks = content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|b| !b.is_empty());
+
+    match blocks.next() {
+        Some(header) if header.starts_with("WEBVTT") => {}
+        _ => return Err("Not a WebVTT file: missing WEBVTT header".to_string()),
+    }
+
+    let mut segments = Vec::new();
+    for block in blocks {
+        if block.starts_with("NOTE") || block.starts_with("STYLE") || block.starts_with("REGION") {
+            continue;
+        }
+
+        let mut lines = block.lines();
+        let mut timing_line = match lines.next() {
+            Some(line) => line,
+            None => continue,
+        };
+        // Optional cue identifier before the timing line
+        if !timing_line.contains("-->") {
+            timing_line = match lines.next() {
+                Some(line) if line.contains("-->") => line,
+                _ => continue,
+            };
+        }
+
+        let (start, end) = parse_timing_line(timing_line)?;
+        let payload = lines.collect::<Vec<_>>().join("\n");
+        let (speaker, text) = parse_cue_payload(&payload);
+
+        if text.is_empty() {
+            continue;
+        }
+
+        segments.push(TranscriptSegment {
+            start,
+            end,
+            text,
+            speaker,
+            ..Default::default()
+        });
+    }
+
+    if segments.is_empty() {
+        return Err("The VTT file does not contain any cues".to_string());
+    }
+
+    Ok(Transcript {
+        segments,
+        ..Default::default()
+    })
+}
+
+fn parse_timing_line(line: &str) -> Result<(f64, f64), String> {
+    let (start, rest) = line
+        .split_once("-->")
+        .ok_or_else(|| format!("Invalid cue timing: {}", line))?;
+    // Cue settings (e.g. `align:start`) may follow the end time
+    let end = rest.split_whitespace().next().unwrap_or_default();
+
+    Ok((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// Parse `HH:MM:SS.mmm` or `MM:SS.mmm` into seconds
+fn parse_timestamp(value: &str) -> Result<f64, String> {
+    let parts: Vec<&str> = value.split(':').collect();
+    let parse = |part: &str| {
+        part.replace(',', ".")
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid timestamp: {}", value))
+    };
+
+    match parts.as_slice() {
+        [hours, minutes, seconds] => {
+            Ok(parse(hours)? * 3600.0 + parse(minutes)? * 60.0 + parse(seconds)?)
+        }
+        [minutes, seconds] => Ok(parse(minutes)? * 60.0 + parse(seconds)?),
+        _ => Err(format!("Invalid timestamp: {}", value)),
+    }
+}
+
+fn parse_cue_payload(payload: &str) -> (Option<String>, String) {
+    let mut speaker = None;
+
+    if let Some(start) = payload.find("<v") {
+        let tag_end = payload[start..].find('>').map(|i| start + i);
+        if let Some(tag_end) = tag_end {
+            // `<v Name>` or `<v.class Name>`
+            let tag = &payload[start + 2..tag_end];
+            let name = match tag.split_once(char::is_whitespace) {
+                Some((_, name)) => name.trim(),
+                None => "",
+            };
+            if !name.is_empty() {
+                speaker = Some(decode_entities(name));
+            }
+        }
+    }
+
+    let text = strip_tags(payload)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let text = decode_entities(&text);
+
+    if speaker.is_none() {
+        if let Some((name, rest)) = split_speaker_prefix(&text) {
+            return (Some(name.to_string()), rest.to_string());
+        }
+    }
+
+    (speaker, text)
+}
+
+/// Zoom writes the speaker as a `Name: ` prefix instead of a voice tag
+fn split_speaker_prefix(text: &str) -> Option<(&str, &str)> {
+    const MAX_NAME_WORDS: usize = 4;
+
+    let (name, rest) = text.split_once(": ")?;
+    let name = name.trim();
+    let is_name = !name.is_empty()
+        && name.split_whitespace().count() <= MAX_NAME_WORDS
+        && !name.contains(['.', '?', '!', ',', '[', ']']);
+
+    if is_name && !rest.trim().is_empty() {
+        Some((name, rest.trim()))
+    } else {
+        None
+    }
+}
+
+fn strip_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+    result
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_teams_voice_tags() {
+        let vtt = "WEBVTT\n\n\
+            a1b2c3/12-0\n\
+            00:00:01.000 --> 00:00:04.500\n\
+            <v Anna Schmidt>Guten Morgen zusammen.</v>\n\n\
+            a1b2c3/13-0\n\
+            00:00:04.500 --> 00:00:07.250\n\
+            <v Jonas Weber>Morgen!</v>\n";
+
+        let transcript = parse_vtt(vtt).unwrap();
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(
+            transcript.segments[0].speaker.as_deref(),
+            Some("Anna Schmidt")
+        );
+        assert_eq!(transcript.segments[0].text, "Guten Morgen zusammen.");
+        assert_eq!(transcript.segments[0].start, 1.0);
+        assert_eq!(transcript.segments[0].end, 4.5);
+        assert_eq!(
+            transcript.segments[1].speaker.as_deref(),
+            Some("Jonas Weber")
+        );
+        assert_eq!(transcript.segments[1].end, 7.25);
+    }
+
+    #[test]
+    fn test_parse_multi_line_cue() {
+        let vtt = "WEBVTT\n\n\
+            00:01:02.000 --> 00:01:09.000 align:start position:0%\n\
+            <v Anna>Das Budget für\n\
+            das nächste Quartal\n\
+            steht fest.</v>\n";
+
+        let transcript = parse_vtt(vtt).unwrap();
+        assert_eq!(transcript.segments.len(), 1);
+        assert_eq!(
+            transcript.segments[0].text,
+            "Das Budget für das nächste Quartal steht fest."
+        );
+        assert_eq!(transcript.segments[0].start, 62.0);
+    }
+
+    #[test]
+    fn test_parse_missing_voice_tags() {
+        let vtt = "WEBVTT\n\n\
+            00:00.000 --> 00:02.000\n\
+            Just some text without speaker. Really: nothing\n\n\
+            00:02.000 --> 00:04.000\n\
+            Jonas Weber: Zoom style prefix\n";
+
+        let transcript = parse_vtt(vtt).unwrap();
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[0].speaker, None);
+        assert_eq!(
+            transcript.segments[0].text,
+            "Just some text without speaker. Really: nothing"
+        );
+        assert_eq!(
+            transcript.segments[1].speaker.as_deref(),
+            Some("Jonas Weber")
+        );
+        assert_eq!(transcript.segments[1].text, "Zoom style prefix");
+    }
+
+    #[test]
+    fn test_skips_notes_and_empty_cues_and_crlf() {
+        let vtt = "\u{feff}WEBVTT - Teams export\r\n\r\nNOTE exported by Teams\r\n\r\n\
+            00:00:00.000 --> 00:00:01.000\r\n<v Anna></v>\r\n\r\n\
+            00:00:01.000 --> 00:00:02.000\r\n<v Anna>Q&amp;A &lt;jetzt&gt;</v>\r\n";
+
+        let transcript = parse_vtt(vtt).unwrap();
+        assert_eq!(transcript.segments.len(), 1);
+        assert_eq!(transcript.segments[0].text, "Q&A <jetzt>");
+    }
+
+    #[test]
+    fn test_rejects_non_vtt() {
+        assert!(parse_vtt("1\n00:00:01,000 --> 00:00:02,000\nSRT").is_err());
+        assert!(parse_vtt("WEBVTT\n\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("01:02:03.500").unwrap(), 3723.5);
+        assert_eq!(parse_timestamp("02:03.000").unwrap(), 123.0);
+        assert!(parse_timestamp("abc").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use std::io;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, Lines};
+use tokio::time::Instant;
+
+/// Output of a child process, line by line. Lets tests drive the watchdog
+/// with a fake stream instead of a process.
+pub trait LineSource {
+    async fn next_line(&mut self) -> io::Result<Option<String>>;
+}
+
+impl<R: AsyncBufRead + Unpin> LineSource for Lines<R> {
+    async fn next_line(&mut self) -> io::Result<Option<String>> {
+        Lines::next_line(self).await
+    }
+}
+
+/// Time of the last output line, shared by the readers of stdout and stderr
+#[derive(Clone)]
+pub struct Activity(Arc<StdMutex<Instant>>);
+
+impl Activity {
+    pub fn new() -> Self {
+        Self(Arc::new(StdMutex::new(Instant::now())))
+    }
+
+    pub fn touch(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    fn last(&self) -> Instant {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Read lines until the end of the stream, resetting the inactivity timer on each
+pub async fn pump_lines<S: LineSource>(
+    mut source: S,
+    activity: &Activity,
+    mut on_line: impl FnMut(&str),
+) -> io::Result<()> {
+    while let Some(line) = source.next_line().await? {
+        activity.touch();
+        on_line(&line);
+    }
+    Ok(())
+}
+
+/// Resolves once there was no output for `idle_timeout`. Keeps counting after
+/// the streams ended, a process can hang after closing its output too.
+pub async fn stalled(activity: &Activity, idle_timeout: Duration) {
+    loop {
+        let deadline = activity.last() + idle_timeout;
+        if Instant::now() >= deadline {
+            return;
+        }
+        tokio::time::sleep_until(deadline).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    const TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+    /// Lines that arrive after the given delay, then the end of the stream
+    struct FakeLines(VecDeque<(Duration, &'static str)>);
+
+    impl FakeLines {
+        fn new(lines: &[(u64, &'static str)]) -> Self {
+            Self(
+                lines
+                    .iter()
+                    .map(|(minutes, line)| (Duration::from_secs(minutes * 60), *line))
+                    .collect(),
+            )
+        }
+    }
+
+    impl LineSource for FakeLines {
+        async fn next_line(&mut self) -> io::Result<Option<String>> {
+            match self.0.pop_front() {
+                Some((delay, line)) => {
+                    tokio::time::sleep(delay).await;
+                    Ok(Some(line.to_string()))
+                }
+                None => Ok(None),
+            }
+        }
+    }
+
+    /// Pump the fake stream until it ends or the watchdog fires,
+    /// returns the lines seen and whether it stalled
+    async fn watch(source: FakeLines) -> (Vec<String>, bool) {
+        let activity = Activity::new();
+        let mut seen = Vec::new();
+        let stalled = tokio::select! {
+            result = pump_lines(source, &activity, |line| seen.push(line.to_string())) => {
+                result.unwrap();
+                false
+            }
+            _ = stalled(&activity, TIMEOUT) => true,
+        };
+        (seen, stalled)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_every_line_resets_the_timer() {
+        let start = Instant::now();
+        // 36 minutes in total, but never more than 9 without output
+        let (seen, stalled) = watch(FakeLines::new(&[
+            (9, "Detecting language"),
+            (9, "[00:00.000 --> 00:04.000] Hallo"),
+            (9, "[00:04.000 --> 00:08.000] zusammen"),
+            (9, "Done"),
+        ]))
+        .await;
+
+        assert!(!stalled);
+        assert_eq!(seen.len(), 4);
+        assert!(start.elapsed() >= Duration::from_secs(36 * 60));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_silence_stalls() {
+        let start = Instant::now();
+        let (seen, stalled) =
+            watch(FakeLines::new(&[(1, "Loading model"), (11, "Too late")])).await;
+
+        assert!(stalled);
+        assert_eq!(seen, vec!["Loading model"]);
+        // Ten minutes after the last line, not after the start
+        assert_eq!(start.elapsed(), Duration::from_secs(11 * 60));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_counts_on_after_the_output_ended() {
+        let activity = Activity::new();
+        pump_lines(FakeLines::new(&[(2, "Done")]), &activity, |_| {})
+            .await
+            .unwrap();
+
+        let start = Instant::now();
+        stalled(&activity, TIMEOUT).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(10 * 60));
+    }
+}
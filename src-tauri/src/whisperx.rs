@@ -1,13 +1,49 @@
-use crate::audio::{analyze_audio, split_audio_into_chunks, AudioChunk};
+use crate::audio::{
+    analyze_audio, check_min_duration, chunk_duration, get_audio_duration, min_audio_duration,
+    split_audio_into_chunks, AudioChunk,
+};
+use crate::audio_cleanup;
+use crate::cancellation::CancellationToken;
+use crate::chunk_manifest::{audio_chunk_entries, save_audio_chunks};
+use crate::disk_space::{ensure_free_space, transcription_space_needed, SystemSpaceProbe};
+use crate::error::TranscriptionError;
+use crate::job_queue::claim_or_queue;
+use crate::job_state::{JobStage, JobStateFile};
+use crate::jobs::{self, wait_tracked_unless, JobKind};
+use crate::llm::file_manager::transcription_chunks_dir;
+use crate::llm::progress::ProgressEvents;
+use crate::meeting::{get_audio_path, update_meeting_metadata, write_atomic};
+use crate::model_cache::{cache_env, current_cache_dirs, model_cache_info};
+use crate::settings::load_settings;
+use crate::transcript::{merge_chunk_transcripts, ChunkTranscript, Transcript};
+use crate::transcript_check::{check_transcript, record_transcription_warnings};
+use crate::transcription_config::{
+    downloaded_models, get_hf_token, TranscriptionConfig, WhisperModelSize,
+    DEFAULT_CHUNK_DURATION_SECONDS,
+};
+use crate::transcription_progress::ProgressReporter;
+use crate::watchdog::{pump_lines, stalled, Activity};
+use crate::whisperx_stderr::StderrLog;
 use crate::AppState;
-use std::process::Stdio;
-use tauri::{AppHandle, Emitter, Manager, State};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
 use tauri_plugin_http::reqwest;
 use tokio::fs;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::Mutex;
 
+pub const DEFAULT_WHISPER_MODEL: &str = "mlx-community/whisper-turbo";
+/// Used when the settings can't be read
+const DEFAULT_IDLE_TIMEOUT_MINUTES: u64 = 10;
+/// What the audio chunks in `chunks/transcription/` were cut from, kept until
+/// every chunk is transcribed
+const CHUNK_SPLIT_FILE_NAME: &str = "split.json";
+
 /// Detects the current platform and returns the appropriate Python download URL
 fn get_python_download_url() -> Result<String, String> {
     let base_url =
@@ -74,7 +110,7 @@ fn get_python_download_url() -> Result<String, String> {
 }
 
 /// Gets the Python executable path based on the platform
-fn get_python_executable_path(python_dir: &std::path::Path) -> String {
+pub fn get_python_executable_path(python_dir: &std::path::Path) -> String {
     if cfg!(windows) {
         format!("{}/python.exe", python_dir.display())
     } else {
@@ -112,6 +148,7 @@ pub async fn check_python_installation(app: AppHandle) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn download_python(app: AppHandle) -> Result<(), String> {
+    let events = ProgressEvents::new(app.clone());
     let app_dir = app
         .path()
         .app_local_data_dir()
@@ -123,20 +160,18 @@ pub async fn download_python(app: AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to create resource directory: {}", e))?;
 
-    app.emit(
+    events.emit(
         "python-download-progress",
         "Detecting platform and selecting Python version...",
-    )
-    .unwrap();
+    );
 
     // Get the appropriate download URL for this platform
     let download_url = get_python_download_url()?;
 
-    app.emit(
+    events.emit(
         "python-download-progress",
         &format!("Downloading Python from: {}", download_url),
-    )
-    .unwrap();
+    );
 
     let res = reqwest::get(&download_url).await;
 
@@ -148,8 +183,7 @@ pub async fn download_python(app: AppHandle) -> Result<(), String> {
         return Err(format!("Failed to download Python: {}", response.status()));
     }
 
-    app.emit("python-download-progress", "Downloading Python tarball...")
-        .unwrap();
+    events.emit("python-download-progress", "Downloading Python tarball...");
 
     let tarball = response
         .bytes()
@@ -168,8 +202,7 @@ pub async fn download_python(app: AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to write Python tarball: {}", e))?;
 
-    app.emit("python-download-progress", "Extracting Python...")
-        .unwrap();
+    events.emit("python-download-progress", "Extracting Python...");
 
     // Extract the tarball - use appropriate command based on file type
     let extract_result = if download_url.ends_with(".tar.gz") {
@@ -202,8 +235,7 @@ pub async fn download_python(app: AppHandle) -> Result<(), String> {
         ));
     }
 
-    app.emit("python-download-progress", "Cleaning up...")
-        .unwrap();
+    events.emit("python-download-progress", "Cleaning up...");
 
     // Clean up the tarball
     fs::remove_file(&tarball_path)
@@ -214,8 +246,7 @@ pub async fn download_python(app: AppHandle) -> Result<(), String> {
     if cfg!(unix) {
         let python_dir = resource_path.join("python");
 
-        app.emit("python-download-progress", "Setting permissions...")
-            .unwrap();
+        events.emit("python-download-progress", "Setting permissions...");
 
         let output = Command::new("chmod")
             .arg("-R")
@@ -234,17 +265,26 @@ pub async fn download_python(app: AppHandle) -> Result<(), String> {
         }
     }
 
-    app.emit(
-        "python-download-progress",
-        "Python installation completed successfully!",
-    )
-    .unwrap();
+    events
+        .emit_terminal(
+            "python-download-progress",
+            "Python installation completed successfully!",
+        )
+        .await;
 
     Ok(())
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct WhisperxInstallation {
+    /// Model sizes already in the model cache
+    pub downloaded_models: Vec<WhisperModelSize>,
+    /// The selected model isn't cached, the next transcription downloads it first
+    pub needs_download: bool,
+}
+
 #[tauri::command]
-pub async fn check_whisperx_installation(app: AppHandle) -> Result<(), String> {
+pub async fn check_whisperx_installation(app: AppHandle) -> Result<WhisperxInstallation, String> {
     check_python_installation(app.clone()).await?;
 
     // Get python resource path
@@ -267,28 +307,42 @@ pub async fn check_whisperx_installation(app: AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to execute whisperx: {}", e))?;
 
-    if output.status.success() {
-        Ok(())
-    } else {
+    if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!(
+        return Err(format!(
             "whisperx not found or returned error: {}",
             stderr.trim()
-        ))
+        ));
     }
+
+    let dirs = current_cache_dirs(&app).await;
+    let cached = tokio::task::spawn_blocking(move || model_cache_info(&dirs).models)
+        .await
+        .map_err(|e| e.to_string())?;
+    let config = transcription_config(&app).await;
+    Ok(WhisperxInstallation {
+        downloaded_models: downloaded_models(&cached),
+        needs_download: config.needs_download(&cached),
+    })
+}
+
+async fn transcription_config(app: &AppHandle) -> TranscriptionConfig {
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().await;
+    state.transcription_config.clone()
 }
 
 #[tauri::command]
 pub async fn download_whisperx(app: AppHandle) -> Result<(), String> {
+    let events = ProgressEvents::new(app.clone());
     // Check if Python is installed
     check_python_installation(app.clone()).await?;
 
     // Emit start event
-    app.emit(
+    events.emit(
         "whisperx-download-progress",
         "Starting WhisperX download...",
-    )
-    .unwrap();
+    );
 
     // Get python resource path
     let app_dir = app
@@ -306,11 +360,10 @@ pub async fn download_whisperx(app: AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to create lib directory: {}", e))?;
 
-    app.emit(
+    events.emit(
         "whisperx-download-progress",
         "Installing WhisperX and dependencies...",
-    )
-    .unwrap(); // Spawn pip install process with piped output for progress tracking
+    ); // Spawn pip install process with piped output for progress tracking
     let python_exe = get_python_executable_path(&resource_path);
     let mut child = Command::new(&python_exe)
         .arg("-m")
@@ -329,7 +382,7 @@ pub async fn download_whisperx(app: AppHandle) -> Result<(), String> {
     let stdout = child.stdout.take().expect("Failed to take stdout");
     let stderr = child.stderr.take().expect("Failed to take stderr");
 
-    let app_clone = app.clone();
+    let stdout_events = events.clone();
     let stdout_task = tokio::spawn(async move {
         let mut lines = BufReader::new(stdout).lines();
         while let Some(line) = lines.next_line().await.unwrap_or(None) {
@@ -337,12 +390,12 @@ pub async fn download_whisperx(app: AppHandle) -> Result<(), String> {
                 || line.contains("Installing")
                 || line.contains("Successfully")
             {
-                app_clone.emit("whisperx-download-progress", &line).unwrap();
+                stdout_events.emit("whisperx-download-progress", &line);
             }
         }
     });
 
-    let app_clone2 = app.clone();
+    let stderr_events = events.clone();
     let stderr_task = tokio::spawn(async move {
         let mut lines = BufReader::new(stderr).lines();
         while let Some(line) = lines.next_line().await.unwrap_or(None) {
@@ -350,9 +403,7 @@ pub async fn download_whisperx(app: AppHandle) -> Result<(), String> {
                 || line.contains("Installing")
                 || line.contains("Successfully")
             {
-                app_clone2
-                    .emit("whisperx-download-progress", &line)
-                    .unwrap();
+                stderr_events.emit("whisperx-download-progress", &line);
             }
         }
     });
@@ -366,50 +417,249 @@ pub async fn download_whisperx(app: AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Failed to wait on pip install: {}", e))?;
 
     if !status.success() {
-        app.emit("whisperx-download-progress", "Installation failed")
-            .unwrap();
+        events
+            .emit_terminal("whisperx-download-progress", "Installation failed")
+            .await;
         return Err("Failed to install whisperx".to_string());
     }
 
-    app.emit(
-        "whisperx-download-progress",
-        "WhisperX installation completed successfully!",
-    )
-    .unwrap();
+    events
+        .emit_terminal(
+            "whisperx-download-progress",
+            "WhisperX installation completed successfully!",
+        )
+        .await;
     Ok(())
 }
 
+/// Audio chunking can need twice the recording's size, check before starting
+async fn ensure_transcription_space(base_dir: &Path, audio_path: &Path) -> Result<(), String> {
+    let audio_bytes = fs::metadata(audio_path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    ensure_free_space(
+        &SystemSpaceProbe,
+        base_dir,
+        transcription_space_needed(audio_bytes),
+    )
+    .map_err(String::from)
+}
+
+/// The transcription slot of a running job. Dropping it frees the slot too,
+/// so an early return or a panic can't leave the app thinking a
+/// transcription is still running.
+pub struct TranscriptionSlot<R: Runtime = Wry> {
+    app: AppHandle<R>,
+    meeting_id: String,
+    /// `None` once released
+    job_id: Option<String>,
+}
+
+impl<R: Runtime> TranscriptionSlot<R> {
+    pub fn new(app: &AppHandle<R>, meeting_id: &str, job_id: String) -> Self {
+        Self {
+            app: app.clone(),
+            meeting_id: meeting_id.to_string(),
+            job_id: Some(job_id),
+        }
+    }
+
+    pub async fn release(mut self) {
+        if let Some(job_id) = self.job_id.take() {
+            let state = self.app.state::<Mutex<AppState>>();
+            free_transcription_slot(&mut *state.lock().await, &self.meeting_id, &job_id);
+        }
+    }
+}
+
+impl<R: Runtime> Drop for TranscriptionSlot<R> {
+    fn drop(&mut self) {
+        let Some(job_id) = self.job_id.take() else {
+            return;
+        };
+        let state = self.app.state::<Mutex<AppState>>();
+        if let Ok(mut state) = state.try_lock() {
+            free_transcription_slot(&mut state, &self.meeting_id, &job_id);
+            return;
+        }
+
+        let app = self.app.clone();
+        let meeting_id = self.meeting_id.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<Mutex<AppState>>();
+            free_transcription_slot(&mut *state.lock().await, &meeting_id, &job_id);
+        });
+    }
+}
+
+/// Free the slot unless another run of the meeting took over in the meantime
+fn free_transcription_slot(state: &mut AppState, meeting_id: &str, job_id: &str) {
+    if state.jobs.has_job(job_id) && state.currently_transcribing.as_deref() == Some(meeting_id) {
+        state.release_transcription();
+    }
+    state.jobs.finish_job(job_id);
+}
+
+/// `diarize`, `min_speakers` and `max_speakers` override the transcription
+/// settings for this meeting only
 #[tauri::command]
 pub async fn transcribe(
     app: AppHandle,
     meeting_id: &str,
+    diarize: Option<bool>,
+    min_speakers: Option<u32>,
+    max_speakers: Option<u32>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
     // Check if WhisperX is Available
     check_whisperx_installation(app.clone()).await?;
 
-    // Check if another transcription is already running
-    // Lock the mutex to get mutable access:
-    let mut state = state.lock().await;
-
-    if state.currently_transcribing.is_some() {
-        return Err("Another Transcription is running".to_string());
-    }
-
-    // Modify the state:
-    state.currently_transcribing = Some(meeting_id.to_string());
-
-    app.emit(meeting_id, "transcription-started").unwrap();
     let app_dir = app
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.ogg", meeting_id);
-    let audio_path = base_dir.join(file_name);
+    let audio_path = get_audio_path(&base_dir, meeting_id).await?;
+    let duration_seconds = get_audio_duration(&audio_path).await?;
+    check_min_duration(duration_seconds, min_audio_duration(&app).await)?;
+    ensure_transcription_space(&base_dir, &audio_path).await?;
+    let config =
+        transcription_config(&app)
+            .await
+            .with_overrides(diarize, min_speakers, max_speakers);
+    config.validate()?;
+    let hf_token = if config.diarize {
+        get_hf_token()?
+    } else {
+        None
+    };
+    let extra_args = whisperx_args(&config, hf_token)?;
+
+    // Wait for another transcription, or a summary of this meeting, to finish
+    let mut state_lock = claim_or_queue(&state, JobKind::Transcription, meeting_id).await?;
+    let cancellation = CancellationToken::default();
+    state_lock.transcription_cancellation = Some(cancellation.clone());
+    let job_id = state_lock
+        .jobs
+        .start_job(JobKind::Transcription, meeting_id);
+    drop(state_lock); // Release the lock so the exit handler can reach the child
+    let slot = TranscriptionSlot::new(&app, meeting_id, job_id);
+
+    let events = ProgressEvents::new(app.clone());
+    events.emit(meeting_id, "transcription-started");
 
     println!("Uploading to {}", audio_path.display());
 
+    let progress = ProgressReporter::new(events.clone(), meeting_id, duration_seconds);
+    let result = run_whisperx(
+        &app,
+        &app_dir,
+        &audio_path,
+        &base_dir,
+        &extra_args,
+        &cancellation,
+        progress,
+    )
+    .await;
+    slot.release().await;
+
+    match &result {
+        // whisperx wrote fresh subtitle files
+        Ok(()) => {
+            update_meeting_metadata(&base_dir, meeting_id, |metadata| {
+                metadata.stale_exports.clear();
+                metadata.audio_duration_seconds = Some(duration_seconds);
+            })
+            .await?;
+            let transcript_path = base_dir.join(format!("{}.txt", meeting_id));
+            match fs::read_to_string(&transcript_path).await {
+                Ok(transcript) => {
+                    after_transcription(
+                        &app,
+                        &base_dir,
+                        meeting_id,
+                        &transcript,
+                        duration_seconds,
+                        true,
+                    )
+                    .await
+                }
+                Err(e) => println!("Warning: Failed to read the transcript: {}", e),
+            }
+        }
+        Err(TranscriptionError::Cancelled) => {
+            emit_transcription_cancelled(&events, meeting_id).await;
+        }
+        Err(e) => {
+            emit_if_stalled(&events, meeting_id, None, e);
+            emit_transcription_failed(&events, meeting_id, e).await;
+        }
+    }
+    result.map_err(String::from)
+}
+
+/// What both transcription commands do once the transcript is written.
+/// `complete` is false when parts of the audio are missing from it.
+async fn after_transcription(
+    app: &AppHandle,
+    base_dir: &Path,
+    meeting_id: &str,
+    transcript: &str,
+    duration_seconds: f64,
+    complete: bool,
+) {
+    let warnings = check_transcript(transcript, duration_seconds);
+    if let Err(e) = record_transcription_warnings(app, base_dir, meeting_id, &warnings).await {
+        println!("Warning: Failed to record transcription warnings: {}", e);
+    }
+
+    // Only consider deleting the audio when all of it made it into a plausible transcript
+    if !complete || !warnings.is_empty() {
+        return;
+    }
+    if let Err(e) =
+        audio_cleanup::handle_audio_after_transcription(app, base_dir, meeting_id, duration_seconds)
+            .await
+    {
+        println!("Keeping audio for meeting {}: {}", meeting_id, e);
+    }
+}
+
+/// `--model` and the diarization arguments of whisperx. Diarization without a
+/// token is refused here, pyannote would only fail after the transcription.
+fn whisperx_args(
+    config: &TranscriptionConfig,
+    hf_token: Option<String>,
+) -> Result<Vec<String>, TranscriptionError> {
+    let mut args = Vec::new();
+    if let Some(model) = config.whisperx_model() {
+        args.extend(["--model".to_string(), model.to_string()]);
+    }
+    if config.diarize {
+        let hf_token = hf_token
+            .filter(|token| !token.trim().is_empty())
+            .ok_or(TranscriptionError::MissingHuggingFaceToken)?;
+        args.extend(["--diarize".to_string(), "--hf_token".to_string(), hf_token]);
+        if let Some(min_speakers) = config.min_speakers {
+            args.extend(["--min_speakers".to_string(), min_speakers.to_string()]);
+        }
+        if let Some(max_speakers) = config.max_speakers {
+            args.extend(["--max_speakers".to_string(), max_speakers.to_string()]);
+        }
+    }
+    Ok(args)
+}
+
+async fn run_whisperx(
+    app: &AppHandle,
+    app_dir: &std::path::Path,
+    audio_path: &std::path::Path,
+    base_dir: &std::path::Path,
+    extra_args: &[String],
+    cancellation: &CancellationToken,
+    progress: ProgressReporter,
+) -> Result<(), TranscriptionError> {
     let resource_path = app_dir.join("python");
     let lib_path = resource_path
         .join("lib")
@@ -417,64 +667,183 @@ pub async fn transcribe(
         .join("site-packages");
     println!("{:?}", resource_path); // Spawn whisperx process with piped stdout and inherited stderr
     let python_exe = get_python_executable_path(&resource_path);
-    let mut child = Command::new(&python_exe)
+    let mut command = Command::new(&python_exe);
+    command
         .env("PYTHONPATH", &lib_path)
+        .envs(cache_env(app).await)
         .arg("-m")
         .arg("whisperx")
-        .arg(&audio_path)
+        .arg(audio_path)
         .arg("--device")
         .arg("cpu")
         .arg("--compute_type")
         .arg("int8")
-        .arg("--diarize")
+        .arg("--print_progress")
+        .arg("True")
         .arg("--output_dir")
-        .arg(&base_dir)
+        .arg(base_dir)
+        .args(extra_args);
+
+    let (status, stderr_log) = run_watched(app, &mut command, cancellation, progress).await?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(stderr_log.into_error(status))
+    }
+}
+
+/// Run a whisperx / mlx_whisper process and print its output. The process is
+/// killed once it printed nothing for the inactivity timeout from the settings,
+/// e.g. when its dataloader deadlocked, or when `cancellation` is cancelled.
+/// Progress lines on either stream go to `progress`.
+async fn run_watched(
+    app: &AppHandle,
+    command: &mut Command,
+    cancellation: &CancellationToken,
+    progress: ProgressReporter,
+) -> Result<(ExitStatus, StderrLog), TranscriptionError> {
+    let idle_timeout = load_settings(app)
+        .await
+        .map(|settings| settings.transcription_idle_timeout_minutes)
+        .unwrap_or(DEFAULT_IDLE_TIMEOUT_MINUTES);
+    let idle_timeout = Duration::from_secs(idle_timeout * 60);
+
+    let mut child = jobs::own_process_group(command)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true)
         .spawn()
-        .map_err(|e| format!("Failed to spawn whisperx: {}", e))?; // Pipe and read stderr concurrently (uvx logs may come here)
+        .map_err(|e| process_error("Failed to spawn whisperx", e))?;
+    let activity = Activity::new();
+    let progress = Arc::new(progress);
+
+    // Pipe and read stderr concurrently (uvx logs may come here)
     let stderr = child.stderr.take().expect("Failed to take stderr");
+    let stderr_activity = activity.clone();
+    let stderr_progress = progress.clone();
     let stderr_task = tokio::spawn(async move {
-        let mut errs = BufReader::new(stderr).lines();
-        while let Some(line) = errs
-            .next_line()
-            .await
-            .map_err(|e| format!("Error reading stderr: {}", e))?
-        {
+        let mut log = StderrLog::default();
+        pump_lines(BufReader::new(stderr).lines(), &stderr_activity, |line| {
             println!("{}", line);
-        }
-        Ok::<(), String>(())
+            stderr_progress.observe(line);
+            log.push(line);
+        })
+        .await
+        .map(|()| log)
     });
-
-    // Read stdout line by line, print and emit events
-    if let Some(stdout) = child.stdout.take() {
-        let mut lines = BufReader::new(stdout).lines();
-        while let Some(line) = lines
-            .next_line()
-            .await
-            .map_err(|e| format!("Error reading stdout: {}", e))?
-        {
+    let stdout = child.stdout.take().expect("Failed to take stdout");
+    let stdout_activity = activity.clone();
+    let stdout_task = tokio::spawn(async move {
+        pump_lines(BufReader::new(stdout).lines(), &stdout_activity, |line| {
             println!("{}", line);
+            progress.observe(line);
+        })
+        .await
+    });
+
+    let stop = async {
+        tokio::select! {
+            _ = stalled(&activity, idle_timeout) => {}
+            _ = cancellation.cancelled() => {}
         }
-    }
+    };
+    let status = wait_tracked_unless(app, child, stop)
+        .await
+        .map_err(|e| process_error("Failed to wait on whisperx", e))?;
+    let Some(status) = status else {
+        // Outside of unix a child of the killed process may still hold the pipes open
+        stdout_task.abort();
+        stderr_task.abort();
+        if cancellation.is_cancelled() {
+            return Err(TranscriptionError::Cancelled);
+        }
+        return Err(TranscriptionError::Stalled {
+            idle_seconds: idle_timeout.as_secs(),
+        });
+    };
 
-    // Wait for stderr reader to finish and process exit
-    stderr_task
+    stdout_task
         .await
-        .map_err(|e| format!("stderr task join error: {}", e))??;
-    let status = child
-        .wait()
+        .map_err(|e| process_error("stdout task join error", e))?
+        .map_err(|e| process_error("Error reading stdout", e))?;
+    let stderr_log = stderr_task
         .await
-        .map_err(|e| format!("Failed to wait on whisperx: {}", e))?;
+        .map_err(|e| process_error("stderr task join error", e))?
+        .map_err(|e| process_error("Error reading stderr", e))?;
+    Ok((status, stderr_log))
+}
 
-    // Clear transcription state
-    state.currently_transcribing = None;
+fn process_error(context: &str, error: impl std::fmt::Display) -> TranscriptionError {
+    TranscriptionError::Process {
+        message: format!("{}: {}", context, error),
+    }
+}
 
-    if status.success() {
-        Ok(())
-    } else {
-        Err(format!("whisperx exited with status: {}", status))
+#[derive(Clone, Serialize)]
+struct TranscriptionFailed<'a> {
+    meeting_id: &'a str,
+    message: String,
+    hint: Option<&'static str>,
+    error: &'a TranscriptionError,
+}
+
+#[derive(Clone, Serialize)]
+struct TranscriptionStalled<'a> {
+    meeting_id: &'a str,
+    /// `None` when the recording was transcribed in one piece
+    chunk_index: Option<usize>,
+    idle_seconds: u64,
+}
+
+fn emit_if_stalled(
+    events: &ProgressEvents,
+    meeting_id: &str,
+    chunk_index: Option<usize>,
+    error: &TranscriptionError,
+) {
+    let TranscriptionError::Stalled { idle_seconds } = error else {
+        return;
+    };
+    let payload = TranscriptionStalled {
+        meeting_id,
+        chunk_index,
+        idle_seconds: *idle_seconds,
+    };
+    events.emit("transcription-stalled", payload);
+}
+
+async fn emit_transcription_failed(
+    events: &ProgressEvents,
+    meeting_id: &str,
+    error: &TranscriptionError,
+) {
+    let payload = TranscriptionFailed {
+        meeting_id,
+        message: error.to_string(),
+        hint: error.hint(),
+        error,
+    };
+    events.emit_terminal("transcription-failed", payload).await;
+}
+
+/// Output of the chunks transcribed so far stays in `chunks/transcription/`
+async fn emit_transcription_cancelled(events: &ProgressEvents, meeting_id: &str) {
+    println!("⏹️  Transcription of {} cancelled", meeting_id);
+    events
+        .emit_terminal("transcription-cancelled", meeting_id)
+        .await;
+}
+
+/// Stop the transcription of `meeting_id` and kill its process. Does nothing
+/// when the meeting isn't being transcribed.
+#[tauri::command]
+pub async fn cancel_transcription(app: AppHandle, meeting_id: &str) -> Result<(), String> {
+    let state = app.state::<Mutex<AppState>>();
+    let mut state = state.lock().await;
+    if state.cancel_transcription(meeting_id) {
+        println!("Cancelling the transcription of {}", meeting_id);
     }
+    Ok(())
 }
 
 #[tauri::command]
@@ -486,65 +855,175 @@ pub async fn is_transcribing(app: AppHandle) -> Result<Option<String>, String> {
     Ok(state.currently_transcribing.clone())
 }
 
-/// Enhanced transcribe function that handles audio chunking automatically
+/// The recording the audio chunks were cut from. Chunk output of an earlier
+/// run is only reused while it matches.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ChunkSplit {
+    audio_bytes: u64,
+    duration_seconds: f64,
+    chunk_count: usize,
+    /// Splits written before the duration was configurable used the default
+    #[serde(default = "default_split_chunk_duration")]
+    chunk_duration_seconds: f64,
+}
+
+fn default_split_chunk_duration() -> f64 {
+    DEFAULT_CHUNK_DURATION_SECONDS as f64
+}
+
+async fn read_chunk_split(chunks_dir: &Path) -> Option<ChunkSplit> {
+    let json = fs::read_to_string(chunks_dir.join(CHUNK_SPLIT_FILE_NAME))
+        .await
+        .ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+async fn write_chunk_split(chunks_dir: &Path, split: &ChunkSplit) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(split).map_err(|e| e.to_string())?;
+    write_atomic(&chunks_dir.join(CHUNK_SPLIT_FILE_NAME), json)
+        .await
+        .map_err(|e| format!("Failed to write chunk split: {}", e))
+}
+
+/// The whisper JSON of one audio chunk, not parsed yet
+struct ChunkJson {
+    chunk_index: usize,
+    start_time: f64,
+    json: String,
+}
+
+/// One transcript from the whisper JSON of each chunk. Unparsable chunks are left out.
+fn merge_chunk_json(parts: &[ChunkJson]) -> Transcript {
+    let transcripts = parts
+        .iter()
+        .filter_map(
+            |part| match serde_json::from_str::<Transcript>(&part.json) {
+                Ok(transcript) => Some(ChunkTranscript {
+                    chunk_index: part.chunk_index,
+                    start_time: part.start_time,
+                    transcript,
+                }),
+                Err(e) => {
+                    println!(
+                        "Warning: Skipping unparsable transcript of chunk {}: {}",
+                        part.chunk_index + 1,
+                        e
+                    );
+                    None
+                }
+            },
+        )
+        .collect();
+    merge_chunk_transcripts(transcripts)
+}
+
+/// `<chunk_stem>.txt` and `<chunk_stem>.json` written by whisper next to the chunk
+fn chunk_output_paths(chunk_path: &Path) -> (PathBuf, PathBuf) {
+    let chunk_dir = chunk_path.parent().unwrap_or(Path::new(""));
+    let chunk_stem = chunk_path.file_stem().unwrap_or_default().to_string_lossy();
+    (
+        chunk_dir.join(format!("{}.txt", chunk_stem)),
+        chunk_dir.join(format!("{}.json", chunk_stem)),
+    )
+}
+
+/// Text and JSON output of a chunk, `None` unless both were written
+async fn read_chunk_output(chunk_path: &Path) -> Option<(String, String)> {
+    let (txt_path, json_path) = chunk_output_paths(chunk_path);
+    let txt = fs::read_to_string(txt_path).await.ok()?;
+    let json = fs::read_to_string(json_path).await.ok()?;
+    Some((txt, json))
+}
+
+#[derive(Clone, Serialize)]
+struct ChunkReuse<'a> {
+    meeting_id: &'a str,
+    /// Chunks with output of an earlier, unfinished run
+    reused: usize,
+    transcribing: usize,
+}
+
+/// Enhanced transcribe function that handles audio chunking automatically.
+/// Chunks transcribed by an unfinished earlier run are reused unless `force` is set.
 #[tauri::command]
 pub async fn transcribe_with_chunking(
     app: AppHandle,
     meeting_id: &str,
+    force: Option<bool>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    // Check if another transcription is already running
-    let mut state_lock = state.lock().await;
-
-    if state_lock.currently_transcribing.is_some() {
-        return Err("Another Transcription is running".to_string());
-    }
-
-    // Modify the state:
-    state_lock.currently_transcribing = Some(meeting_id.to_string());
-    drop(state_lock); // Release the lock early
-
-    app.emit(meeting_id, "transcription-started").unwrap();
-
     let app_dir = app
         .path()
         .app_local_data_dir()
         .expect("Failed to get app local data directory");
     let base_dir = app_dir.join("uploads").join(meeting_id);
-    let file_name = format!("{}.ogg", meeting_id);
-    let audio_path = base_dir.join(file_name);
+    let audio_path = get_audio_path(&base_dir, meeting_id).await?;
 
     println!("Analyzing audio file: {}", audio_path.display());
 
     // Analyze the audio to determine if chunking is needed
-    let audio_info = analyze_audio(&audio_path).await.map_err(|e| {
-        // Clear state on error
-        let mut state_lock = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(state.lock())
-        });
-        state_lock.currently_transcribing = None;
-        e
-    })?;
+    let min_duration_seconds = min_audio_duration(&app).await;
+    let chunk_duration_seconds = chunk_duration(&app).await;
+    let audio_info =
+        analyze_audio(&audio_path, min_duration_seconds, chunk_duration_seconds).await?;
+    if audio_info.too_short {
+        check_min_duration(audio_info.duration_seconds, min_duration_seconds)?;
+    }
+    ensure_transcription_space(&base_dir, &audio_path).await?;
+
+    // Wait for another transcription, or a summary of this meeting, to finish
+    let mut state_lock = claim_or_queue(&state, JobKind::Transcription, meeting_id).await?;
+    let cancellation = CancellationToken::default();
+    state_lock.transcription_cancellation = Some(cancellation.clone());
+    let job_id = state_lock
+        .jobs
+        .start_job(JobKind::Transcription, meeting_id);
+    drop(state_lock); // Release the lock early
+    let slot = TranscriptionSlot::new(&app, meeting_id, job_id);
+
+    let events = ProgressEvents::new(app.clone());
+    events.emit(meeting_id, "transcription-started");
 
     println!("Audio duration: {:.2} seconds", audio_info.duration_seconds);
     println!("Needs splitting: {}", audio_info.needs_splitting);
     println!("Chunk count: {}", audio_info.chunk_count);
 
+    let chunks_dir = transcription_chunks_dir(&base_dir);
+    let mut resuming = false;
     let chunks = if audio_info.needs_splitting {
         println!(
-            "Audio is longer than 30 minutes, splitting into {} chunks",
-            audio_info.chunk_count
+            "Audio is longer than {} seconds, splitting into {} chunks",
+            chunk_duration_seconds, audio_info.chunk_count
         );
-        split_audio_into_chunks(&audio_path, &base_dir, meeting_id, app.clone())
-            .await
-            .map_err(|e| {
-                // Clear state on error
-                let mut state_lock = tokio::task::block_in_place(|| {
-                    tokio::runtime::Handle::current().block_on(state.lock())
-                });
-                state_lock.currently_transcribing = None;
-                e
-            })?
+        let split = ChunkSplit {
+            audio_bytes: fs::metadata(&audio_path)
+                .await
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+            duration_seconds: audio_info.duration_seconds,
+            chunk_count: audio_info.chunk_count,
+            chunk_duration_seconds,
+        };
+        resuming =
+            !force.unwrap_or(false) && read_chunk_split(&chunks_dir).await.as_ref() == Some(&split);
+        // Chunks of an earlier transcription may have been cut differently
+        if chunks_dir.exists() && !resuming {
+            if let Err(e) = fs::remove_dir_all(&chunks_dir).await {
+                println!("Warning: Failed to clear old audio chunks: {}", e);
+            }
+        }
+        let chunks = split_audio_into_chunks(
+            &audio_path,
+            &chunks_dir,
+            meeting_id,
+            chunk_duration_seconds,
+            app.clone(),
+        )
+        .await?;
+        if let Err(e) = write_chunk_split(&chunks_dir, &split).await {
+            println!("Warning: {}", e);
+        }
+        chunks
     } else {
         println!("Audio is under 30 minutes, processing as single file");
         vec![AudioChunk {
@@ -556,134 +1035,362 @@ pub async fn transcribe_with_chunking(
     };
 
     // Transcribe each chunk
-    let mut all_transcripts = Vec::new();
+    // One entry per audio chunk so the manifest can map text back to time
+    let mut chunk_transcripts: Vec<Option<String>> = Vec::new();
     let mut all_json_parts = Vec::new();
+    let mut failed_chunks = 0;
+    let mut last_error = None;
 
-    app.emit("whisperx-start", chunks.len()).unwrap();
+    let mut reusable_outputs = Vec::new();
+    for chunk in &chunks {
+        let output = if resuming {
+            read_chunk_output(Path::new(&chunk.file_path)).await
+        } else {
+            None
+        };
+        reusable_outputs.push(output);
+    }
+    let reused = reusable_outputs.iter().flatten().count();
+    if reused > 0 {
+        println!(
+            "Reusing the transcripts of {} of {} chunks",
+            reused,
+            chunks.len()
+        );
+    }
+    events.emit(
+        "whisperx-chunks",
+        ChunkReuse {
+            meeting_id,
+            reused,
+            transcribing: chunks.len() - reused,
+        },
+    );
+
+    events.emit("whisperx-start", chunks.len());
+    let mut job_state = JobStateFile::start(&base_dir, JobKind::Transcription, chunks.len()).await;
+
+    for ((i, chunk), reusable_output) in chunks.iter().enumerate().zip(reusable_outputs) {
+        if jobs::is_shutting_down(&app).await {
+            println!("App is exiting, skipping the remaining chunks");
+            break;
+        }
+        if cancellation.is_cancelled() {
+            break;
+        }
 
-    for (i, chunk) in chunks.iter().enumerate() {
+        events.emit("whisperx-progress", i);
+        if let Some((txt_content, json_content)) = reusable_output {
+            println!("Chunk {} of {} was transcribed before", i + 1, chunks.len());
+            chunk_transcripts.push(Some(txt_content));
+            all_json_parts.push(ChunkJson {
+                chunk_index: i,
+                start_time: chunk.start_time,
+                json: json_content,
+            });
+            job_state.complete_chunk().await;
+            continue;
+        }
         println!("Transcribing chunk {} of {}", i + 1, chunks.len());
-        app.emit("whisperx-progress", i).unwrap();
 
         let chunk_path = std::path::Path::new(&chunk.file_path);
         let chunk_dir = chunk_path.parent().unwrap(); // Run whisperx on this chunk
-        let result = transcribe_single_chunk(&app, chunk_path, chunk_dir).await;
+        let mut progress =
+            ProgressReporter::new(events.clone(), meeting_id, audio_info.duration_seconds);
+        if audio_info.needs_splitting {
+            progress = progress.for_chunk(i, chunk.start_time, chunk.end_time);
+        }
+        let result =
+            transcribe_single_chunk(&app, chunk_path, chunk_dir, &cancellation, progress).await;
+        let mut chunk_transcript = None;
 
         match result {
             Ok(_) => {
                 // Read the generated transcript files for this chunk
-                let chunk_stem = chunk_path.file_stem().unwrap().to_string_lossy();
-                let txt_path = chunk_dir.join(format!("{}.txt", chunk_stem));
-                let json_path = chunk_dir.join(format!("{}.json", chunk_stem));
+                let (txt_path, json_path) = chunk_output_paths(chunk_path);
 
                 if let Ok(txt_content) = fs::read_to_string(&txt_path).await {
-                    all_transcripts.push(txt_content);
+                    chunk_transcript = Some(txt_content);
                 }
 
                 if let Ok(json_content) = fs::read_to_string(&json_path).await {
-                    all_json_parts.push(json_content);
+                    all_json_parts.push(ChunkJson {
+                        chunk_index: i,
+                        start_time: chunk.start_time,
+                        json: json_content,
+                    });
                 }
             }
+            Err(TranscriptionError::Cancelled) => break,
             Err(e) => {
                 println!("Warning: Failed to transcribe chunk {}: {}", i + 1, e);
+                emit_if_stalled(&events, meeting_id, Some(i), &e);
+                failed_chunks += 1;
+                last_error = Some(e);
                 // Continue with other chunks rather than failing completely
             }
         }
+
+        chunk_transcripts.push(chunk_transcript);
+        job_state.complete_chunk().await;
     }
 
-    // Combine all transcripts into final files
-    let combined_transcript = all_transcripts.join("\n\n");
-    let final_txt_path = base_dir.join(format!("{}.txt", meeting_id));
+    // The transcript is left as it was, finished chunks keep their output
+    if cancellation.is_cancelled() {
+        job_state.finish().await;
+        slot.release().await;
+        emit_transcription_cancelled(&events, meeting_id).await;
+        return Err(TranscriptionError::Cancelled.into());
+    }
 
-    if let Err(e) = fs::write(&final_txt_path, combined_transcript).await {
-        println!("Warning: Failed to write combined transcript: {}", e);
+    // Nothing was transcribed, report why instead of writing an empty transcript
+    if let Some(error) = last_error.filter(|_| failed_chunks == chunks.len()) {
+        job_state.finish().await;
+        slot.release().await;
+        emit_transcription_failed(&events, meeting_id, &error).await;
+        return Err(error.into());
     }
 
-    // For JSON, we'll combine them into an array or concatenate based on format
-    if !all_json_parts.is_empty() {
-        let combined_json = if all_json_parts.len() == 1 {
-            all_json_parts[0].clone()
-        } else {
-            // Combine multiple JSON chunks - this is a simple concatenation
-            // In a real scenario, you might want to parse and properly merge JSON
-            all_json_parts.join("\n")
-        };
+    job_state.set_stage(JobStage::Combining).await;
+
+    // Unsplit recordings keep whisper's JSON as it is
+    let merged = match all_json_parts.as_slice() {
+        [] => None,
+        [part] if part.chunk_index == 0 => None,
+        parts => Some(merge_chunk_json(parts)),
+    };
+
+    // Combine all transcripts into final files
+    let mut combined_transcript = chunk_transcripts
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    // Speakers were relabeled per chunk, the text has to use the same labels
+    if let Some(merged) = merged.as_ref().filter(|merged| merged.has_speakers()) {
+        combined_transcript = merged.to_text();
+    }
+    let final_txt_path = base_dir.join(format!("{}.txt", meeting_id));
+
+    let transcript_written = match fs::write(&final_txt_path, &combined_transcript).await {
+        Ok(()) => true,
+        Err(e) => {
+            println!("Warning: Failed to write combined transcript: {}", e);
+            false
+        }
+    };
 
+    let combined_json = match &merged {
+        Some(merged) => serde_json::to_string(merged).ok(),
+        None => all_json_parts.first().map(|part| part.json.clone()),
+    };
+    if let Some(combined_json) = combined_json {
         let final_json_path = base_dir.join(format!("{}.json", meeting_id));
         if let Err(e) = fs::write(&final_json_path, combined_json).await {
             println!("Warning: Failed to write combined JSON transcript: {}", e);
         }
     }
 
-    // Clear transcription state
-    let mut state_lock = state.lock().await;
-    state_lock.currently_transcribing = None;
-    drop(state_lock);
+    job_state.finish().await;
+    slot.release().await;
 
-    app.emit(meeting_id, "transcription-finished").unwrap();
+    // Complete, so the next transcription starts over
+    let complete =
+        chunk_transcripts.len() == chunks.len() && chunk_transcripts.iter().all(Option::is_some);
+    if transcript_written && complete {
+        let _ = fs::remove_file(chunks_dir.join(CHUNK_SPLIT_FILE_NAME)).await;
+    }
+
+    if transcript_written {
+        let duration_seconds = audio_info.duration_seconds;
+        if let Err(e) = update_meeting_metadata(&base_dir, meeting_id, |metadata| {
+            metadata.audio_duration_seconds = Some(duration_seconds);
+        })
+        .await
+        {
+            println!("Warning: Failed to record audio duration: {}", e);
+        }
+
+        let audio_chunks = audio_chunk_entries(&base_dir, &chunks, &chunk_transcripts);
+        if let Err(e) = save_audio_chunks(&base_dir, audio_chunks).await {
+            println!("Warning: Failed to write chunk manifest: {}", e);
+        }
+
+        // Skipped chunks and unreadable chunk transcripts leave parts of the audio out
+        after_transcription(
+            &app,
+            &base_dir,
+            meeting_id,
+            &combined_transcript,
+            audio_info.duration_seconds,
+            complete,
+        )
+        .await;
+    }
+
+    events
+        .emit_terminal(meeting_id, "transcription-finished")
+        .await;
 
     println!("Transcription completed for meeting {}", meeting_id);
     Ok(())
 }
 
-/// Helper function to transcribe a single audio chunk
+/// Helper function to transcribe a single audio chunk. mlx_whisper has no
+/// diarization, so chunk transcripts never carry speaker prefixes.
 async fn transcribe_single_chunk(
     app: &AppHandle,
     audio_path: &std::path::Path,
     output_dir: &std::path::Path,
-) -> Result<(), String> {
+    cancellation: &CancellationToken,
+    progress: ProgressReporter,
+) -> Result<(), TranscriptionError> {
     println!("Transcribing: {}", audio_path.display());
 
-    // Get python resource path
-    let app_dir = app
-        .path()
-        .app_local_data_dir()
-        .expect("Failed to get app local data directory");
-    let resource_path = app_dir.join("python");
-    let _lib_path = resource_path
-        .join("lib")
-        .join("python3.12")
-        .join("site-packages");
-    let _python_exe = get_python_executable_path(&resource_path);
-    // let output = Command::new(&python_exe)
-    //     .env("PYTHONPATH", &lib_path)
-    //     .arg("-m")
-    //     .arg("whisperx")
-    //     .arg(audio_path)
-    //     // .arg("--device")
-    //     // .arg("cpu")
-    //     .arg("--compute_type")
-    //     .arg("int8")
-    //     .arg("--diarize")
-    //     .arg("--output_dir")
-    //     .arg(output_dir)
-    //     .arg("--hf_token")
-    //     .arg("HFTOKEN")
-    //     .output()
-    //     .await
-    //     .map_err(|e| format!("Failed to execute whisperx: {}", e))?;
-
     // uv run --with mlx_whisper mlx_whisper --model mlx-community/whisper-turbo --output-dir mlx --output-format all recording-1749583019.ogg
-    let output = Command::new("uv")
+    let model = transcription_config(app).await.mlx_model();
+    let mut command = mlx_whisper_command(model, output_dir, audio_path);
+    command.envs(cache_env(app).await);
+    let (status, stderr_log) = run_watched(app, &mut command, cancellation, progress).await?;
+
+    for warning in stderr_log.warnings() {
+        println!("whisperx warning: {}", warning);
+    }
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(stderr_log.into_error(status))
+    }
+}
+
+/// `uv run --with mlx_whisper mlx_whisper` transcribing `audio_path` into `output_dir`
+pub fn mlx_whisper_command(
+    model: &str,
+    output_dir: &std::path::Path,
+    audio_path: &std::path::Path,
+) -> Command {
+    let mut command = Command::new("uv");
+    command
         .arg("run")
         .arg("--with")
         .arg("mlx_whisper")
         .arg("mlx_whisper")
         .arg("--model")
-        .arg("mlx-community/whisper-turbo")
+        .arg(model)
         .arg("--output-dir")
         .arg(output_dir)
         .arg("--condition-on-previous-text")
         .arg("False")
-        .arg(audio_path)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute whisperx: {}", e))?;
+        .arg(audio_path);
+    command
+}
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("whisperx failed: {}", stderr.trim()))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diarization_needs_a_token() {
+        let config = TranscriptionConfig::default();
+        assert!(matches!(
+            whisperx_args(&config, None),
+            Err(TranscriptionError::MissingHuggingFaceToken)
+        ));
+        assert!(matches!(
+            whisperx_args(&config, Some("  ".to_string())),
+            Err(TranscriptionError::MissingHuggingFaceToken)
+        ));
+        assert_eq!(
+            whisperx_args(&config, Some("hf_token".to_string())).unwrap(),
+            vec!["--diarize", "--hf_token", "hf_token"]
+        );
+
+        let hinted = TranscriptionConfig {
+            min_speakers: Some(2),
+            max_speakers: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            whisperx_args(&hinted, Some("hf_token".to_string())).unwrap(),
+            vec![
+                "--diarize",
+                "--hf_token",
+                "hf_token",
+                "--min_speakers",
+                "2",
+                "--max_speakers",
+                "4"
+            ]
+        );
+
+        // Speaker hints mean nothing without diarization
+        let config = TranscriptionConfig {
+            model: Some(WhisperModelSize::Small),
+            diarize: false,
+            ..hinted
+        };
+        assert_eq!(
+            whisperx_args(&config, None).unwrap(),
+            vec!["--model", "small"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunk_output_needs_text_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let chunk_path = dir.path().join("recording-1_chunk_03.ogg");
+        assert_eq!(read_chunk_output(&chunk_path).await, None);
+
+        std::fs::write(dir.path().join("recording-1_chunk_03.txt"), "Hallo").unwrap();
+        // whisper was killed before it wrote the JSON
+        assert_eq!(read_chunk_output(&chunk_path).await, None);
+
+        std::fs::write(dir.path().join("recording-1_chunk_03.json"), "{}").unwrap();
+        assert_eq!(
+            read_chunk_output(&chunk_path).await,
+            Some(("Hallo".to_string(), "{}".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunk_split_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_chunk_split(dir.path()).await, None);
+
+        let split = ChunkSplit {
+            audio_bytes: 52_000_000,
+            duration_seconds: 5412.3,
+            chunk_count: 10,
+            chunk_duration_seconds: 600.0,
+        };
+        write_chunk_split(dir.path(), &split).await.unwrap();
+        assert_eq!(read_chunk_split(dir.path()).await, Some(split));
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_frees_the_transcription_slot() {
+        let app = crate::llm::test_harness::TestApp::new(Default::default());
+        let handle = app.handle();
+        let state = handle.state::<Mutex<AppState>>();
+        let meeting_id = "recording-1";
+
+        let job_id = {
+            let mut state = state.lock().await;
+            state.claim_transcription(meeting_id).unwrap();
+            state.jobs.start_job(JobKind::Transcription, meeting_id)
+        };
+        let run = async {
+            let _slot = TranscriptionSlot::new(&handle, meeting_id, job_id);
+            let missing = app.meeting_dir(meeting_id).join("recording-1.ogg");
+            analyze_audio(&missing, 0.0, 600.0).await?;
+            Ok::<_, String>(())
+        };
+        assert!(run.await.is_err());
+
+        let state = state.lock().await;
+        assert!(state.currently_transcribing.is_none());
+        assert!(!state.jobs.has_running_jobs());
     }
 }
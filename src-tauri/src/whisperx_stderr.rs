@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+use std::process::ExitStatus;
+
+use crate::error::TranscriptionError;
+
+/// Lines of unrecognized stderr kept for the error message
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Known fatal errors, checked in order. Matching is case sensitive because
+/// the patterns are exact fragments of Python exception messages. pyannote's
+/// "Model was trained with ..." isn't one, successful runs print it as well.
+const FATAL_PATTERNS: &[(&str, TranscriptionError)] = &[
+    ("CUDA out of memory", TranscriptionError::OutOfMemory),
+    ("OutOfMemoryError", TranscriptionError::OutOfMemory),
+    ("MPS backend out of memory", TranscriptionError::OutOfMemory),
+    ("Insufficient Memory", TranscriptionError::OutOfMemory),
+    ("std::bad_alloc", TranscriptionError::OutOfMemory),
+    (
+        "401 Client Error",
+        TranscriptionError::HuggingFaceUnauthorized,
+    ),
+    (
+        "GatedRepoError",
+        TranscriptionError::HuggingFaceUnauthorized,
+    ),
+    (
+        "Cannot access gated repo",
+        TranscriptionError::HuggingFaceUnauthorized,
+    ),
+    (
+        "Invalid user token",
+        TranscriptionError::HuggingFaceUnauthorized,
+    ),
+    ("Failed to load audio", TranscriptionError::UnreadableAudio),
+    (
+        "Invalid data found when processing input",
+        TranscriptionError::UnreadableAudio,
+    ),
+    (
+        "operator torchvision::nms does not exist",
+        TranscriptionError::TorchVersionMismatch,
+    ),
+];
+
+const WARNING_PATTERNS: &[&str] = &["Warning:", "UserWarning", "warnings.warn"];
+
+/// Collects stderr of a transcription run line by line
+#[derive(Debug, Default)]
+pub struct StderrLog {
+    fatal: Option<TranscriptionError>,
+    warnings: Vec<String>,
+    tail: VecDeque<String>,
+}
+
+impl StderrLog {
+    pub fn from_output(stderr: &[u8]) -> Self {
+        let mut log = Self::default();
+        for line in String::from_utf8_lossy(stderr).lines() {
+            log.push(line);
+        }
+        log
+    }
+
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            return;
+        }
+
+        if self.fatal.is_none() {
+            self.fatal = classify_line(line);
+        }
+        if WARNING_PATTERNS
+            .iter()
+            .any(|pattern| line.contains(pattern))
+        {
+            self.warnings.push(line.to_string());
+        }
+
+        self.tail.push_back(line.to_string());
+        if self.tail.len() > STDERR_TAIL_LINES {
+            self.tail.pop_front();
+        }
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// The error for a failed run. A known cause wins over the exit status.
+    pub fn into_error(self, status: ExitStatus) -> TranscriptionError {
+        self.fatal.unwrap_or_else(|| TranscriptionError::Failed {
+            status: status.to_string(),
+            stderr_tail: self.tail.into(),
+        })
+    }
+}
+
+/// The fatal error a single stderr line points to, if any
+pub fn classify_line(line: &str) -> Option<TranscriptionError> {
+    FATAL_PATTERNS
+        .iter()
+        .find(|(pattern, _)| line.contains(pattern))
+        .map(|(_, error)| error.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> ExitStatus {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatus::from_raw(code << 8)
+    }
+
+    #[cfg(windows)]
+    fn exit_status(code: i32) -> ExitStatus {
+        use std::os::windows::process::ExitStatusExt;
+        ExitStatus::from_raw(code as u32)
+    }
+
+    #[test]
+    fn test_classify_known_stderr() {
+        let cases: &[(&str, Option<TranscriptionError>)] = &[
+            (
+                "torch.OutOfMemoryError: CUDA out of memory. Tried to allocate 20.00 MiB (GPU 0; 3.81 GiB total capacity; 2.91 GiB already allocated)",
+                Some(TranscriptionError::OutOfMemory),
+            ),
+            (
+                "RuntimeError: MPS backend out of memory (MPS allocated: 17.61 GB, other allocations: 528.00 KB, max allowed: 18.13 GB).",
+                Some(TranscriptionError::OutOfMemory),
+            ),
+            (
+                "libc++abi: terminating due to uncaught exception of type std::runtime_error: [METAL] Command buffer execution failed: Insufficient Memory (00000008:kIOGPUCommandBufferCallbackErrorOutOfMemory)",
+                Some(TranscriptionError::OutOfMemory),
+            ),
+            (
+                "requests.exceptions.HTTPError: 401 Client Error: Unauthorized for url: https://huggingface.co/pyannote/speaker-diarization-3.1/resolve/main/config.yaml",
+                Some(TranscriptionError::HuggingFaceUnauthorized),
+            ),
+            (
+                "huggingface_hub.errors.GatedRepoError: 401 Client Error. (Request ID: Root=1-6650a1b2)",
+                Some(TranscriptionError::HuggingFaceUnauthorized),
+            ),
+            (
+                "Cannot access gated repo for url https://huggingface.co/pyannote/segmentation-3.0/resolve/main/pytorch_model.bin.",
+                Some(TranscriptionError::HuggingFaceUnauthorized),
+            ),
+            (
+                "RuntimeError: Failed to load audio: ffmpeg version 6.1.1 Copyright (c) 2000-2023 the FFmpeg developers",
+                Some(TranscriptionError::UnreadableAudio),
+            ),
+            (
+                "/tmp/recording.ogg: Invalid data found when processing input",
+                Some(TranscriptionError::UnreadableAudio),
+            ),
+            (
+                "Model was trained with pyannote.audio 0.0.1, yours is 3.3.2. Bad things might happen unless you revert pyannote.audio to 0.x.",
+                None,
+            ),
+            (
+                "Model was trained with torch 1.10.0+cu102, yours is 2.5.1. Bad things might happen unless you revert torch to 1.x.",
+                None,
+            ),
+            (
+                "RuntimeError: operator torchvision::nms does not exist",
+                Some(TranscriptionError::TorchVersionMismatch),
+            ),
+            (
+                "/python/lib/python3.12/site-packages/pyannote/audio/core/io.py:43: UserWarning: torchaudio._backend.set_audio_backend has been deprecated.",
+                None,
+            ),
+            ("Lightning automatically upgraded your loaded checkpoint from v1.5.4 to v2.5.0.", None),
+            ("100%|██████████| 1.51G/1.51G [00:42<00:00, 38.2MiB/s]", None),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(&classify_line(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_known_error_wins_over_status() {
+        let mut log = StderrLog::default();
+        log.push("Traceback (most recent call last):");
+        log.push("  File \"whisperx/audio.py\", line 58, in load_audio");
+        log.push("RuntimeError: Failed to load audio: broken.ogg: Invalid data found when processing input");
+
+        assert_eq!(
+            log.into_error(exit_status(1)),
+            TranscriptionError::UnreadableAudio
+        );
+    }
+
+    #[test]
+    fn test_successful_run_has_no_fatal_error() {
+        let output = crate::llm::test_harness::read_fixture("whisper/whisperx.txt");
+        for line in output.lines() {
+            assert_eq!(classify_line(line), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_unknown_stderr_keeps_tail() {
+        let stderr: String = (0..30).map(|i| format!("line {}\n", i)).collect();
+        let log = StderrLog::from_output(stderr.as_bytes());
+
+        match log.into_error(exit_status(2)) {
+            TranscriptionError::Failed { stderr_tail, .. } => {
+                assert_eq!(stderr_tail.len(), STDERR_TAIL_LINES);
+                assert_eq!(stderr_tail[0], "line 10");
+                assert_eq!(stderr_tail.last().unwrap(), "line 29");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_warnings_are_collected() {
+        let log = StderrLog::from_output(
+            b"UserWarning: TensorFloat-32 (TF32) has been disabled\n\nDetected language: de (0.99)\nWarning: audio is shorter than 30s, language detection may be inaccurate.\n",
+        );
+
+        assert_eq!(log.warnings().len(), 2);
+    }
+
+    #[test]
+    fn test_error_message_has_hint() {
+        let message = TranscriptionError::HuggingFaceUnauthorized.to_string();
+        assert!(message.contains("401"));
+        assert!(message.contains("Hugging Face token"));
+    }
+}
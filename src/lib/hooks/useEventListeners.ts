@@ -1,3 +1,4 @@
+import { invoke } from "@tauri-apps/api/core";
 import { listen, once } from "@tauri-apps/api/event";
 import { toast } from "svelte-sonner";
 import type { useProgressTracking } from "./useProgressTracking.svelte";
@@ -9,6 +10,7 @@ export function useEventListeners(
 		onTranscriptionStarted?: () => void;
 		onTranscriptionFinished?: () => void;
 		onSummarizationStarted?: (meetingId: string) => void;
+		onAudioDeleted?: () => void;
 	}
 ) {
 	let listeners: (() => void)[] = [];
@@ -47,6 +49,17 @@ export function useEventListeners(
 			progressTracking.updateSummarizationProgress(event.payload);
 		});
 
+		const summarizationChunkEstimateListener = await listen<{ step: number; percent: number }>(
+			"summarization-chunk-estimate",
+			(event) => {
+				progressTracking.updateSummarizationEstimate(event.payload.step, event.payload.percent);
+			}
+		);
+
+		const llmTokenListener = await listen<{ chunk_index: number; text: string }>("llm-token", (event) => {
+			progressTracking.updateSummarizationText(event.payload.chunk_index, event.payload.text);
+		});
+
 		// Meeting-specific events
 		const summarizationListener = await once<string>("summarization-started", (event) => {
 			console.log(event);
@@ -65,6 +78,48 @@ export function useEventListeners(
 			}
 		});
 
+		const transcriptionWarningListener = await listen<{ meeting_id: string; warnings: string[] }>(
+			"transcription-warning",
+			(event) => {
+				if (event.payload.meeting_id !== meetingId) return;
+				for (const warning of event.payload.warnings) {
+					toast.warning(warning);
+				}
+			}
+		);
+
+		const transcriptionStalledListener = await listen<{
+			meeting_id: string;
+			chunk_index: number | null;
+			idle_seconds: number;
+		}>("transcription-stalled", (event) => {
+			if (event.payload.meeting_id !== meetingId) return;
+			const part =
+				event.payload.chunk_index === null ? "Transcription" : `Chunk ${event.payload.chunk_index + 1}`;
+			toast.warning(
+				`${part} printed nothing for ${Math.round(event.payload.idle_seconds / 60)} minutes and was stopped`
+			);
+		});
+
+		const audioDeletionListener = await listen<string>("audio-deletion-requested", (event) => {
+			if (event.payload !== meetingId) return;
+			toast("Transcript saved. Delete the original audio to free up space?", {
+				duration: Infinity,
+				action: {
+					label: "Delete audio",
+					onClick: async () => {
+						try {
+							await invoke("confirm_audio_deletion", { meetingId });
+							toast.success("Audio deleted");
+							callbacks.onAudioDeleted?.();
+						} catch (error) {
+							toast.error("Failed to delete audio: " + error);
+						}
+					},
+				},
+			});
+		});
+
 		// Store listeners for cleanup
 		listeners = [
 			whisperxStartListener,
@@ -73,8 +128,13 @@ export function useEventListeners(
 			ffmpegProgressListener,
 			summarizationChunkStartListener,
 			summarizationChunkProgressListener,
+			summarizationChunkEstimateListener,
+			llmTokenListener,
 			summarizationListener,
 			transcriptionListener,
+			transcriptionWarningListener,
+			transcriptionStalledListener,
+			audioDeletionListener,
 		];
 	}
 
@@ -2,10 +2,105 @@ import { invoke } from "@tauri-apps/api/core";
 import { readFile, BaseDirectory } from "@tauri-apps/plugin-fs";
 import { toast } from "svelte-sonner";
 
+interface SummaryResult {
+  meeting_id: string;
+  markdown: string;
+  title: string;
+  duration_seconds: number;
+  chunk_count: number;
+  reused_chunks: number;
+  warnings: string[];
+  failed_chunks: FailedChunk[];
+}
+
+export interface OutlineEntry {
+  title: string;
+  anchor: string;
+  level: number;
+  bullet_count: number;
+  children: OutlineEntry[];
+}
+
+export interface Approval {
+  at: string;
+  by: string;
+  checksum: string;
+  version: string;
+  superseded_at?: string;
+}
+
+/** Token usage of the last summarization run */
+export interface SummaryMetrics {
+  total_duration: number;
+  api_calls: number;
+  prompt_tokens: number;
+  completion_tokens: number;
+  tokens_per_second: number;
+}
+
+export interface FailedChunk {
+  chunk_index: number;
+  error: string;
+}
+
+export interface PartialSummaryInfo {
+  key: string;
+  description: string;
+  title: string;
+  created_at: string;
+}
+
 interface ChunkSummary {
   chunk_number: number;
   content: string;
   markdown_content: string;
+  stale: boolean;
+}
+
+const REMOTE_ENDPOINT_NOT_CONFIRMED = "Remote endpoint not confirmed";
+const STALE_CHUNKS = "Stale chunk summaries";
+const SUMMARY_APPROVED = "Summary approved";
+
+/** Invoke a command that sends the meeting to the LLM, asking first if the
+ * endpoint is not on this machine */
+async function invokeWithRemoteConsent<T>(
+  command: string,
+  args: Record<string, unknown>,
+): Promise<T> {
+  try {
+    return await invoke<T>(command, args);
+  } catch (error) {
+    if (!String(error).startsWith(REMOTE_ENDPOINT_NOT_CONFIRMED)) {
+      throw error;
+    }
+    const message = String(error).split(": ").slice(1).join(": ");
+    if (!confirm(`${message}\n\nSend the meeting anyway?`)) {
+      throw error;
+    }
+    return await invoke<T>(command, { ...args, allowRemote: true });
+  }
+}
+
+/** Invoke a command that replaces the summary, asking first if it replaces
+ * an approved one */
+async function invokeReplacingSummary<T>(
+  command: string,
+  args: Record<string, unknown>,
+): Promise<T> {
+  try {
+    return await invokeWithRemoteConsent<T>(command, args);
+  } catch (error) {
+    if (
+      !String(error).startsWith(SUMMARY_APPROVED) ||
+      !confirm(`${error}\n\nReplace the approved summary?`)
+    ) {
+      throw error;
+    }
+    return await invokeWithRemoteConsent<T>(command, {
+      ...args,
+      supersedeApproved: true,
+    });
+  }
 }
 
 export function useMeetingData(meetingId: string) {
@@ -13,8 +108,23 @@ export function useMeetingData(meetingId: string) {
   let transcriptJsonContent: string | null = $state(null);
   let summaryContent: string | null = $state("");
   let chunkSummaries: ChunkSummary[] = $state([]);
+  // Chunks skipped by the last summary run, until they are regenerated
+  let failedChunks: FailedChunk[] = $state([]);
+  let summaryOutline: OutlineEntry[] = $state([]);
+  let summaryMetrics: SummaryMetrics | null = $state(null);
   let audio: Uint8Array | null = $state(null);
-  let meetingMetadata: { name?: string } = $state({});
+  let notesContent = $state("");
+  let meetingMetadata: {
+    name?: string;
+    emoji?: string;
+    has_audio?: boolean;
+    has_notes?: boolean;
+    pinned?: boolean;
+    /** Summary language, the LLM config's when unset */
+    language?: string;
+    partial_summaries?: PartialSummaryInfo[];
+    approved?: Approval;
+  } = $state({});
 
   const audioURL = $derived.by(() => {
     if (!audio) return "";
@@ -46,14 +156,27 @@ export function useMeetingData(meetingId: string) {
   async function getSummary() {
     try {
       summaryContent = await invoke("get_meeting_summary", { meetingId });
+      summaryOutline = await invoke("get_summary_outline", { meetingId });
+      await getSummaryMetrics();
       return summaryContent;
     } catch (error) {
       console.error("Error fetching summary:", error);
       summaryContent = null;
+      summaryOutline = [];
       throw error;
     }
   }
 
+  async function getSummaryMetrics() {
+    try {
+      summaryMetrics = await invoke("get_summary_metrics", { meetingId });
+    } catch (error) {
+      console.error("Error fetching summary metrics:", error);
+      summaryMetrics = null;
+    }
+    return summaryMetrics;
+  }
+
   async function getChunkSummaries() {
     try {
       chunkSummaries = await invoke("get_chunk_summaries", { meetingId });
@@ -65,6 +188,25 @@ export function useMeetingData(meetingId: string) {
     }
   }
 
+  async function getNotes() {
+    try {
+      notesContent = await invoke("get_meeting_notes", { meetingId });
+      return notesContent;
+    } catch (error) {
+      console.error("Error fetching notes:", error);
+      throw error;
+    }
+  }
+
+  async function saveNotes() {
+    try {
+      await invoke("set_meeting_notes", { meetingId, markdown: notesContent });
+    } catch (error) {
+      console.error("Error saving notes:", error);
+      throw error;
+    }
+  }
+
   async function getAudio() {
     try {
       const audioData = await readFile(`uploads/${meetingId}/${meetingId}.ogg`, {
@@ -90,10 +232,31 @@ export function useMeetingData(meetingId: string) {
     }
   }
 
-  async function regenerateSummary() {
+  async function getPartialSummary(key: string) {
     try {
-      summaryContent = await invoke("generate_summary", { meetingId });
+      return await invoke<string>("get_partial_summary", { meetingId, key });
+    } catch (error) {
+      console.error("Error fetching partial summary:", error);
+      throw error;
+    }
+  }
+
+  /** `force` summarizes the chunks unchanged since the last run again */
+  async function regenerateSummary(force = false) {
+    try {
+      const result = await invokeReplacingSummary<SummaryResult>(
+        "generate_summary",
+        { meetingId, force },
+      );
+      summaryContent = result.markdown;
+      failedChunks = result.failed_chunks ?? [];
+      if (failedChunks.length > 0) {
+        toast.warning(
+          `${failedChunks.length} of ${result.chunk_count} chunks failed and are missing from the summary`,
+        );
+      }
       await getMeetingMetadata();
+      await getSummaryMetrics();
       return summaryContent;
     } catch (error) {
       console.error("Error regenerating summary:", error);
@@ -104,7 +267,23 @@ export function useMeetingData(meetingId: string) {
 
   async function regenerateFinalSummary() {
     try {
-      summaryContent = await invoke("regenerate_final_summary", { meetingId });
+      try {
+        summaryContent = await invokeReplacingSummary<string>(
+          "regenerate_final_summary",
+          { meetingId },
+        );
+      } catch (error) {
+        if (
+          !String(error).startsWith(STALE_CHUNKS) ||
+          !confirm(`${error}\n\nRegenerate the final summary from them anyway?`)
+        ) {
+          throw error;
+        }
+        summaryContent = await invokeReplacingSummary<string>(
+          "regenerate_final_summary",
+          { meetingId, allowStale: true },
+        );
+      }
       await getMeetingMetadata();
       return summaryContent;
     } catch (error) {
@@ -114,6 +293,37 @@ export function useMeetingData(meetingId: string) {
     }
   }
 
+  /** Freeze the current summary as the approved minutes */
+  async function approveSummary() {
+    const approverName = prompt("Approved by:");
+    if (!approverName?.trim()) return;
+    try {
+      await invoke<Approval>("approve_summary", { meetingId, approverName });
+      await getMeetingMetadata();
+      toast.success("Summary approved");
+    } catch (error) {
+      console.error("Error approving summary:", error);
+      toast.error("Error approving summary: " + error);
+    }
+  }
+
+  async function regenerateChunkSummary(chunkIndex: number) {
+    try {
+      await invokeWithRemoteConsent<string>("regenerate_chunk_summary", {
+        meetingId,
+        chunkIndex,
+      });
+      failedChunks = failedChunks.filter(
+        (chunk) => chunk.chunk_index !== chunkIndex,
+      );
+      await getChunkSummaries();
+    } catch (error) {
+      console.error("Error regenerating chunk summary:", error);
+      toast.error(`Error regenerating chunk ${chunkIndex + 1}: ` + error);
+      throw error;
+    }
+  }
+
   async function transcribe() {
     try {
       await invoke("transcribe_with_chunking", { meetingId });
@@ -135,8 +345,13 @@ export function useMeetingData(meetingId: string) {
     get transcriptJsonContent() { return transcriptJsonContent; },
     get summaryContent() { return summaryContent; },
     get chunkSummaries() { return chunkSummaries; },
+    get failedChunks() { return failedChunks; },
+    get summaryOutline() { return summaryOutline; },
+    get summaryMetrics() { return summaryMetrics; },
     get audio() { return audio; },
     get audioURL() { return audioURL; },
+    get notesContent() { return notesContent; },
+    set notesContent(value: string) { notesContent = value; },
     get meetingMetadata() { return meetingMetadata; },
 
     // Actions
@@ -144,10 +359,15 @@ export function useMeetingData(meetingId: string) {
     getTranscriptJson,
     getSummary,
     getChunkSummaries,
+    getNotes,
+    saveNotes,
     getAudio,
     getMeetingMetadata,
+    getPartialSummary,
     regenerateSummary,
     regenerateFinalSummary,
+    regenerateChunkSummary,
+    approveSummary,
     transcribe,
   };
 }
@@ -77,7 +77,10 @@ export function useMeetingData(meetingId: string) {
 
 	async function regenerateSummary() {
 		try {
-			state.summaryContent = await invoke("generate_summary", { meetingId });
+			const result = await invoke<{ markdown: string }>("generate_summary", {
+				meetingId,
+			});
+			state.summaryContent = result.markdown;
 			await getMeetingMetadata();
 			return state.summaryContent;
 		} catch (error) {
@@ -2,6 +2,10 @@ export interface ProgressState {
   currentChunk: number;
   totalChunks: number;
   isActive: boolean;
+  /** Estimated progress within the current chunk, 0-95 */
+  stepPercent?: number;
+  /** What the model generated so far for the current chunk, when streaming */
+  streamedText?: string;
 }
 
 export function useProgressTracking() {
@@ -73,6 +77,19 @@ export function useProgressTracking() {
 
   function updateSummarizationProgress(chunkIndex: number) {
     summarizationProgress.currentChunk = chunkIndex + 1; // +1 because backend sends 0-based index
+    summarizationProgress.stepPercent = 0;
+    summarizationProgress.streamedText = "";
+  }
+
+  function updateSummarizationEstimate(chunkIndex: number, percent: number) {
+    // Ignore late estimates for a chunk that already finished
+    if (chunkIndex + 1 !== summarizationProgress.currentChunk) return;
+    summarizationProgress.stepPercent = percent;
+  }
+
+  function updateSummarizationText(chunkIndex: number, text: string) {
+    if (chunkIndex + 1 !== summarizationProgress.currentChunk) return;
+    summarizationProgress.streamedText = text;
   }
 
   return {
@@ -91,5 +108,7 @@ export function useProgressTracking() {
     updateAudioSplittingProgress,
     startSummarization,
     updateSummarizationProgress,
+    updateSummarizationEstimate,
+    updateSummarizationText,
   };
 }